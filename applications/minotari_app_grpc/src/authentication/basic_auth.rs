@@ -170,6 +170,13 @@ impl BasicAuthCredentials {
         let header = header.parse()?;
         Ok(header)
     }
+
+    /// Generates a `Bearer` HTTP Authorization header value from the given token.
+    pub fn generate_bearer_header(token: &[u8]) -> Result<MetadataValue<Ascii>, BasicAuthError> {
+        let header = Zeroizing::new(format!("Bearer {}", String::from_utf8(token.to_vec())?));
+        let header = header.parse()?;
+        Ok(header)
+    }
 }
 
 /// Authorization Header Error
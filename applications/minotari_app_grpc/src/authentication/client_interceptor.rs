@@ -39,10 +39,15 @@ pub struct ClientAuthenticationInterceptor {
 impl ClientAuthenticationInterceptor {
     pub fn create(auth: &GrpcAuthentication) -> Result<Self, BasicAuthError> {
         let authorization_header = match auth {
-            GrpcAuthentication::None => None,
+            GrpcAuthentication::None | GrpcAuthentication::ClientCertificate { .. } => None,
             GrpcAuthentication::Basic { username, password } => {
                 Some(BasicAuthCredentials::generate_header(username, password.reveal())?)
             },
+            // Clients only ever send the first configured token
+            GrpcAuthentication::Token { tokens } => tokens
+                .first()
+                .map(|t| BasicAuthCredentials::generate_bearer_header(t.token.reveal()))
+                .transpose()?,
         };
         Ok(Self { authorization_header })
     }
@@ -64,12 +69,42 @@ impl Interceptor for ClientAuthenticationInterceptor {
 
 #[cfg(test)]
 mod test {
-    use tari_common_types::grpc_authentication::GrpcAuthentication;
+    use tari_common_types::grpc_authentication::{
+        GrpcAccessToken,
+        GrpcAuthentication,
+        GrpcClientCertificate,
+        GrpcScope,
+    };
     use tari_utilities::SafePassword;
-    use tonic::{codegen::http::header::AUTHORIZATION, service::Interceptor, Request};
+    use tonic::{codegen::http::header::AUTHORIZATION, service::Interceptor, Code, Request};
 
     use super::ClientAuthenticationInterceptor;
-    use crate::authentication::ServerAuthenticationInterceptor;
+    use crate::authentication::{GrpcClientIdentity, GrpcMethod, ServerAuthenticationInterceptor};
+
+    fn token_auth(token: &str, scope: GrpcScope) -> GrpcAuthentication {
+        GrpcAuthentication::Token {
+            tokens: vec![GrpcAccessToken {
+                token: SafePassword::from(token),
+                scope,
+            }],
+        }
+    }
+
+    fn test_method_scopes(method: &str) -> GrpcScope {
+        match method {
+            "GetBalance" => GrpcScope::Read,
+            "Transfer" => GrpcScope::Spend,
+            _ => GrpcScope::Admin,
+        }
+    }
+
+    fn method_request(method: &str) -> Request<()> {
+        let mut request = Request::new(());
+        request
+            .extensions_mut()
+            .insert(GrpcMethod(format!("/tari.rpc.Wallet/{}", method)));
+        request
+    }
 
     #[test]
     fn test_no_authentication_success() {
@@ -163,4 +198,102 @@ mod test {
         // Process the request through the server interceptor; this mismatch is not allowed
         assert!(server_interceptor.call(client_request).is_err());
     }
+
+    #[test]
+    fn test_token_authentication_scopes() {
+        // Set up the server with a read token and a spend token
+        let server_auth = GrpcAuthentication::Token {
+            tokens: vec![
+                GrpcAccessToken {
+                    token: SafePassword::from("reader"),
+                    scope: GrpcScope::Read,
+                },
+                GrpcAccessToken {
+                    token: SafePassword::from("spender"),
+                    scope: GrpcScope::Spend,
+                },
+            ],
+        };
+        let mut server_interceptor = ServerAuthenticationInterceptor::new(server_auth)
+            .unwrap()
+            .with_method_scopes(test_method_scopes);
+        let mut read_client = ClientAuthenticationInterceptor::create(&token_auth("reader", GrpcScope::Read)).unwrap();
+        let mut spend_client =
+            ClientAuthenticationInterceptor::create(&token_auth("spender", GrpcScope::Spend)).unwrap();
+
+        // The read token may only call read methods
+        let client_request = read_client.call(method_request("GetBalance")).unwrap();
+        assert_eq!(
+            client_request.metadata().get(AUTHORIZATION.as_str()).unwrap(),
+            "Bearer reader"
+        );
+        assert!(server_interceptor.call(client_request).is_ok());
+        let client_request = read_client.call(method_request("Transfer")).unwrap();
+        let status = server_interceptor.call(client_request).unwrap_err();
+        assert_eq!(status.code(), Code::PermissionDenied);
+
+        // The spend token may call read and spend methods, but not admin methods
+        let client_request = spend_client.call(method_request("GetBalance")).unwrap();
+        let server_request = server_interceptor.call(client_request).unwrap();
+        let identity = server_request.extensions().get::<GrpcClientIdentity>().unwrap();
+        assert!(identity.0.starts_with("token:"));
+        let client_request = spend_client.call(method_request("Transfer")).unwrap();
+        assert!(server_interceptor.call(client_request).is_ok());
+        let client_request = spend_client.call(method_request("SetBaseNode")).unwrap();
+        let status = server_interceptor.call(client_request).unwrap_err();
+        assert_eq!(status.code(), Code::PermissionDenied);
+
+        // Requests that cannot be matched to a method require the admin scope
+        let client_request = spend_client.call(Request::new(())).unwrap();
+        let status = server_interceptor.call(client_request).unwrap_err();
+        assert_eq!(status.code(), Code::PermissionDenied);
+    }
+
+    #[test]
+    fn test_token_authentication_failure() {
+        let mut server_interceptor = ServerAuthenticationInterceptor::new(token_auth("secret", GrpcScope::Admin))
+            .unwrap()
+            .with_method_scopes(test_method_scopes);
+
+        // An unknown token is rejected
+        let mut client_interceptor =
+            ClientAuthenticationInterceptor::create(&token_auth("evil", GrpcScope::Admin)).unwrap();
+        let client_request = client_interceptor.call(method_request("GetBalance")).unwrap();
+        let status = server_interceptor.call(client_request).unwrap_err();
+        assert_eq!(status.code(), Code::Unauthenticated);
+
+        // Basic credentials are not accepted in place of a token
+        let client_auth = GrpcAuthentication::Basic {
+            username: "foo".to_string(),
+            password: SafePassword::from("secret"),
+        };
+        let mut client_interceptor = ClientAuthenticationInterceptor::create(&client_auth).unwrap();
+        let client_request = client_interceptor.call(method_request("GetBalance")).unwrap();
+        let status = server_interceptor.call(client_request).unwrap_err();
+        assert_eq!(status.code(), Code::Unauthenticated);
+
+        // A missing token is rejected
+        let client_request = method_request("GetBalance");
+        let status = server_interceptor.call(client_request).unwrap_err();
+        assert_eq!(status.code(), Code::Unauthenticated);
+    }
+
+    #[test]
+    fn test_client_certificate_authentication_without_certificate() {
+        // Client certificate authentication does not send an authorization header
+        let auth = GrpcAuthentication::ClientCertificate {
+            client_certificates: vec![GrpcClientCertificate {
+                fingerprint: "AB:CD".to_string(),
+                scope: GrpcScope::Admin,
+            }],
+        };
+        let mut client_interceptor = ClientAuthenticationInterceptor::create(&auth).unwrap();
+        let mut server_interceptor = ServerAuthenticationInterceptor::new(auth).unwrap();
+        let client_request = client_interceptor.call(method_request("GetBalance")).unwrap();
+        assert!(client_request.metadata().is_empty());
+
+        // Without a TLS connection there is no peer certificate to authenticate
+        let status = server_interceptor.call(client_request).unwrap_err();
+        assert_eq!(status.code(), Code::Unauthenticated);
+    }
 }
@@ -26,7 +26,10 @@ pub use client_interceptor::ClientAuthenticationInterceptor;
 mod basic_auth;
 pub use basic_auth::{BasicAuthCredentials, BasicAuthError};
 
+mod grpc_method;
+pub use grpc_method::{GrpcMethod, GrpcMethodService};
+
 pub mod salted_password;
 
 mod server_interceptor;
-pub use server_interceptor::ServerAuthenticationInterceptor;
+pub use server_interceptor::{GrpcClientIdentity, ServerAuthenticationInterceptor};
@@ -20,18 +20,34 @@
 //  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use std::fmt;
+
 use log::*;
-use tari_common_types::grpc_authentication::GrpcAuthentication;
-use tari_utilities::SafePassword;
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+use tari_common_types::grpc_authentication::{GrpcAccessToken, GrpcAuthentication, GrpcClientCertificate, GrpcScope};
+use tari_utilities::{hex::to_hex, SafePassword};
 use tonic::{codegen::http::header::AUTHORIZATION, service::Interceptor, Request, Status};
 
-use crate::authentication::{salted_password::create_salted_hashed_password, BasicAuthCredentials};
+use crate::authentication::{
+    salted_password::create_salted_hashed_password,
+    BasicAuthCredentials,
+    BasicAuthError,
+    GrpcMethod,
+};
 
 const LOG_TARGET: &str = "applications::minotari_app_grpc::authentication";
 
-#[derive(Debug)]
+/// The authenticated client of a request, added to the request extensions by [ServerAuthenticationInterceptor] so
+/// that services can attribute requests to clients. It is absent when no authentication is configured.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GrpcClientIdentity(pub String);
+
 pub struct ServerAuthenticationInterceptor {
-    auth: GrpcAuthentication, // this contains a hashed PHC password in the case of basic authentication
+    // this contains a hashed PHC password in the case of basic authentication, hashed tokens in the case of token
+    // authentication and normalized fingerprints in the case of client certificate authentication
+    auth: GrpcAuthentication,
+    required_scope: fn(&str) -> GrpcScope,
 }
 
 impl ServerAuthenticationInterceptor {
@@ -43,9 +59,37 @@ impl ServerAuthenticationInterceptor {
                 username,
                 password: create_salted_hashed_password(password.reveal()).ok()?.as_str().into(),
             },
+            GrpcAuthentication::Token { tokens } => GrpcAuthentication::Token {
+                tokens: tokens
+                    .into_iter()
+                    .map(|t| GrpcAccessToken {
+                        token: SafePassword::from(to_hex(&Sha256::digest(t.token.reveal()))),
+                        scope: t.scope,
+                    })
+                    .collect(),
+            },
+            GrpcAuthentication::ClientCertificate { client_certificates } => GrpcAuthentication::ClientCertificate {
+                client_certificates: client_certificates
+                    .into_iter()
+                    .map(|c| GrpcClientCertificate {
+                        fingerprint: normalize_fingerprint(&c.fingerprint),
+                        scope: c.scope,
+                    })
+                    .collect(),
+            },
         };
 
-        Some(Self { auth: processed_auth })
+        Some(Self {
+            auth: processed_auth,
+            required_scope: |_| GrpcScope::Admin,
+        })
+    }
+
+    /// Sets the scope a client needs to call each gRPC method, keyed by the method name. Until this is set every
+    /// method requires the admin scope. Basic and no authentication always grant the admin scope.
+    pub fn with_method_scopes(mut self, required_scope: fn(&str) -> GrpcScope) -> Self {
+        self.required_scope = required_scope;
+        self
     }
 
     fn handle_basic_auth(
@@ -74,16 +118,98 @@ impl ServerAuthenticationInterceptor {
             _ => Err(unauthenticated("Missing authorization header")),
         }
     }
+
+    fn handle_token_auth(
+        &self,
+        req: &Request<()>,
+        valid_tokens: &[GrpcAccessToken],
+    ) -> Result<(GrpcScope, GrpcClientIdentity), Status> {
+        let header = req
+            .metadata()
+            .get(AUTHORIZATION.as_str())
+            .ok_or_else(|| unauthenticated("Missing authorization header"))?
+            .to_str()
+            .map_err(unauthenticated)?;
+        let (auth_type, token) = header
+            .split_once(' ')
+            .ok_or_else(|| unauthenticated(BasicAuthError::InvalidAuthorizationHeader))?;
+        if auth_type.to_lowercase() != "bearer" {
+            return Err(unauthenticated(BasicAuthError::InvalidScheme(auth_type.to_string())));
+        }
+
+        // Every configured token is compared so that the time taken does not depend on which token matched
+        let token_hash = to_hex(&Sha256::digest(token.as_bytes()));
+        let scope = valid_tokens
+            .iter()
+            .filter(|t| bool::from(t.token.reveal().ct_eq(token_hash.as_bytes())))
+            .map(|t| t.scope)
+            .max()
+            .ok_or_else(|| unauthenticated("Invalid bearer token"))?;
+
+        // Clients are identified by a prefix of the token hash so that the token itself is never logged
+        Ok((scope, GrpcClientIdentity(format!("token:{}", &token_hash[..16]))))
+    }
+
+    fn handle_client_certificate_auth(
+        &self,
+        req: &Request<()>,
+        valid_certificates: &[GrpcClientCertificate],
+    ) -> Result<(GrpcScope, GrpcClientIdentity), Status> {
+        // The TLS layer has already verified the certificate chain against the client CA, so only the fingerprint of
+        // the leaf certificate needs to be matched here.
+        let peer_certs = req
+            .peer_certs()
+            .ok_or_else(|| unauthenticated("No client certificate was presented"))?;
+        let client_cert = peer_certs
+            .first()
+            .ok_or_else(|| unauthenticated("No client certificate was presented"))?;
+        let fingerprint = to_hex(&Sha256::digest(client_cert.get_ref()));
+
+        let scope = valid_certificates
+            .iter()
+            .filter(|c| bool::from(c.fingerprint.as_bytes().ct_eq(fingerprint.as_bytes())))
+            .map(|c| c.scope)
+            .max()
+            .ok_or_else(|| unauthenticated(format!("Unknown client certificate {}", fingerprint)))?;
+
+        Ok((scope, GrpcClientIdentity(format!("certificate:{}", fingerprint))))
+    }
 }
 
 impl Interceptor for ServerAuthenticationInterceptor {
-    fn call(&mut self, request: Request<()>) -> Result<Request<()>, Status> {
-        match &self.auth {
-            GrpcAuthentication::None => Ok(request),
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        // Requests that did not pass through `GrpcMethodService` cannot be matched to a method, so they need admin
+        let required_scope = request
+            .extensions()
+            .get::<GrpcMethod>()
+            .map_or(GrpcScope::Admin, |method| (self.required_scope)(method.name()));
+        let (scope, identity) = match &self.auth {
+            GrpcAuthentication::None => return Ok(request),
             GrpcAuthentication::Basic {
                 username,
                 password: phc_password,
-            } => self.handle_basic_auth(request, username, phc_password),
+            } => {
+                let mut request = self.handle_basic_auth(request, username, phc_password)?;
+                request
+                    .extensions_mut()
+                    .insert(GrpcClientIdentity(format!("basic:{}", username)));
+                return Ok(request);
+            },
+            GrpcAuthentication::Token { tokens } => self.handle_token_auth(&request, tokens)?,
+            GrpcAuthentication::ClientCertificate { client_certificates } => {
+                self.handle_client_certificate_auth(&request, client_certificates)?
+            },
+        };
+
+        if scope.allows(required_scope) {
+            request.extensions_mut().insert(identity);
+            Ok(request)
+        } else {
+            warn!(
+                target: LOG_TARGET,
+                "GRPC authorization failed: scope {:?} does not allow a method requiring {:?}", scope, required_scope
+            );
+            Err(Status::permission_denied("Insufficient scope"))
         }
     }
 }
@@ -92,10 +218,24 @@ impl Clone for ServerAuthenticationInterceptor {
     fn clone(&self) -> Self {
         Self {
             auth: self.auth.clone(),
+            required_scope: self.required_scope,
         }
     }
 }
 
+impl fmt::Debug for ServerAuthenticationInterceptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ServerAuthenticationInterceptor")
+            .field("auth", &self.auth)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Fingerprints are accepted with or without colon separators and in either case
+fn normalize_fingerprint(fingerprint: &str) -> String {
+    fingerprint.replace(':', "").to_lowercase()
+}
+
 /// Standard unauthenticated response
 fn unauthenticated<E: ToString>(err: E) -> Status {
     warn!(target: LOG_TARGET, "GRPC authentication failed: {}", err.to_string());
@@ -27,10 +27,24 @@ use std::{
 };
 
 use rcgen::{generate_simple_self_signed, Certificate, CertificateParams, DnType, IsCa::Ca};
+use sha2::{Digest, Sha256};
+use tari_utilities::hex::to_hex;
 
 use crate::tls::error::GrpcTlsError;
 
-pub fn generate_self_signed_certs() -> Result<(String, String, String), GrpcTlsError> {
+/// PEM encoded certificates and keys produced by [generate_self_signed_certs]
+pub struct SelfSignedCerts {
+    pub ca_cert: String,
+    pub server_cert: String,
+    pub server_private_key: String,
+    /// Client certificate signed by the same CA, for use with client certificate authentication
+    pub client_cert: String,
+    pub client_private_key: String,
+    /// Hex encoded SHA-256 fingerprint of the DER encoded client certificate
+    pub client_fingerprint: String,
+}
+
+pub fn generate_self_signed_certs() -> Result<SelfSignedCerts, GrpcTlsError> {
     let subject_alt_names = vec!["localhost".to_string(), "127.0.0.1".to_string(), "0.0.0.0".to_string()];
     let mut params = CertificateParams::new(subject_alt_names.clone());
     params.distinguished_name.push(DnType::CommonName, "127.0.0.1");
@@ -40,11 +54,34 @@ pub fn generate_self_signed_certs() -> Result<(String, String, String), GrpcTlsE
 
     let server_cert = generate_simple_self_signed(subject_alt_names).unwrap();
 
-    Ok((
-        cacert,
-        server_cert.serialize_pem_with_signer(&ca).unwrap(),
-        server_cert.serialize_private_key_pem(),
-    ))
+    let mut client_params = CertificateParams::new(vec![]);
+    client_params
+        .distinguished_name
+        .push(DnType::CommonName, "minotari grpc client");
+    let client_cert = Certificate::from_params(client_params)?;
+    // The certificate is only serialized once, since every serialization produces a new signature and the fingerprint
+    // has to match the certificate that is written to disk
+    let client_der = client_cert.serialize_der_with_signer(&ca)?;
+
+    Ok(SelfSignedCerts {
+        ca_cert: cacert,
+        server_cert: server_cert.serialize_pem_with_signer(&ca).unwrap(),
+        server_private_key: server_cert.serialize_private_key_pem(),
+        client_cert: der_to_pem(&client_der),
+        client_private_key: client_cert.serialize_private_key_pem(),
+        client_fingerprint: to_hex(&Sha256::digest(&client_der)),
+    })
+}
+
+fn der_to_pem(der: &[u8]) -> String {
+    let encoded = base64::encode(der);
+    let mut pem = String::from("-----BEGIN CERTIFICATE-----\n");
+    for line in encoded.as_bytes().chunks(64) {
+        pem.push_str(&String::from_utf8_lossy(line));
+        pem.push('\n');
+    }
+    pem.push_str("-----END CERTIFICATE-----\n");
+    pem
 }
 
 pub fn write_cert_to_disk(dir: PathBuf, filename: &str, data: &String) -> Result<(), GrpcTlsError> {
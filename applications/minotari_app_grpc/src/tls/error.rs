@@ -32,4 +32,6 @@ pub enum GrpcTlsError {
     CertGenerationError(#[from] RcgenError),
     #[error("Error opening or writing the file: {0}")]
     IoError(#[from] io::Error),
+    #[error("Client certificate authentication requires gRPC TLS to be enabled")]
+    ClientCertificateWithoutTls,
 }
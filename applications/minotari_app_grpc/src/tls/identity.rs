@@ -22,18 +22,56 @@
 
 use std::path::PathBuf;
 
-use tonic::transport::Identity;
+use tari_common_types::grpc_authentication::GrpcAuthentication;
+use tonic::transport::{Certificate, Identity, ServerTlsConfig};
 
 use crate::tls::error::GrpcTlsError;
 
 pub async fn read_identity(config_dir: PathBuf) -> Result<Identity, GrpcTlsError> {
+    read_identity_files(config_dir, "server.pem", "server.key").await
+}
+
+/// Reads the client certificate and key written by the `create-tls-certs` command, for use with client certificate
+/// authentication
+pub async fn read_client_identity(config_dir: PathBuf) -> Result<Identity, GrpcTlsError> {
+    read_identity_files(config_dir, "client.pem", "client.key").await
+}
+
+async fn read_identity_files(config_dir: PathBuf, cert_name: &str, key_name: &str) -> Result<Identity, GrpcTlsError> {
     let err = |file| move |e| GrpcTlsError::FileReadError(format!("Could not load the file `{:?}`: {}", file, e));
 
-    let cert_file = config_dir.join("server.pem");
+    let cert_file = config_dir.join(cert_name);
     let cert = tokio::fs::read(&cert_file).await.map_err(err(cert_file))?;
 
-    let key_file = config_dir.join("server.key");
+    let key_file = config_dir.join(key_name);
     let key = tokio::fs::read(&key_file).await.map_err(err(key_file))?;
 
     Ok(Identity::from_pem(cert, key))
 }
+
+/// Builds the server TLS configuration. When client certificate authentication is configured, clients must present a
+/// certificate signed by the CA in `ca_file_name`.
+pub async fn read_server_tls_config(
+    config_dir: PathBuf,
+    ca_file_name: &str,
+    auth: &GrpcAuthentication,
+    tls_enabled: bool,
+) -> Result<Option<ServerTlsConfig>, GrpcTlsError> {
+    if !tls_enabled {
+        if auth.requires_client_certificate() {
+            return Err(GrpcTlsError::ClientCertificateWithoutTls);
+        }
+        return Ok(None);
+    }
+
+    let mut tls_config = ServerTlsConfig::new().identity(read_identity(config_dir.clone()).await?);
+    if auth.requires_client_certificate() {
+        let ca_file = config_dir.join(ca_file_name);
+        let ca = tokio::fs::read(&ca_file)
+            .await
+            .map_err(|e| GrpcTlsError::FileReadError(format!("Could not load the file `{:?}`: {}", ca_file, e)))?;
+        tls_config = tls_config.client_ca_root(Certificate::from_pem(ca));
+    }
+
+    Ok(Some(tls_config))
+}
@@ -28,6 +28,7 @@ use std::{
     io,
     io::{LineWriter, Write},
     path::{Path, PathBuf},
+    str::FromStr,
     time::{Duration, Instant},
 };
 
@@ -40,11 +41,14 @@ use minotari_wallet::{
     connectivity_service::WalletConnectivityInterface,
     output_manager_service::{
         handle::{OutputManagerEvent, OutputManagerHandle},
+        storage::OutputStatus,
         UtxoSelectionCriteria,
     },
     transaction_service::{
-        handle::{TransactionEvent, TransactionServiceHandle},
-        storage::models::WalletTransaction,
+        handle::{OneSidedRecipient, TransactionEvent, TransactionServiceHandle},
+        partially_signed_transaction::PartiallySignedTransaction,
+        spending_policy::SpendingPolicy,
+        storage::models::{TransactionExportFilter, TransactionExportRecord, WalletTransaction},
     },
     utxo_scanner_service::handle::UtxoScannerEvent,
     TransactionStage,
@@ -86,6 +90,7 @@ use tari_core::{
             UnblindedOutput,
             WalletOutput,
         },
+        PaymentProof,
     },
 };
 use tari_crypto::ristretto::{pedersen::PedersenCommitment, RistrettoSecretKey};
@@ -118,7 +123,7 @@ use crate::{
         PreMineSpendStep3OutputsForSelf,
         PreMineSpendStep4OutputsForLeader,
     },
-    cli::{CliCommands, MakeItRainTransactionType},
+    cli::{CliCommands, ExportFormat, MakeItRainTransactionType},
     utils::db::{CUSTOM_BASE_NODE_ADDRESS_KEY, CUSTOM_BASE_NODE_PUBLIC_KEY_KEY},
 };
 
@@ -140,6 +145,7 @@ pub async fn send_tari(
     mut wallet_transaction_service: TransactionServiceHandle,
     fee_per_gram: u64,
     amount: MicroMinotari,
+    selection_criteria: UtxoSelectionCriteria,
     destination: TariAddress,
     message: String,
 ) -> Result<TxId, CommandError> {
@@ -147,7 +153,7 @@ pub async fn send_tari(
         .send_transaction(
             destination,
             amount,
-            UtxoSelectionCriteria::default(),
+            selection_criteria,
             OutputFeatures::default(),
             fee_per_gram * uT,
             message,
@@ -340,6 +346,19 @@ pub async fn send_one_sided_to_stealth_address(
         .map_err(CommandError::TransactionServiceError)
 }
 
+pub async fn send_batch_one_sided(
+    mut wallet_transaction_service: TransactionServiceHandle,
+    fee_per_gram: u64,
+    recipients: Vec<OneSidedRecipient>,
+    selection_criteria: UtxoSelectionCriteria,
+    message: String,
+) -> Result<TxId, CommandError> {
+    wallet_transaction_service
+        .send_batch_one_sided_transaction(recipients, selection_criteria, fee_per_gram * uT, message)
+        .await
+        .map_err(CommandError::TransactionServiceError)
+}
+
 pub async fn coin_split(
     amount_per_split: MicroMinotari,
     num_splits: usize,
@@ -515,7 +534,15 @@ pub async fn make_it_rain(
                     // Send transaction
                     let tx_id = match transaction_type {
                         MakeItRainTransactionType::Interactive => {
-                            send_tari(tx_service, fee, amount, address.clone(), msg.clone()).await
+                            send_tari(
+                                tx_service,
+                                fee,
+                                amount,
+                                UtxoSelectionCriteria::default(),
+                                address.clone(),
+                                msg.clone(),
+                            )
+                            .await
                         },
                         MakeItRainTransactionType::StealthOneSided => {
                             send_one_sided_to_stealth_address(
@@ -875,6 +902,7 @@ pub async fn command_runner(
                     recipient_address: args.recipient_address,
                     fee_per_gram: args.fee_per_gram,
                     output_index: args.output_index,
+                    multisig: false,
                 };
 
                 let out_file = out_dir.join(get_file_name(SPEND_SESSION_INFO, None));
@@ -955,6 +983,10 @@ pub async fn command_runner(
 
                 // Read session info
                 let session_info = read_session_info::<PreMineSpendStep1SessionInfo>(args.input_file.clone())?;
+                if session_info.multisig {
+                    eprintln!("\nError: Use 'multisig-spend-party-details' for multisig account spends!\n");
+                    break;
+                }
 
                 if session_info.output_index != args.output_index {
                     eprintln!(
@@ -1052,16 +1084,17 @@ pub async fn command_runner(
                 println!();
             },
             PreMineSpendEncumberAggregateUtxo(args) => {
-                match *key_manager_service.get_wallet_type().await {
-                    WalletType::Ledger(_) => {},
-                    _ => {
-                        eprintln!("\nError: Wallet type must be 'Ledger' to spend pre-mine outputs!\n");
-                        break;
-                    },
-                }
-
                 // Read session info
                 let session_info = read_verify_session_info::<PreMineSpendStep1SessionInfo>(&args.session_id)?;
+                if !session_info.multisig {
+                    match *key_manager_service.get_wallet_type().await {
+                        WalletType::Ledger(_) => {},
+                        _ => {
+                            eprintln!("\nError: Wallet type must be 'Ledger' to spend pre-mine outputs!\n");
+                            break;
+                        },
+                    }
+                }
 
                 #[allow(clippy::mutable_key_type)]
                 let mut input_shares = HashMap::new();
@@ -1146,16 +1179,17 @@ pub async fn command_runner(
                 }
             },
             PreMineSpendInputOutputSigs(args) => {
-                match *key_manager_service.get_wallet_type().await {
-                    WalletType::Ledger(_) => {},
-                    _ => {
-                        eprintln!("\nError: Wallet type must be 'Ledger' to spend pre-mine outputs!\n");
-                        break;
-                    },
-                }
-
                 // Read session info
                 let session_info = read_verify_session_info::<PreMineSpendStep1SessionInfo>(&args.session_id)?;
+                if !session_info.multisig {
+                    match *key_manager_service.get_wallet_type().await {
+                        WalletType::Ledger(_) => {},
+                        _ => {
+                            eprintln!("\nError: Wallet type must be 'Ledger' to spend pre-mine outputs!\n");
+                            break;
+                        },
+                    }
+                }
                 // Read leader input
                 let leader_info = read_and_verify::<PreMineSpendStep3OutputsForParties>(
                     &args.session_id,
@@ -1258,16 +1292,17 @@ pub async fn command_runner(
                 }
             },
             PreMineSpendAggregateTransaction(args) => {
-                match *key_manager_service.get_wallet_type().await {
-                    WalletType::Ledger(_) => {},
-                    _ => {
-                        eprintln!("\nError: Wallet type must be 'Ledger' to spend pre-mine outputs!\n");
-                        break;
-                    },
-                }
-
                 // Read session info
                 let session_info = read_verify_session_info::<PreMineSpendStep1SessionInfo>(&args.session_id)?;
+                if !session_info.multisig {
+                    match *key_manager_service.get_wallet_type().await {
+                        WalletType::Ledger(_) => {},
+                        _ => {
+                            eprintln!("\nError: Wallet type must be 'Ledger' to spend pre-mine outputs!\n");
+                            break;
+                        },
+                    }
+                }
 
                 let mut metadata_signatures = Vec::with_capacity(args.input_file_names.len());
                 let mut script_signatures = Vec::with_capacity(args.input_file_names.len());
@@ -1313,6 +1348,10 @@ pub async fn command_runner(
                     transaction_service.clone(),
                     config.fee_per_gram,
                     args.amount,
+                    UtxoSelectionCriteria {
+                        ordering: args.utxo_selection,
+                        ..Default::default()
+                    },
                     args.destination,
                     args.message,
                 )
@@ -1330,7 +1369,10 @@ pub async fn command_runner(
                     transaction_service.clone(),
                     config.fee_per_gram,
                     args.amount,
-                    UtxoSelectionCriteria::default(),
+                    UtxoSelectionCriteria {
+                        ordering: args.utxo_selection,
+                        ..Default::default()
+                    },
                     args.destination,
                     args.message,
                     PaymentId::Empty,
@@ -1347,6 +1389,35 @@ pub async fn command_runner(
                     Err(e) => eprintln!("SendOneSidedToStealthAddress error! {}", e),
                 }
             },
+            SendBatchOneSided(args) => {
+                let recipients = match load_batch_recipients_from_csv_file(args.input_file) {
+                    Ok(recipients) => recipients,
+                    Err(e) => {
+                        eprintln!("SendBatchOneSided error! {}", e);
+                        continue;
+                    },
+                };
+                let num_recipients = recipients.len();
+                match send_batch_one_sided(
+                    transaction_service.clone(),
+                    config.fee_per_gram,
+                    recipients,
+                    UtxoSelectionCriteria {
+                        ordering: args.utxo_selection,
+                        ..Default::default()
+                    },
+                    args.message,
+                )
+                .await
+                {
+                    Ok(tx_id) => {
+                        debug!(target: LOG_TARGET, "send-batch-one-sided concluded with tx_id {}", tx_id);
+                        println!("Sent to {} recipients in tx_id: {}", num_recipients, tx_id);
+                        tx_ids.push(tx_id);
+                    },
+                    Err(e) => eprintln!("SendBatchOneSided error! {}", e),
+                }
+            },
             MakeItRain(args) => {
                 let transaction_type = args.transaction_type();
                 if let Err(e) = make_it_rain(
@@ -1447,6 +1518,39 @@ pub async fn command_runner(
                 },
                 Err(e) => eprintln!("ExportTx error! {}", e),
             },
+            ExportTransactions(args) => {
+                let filter = TransactionExportFilter {
+                    from_timestamp: args.from_date.map(|d| d.naive_utc()),
+                    to_timestamp: args.to_date.map(|d| d.naive_utc()),
+                    from_height: args.from_height,
+                    to_height: args.to_height,
+                };
+                match transaction_service.export_transactions(filter).await {
+                    Ok(records) => {
+                        let count = records.len();
+                        let contents = match args.format {
+                            ExportFormat::Csv => transactions_to_csv(&records),
+                            ExportFormat::Json => transactions_to_json(&records)?,
+                        };
+                        if let Some(file) = args.output_file {
+                            match fs::write(&file, contents) {
+                                Ok(_) => println!("Exported {} transactions to {}", count, file.display()),
+                                Err(e) => eprintln!("ExportTransactions error! {}", e),
+                            }
+                        } else {
+                            print!("{}", contents);
+                        }
+                    },
+                    Err(e) => eprintln!("ExportTransactions error! {}", e),
+                }
+            },
+            Backup(args) => match wallet.db.create_backup() {
+                Ok(archive) => match fs::write(&args.output_file, archive) {
+                    Ok(_) => println!("Wallet backup written to {}", args.output_file.display()),
+                    Err(e) => eprintln!("Backup error! {}", e),
+                },
+                Err(e) => eprintln!("Backup error! {}", e),
+            },
             ImportTx(args) => {
                 match load_tx_from_csv_file(args.input_file) {
                     Ok(txs) => {
@@ -1522,6 +1626,45 @@ pub async fn command_runner(
                 },
                 Err(e) => eprintln!("CountUtxos error! {}", e),
             },
+            ListOutputs(args) => match output_service.get_unspent_outputs().await {
+                Ok(utxos) => {
+                    let utxos: Vec<_> = utxos.into_iter().filter(|o| !args.frozen_only || o.frozen).collect();
+                    for (i, utxo) in utxos.iter().enumerate() {
+                        println!(
+                            "{}. Commitment: {}, Value: {}, Frozen: {}, Label: {}, Note: {}",
+                            i + 1,
+                            utxo.commitment.to_hex(),
+                            utxo.wallet_output.value,
+                            utxo.frozen,
+                            utxo.label.as_deref().unwrap_or(""),
+                            utxo.note.as_deref().unwrap_or("")
+                        );
+                    }
+                    println!("Total number of UTXOs: {}", utxos.len());
+                },
+                Err(e) => eprintln!("ListOutputs error! {}", e),
+            },
+            FreezeOutput(args) => match Commitment::from_hex(&args.commitment) {
+                Ok(commitment) => match output_service.set_output_frozen(commitment, true).await {
+                    Ok(()) => println!("Output {} frozen", args.commitment),
+                    Err(e) => eprintln!("FreezeOutput error! {}", e),
+                },
+                Err(e) => eprintln!("FreezeOutput error! {}", e),
+            },
+            UnfreezeOutput(args) => match Commitment::from_hex(&args.commitment) {
+                Ok(commitment) => match output_service.set_output_frozen(commitment, false).await {
+                    Ok(()) => println!("Output {} unfrozen", args.commitment),
+                    Err(e) => eprintln!("UnfreezeOutput error! {}", e),
+                },
+                Err(e) => eprintln!("UnfreezeOutput error! {}", e),
+            },
+            LabelOutput(args) => match Commitment::from_hex(&args.commitment) {
+                Ok(commitment) => match output_service.set_output_label(commitment, args.label, args.note).await {
+                    Ok(()) => println!("Output {} labelled", args.commitment),
+                    Err(e) => eprintln!("LabelOutput error! {}", e),
+                },
+                Err(e) => eprintln!("LabelOutput error! {}", e),
+            },
             SetBaseNode(args) => {
                 if let Err(e) = set_base_node_peer(wallet.clone(), args.public_key.into(), args.address).await {
                     eprintln!("SetBaseNode error! {}", e);
@@ -1569,7 +1712,10 @@ pub async fn command_runner(
                     transaction_service.clone(),
                     config.fee_per_gram,
                     args.amount,
-                    UtxoSelectionCriteria::default(),
+                    UtxoSelectionCriteria {
+                        ordering: args.utxo_selection,
+                        ..Default::default()
+                    },
                     args.destination,
                     args.message,
                 )
@@ -1662,12 +1808,14 @@ pub async fn command_runner(
                 tx_ids.push(tx_id);
             },
             CreateTlsCerts => match generate_self_signed_certs() {
-                Ok((cacert, cert, private_key)) => {
+                Ok(certs) => {
                     print_warning();
 
-                    write_cert_to_disk(config.config_dir.clone(), "wallet_ca.pem", &cacert)?;
-                    write_cert_to_disk(config.config_dir.clone(), "server.pem", &cert)?;
-                    write_cert_to_disk(config.config_dir.clone(), "server.key", &private_key)?;
+                    write_cert_to_disk(config.config_dir.clone(), "wallet_ca.pem", &certs.ca_cert)?;
+                    write_cert_to_disk(config.config_dir.clone(), "server.pem", &certs.server_cert)?;
+                    write_cert_to_disk(config.config_dir.clone(), "server.key", &certs.server_private_key)?;
+                    write_cert_to_disk(config.config_dir.clone(), "client.pem", &certs.client_cert)?;
+                    write_cert_to_disk(config.config_dir.clone(), "client.key", &certs.client_private_key)?;
 
                     println!();
                     println!("Certificates generated successfully.");
@@ -1677,6 +1825,12 @@ pub async fn command_runner(
                          grpc_tls_enabled=true"
                     );
                     println!();
+                    println!(
+                        "To use client certificate authentication, give `client.pem` and `client.key` to the client \
+                         and add its fingerprint to `grpc_authentication.client_certificates`: {}",
+                        certs.client_fingerprint
+                    );
+                    println!();
                 },
                 Err(err) => eprintln!("Error generating certificates: {}", err),
             },
@@ -1816,6 +1970,501 @@ pub async fn command_runner(
                     println!("Spend key: {}", spend_key_hex);
                 }
             },
+            PrepareUnsignedTransaction(args) => {
+                match output_service
+                    .prepare_unsigned_transaction(
+                        args.destination,
+                        args.amount,
+                        UtxoSelectionCriteria {
+                            ordering: args.utxo_selection,
+                            ..Default::default()
+                        },
+                        config.fee_per_gram.into(),
+                        args.message,
+                    )
+                    .await
+                {
+                    Ok(unsigned_tx) => {
+                        write_json_file(&args.output_file, &unsigned_tx)?;
+                        println!(
+                            "Unsigned transaction {} spending {} inputs (amount: {}, fee: {}, change: {}) saved to {}",
+                            unsigned_tx.tx_id,
+                            unsigned_tx.inputs.len(),
+                            unsigned_tx.amount,
+                            unsigned_tx.fee,
+                            unsigned_tx.change,
+                            args.output_file.display()
+                        );
+                    },
+                    Err(e) => eprintln!("PrepareUnsignedTransaction error! {}", e),
+                }
+            },
+            CreatePartiallySignedTransaction(args) => {
+                match transaction_service
+                    .create_partially_signed_transaction(
+                        args.destination,
+                        args.amount,
+                        UtxoSelectionCriteria {
+                            ordering: args.utxo_selection,
+                            ..Default::default()
+                        },
+                        config.fee_per_gram.into(),
+                        args.message,
+                        PaymentId::Empty,
+                    )
+                    .await
+                {
+                    Ok(tx) => {
+                        write_json_file(&args.output_file, &tx)?;
+                        println!(
+                            "Partially signed transaction {} spending {} inputs saved to {}",
+                            tx.tx_id,
+                            tx.inputs.len(),
+                            args.output_file.display()
+                        );
+                    },
+                    Err(e) => eprintln!("CreatePartiallySignedTransaction error! {}", e),
+                }
+            },
+            SignPartiallySignedTransaction(args) => {
+                let tx: PartiallySignedTransaction = read_json_file(&args.input_file)?;
+                match transaction_service.sign_partially_signed_transaction(tx).await {
+                    Ok(tx) => {
+                        write_json_file(&args.output_file, &tx)?;
+                        if tx.is_fully_signed() {
+                            println!(
+                                "Signed transaction {} (amount: {}, fee: {}) saved to {}",
+                                tx.tx_id,
+                                tx.amount,
+                                tx.fee,
+                                args.output_file.display()
+                            );
+                        } else {
+                            println!(
+                                "Committed the kernel nonce of transaction {} (amount: {}, fee: {}), saved to {}. \
+                                 Sign it again to complete the signatures",
+                                tx.tx_id,
+                                tx.amount,
+                                tx.fee,
+                                args.output_file.display()
+                            );
+                        }
+                    },
+                    Err(e) => eprintln!("SignPartiallySignedTransaction error! {}", e),
+                }
+            },
+            FinalizePartiallySignedTransaction(args) => {
+                let tx: PartiallySignedTransaction = read_json_file(&args.input_file)?;
+                match transaction_service.finalize_partially_signed_transaction(tx).await {
+                    Ok(tx_id) => {
+                        debug!(
+                            target: LOG_TARGET,
+                            "finalize-partially-signed-transaction concluded with tx_id {}", tx_id
+                        );
+                        println!("Broadcasting transaction {}", tx_id);
+                        tx_ids.push(tx_id);
+                    },
+                    Err(e) => eprintln!("FinalizePartiallySignedTransaction error! {}", e),
+                }
+            },
+            CreateSubAddress(args) => match output_service.create_sub_address(args.label).await {
+                Ok(sub_address) => {
+                    println!("Sub-address {}: {}", sub_address.index, sub_address.label);
+                    println!("Address:  {}", sub_address.address.to_base58());
+                    println!("Emoji ID: {}", sub_address.address.to_emoji_string());
+                },
+                Err(e) => eprintln!("CreateSubAddress error! {}", e),
+            },
+            ListSubAddresses => {
+                let sub_addresses = output_service.get_sub_addresses().await?;
+                if sub_addresses.is_empty() {
+                    println!("No sub-addresses");
+                }
+                for sub_address in sub_addresses {
+                    let outputs = output_service.get_sub_address_outputs(sub_address.index).await?;
+                    let received = outputs
+                        .iter()
+                        .map(|o| o.wallet_output.value)
+                        .fold(MicroMinotari::zero(), |total, value| total + value);
+                    let unspent = outputs
+                        .iter()
+                        .filter(|o| o.status == OutputStatus::Unspent)
+                        .map(|o| o.wallet_output.value)
+                        .fold(MicroMinotari::zero(), |total, value| total + value);
+                    println!(
+                        "{}. {} {} received {} in {} output(s), {} unspent",
+                        sub_address.index,
+                        sub_address.label,
+                        sub_address.address.to_base58(),
+                        received,
+                        outputs.len(),
+                        unspent
+                    );
+                }
+            },
+            GetSpendingPolicy => {
+                let policy = transaction_service.get_spending_policy().await?;
+                if policy.is_unrestricted() {
+                    println!("No spending limits are set");
+                }
+                if let Some(max) = policy.max_amount_per_transaction {
+                    println!("Maximum per transaction: {}", max);
+                }
+                if let Some(limit) = policy.daily_limit {
+                    println!("Daily limit: {}", limit);
+                }
+                if let Some(destinations) = policy.allowed_destinations {
+                    println!("Allowed destinations:");
+                    for destination in destinations {
+                        println!("    {}", destination.to_base58());
+                    }
+                }
+                if let Some(threshold) = policy.large_send_threshold {
+                    println!(
+                        "Sends of {} or more are delayed by {}s",
+                        threshold,
+                        policy.large_send_delay.as_secs()
+                    );
+                }
+            },
+            SetSpendingPolicy(args) => {
+                let policy = SpendingPolicy {
+                    max_amount_per_transaction: args.max_amount_per_transaction,
+                    daily_limit: args.daily_limit,
+                    allowed_destinations: Some(args.allowed_destinations).filter(|d| !d.is_empty()),
+                    large_send_threshold: args.large_send_threshold,
+                    large_send_delay: args.large_send_delay,
+                };
+                match transaction_service.set_spending_policy(policy).await {
+                    Ok(()) => println!("Spending policy updated"),
+                    Err(e) => eprintln!("SetSpendingPolicy error! {}", e),
+                }
+            },
+            CreatePaymentProof(args) => match transaction_service
+                .generate_payment_proof(args.tx_id.into(), args.recipient)
+                .await
+            {
+                Ok(proof) => {
+                    write_json_file(&args.output_file, &proof)?;
+                    println!(
+                        "Payment proof for {} to {} saved to {}",
+                        proof.amount,
+                        proof.recipient.to_base58(),
+                        args.output_file.display()
+                    );
+                },
+                Err(e) => eprintln!("CreatePaymentProof error! {}", e),
+            },
+            VerifyPaymentProof(args) => {
+                let proof: PaymentProof = read_json_file(&args.input_file)?;
+                let amount = proof.amount;
+                let recipient = proof.recipient.to_base58();
+                let payment_id = proof.payment_id.to_string();
+                match transaction_service.verify_payment_proof(proof).await {
+                    Ok(verification) => {
+                        println!("Payment proof is valid");
+                        println!("Amount: {}", amount);
+                        println!("Recipient: {}", recipient);
+                        println!("Payment id: {}", payment_id);
+                        println!(
+                            "Mined in block {} at height {} ({} confirmations)",
+                            verification.block_hash.to_hex(),
+                            verification.mined_height,
+                            verification.confirmations
+                        );
+                    },
+                    Err(e) => eprintln!("VerifyPaymentProof error! {}", e),
+                }
+            },
+            CreatePaymentRequest(args) => {
+                let expiry = args
+                    .expires_in
+                    .map(|expires_in| Utc::now().timestamp() as u64 + expires_in.as_secs());
+                match wallet
+                    .create_payment_request(args.amount, args.payment_id.into_bytes(), expiry, args.memo)
+                    .await
+                {
+                    Ok(request) => {
+                        println!("Payment request for {}", args.amount);
+                        println!("{}", request.to_uri());
+                        println!("The payment must be sent one-sided to be matched to this request");
+                    },
+                    Err(e) => eprintln!("CreatePaymentRequest error! {}", e),
+                }
+            },
+            ListPaymentRequests => {
+                let invoices = wallet.db.fetch_invoices()?;
+                if invoices.is_empty() {
+                    println!("No payment requests");
+                }
+                for invoice in invoices {
+                    let status = match invoice.paid_tx_id {
+                        Some(tx_id) => format!("paid in transaction {}", tx_id),
+                        None => "unpaid".to_string(),
+                    };
+                    println!(
+                        "{} {} {} {}",
+                        invoice.created_at,
+                        MicroMinotari::from(invoice.request.amount()),
+                        String::from_utf8_lossy(invoice.request.payment_id()),
+                        status
+                    );
+                }
+            },
+            NewMultisigAccountKey => {
+                let key = key_manager_service
+                    .get_next_key(TransactionKeyManagerBranch::Multisig.get_branch_key())
+                    .await?;
+                println!("Multisig cosigner public key: {}", key.pub_key.to_hex());
+                println!("Share this key with the other cosigners of the account");
+            },
+            CreateMultisigAccount(args) => {
+                let own_public_key: PublicKey = args.own_public_key.into();
+                let mut public_keys: Vec<PublicKey> =
+                    args.cosigner_public_keys.into_iter().map(PublicKey::from).collect();
+                public_keys.push(own_public_key.clone());
+                match output_service
+                    .create_multisig_account(args.name, args.threshold, public_keys, own_public_key)
+                    .await
+                {
+                    Ok(account) => {
+                        println!(
+                            "Created {}-of-{} multisig account '{}'",
+                            account.threshold,
+                            account.public_keys.len(),
+                            account.name
+                        );
+                    },
+                    Err(e) => eprintln!("CreateMultisigAccount error! {}", e),
+                }
+            },
+            ListMultisigAccounts => {
+                let accounts = output_service.get_multisig_accounts().await?;
+                if accounts.is_empty() {
+                    println!("No multisig accounts");
+                }
+                for account in accounts {
+                    println!(
+                        "{} ({}-of-{})",
+                        account.name,
+                        account.threshold,
+                        account.public_keys.len()
+                    );
+                    for output in output_service.get_multisig_account_outputs(account.name).await? {
+                        let status = match (output.spent_at_height, output.spent_in_tx_id) {
+                            (Some(height), _) => format!("spent at {}", height),
+                            (None, Some(tx_id)) => format!("spent in {}", tx_id),
+                            (None, None) => "unspent".to_string(),
+                        };
+                        println!(
+                            "    {} {} mined at {} ({})",
+                            output.output_hash.to_hex(),
+                            output.value,
+                            output.mined_height,
+                            status
+                        );
+                    }
+                }
+            },
+            SendToMultisigAccount(args) => {
+                match transaction_service
+                    .send_to_multisig_account(
+                        args.threshold,
+                        args.public_keys.into_iter().map(PublicKey::from).collect(),
+                        args.amount,
+                        UtxoSelectionCriteria {
+                            ordering: args.utxo_selection,
+                            ..Default::default()
+                        },
+                        config.fee_per_gram.into(),
+                        args.message,
+                    )
+                    .await
+                {
+                    Ok(tx_id) => {
+                        debug!(target: LOG_TARGET, "send-to-multisig-account concluded with tx_id {}", tx_id);
+                        tx_ids.push(tx_id);
+                    },
+                    Err(e) => eprintln!("SendToMultisigAccount error! {}", e),
+                }
+            },
+            MultisigSpendSessionInfo(args) => {
+                let output_hash =
+                    FixedHash::from_hex(&args.output_hash).map_err(|e| CommandError::InvalidArgument(e.to_string()))?;
+                let output = match output_service
+                    .get_multisig_account_outputs(args.account.clone())
+                    .await?
+                    .into_iter()
+                    .find(|o| o.output_hash == output_hash)
+                {
+                    Some(output) => output,
+                    None => {
+                        eprintln!(
+                            "\nError: Output '{}' does not belong to multisig account '{}'!\n",
+                            output_hash, args.account
+                        );
+                        break;
+                    },
+                };
+                if output.spent_at_height.is_some() ||
+                    transaction_service
+                        .fetch_unspent_outputs(vec![output_hash])
+                        .await?
+                        .is_empty()
+                {
+                    eprintln!(
+                        "\nError: Output with output_hash '{}' has already been spent!\n",
+                        output_hash
+                    );
+                    break;
+                }
+
+                let (session_id, out_dir) = match create_pre_mine_output_dir(None) {
+                    Ok(values) => values,
+                    Err(e) => {
+                        eprintln!("\nError: {}\n", e);
+                        return Ok(());
+                    },
+                };
+                let session_info = PreMineSpendStep1SessionInfo {
+                    session_id: session_id.clone(),
+                    commitment_to_spend: output.commitment.to_hex(),
+                    output_hash: output_hash.to_hex(),
+                    recipient_address: args.recipient_address,
+                    fee_per_gram: args.fee_per_gram,
+                    output_index: 0,
+                    multisig: true,
+                };
+
+                let out_file = out_dir.join(get_file_name(SPEND_SESSION_INFO, None));
+                write_to_json_file(&out_file, true, session_info)?;
+                println!();
+                println!("Concluded step 1 'multisig-spend-session-info'");
+                println!("Your session ID is:                 '{}'", session_id);
+                println!("Your session's output directory is: '{}'", out_dir.display());
+                println!("Session info saved to:              '{}'", out_file.display());
+                println!(
+                    "Send '{}' to cosigners for step 2",
+                    get_file_name(SPEND_SESSION_INFO, None)
+                );
+                println!();
+            },
+            MultisigSpendPartyDetails(args) => {
+                if args.alias.is_empty() || args.alias.chars().any(|c| !c.is_alphanumeric() && c != '_') {
+                    eprintln!("\nError: Alias contains invalid characters! Only alphanumeric and '_' are allowed.\n");
+                    break;
+                }
+
+                // Read session info
+                let session_info = read_session_info::<PreMineSpendStep1SessionInfo>(args.input_file.clone())?;
+                if !session_info.multisig {
+                    eprintln!(
+                        "\nError: Session '{}' is not a multisig spend!\n",
+                        session_info.session_id
+                    );
+                    break;
+                }
+
+                // The cosigners may have named the account differently, so it is identified by the output's script
+                let output_hash = FixedHash::from_hex(&session_info.output_hash)
+                    .map_err(|e| CommandError::InvalidArgument(e.to_string()))?;
+                let output = match transaction_service
+                    .fetch_unspent_outputs(vec![output_hash])
+                    .await?
+                    .pop()
+                {
+                    Some(output) => output,
+                    None => {
+                        eprintln!(
+                            "\nError: Output with output_hash '{}' has already been spent!\n",
+                            output_hash
+                        );
+                        break;
+                    },
+                };
+                if output.commitment().to_hex() != session_info.commitment_to_spend {
+                    eprintln!(
+                        "\nError: Mismatched commitment from leader '{}' vs. '{}'!\n",
+                        session_info.commitment_to_spend,
+                        output.commitment().to_hex()
+                    );
+                    break;
+                }
+                let account = match output_service
+                    .get_multisig_accounts()
+                    .await?
+                    .into_iter()
+                    .find(|a| a.script() == output.script)
+                {
+                    Some(account) => account,
+                    None => {
+                        eprintln!(
+                            "\nError: Output '{}' does not belong to a multisig account of this wallet!\n",
+                            output_hash
+                        );
+                        break;
+                    },
+                };
+
+                let wallet_spend_key = wallet.key_manager_service.get_spend_key().await?;
+                let script_nonce_key = key_manager_service.get_random_key().await?;
+                let sender_offset_key = key_manager_service.get_random_key().await?;
+                let sender_offset_nonce = key_manager_service.get_random_key().await?;
+
+                let shared_secret = key_manager_service
+                    .get_diffie_hellman_shared_secret(
+                        &sender_offset_key.key_id,
+                        session_info
+                            .recipient_address
+                            .public_view_key()
+                            .ok_or(CommandError::InvalidArgument("Missing public view key".to_string()))?,
+                    )
+                    .await?;
+                let shared_secret_public_key = PublicKey::from_canonical_bytes(shared_secret.as_bytes())?;
+
+                let pre_mine_script_key_id = account.key_id();
+                let pre_mine_public_script_key = key_manager_service
+                    .get_public_key_at_key_id(&pre_mine_script_key_id)
+                    .await?;
+                let script_input_signature = key_manager_service
+                    .sign_script_message(&pre_mine_script_key_id, &account.message())
+                    .await?;
+
+                let out_dir = out_dir(&session_info.session_id)?;
+                let step_2_outputs_for_leader = PreMineSpendStep2OutputsForLeader {
+                    script_input_signature,
+                    public_script_nonce_key: script_nonce_key.pub_key,
+                    public_sender_offset_key: sender_offset_key.pub_key,
+                    public_sender_offset_nonce_key: sender_offset_nonce.pub_key,
+                    dh_shared_secret_public_key: shared_secret_public_key,
+                    pre_mine_public_script_key,
+                };
+                let out_file_leader = out_dir.join(get_file_name(SPEND_STEP_2_LEADER, Some(args.alias.clone())));
+                write_json_object_to_file_as_line(&out_file_leader, true, session_info.clone())?;
+                write_json_object_to_file_as_line(&out_file_leader, false, step_2_outputs_for_leader)?;
+
+                let step_2_outputs_for_self = PreMineSpendStep2OutputsForSelf {
+                    alias: args.alias.clone(),
+                    wallet_spend_key_id: wallet_spend_key.key_id,
+                    script_nonce_key_id: script_nonce_key.key_id,
+                    sender_offset_key_id: sender_offset_key.key_id,
+                    sender_offset_nonce_key_id: sender_offset_nonce.key_id,
+                    pre_mine_script_key_id,
+                };
+                let out_file_self = out_dir.join(get_file_name(SPEND_STEP_2_SELF, None));
+                write_json_object_to_file_as_line(&out_file_self, true, session_info.clone())?;
+                write_json_object_to_file_as_line(&out_file_self, false, step_2_outputs_for_self)?;
+
+                println!();
+                println!("Concluded step 2 'multisig-spend-party-details'");
+                println!("Your session's output directory is '{}'", out_dir.display());
+                move_session_file_to_session_dir(&session_info.session_id, &args.input_file)?;
+                println!(
+                    "Send '{}' to leader for step 3 'pre-mine-spend-encumber-aggregate-utxo'",
+                    get_file_name(SPEND_STEP_2_LEADER, Some(args.alias))
+                );
+                println!();
+            },
         }
     }
 
@@ -1976,6 +2625,79 @@ fn write_tx_to_csv_file(tx: WalletTransaction, file_path: PathBuf) -> Result<(),
     Ok(())
 }
 
+const EXPORT_TIMESTAMP_FORMAT: &str = "%Y-%m-%dT%H:%M:%SZ";
+
+/// Payment ids are exported as text where possible, falling back to hex for binary data
+fn payment_id_to_export_string(payment_id: Option<&PaymentId>) -> String {
+    let data_to_string = |data: &[u8]| String::from_utf8(data.to_vec()).unwrap_or_else(|_| data.to_hex());
+    match payment_id {
+        None | Some(PaymentId::Empty) => String::new(),
+        Some(PaymentId::U64(v)) => v.to_string(),
+        Some(PaymentId::U256(v)) => v.to_string(),
+        Some(PaymentId::Address(address)) => address.to_base58(),
+        Some(PaymentId::Open(data)) | Some(PaymentId::AddressAndData(_, data)) => data_to_string(data),
+    }
+}
+
+fn transactions_to_csv(records: &[TransactionExportRecord]) -> String {
+    let quote = |value: String| format!("\"{}\"", value.replace('"', "\"\""));
+    let mut csv = String::from(
+        r##""tx_id","direction","status","cancelled","amount","fee","balance_change","payment_id","counterparty","timestamp","mined_height","mined_timestamp","confirmations","kernel_excess","message""##,
+    );
+    csv.push('\n');
+    for record in records {
+        let fields = [
+            record.tx_id.to_string(),
+            record.direction.to_string(),
+            record.status.to_string(),
+            record.cancelled.to_string(),
+            record.amount.as_u64().to_string(),
+            record.fee.as_u64().to_string(),
+            record.balance_change().to_string(),
+            payment_id_to_export_string(record.payment_id.as_ref()),
+            record.counterparty.to_base58(),
+            record.timestamp.format(EXPORT_TIMESTAMP_FORMAT).to_string(),
+            record.mined_height.map(|h| h.to_string()).unwrap_or_default(),
+            record
+                .mined_timestamp
+                .map(|t| t.format(EXPORT_TIMESTAMP_FORMAT).to_string())
+                .unwrap_or_default(),
+            record.confirmations.map(|c| c.to_string()).unwrap_or_default(),
+            record.kernel_excess.as_ref().map(|k| k.to_hex()).unwrap_or_default(),
+            record.message.clone(),
+        ];
+        csv.push_str(&fields.into_iter().map(quote).collect::<Vec<_>>().join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+fn transactions_to_json(records: &[TransactionExportRecord]) -> Result<String, CommandError> {
+    let records = records
+        .iter()
+        .map(|record| {
+            serde_json::json!({
+                "tx_id": u64::from(record.tx_id),
+                "direction": record.direction.to_string(),
+                "status": record.status.to_string(),
+                "cancelled": record.cancelled,
+                "amount": record.amount.as_u64(),
+                "fee": record.fee.as_u64(),
+                "balance_change": record.balance_change(),
+                "payment_id": payment_id_to_export_string(record.payment_id.as_ref()),
+                "counterparty": record.counterparty.to_base58(),
+                "timestamp": record.timestamp.format(EXPORT_TIMESTAMP_FORMAT).to_string(),
+                "mined_height": record.mined_height,
+                "mined_timestamp": record.mined_timestamp.map(|t| t.format(EXPORT_TIMESTAMP_FORMAT).to_string()),
+                "confirmations": record.confirmations,
+                "kernel_excess": record.kernel_excess.as_ref().map(|k| k.to_hex()),
+                "message": record.message,
+            })
+        })
+        .collect::<Vec<_>>();
+    serde_json::to_string_pretty(&records).map_err(|e| CommandError::JsonFile(e.to_string()))
+}
+
 fn load_tx_from_csv_file(file_path: PathBuf) -> Result<Vec<WalletTransaction>, CommandError> {
     let file_contents = fs::read_to_string(file_path).map_err(|e| CommandError::CSVFile(e.to_string()))?;
     let mut results = Vec::new();
@@ -1989,6 +2711,41 @@ fn load_tx_from_csv_file(file_path: PathBuf) -> Result<Vec<WalletTransaction>, C
     Ok(results)
 }
 
+/// Reads `address,amount[,payment_id]` lines. Empty lines and lines starting with `#` are ignored, and the optional
+/// payment id is used as open (free-form) payment data.
+fn load_batch_recipients_from_csv_file(file_path: PathBuf) -> Result<Vec<OneSidedRecipient>, CommandError> {
+    let file_contents = fs::read_to_string(file_path).map_err(|e| CommandError::CSVFile(e.to_string()))?;
+    let mut recipients = Vec::new();
+    for (idx, line) in file_contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut fields = line.splitn(3, ',').map(str::trim);
+        let destination = fields
+            .next()
+            .and_then(|s| TariAddress::from_str(s).ok())
+            .ok_or_else(|| CommandError::CSVFile(format!("Invalid address on line {}", idx + 1)))?;
+        let amount = fields
+            .next()
+            .and_then(|s| MicroMinotari::from_str(s).ok())
+            .ok_or_else(|| CommandError::CSVFile(format!("Invalid amount on line {}", idx + 1)))?;
+        let payment_id = match fields.next() {
+            Some(s) if !s.is_empty() => PaymentId::Open(s.as_bytes().to_vec()),
+            _ => PaymentId::Empty,
+        };
+        recipients.push(OneSidedRecipient {
+            destination,
+            amount,
+            payment_id,
+        });
+    }
+    if recipients.is_empty() {
+        return Err(CommandError::CSVFile("No recipients found".to_string()));
+    }
+    Ok(recipients)
+}
+
 #[allow(dead_code)]
 fn write_json_file<P: AsRef<Path>, T: Serialize>(path: P, data: &T) -> Result<(), CommandError> {
     fs::create_dir_all(path.as_ref().parent().unwrap()).map_err(|e| CommandError::JsonFile(e.to_string()))?;
@@ -48,6 +48,9 @@ struct PreMineSpendStep1SessionInfo {
     output_hash: String,
     recipient_address: TariAddress,
     output_index: usize,
+    // Set when the output belongs to a multisig account rather than the pre-mine
+    #[serde(default)]
+    multisig: bool,
 }
 
 impl SessionId for PreMineSpendStep1SessionInfo {
@@ -23,12 +23,14 @@
 use std::{
     fmt::{Debug, Display, Formatter},
     path::PathBuf,
+    str::FromStr,
     time::Duration,
 };
 
 use chrono::{DateTime, Utc};
 use clap::{Args, Parser, Subcommand};
 use minotari_app_utilities::{common_cli_args::CommonCliArgs, utilities::UniPublicKey};
+use minotari_wallet::output_manager_service::UtxoSelectionOrdering;
 use tari_common::configuration::{ConfigOverrideProvider, Network};
 use tari_common_types::tari_address::TariAddress;
 use tari_comms::multiaddr::Multiaddr;
@@ -65,6 +67,9 @@ pub struct Cli {
     /// Supply the optional file name to save the wallet seed words into
     #[clap(long, aliases = &["seed_words_file_name", "seed-words-file"], parse(from_os_str))]
     pub seed_words_file_name: Option<PathBuf>,
+    /// Restore the wallet from a backup file created with the `backup` command. The wallet must not exist yet.
+    #[clap(long, parse(from_os_str))]
+    pub restore_backup: Option<PathBuf>,
     /// Run in non-interactive mode, with no UI.
     #[clap(short, long, alias = "non-interactive")]
     pub non_interactive_mode: bool,
@@ -129,15 +134,22 @@ pub enum CliCommands {
     PreMineSpendAggregateTransaction(PreMineSpendAggregateTransactionArgs),
     PreMineSpendBackupUtxo(PreMineSpendBackupUtxoArgs),
     SendOneSidedToStealthAddress(SendMinotariArgs),
+    SendBatchOneSided(SendBatchOneSidedArgs),
     MakeItRain(MakeItRainArgs),
     CoinSplit(CoinSplitArgs),
     DiscoverPeer(DiscoverPeerArgs),
     Whois(WhoisArgs),
     ExportUtxos(ExportUtxosArgs),
     ExportTx(ExportTxArgs),
+    ExportTransactions(ExportTransactionsArgs),
+    Backup(BackupArgs),
     ImportTx(ImportTxArgs),
     ExportSpentUtxos(ExportUtxosArgs),
     CountUtxos,
+    ListOutputs(ListOutputsArgs),
+    FreezeOutput(OutputCommitmentArgs),
+    UnfreezeOutput(OutputCommitmentArgs),
+    LabelOutput(LabelOutputArgs),
     SetBaseNode(SetBaseNodeArgs),
     SetCustomBaseNode(SetBaseNodeArgs),
     ClearCustomBaseNode,
@@ -149,6 +161,24 @@ pub enum CliCommands {
     CreateTlsCerts,
     Sync(SyncArgs),
     ExportViewKeyAndSpendKey(ExportViewKeyAndSpendKeyArgs),
+    PrepareUnsignedTransaction(PrepareUnsignedTransactionArgs),
+    CreatePartiallySignedTransaction(PrepareUnsignedTransactionArgs),
+    SignPartiallySignedTransaction(SignPartiallySignedTransactionArgs),
+    FinalizePartiallySignedTransaction(FinalizePartiallySignedTransactionArgs),
+    NewMultisigAccountKey,
+    CreateMultisigAccount(CreateMultisigAccountArgs),
+    ListMultisigAccounts,
+    SendToMultisigAccount(SendToMultisigAccountArgs),
+    MultisigSpendSessionInfo(MultisigSpendSessionInfoArgs),
+    MultisigSpendPartyDetails(MultisigSpendPartyDetailsArgs),
+    CreateSubAddress(CreateSubAddressArgs),
+    ListSubAddresses,
+    GetSpendingPolicy,
+    SetSpendingPolicy(SetSpendingPolicyArgs),
+    CreatePaymentProof(CreatePaymentProofArgs),
+    VerifyPaymentProof(VerifyPaymentProofArgs),
+    CreatePaymentRequest(CreatePaymentRequestArgs),
+    ListPaymentRequests,
 }
 
 #[derive(Debug, Args, Clone)]
@@ -162,6 +192,21 @@ pub struct SendMinotariArgs {
     pub destination: TariAddress,
     #[clap(short, long, default_value = "<No message>")]
     pub message: String,
+    /// One of default, smallest-first, largest-first, branch-and-bound, privacy or consolidation
+    #[clap(long, default_value = "default")]
+    pub utxo_selection: UtxoSelectionOrdering,
+}
+
+/// Recipients are read from a CSV file with one `address,amount[,payment_id]` entry per line
+#[derive(Debug, Args, Clone)]
+pub struct SendBatchOneSidedArgs {
+    #[clap(short, long)]
+    pub input_file: PathBuf,
+    #[clap(short, long, default_value = "<No message>")]
+    pub message: String,
+    /// One of default, smallest-first, largest-first, branch-and-bound, privacy or consolidation
+    #[clap(long, default_value = "default")]
+    pub utxo_selection: UtxoSelectionOrdering,
 }
 
 #[derive(Debug, Args, Clone)]
@@ -306,6 +351,30 @@ pub struct ExportUtxosArgs {
     pub with_private_keys: bool,
 }
 
+#[derive(Debug, Args, Clone)]
+pub struct ListOutputsArgs {
+    /// Only list outputs that are frozen
+    #[clap(long)]
+    pub frozen_only: bool,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct OutputCommitmentArgs {
+    /// Hex encoded commitment of the output
+    pub commitment: String,
+}
+
+/// Omitting the label or note clears it
+#[derive(Debug, Args, Clone)]
+pub struct LabelOutputArgs {
+    /// Hex encoded commitment of the output
+    pub commitment: String,
+    #[clap(short, long)]
+    pub label: Option<String>,
+    #[clap(short, long)]
+    pub note: Option<String>,
+}
+
 #[derive(Debug, Args, Clone)]
 pub struct ExportTxArgs {
     pub tx_id: u64,
@@ -313,12 +382,193 @@ pub struct ExportTxArgs {
     pub output_file: Option<PathBuf>,
 }
 
+/// Writes an encrypted backup of the complete wallet database, including transaction history, contacts and known
+/// one-sided payment scripts. Restore it with `--restore-backup` and the wallet passphrase at the time of the backup.
+#[derive(Debug, Args, Clone)]
+pub struct BackupArgs {
+    #[clap(short, long)]
+    pub output_file: PathBuf,
+}
+
+/// Exports completed, pending and cancelled transactions. Date bounds are RFC 3339 timestamps and all bounds are
+/// inclusive.
+#[derive(Debug, Args, Clone)]
+pub struct ExportTransactionsArgs {
+    #[clap(short, long)]
+    pub output_file: Option<PathBuf>,
+    /// Either csv or json
+    #[clap(long, default_value = "csv")]
+    pub format: ExportFormat,
+    #[clap(long)]
+    pub from_date: Option<DateTime<Utc>>,
+    #[clap(long)]
+    pub to_date: Option<DateTime<Utc>>,
+    #[clap(long)]
+    pub from_height: Option<u64>,
+    #[clap(long)]
+    pub to_height: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(ExportFormat::Csv),
+            "json" => Ok(ExportFormat::Json),
+            _ => Err(format!("Unknown export format '{}'", s)),
+        }
+    }
+}
+
 #[derive(Debug, Args, Clone)]
 pub struct ExportViewKeyAndSpendKeyArgs {
     #[clap(short, long)]
     pub output_file: Option<PathBuf>,
 }
 
+/// Selects inputs for a payment and writes the unsigned transaction to a file for signing elsewhere
+#[derive(Debug, Args, Clone)]
+pub struct PrepareUnsignedTransactionArgs {
+    pub amount: MicroMinotari,
+    pub destination: TariAddress,
+    #[clap(short, long, default_value = "<No message>")]
+    pub message: String,
+    /// One of default, smallest-first, largest-first, branch-and-bound, privacy or consolidation
+    #[clap(long, default_value = "default")]
+    pub utxo_selection: UtxoSelectionOrdering,
+    #[clap(short, long)]
+    pub output_file: PathBuf,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct SignPartiallySignedTransactionArgs {
+    #[clap(short, long)]
+    pub input_file: PathBuf,
+    #[clap(short, long)]
+    pub output_file: PathBuf,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct FinalizePartiallySignedTransactionArgs {
+    #[clap(short, long)]
+    pub input_file: PathBuf,
+}
+
+/// Creates an m-of-n account from this wallet's cosigner key and the keys of the other cosigners
+#[derive(Debug, Args, Clone)]
+pub struct CreateMultisigAccountArgs {
+    #[clap(long)]
+    pub name: String,
+    #[clap(long)]
+    pub threshold: u8,
+    /// This wallet's cosigner key, as printed by `new-multisig-account-key`
+    #[clap(long)]
+    pub own_public_key: UniPublicKey,
+    #[clap(long)]
+    pub cosigner_public_keys: Vec<UniPublicKey>,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct SendToMultisigAccountArgs {
+    pub amount: MicroMinotari,
+    #[clap(long)]
+    pub threshold: u8,
+    #[clap(long)]
+    pub public_keys: Vec<UniPublicKey>,
+    #[clap(short, long, default_value = "<No message>")]
+    pub message: String,
+    /// One of default, smallest-first, largest-first, branch-and-bound, privacy or consolidation
+    #[clap(long, default_value = "default")]
+    pub utxo_selection: UtxoSelectionOrdering,
+}
+
+/// Starts a spend of a multisig account output; the remaining steps are shared with the pre-mine spend
+#[derive(Debug, Args, Clone)]
+pub struct MultisigSpendSessionInfoArgs {
+    #[clap(long)]
+    pub account: String,
+    /// Hex encoded hash of the account output to spend, as listed by `list-multisig-accounts`
+    #[clap(long)]
+    pub output_hash: String,
+    #[clap(long)]
+    pub fee_per_gram: MicroMinotari,
+    #[clap(long)]
+    pub recipient_address: TariAddress,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct MultisigSpendPartyDetailsArgs {
+    #[clap(long)]
+    pub input_file: PathBuf,
+    #[clap(long)]
+    pub alias: String,
+}
+
+/// Derives a new receiving address from the wallet seed, e.g. to attribute payments to a single customer
+#[derive(Debug, Args, Clone)]
+pub struct CreateSubAddressArgs {
+    #[clap(short, long, default_value = "")]
+    pub label: String,
+}
+
+/// Replaces the spending policy of the wallet, limits that are not given are removed
+#[derive(Debug, Args, Clone)]
+pub struct SetSpendingPolicyArgs {
+    #[clap(long)]
+    pub max_amount_per_transaction: Option<MicroMinotari>,
+    #[clap(long)]
+    pub daily_limit: Option<MicroMinotari>,
+    /// May be repeated, when given funds can only be sent to these addresses
+    #[clap(long = "allow-destination")]
+    pub allowed_destinations: Vec<TariAddress>,
+    #[clap(long)]
+    pub large_send_threshold: Option<MicroMinotari>,
+    /// Seconds a large send must wait before it is repeated
+    #[clap(long, parse(try_from_str = parse_duration), default_value = "0")]
+    pub large_send_delay: Duration,
+}
+
+/// Writes a proof that a mined one-sided transaction paid its recipient. Only plain one-sided payments, whose output is
+/// locked to the recipient's public spend key, are supported; stealth one-sided and interactive payments cannot be
+/// proven. The proof can be created by the sender or by the receiver of the payment, and is checked with
+/// `verify-payment-proof` on a wallet or a base node.
+#[derive(Debug, Args, Clone)]
+pub struct CreatePaymentProofArgs {
+    pub tx_id: u64,
+    #[clap(short, long)]
+    pub output_file: PathBuf,
+    /// The recipient to prove the payment to, required for transactions that pay several recipients
+    #[clap(long)]
+    pub recipient: Option<TariAddress>,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct VerifyPaymentProofArgs {
+    #[clap(short, long)]
+    pub input_file: PathBuf,
+}
+
+/// Creates a payment request to the wallet's one-sided address. The payment is matched to the request while scanning
+/// when it carries the request's payment id, which only one-sided transactions do.
+#[derive(Debug, Args, Clone)]
+pub struct CreatePaymentRequestArgs {
+    pub amount: MicroMinotari,
+    #[clap(long)]
+    pub payment_id: String,
+    /// Seconds after which the request should no longer be paid
+    #[clap(long, parse(try_from_str = parse_duration))]
+    pub expires_in: Option<Duration>,
+    #[clap(long)]
+    pub memo: Option<String>,
+}
+
 #[derive(Debug, Args, Clone)]
 pub struct ImportTxArgs {
     #[clap(short, long)]
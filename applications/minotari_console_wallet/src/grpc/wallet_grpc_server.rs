@@ -23,8 +23,10 @@
 use std::{
     convert::{TryFrom, TryInto},
     str::FromStr,
+    time::Duration,
 };
 
+use chrono::NaiveDateTime;
 use futures::{
     channel::mpsc::{self, Sender},
     future,
@@ -45,8 +47,16 @@ use minotari_app_grpc::tari_rpc::{
     CommitmentSignature,
     CreateBurnTransactionRequest,
     CreateBurnTransactionResponse,
+    CreatePartiallySignedTransactionRequest,
+    CreateSubAddressRequest,
+    CreateSubAddressResponse,
     CreateTemplateRegistrationRequest,
     CreateTemplateRegistrationResponse,
+    ExportTransactionsRequest,
+    ExportTransactionsResponse,
+    ExportedTransaction,
+    FinalizePartiallySignedTransactionRequest,
+    FinalizePartiallySignedTransactionResponse,
     GetAddressResponse,
     GetBalanceRequest,
     GetBalanceResponse,
@@ -55,6 +65,8 @@ use minotari_app_grpc::tari_rpc::{
     GetConnectivityRequest,
     GetIdentityRequest,
     GetIdentityResponse,
+    GetSpendingPolicyRequest,
+    GetSpendingPolicyResponse,
     GetTransactionInfoRequest,
     GetTransactionInfoResponse,
     GetUnspentAmountsResponse,
@@ -62,14 +74,31 @@ use minotari_app_grpc::tari_rpc::{
     GetVersionResponse,
     ImportUtxosRequest,
     ImportUtxosResponse,
+    LabelledOutput,
+    ListOutputsRequest,
+    ListOutputsResponse,
+    ListSubAddressesRequest,
+    ListSubAddressesResponse,
+    PartiallySignedTransactionResponse,
     RegisterValidatorNodeRequest,
     RegisterValidatorNodeResponse,
     RevalidateRequest,
     RevalidateResponse,
+    SendBatchOneSidedRequest,
+    SendBatchOneSidedResponse,
     SendShaAtomicSwapRequest,
     SendShaAtomicSwapResponse,
     SetBaseNodeRequest,
     SetBaseNodeResponse,
+    SetOutputFrozenRequest,
+    SetOutputFrozenResponse,
+    SetOutputLabelRequest,
+    SetOutputLabelResponse,
+    SetSpendingPolicyRequest,
+    SetSpendingPolicyResponse,
+    SignPartiallySignedTransactionRequest,
+    SpendingPolicy as GrpcSpendingPolicy,
+    SubAddress,
     TransactionDirection,
     TransactionEvent,
     TransactionEventRequest,
@@ -85,17 +114,25 @@ use minotari_app_grpc::tari_rpc::{
 use minotari_wallet::{
     connectivity_service::{OnlineStatus, WalletConnectivityInterface},
     error::WalletStorageError,
-    output_manager_service::{handle::OutputManagerHandle, UtxoSelectionCriteria},
+    output_manager_service::{
+        handle::OutputManagerHandle,
+        storage::OutputStatus,
+        UtxoSelectionCriteria,
+        UtxoSelectionOrdering,
+    },
     transaction_service::{
-        handle::TransactionServiceHandle,
-        storage::models::{self, WalletTransaction},
+        handle::{OneSidedRecipient, TransactionServiceHandle},
+        partially_signed_transaction::PartiallySignedTransaction,
+        spending_policy::SpendingPolicy,
+        storage::models::{self, TransactionExportFilter, WalletTransaction},
     },
     WalletSqlite,
 };
 use tari_common_types::{
+    grpc_authentication::GrpcScope,
     tari_address::TariAddress,
     transaction::TxId,
-    types::{BlockHash, PublicKey, Signature},
+    types::{BlockHash, Commitment, PublicKey, Signature},
 };
 use tari_comms::{multiaddr::Multiaddr, types::CommsPublicKey, CommsNode};
 use tari_core::{
@@ -124,6 +161,111 @@ use crate::{
 
 const LOG_TARGET: &str = "wallet::ui::grpc";
 
+/// The scope a gRPC client needs to call the given wallet method. Unknown methods require the admin scope.
+pub fn required_scope(method: &str) -> GrpcScope {
+    match method {
+        "GetVersion" |
+        "CheckConnectivity" |
+        "CheckForUpdates" |
+        "Identify" |
+        "GetAddress" |
+        "GetTransactionInfo" |
+        "GetCompletedTransactions" |
+        "GetBalance" |
+        "GetUnspentAmounts" |
+        "GetNetworkStatus" |
+        "ListConnectedPeers" |
+        "StreamTransactionEvents" |
+        "ListOutputs" |
+        "ExportTransactions" |
+        "ListSubAddresses" |
+        "GetSpendingPolicy" => GrpcScope::Read,
+        "Transfer" |
+        "CoinSplit" |
+        "CancelTransaction" |
+        "SendShaAtomicSwapTransaction" |
+        "CreateBurnTransaction" |
+        "SendBatchOneSided" |
+        "ClaimShaAtomicSwapTransaction" |
+        "ClaimHtlcRefundTransaction" |
+        "CreateTemplateRegistration" |
+        "RegisterValidatorNode" |
+        "SetOutputFrozen" |
+        "SetOutputLabel" |
+        "CreatePartiallySignedTransaction" |
+        "SignPartiallySignedTransaction" |
+        "FinalizePartiallySignedTransaction" |
+        "CreateSubAddress" => GrpcScope::Spend,
+        _ => GrpcScope::Admin,
+    }
+}
+
+fn spending_policy_from_grpc(policy: GrpcSpendingPolicy) -> Result<SpendingPolicy, Status> {
+    let amount = |value: u64| Some(MicroMinotari::from(value)).filter(|a| *a > MicroMinotari::zero());
+    let allowed_destinations = policy
+        .allowed_destinations
+        .iter()
+        .map(|a| TariAddress::from_str(a).map_err(|_| Status::invalid_argument(format!("Invalid destination `{}`", a))))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(SpendingPolicy {
+        max_amount_per_transaction: amount(policy.max_amount_per_transaction),
+        daily_limit: amount(policy.daily_limit),
+        allowed_destinations: Some(allowed_destinations).filter(|d| !d.is_empty()),
+        large_send_threshold: amount(policy.large_send_threshold),
+        large_send_delay: Duration::from_secs(policy.large_send_delay_seconds),
+    })
+}
+
+fn spending_policy_to_grpc(policy: SpendingPolicy) -> GrpcSpendingPolicy {
+    GrpcSpendingPolicy {
+        max_amount_per_transaction: policy
+            .max_amount_per_transaction
+            .map(|a| a.as_u64())
+            .unwrap_or_default(),
+        daily_limit: policy.daily_limit.map(|a| a.as_u64()).unwrap_or_default(),
+        allowed_destinations: policy
+            .allowed_destinations
+            .unwrap_or_default()
+            .iter()
+            .map(|a| a.to_base58())
+            .collect(),
+        large_send_threshold: policy.large_send_threshold.map(|a| a.as_u64()).unwrap_or_default(),
+        large_send_delay_seconds: policy.large_send_delay.as_secs(),
+    }
+}
+
+fn parse_partially_signed_transaction(json: &str) -> Result<PartiallySignedTransaction, Status> {
+    serde_json::from_str(json)
+        .map_err(|e| Status::invalid_argument(format!("Malformed partially signed transaction: {}", e)))
+}
+
+fn partially_signed_transaction_response(
+    transaction: &PartiallySignedTransaction,
+) -> Result<Response<PartiallySignedTransactionResponse>, Status> {
+    Ok(Response::new(PartiallySignedTransactionResponse {
+        tx_id: transaction.tx_id.into(),
+        partially_signed_transaction: serde_json::to_string(transaction)
+            .map_err(|e| Status::internal(format!("Could not serialize partially signed transaction: {}", e)))?,
+        is_fully_signed: transaction.is_fully_signed(),
+    }))
+}
+
+fn utxo_selection_criteria(ordering: i32) -> Result<UtxoSelectionCriteria, String> {
+    let ordering = match tari_rpc::UtxoSelectionOrdering::from_i32(ordering) {
+        Some(tari_rpc::UtxoSelectionOrdering::Default) => UtxoSelectionOrdering::Default,
+        Some(tari_rpc::UtxoSelectionOrdering::SmallestFirst) => UtxoSelectionOrdering::SmallestFirst,
+        Some(tari_rpc::UtxoSelectionOrdering::LargestFirst) => UtxoSelectionOrdering::LargestFirst,
+        Some(tari_rpc::UtxoSelectionOrdering::BranchAndBound) => UtxoSelectionOrdering::BranchAndBound,
+        Some(tari_rpc::UtxoSelectionOrdering::Privacy) => UtxoSelectionOrdering::Privacy,
+        Some(tari_rpc::UtxoSelectionOrdering::Consolidation) => UtxoSelectionOrdering::Consolidation,
+        None => return Err(format!("Invalid UTXO selection ordering {}", ordering)),
+    };
+    Ok(UtxoSelectionCriteria {
+        ordering,
+        ..Default::default()
+    })
+}
+
 async fn send_transaction_event(
     transaction_event: TransactionEvent,
     sender: &mut Sender<Result<TransactionEventResponse, Status>>,
@@ -296,6 +438,247 @@ impl wallet_server::Wallet for WalletGrpcServer {
         }))
     }
 
+    async fn list_outputs(
+        &self,
+        request: Request<ListOutputsRequest>,
+    ) -> Result<Response<ListOutputsResponse>, Status> {
+        let message = request.into_inner();
+        let mut output_service = self.get_output_manager_service();
+        let outputs = output_service
+            .get_unspent_outputs()
+            .await
+            .map_err(|e| Status::not_found(format!("ListOutputs error! {}", e)))?;
+        Ok(Response::new(ListOutputsResponse {
+            outputs: outputs
+                .into_iter()
+                .filter(|o| !message.frozen_only || o.frozen)
+                .map(|o| LabelledOutput {
+                    commitment: o.commitment.to_vec(),
+                    value: o.wallet_output.value.as_u64(),
+                    frozen: o.frozen,
+                    label: o.label.unwrap_or_default(),
+                    note: o.note.unwrap_or_default(),
+                })
+                .collect(),
+        }))
+    }
+
+    async fn set_output_frozen(
+        &self,
+        request: Request<SetOutputFrozenRequest>,
+    ) -> Result<Response<SetOutputFrozenResponse>, Status> {
+        let message = request.into_inner();
+        let commitment = Commitment::from_vec(&message.commitment)
+            .map_err(|e| Status::invalid_argument(format!("Commitment was not valid: {}", e)))?;
+        let mut output_service = self.get_output_manager_service();
+        output_service
+            .set_output_frozen(commitment, message.frozen)
+            .await
+            .map_err(|e| Status::not_found(e.to_string()))?;
+        Ok(Response::new(SetOutputFrozenResponse {}))
+    }
+
+    async fn set_output_label(
+        &self,
+        request: Request<SetOutputLabelRequest>,
+    ) -> Result<Response<SetOutputLabelResponse>, Status> {
+        let message = request.into_inner();
+        let commitment = Commitment::from_vec(&message.commitment)
+            .map_err(|e| Status::invalid_argument(format!("Commitment was not valid: {}", e)))?;
+        let label = Some(message.label).filter(|l| !l.is_empty());
+        let note = Some(message.note).filter(|n| !n.is_empty());
+        let mut output_service = self.get_output_manager_service();
+        output_service
+            .set_output_label(commitment, label, note)
+            .await
+            .map_err(|e| Status::not_found(e.to_string()))?;
+        Ok(Response::new(SetOutputLabelResponse {}))
+    }
+
+    async fn export_transactions(
+        &self,
+        request: Request<ExportTransactionsRequest>,
+    ) -> Result<Response<ExportTransactionsResponse>, Status> {
+        let message = request.into_inner();
+        let to_timestamp = |seconds: u64| {
+            Some(seconds).filter(|s| *s > 0).map(|s| {
+                i64::try_from(s)
+                    .ok()
+                    .and_then(|s| NaiveDateTime::from_timestamp_opt(s, 0))
+                    .ok_or_else(|| Status::invalid_argument(format!("Timestamp {} is out of range", s)))
+            })
+        };
+        let filter = TransactionExportFilter {
+            from_timestamp: to_timestamp(message.from_timestamp).transpose()?,
+            to_timestamp: to_timestamp(message.to_timestamp).transpose()?,
+            from_height: Some(message.from_height).filter(|h| *h > 0),
+            to_height: Some(message.to_height).filter(|h| *h > 0),
+        };
+        let mut transaction_service = self.get_transaction_service();
+        let records = transaction_service
+            .export_transactions(filter)
+            .await
+            .map_err(|e| Status::internal(format!("ExportTransactions error! {}", e)))?;
+        Ok(Response::new(ExportTransactionsResponse {
+            transactions: records
+                .into_iter()
+                .map(|record| ExportedTransaction {
+                    tx_id: record.tx_id.into(),
+                    balance_change: record.balance_change(),
+                    direction: TransactionDirection::from(record.direction) as i32,
+                    status: TransactionStatus::from(record.status) as i32,
+                    is_cancelled: record.cancelled,
+                    amount: record.amount.as_u64(),
+                    fee: record.fee.as_u64(),
+                    payment_id: record.payment_id.map(|id| id.to_bytes()).unwrap_or_default(),
+                    counterparty_address: record.counterparty.to_vec(),
+                    message: record.message,
+                    timestamp: u64::try_from(record.timestamp.timestamp()).unwrap_or_default(),
+                    mined_height: record.mined_height.unwrap_or_default(),
+                    mined_timestamp: record
+                        .mined_timestamp
+                        .and_then(|t| u64::try_from(t.timestamp()).ok())
+                        .unwrap_or_default(),
+                    confirmations: record.confirmations.unwrap_or_default(),
+                    kernel_excess: record.kernel_excess.map(|k| k.to_vec()).unwrap_or_default(),
+                })
+                .collect(),
+        }))
+    }
+
+    async fn create_partially_signed_transaction(
+        &self,
+        request: Request<CreatePartiallySignedTransactionRequest>,
+    ) -> Result<Response<PartiallySignedTransactionResponse>, Status> {
+        let message = request.into_inner();
+        let address = TariAddress::from_str(&message.address)
+            .map_err(|_| Status::invalid_argument("Destination address is malformed"))?;
+        let payment_id =
+            PaymentId::from_bytes(&message.payment_id).map_err(|_| Status::invalid_argument("Invalid payment id"))?;
+        let selection_criteria = utxo_selection_criteria(message.utxo_selection).map_err(Status::invalid_argument)?;
+        let mut transaction_service = self.get_transaction_service();
+        let transaction = transaction_service
+            .create_partially_signed_transaction(
+                address,
+                message.amount.into(),
+                selection_criteria,
+                message.fee_per_gram.into(),
+                message.message,
+                payment_id,
+            )
+            .await
+            .map_err(|e| Status::internal(format!("CreatePartiallySignedTransaction error! {}", e)))?;
+        partially_signed_transaction_response(&transaction)
+    }
+
+    async fn sign_partially_signed_transaction(
+        &self,
+        request: Request<SignPartiallySignedTransactionRequest>,
+    ) -> Result<Response<PartiallySignedTransactionResponse>, Status> {
+        let transaction = parse_partially_signed_transaction(&request.into_inner().partially_signed_transaction)?;
+        let mut transaction_service = self.get_transaction_service();
+        let transaction = transaction_service
+            .sign_partially_signed_transaction(transaction)
+            .await
+            .map_err(|e| Status::internal(format!("SignPartiallySignedTransaction error! {}", e)))?;
+        partially_signed_transaction_response(&transaction)
+    }
+
+    async fn finalize_partially_signed_transaction(
+        &self,
+        request: Request<FinalizePartiallySignedTransactionRequest>,
+    ) -> Result<Response<FinalizePartiallySignedTransactionResponse>, Status> {
+        let transaction = parse_partially_signed_transaction(&request.into_inner().partially_signed_transaction)?;
+        let mut transaction_service = self.get_transaction_service();
+        let tx_id = transaction_service
+            .finalize_partially_signed_transaction(transaction)
+            .await
+            .map_err(|e| Status::internal(format!("FinalizePartiallySignedTransaction error! {}", e)))?;
+        Ok(Response::new(FinalizePartiallySignedTransactionResponse {
+            tx_id: tx_id.into(),
+        }))
+    }
+
+    async fn create_sub_address(
+        &self,
+        request: Request<CreateSubAddressRequest>,
+    ) -> Result<Response<CreateSubAddressResponse>, Status> {
+        let mut output_service = self.get_output_manager_service();
+        let sub_address = output_service
+            .create_sub_address(request.into_inner().label)
+            .await
+            .map_err(|e| Status::internal(format!("CreateSubAddress error! {}", e)))?;
+        Ok(Response::new(CreateSubAddressResponse {
+            sub_address: Some(SubAddress {
+                index: sub_address.index,
+                label: sub_address.label,
+                address: sub_address.address.to_vec(),
+                received: 0,
+                unspent: 0,
+                num_outputs: 0,
+            }),
+        }))
+    }
+
+    async fn list_sub_addresses(
+        &self,
+        _request: Request<ListSubAddressesRequest>,
+    ) -> Result<Response<ListSubAddressesResponse>, Status> {
+        let mut output_service = self.get_output_manager_service();
+        let sub_addresses = output_service
+            .get_sub_addresses()
+            .await
+            .map_err(|e| Status::internal(format!("ListSubAddresses error! {}", e)))?;
+        let mut response = Vec::with_capacity(sub_addresses.len());
+        for sub_address in sub_addresses {
+            let outputs = output_service
+                .get_sub_address_outputs(sub_address.index)
+                .await
+                .map_err(|e| Status::internal(format!("ListSubAddresses error! {}", e)))?;
+            response.push(SubAddress {
+                index: sub_address.index,
+                label: sub_address.label,
+                address: sub_address.address.to_vec(),
+                received: outputs.iter().map(|o| o.wallet_output.value.as_u64()).sum(),
+                unspent: outputs
+                    .iter()
+                    .filter(|o| o.status == OutputStatus::Unspent)
+                    .map(|o| o.wallet_output.value.as_u64())
+                    .sum(),
+                num_outputs: outputs.len() as u64,
+            });
+        }
+        Ok(Response::new(ListSubAddressesResponse {
+            sub_addresses: response,
+        }))
+    }
+
+    async fn get_spending_policy(
+        &self,
+        _request: Request<GetSpendingPolicyRequest>,
+    ) -> Result<Response<GetSpendingPolicyResponse>, Status> {
+        let policy = self
+            .get_transaction_service()
+            .get_spending_policy()
+            .await
+            .map_err(|e| Status::internal(format!("GetSpendingPolicy error! {}", e)))?;
+        Ok(Response::new(GetSpendingPolicyResponse {
+            policy: Some(spending_policy_to_grpc(policy)),
+        }))
+    }
+
+    async fn set_spending_policy(
+        &self,
+        request: Request<SetSpendingPolicyRequest>,
+    ) -> Result<Response<SetSpendingPolicyResponse>, Status> {
+        let policy = spending_policy_from_grpc(request.into_inner().policy.unwrap_or_default())?;
+        self.get_transaction_service()
+            .set_spending_policy(policy)
+            .await
+            .map_err(|e| Status::internal(format!("SetSpendingPolicy error! {}", e)))?;
+        Ok(Response::new(SetSpendingPolicyResponse {}))
+    }
+
     async fn revalidate_all_transactions(
         &self,
         _request: Request<RevalidateRequest>,
@@ -509,13 +892,17 @@ impl wallet_server::Wallet for WalletGrpcServer {
                     dest.message,
                     dest.payment_type,
                     dest.payment_id,
+                    utxo_selection_criteria(dest.utxo_selection)
+                        .map_err(|e| format!("{} for recipient at index {}", e, idx))?,
                 ))
             })
             .collect::<Result<Vec<_>, _>>()
             .map_err(Status::invalid_argument)?;
 
         let mut transfers = Vec::new();
-        for (hex_address, address, amount, fee_per_gram, message, payment_type, payment_id) in recipients {
+        for (hex_address, address, amount, fee_per_gram, message, payment_type, payment_id, selection_criteria) in
+            recipients
+        {
             let payment_id = PaymentId::from_bytes(&payment_id)
                 .map_err(|_| "Invalid payment id".to_string())
                 .map_err(Status::invalid_argument)?;
@@ -528,7 +915,7 @@ impl wallet_server::Wallet for WalletGrpcServer {
                             .send_transaction(
                                 address,
                                 amount.into(),
-                                UtxoSelectionCriteria::default(),
+                                selection_criteria,
                                 OutputFeatures::default(),
                                 fee_per_gram.into(),
                                 message,
@@ -539,7 +926,7 @@ impl wallet_server::Wallet for WalletGrpcServer {
                             .send_one_sided_transaction(
                                 address,
                                 amount.into(),
-                                UtxoSelectionCriteria::default(),
+                                selection_criteria,
                                 OutputFeatures::default(),
                                 fee_per_gram.into(),
                                 message,
@@ -551,7 +938,7 @@ impl wallet_server::Wallet for WalletGrpcServer {
                             .send_one_sided_to_stealth_address_transaction(
                                 address,
                                 amount.into(),
-                                UtxoSelectionCriteria::default(),
+                                selection_criteria,
                                 OutputFeatures::default(),
                                 fee_per_gram.into(),
                                 message,
@@ -592,6 +979,69 @@ impl wallet_server::Wallet for WalletGrpcServer {
         Ok(Response::new(TransferResponse { results }))
     }
 
+    async fn send_batch_one_sided(
+        &self,
+        request: Request<SendBatchOneSidedRequest>,
+    ) -> Result<Response<SendBatchOneSidedResponse>, Status> {
+        let message = request.into_inner();
+        let recipients = message
+            .recipients
+            .into_iter()
+            .enumerate()
+            .map(|(idx, dest)| -> Result<_, String> {
+                let destination = TariAddress::from_str(&dest.address)
+                    .map_err(|_| format!("Destination address at index {} is malformed", idx))?;
+                let payment_id = PaymentId::from_bytes(&dest.payment_id)
+                    .map_err(|_| format!("Payment id at index {} is invalid", idx))?;
+                Ok(OneSidedRecipient {
+                    destination,
+                    amount: dest.amount.into(),
+                    payment_id,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(Status::invalid_argument)?;
+        if recipients.is_empty() {
+            return Err(Status::invalid_argument("At least one recipient is required"));
+        }
+        let selection_criteria = utxo_selection_criteria(message.utxo_selection).map_err(Status::invalid_argument)?;
+
+        let mut transaction_service = self.get_transaction_service();
+        debug!(
+            target: LOG_TARGET,
+            "Trying to send a batch of {} one-sided outputs",
+            recipients.len()
+        );
+        let response = match transaction_service
+            .send_batch_one_sided_transaction(
+                recipients,
+                selection_criteria,
+                message.fee_per_gram.into(),
+                message.message,
+            )
+            .await
+        {
+            Ok(tx_id) => {
+                debug!(target: LOG_TARGET, "Transaction broadcast: {}", tx_id);
+                SendBatchOneSidedResponse {
+                    transaction_id: tx_id.as_u64(),
+                    is_success: true,
+                    failure_message: Default::default(),
+                }
+            },
+            Err(e) => {
+                warn!(target: LOG_TARGET, "Failed to send batch one-sided transaction: {}", e);
+                SendBatchOneSidedResponse {
+                    is_success: false,
+                    failure_message: e.to_string(),
+                    ..Default::default()
+                }
+            },
+        };
+
+        Ok(Response::new(response))
+    }
+
     async fn create_burn_transaction(
         &self,
         request: Request<CreateBurnTransactionRequest>,
@@ -22,7 +22,14 @@
 
 #![allow(dead_code, unused)]
 
-use std::{fs, io, path::PathBuf, str::FromStr, sync::Arc, time::Instant};
+use std::{
+    fs,
+    io,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+    time::Instant,
+};
 
 use crossterm::terminal::{disable_raw_mode, enable_raw_mode, is_raw_mode_enabled};
 use log::*;
@@ -34,6 +41,7 @@ use minotari_wallet::{
     output_manager_service::storage::database::OutputManagerDatabase,
     storage::{
         database::{WalletBackend, WalletDatabase},
+        sqlite_db::wallet::restore_wallet_backup,
         sqlite_utilities::initialize_sqlite_database_backends,
     },
     wallet::{derive_comms_secret_key, read_or_create_master_seed, read_or_create_wallet_type},
@@ -480,6 +488,20 @@ pub async fn init_wallet(
         "Wallet started in {}ms", now.elapsed().as_millis()
     );
 
+    if config.wallet.use_block_filters {
+        println!();
+        println!("=========================");
+        println!("        WARNING!         ");
+        println!("=========================");
+        println!("Block filter scanning is enabled (use_block_filters = true).");
+        println!(
+            "Stealth one-sided payments, which most wallets send by default, can not be matched by a block filter"
+        );
+        println!("and are missed in the blocks that are skipped. Run a wallet recovery to find them.");
+        println!("=========================");
+        println!();
+    }
+
     if let Some(file_name) = seed_words_file_name {
         let seed_words = wallet.get_seed_words(&MnemonicLanguage::English)?.join(" ");
         let _result = fs::write(file_name, seed_words.reveal()).map_err(|e| {
@@ -644,6 +666,60 @@ pub async fn start_wallet(
     Ok(())
 }
 
+/// Decrypts and validates a wallet backup and writes the wallet database it contains to the configured database file.
+/// The passphrase must be the wallet passphrase at the time the backup was created.
+pub fn restore_backup(
+    backup_file: &Path,
+    wallet_config: &WalletConfig,
+    passphrase: &SafePassword,
+) -> Result<(), ExitError> {
+    let archive = fs::read(backup_file).map_err(|e| {
+        ExitError::new(
+            ExitCode::IOError,
+            format!("Could not read wallet backup {}: {}", backup_file.display(), e),
+        )
+    })?;
+    let database = restore_wallet_backup(&archive, passphrase)?;
+
+    let db_path = &wallet_config.db_file;
+    fs::create_dir_all(
+        db_path
+            .parent()
+            .expect("console_wallet_db_file cannot be set to a root directory"),
+    )
+    .map_err(|e| ExitError::new(ExitCode::WalletError, format!("Error creating Wallet folder. {}", e)))?;
+    fs::write(db_path, database)
+        .map_err(|e| ExitError::new(ExitCode::IOError, format!("Could not write wallet database: {}", e)))?;
+
+    // Opening the database runs any pending migrations and checks the passphrase
+    if let Err(e) = initialize_sqlite_database_backends(db_path, passphrase.clone(), 1) {
+        let _ignored = fs::remove_file(db_path);
+        return Err(e.into());
+    }
+    info!(
+        target: LOG_TARGET,
+        "Wallet restored from backup {}",
+        backup_file.display()
+    );
+    println!("Wallet restored from backup {}", backup_file.display());
+    Ok(())
+}
+
+/// Revalidates all outputs and transactions of a restored wallet against the base node, the backup may be out of date
+pub async fn revalidate_restored_wallet(wallet: &mut WalletSqlite) -> Result<(), ExitError> {
+    wallet
+        .output_manager_service
+        .revalidate_all_outputs()
+        .await
+        .map_err(|e| ExitError::new(ExitCode::WalletError, format!("Error revalidating outputs. {}", e)))?;
+    wallet
+        .transaction_service
+        .revalidate_all_transactions()
+        .await
+        .map_err(|e| ExitError::new(ExitCode::WalletError, format!("Error revalidating transactions. {}", e)))?;
+    Ok(())
+}
+
 async fn validate_txos(wallet: &mut WalletSqlite) -> Result<(), ExitError> {
     debug!(target: LOG_TARGET, "Starting TXO validations.");
 
@@ -742,6 +818,21 @@ pub fn tari_splash_screen(heading: &str) {
 fn boot(cli: &Cli, wallet_config: &WalletConfig) -> Result<WalletBoot, ExitError> {
     let wallet_exists = wallet_config.db_file.exists();
 
+    // restore from a backup, the wallet database is written from the backup before it is opened
+    if cli.restore_backup.is_some() {
+        if wallet_exists {
+            return Err(ExitError::new(
+                ExitCode::WalletError,
+                format!(
+                    "Wallet already exists at {:#?}. Remove it if you really want to restore a backup in this \
+                     directory!",
+                    wallet_config.db_file
+                ),
+            ));
+        }
+        return Ok(WalletBoot::Existing);
+    }
+
     // forced recovery
     if cli.recovery {
         if wallet_exists {
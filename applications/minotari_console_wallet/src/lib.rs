@@ -46,7 +46,16 @@ pub use cli::{
     SetBaseNodeArgs,
     WhoisArgs,
 };
-use init::{change_password, get_base_node_peer_config, init_wallet, start_wallet, tari_splash_screen, WalletBoot};
+use init::{
+    change_password,
+    get_base_node_peer_config,
+    init_wallet,
+    restore_backup,
+    revalidate_restored_wallet,
+    start_wallet,
+    tari_splash_screen,
+    WalletBoot,
+};
 use log::*;
 use minotari_app_utilities::{common_cli_args::CommonCliArgs, consts};
 use minotari_wallet::transaction_service::config::TransactionRoutingMechanism;
@@ -90,6 +99,7 @@ pub fn run_wallet(shutdown: &mut Shutdown, runtime: Runtime, config: &mut Applic
         recovery: false,
         seed_words: None,
         seed_words_file_name: None,
+        restore_backup: None,
         non_interactive_mode: true,
         input_file: None,
         command: None,
@@ -129,6 +139,10 @@ pub fn run_wallet_with_cli(
     // check for recovery based on existence of wallet file
     let (mut boot_mode, password) = boot_with_password(&cli, &config.wallet)?;
 
+    if let Some(backup_file) = cli.restore_backup.as_ref() {
+        restore_backup(backup_file, &config.wallet, &password)?;
+    }
+
     let wallet_type = prompt_wallet_type(
         boot_mode,
         &config.wallet,
@@ -224,6 +238,11 @@ pub fn run_wallet_with_cli(
     // start wallet
     runtime.block_on(start_wallet(&mut wallet, &base_node_selected, &wallet_mode))?;
 
+    // the backup may be out of date, so everything it contains is checked against the base node again
+    if cli.restore_backup.is_some() {
+        runtime.block_on(revalidate_restored_wallet(&mut wallet))?;
+    }
+
     debug!(target: LOG_TARGET, "Starting app");
 
     let handle = runtime.handle().clone();
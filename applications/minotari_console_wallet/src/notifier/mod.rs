@@ -39,6 +39,10 @@ use minotari_wallet::{
 use tari_common_types::transaction::TxId;
 use tari_utilities::hex::Hex;
 use tokio::{runtime::Handle, sync::broadcast::Sender};
+
+mod webhook;
+pub use webhook::WebhookNotifier;
+
 pub const LOG_TARGET: &str = "wallet::notifier";
 pub const RECEIVED: &str = "received";
 pub const SENT: &str = "sent";
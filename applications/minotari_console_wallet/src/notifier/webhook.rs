@@ -0,0 +1,687 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Delivers transaction events and balance changes to HTTP webhooks as signed JSON payloads. Events are written to a
+//! persistent outbox before they are sent so that they survive restarts, and failed deliveries are retried with
+//! exponential backoff. Delivery runs in its own task, so a slow or unreachable endpoint does not hold up event
+//! handling.
+
+use std::{
+    fs,
+    io,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use futures::future;
+use hmac::{Hmac, Mac};
+use log::*;
+use minotari_wallet::{
+    output_manager_service::service::Balance,
+    transaction_service::{handle::TransactionEvent, storage::models::CompletedTransaction},
+    WalletConfig,
+    WalletSqlite,
+};
+use rand::{rngs::OsRng, RngCore};
+use reqwest::header::CONTENT_TYPE;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::Sha256;
+use tari_shutdown::ShutdownSignal;
+use tari_utilities::{
+    hex::{to_hex, Hex},
+    SafePassword,
+};
+use tokio::{
+    sync::{broadcast, Notify},
+    time::{self, MissedTickBehavior},
+};
+
+use super::{CANCELLED, CONFIRMATION, MINED, RECEIVED};
+
+const LOG_TARGET: &str = "wallet::notifier::webhook";
+
+pub const BALANCE: &str = "balance";
+/// The header carrying the hex encoded HMAC-SHA256 signature of the request body
+pub const SIGNATURE_HEADER: &str = "X-Tari-Signature";
+
+const DELIVERY_INTERVAL: Duration = Duration::from_secs(1);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+const INITIAL_BACKOFF: Duration = Duration::from_secs(5);
+const MAX_BACKOFF: Duration = Duration::from_secs(60 * 60);
+
+/// A webhook payload waiting to be delivered to a single URL
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutboxEntry {
+    pub url: String,
+    pub body: String,
+    pub attempts: u32,
+    /// Unix timestamp in seconds of the earliest time the next delivery attempt may be made
+    pub next_attempt: u64,
+}
+
+impl OutboxEntry {
+    /// The body carries a random event id, so the URL and body identify an entry
+    fn is_same_delivery(&self, other: &OutboxEntry) -> bool {
+        self.url == other.url && self.body == other.body
+    }
+}
+
+/// The persistent queue of undelivered webhook payloads. Every change is written to disk straight away.
+#[derive(Debug)]
+pub struct WebhookOutbox {
+    path: PathBuf,
+    entries: Vec<OutboxEntry>,
+}
+
+impl WebhookOutbox {
+    /// Loads the outbox from `path`, an outbox that does not exist yet is empty
+    pub fn load(path: PathBuf) -> Result<Self, io::Error> {
+        let entries = if path.exists() {
+            serde_json::from_slice(&fs::read(&path)?).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?
+        } else {
+            Vec::new()
+        };
+        Ok(Self { path, entries })
+    }
+
+    pub fn entries(&self) -> &[OutboxEntry] {
+        &self.entries
+    }
+
+    pub fn push(&mut self, entry: OutboxEntry) -> Result<(), io::Error> {
+        self.entries.push(entry);
+        self.save()
+    }
+
+    /// Writes the outbox to a temporary file first so that a crash cannot leave a truncated outbox behind
+    fn save(&self) -> Result<(), io::Error> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp_path = self.path.with_extension("tmp");
+        let data = serde_json::to_vec(&self.entries).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(&tmp_path, data)?;
+        fs::rename(tmp_path, &self.path)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// Turns wallet events into webhook payloads and adds them to the outbox
+pub struct WebhookNotifier {
+    urls: Vec<String>,
+    outbox: Arc<Mutex<WebhookOutbox>>,
+    new_entries: Arc<Notify>,
+    delivery: Option<WebhookDelivery>,
+    wallet: WalletSqlite,
+    last_balance: Option<Balance>,
+}
+
+impl WebhookNotifier {
+    /// Returns `None` if no webhook URLs are configured
+    pub fn new(config: &WalletConfig, wallet: WalletSqlite) -> Result<Option<Self>, io::Error> {
+        if config.webhook_urls.is_empty() {
+            return Ok(None);
+        }
+        let outbox = WebhookOutbox::load(config.webhook_outbox_file.clone())?;
+        if !outbox.entries().is_empty() {
+            info!(
+                target: LOG_TARGET,
+                "{} undelivered webhook event(s) loaded from {}",
+                outbox.entries().len(),
+                outbox.path().display()
+            );
+        }
+        let delivery = WebhookDelivery::new(
+            config.webhook_urls.clone().into_vec(),
+            Arc::new(Mutex::new(outbox)),
+            config.webhook_secret.clone(),
+            config.webhook_max_attempts,
+        )?;
+        Ok(Some(Self {
+            urls: delivery.urls.clone(),
+            outbox: delivery.outbox.clone(),
+            new_entries: delivery.new_entries.clone(),
+            delivery: Some(delivery),
+            wallet,
+            last_balance: None,
+        }))
+    }
+
+    pub async fn run(mut self) {
+        let mut shutdown_signal = self.wallet.comms.shutdown_signal();
+        let mut transaction_events = self.wallet.transaction_service.get_event_stream();
+        let mut output_manager_events = self.wallet.output_manager_service.get_event_stream();
+        if let Some(delivery) = self.delivery.take() {
+            tokio::spawn(delivery.run(shutdown_signal.clone()));
+        }
+
+        self.last_balance = self.wallet.output_manager_service.get_balance().await.ok();
+        info!(target: LOG_TARGET, "Webhook notifier started for {} URL(s)", self.urls.len());
+        loop {
+            tokio::select! {
+                result = transaction_events.recv() => {
+                    match result {
+                        Ok(event) => {
+                            self.handle_transaction_event(&event).await;
+                            self.check_balance().await;
+                        },
+                        Err(broadcast::error::RecvError::Lagged(n)) => {
+                            warn!(target: LOG_TARGET, "Webhook notifier lagged {} transaction events", n);
+                        },
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    }
+                },
+                result = output_manager_events.recv() => {
+                    match result {
+                        Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => self.check_balance().await,
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    }
+                },
+                _ = shutdown_signal.wait() => break,
+            }
+        }
+        info!(target: LOG_TARGET, "Webhook notifier stopped");
+    }
+
+    async fn handle_transaction_event(&mut self, event: &TransactionEvent) {
+        let (event_name, tx_id, confirmations) = match *event {
+            TransactionEvent::ReceivedFinalizedTransaction(tx_id) => (RECEIVED, tx_id, None),
+            TransactionEvent::TransactionMinedUnconfirmed {
+                tx_id,
+                num_confirmations,
+                ..
+            } |
+            TransactionEvent::DetectedTransactionUnconfirmed {
+                tx_id,
+                num_confirmations,
+                ..
+            } => (CONFIRMATION, tx_id, Some(num_confirmations)),
+            TransactionEvent::TransactionMined { tx_id, .. } |
+            TransactionEvent::DetectedTransactionConfirmed { tx_id, .. } => (MINED, tx_id, None),
+            TransactionEvent::TransactionCancelled(tx_id, _) => (CANCELLED, tx_id, None),
+            _ => return,
+        };
+        match self.wallet.transaction_service.get_any_transaction(tx_id).await {
+            Ok(Some(tx)) => {
+                let data = transaction_data(&CompletedTransaction::from(tx), confirmations);
+                self.enqueue(event_name, data);
+            },
+            Ok(None) => error!(target: LOG_TARGET, "Transaction not found tx_id: {}", tx_id),
+            Err(e) => error!(target: LOG_TARGET, "Transaction service error: {}", e),
+        }
+    }
+
+    async fn check_balance(&mut self) {
+        match self.wallet.output_manager_service.get_balance().await {
+            Ok(balance) => {
+                if self.last_balance.as_ref() != Some(&balance) {
+                    self.enqueue(BALANCE, balance_data(&balance));
+                    self.last_balance = Some(balance);
+                }
+            },
+            Err(e) => error!(target: LOG_TARGET, "Output manager service error: {}", e),
+        }
+    }
+
+    fn enqueue(&mut self, event: &str, data: serde_json::Value) {
+        let body = payload(event, now(), data).to_string();
+        let mut outbox = self.outbox.lock().expect("webhook outbox lock poisoned");
+        for url in &self.urls {
+            let entry = OutboxEntry {
+                url: url.clone(),
+                body: body.clone(),
+                attempts: 0,
+                next_attempt: 0,
+            };
+            if let Err(e) = outbox.push(entry) {
+                error!(target: LOG_TARGET, "Could not persist webhook event '{}' for {}: {}", event, url, e);
+            }
+        }
+        self.new_entries.notify_waiters();
+    }
+}
+
+/// Delivers the entries of the outbox. Each URL has its own worker, so that a slow or unreachable endpoint does not
+/// delay delivery to the others. Entries for the same URL are delivered in the order they were queued.
+pub struct WebhookDelivery {
+    urls: Vec<String>,
+    outbox: Arc<Mutex<WebhookOutbox>>,
+    new_entries: Arc<Notify>,
+    secret: Option<SafePassword>,
+    max_attempts: u32,
+    client: reqwest::Client,
+}
+
+impl WebhookDelivery {
+    pub fn new(
+        urls: Vec<String>,
+        outbox: Arc<Mutex<WebhookOutbox>>,
+        secret: Option<SafePassword>,
+        max_attempts: u32,
+    ) -> Result<Self, io::Error> {
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        Ok(Self {
+            urls,
+            outbox,
+            new_entries: Arc::new(Notify::new()),
+            secret,
+            max_attempts: max_attempts.max(1),
+            client,
+        })
+    }
+
+    /// Runs a worker for every configured URL, and for any other URL that undelivered entries were loaded for
+    pub async fn run(self, shutdown_signal: ShutdownSignal) {
+        let mut urls = self.urls.clone();
+        for entry in self.lock_outbox().entries() {
+            if !urls.contains(&entry.url) {
+                urls.push(entry.url.clone());
+            }
+        }
+        let delivery = Arc::new(self);
+        future::join_all(
+            urls.into_iter()
+                .map(|url| tokio::spawn(delivery.clone().run_for_url(url, shutdown_signal.clone()))),
+        )
+        .await;
+        debug!(target: LOG_TARGET, "Webhook delivery stopped");
+    }
+
+    async fn run_for_url(self: Arc<Self>, url: String, mut shutdown_signal: ShutdownSignal) {
+        let mut interval = time::interval(DELIVERY_INTERVAL);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {},
+                _ = self.new_entries.notified() => {},
+                _ = shutdown_signal.wait() => break,
+            }
+            tokio::select! {
+                _ = self.deliver_due(&url) => {},
+                _ = shutdown_signal.wait() => break,
+            }
+        }
+    }
+
+    /// Attempts delivery of the outbox entries for `url` in order, up to the first entry that is waiting out its
+    /// backoff. After a failure the remaining entries are left for the next round, so an unreachable endpoint costs one
+    /// request timeout per round.
+    async fn deliver_due(&self, url: &str) {
+        let now = now();
+        let due = self
+            .lock_outbox()
+            .entries()
+            .iter()
+            .filter(|e| e.url == url)
+            .take_while(|e| e.next_attempt <= now)
+            .cloned()
+            .collect::<Vec<_>>();
+        for entry in due {
+            let result = self.post(&entry).await;
+            let failed = result.is_err();
+            self.record_attempt(entry, result, now);
+            if failed {
+                break;
+            }
+        }
+    }
+
+    /// Entries are removed once delivered or after the maximum number of attempts, all others are rescheduled with
+    /// exponential backoff
+    fn record_attempt(&self, mut entry: OutboxEntry, result: Result<(), String>, now: u64) {
+        let mut outbox = self.lock_outbox();
+        let Some(pos) = outbox.entries.iter().position(|e| e.is_same_delivery(&entry)) else {
+            return;
+        };
+        match result {
+            Ok(()) => {
+                debug!(target: LOG_TARGET, "Webhook event delivered to {}", entry.url);
+                outbox.entries.remove(pos);
+            },
+            Err(e) => {
+                entry.attempts += 1;
+                if entry.attempts >= self.max_attempts {
+                    error!(
+                        target: LOG_TARGET,
+                        "Dropping webhook event for {} after {} attempts: {}. Payload: {}",
+                        entry.url,
+                        entry.attempts,
+                        e,
+                        entry.body
+                    );
+                    outbox.entries.remove(pos);
+                } else {
+                    let backoff = backoff(entry.attempts);
+                    warn!(
+                        target: LOG_TARGET,
+                        "Webhook delivery to {} failed (attempt {}), retrying in {}s: {}",
+                        entry.url,
+                        entry.attempts,
+                        backoff.as_secs(),
+                        e
+                    );
+                    entry.next_attempt = now + backoff.as_secs();
+                    outbox.entries[pos] = entry;
+                }
+            },
+        }
+        if let Err(e) = outbox.save() {
+            error!(target: LOG_TARGET, "Could not persist webhook outbox: {}", e);
+        }
+    }
+
+    async fn post(&self, entry: &OutboxEntry) -> Result<(), String> {
+        let mut request = self
+            .client
+            .post(&entry.url)
+            .header(CONTENT_TYPE, "application/json")
+            .body(entry.body.clone());
+        if let Some(secret) = &self.secret {
+            request = request.header(SIGNATURE_HEADER, sign(secret.reveal(), entry.body.as_bytes()));
+        }
+        let response = request.send().await.map_err(|e| e.to_string())?;
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(format!("HTTP status {}", response.status()))
+        }
+    }
+
+    fn lock_outbox(&self) -> MutexGuard<'_, WebhookOutbox> {
+        self.outbox.lock().expect("webhook outbox lock poisoned")
+    }
+}
+
+/// The hex encoded HMAC-SHA256 of `body` keyed with `secret`
+pub fn sign(secret: &[u8], body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC can take a key of any size");
+    mac.update(body);
+    to_hex(&mac.finalize().into_bytes())
+}
+
+/// The delay before the next delivery attempt after `attempts` failed attempts
+pub fn backoff(attempts: u32) -> Duration {
+    INITIAL_BACKOFF
+        .saturating_mul(2u32.saturating_pow(attempts.saturating_sub(1)))
+        .min(MAX_BACKOFF)
+}
+
+fn payload(event: &str, timestamp: u64, data: serde_json::Value) -> serde_json::Value {
+    let mut id = [0u8; 16];
+    OsRng.fill_bytes(&mut id);
+    json!({
+        "id": to_hex(&id),
+        "event": event,
+        "timestamp": timestamp,
+        "data": data,
+    })
+}
+
+fn transaction_data(tx: &CompletedTransaction, confirmations: Option<u64>) -> serde_json::Value {
+    json!({
+        "tx_id": tx.tx_id.as_u64(),
+        "amount": tx.amount.as_u64(),
+        "fee": tx.fee.as_u64(),
+        "source_address": tx.source_address.to_base58(),
+        "destination_address": tx.destination_address.to_base58(),
+        "message": tx.message,
+        "payment_id": tx.payment_id.as_ref().map(|p| p.to_string()),
+        "status": tx.status.to_string(),
+        "direction": tx.direction.to_string(),
+        "cancelled": tx.cancelled.is_some(),
+        "excess": tx.transaction.body.kernels().first().map(|k| k.excess.to_hex()),
+        "mined_height": tx.mined_height,
+        "confirmations": confirmations.or(tx.confirmations),
+    })
+}
+
+fn balance_data(balance: &Balance) -> serde_json::Value {
+    json!({
+        "available": balance.available_balance.as_u64(),
+        "time_locked": balance.time_locked_balance.map(|b| b.as_u64()),
+        "pending_incoming": balance.pending_incoming_balance.as_u64(),
+        "pending_outgoing": balance.pending_outgoing_balance.as_u64(),
+    })
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod test {
+    use tari_shutdown::Shutdown;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+        sync::mpsc,
+    };
+
+    use super::*;
+
+    /// Starts an HTTP endpoint that answers every request with `status`, or never answers if `status` is `None`.
+    /// Returns the URL of the endpoint and a receiver of the requests it was sent.
+    async fn endpoint(status: Option<&'static str>) -> (String, mpsc::UnboundedReceiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/webhook", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let tx = tx.clone();
+                tokio::spawn(async move {
+                    let mut buf = vec![0u8; 64 * 1024];
+                    let n = socket.read(&mut buf).await.unwrap();
+                    let _ = tx.send(String::from_utf8_lossy(&buf[..n]).to_string());
+                    match status {
+                        Some(status) => {
+                            let response =
+                                format!("HTTP/1.1 {}\r\ncontent-length: 0\r\nconnection: close\r\n\r\n", status);
+                            socket.write_all(response.as_bytes()).await.unwrap();
+                        },
+                        None => future::pending::<()>().await,
+                    }
+                });
+            }
+        });
+        (url, rx)
+    }
+
+    fn outbox_with_entries(urls: &[&str]) -> Arc<Mutex<WebhookOutbox>> {
+        let path = std::env::temp_dir().join(format!("webhook_outbox_{}.json", OsRng.next_u64()));
+        let mut outbox = WebhookOutbox::load(path).unwrap();
+        for url in urls {
+            outbox
+                .push(OutboxEntry {
+                    url: url.to_string(),
+                    body: payload(BALANCE, 1, json!({"available": 1})).to_string(),
+                    attempts: 0,
+                    next_attempt: 0,
+                })
+                .unwrap();
+        }
+        Arc::new(Mutex::new(outbox))
+    }
+
+    #[test]
+    fn it_signs_with_hmac_sha256() {
+        // RFC 4231 test case 2
+        assert_eq!(
+            sign(b"Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn it_backs_off_exponentially() {
+        assert_eq!(backoff(1), Duration::from_secs(5));
+        assert_eq!(backoff(2), Duration::from_secs(10));
+        assert_eq!(backoff(4), Duration::from_secs(40));
+        assert_eq!(backoff(20), MAX_BACKOFF);
+        assert_eq!(backoff(u32::MAX), MAX_BACKOFF);
+    }
+
+    #[test]
+    fn it_persists_the_outbox() {
+        let path = std::env::temp_dir().join(format!("webhook_outbox_{}.json", OsRng.next_u64()));
+        let mut outbox = WebhookOutbox::load(path.clone()).unwrap();
+        assert!(outbox.entries().is_empty());
+        let entry = OutboxEntry {
+            url: "http://localhost/webhook".to_string(),
+            body: payload(BALANCE, 1, json!({"available": 1})).to_string(),
+            attempts: 2,
+            next_attempt: 3,
+        };
+        outbox.push(entry.clone()).unwrap();
+
+        let outbox = WebhookOutbox::load(path.clone()).unwrap();
+        assert_eq!(outbox.entries(), &[entry]);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn it_delivers_past_a_slow_endpoint() {
+        let (slow_url, mut slow_requests) = endpoint(None).await;
+        let (url, mut requests) = endpoint(Some("200 OK")).await;
+        let outbox = outbox_with_entries(&[&slow_url, &slow_url, &url]);
+        let delivery = WebhookDelivery::new(vec![slow_url.clone(), url.clone()], outbox.clone(), None, 3).unwrap();
+        let new_entries = delivery.new_entries.clone();
+        let shutdown = Shutdown::new();
+        tokio::spawn(delivery.run(shutdown.to_signal()));
+
+        // The slow endpoint does not answer within the request timeout, which must not hold up the other endpoint
+        time::timeout(Duration::from_secs(5), requests.recv())
+            .await
+            .expect("the webhook was not delivered")
+            .unwrap();
+        slow_requests.recv().await.unwrap();
+
+        // Events queued while the slow endpoint is still outstanding are delivered to the other endpoint too
+        outbox
+            .lock()
+            .unwrap()
+            .push(OutboxEntry {
+                url: url.clone(),
+                body: payload(BALANCE, 2, json!({"available": 2})).to_string(),
+                attempts: 0,
+                next_attempt: 0,
+            })
+            .unwrap();
+        new_entries.notify_waiters();
+        time::timeout(Duration::from_secs(5), requests.recv())
+            .await
+            .expect("the queued webhook was not delivered")
+            .unwrap();
+        time::timeout(Duration::from_secs(5), async {
+            while outbox.lock().unwrap().entries().iter().any(|e| e.url == url) {
+                time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("the delivered entries were not removed");
+
+        // Only the first entry for the slow endpoint is in flight, the second waits for it
+        assert!(slow_requests.try_recv().is_err());
+        let outbox = outbox.lock().unwrap();
+        assert_eq!(outbox.entries().len(), 2);
+        assert!(outbox.entries().iter().all(|e| e.url == slow_url && e.attempts == 0));
+        fs::remove_file(outbox.path()).ok();
+    }
+
+    #[tokio::test]
+    async fn it_reschedules_failed_deliveries() {
+        let (url, mut requests) = endpoint(Some("500 Internal Server Error")).await;
+        let outbox = outbox_with_entries(&[&url]);
+        let delivery =
+            WebhookDelivery::new(vec![url.clone()], outbox.clone(), Some(SafePassword::from("secret")), 2).unwrap();
+
+        delivery.deliver_due(&url).await;
+        let request = requests.recv().await.unwrap();
+        assert!(request.to_lowercase().contains(&SIGNATURE_HEADER.to_lowercase()));
+        let path = outbox.lock().unwrap().path().to_path_buf();
+        let persisted = WebhookOutbox::load(path.clone()).unwrap();
+        assert_eq!(persisted.entries().len(), 1);
+        assert_eq!(persisted.entries()[0].attempts, 1);
+        assert!(persisted.entries()[0].next_attempt > now());
+
+        // The entry is not retried before its backoff has passed, and is dropped after the last attempt
+        delivery.deliver_due(&url).await;
+        assert!(requests.try_recv().is_err());
+        outbox.lock().unwrap().entries[0].next_attempt = 0;
+        delivery.deliver_due(&url).await;
+        requests.recv().await.unwrap();
+        assert!(WebhookOutbox::load(path.clone()).unwrap().entries().is_empty());
+        fs::remove_file(path).ok();
+    }
+
+    #[tokio::test]
+    async fn it_does_not_deliver_past_an_entry_that_is_backing_off() {
+        let (url, mut requests) = endpoint(Some("200 OK")).await;
+        let outbox = outbox_with_entries(&[&url]);
+        outbox
+            .lock()
+            .unwrap()
+            .push(OutboxEntry {
+                url: url.clone(),
+                body: payload(BALANCE, 2, json!({"available": 2})).to_string(),
+                attempts: 0,
+                next_attempt: 0,
+            })
+            .unwrap();
+        let bodies = outbox
+            .lock()
+            .unwrap()
+            .entries()
+            .iter()
+            .map(|e| e.body.clone())
+            .collect::<Vec<_>>();
+        let delivery =
+            WebhookDelivery::new(vec![url.clone()], outbox.clone(), Some(SafePassword::from("secret")), 3).unwrap();
+
+        // The later entry waits for the first one to be delivered
+        outbox.lock().unwrap().entries[0].next_attempt = now() + 60;
+        delivery.deliver_due(&url).await;
+        assert!(requests.try_recv().is_err());
+        assert_eq!(outbox.lock().unwrap().entries().len(), 2);
+
+        outbox.lock().unwrap().entries[0].next_attempt = 0;
+        delivery.deliver_due(&url).await;
+        // The signature header identifies the body that was sent
+        for body in bodies {
+            let request = requests.recv().await.unwrap();
+            assert!(request.contains(&sign(b"secret", body.as_bytes())));
+        }
+        assert!(outbox.lock().unwrap().entries().is_empty());
+        fs::remove_file(outbox.lock().unwrap().path()).ok();
+    }
+}
@@ -100,21 +100,25 @@ impl<B: Backend> App<B> {
             wallet_config,
         );
 
-        let tabs = TabsContainer::<B>::new(title.clone())
-            .add("Transactions".into(), Box::new(TransactionsTab::new()))
-            .add(
-                "Send".into(),
-                Box::new(SendTab::new(
-                    &app_state,
-                    app_state
-                        .get_wallet_type()
-                        .await
-                        .map_err(|e| ExitError::new(ExitCode::WalletError, e))?,
-                )),
-            )
-            .add("Receive".into(), Box::new(ReceiveTab::new()))
-            .add("Burn".into(), Box::new(BurnTab::new(&app_state)))
-            .add("Templates".into(), Box::new(RegisterTemplateTab::new(&app_state)))
+        let wallet_type = app_state
+            .get_wallet_type()
+            .await
+            .map_err(|e| ExitError::new(ExitCode::WalletError, e))?;
+        // A watch-only wallet cannot sign transactions, so the tabs that spend funds are left out
+        let watch_only = wallet_type.is_watch_only();
+
+        let mut tabs =
+            TabsContainer::<B>::new(title.clone()).add("Transactions".into(), Box::new(TransactionsTab::new()));
+        if !watch_only {
+            tabs = tabs.add("Send".into(), Box::new(SendTab::new(&app_state, wallet_type)));
+        }
+        tabs = tabs.add("Receive".into(), Box::new(ReceiveTab::new()));
+        if !watch_only {
+            tabs = tabs
+                .add("Burn".into(), Box::new(BurnTab::new(&app_state)))
+                .add("Templates".into(), Box::new(RegisterTemplateTab::new(&app_state)));
+        }
+        let tabs = tabs
             .add("Contacts".into(), Box::new(ContactsTab::new()))
             .add("Network".into(), Box::new(NetworkTab::new(base_node_selected)))
             .add("Events".into(), Box::new(EventsComponent::new()))
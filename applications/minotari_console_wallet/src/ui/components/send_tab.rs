@@ -1,9 +1,14 @@
 // Copyright 2022 The Tari Project
 // SPDX-License-Identifier: BSD-3-Clause
 
+use std::{
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
 use log::*;
 use minotari_wallet::output_manager_service::UtxoSelectionCriteria;
-use tari_common_types::wallet_types::WalletType;
+use tari_common_types::{payment_request::PaymentRequest, wallet_types::WalletType};
 use tari_core::transactions::tari_amount::MicroMinotari;
 use tari_utilities::hex::Hex;
 use tokio::{runtime::Handle, sync::watch};
@@ -139,7 +144,7 @@ impl SendTab {
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .title("(T)o (Tari Address or Emoji ID) :"),
+                    .title("(T)o (Tari Address, Emoji ID or Payment Request) :"),
             );
         f.render_widget(to_input, vert_chunks[1]);
 
@@ -361,7 +366,10 @@ impl SendTab {
             match self.send_input_mode {
                 SendInputMode::None => (),
                 SendInputMode::To => match c {
-                    '\n' => self.send_input_mode = SendInputMode::Amount,
+                    '\n' => {
+                        self.send_input_mode = SendInputMode::Amount;
+                        self.apply_payment_request();
+                    },
                     c => {
                         self.to_field.push(c);
                         return KeyHandled::Handled;
@@ -413,6 +421,50 @@ impl SendTab {
         KeyHandled::NotHandled
     }
 
+    /// If the To field holds a payment request URI, fills in the send form from the request
+    fn apply_payment_request(&mut self) {
+        if !self.to_field.trim().starts_with("tari://") {
+            return;
+        }
+        let request = match PaymentRequest::from_str(&self.to_field) {
+            Ok(request) => request,
+            // A plain address link is handled by the address parser when sending
+            Err(_) if !self.to_field.contains("amount=") => return,
+            Err(e) => {
+                self.error_message = Some(format!("Invalid payment request: {}\nPress Enter to continue.", e));
+                return;
+            },
+        };
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        if request.is_expired_at(now) {
+            self.error_message = Some("Payment request has expired\nPress Enter to continue.".to_string());
+            return;
+        }
+        let payment_id = match String::from_utf8(request.payment_id().to_vec()) {
+            Ok(payment_id) => payment_id,
+            Err(_) => {
+                self.error_message =
+                    Some("Payment request has an unsupported payment id\nPress Enter to continue.".to_string());
+                return;
+            },
+        };
+        debug!(
+            target: LOG_TARGET,
+            "Filling send form from payment request to {} (signed: {})",
+            request.address(),
+            request.has_valid_signature()
+        );
+        self.to_field = request.address().to_base58();
+        self.amount_field = request.amount().to_string();
+        self.message_field = request.memo().unwrap_or_default().to_string();
+        self.payment_id_field = payment_id;
+        self.selected_unique_id = None;
+        self.send_input_mode = SendInputMode::None;
+    }
+
     fn on_key_show_contacts(&mut self, c: char, app_state: &mut AppState) -> KeyHandled {
         if self.show_contacts && c == '\n' {
             if let Some(c) = self
@@ -604,6 +656,16 @@ impl<B: Backend> Component<B> for SendTab {
                         Some("Amount should be a valid amount of Minotari\nPress Enter to continue.".to_string());
                     return;
                 }
+                // Only one-sided transactions carry a payment id, so an interactive send would not be matched to the
+                // payment request that filled in the form
+                if c == 's' && !self.payment_id_field.is_empty() {
+                    self.error_message = Some(
+                        "A payment id is only sent with a one-sided transaction, press O to send\nPress Enter to \
+                         continue."
+                            .to_string(),
+                    );
+                    return;
+                }
 
                 self.confirmation_dialog = Some(match c {
                     'o' => ConfirmationDialogType::StealthAddress,
@@ -26,21 +26,24 @@ use std::{fs, io::Stdout, path::PathBuf};
 
 use clap::Parser;
 use log::*;
-use minotari_app_grpc::{authentication::ServerAuthenticationInterceptor, tls::identity::read_identity};
+use minotari_app_grpc::{
+    authentication::{GrpcMethodService, ServerAuthenticationInterceptor},
+    tls::identity::read_server_tls_config,
+};
 use minotari_wallet::{WalletConfig, WalletSqlite};
 use rand::{rngs::OsRng, seq::SliceRandom};
 use tari_common::exit_codes::{ExitCode, ExitError};
 use tari_common_types::grpc_authentication::GrpcAuthentication;
 use tari_comms::{multiaddr::Multiaddr, peer_manager::Peer, utils::multiaddr::multiaddr_to_socketaddr};
 use tokio::{runtime::Handle, sync::broadcast};
-use tonic::transport::{Identity, Server, ServerTlsConfig};
+use tonic::transport::{Server, ServerTlsConfig};
 use tui::backend::CrosstermBackend;
 
 use crate::{
     automation::commands::command_runner,
     cli::{Cli, CliCommands},
-    grpc::WalletGrpcServer,
-    notifier::Notifier,
+    grpc::{required_scope, WalletGrpcServer},
+    notifier::{Notifier, WebhookNotifier},
     recovery::wallet_recovery,
     ui,
     ui::App,
@@ -330,23 +333,20 @@ pub fn tui_mode(
                 details: Some(e.to_string()),
             })?;
 
-            let mut tls_identity = None;
-            if config.grpc_tls_enabled {
-                match handle
-                    .block_on(read_identity(config.config_dir.clone()))
-                    .map(Some)
-                    .map_err(|e| ExitError::new(ExitCode::TlsConfigurationError, e.to_string()))
-                {
-                    Ok(identity) => tls_identity = identity,
-                    Err(e) => return Err(e),
-                }
-            }
+            let tls_config = handle
+                .block_on(read_server_tls_config(
+                    config.config_dir.clone(),
+                    "wallet_ca.pem",
+                    &config.grpc_authentication,
+                    config.grpc_tls_enabled,
+                ))
+                .map_err(|e| ExitError::new(ExitCode::TlsConfigurationError, e.to_string()))?;
 
             handle.spawn(run_grpc(
                 grpc,
                 address,
                 config.grpc_authentication.clone(),
-                tls_identity,
+                tls_config,
                 wallet.clone(),
             ));
         }
@@ -357,6 +357,8 @@ pub fn tui_mode(
         ));
     }
 
+    start_webhook_notifier(&handle, config, &wallet)?;
+
     let notifier = Notifier::new(
         config.notify_file.clone(),
         handle.clone(),
@@ -449,6 +451,7 @@ pub fn recovery_mode(
 }
 
 pub fn grpc_mode(handle: Handle, config: &WalletConfig, wallet: WalletSqlite) -> Result<(), ExitError> {
+    start_webhook_notifier(&handle, config, &wallet)?;
     info!(target: LOG_TARGET, "Starting grpc server");
     if let Some(address) = config.grpc_address.as_ref().filter(|_| config.grpc_enabled).cloned() {
         #[cfg(feature = "grpc")]
@@ -459,20 +462,17 @@ pub fn grpc_mode(handle: Handle, config: &WalletConfig, wallet: WalletSqlite) ->
             })?;
             let auth = config.grpc_authentication.clone();
 
-            let mut tls_identity = None;
-            if config.grpc_tls_enabled {
-                match handle
-                    .block_on(read_identity(config.config_dir.clone()))
-                    .map(Some)
-                    .map_err(|e| ExitError::new(ExitCode::TlsConfigurationError, e.to_string()))
-                {
-                    Ok(identity) => tls_identity = identity,
-                    Err(e) => return Err(e),
-                }
-            }
+            let tls_config = handle
+                .block_on(read_server_tls_config(
+                    config.config_dir.clone(),
+                    "wallet_ca.pem",
+                    &config.grpc_authentication,
+                    config.grpc_tls_enabled,
+                ))
+                .map_err(|e| ExitError::new(ExitCode::TlsConfigurationError, e.to_string()))?;
 
             handle
-                .block_on(run_grpc(grpc, address, auth, tls_identity, wallet))
+                .block_on(run_grpc(grpc, address, auth, tls_config, wallet))
                 .map_err(|e| ExitError::new(ExitCode::GrpcError, e))?;
         }
         #[cfg(not(feature = "grpc"))]
@@ -487,11 +487,24 @@ pub fn grpc_mode(handle: Handle, config: &WalletConfig, wallet: WalletSqlite) ->
     Ok(())
 }
 
+fn start_webhook_notifier(handle: &Handle, config: &WalletConfig, wallet: &WalletSqlite) -> Result<(), ExitError> {
+    let webhook_notifier = WebhookNotifier::new(config, wallet.clone()).map_err(|e| {
+        ExitError::new(
+            ExitCode::ConfigError,
+            format!("Could not start the webhook notifier: {}", e),
+        )
+    })?;
+    if let Some(webhook_notifier) = webhook_notifier {
+        handle.spawn(webhook_notifier.run());
+    }
+    Ok(())
+}
+
 async fn run_grpc(
     grpc: WalletGrpcServer,
     grpc_listener_addr: Multiaddr,
     auth_config: GrpcAuthentication,
-    tls_identity: Option<Identity>,
+    tls_config: Option<ServerTlsConfig>,
     wallet: WalletSqlite,
 ) -> Result<(), String> {
     // Do not remove this println!
@@ -501,13 +514,13 @@ async fn run_grpc(
     info!(target: LOG_TARGET, "Starting GRPC on {}", grpc_listener_addr);
     let address = multiaddr_to_socketaddr(&grpc_listener_addr).map_err(|e| e.to_string())?;
     let auth = ServerAuthenticationInterceptor::new(auth_config)
-        .ok_or("Unable to prepare server gRPC authentication".to_string())?;
-    let service = minotari_app_grpc::tari_rpc::wallet_server::WalletServer::with_interceptor(grpc, auth);
+        .ok_or("Unable to prepare server gRPC authentication".to_string())?
+        .with_method_scopes(required_scope);
+    let service =
+        GrpcMethodService::new(minotari_app_grpc::tari_rpc::wallet_server::WalletServer::with_interceptor(grpc, auth));
 
-    let mut server_builder = if let Some(identity) = tls_identity {
-        Server::builder()
-            .tls_config(ServerTlsConfig::new().identity(identity))
-            .map_err(|e| e.to_string())?
+    let mut server_builder = if let Some(tls_config) = tls_config {
+        Server::builder().tls_config(tls_config).map_err(|e| e.to_string())?
     } else {
         Server::builder()
     };
@@ -528,9 +541,14 @@ async fn run_grpc(
 
 #[cfg(test)]
 mod test {
-    use std::path::Path;
+    use std::{path::Path, time::Duration};
+
+    use tari_core::transactions::tari_amount::MicroMinotari;
 
-    use crate::{cli::CliCommands, wallet_modes::parse_command_file};
+    use crate::{
+        cli::{CliCommands, ExportFormat},
+        wallet_modes::parse_command_file,
+    };
 
     #[test]
     #[allow(clippy::too_many_lines)]
@@ -550,6 +568,8 @@ mod test {
             
             burn-minotari --message Ups_these_funds_will_be_burned! 100T
 
+            send-batch-one-sided --message Payroll --input-file ./payroll.csv
+
             pre-mine-spend-get-output-status
 
             pre-mine-spend-session-info --fee-per-gram 2 --output-index 123 --recipient-address \
@@ -576,8 +596,37 @@ mod test {
 
             export-tx 123456789 --output-file pie.txt
 
+            export-transactions --format json --from-date 2024-01-01T00:00:00Z --from-height 100 --output-file \
+             history.json
+
             import-tx --input-file pie_this_message.txt
 
+            backup --output-file wallet.backup
+
+            prepare-unsigned-transaction --output-file unsigned.json 10T \
+             f425UWsDp714RiN53c1G6ek57rfFnotB5NCMyrn4iDgbR8i2sXVHa4xSsedd66o9KmkRgErQnyDdCaAdNLzcKrj7eUb
+
+            sign-partially-signed-transaction --input-file pst.json --output-file signed.json
+
+            finalize-partially-signed-transaction --input-file signed.json
+
+            create-multisig-account --name treasury --threshold 2 --own-public-key \
+             5c4f2a4b3f3f84e047333218a84fd24f581a9d7e4f23b78e3714e9d174427d61 \
+             --cosigner-public-keys=f6b2ca781342a3ebe30ee1643655c96f1d7c14f4d49f077695395de98ae73665 \
+             --cosigner-public-keys=c69fbe5f05a304eaec65d5f234a6aa258a90b8bb5b9ceffea779653667ef2108
+
+            multisig-spend-party-details --input-file ./step_1_session_info.txt --alias alice
+
+            create-sub-address --label customer_42
+
+            set-spending-policy --daily-limit 1000T --large-send-threshold 100T --large-send-delay 3600
+
+            create-payment-proof 1234567890 --output-file proof.json
+
+            verify-payment-proof --input-file proof.json
+
+            create-payment-request 25T --payment-id order_42 --expires-in 86400
+
             # End of script file
             "
             .to_string();
@@ -587,6 +636,7 @@ mod test {
         let mut get_balance = false;
         let mut send_tari = false;
         let mut burn_tari = false;
+        let mut send_batch_one_sided = false;
         let mut pre_mine_spend_get_output_status = false;
         let mut pre_mine_spend_session_info = false;
         let mut pre_mine_spend_encumber_aggregate_utxo = false;
@@ -597,7 +647,19 @@ mod test {
         let mut coin_split = false;
         let mut discover_peer = false;
         let mut export_tx = false;
+        let mut export_transactions = false;
         let mut import_tx = false;
+        let mut backup = false;
+        let mut prepare_unsigned_transaction = false;
+        let mut sign_partially_signed_transaction = false;
+        let mut finalize_partially_signed_transaction = false;
+        let mut create_multisig_account = false;
+        let mut multisig_spend_party_details = false;
+        let mut create_sub_address = false;
+        let mut set_spending_policy = false;
+        let mut create_payment_proof = false;
+        let mut verify_payment_proof = false;
+        let mut create_payment_request = false;
         let mut whois = false;
         for command in commands {
             match command {
@@ -611,6 +673,11 @@ mod test {
                 CliCommands::PreMineSpendInputOutputSigs(_) => pre_mine_spend_input_output_sigs = true,
                 CliCommands::PreMineSpendAggregateTransaction(_) => pre_mine_spend_aggregate_transaction = true,
                 CliCommands::SendOneSidedToStealthAddress(_) => {},
+                CliCommands::SendBatchOneSided(args) => {
+                    if args.input_file == Path::new("./payroll.csv") && args.message == "Payroll" {
+                        send_batch_one_sided = true
+                    }
+                },
                 CliCommands::MakeItRain(_) => make_it_rain = true,
                 CliCommands::CoinSplit(_) => coin_split = true,
                 CliCommands::DiscoverPeer(_) => discover_peer = true,
@@ -621,6 +688,21 @@ mod test {
                         export_tx = true
                     }
                 },
+                CliCommands::ExportTransactions(args) => {
+                    if args.format == ExportFormat::Json &&
+                        args.from_height == Some(100) &&
+                        args.to_height.is_none() &&
+                        args.from_date.is_some() &&
+                        args.output_file == Some("history.json".into())
+                    {
+                        export_transactions = true
+                    }
+                },
+                CliCommands::Backup(args) => {
+                    if args.output_file == Path::new("wallet.backup") {
+                        backup = true
+                    }
+                },
                 CliCommands::ImportTx(args) => {
                     if args.input_file == Path::new("pie_this_message.txt") {
                         import_tx = true
@@ -628,6 +710,10 @@ mod test {
                 },
                 CliCommands::ExportSpentUtxos(_) => {},
                 CliCommands::CountUtxos => {},
+                CliCommands::ListOutputs(_) => {},
+                CliCommands::FreezeOutput(_) => {},
+                CliCommands::UnfreezeOutput(_) => {},
+                CliCommands::LabelOutput(_) => {},
                 CliCommands::SetBaseNode(_) => {},
                 CliCommands::SetCustomBaseNode(_) => {},
                 CliCommands::ClearCustomBaseNode => {},
@@ -640,12 +726,70 @@ mod test {
                 CliCommands::PreMineSpendBackupUtxo(_) => {},
                 CliCommands::Sync(_) => {},
                 CliCommands::ExportViewKeyAndSpendKey(_) => {},
+                CliCommands::PrepareUnsignedTransaction(args) => {
+                    if args.output_file == Path::new("unsigned.json") && args.amount == MicroMinotari::from(10_000_000)
+                    {
+                        prepare_unsigned_transaction = true
+                    }
+                },
+                CliCommands::CreatePartiallySignedTransaction(_) => {},
+                CliCommands::SignPartiallySignedTransaction(args) => {
+                    if args.input_file == Path::new("pst.json") && args.output_file == Path::new("signed.json") {
+                        sign_partially_signed_transaction = true
+                    }
+                },
+                CliCommands::FinalizePartiallySignedTransaction(args) => {
+                    if args.input_file == Path::new("signed.json") {
+                        finalize_partially_signed_transaction = true
+                    }
+                },
+                CliCommands::NewMultisigAccountKey => {},
+                CliCommands::CreateMultisigAccount(args) => {
+                    if args.name == "treasury" && args.threshold == 2 && args.cosigner_public_keys.len() == 2 {
+                        create_multisig_account = true
+                    }
+                },
+                CliCommands::ListMultisigAccounts => {},
+                CliCommands::SendToMultisigAccount(_) => {},
+                CliCommands::MultisigSpendSessionInfo(_) => {},
+                CliCommands::MultisigSpendPartyDetails(args) => {
+                    if args.alias == "alice" {
+                        multisig_spend_party_details = true
+                    }
+                },
+                CliCommands::CreateSubAddress(args) => {
+                    if args.label == "customer_42" {
+                        create_sub_address = true
+                    }
+                },
+                CliCommands::ListSubAddresses => {},
+                CliCommands::GetSpendingPolicy => {},
+                CliCommands::SetSpendingPolicy(args) => {
+                    if args.daily_limit == Some(MicroMinotari::from(1_000_000_000)) &&
+                        args.large_send_delay == Duration::from_secs(3600)
+                    {
+                        set_spending_policy = true
+                    }
+                },
+                CliCommands::CreatePaymentProof(args) => {
+                    if args.tx_id == 1234567890 {
+                        create_payment_proof = true
+                    }
+                },
+                CliCommands::CreatePaymentRequest(args) => {
+                    if args.payment_id == "order_42" && args.expires_in == Some(Duration::from_secs(86400)) {
+                        create_payment_request = true
+                    }
+                },
+                CliCommands::ListPaymentRequests => {},
+                CliCommands::VerifyPaymentProof(_) => verify_payment_proof = true,
             }
         }
         assert!(
             get_balance &&
                 send_tari &&
                 burn_tari &&
+                send_batch_one_sided &&
                 pre_mine_spend_get_output_status &&
                 pre_mine_spend_session_info &&
                 pre_mine_spend_encumber_aggregate_utxo &&
@@ -657,7 +801,19 @@ mod test {
                 discover_peer &&
                 whois &&
                 export_tx &&
-                import_tx
+                export_transactions &&
+                import_tx &&
+                backup &&
+                prepare_unsigned_transaction &&
+                sign_partially_signed_transaction &&
+                finalize_partially_signed_transaction &&
+                create_multisig_account &&
+                multisig_spend_party_details &&
+                create_sub_address &&
+                set_spending_policy &&
+                create_payment_proof &&
+                verify_payment_proof &&
+                create_payment_request
         );
     }
 }
@@ -107,6 +107,8 @@ pub enum Branch {
     Spend = 0x07,
     RandomKey = 0x08,
     PreMine = 0x09,
+    Multisig = 0x0a,
+    SubAddress = 0x0b,
 }
 
 impl Branch {
@@ -126,6 +128,8 @@ impl Branch {
             0x07 => Some(Branch::Spend),
             0x08 => Some(Branch::RandomKey),
             0x09 => Some(Branch::PreMine),
+            0x0a => Some(Branch::Multisig),
+            0x0b => Some(Branch::SubAddress),
             _ => None,
         }
     }
@@ -292,6 +296,8 @@ mod test {
             (0x07, Branch::Spend),
             (0x08, Branch::RandomKey),
             (0x09, Branch::PreMine),
+            (0x0a, Branch::Multisig),
+            (0x0b, Branch::SubAddress),
         ];
 
         for (expected_byte, branch) in &mappings {
@@ -336,6 +342,14 @@ mod test {
                     assert_eq!(branch.as_byte(), *expected_byte);
                     assert_eq!(Branch::from_byte(*expected_byte), Some(*branch));
                 },
+                Branch::Multisig => {
+                    assert_eq!(branch.as_byte(), *expected_byte);
+                    assert_eq!(Branch::from_byte(*expected_byte), Some(*branch));
+                },
+                Branch::SubAddress => {
+                    assert_eq!(branch.as_byte(), *expected_byte);
+                    assert_eq!(Branch::from_byte(*expected_byte), Some(*branch));
+                },
             }
         }
     }
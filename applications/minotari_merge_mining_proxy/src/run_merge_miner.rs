@@ -25,7 +25,7 @@ use std::{convert::Infallible, str::FromStr};
 use futures::future;
 use hyper::{service::make_service_fn, Server};
 use log::*;
-use minotari_app_grpc::tls::protocol_string;
+use minotari_app_grpc::tls::{identity::read_client_identity, protocol_string};
 use minotari_app_utilities::parse_miner_input::{
     base_node_socket_address,
     verify_base_node_grpc_mining_responses,
@@ -158,7 +158,13 @@ async fn connect_base_node(config: &MergeMiningProxyConfig) -> Result<BaseNodeGr
             .map_err(|e| MmProxyError::TlsConnectionError(e.to_string()))?;
         let ca = Certificate::from_pem(pem);
 
-        let tls = ClientTlsConfig::new().ca_certificate(ca).domain_name(domain_name);
+        let mut tls = ClientTlsConfig::new().ca_certificate(ca).domain_name(domain_name);
+        if config.base_node_grpc_authentication.requires_client_certificate() {
+            let identity = read_client_identity(config.config_dir.clone())
+                .await
+                .map_err(|e| MmProxyError::TlsConnectionError(e.to_string()))?;
+            tls = tls.identity(identity);
+        }
         endpoint = endpoint
             .tls_config(tls)
             .map_err(|e| MmProxyError::TlsConnectionError(e.to_string()))?;
@@ -35,7 +35,7 @@ use minotari_app_grpc::{
         SubmitBlockResponse,
         TransactionOutput as GrpcTransactionOutput,
     },
-    tls::protocol_string,
+    tls::{identity::read_client_identity, protocol_string},
 };
 use minotari_app_utilities::parse_miner_input::{
     base_node_socket_address,
@@ -288,7 +288,13 @@ async fn connect_sha_p2pool(config: &MinerConfig) -> Result<ShaP2PoolGrpcClient,
             .map_err(|e| MinerError::TlsConnectionError(e.to_string()))?;
         let ca = Certificate::from_pem(pem);
 
-        let tls = ClientTlsConfig::new().ca_certificate(ca).domain_name(domain_name);
+        let mut tls = ClientTlsConfig::new().ca_certificate(ca).domain_name(domain_name);
+        if config.base_node_grpc_authentication.requires_client_certificate() {
+            let identity = read_client_identity(config.config_dir.clone())
+                .await
+                .map_err(|e| MinerError::TlsConnectionError(e.to_string()))?;
+            tls = tls.identity(identity);
+        }
         endpoint = endpoint
             .tls_config(tls)
             .map_err(|e| MinerError::TlsConnectionError(e.to_string()))?;
@@ -323,7 +329,13 @@ async fn connect_base_node(config: &MinerConfig) -> Result<BaseNodeGrpcClient, M
             .map_err(|e| MinerError::TlsConnectionError(e.to_string()))?;
         let ca = Certificate::from_pem(pem);
 
-        let tls = ClientTlsConfig::new().ca_certificate(ca).domain_name(domain_name);
+        let mut tls = ClientTlsConfig::new().ca_certificate(ca).domain_name(domain_name);
+        if config.base_node_grpc_authentication.requires_client_certificate() {
+            let identity = read_client_identity(config.config_dir.clone())
+                .await
+                .map_err(|e| MinerError::TlsConnectionError(e.to_string()))?;
+            tls = tls.identity(identity);
+        }
         endpoint = endpoint
             .tls_config(tls)
             .map_err(|e| MinerError::TlsConnectionError(e.to_string()))?;
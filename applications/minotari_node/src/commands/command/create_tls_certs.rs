@@ -41,12 +41,15 @@ impl HandleCommand<Args> for CommandContext {
 impl CommandContext {
     pub fn create_tls_certs(&self) -> Result<(), Error> {
         match generate_self_signed_certs() {
-            Ok((cacert, cert, private_key)) => {
+            Ok(certs) => {
                 print_warning();
 
-                write_cert_to_disk(self.config.base_node.config_dir.clone(), "node_ca.pem", &cacert)?;
-                write_cert_to_disk(self.config.base_node.config_dir.clone(), "server.pem", &cert)?;
-                write_cert_to_disk(self.config.base_node.config_dir.clone(), "server.key", &private_key)?;
+                let config_dir = self.config.base_node.config_dir.clone();
+                write_cert_to_disk(config_dir.clone(), "node_ca.pem", &certs.ca_cert)?;
+                write_cert_to_disk(config_dir.clone(), "server.pem", &certs.server_cert)?;
+                write_cert_to_disk(config_dir.clone(), "server.key", &certs.server_private_key)?;
+                write_cert_to_disk(config_dir.clone(), "client.pem", &certs.client_cert)?;
+                write_cert_to_disk(config_dir, "client.key", &certs.client_private_key)?;
 
                 println!();
                 println!("Certificates generated successfully.");
@@ -55,6 +58,12 @@ impl CommandContext {
                      directory. Restart the base node with the configuration grpc_tls_enabled=true"
                 );
                 println!();
+                println!(
+                    "To use client certificate authentication, give `client.pem` and `client.key` to the client and \
+                     add its fingerprint to `grpc_authentication.client_certificates`: {}",
+                    certs.client_fingerprint
+                );
+                println!();
             },
             Err(err) => eprintln!("Error generating certificates: {}", err),
         }
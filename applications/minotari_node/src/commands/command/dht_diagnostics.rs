@@ -0,0 +1,160 @@
+//  Copyright 2024, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{path::PathBuf, str::FromStr};
+
+use anyhow::{anyhow, Error};
+use async_trait::async_trait;
+use clap::Parser;
+use tari_comms_dht::diagnostics::DhtDiagnosticsReport;
+use tokio::fs;
+
+use super::{CommandContext, HandleCommand};
+use crate::table::Table;
+
+/// Dumps a report of how this node sees the DHT: its closest-peer neighbourhood, distance bucket occupancy, network
+/// discovery state transitions and store and forward buffer contents.
+/// dht-diagnostics
+/// dht-diagnostics --export dot --output dht.dot
+#[derive(Debug, Parser)]
+pub struct Args {
+    /// Export a graph of known peers and their distances: dot|json
+    #[clap(long)]
+    export: Option<ExportFormat>,
+    /// The file to write the export to. If omitted, the export is printed.
+    #[clap(long, short)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum ExportFormat {
+    Dot,
+    Json,
+}
+
+impl FromStr for ExportFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "dot" => Ok(ExportFormat::Dot),
+            "json" => Ok(ExportFormat::Json),
+            _ => Err(anyhow!("Invalid export format '{}'. Expected dot or json", s)),
+        }
+    }
+}
+
+#[async_trait]
+impl HandleCommand<Args> for CommandContext {
+    async fn handle_command(&mut self, args: Args) -> Result<(), Error> {
+        let report = self.dht_diagnostics.generate_report().await?;
+        match args.export {
+            Some(format) => {
+                let export = match format {
+                    ExportFormat::Dot => report.to_dot(),
+                    ExportFormat::Json => format!("{:#}", report.to_json()),
+                };
+                match args.output {
+                    Some(path) => {
+                        fs::write(&path, export).await?;
+                        println!("DHT diagnostics exported to {}", path.display());
+                    },
+                    None => println!("{}", export),
+                }
+            },
+            None => print_dht_diagnostics(&report),
+        }
+        Ok(())
+    }
+}
+
+fn print_dht_diagnostics(report: &DhtDiagnosticsReport) {
+    println!();
+    println!("Node: {} ({})", report.node_id, report.features);
+    println!(
+        "Known peers: {}, connected: {}",
+        report.peers.len(),
+        report.num_connected()
+    );
+
+    println!();
+    println!("Neighbourhood ({} closest nodes)", report.num_neighbouring_nodes);
+    println!("-----------------------------------");
+    let mut table = Table::new();
+    table.set_titles(vec![
+        "NodeId",
+        "Public Key",
+        "Bucket",
+        "Connected",
+        "Last Seen",
+        "Reputation",
+    ]);
+    for peer in report.neighbours() {
+        table.add_row(row![
+            peer.node_id,
+            peer.public_key,
+            peer.bucket,
+            if peer.is_connected { "yes" } else { "no" },
+            peer.last_seen
+                .map(|dt| dt.to_string())
+                .unwrap_or_else(|| "never".to_string()),
+            peer.reputation_score,
+        ]);
+    }
+    table.print_stdout();
+
+    println!();
+    println!("Distance buckets");
+    println!("----------------");
+    let mut table = Table::new();
+    table.set_titles(vec!["Bucket", "Peers", "Connected", "Offline", "Banned"]);
+    for bucket in &report.buckets {
+        table.add_row(row![
+            bucket.index,
+            bucket.num_peers,
+            bucket.num_connected,
+            bucket.num_offline,
+            bucket.num_banned
+        ]);
+    }
+    table.print_stdout();
+
+    println!();
+    println!("Network discovery");
+    println!("-----------------");
+    println!(
+        "Current state: {}",
+        report
+            .network_discovery
+            .current_state
+            .as_deref()
+            .unwrap_or("<not started>")
+    );
+    for transition in &report.network_discovery.transitions {
+        println!("  {}", transition);
+    }
+
+    println!();
+    println!("Store and forward");
+    println!("-----------------");
+    println!("{}", report.store_and_forward);
+}
@@ -20,6 +20,8 @@
 //  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use std::cmp::Reverse;
+
 use anyhow::Error;
 use async_trait::async_trait;
 use chrono::Utc;
@@ -34,17 +36,20 @@ use crate::{table::Table, utils::format_duration_basic};
 #[derive(Debug, Parser)]
 pub struct Args {
     filter: Option<String>,
+    /// Sort peers by reputation, best first, and show the reputation score
+    #[clap(long)]
+    by_reputation: bool,
 }
 
 #[async_trait]
 impl HandleCommand<Args> for CommandContext {
     async fn handle_command(&mut self, args: Args) -> Result<(), Error> {
-        self.list_peers(args.filter).await
+        self.list_peers(args.filter, args.by_reputation).await
     }
 }
 
 impl CommandContext {
-    pub async fn list_peers(&self, filter: Option<String>) -> Result<(), Error> {
+    pub async fn list_peers(&self, filter: Option<String>, by_reputation: bool) -> Result<(), Error> {
         let mut query = PeerQuery::new();
         if let Some(f) = filter {
             let filter = f.to_lowercase();
@@ -58,10 +63,15 @@ impl CommandContext {
         let num_peers = peers.len();
         println!();
         let mut table = Table::new();
-        table.set_titles(vec!["NodeId", "Public Key", "Role", "User Agent", "Info"]);
+        table.set_titles(vec!["NodeId", "Public Key", "Role", "User Agent", "Reputation", "Info"]);
 
-        peers.sort_by(|a, b| a.node_id.cmp(&b.node_id));
+        if by_reputation {
+            peers.sort_by_cached_key(|p| Reverse(p.reputation_score()));
+        } else {
+            peers.sort_by(|a, b| a.node_id.cmp(&b.node_id));
+        }
         for peer in peers {
+            let reputation = peer.reputation_score();
             let info_str = {
                 let mut s = vec![];
                 if peer.is_seed() {
@@ -123,6 +133,7 @@ impl CommandContext {
                         ua.as_ref()
                     }
                 },
+                reputation,
                 info_str,
             ]);
         }
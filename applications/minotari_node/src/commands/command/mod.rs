@@ -26,6 +26,7 @@ mod block_timing;
 mod check_db;
 mod check_for_updates;
 mod create_tls_certs;
+mod dht_diagnostics;
 mod dial_peer;
 mod discover_peer;
 mod get_block;
@@ -52,6 +53,7 @@ mod search_kernel;
 mod search_utxo;
 mod status;
 mod unban_all_peers;
+mod verify_payment_proof;
 mod version;
 mod watch_command;
 mod whoami;
@@ -72,7 +74,7 @@ use tari_comms::{
     CommsNode,
     NodeIdentity,
 };
-use tari_comms_dht::{DhtDiscoveryRequester, MetricsCollectorHandle};
+use tari_comms_dht::{diagnostics::DhtDiagnostics, DhtDiscoveryRequester, MetricsCollectorHandle};
 use tari_core::{
     base_node::{state_machine_service::states::StatusInfo, LocalNodeCommsInterface},
     blocks::ChainHeader,
@@ -127,12 +129,14 @@ pub enum Command {
     GetBlock(get_block::Args),
     SearchUtxo(search_utxo::Args),
     SearchKernel(search_kernel::Args),
+    VerifyPaymentProof(verify_payment_proof::Args),
     GetMempoolStats(get_mempool_stats::Args),
     GetMempoolState(get_mempool_state::Args),
     GetMempoolTx(get_mempool_state::ArgsTx),
     Whoami(whoami::Args),
     GetStateInfo(get_state_info::Args),
     GetNetworkStats(get_network_stats::Args),
+    DhtDiagnostics(dht_diagnostics::Args),
     ListValidatorNodes(list_validator_nodes::Args),
     CreateTlsCerts(create_tls_certs::Args),
     Quit(quit::Args),
@@ -157,6 +161,7 @@ pub struct CommandContext {
     blockchain_db: AsyncBlockchainDb<LMDBDatabase>,
     discovery_service: DhtDiscoveryRequester,
     dht_metrics_collector: MetricsCollectorHandle,
+    dht_diagnostics: DhtDiagnostics,
     rpc_server: RpcServerHandle,
     base_node_identity: Arc<NodeIdentity>,
     comms: CommsNode,
@@ -177,6 +182,7 @@ impl CommandContext {
             blockchain_db: ctx.blockchain_db().into(),
             discovery_service: ctx.base_node_dht().discovery_service_requester(),
             dht_metrics_collector: ctx.base_node_dht().metrics_collector(),
+            dht_diagnostics: ctx.base_node_dht().diagnostics(),
             rpc_server: ctx.rpc_server(),
             base_node_identity: ctx.base_node_identity(),
             comms: ctx.base_node_comms().clone(),
@@ -214,6 +220,7 @@ impl CommandContext {
                 Command::ListBannedPeers(_) |
                 Command::ListConnections(_) |
                 Command::GetNetworkStats(_) |
+                Command::DhtDiagnostics(_) |
                 Command::BlockTiming(_) |
                 Command::GetChainMetadata(_) |
                 Command::GetDbStats(_) |
@@ -224,6 +231,7 @@ impl CommandContext {
                 Command::HeaderStats(_) |
                 Command::SearchUtxo(_) |
                 Command::SearchKernel(_) |
+                Command::VerifyPaymentProof(_) |
                 Command::GetMempoolStats(_) |
                 Command::GetMempoolState(_) |
                 Command::GetMempoolTx(_) |
@@ -268,6 +276,7 @@ impl HandleCommand<Command> for CommandContext {
             Command::GetPeer(args) => self.handle_command(args).await,
             Command::GetStateInfo(args) => self.handle_command(args).await,
             Command::GetNetworkStats(args) => self.handle_command(args).await,
+            Command::DhtDiagnostics(args) => self.handle_command(args).await,
             Command::ListPeers(args) => self.handle_command(args).await,
             Command::DialPeer(args) => self.handle_command(args).await,
             Command::PingPeer(args) => self.handle_command(args).await,
@@ -287,6 +296,7 @@ impl HandleCommand<Command> for CommandContext {
             Command::GetBlock(args) => self.handle_command(args).await,
             Command::SearchUtxo(args) => self.handle_command(args).await,
             Command::SearchKernel(args) => self.handle_command(args).await,
+            Command::VerifyPaymentProof(args) => self.handle_command(args).await,
             Command::ListConnections(args) => self.handle_command(args).await,
             Command::GetMempoolStats(args) => self.handle_command(args).await,
             Command::GetMempoolState(args) => self.handle_command(args).await,
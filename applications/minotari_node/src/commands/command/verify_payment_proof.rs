@@ -0,0 +1,117 @@
+//  Copyright 2024, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+
+use std::{fs, path::PathBuf};
+
+use anyhow::{anyhow, Error};
+use async_trait::async_trait;
+use clap::Parser;
+use tari_core::transactions::PaymentProof;
+use tari_utilities::{hex::Hex, message_format::MessageFormat};
+
+use super::{CommandContext, HandleCommand};
+
+/// Verifies a payment proof created by a wallet. The proof signature is
+/// checked, the kernel is searched for via its excess signature and the
+/// output must have been mined in the same block as the kernel, locked to
+/// the recipient's public spend key.
+#[derive(Debug, Parser)]
+pub struct Args {
+    /// path of the payment proof json file
+    input_file: PathBuf,
+}
+
+#[async_trait]
+impl HandleCommand<Args> for CommandContext {
+    async fn handle_command(&mut self, args: Args) -> Result<(), Error> {
+        let proof = PaymentProof::from_json(&fs::read_to_string(&args.input_file)?)?;
+        self.verify_payment_proof(proof).await
+    }
+}
+
+impl CommandContext {
+    /// Function to process the verify payment proof command
+    pub async fn verify_payment_proof(&mut self, proof: PaymentProof) -> Result<(), Error> {
+        if !proof.is_valid_signature() {
+            return Err(anyhow!("The payment proof signature is invalid"));
+        }
+
+        let block = self
+            .node_service
+            .get_blocks_with_kernels(vec![proof.kernel_signature.clone()])
+            .await?
+            .pop()
+            .ok_or_else(|| anyhow!("The transaction kernel has not been mined"))?;
+        if !block
+            .block()
+            .body
+            .kernels()
+            .iter()
+            .any(|k| k.excess == proof.kernel_excess && k.excess_sig == proof.kernel_signature)
+        {
+            return Err(anyhow!("The mined kernel does not match the proof kernel excess"));
+        }
+
+        let output = match self.blockchain_db.fetch_output(proof.output_hash).await? {
+            Some(output) => output,
+            None => {
+                // A pruned node discards spent outputs below its pruned height, so a missing output proves nothing
+                let metadata = self.blockchain_db.get_chain_metadata().await?;
+                if metadata.is_pruned_node() && block.header().height <= metadata.pruned_height() {
+                    return Err(anyhow!(
+                        "The output {} could not be checked. The transaction kernel was mined at height {}, which \
+                         this node has pruned up to height {}, so the output may have been spent and pruned. Verify \
+                         the proof on an archival node.",
+                        proof.output_hash,
+                        block.header().height,
+                        metadata.pruned_height()
+                    ));
+                }
+                return Err(anyhow!("The output {} has not been mined", proof.output_hash));
+            },
+        };
+        if output.output.commitment != proof.output_commitment {
+            return Err(anyhow!("The mined output does not match the proof commitment"));
+        }
+        if !proof.is_locked_to_recipient(&output.output) {
+            return Err(anyhow!(
+                "The mined output is not locked to the recipient's public spend key"
+            ));
+        }
+        if output.header_hash != *block.hash() {
+            return Err(anyhow!(
+                "The output and the transaction kernel were not mined in the same block"
+            ));
+        }
+
+        println!("Payment proof is valid");
+        println!("Amount: {}", proof.amount);
+        println!("Recipient: {}", proof.recipient.to_base58());
+        println!("Payment id: {}", proof.payment_id);
+        println!(
+            "Mined in block {} at height {} ({} confirmations)",
+            block.hash().to_hex(),
+            block.header().height,
+            block.confirmations()
+        );
+        Ok(())
+    }
+}
@@ -21,6 +21,7 @@
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use std::{
+    collections::HashMap,
     fmt,
     path::{Path, PathBuf},
     time::Duration,
@@ -94,6 +95,8 @@ pub struct BaseNodeConfig {
     pub grpc_authentication: GrpcAuthentication,
     /// GRPC tls enabled
     pub grpc_tls_enabled: bool,
+    /// GRPC per-client rate limits, per-method concurrency caps and stream length limits
+    pub grpc_rate_limit: GrpcRateLimitConfig,
     /// Enable mining on the base node, overriding other settings regarding mining
     pub mining_enabled: bool,
     /// Enable second layer specific grpc methods.
@@ -158,6 +161,7 @@ impl Default for BaseNodeConfig {
             grpc_server_allow_methods: vec![GrpcMethod::GetVersion],
             grpc_authentication: GrpcAuthentication::default(),
             grpc_tls_enabled: false,
+            grpc_rate_limit: GrpcRateLimitConfig::default(),
             mining_enabled: false,
             second_layer_grpc_enabled: false,
             identity_file: PathBuf::from("config/base_node_id.json"),
@@ -217,8 +221,37 @@ pub enum DatabaseType {
     Lmdb,
 }
 
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct GrpcRateLimitConfig {
+    /// The sustained number of requests per second allowed for each client, where clients are identified by their
+    /// authenticated identity or otherwise by their remote IP address. Set to 0 to disable rate limiting.
+    pub requests_per_second: u32,
+    /// The number of requests a client may make in a burst before being held to `requests_per_second`
+    pub burst_size: u32,
+    /// The maximum number of requests, including open streams, each method serves at once across all clients. Set to
+    /// 0 to disable.
+    pub max_concurrent_requests_per_method: usize,
+    /// Overrides `max_concurrent_requests_per_method` for individual methods
+    pub method_concurrency_limits: HashMap<GrpcMethod, usize>,
+    /// The maximum number of messages a streaming method may return. Set to 0 to disable.
+    pub max_stream_length: u64,
+}
+
+impl Default for GrpcRateLimitConfig {
+    fn default() -> Self {
+        Self {
+            requests_per_second: 100,
+            burst_size: 200,
+            max_concurrent_requests_per_method: 32,
+            method_concurrency_limits: HashMap::new(),
+            max_stream_length: 0,
+        }
+    }
+}
+
 /// A list of all the GRPC methods that can be enabled/disabled
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq, Hash)]
 #[serde(rename_all = "snake_case")]
 pub enum GrpcMethod {
     ListHeaders,
@@ -253,6 +286,7 @@ pub enum GrpcMethod {
     GetNetworkStatus,
     ListConnectedPeers,
     GetMempoolStats,
+    GetDhtDiagnostics,
     GetActiveValidatorNodes,
     GetShardKey,
     GetTemplateRegistrations,
@@ -36,11 +36,13 @@ use minotari_app_grpc::{
 };
 use minotari_app_utilities::consts;
 use tari_common_types::{
+    grpc_authentication::GrpcScope,
     key_branches::TransactionKeyManagerBranch,
     tari_address::TariAddress,
     types::{Commitment, FixedHash, PublicKey, Signature},
 };
 use tari_comms::{Bytes, CommsNode};
+use tari_comms_dht::diagnostics::DhtDiagnostics;
 use tari_core::{
     base_node::{
         comms_interface::CommsInterfaceError,
@@ -79,8 +81,10 @@ use crate::{
     config::GrpcMethod,
     grpc::{
         blocks::{block_fees, block_heights, block_size, GET_BLOCKS_MAX_HEIGHTS, GET_BLOCKS_PAGE_SIZE},
+        dht_diagnostics::dht_diagnostics_to_grpc,
         hash_rate::HashRateMovingAverage,
         helpers::{mean, median},
+        rate_limit::{GrpcRateLimiter, GrpcRequestPermit, LimitedStream},
     },
     BaseNodeConfig,
 };
@@ -113,7 +117,9 @@ pub struct BaseNodeGrpcServer {
     software_updater: SoftwareUpdaterHandle,
     comms: CommsNode,
     liveness: LivenessHandle,
+    dht_diagnostics: DhtDiagnostics,
     report_grpc_error: bool,
+    rate_limiter: GrpcRateLimiter,
     config: BaseNodeConfig,
 }
 
@@ -128,7 +134,9 @@ impl BaseNodeGrpcServer {
             software_updater: ctx.software_updater(),
             comms: ctx.base_node_comms().clone(),
             liveness: ctx.liveness(),
+            dht_diagnostics: ctx.base_node_dht().diagnostics(),
             report_grpc_error: ctx.get_report_grpc_error(),
+            rate_limiter: GrpcRateLimiter::new(config.grpc_rate_limit.clone()),
             config,
         }
     }
@@ -182,6 +190,13 @@ impl BaseNodeGrpcServer {
         }
         Ok(())
     }
+
+    /// Checks that the method is enabled and that the request is within the configured rate and concurrency limits.
+    /// The returned permit must be held until the request, or the stream it returns, is complete.
+    fn start_request<T>(&self, method: GrpcMethod, request: &Request<T>) -> Result<GrpcRequestPermit, Status> {
+        self.check_method_enabled(method)?;
+        self.rate_limiter.check(method, request)
+    }
 }
 
 pub fn obscure_error_if_true(report: bool, status: Status) -> Status {
@@ -193,6 +208,47 @@ pub fn obscure_error_if_true(report: bool, status: Status) -> Status {
     }
 }
 
+/// The scope a gRPC client needs to call the given base node method. Unknown methods require the admin scope.
+pub fn required_scope(method: &str) -> GrpcScope {
+    match method {
+        "SubmitBlock" | "SubmitBlockBlob" | "SubmitTransaction" => GrpcScope::Spend,
+        "ListHeaders" |
+        "GetHeaderByHash" |
+        "GetBlocks" |
+        "GetBlockTiming" |
+        "GetConstants" |
+        "GetBlockSize" |
+        "GetBlockFees" |
+        "GetVersion" |
+        "CheckForUpdates" |
+        "GetTokensInCirculation" |
+        "GetNetworkDifficulty" |
+        "GetNewBlockTemplate" |
+        "GetNewBlock" |
+        "GetNewBlockWithCoinbases" |
+        "GetNewBlockTemplateWithCoinbases" |
+        "GetNewBlockBlob" |
+        "GetSyncInfo" |
+        "GetSyncProgress" |
+        "GetTipInfo" |
+        "SearchKernels" |
+        "SearchUtxos" |
+        "FetchMatchingUtxos" |
+        "GetPeers" |
+        "GetMempoolTransactions" |
+        "TransactionState" |
+        "Identify" |
+        "GetNetworkStatus" |
+        "ListConnectedPeers" |
+        "GetMempoolStats" |
+        "GetActiveValidatorNodes" |
+        "GetShardKey" |
+        "GetTemplateRegistrations" |
+        "GetSideChainUtxos" => GrpcScope::Read,
+        _ => GrpcScope::Admin,
+    }
+}
+
 pub async fn get_heights(
     request: &tari_rpc::HeightRequest,
     handler: LocalNodeCommsInterface,
@@ -203,25 +259,25 @@ impl BaseNodeGrpcServer {}
 
 #[tonic::async_trait]
 impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
-    type FetchMatchingUtxosStream = mpsc::Receiver<Result<tari_rpc::FetchMatchingUtxosResponse, Status>>;
-    type GetActiveValidatorNodesStream = mpsc::Receiver<Result<tari_rpc::GetActiveValidatorNodesResponse, Status>>;
-    type GetBlocksStream = mpsc::Receiver<Result<tari_rpc::HistoricalBlock, Status>>;
-    type GetMempoolTransactionsStream = mpsc::Receiver<Result<tari_rpc::GetMempoolTransactionsResponse, Status>>;
-    type GetNetworkDifficultyStream = mpsc::Receiver<Result<tari_rpc::NetworkDifficultyResponse, Status>>;
-    type GetPeersStream = mpsc::Receiver<Result<tari_rpc::GetPeersResponse, Status>>;
-    type GetSideChainUtxosStream = mpsc::Receiver<Result<tari_rpc::GetSideChainUtxosResponse, Status>>;
-    type GetTemplateRegistrationsStream = mpsc::Receiver<Result<tari_rpc::GetTemplateRegistrationResponse, Status>>;
-    type GetTokensInCirculationStream = mpsc::Receiver<Result<tari_rpc::ValueAtHeightResponse, Status>>;
-    type ListHeadersStream = mpsc::Receiver<Result<tari_rpc::BlockHeaderResponse, Status>>;
-    type SearchKernelsStream = mpsc::Receiver<Result<tari_rpc::HistoricalBlock, Status>>;
-    type SearchUtxosStream = mpsc::Receiver<Result<tari_rpc::HistoricalBlock, Status>>;
+    type FetchMatchingUtxosStream = LimitedStream<tari_rpc::FetchMatchingUtxosResponse>;
+    type GetActiveValidatorNodesStream = LimitedStream<tari_rpc::GetActiveValidatorNodesResponse>;
+    type GetBlocksStream = LimitedStream<tari_rpc::HistoricalBlock>;
+    type GetMempoolTransactionsStream = LimitedStream<tari_rpc::GetMempoolTransactionsResponse>;
+    type GetNetworkDifficultyStream = LimitedStream<tari_rpc::NetworkDifficultyResponse>;
+    type GetPeersStream = LimitedStream<tari_rpc::GetPeersResponse>;
+    type GetSideChainUtxosStream = LimitedStream<tari_rpc::GetSideChainUtxosResponse>;
+    type GetTemplateRegistrationsStream = LimitedStream<tari_rpc::GetTemplateRegistrationResponse>;
+    type GetTokensInCirculationStream = LimitedStream<tari_rpc::ValueAtHeightResponse>;
+    type ListHeadersStream = LimitedStream<tari_rpc::BlockHeaderResponse>;
+    type SearchKernelsStream = LimitedStream<tari_rpc::HistoricalBlock>;
+    type SearchUtxosStream = LimitedStream<tari_rpc::HistoricalBlock>;
 
     #[allow(clippy::too_many_lines)]
     async fn get_network_difficulty(
         &self,
         request: Request<tari_rpc::HeightRequest>,
     ) -> Result<Response<Self::GetNetworkDifficultyStream>, Status> {
-        self.check_method_enabled(GrpcMethod::GetNetworkDifficulty)?;
+        let permit = self.start_request(GrpcMethod::GetNetworkDifficulty, &request)?;
         let report_error_flag = self.report_error_flag();
         let request = request.into_inner();
         debug!(
@@ -332,14 +388,14 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
             target: LOG_TARGET,
             "Sending GetNetworkDifficulty response stream to client"
         );
-        Ok(Response::new(rx))
+        Ok(Response::new(permit.limit_stream(rx)))
     }
 
     async fn get_mempool_transactions(
         &self,
         request: Request<tari_rpc::GetMempoolTransactionsRequest>,
     ) -> Result<Response<Self::GetMempoolTransactionsStream>, Status> {
-        self.check_method_enabled(GrpcMethod::GetMempoolTransactions)?;
+        let permit = self.start_request(GrpcMethod::GetMempoolTransactions, &request)?;
         let report_error_flag = self.report_error_flag();
         let _request = request.into_inner();
         debug!(target: LOG_TARGET, "Incoming GRPC request for GetMempoolTransactions",);
@@ -390,7 +446,7 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
             }
         });
         debug!(target: LOG_TARGET, "Sending GetMempool response stream to client");
-        Ok(Response::new(rx))
+        Ok(Response::new(permit.limit_stream(rx)))
     }
 
     // casting here is okay as a block cannot have more than u32 kernels
@@ -400,7 +456,7 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
         &self,
         request: Request<tari_rpc::ListHeadersRequest>,
     ) -> Result<Response<Self::ListHeadersStream>, Status> {
-        self.check_method_enabled(GrpcMethod::ListHeaders)?;
+        let permit = self.start_request(GrpcMethod::ListHeaders, &request)?;
         let report_error_flag = self.report_error_flag();
         let request = request.into_inner();
         debug!(
@@ -564,14 +620,14 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
         });
 
         debug!(target: LOG_TARGET, "Sending ListHeaders response stream to client");
-        Ok(Response::new(rx))
+        Ok(Response::new(permit.limit_stream(rx)))
     }
 
     async fn get_new_block_template(
         &self,
         request: Request<tari_rpc::NewBlockTemplateRequest>,
     ) -> Result<Response<tari_rpc::NewBlockTemplateResponse>, Status> {
-        self.check_method_enabled(GrpcMethod::GetNewBlockTemplate)?;
+        let _permit = self.start_request(GrpcMethod::GetNewBlockTemplate, &request)?;
         let report_error_flag = self.report_error_flag();
         let request = request.into_inner();
         debug!(target: LOG_TARGET, "Incoming GRPC request for get new block template");
@@ -633,7 +689,7 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
         &self,
         request: Request<tari_rpc::NewBlockTemplate>,
     ) -> Result<Response<tari_rpc::GetNewBlockResult>, Status> {
-        self.check_method_enabled(GrpcMethod::GetNewBlock)?;
+        let _permit = self.start_request(GrpcMethod::GetNewBlock, &request)?;
         let report_error_flag = self.report_error_flag();
         let request = request.into_inner();
         debug!(target: LOG_TARGET, "Incoming GRPC request for get new block");
@@ -743,6 +799,9 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
                 "`GetNewBlockTemplateWithCoinbases` method not made available",
             ));
         }
+        let _permit = self
+            .rate_limiter
+            .check(GrpcMethod::GetNewBlockTemplateWithCoinbases, &request)?;
         debug!(target: LOG_TARGET, "Incoming GRPC request for get new block template with coinbases");
         let report_error_flag = self.report_error_flag();
         let request = request.into_inner();
@@ -984,6 +1043,9 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
                 "`GetNewBlockWithCoinbasesRequest` method not made available",
             ));
         }
+        let _permit = self
+            .rate_limiter
+            .check(GrpcMethod::GetNewBlockWithCoinbases, &request)?;
         let report_error_flag = self.report_error_flag();
         let request = request.into_inner();
         debug!(target: LOG_TARGET, "Incoming GRPC request for get new block with coinbases");
@@ -1201,7 +1263,7 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
         &self,
         request: Request<tari_rpc::NewBlockTemplate>,
     ) -> Result<Response<tari_rpc::GetNewBlockBlobResult>, Status> {
-        self.check_method_enabled(GrpcMethod::GetNewBlockBlob)?;
+        let _permit = self.start_request(GrpcMethod::GetNewBlockBlob, &request)?;
         let report_error_flag = self.report_error_flag();
         let request = request.into_inner();
         debug!(target: LOG_TARGET, "Incoming GRPC request for get new block blob");
@@ -1284,7 +1346,7 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
         &self,
         request: Request<tari_rpc::Block>,
     ) -> Result<Response<tari_rpc::SubmitBlockResponse>, Status> {
-        self.check_method_enabled(GrpcMethod::SubmitBlock)?;
+        let _permit = self.start_request(GrpcMethod::SubmitBlock, &request)?;
         let report_error_flag = self.report_error_flag();
         let request = request.into_inner();
         let block = Block::try_from(request).map_err(|e| {
@@ -1318,7 +1380,7 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
         &self,
         request: Request<tari_rpc::BlockBlobRequest>,
     ) -> Result<Response<tari_rpc::SubmitBlockResponse>, Status> {
-        self.check_method_enabled(GrpcMethod::SubmitBlockBlob)?;
+        let _permit = self.start_request(GrpcMethod::SubmitBlockBlob, &request)?;
         let report_error_flag = self.report_error_flag();
         debug!(target: LOG_TARGET, "Received block blob from miner: {:?}", request);
         let request = request.into_inner();
@@ -1359,7 +1421,7 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
         &self,
         request: Request<tari_rpc::SubmitTransactionRequest>,
     ) -> Result<Response<tari_rpc::SubmitTransactionResponse>, Status> {
-        self.check_method_enabled(GrpcMethod::SubmitTransaction)?;
+        let _permit = self.start_request(GrpcMethod::SubmitTransaction, &request)?;
         let report_error_flag = self.report_error_flag();
         let request = request.into_inner();
         let txn: Transaction = request
@@ -1411,7 +1473,7 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
         &self,
         request: Request<tari_rpc::TransactionStateRequest>,
     ) -> Result<Response<tari_rpc::TransactionStateResponse>, Status> {
-        self.check_method_enabled(GrpcMethod::TransactionState)?;
+        let _permit = self.start_request(GrpcMethod::TransactionState, &request)?;
         let report_error_flag = self.report_error_flag();
         let request = request.into_inner();
         let excess_sig: Signature = request
@@ -1496,9 +1558,9 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
 
     async fn get_peers(
         &self,
-        _request: Request<tari_rpc::GetPeersRequest>,
+        request: Request<tari_rpc::GetPeersRequest>,
     ) -> Result<Response<Self::GetPeersStream>, Status> {
-        self.check_method_enabled(GrpcMethod::GetPeers)?;
+        let permit = self.start_request(GrpcMethod::GetPeers, &request)?;
         let report_error_flag = self.report_error_flag();
         debug!(target: LOG_TARGET, "Incoming GRPC request for get all peers");
 
@@ -1524,14 +1586,14 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
         });
 
         debug!(target: LOG_TARGET, "Sending peers response to client");
-        Ok(Response::new(rx))
+        Ok(Response::new(permit.limit_stream(rx)))
     }
 
     async fn get_blocks(
         &self,
         request: Request<tari_rpc::GetBlocksRequest>,
     ) -> Result<Response<Self::GetBlocksStream>, Status> {
-        self.check_method_enabled(GrpcMethod::GetBlocks)?;
+        let permit = self.start_request(GrpcMethod::GetBlocks, &request)?;
         let report_error_flag = self.report_error_flag();
         let request = request.into_inner();
         debug!(
@@ -1593,14 +1655,14 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
         });
 
         debug!(target: LOG_TARGET, "Sending GetBlocks response stream to client");
-        Ok(Response::new(rx))
+        Ok(Response::new(permit.limit_stream(rx)))
     }
 
     async fn get_tip_info(
         &self,
-        _request: Request<tari_rpc::Empty>,
+        request: Request<tari_rpc::Empty>,
     ) -> Result<Response<tari_rpc::TipInfoResponse>, Status> {
-        self.check_method_enabled(GrpcMethod::GetTipInfo)?;
+        let _permit = self.start_request(GrpcMethod::GetTipInfo, &request)?;
         let report_error_flag = self.report_error_flag();
         debug!(target: LOG_TARGET, "Incoming GRPC request for BN tip data");
 
@@ -1628,7 +1690,7 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
         &self,
         request: Request<tari_rpc::SearchKernelsRequest>,
     ) -> Result<Response<Self::SearchKernelsStream>, Status> {
-        self.check_method_enabled(GrpcMethod::SearchKernels)?;
+        let permit = self.start_request(GrpcMethod::SearchKernels, &request)?;
         let report_error_flag = self.report_error_flag();
         debug!(target: LOG_TARGET, "Incoming GRPC request for SearchKernels");
         let request = request.into_inner();
@@ -1677,14 +1739,14 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
         });
 
         debug!(target: LOG_TARGET, "Sending SearchKernels response stream to client");
-        Ok(Response::new(rx))
+        Ok(Response::new(permit.limit_stream(rx)))
     }
 
     async fn search_utxos(
         &self,
         request: Request<tari_rpc::SearchUtxosRequest>,
     ) -> Result<Response<Self::SearchUtxosStream>, Status> {
-        self.check_method_enabled(GrpcMethod::SearchUtxos)?;
+        let permit = self.start_request(GrpcMethod::SearchUtxos, &request)?;
         let report_error_flag = self.report_error_flag();
         debug!(target: LOG_TARGET, "Incoming GRPC request for SearchUtxos");
         let request = request.into_inner();
@@ -1732,7 +1794,7 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
         });
 
         debug!(target: LOG_TARGET, "Sending SearchUtxos response stream to client");
-        Ok(Response::new(rx))
+        Ok(Response::new(permit.limit_stream(rx)))
     }
 
     #[allow(clippy::useless_conversion)]
@@ -1740,7 +1802,7 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
         &self,
         request: Request<tari_rpc::FetchMatchingUtxosRequest>,
     ) -> Result<Response<Self::FetchMatchingUtxosStream>, Status> {
-        self.check_method_enabled(GrpcMethod::FetchMatchingUtxos)?;
+        let permit = self.start_request(GrpcMethod::FetchMatchingUtxos, &request)?;
         let report_error_flag = self.report_error_flag();
         debug!(target: LOG_TARGET, "Incoming GRPC request for FetchMatchingUtxos");
         let request = request.into_inner();
@@ -1802,14 +1864,14 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
             target: LOG_TARGET,
             "Sending FindMatchingUtxos response stream to client"
         );
-        Ok(Response::new(rx))
+        Ok(Response::new(permit.limit_stream(rx)))
     }
 
     async fn get_block_timing(
         &self,
         request: Request<tari_rpc::HeightRequest>,
     ) -> Result<Response<tari_rpc::BlockTimingResponse>, Status> {
-        self.check_method_enabled(GrpcMethod::GetBlockTiming)?;
+        let _permit = self.start_request(GrpcMethod::GetBlockTiming, &request)?;
         let report_error_flag = self.report_error_flag();
         let request = request.into_inner();
         debug!(
@@ -1859,7 +1921,7 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
         &self,
         request: Request<tari_rpc::BlockHeight>,
     ) -> Result<Response<tari_rpc::ConsensusConstants>, Status> {
-        self.check_method_enabled(GrpcMethod::GetConstants)?;
+        let _permit = self.start_request(GrpcMethod::GetConstants, &request)?;
         let report_error_flag = self.report_error_flag();
         debug!(target: LOG_TARGET, "Incoming GRPC request for GetConstants",);
         debug!(target: LOG_TARGET, "Sending GetConstants response to client");
@@ -1885,7 +1947,7 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
         &self,
         request: Request<tari_rpc::BlockGroupRequest>,
     ) -> Result<Response<tari_rpc::BlockGroupResponse>, Status> {
-        self.check_method_enabled(GrpcMethod::GetBlockSize)?;
+        let _permit = self.start_request(GrpcMethod::GetBlockSize, &request)?;
         let report_error_flag = self.report_error_flag();
         get_block_group(
             self.node_service.clone(),
@@ -1900,7 +1962,7 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
         &self,
         request: Request<tari_rpc::BlockGroupRequest>,
     ) -> Result<Response<tari_rpc::BlockGroupResponse>, Status> {
-        self.check_method_enabled(GrpcMethod::GetBlockFees)?;
+        let _permit = self.start_request(GrpcMethod::GetBlockFees, &request)?;
         let report_error_flag = self.report_error_flag();
         get_block_group(
             self.node_service.clone(),
@@ -1911,16 +1973,16 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
         .await
     }
 
-    async fn get_version(&self, _request: Request<tari_rpc::Empty>) -> Result<Response<tari_rpc::StringValue>, Status> {
-        self.check_method_enabled(GrpcMethod::GetVersion)?;
+    async fn get_version(&self, request: Request<tari_rpc::Empty>) -> Result<Response<tari_rpc::StringValue>, Status> {
+        let _permit = self.start_request(GrpcMethod::GetVersion, &request)?;
         Ok(Response::new(consts::APP_VERSION.to_string().into()))
     }
 
     async fn check_for_updates(
         &self,
-        _request: Request<tari_rpc::Empty>,
+        request: Request<tari_rpc::Empty>,
     ) -> Result<Response<tari_rpc::SoftwareUpdate>, Status> {
-        self.check_method_enabled(GrpcMethod::CheckForUpdates)?;
+        let _permit = self.start_request(GrpcMethod::CheckForUpdates, &request)?;
         let mut resp = tari_rpc::SoftwareUpdate::default();
 
         if let Some(ref update) = *self.software_updater.update_notifier().borrow() {
@@ -1937,7 +1999,7 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
         &self,
         request: Request<tari_rpc::GetBlocksRequest>,
     ) -> Result<Response<Self::GetTokensInCirculationStream>, Status> {
-        self.check_method_enabled(GrpcMethod::GetTokensInCirculation)?;
+        let permit = self.start_request(GrpcMethod::GetTokensInCirculation, &request)?;
         let report_error_flag = self.report_error_flag();
         debug!(target: LOG_TARGET, "Incoming GRPC request for GetTokensInCirculation",);
         let request = request.into_inner();
@@ -1988,14 +2050,14 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
         });
 
         debug!(target: LOG_TARGET, "Sending GetTokensInCirculation response to client");
-        Ok(Response::new(rx))
+        Ok(Response::new(permit.limit_stream(rx)))
     }
 
     async fn get_sync_progress(
         &self,
-        _request: Request<tari_rpc::Empty>,
+        request: Request<tari_rpc::Empty>,
     ) -> Result<Response<tari_rpc::SyncProgressResponse>, Status> {
-        self.check_method_enabled(GrpcMethod::GetSyncProgress)?;
+        let _permit = self.start_request(GrpcMethod::GetSyncProgress, &request)?;
         let state = self
             .state_machine_handle
             .get_status_info_watch()
@@ -2038,9 +2100,9 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
 
     async fn get_sync_info(
         &self,
-        _request: Request<tari_rpc::Empty>,
+        request: Request<tari_rpc::Empty>,
     ) -> Result<Response<tari_rpc::SyncInfoResponse>, Status> {
-        self.check_method_enabled(GrpcMethod::GetSyncInfo)?;
+        let _permit = self.start_request(GrpcMethod::GetSyncInfo, &request)?;
         debug!(target: LOG_TARGET, "Incoming GRPC request for BN sync data");
         let response = self
             .state_machine_handle
@@ -2068,7 +2130,7 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
         &self,
         request: Request<tari_rpc::GetHeaderByHashRequest>,
     ) -> Result<Response<tari_rpc::BlockHeaderResponse>, Status> {
-        self.check_method_enabled(GrpcMethod::GetHeaderByHash)?;
+        let _permit = self.start_request(GrpcMethod::GetHeaderByHash, &request)?;
         let report_error_flag = self.report_error_flag();
         let tari_rpc::GetHeaderByHashRequest { hash } = request.into_inner();
         let mut node_service = self.node_service.clone();
@@ -2107,8 +2169,8 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
         Ok(Response::new(resp))
     }
 
-    async fn identify(&self, _: Request<tari_rpc::Empty>) -> Result<Response<tari_rpc::NodeIdentity>, Status> {
-        self.check_method_enabled(GrpcMethod::Identify)?;
+    async fn identify(&self, request: Request<tari_rpc::Empty>) -> Result<Response<tari_rpc::NodeIdentity>, Status> {
+        let _permit = self.start_request(GrpcMethod::Identify, &request)?;
         let identity = self.comms.node_identity_ref();
         Ok(Response::new(tari_rpc::NodeIdentity {
             public_key: identity.public_key().to_vec(),
@@ -2119,9 +2181,9 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
 
     async fn get_network_status(
         &self,
-        _: Request<tari_rpc::Empty>,
+        request: Request<tari_rpc::Empty>,
     ) -> Result<Response<tari_rpc::NetworkStatusResponse>, Status> {
-        self.check_method_enabled(GrpcMethod::GetNetworkStatus)?;
+        let _permit = self.start_request(GrpcMethod::GetNetworkStatus, &request)?;
         let report_error_flag = self.report_error_flag();
         let status = self
             .comms
@@ -2155,9 +2217,9 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
 
     async fn list_connected_peers(
         &self,
-        _: Request<tari_rpc::Empty>,
+        request: Request<tari_rpc::Empty>,
     ) -> Result<Response<tari_rpc::ListConnectedPeersResponse>, Status> {
-        self.check_method_enabled(GrpcMethod::ListConnectedPeers)?;
+        let _permit = self.start_request(GrpcMethod::ListConnectedPeers, &request)?;
         let report_error_flag = self.report_error_flag();
         let mut connectivity = self.comms.connectivity();
         let peer_manager = self.comms.peer_manager();
@@ -2189,11 +2251,39 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
         Ok(Response::new(resp))
     }
 
+    async fn get_dht_diagnostics(
+        &self,
+        request: Request<tari_rpc::GetDhtDiagnosticsRequest>,
+    ) -> Result<Response<tari_rpc::GetDhtDiagnosticsResponse>, Status> {
+        let _permit = self.start_request(GrpcMethod::GetDhtDiagnostics, &request)?;
+        let report_error_flag = self.report_error_flag();
+        let request = request.into_inner();
+        let report = self
+            .dht_diagnostics
+            .generate_report()
+            .await
+            .map_err(|err| obscure_error_if_true(report_error_flag, Status::internal(err.to_string())))?;
+
+        let graph = match tari_rpc::DhtGraphFormat::from_i32(request.graph_format) {
+            Some(tari_rpc::DhtGraphFormat::Dot) => report.to_dot(),
+            Some(tari_rpc::DhtGraphFormat::Json) => report.to_json().to_string(),
+            Some(tari_rpc::DhtGraphFormat::None) => String::new(),
+            None => {
+                return Err(obscure_error_if_true(
+                    report_error_flag,
+                    Status::invalid_argument(format!("Invalid graph format {}", request.graph_format)),
+                ))
+            },
+        };
+
+        Ok(Response::new(dht_diagnostics_to_grpc(report, graph)))
+    }
+
     async fn get_mempool_stats(
         &self,
-        _: Request<tari_rpc::Empty>,
+        request: Request<tari_rpc::Empty>,
     ) -> Result<Response<tari_rpc::MempoolStatsResponse>, Status> {
-        self.check_method_enabled(GrpcMethod::GetMempoolStats)?;
+        let _permit = self.start_request(GrpcMethod::GetMempoolStats, &request)?;
         let report_error_flag = self.report_error_flag();
         let mut mempool_handle = self.mempool_service.clone();
 
@@ -2215,7 +2305,7 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
         &self,
         request: Request<tari_rpc::GetShardKeyRequest>,
     ) -> Result<Response<tari_rpc::GetShardKeyResponse>, Status> {
-        self.check_method_enabled(GrpcMethod::GetShardKey)?;
+        let _permit = self.start_request(GrpcMethod::GetShardKey, &request)?;
         let request = request.into_inner();
         let report_error_flag = self.report_error_flag();
         let mut handler = self.node_service.clone();
@@ -2243,7 +2333,7 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
         &self,
         request: Request<tari_rpc::GetActiveValidatorNodesRequest>,
     ) -> Result<Response<Self::GetActiveValidatorNodesStream>, Status> {
-        self.check_method_enabled(GrpcMethod::GetActiveValidatorNodes)?;
+        let permit = self.start_request(GrpcMethod::GetActiveValidatorNodes, &request)?;
         let request = request.into_inner();
         debug!(target: LOG_TARGET, "Incoming GRPC request for GetActiveValidatorNodes");
 
@@ -2278,14 +2368,14 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
             target: LOG_TARGET,
             "Sending GetActiveValidatorNodes response stream to client"
         );
-        Ok(Response::new(rx))
+        Ok(Response::new(permit.limit_stream(rx)))
     }
 
     async fn get_template_registrations(
         &self,
         request: Request<tari_rpc::GetTemplateRegistrationsRequest>,
     ) -> Result<Response<Self::GetTemplateRegistrationsStream>, Status> {
-        self.check_method_enabled(GrpcMethod::GetTemplateRegistrations)?;
+        let permit = self.start_request(GrpcMethod::GetTemplateRegistrations, &request)?;
         let request = request.into_inner();
         let report_error_flag = self.report_error_flag();
         debug!(target: LOG_TARGET, "Incoming GRPC request for GetTemplateRegistrations");
@@ -2319,7 +2409,7 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
         };
 
         if request.count == 0 {
-            return Ok(Response::new(rx));
+            return Ok(Response::new(permit.limit_stream(rx)));
         }
 
         let end_height = start_height.checked_add(request.count).ok_or_else(|| {
@@ -2359,7 +2449,7 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
             target: LOG_TARGET,
             "Sending GetTemplateRegistrations response stream to client"
         );
-        Ok(Response::new(rx))
+        Ok(Response::new(permit.limit_stream(rx)))
     }
 
     #[allow(clippy::too_many_lines)]
@@ -2367,7 +2457,7 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
         &self,
         request: Request<tari_rpc::GetSideChainUtxosRequest>,
     ) -> Result<Response<Self::GetSideChainUtxosStream>, Status> {
-        self.check_method_enabled(GrpcMethod::GetSideChainUtxos)?;
+        let permit = self.start_request(GrpcMethod::GetSideChainUtxos, &request)?;
         let request = request.into_inner();
         let report_error_flag = self.report_error_flag();
         debug!(target: LOG_TARGET, "Incoming GRPC request for GetTemplateRegistrations");
@@ -2403,7 +2493,7 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
         };
 
         if request.count == 0 {
-            return Ok(Response::new(rx));
+            return Ok(Response::new(permit.limit_stream(rx)));
         }
 
         let start_height = start_header.height();
@@ -2490,7 +2580,7 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
             target: LOG_TARGET,
             "Sending GetTemplateRegistrations response stream to client"
         );
-        Ok(Response::new(rx))
+        Ok(Response::new(permit.limit_stream(rx)))
     }
 }
 
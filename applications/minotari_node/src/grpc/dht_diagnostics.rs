@@ -0,0 +1,95 @@
+//  Copyright 2024, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::convert::TryFrom;
+
+use chrono::NaiveDateTime;
+use minotari_app_grpc::tari_rpc;
+use tari_comms_dht::diagnostics::{BucketOccupancy, DhtDiagnosticsReport, PeerDiagnostics};
+use tari_utilities::ByteArray;
+
+/// Converts a DHT diagnostics report into the gRPC response type. `graph` is the exported graph of known peers, or
+/// an empty string if none was requested.
+pub fn dht_diagnostics_to_grpc(report: DhtDiagnosticsReport, graph: String) -> tari_rpc::GetDhtDiagnosticsResponse {
+    let summary = &report.store_and_forward;
+    let store_and_forward = tari_rpc::DhtStoreAndForwardSummary {
+        total: summary.total as u64,
+        num_high_priority: summary.num_high_priority as u64,
+        num_low_priority: summary.num_low_priority as u64,
+        num_encrypted: summary.num_encrypted as u64,
+        num_anonymous: summary.num_anonymous as u64,
+        total_body_bytes: summary.total_body_bytes as u64,
+        oldest: summary.oldest.map(to_timestamp).unwrap_or_default(),
+        newest: summary.newest.map(to_timestamp).unwrap_or_default(),
+    };
+
+    tari_rpc::GetDhtDiagnosticsResponse {
+        node_id: report.node_id.to_vec(),
+        num_neighbouring_nodes: report.num_neighbouring_nodes as u64,
+        peers: report.peers.iter().map(peer_to_grpc).collect(),
+        buckets: report.buckets.iter().map(bucket_to_grpc).collect(),
+        discovery_state: report.network_discovery.current_state.unwrap_or_default(),
+        discovery_transitions: report
+            .network_discovery
+            .transitions
+            .into_iter()
+            .map(|t| tari_rpc::DhtDiscoveryStateTransition {
+                timestamp: to_timestamp(t.timestamp.naive_utc()),
+                from_state: t.from_state,
+                event: t.event,
+                to_state: t.to_state,
+            })
+            .collect(),
+        store_and_forward: Some(store_and_forward),
+        graph,
+    }
+}
+
+fn peer_to_grpc(peer: &PeerDiagnostics) -> tari_rpc::DhtPeerDiagnostics {
+    tari_rpc::DhtPeerDiagnostics {
+        node_id: peer.node_id.to_vec(),
+        public_key: peer.public_key.to_vec(),
+        features: peer.features.bits(),
+        distance: peer.distance.to_bytes().to_vec(),
+        bucket: u32::from(peer.bucket),
+        is_neighbour: peer.is_neighbour,
+        is_connected: peer.is_connected,
+        is_banned: peer.is_banned,
+        is_offline: peer.is_offline,
+        last_seen: peer.last_seen.map(to_timestamp).unwrap_or_default(),
+        reputation_score: peer.reputation_score,
+    }
+}
+
+fn bucket_to_grpc(bucket: &BucketOccupancy) -> tari_rpc::DhtBucketOccupancy {
+    tari_rpc::DhtBucketOccupancy {
+        index: u32::from(bucket.index),
+        num_peers: bucket.num_peers as u64,
+        num_connected: bucket.num_connected as u64,
+        num_offline: bucket.num_offline as u64,
+        num_banned: bucket.num_banned as u64,
+    }
+}
+
+fn to_timestamp(dt: NaiveDateTime) -> u64 {
+    u64::try_from(dt.timestamp()).unwrap_or_default()
+}
@@ -22,5 +22,9 @@
 
 pub mod base_node_grpc_server;
 pub mod blocks;
+pub mod dht_diagnostics;
 pub mod hash_rate;
 pub mod helpers;
+#[cfg(feature = "metrics")]
+mod metrics;
+pub mod rate_limit;
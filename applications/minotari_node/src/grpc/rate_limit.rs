@@ -0,0 +1,385 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{
+    collections::HashMap,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::Instant,
+};
+
+use futures::{channel::mpsc, Stream, StreamExt};
+use log::*;
+use minotari_app_grpc::authentication::GrpcClientIdentity;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tonic::{Request, Status};
+
+use crate::config::{GrpcMethod, GrpcRateLimitConfig};
+#[cfg(feature = "metrics")]
+use crate::grpc::metrics;
+
+const LOG_TARGET: &str = "minotari::base_node::grpc::rate_limit";
+
+/// Idle clients are only forgotten once this many are being tracked
+const MAX_TRACKED_CLIENTS: usize = 1_000;
+
+/// A token bucket that refills at `requests_per_second` up to `burst_size` tokens
+#[derive(Debug, Clone)]
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(burst_size: u32, now: Instant) -> Self {
+        Self {
+            tokens: f64::from(burst_size),
+            last_refill: now,
+        }
+    }
+
+    fn refill(&mut self, config: &GrpcRateLimitConfig, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * f64::from(config.requests_per_second)).min(f64::from(config.burst_size));
+        self.last_refill = now;
+    }
+
+    fn try_take(&mut self, config: &GrpcRateLimitConfig, now: Instant) -> bool {
+        self.refill(config, now);
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn is_full(&self, config: &GrpcRateLimitConfig) -> bool {
+        self.tokens >= f64::from(config.burst_size)
+    }
+}
+
+/// Enforces the per-client request rate and per-method concurrency limits of the base node gRPC server
+pub struct GrpcRateLimiter {
+    config: GrpcRateLimitConfig,
+    clients: Mutex<HashMap<String, TokenBucket>>,
+    method_permits: Mutex<HashMap<GrpcMethod, Arc<Semaphore>>>,
+}
+
+impl GrpcRateLimiter {
+    pub fn new(config: GrpcRateLimitConfig) -> Self {
+        Self {
+            config,
+            clients: Mutex::new(HashMap::new()),
+            method_permits: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Admits a request for `method`, or returns a `RESOURCE_EXHAUSTED` status if the client is over its rate limit or
+    /// the method is already serving its maximum number of concurrent requests. The returned permit must be held
+    /// until the request, or the stream it returns, is complete.
+    pub fn check<T>(&self, method: GrpcMethod, request: &Request<T>) -> Result<GrpcRequestPermit, Status> {
+        let client = client_key(request);
+        if !self.try_take_client_token(&client, Instant::now()) {
+            warn!(
+                target: LOG_TARGET,
+                "gRPC client `{}` exceeded the rate limit calling `{}`", client, method
+            );
+            return Err(rejected(
+                method,
+                "rate_limit",
+                format!("Rate limit exceeded calling `{}`", method),
+            ));
+        }
+
+        let permit = match self.method_semaphore(method) {
+            Some(semaphore) => Some(semaphore.try_acquire_owned().map_err(|_| {
+                warn!(
+                    target: LOG_TARGET,
+                    "Too many concurrent `{}` requests, rejecting request from `{}`", method, client
+                );
+                rejected(
+                    method,
+                    "concurrency",
+                    format!("Too many concurrent `{}` requests, try again later", method),
+                )
+            })?),
+            None => None,
+        };
+
+        Ok(GrpcRequestPermit {
+            method,
+            max_stream_length: self.config.max_stream_length,
+            _permit: permit,
+        })
+    }
+
+    fn try_take_client_token(&self, client: &str, now: Instant) -> bool {
+        if self.config.requests_per_second == 0 {
+            return true;
+        }
+        let mut clients = self.clients.lock().expect("rate limiter lock poisoned");
+        if clients.len() >= MAX_TRACKED_CLIENTS && !clients.contains_key(client) {
+            // Clients with a full bucket have been idle long enough that forgetting them changes nothing
+            clients.retain(|_, bucket| {
+                bucket.refill(&self.config, now);
+                !bucket.is_full(&self.config)
+            });
+        }
+        clients
+            .entry(client.to_string())
+            .or_insert_with(|| TokenBucket::new(self.config.burst_size, now))
+            .try_take(&self.config, now)
+    }
+
+    fn method_semaphore(&self, method: GrpcMethod) -> Option<Arc<Semaphore>> {
+        let limit = self
+            .config
+            .method_concurrency_limits
+            .get(&method)
+            .copied()
+            .unwrap_or(self.config.max_concurrent_requests_per_method);
+        if limit == 0 {
+            return None;
+        }
+        let mut method_permits = self.method_permits.lock().expect("rate limiter lock poisoned");
+        Some(
+            method_permits
+                .entry(method)
+                .or_insert_with(|| Arc::new(Semaphore::new(limit)))
+                .clone(),
+        )
+    }
+}
+
+/// Holds a method's concurrency slot for as long as a request is being served
+pub struct GrpcRequestPermit {
+    method: GrpcMethod,
+    max_stream_length: u64,
+    _permit: Option<OwnedSemaphorePermit>,
+}
+
+impl GrpcRequestPermit {
+    /// Wraps the receiving end of a streaming response so that the permit is held until the stream is finished or
+    /// dropped, and so that the stream ends with a `RESOURCE_EXHAUSTED` status if it goes past the maximum length
+    pub fn limit_stream<T>(self, stream: mpsc::Receiver<Result<T, Status>>) -> LimitedStream<T> {
+        LimitedStream {
+            inner: stream,
+            remaining: self.max_stream_length,
+            exhausted: false,
+            permit: self,
+        }
+    }
+}
+
+/// A streaming response limited by a [GrpcRequestPermit]
+pub struct LimitedStream<T> {
+    inner: mpsc::Receiver<Result<T, Status>>,
+    /// The number of messages left before the stream is cut off, unless the permit has no maximum stream length
+    remaining: u64,
+    exhausted: bool,
+    permit: GrpcRequestPermit,
+}
+
+impl<T> Stream for LimitedStream<T> {
+    type Item = Result<T, Status>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.exhausted {
+            return Poll::Ready(None);
+        }
+        let limited = this.permit.max_stream_length > 0;
+        if limited && this.remaining == 0 {
+            // A stream of exactly the maximum length ends normally, only a further message is over the limit
+            match this.inner.poll_next_unpin(cx) {
+                Poll::Ready(Some(_)) => {},
+                Poll::Ready(None) => {
+                    this.exhausted = true;
+                    return Poll::Ready(None);
+                },
+                Poll::Pending => return Poll::Pending,
+            }
+            this.exhausted = true;
+            // Closing the receiver stops the task producing the stream
+            this.inner.close();
+            let method = this.permit.method;
+            return Poll::Ready(Some(Err(rejected(
+                method,
+                "stream_length",
+                format!(
+                    "`{}` stream exceeded the maximum length of {} messages",
+                    method, this.permit.max_stream_length
+                ),
+            ))));
+        }
+
+        let item = this.inner.poll_next_unpin(cx);
+        if limited {
+            if let Poll::Ready(Some(_)) = item {
+                this.remaining -= 1;
+            }
+        }
+        item
+    }
+}
+
+/// Requests are attributed to their authenticated client where there is one, and otherwise to their remote IP
+fn client_key<T>(request: &Request<T>) -> String {
+    if let Some(identity) = request.extensions().get::<GrpcClientIdentity>() {
+        return identity.0.clone();
+    }
+    request
+        .remote_addr()
+        .map(|addr| addr.ip().to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn rejected(method: GrpcMethod, reason: &str, message: String) -> Status {
+    #[cfg(feature = "metrics")]
+    metrics::rejected_requests(method, reason).inc();
+    #[cfg(not(feature = "metrics"))]
+    let _ = (method, reason);
+    Status::resource_exhausted(message)
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use futures::FutureExt;
+
+    use super::*;
+
+    fn config(
+        requests_per_second: u32,
+        burst_size: u32,
+        max_concurrent_requests_per_method: usize,
+    ) -> GrpcRateLimitConfig {
+        GrpcRateLimitConfig {
+            requests_per_second,
+            burst_size,
+            max_concurrent_requests_per_method,
+            method_concurrency_limits: HashMap::new(),
+            max_stream_length: 0,
+        }
+    }
+
+    #[test]
+    fn it_limits_clients_to_the_burst_size_and_refills() {
+        let limiter = GrpcRateLimiter::new(config(2, 3, 0));
+        let now = Instant::now();
+
+        // the burst is allowed, after which the client has to wait for a refill
+        for _ in 0..3 {
+            assert!(limiter.try_take_client_token("a", now));
+        }
+        assert!(!limiter.try_take_client_token("a", now));
+
+        // other clients have their own bucket
+        assert!(limiter.try_take_client_token("b", now));
+
+        // at 2 requests per second a token is available after half a second
+        let later = now + Duration::from_millis(500);
+        assert!(limiter.try_take_client_token("a", later));
+        assert!(!limiter.try_take_client_token("a", later));
+    }
+
+    #[test]
+    fn it_does_not_rate_limit_when_disabled() {
+        let limiter = GrpcRateLimiter::new(config(0, 0, 0));
+        let now = Instant::now();
+        for _ in 0..1000 {
+            assert!(limiter.try_take_client_token("a", now));
+        }
+        assert!(limiter.clients.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn it_caps_concurrent_requests_per_method() {
+        let mut config = config(0, 0, 2);
+        config.method_concurrency_limits.insert(GrpcMethod::GetBlocks, 1);
+        let limiter = GrpcRateLimiter::new(config);
+
+        let first = limiter.check(GrpcMethod::GetBlocks, &Request::new(())).unwrap();
+        let err = limiter.check(GrpcMethod::GetBlocks, &Request::new(())).err().unwrap();
+        assert_eq!(err.code(), tonic::Code::ResourceExhausted);
+
+        // other methods use the default limit
+        let _a = limiter.check(GrpcMethod::SearchUtxos, &Request::new(())).unwrap();
+        let _b = limiter.check(GrpcMethod::SearchUtxos, &Request::new(())).unwrap();
+        assert!(limiter.check(GrpcMethod::SearchUtxos, &Request::new(())).is_err());
+
+        // the slot is released when the permit is dropped
+        drop(first);
+        assert!(limiter.check(GrpcMethod::GetBlocks, &Request::new(())).is_ok());
+    }
+
+    fn limited_stream(max_stream_length: u64, messages: u64) -> LimitedStream<u64> {
+        let mut config = config(0, 0, 0);
+        config.max_stream_length = max_stream_length;
+        let limiter = GrpcRateLimiter::new(config);
+        let (mut tx, rx) = mpsc::channel(messages as usize);
+        for i in 0..messages {
+            tx.try_send(Ok(i)).unwrap();
+        }
+        limiter
+            .check(GrpcMethod::GetBlocks, &Request::new(()))
+            .unwrap()
+            .limit_stream(rx)
+    }
+
+    #[test]
+    fn it_ends_a_stream_of_exactly_the_maximum_length_normally() {
+        let items = limited_stream(3, 3).collect::<Vec<_>>().now_or_never().unwrap();
+        assert_eq!(items.len(), 3);
+        assert!(items.iter().all(Result::is_ok));
+    }
+
+    #[test]
+    fn it_cuts_off_a_stream_past_the_maximum_length() {
+        let items = limited_stream(3, 4).collect::<Vec<_>>().now_or_never().unwrap();
+        assert_eq!(items.len(), 4);
+        assert!(items[..3].iter().all(Result::is_ok));
+        assert_eq!(items[3].as_ref().unwrap_err().code(), tonic::Code::ResourceExhausted);
+    }
+
+    #[test]
+    fn it_does_not_limit_streams_without_a_maximum_length() {
+        let items = limited_stream(0, 10).collect::<Vec<_>>().now_or_never().unwrap();
+        assert_eq!(items.len(), 10);
+        assert!(items.iter().all(Result::is_ok));
+    }
+
+    #[test]
+    fn it_identifies_clients() {
+        let request = Request::new(());
+        assert_eq!(client_key(&request), "unknown");
+
+        let mut request = Request::new(());
+        request
+            .extensions_mut()
+            .insert(GrpcClientIdentity("token:0123456789abcdef".to_string()));
+        assert_eq!(client_key(&request), "token:0123456789abcdef");
+    }
+}
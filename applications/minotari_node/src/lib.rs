@@ -42,7 +42,10 @@ use std::{process, sync::Arc};
 use commands::{cli_loop::CliLoop, command::CommandContext};
 use futures::FutureExt;
 use log::*;
-use minotari_app_grpc::{authentication::ServerAuthenticationInterceptor, tls::identity::read_identity};
+use minotari_app_grpc::{
+    authentication::{GrpcMethodService, ServerAuthenticationInterceptor},
+    tls::identity::read_server_tls_config,
+};
 use minotari_app_utilities::common_cli_args::CommonCliArgs;
 use tari_common::{
     configuration::bootstrap::{grpc_default_port, ApplicationType},
@@ -52,7 +55,7 @@ use tari_common_types::grpc_authentication::GrpcAuthentication;
 use tari_comms::{multiaddr::Multiaddr, utils::multiaddr::multiaddr_to_socketaddr, NodeIdentity};
 use tari_shutdown::{Shutdown, ShutdownSignal};
 use tokio::task;
-use tonic::transport::{Identity, Server, ServerTlsConfig};
+use tonic::transport::{Server, ServerTlsConfig};
 
 use crate::cli::Cli;
 pub use crate::config::{ApplicationConfig, BaseNodeConfig, DatabaseType};
@@ -139,14 +142,15 @@ pub async fn run_base_node_with_cli(
             grpc::base_node_grpc_server::BaseNodeGrpcServer::from_base_node_context(&ctx, config.base_node.clone());
         let auth = config.base_node.grpc_authentication.clone();
 
-        let mut tls_identity = None;
-        if config.base_node.grpc_tls_enabled {
-            tls_identity = read_identity(config.base_node.config_dir.clone())
-                .await
-                .map(Some)
-                .map_err(|e| ExitError::new(ExitCode::TlsConfigurationError, e.to_string()))?;
-        }
-        task::spawn(run_grpc(grpc, grpc_address, auth, tls_identity, shutdown.to_signal()));
+        let tls_config = read_server_tls_config(
+            config.base_node.config_dir.clone(),
+            "node_ca.pem",
+            &auth,
+            config.base_node.grpc_tls_enabled,
+        )
+        .await
+        .map_err(|e| ExitError::new(ExitCode::TlsConfigurationError, e.to_string()))?;
+        task::spawn(run_grpc(grpc, grpc_address, auth, tls_config, shutdown.to_signal()));
     }
 
     // Run, node, run!
@@ -181,18 +185,21 @@ async fn run_grpc(
     grpc: grpc::base_node_grpc_server::BaseNodeGrpcServer,
     grpc_address: Multiaddr,
     auth_config: GrpcAuthentication,
-    tls_identity: Option<Identity>,
+    tls_config: Option<ServerTlsConfig>,
     interrupt_signal: ShutdownSignal,
 ) -> Result<(), anyhow::Error> {
     info!(target: LOG_TARGET, "Starting GRPC on {}", grpc_address);
 
     let grpc_address = multiaddr_to_socketaddr(&grpc_address)?;
     let auth = ServerAuthenticationInterceptor::new(auth_config)
-        .ok_or(anyhow::anyhow!("Unable to prepare server gRPC authentication"))?;
-    let service = minotari_app_grpc::tari_rpc::base_node_server::BaseNodeServer::with_interceptor(grpc, auth);
-
-    let mut server_builder = if let Some(identity) = tls_identity {
-        Server::builder().tls_config(ServerTlsConfig::new().identity(identity))?
+        .ok_or(anyhow::anyhow!("Unable to prepare server gRPC authentication"))?
+        .with_method_scopes(grpc::base_node_grpc_server::required_scope);
+    let service = GrpcMethodService::new(
+        minotari_app_grpc::tari_rpc::base_node_server::BaseNodeServer::with_interceptor(grpc, auth),
+    );
+
+    let mut server_builder = if let Some(tls_config) = tls_config {
+        Server::builder().tls_config(tls_config)?
     } else {
         Server::builder()
     };
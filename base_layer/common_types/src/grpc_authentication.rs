@@ -33,6 +33,46 @@ pub enum GrpcAuthentication {
         #[serde(deserialize_with = "deserialize_safe_password")]
         password: SafePassword,
     },
+    /// Bearer tokens, each granting access to the gRPC methods allowed by its scope
+    Token { tokens: Vec<GrpcAccessToken> },
+    /// Mutual TLS, where clients are identified by the SHA-256 fingerprint of their DER encoded certificate
+    ClientCertificate {
+        client_certificates: Vec<GrpcClientCertificate>,
+    },
+}
+
+/// The level of access granted to a gRPC client. Scopes are ordered so that a higher scope includes all the methods
+/// allowed by the lower ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GrpcScope {
+    /// Methods that only query state
+    Read,
+    /// Methods that create or send transactions
+    Spend,
+    /// Everything, including methods that change node or wallet configuration
+    Admin,
+}
+
+impl GrpcScope {
+    /// Returns true if a client with this scope may call a method that requires the `required` scope
+    pub fn allows(self, required: GrpcScope) -> bool {
+        self >= required
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrpcAccessToken {
+    #[serde(deserialize_with = "deserialize_safe_password")]
+    pub token: SafePassword,
+    pub scope: GrpcScope,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrpcClientCertificate {
+    /// Hex encoded SHA-256 fingerprint of the client certificate, as printed by the `create-tls-certs` command
+    pub fingerprint: String,
+    pub scope: GrpcScope,
 }
 
 impl GrpcAuthentication {
@@ -42,6 +82,10 @@ impl GrpcAuthentication {
             _ => None,
         }
     }
+
+    pub fn requires_client_certificate(&self) -> bool {
+        matches!(self, GrpcAuthentication::ClientCertificate { .. })
+    }
 }
 
 fn deserialize_safe_password<'de, D>(deserializer: D) -> Result<SafePassword, D::Error>
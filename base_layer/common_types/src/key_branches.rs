@@ -40,6 +40,8 @@ pub enum TransactionKeyManagerBranch {
     Spend = Branch::Spend as u8,
     RandomKey = Branch::RandomKey as u8,
     PreMine = Branch::PreMine as u8,
+    Multisig = Branch::Multisig as u8,
+    SubAddress = Branch::SubAddress as u8,
 }
 
 pub const DATA_ENCRYPTION: &str = "data encryption";
@@ -51,6 +53,8 @@ pub const SENDER_OFFSET: &str = "sender offset";
 pub const ONE_SIDED_SENDER_OFFSET: &str = "one sided sender offset";
 pub const RANDOM_KEY: &str = "random key";
 pub const PRE_MINE: &str = "pre-mine";
+pub const MULTISIG: &str = "multisig";
+pub const SUB_ADDRESS: &str = "sub-address";
 
 impl TransactionKeyManagerBranch {
     /// Warning: Changing these strings will affect the backwards compatibility of the wallet with older databases or
@@ -67,6 +71,8 @@ impl TransactionKeyManagerBranch {
             TransactionKeyManagerBranch::RandomKey => RANDOM_KEY.to_string(),
             TransactionKeyManagerBranch::Spend => WALLET_COMMS_AND_SPEND_KEY_BRANCH.to_string(),
             TransactionKeyManagerBranch::PreMine => PRE_MINE.to_string(),
+            TransactionKeyManagerBranch::Multisig => MULTISIG.to_string(),
+            TransactionKeyManagerBranch::SubAddress => SUB_ADDRESS.to_string(),
         }
     }
 
@@ -82,6 +88,8 @@ impl TransactionKeyManagerBranch {
             RANDOM_KEY => TransactionKeyManagerBranch::RandomKey,
             WALLET_COMMS_AND_SPEND_KEY_BRANCH => TransactionKeyManagerBranch::Spend,
             PRE_MINE => TransactionKeyManagerBranch::PreMine,
+            MULTISIG => TransactionKeyManagerBranch::Multisig,
+            SUB_ADDRESS => TransactionKeyManagerBranch::SubAddress,
             _ => TransactionKeyManagerBranch::Nonce,
         }
     }
@@ -102,6 +110,8 @@ impl TransactionKeyManagerBranch {
             Some(Branch::Spend) => Some(TransactionKeyManagerBranch::Spend),
             Some(Branch::RandomKey) => Some(TransactionKeyManagerBranch::RandomKey),
             Some(Branch::PreMine) => Some(TransactionKeyManagerBranch::PreMine),
+            Some(Branch::Multisig) => Some(TransactionKeyManagerBranch::Multisig),
+            Some(Branch::SubAddress) => Some(TransactionKeyManagerBranch::SubAddress),
             None => None,
         }
     }
@@ -118,11 +128,13 @@ mod test {
             DATA_ENCRYPTION,
             KERNEL_NONCE,
             METADATA_EPHEMERAL_NONCE,
+            MULTISIG,
             NONCE,
             ONE_SIDED_SENDER_OFFSET,
             PRE_MINE,
             RANDOM_KEY,
             SENDER_OFFSET,
+            SUB_ADDRESS,
         },
         WALLET_COMMS_AND_SPEND_KEY_BRANCH,
     };
@@ -173,6 +185,12 @@ mod test {
                 RANDOM_KEY,
             ),
             (Branch::PreMine as u8, TransactionKeyManagerBranch::PreMine, PRE_MINE),
+            (Branch::Multisig as u8, TransactionKeyManagerBranch::Multisig, MULTISIG),
+            (
+                Branch::SubAddress as u8,
+                TransactionKeyManagerBranch::SubAddress,
+                SUB_ADDRESS,
+            ),
         ];
 
         for (expected_byte, branch, key) in &mappings {
@@ -237,6 +255,18 @@ mod test {
                     assert_eq!(&branch.get_branch_key(), *key);
                     assert_eq!(TransactionKeyManagerBranch::from_key(key), *branch);
                 },
+                TransactionKeyManagerBranch::Multisig => {
+                    assert_eq!(branch.as_byte(), *expected_byte);
+                    assert_eq!(TransactionKeyManagerBranch::from_byte(*expected_byte), Some(*branch));
+                    assert_eq!(&branch.get_branch_key(), *key);
+                    assert_eq!(TransactionKeyManagerBranch::from_key(key), *branch);
+                },
+                TransactionKeyManagerBranch::SubAddress => {
+                    assert_eq!(branch.as_byte(), *expected_byte);
+                    assert_eq!(TransactionKeyManagerBranch::from_byte(*expected_byte), Some(*branch));
+                    assert_eq!(&branch.get_branch_key(), *key);
+                    assert_eq!(TransactionKeyManagerBranch::from_key(key), *branch);
+                },
             }
         }
     }
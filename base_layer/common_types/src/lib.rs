@@ -32,6 +32,7 @@ pub mod epoch;
 pub mod grpc_authentication;
 pub mod key_branches;
 mod max_size;
+pub mod payment_request;
 pub mod serializers;
 pub mod tari_address;
 pub mod transaction;
@@ -0,0 +1,428 @@
+//  Copyright 2024, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! A payment request (invoice) lets a receiver ask for a specific amount, tagged with a payment id the receiving
+//! wallet can match once the payment arrives. Requests are shared as `tari://` URIs that extend the plain
+//! `tari://<network>/transactions/send?tariAddress=<address>` link handed out for an address.
+
+use std::{
+    fmt,
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use blake2::Blake2b;
+use digest::consts::U64;
+use rand::rngs::OsRng;
+use serde::{Deserialize, Serialize};
+use tari_common::configuration::Network;
+use tari_crypto::{hash_domain, hashing::DomainSeparatedHasher, keys::PublicKey as PublicKeyT};
+use tari_utilities::{
+    hex::{from_hex, Hex},
+    ByteArray,
+};
+use thiserror::Error;
+
+use crate::{
+    tari_address::TariAddress,
+    types::{PrivateKey, PublicKey, Signature},
+};
+
+hash_domain!(
+    PaymentRequestHashDomain,
+    "com.tari.base_layer.common_types.payment_request",
+    0
+);
+
+const URI_SCHEME: &str = "tari://";
+const URI_PATH: &str = "transactions/send";
+
+#[derive(Debug, Error, PartialEq)]
+pub enum PaymentRequestError {
+    #[error("Not a Tari payment request URI")]
+    InvalidUri,
+    #[error("Payment request is missing the `{0}` field")]
+    MissingField(&'static str),
+    #[error("Payment request field `{field}` is invalid: {reason}")]
+    InvalidField { field: &'static str, reason: String },
+    #[error("Payment request network `{uri}` does not match the address network `{address}`")]
+    NetworkMismatch { uri: Network, address: Network },
+    #[error("Payment request signature is not valid for the receiving address")]
+    InvalidSignature,
+    #[error("Secret key does not belong to the receiving address")]
+    KeyMismatch,
+}
+
+/// A request for payment of `amount` micro Minotari to `address`. The payment id is the data the sender should attach
+/// to the payment as an open payment id so that the receiver can match the payment to this request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PaymentRequest {
+    address: TariAddress,
+    amount: u64,
+    payment_id: Vec<u8>,
+    expiry: Option<u64>,
+    memo: Option<String>,
+    signature: Option<Signature>,
+}
+
+impl PaymentRequest {
+    /// Creates a new unsigned payment request. `expiry` is a unix timestamp in seconds.
+    pub fn new(
+        address: TariAddress,
+        amount: u64,
+        payment_id: Vec<u8>,
+        expiry: Option<u64>,
+        memo: Option<String>,
+    ) -> Self {
+        Self {
+            address,
+            amount,
+            payment_id,
+            expiry,
+            memo,
+            signature: None,
+        }
+    }
+
+    pub fn address(&self) -> &TariAddress {
+        &self.address
+    }
+
+    /// The requested amount in micro Minotari
+    pub fn amount(&self) -> u64 {
+        self.amount
+    }
+
+    pub fn payment_id(&self) -> &[u8] {
+        &self.payment_id
+    }
+
+    /// The unix timestamp in seconds after which the request should no longer be paid
+    pub fn expiry(&self) -> Option<u64> {
+        self.expiry
+    }
+
+    pub fn memo(&self) -> Option<&str> {
+        self.memo.as_deref()
+    }
+
+    pub fn signature(&self) -> Option<&Signature> {
+        self.signature.as_ref()
+    }
+
+    /// Returns true if the request has an expiry at or before the given unix timestamp in seconds
+    pub fn is_expired_at(&self, timestamp: u64) -> bool {
+        self.expiry.map_or(false, |expiry| expiry <= timestamp)
+    }
+
+    /// Signs the request with the secret key belonging to the spend key of the receiving address
+    pub fn sign(&mut self, secret_key: &PrivateKey) -> Result<(), PaymentRequestError> {
+        if &PublicKey::from_secret_key(secret_key) != self.address.public_spend_key() {
+            return Err(PaymentRequestError::KeyMismatch);
+        }
+        let (secret_nonce, public_nonce) = PublicKey::random_keypair(&mut OsRng);
+        let challenge = self.construct_challenge(&public_nonce);
+        let signature = Signature::sign_raw_uniform(secret_key, secret_nonce, &challenge)
+            .expect("Sign cannot fail with 64-byte challenge and a RistrettoPublicKey");
+        self.signature = Some(signature);
+        Ok(())
+    }
+
+    /// Returns true if the request carries a signature made by the spend key of the receiving address
+    pub fn has_valid_signature(&self) -> bool {
+        match &self.signature {
+            Some(signature) => {
+                let challenge = self.construct_challenge(signature.get_public_nonce());
+                signature.verify_raw_uniform(self.address.public_spend_key(), &challenge)
+            },
+            None => false,
+        }
+    }
+
+    fn construct_challenge(&self, public_nonce: &PublicKey) -> [u8; 64] {
+        let hasher = DomainSeparatedHasher::<Blake2b<U64>, PaymentRequestHashDomain>::new_with_label("signature")
+            .chain(self.address.public_spend_key().as_bytes())
+            .chain(public_nonce.as_bytes())
+            .chain(self.address.to_vec())
+            .chain(self.amount.to_le_bytes())
+            .chain((self.payment_id.len() as u64).to_le_bytes())
+            .chain(&self.payment_id)
+            .chain(self.expiry.unwrap_or_default().to_le_bytes())
+            .chain(self.memo.as_deref().unwrap_or_default().as_bytes());
+        digest::Digest::finalize(hasher).into()
+    }
+
+    /// Encodes the request as a `tari://` URI
+    pub fn to_uri(&self) -> String {
+        let mut uri = format!(
+            "{}{}/{}?tariAddress={}&amount={}",
+            URI_SCHEME,
+            self.address.network(),
+            URI_PATH,
+            self.address.to_base58(),
+            self.amount
+        );
+        if !self.payment_id.is_empty() {
+            uri.push_str(&format!("&paymentId={}", self.payment_id.to_hex()));
+        }
+        if let Some(expiry) = self.expiry {
+            uri.push_str(&format!("&expiry={}", expiry));
+        }
+        if let Some(memo) = &self.memo {
+            uri.push_str(&format!("&memo={}", percent_encode(memo)));
+        }
+        if let Some(signature) = &self.signature {
+            uri.push_str(&format!(
+                "&signature={}{}",
+                signature.get_public_nonce().to_hex(),
+                signature.get_signature().to_hex()
+            ));
+        }
+        uri
+    }
+
+    /// Decodes a request from a `tari://` URI. A signature, if present, must be valid for the receiving address.
+    pub fn from_uri(uri: &str) -> Result<Self, PaymentRequestError> {
+        let rest = uri
+            .trim()
+            .strip_prefix(URI_SCHEME)
+            .ok_or(PaymentRequestError::InvalidUri)?;
+        let (network, rest) = rest.split_once('/').ok_or(PaymentRequestError::InvalidUri)?;
+        let (path, query) = rest.split_once('?').ok_or(PaymentRequestError::InvalidUri)?;
+        if path != URI_PATH {
+            return Err(PaymentRequestError::InvalidUri);
+        }
+        let network = Network::from_str(network).map_err(|e| PaymentRequestError::InvalidField {
+            field: "network",
+            reason: e.to_string(),
+        })?;
+
+        let mut address = None;
+        let mut amount = None;
+        let mut payment_id = Vec::new();
+        let mut expiry = None;
+        let mut memo = None;
+        let mut signature = None;
+        for pair in query.split('&').filter(|p| !p.is_empty()) {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            match key {
+                "tariAddress" => {
+                    address = Some(
+                        TariAddress::from_str(value).map_err(|e| PaymentRequestError::InvalidField {
+                            field: "tariAddress",
+                            reason: e.to_string(),
+                        })?,
+                    )
+                },
+                "amount" => amount = Some(parse_u64("amount", value)?),
+                "paymentId" => {
+                    payment_id = from_hex(value).map_err(|e| PaymentRequestError::InvalidField {
+                        field: "paymentId",
+                        reason: e.to_string(),
+                    })?
+                },
+                "expiry" => expiry = Some(parse_u64("expiry", value)?),
+                "memo" => memo = Some(percent_decode(value)?),
+                "signature" => signature = Some(parse_signature(value)?),
+                // Unknown fields are ignored so that newer wallets can extend the format
+                _ => {},
+            }
+        }
+
+        let address = address.ok_or(PaymentRequestError::MissingField("tariAddress"))?;
+        if address.network() != network {
+            return Err(PaymentRequestError::NetworkMismatch {
+                uri: network,
+                address: address.network(),
+            });
+        }
+        let request = Self {
+            address,
+            amount: amount.ok_or(PaymentRequestError::MissingField("amount"))?,
+            payment_id,
+            expiry,
+            memo,
+            signature,
+        };
+        if request.signature.is_some() && !request.has_valid_signature() {
+            return Err(PaymentRequestError::InvalidSignature);
+        }
+        Ok(request)
+    }
+}
+
+impl Display for PaymentRequest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_uri())
+    }
+}
+
+impl FromStr for PaymentRequest {
+    type Err = PaymentRequestError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_uri(s)
+    }
+}
+
+fn parse_u64(field: &'static str, value: &str) -> Result<u64, PaymentRequestError> {
+    value.parse::<u64>().map_err(|e| PaymentRequestError::InvalidField {
+        field,
+        reason: e.to_string(),
+    })
+}
+
+fn parse_signature(value: &str) -> Result<Signature, PaymentRequestError> {
+    let invalid = |reason: String| PaymentRequestError::InvalidField {
+        field: "signature",
+        reason,
+    };
+    let bytes = from_hex(value).map_err(|e| invalid(e.to_string()))?;
+    if bytes.len() != 64 {
+        return Err(invalid(format!("expected 64 bytes, got {}", bytes.len())));
+    }
+    let public_nonce = PublicKey::from_canonical_bytes(&bytes[..32]).map_err(|e| invalid(e.to_string()))?;
+    let signature = PrivateKey::from_canonical_bytes(&bytes[32..]).map_err(|e| invalid(e.to_string()))?;
+    Ok(Signature::new(public_nonce, signature))
+}
+
+/// Percent-encodes everything except the URI unreserved characters
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(char::from(byte));
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+fn percent_decode(value: &str) -> Result<String, PaymentRequestError> {
+    let invalid = |reason: &str| PaymentRequestError::InvalidField {
+        field: "memo",
+        reason: reason.to_string(),
+    };
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = bytes.get(i + 1..i + 3).ok_or_else(|| invalid("truncated escape"))?;
+            let hex = std::str::from_utf8(hex).map_err(|_| invalid("invalid escape"))?;
+            decoded.push(u8::from_str_radix(hex, 16).map_err(|_| invalid("invalid escape"))?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(decoded).map_err(|_| invalid("not valid UTF-8"))
+}
+
+#[cfg(test)]
+mod test {
+    use tari_crypto::keys::SecretKey;
+
+    use super::*;
+
+    fn address_and_key() -> (TariAddress, PrivateKey) {
+        let secret_key = PrivateKey::random(&mut OsRng);
+        let view_key = PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng));
+        let address = TariAddress::new_dual_address_with_default_features(
+            view_key,
+            PublicKey::from_secret_key(&secret_key),
+            Network::Esmeralda,
+        );
+        (address, secret_key)
+    }
+
+    #[test]
+    fn it_round_trips_through_a_uri() {
+        let (address, _) = address_and_key();
+        let request = PaymentRequest::new(
+            address,
+            1_234_567,
+            b"invoice-42".to_vec(),
+            Some(1_700_000_000),
+            Some("Coffee & cake, 2x".to_string()),
+        );
+        let uri = request.to_uri();
+        assert!(uri.starts_with("tari://esmeralda/transactions/send?tariAddress="));
+        assert_eq!(PaymentRequest::from_uri(&uri).unwrap(), request);
+    }
+
+    #[test]
+    fn it_verifies_signatures() {
+        let (address, secret_key) = address_and_key();
+        let mut request = PaymentRequest::new(address, 1000, vec![1, 2, 3], None, None);
+        assert!(!request.has_valid_signature());
+        request.sign(&secret_key).unwrap();
+        assert!(request.has_valid_signature());
+
+        let decoded = PaymentRequest::from_uri(&request.to_uri()).unwrap();
+        assert!(decoded.has_valid_signature());
+
+        // Tampering with the amount invalidates the signature
+        let tampered = request.to_uri().replace("amount=1000", "amount=1");
+        assert_eq!(
+            PaymentRequest::from_uri(&tampered).unwrap_err(),
+            PaymentRequestError::InvalidSignature
+        );
+
+        // Only the key of the receiving address may sign
+        let (_, other_key) = address_and_key();
+        assert_eq!(request.sign(&other_key).unwrap_err(), PaymentRequestError::KeyMismatch);
+    }
+
+    #[test]
+    fn it_rejects_invalid_uris() {
+        let (address, _) = address_and_key();
+        let plain_address_uri = format!("tari://esmeralda/transactions/send?tariAddress={}", address.to_base58());
+        assert_eq!(
+            PaymentRequest::from_uri(&plain_address_uri).unwrap_err(),
+            PaymentRequestError::MissingField("amount")
+        );
+        let wrong_network = format!(
+            "tari://nextnet/transactions/send?tariAddress={}&amount=1",
+            address.to_base58()
+        );
+        assert!(matches!(
+            PaymentRequest::from_uri(&wrong_network).unwrap_err(),
+            PaymentRequestError::NetworkMismatch { .. }
+        ));
+        assert_eq!(
+            PaymentRequest::from_uri("https://example.com").unwrap_err(),
+            PaymentRequestError::InvalidUri
+        );
+    }
+
+    #[test]
+    fn it_checks_expiry() {
+        let (address, _) = address_and_key();
+        let request = PaymentRequest::new(address.clone(), 1, vec![], Some(100), None);
+        assert!(!request.is_expired_at(99));
+        assert!(request.is_expired_at(100));
+        assert!(!PaymentRequest::new(address, 1, vec![], None, None).is_expired_at(u64::MAX));
+    }
+}
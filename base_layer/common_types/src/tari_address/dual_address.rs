@@ -74,6 +74,17 @@ impl DualAddress {
         }
     }
 
+    /// Creates a new sub-address from the wallet's public view key and a sub-address public spend key. Sub-addresses
+    /// only support standard one-sided payments, as stealth payments are derived from the wallet's main spend key.
+    pub fn new_sub_address(view_key: PublicKey, spend_key: PublicKey, network: Network) -> DualAddress {
+        Self {
+            network,
+            features: TariAddressFeatures::create_sub_address(),
+            public_view_key: view_key,
+            public_spend_key: spend_key,
+        }
+    }
+
     /// helper function to convert emojis to u8
     pub fn emoji_to_bytes(emoji: &str) -> Result<Vec<u8>, TariAddressError> {
         // The string must be the correct size, including the checksum
@@ -422,6 +433,28 @@ mod test {
         assert_eq!(address_emoji.network(), address.network());
         assert_eq!(address_emoji.features(), address.features());
     }
+    #[test]
+    /// Test encoding for dual tari sub-address
+    fn sub_address_encoding() {
+        let mut rng = rand::thread_rng();
+        let view_key = PublicKey::from_secret_key(&PrivateKey::random(&mut rng));
+        let spend_key = PublicKey::from_secret_key(&PrivateKey::random(&mut rng));
+
+        let address = DualAddress::new_sub_address(view_key.clone(), spend_key.clone(), Network::Esmeralda);
+        assert_eq!(address.features(), TariAddressFeatures::create_sub_address());
+        assert!(address.features().contains(TariAddressFeatures::ONE_SIDED));
+        assert!(!address.features().contains(TariAddressFeatures::INTERACTIVE));
+
+        let address_base58 = DualAddress::from_base58(&address.to_base58()).unwrap();
+        assert_eq!(address_base58, address);
+        let address_emoji = DualAddress::from_emoji_string(&address.to_emoji_string()).unwrap();
+        assert_eq!(address_emoji, address);
+        let address_hex = DualAddress::from_hex(&address.to_hex()).unwrap();
+        assert_eq!(address_hex.public_view_key(), &view_key);
+        assert_eq!(address_hex.public_spend_key(), &spend_key);
+        assert_eq!(address_hex.features(), TariAddressFeatures::create_sub_address());
+    }
+
     #[test]
     /// Test invalid size
     fn invalid_size() {
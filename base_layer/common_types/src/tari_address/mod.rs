@@ -57,6 +57,8 @@ bitflags! {
         const INTERACTIVE = 2u8;
         ///one sided payment
         const ONE_SIDED = 1u8;
+        ///sub-address derived from the wallet seed, only standard one-sided payments can be received
+        const SUB_ADDRESS = 8u8;
     }
 }
 
@@ -73,6 +75,10 @@ impl TariAddressFeatures {
         TariAddressFeatures::INTERACTIVE | TariAddressFeatures::ONE_SIDED
     }
 
+    pub fn create_sub_address() -> TariAddressFeatures {
+        TariAddressFeatures::ONE_SIDED | TariAddressFeatures::SUB_ADDRESS
+    }
+
     pub fn as_u8(&self) -> u8 {
         self.0
     }
@@ -96,6 +102,9 @@ impl fmt::Display for TariAddressFeatures {
         if self.contains(TariAddressFeatures::ONE_SIDED) {
             write!(f, "One-sided,")?;
         }
+        if self.contains(TariAddressFeatures::SUB_ADDRESS) {
+            write!(f, "Sub-address,")?;
+        }
         Ok(())
     }
 }
@@ -143,6 +152,11 @@ impl TariAddress {
         TariAddress::Dual(DualAddress::new(view_key, spend_key, network, features))
     }
 
+    /// Creates a new sub-address from the wallet's public view key and the sub-address public spend key
+    pub fn new_sub_address(view_key: PublicKey, spend_key: PublicKey, network: Network) -> Self {
+        TariAddress::Dual(DualAddress::new_sub_address(view_key, spend_key, network))
+    }
+
     /// Creates a new Tari Address from the provided public keys, network and features
     pub fn new_single_address(spend_key: PublicKey, network: Network, features: TariAddressFeatures) -> Self {
         TariAddress::Single(SingleAddress::new(spend_key, network, features))
@@ -239,6 +253,11 @@ impl TariAddress {
         }
     }
 
+    /// Returns true if this is a sub-address of another wallet address
+    pub fn is_sub_address(&self) -> bool {
+        self.features().contains(TariAddressFeatures::SUB_ADDRESS)
+    }
+
     /// Gets the checksum from the Tari Address
     pub fn calculate_checksum(&self) -> u8 {
         let bytes = self.to_vec();
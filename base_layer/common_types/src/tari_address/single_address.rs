@@ -125,6 +125,10 @@ impl SingleAddress {
         }
         let network = Network::try_from(bytes[0]).map_err(|_| TariAddressError::InvalidNetwork)?;
         let features = TariAddressFeatures::from_bits(bytes[1]).ok_or(TariAddressError::InvalidFeatures)?;
+        // Sub-addresses need a view key, so they are always dual addresses
+        if features.contains(TariAddressFeatures::SUB_ADDRESS) {
+            return Err(TariAddressError::InvalidFeatures);
+        }
         let public_spend_key =
             PublicKey::from_canonical_bytes(&bytes[2..34]).map_err(|_| TariAddressError::CannotRecoverPublicKey)?;
         Ok(Self {
@@ -39,6 +39,14 @@ pub enum WalletType {
     ProvidedKeys(ProvidedKeysWallet),
 }
 
+impl WalletType {
+    /// A watch-only wallet holds the view key but not the spend key, so it can scan for and track outputs but cannot
+    /// sign transactions spending them
+    pub fn is_watch_only(&self) -> bool {
+        matches!(self, WalletType::ProvidedKeys(wallet) if wallet.private_spend_key.is_none())
+    }
+}
+
 impl Display for WalletType {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
@@ -79,6 +79,7 @@ pub fn setup_contacts_service<T: ContactsBackend + 'static>(
         auxiliary_tcp_listener_address: None,
         datastore_path: tempdir().unwrap().into_path(),
         peer_database_name: random::string(8),
+        peer_database_backend: Default::default(),
         max_concurrent_inbound_tasks: 10,
         max_concurrent_outbound_tasks: 10,
         dht: DhtConfig {
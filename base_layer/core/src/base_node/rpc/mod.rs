@@ -43,6 +43,8 @@ use crate::{
         base_node::{
             FetchMatchingUtxos,
             FetchUtxosResponse,
+            GetBlockFiltersRequest,
+            GetBlockFiltersResponse,
             GetMempoolFeePerGramStatsRequest,
             GetMempoolFeePerGramStatsResponse,
             QueryDeletedRequest,
@@ -119,6 +121,12 @@ pub trait BaseNodeWalletService: Send + Sync + 'static {
         &self,
         request: Request<GetMempoolFeePerGramStatsRequest>,
     ) -> Result<Response<GetMempoolFeePerGramStatsResponse>, RpcStatus>;
+
+    #[rpc(method = 13)]
+    async fn get_block_filters(
+        &self,
+        request: Request<GetBlockFiltersRequest>,
+    ) -> Result<Response<GetBlockFiltersResponse>, RpcStatus>;
 }
 
 #[cfg(feature = "base_node")]
@@ -40,8 +40,11 @@ use crate::{
     proto,
     proto::{
         base_node::{
+            BlockFilter as BlockFilterProto,
             FetchMatchingUtxos,
             FetchUtxosResponse,
+            GetBlockFiltersRequest,
+            GetBlockFiltersResponse,
             GetMempoolFeePerGramStatsRequest,
             GetMempoolFeePerGramStatsResponse,
             QueryDeletedData,
@@ -68,6 +71,7 @@ use crate::{
 
 const LOG_TARGET: &str = "c::base_node::rpc";
 const MAX_QUERY_DELETED_HASHES: usize = 1000;
+const MAX_BLOCK_FILTERS_PER_REQUEST: u64 = 1000;
 
 pub struct BaseNodeWalletRpcService<B> {
     db: AsyncBlockchainDb<B>,
@@ -677,4 +681,57 @@ impl<B: BlockchainBackend + 'static> BaseNodeWalletService for BaseNodeWalletRpc
 
         Ok(Response::new(stats.into()))
     }
+
+    async fn get_block_filters(
+        &self,
+        request: Request<GetBlockFiltersRequest>,
+    ) -> Result<Response<GetBlockFiltersResponse>, RpcStatus> {
+        let req = request.into_message();
+        if req.count == 0 || req.count > MAX_BLOCK_FILTERS_PER_REQUEST {
+            return Err(RpcStatus::bad_request(&format!(
+                "count must be between 1 and {}",
+                MAX_BLOCK_FILTERS_PER_REQUEST
+            )));
+        }
+
+        let metadata = self
+            .db
+            .get_chain_metadata()
+            .await
+            .rpc_status_internal_error(LOG_TARGET)?;
+        // Spent outputs below the pruning horizon are no longer available to build filters from
+        if metadata.pruned_height() > 0 && req.start_height < metadata.pruned_height() {
+            return Err(RpcStatus::bad_request(&format!(
+                "Block filters are not available below the pruned height {}",
+                metadata.pruned_height()
+            )));
+        }
+        let end_height = req
+            .start_height
+            .saturating_add(req.count - 1)
+            .min(metadata.best_block_height());
+
+        let headers = self
+            .db()
+            .fetch_headers(req.start_height..=end_height)
+            .await
+            .rpc_status_internal_error(LOG_TARGET)?;
+        let mut filters = Vec::with_capacity(headers.len());
+        for header in headers {
+            let header_hash = header.hash();
+            let filter = self
+                .db()
+                .fetch_block_filter(header_hash)
+                .await
+                .rpc_status_internal_error(LOG_TARGET)?;
+            filters.push(BlockFilterProto {
+                height: header.height,
+                header_hash: header_hash.to_vec(),
+                num_items: filter.num_items(),
+                data: filter.data().to_vec(),
+            });
+        }
+
+        Ok(Response::new(GetBlockFiltersResponse { filters }))
+    }
 }
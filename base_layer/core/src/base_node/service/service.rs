@@ -29,7 +29,10 @@ use futures::{pin_mut, stream::StreamExt, Stream};
 use log::*;
 use rand::rngs::OsRng;
 use tari_common_types::types::BlockHash;
-use tari_comms::{connectivity::ConnectivityRequester, peer_manager::NodeId};
+use tari_comms::{
+    connectivity::ConnectivityRequester,
+    peer_manager::{NodeId, ReputationEvent},
+};
 use tari_comms_dht::{
     domain_message::OutboundDomainMessage,
     envelope::NodeDestination,
@@ -351,7 +354,12 @@ where B: BlockchainBackend + 'static
             let result = handle_incoming_block(inbound_nch, new_block).await;
 
             match result {
-                Ok(()) => {},
+                Ok(()) => {
+                    let _drop = connectivity_requester
+                        .record_reputation_event(source_peer.node_id, ReputationEvent::ValidBlockRelayed)
+                        .await
+                        .map_err(|e| error!(target: LOG_TARGET, "Failed to record peer reputation: {:?}", e));
+                },
                 Err(BaseNodeServiceError::CommsInterfaceError(CommsInterfaceError::ChainStorageError(
                     ChainStorageError::AddBlockOperationLocked,
                 ))) => {
@@ -20,7 +20,10 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::{cmp::Ordering, time::Instant};
+use std::{
+    cmp::{Ordering, Reverse},
+    time::Instant,
+};
 
 use log::*;
 use tari_common_types::chain_metadata::ChainMetadata;
@@ -77,6 +80,26 @@ impl HeaderSyncState {
         }
     }
 
+    /// Moves sync peers with a good reputation to the front and peers with a poor reputation to the back of the sync
+    /// peer list. The sort is stable so the latency ordering is preserved for peers of a similar reputation.
+    async fn sort_sync_peers_by_reputation<B: BlockchainBackend + 'static>(
+        &mut self,
+        shared: &BaseNodeStateMachine<B>,
+    ) {
+        let node_ids = self.sync_peers.iter().map(|p| p.node_id().clone()).collect::<Vec<_>>();
+        match shared.peer_manager.get_reputation_scores(&node_ids).await {
+            Ok(scores) => {
+                for sync_peer in &mut self.sync_peers {
+                    if let Some(score) = scores.get(sync_peer.node_id()) {
+                        sync_peer.set_reputation(*score);
+                    }
+                }
+                self.sync_peers.sort_by_key(|p| Reverse(p.reputation().signum()));
+            },
+            Err(err) => warn!(target: LOG_TARGET, "Failed to fetch sync peer reputations: {}", err),
+        }
+    }
+
     // converting u64 to i64 is okay as the future time limit is the hundreds so way below u32 even
     #[allow(clippy::too_many_lines)]
     #[allow(clippy::cast_possible_wrap)]
@@ -106,6 +129,7 @@ impl HeaderSyncState {
             },
             Err(e) => return StateEvent::FatalError(format!("{}", e)),
         }
+        self.sort_sync_peers_by_reputation(shared).await;
 
         let mut synchronizer = HeaderSynchronizer::new(
             shared.config.blockchain_sync_config.clone(),
@@ -23,7 +23,10 @@
 use std::time::Duration;
 
 use log::*;
-use tari_comms::{connectivity::ConnectivityRequester, peer_manager::NodeId};
+use tari_comms::{
+    connectivity::ConnectivityRequester,
+    peer_manager::{NodeId, ReputationEvent},
+};
 
 use crate::base_node::BlockchainSyncConfig;
 
@@ -62,4 +65,14 @@ impl PeerBanManager {
             Err(err) => error!(target: LOG_TARGET, "Failed to ban sync peer {}: {}", node_id, err),
         }
     }
+
+    /// Records a reputation event for a sync peer, used to prefer well-behaved peers in future sync rounds
+    pub async fn record_reputation_event(&mut self, node_id: &NodeId, event: ReputationEvent) {
+        if let Err(err) = self.connectivity.record_reputation_event(node_id.clone(), event).await {
+            error!(
+                target: LOG_TARGET,
+                "Failed to record reputation event {} for sync peer {}: {}", event, node_id, err
+            );
+        }
+    }
 }
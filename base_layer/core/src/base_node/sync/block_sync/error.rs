@@ -137,4 +137,18 @@ impl BlockSyncError {
             BlockSyncError::ValidationError(err) => ValidationError::get_ban_reason(err),
         }
     }
+
+    /// Returns true if the error was caused by a block from the sync peer that failed validation, as opposed to a
+    /// local or connectivity error
+    pub fn is_invalid_block(&self) -> bool {
+        match self {
+            BlockSyncError::InvalidBlockBody(_) => true,
+            BlockSyncError::ValidationError(err) |
+            BlockSyncError::ChainStorageError(ChainStorageError::ValidationError { source: err }) => {
+                err.get_ban_reason().is_some()
+            },
+            BlockSyncError::ChainStorageError(ChainStorageError::InvalidBlock(_)) => true,
+            _ => false,
+        }
+    }
 }
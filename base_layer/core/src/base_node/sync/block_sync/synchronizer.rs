@@ -28,7 +28,12 @@ use std::{
 
 use futures::StreamExt;
 use log::*;
-use tari_comms::{connectivity::ConnectivityRequester, peer_manager::NodeId, protocol::rpc::RpcClient, PeerConnection};
+use tari_comms::{
+    connectivity::ConnectivityRequester,
+    peer_manager::{NodeId, ReputationEvent},
+    protocol::rpc::RpcClient,
+    PeerConnection,
+};
 use tari_utilities::hex::Hex;
 use tokio::task;
 
@@ -184,7 +189,12 @@ impl<'a, B: BlockchainBackend + 'static> BlockSynchronizer<'a, B> {
                 "Attempting to synchronize blocks with `{}` latency: {:.2?}", node_id, latency
             );
             match self.synchronize_blocks(sync_peer, client, max_latency).await {
-                Ok(_) => return Ok(()),
+                Ok(_) => {
+                    self.peer_ban_manager
+                        .record_reputation_event(&node_id, ReputationEvent::SuccessfulSync)
+                        .await;
+                    return Ok(());
+                },
                 Err(err) => {
                     warn!(target: LOG_TARGET, "{}", err);
                     let ban_reason = BlockSyncError::get_ban_reason(&err);
@@ -198,7 +208,15 @@ impl<'a, B: BlockchainBackend + 'static> BlockSynchronizer<'a, B> {
                             .ban_peer_if_required(&node_id, reason.reason, duration)
                             .await;
                     }
+                    if err.is_invalid_block() {
+                        self.peer_ban_manager
+                            .record_reputation_event(&node_id, ReputationEvent::InvalidBlock)
+                            .await;
+                    }
                     if let BlockSyncError::MaxLatencyExceeded { .. } = err {
+                        self.peer_ban_manager
+                            .record_reputation_event(&node_id, ReputationEvent::SlowRpc)
+                            .await;
                         latency_counter += 1;
                     } else {
                         self.remove_sync_peer(&node_id);
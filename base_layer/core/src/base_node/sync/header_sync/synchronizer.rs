@@ -31,7 +31,7 @@ use primitive_types::U256;
 use tari_common_types::{chain_metadata::ChainMetadata, types::HashOutput};
 use tari_comms::{
     connectivity::ConnectivityRequester,
-    peer_manager::NodeId,
+    peer_manager::{NodeId, ReputationEvent},
     protocol::rpc::{RpcClient, RpcError},
     PeerConnection,
 };
@@ -154,7 +154,12 @@ impl<'a, B: BlockchainBackend + 'static> HeaderSynchronizer<'a, B> {
         let mut latency_counter = 0usize;
         for node_id in sync_peer_node_ids {
             match self.connect_and_attempt_sync(&node_id, max_latency).await {
-                Ok((peer, sync_result)) => return Ok((peer, sync_result)),
+                Ok((peer, sync_result)) => {
+                    self.peer_ban_manager
+                        .record_reputation_event(&node_id, ReputationEvent::SuccessfulSync)
+                        .await;
+                    return Ok((peer, sync_result));
+                },
                 Err(err) => {
                     let ban_reason = BlockHeaderSyncError::get_ban_reason(&err);
                     if let Some(reason) = ban_reason {
@@ -168,6 +173,9 @@ impl<'a, B: BlockchainBackend + 'static> HeaderSynchronizer<'a, B> {
                             .await;
                     }
                     if let BlockHeaderSyncError::MaxLatencyExceeded { .. } = err {
+                        self.peer_ban_manager
+                            .record_reputation_event(&node_id, ReputationEvent::SlowRpc)
+                            .await;
                         latency_counter += 1;
                     } else {
                         self.remove_sync_peer(&node_id);
@@ -30,7 +30,12 @@ use std::{
 use futures::StreamExt;
 use log::*;
 use tari_common_types::types::{Commitment, FixedHash, RangeProofService};
-use tari_comms::{connectivity::ConnectivityRequester, peer_manager::NodeId, protocol::rpc::RpcClient, PeerConnection};
+use tari_comms::{
+    connectivity::ConnectivityRequester,
+    peer_manager::{NodeId, ReputationEvent},
+    protocol::rpc::RpcClient,
+    PeerConnection,
+};
 use tari_crypto::commitment::HomomorphicCommitment;
 use tari_mmr::sparse_merkle_tree::{DeleteResult, NodeKey, ValueHash};
 use tari_utilities::{hex::Hex, ByteArray};
@@ -180,7 +185,12 @@ impl<'a, B: BlockchainBackend + 'static> HorizonStateSynchronization<'a, B> {
         let mut latency_counter = 0usize;
         for node_id in sync_peer_node_ids {
             match self.connect_and_attempt_sync(&node_id, to_header).await {
-                Ok(_) => return Ok(()),
+                Ok(_) => {
+                    self.peer_ban_manager
+                        .record_reputation_event(&node_id, ReputationEvent::SuccessfulSync)
+                        .await;
+                    return Ok(());
+                },
                 // Try another peer
                 Err(err) => {
                     let ban_reason = HorizonSyncError::get_ban_reason(&err);
@@ -196,6 +206,9 @@ impl<'a, B: BlockchainBackend + 'static> HorizonStateSynchronization<'a, B> {
                             .await;
                     }
                     if let HorizonSyncError::MaxLatencyExceeded { .. } = err {
+                        self.peer_ban_manager
+                            .record_reputation_event(&node_id, ReputationEvent::SlowRpc)
+                            .await;
                         latency_counter += 1;
                     } else {
                         self.remove_sync_peer(&node_id);
@@ -35,6 +35,7 @@ use crate::{base_node::chain_metadata_service::PeerChainMetadata, common::rollin
 pub struct SyncPeer {
     peer_metadata: PeerChainMetadata,
     avg_latency: RollingAverageTime,
+    reputation: i64,
 }
 
 impl SyncPeer {
@@ -67,6 +68,16 @@ impl SyncPeer {
     pub fn calc_avg_latency(&self) -> Option<Duration> {
         self.avg_latency.calculate_average()
     }
+
+    /// The reputation score of the peer, as recorded by the peer manager when the peer was selected for sync
+    pub fn reputation(&self) -> i64 {
+        self.reputation
+    }
+
+    pub(crate) fn set_reputation(&mut self, reputation: i64) -> &mut Self {
+        self.reputation = reputation;
+        self
+    }
 }
 
 impl From<PeerChainMetadata> for SyncPeer {
@@ -74,6 +85,7 @@ impl From<PeerChainMetadata> for SyncPeer {
         Self {
             peer_metadata,
             avg_latency: RollingAverageTime::new(20),
+            reputation: 0,
         }
     }
 }
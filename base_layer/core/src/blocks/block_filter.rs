@@ -0,0 +1,292 @@
+//  Copyright 2024, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Compact block filters allow a light client to test whether a block may contain outputs of interest without
+//! downloading the block. A filter is a Golomb-coded set of the output script material in a block: the public key of
+//! `PushPubKey` scripts, or the serialized script for any other script. Filters are probabilistic; a match may be a
+//! false positive (roughly 1 in 784,931 per item tested) but a non-match is never a false negative.
+
+use blake2::Blake2b;
+use digest::consts::U64;
+use serde::{Deserialize, Serialize};
+use tari_common_types::types::FixedHash;
+use tari_crypto::hashing::DomainSeparatedHasher;
+use tari_script::{Opcode, TariScript};
+use tari_utilities::ByteArray;
+
+use crate::{blocks::BlocksHashDomain, transactions::transaction_components::TransactionOutput};
+
+/// The number of low bits of each delta that are written verbatim in the Golomb-Rice coding
+const FILTER_P: u8 = 19;
+/// The inverse false positive rate of a single item lookup
+const FILTER_M: u64 = 784_931;
+
+/// A Golomb-coded set of the output script items in a block
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockFilter {
+    num_items: u64,
+    data: Vec<u8>,
+}
+
+impl BlockFilter {
+    /// Builds the filter for the block with the given hash from the filter items of its outputs
+    pub fn new<I, T>(block_hash: &FixedHash, items: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<[u8]>,
+    {
+        let mut items = items.into_iter().map(|i| i.as_ref().to_vec()).collect::<Vec<_>>();
+        items.sort();
+        items.dedup();
+        let num_items = items.len() as u64;
+        let range = num_items.saturating_mul(FILTER_M);
+        let mut values = items
+            .iter()
+            .map(|item| hash_to_range(block_hash, item, range))
+            .collect::<Vec<_>>();
+        values.sort_unstable();
+
+        let mut writer = BitWriter::default();
+        let mut last = 0u64;
+        for value in values {
+            golomb_encode(&mut writer, value - last);
+            last = value;
+        }
+        Self {
+            num_items,
+            data: writer.finish(),
+        }
+    }
+
+    /// Builds the filter for a block from its outputs
+    pub fn from_outputs<'a, I>(block_hash: &FixedHash, outputs: I) -> Self
+    where I: IntoIterator<Item = &'a TransactionOutput> {
+        Self::new(block_hash, outputs.into_iter().map(|o| Self::script_item(&o.script)))
+    }
+
+    /// Restores a filter from its encoded parts, as received from a peer
+    pub fn from_parts(num_items: u64, data: Vec<u8>) -> Self {
+        Self { num_items, data }
+    }
+
+    /// The filter item for an output script: the public key of a `PushPubKey` script, otherwise the script bytes
+    pub fn script_item(script: &TariScript) -> Vec<u8> {
+        match script.as_slice() {
+            [Opcode::PushPubKey(public_key)] => public_key.as_bytes().to_vec(),
+            _ => script.to_bytes(),
+        }
+    }
+
+    pub fn num_items(&self) -> u64 {
+        self.num_items
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Returns true if any of the items may be in the filter of the block with the given hash. A filter that cannot
+    /// be decoded is treated as a match, so that a malformed filter never causes an output to be missed.
+    pub fn matches_any<I, T>(&self, block_hash: &FixedHash, items: I) -> bool
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<[u8]>,
+    {
+        if self.num_items == 0 {
+            return false;
+        }
+        let range = self.num_items.saturating_mul(FILTER_M);
+        let mut queries = items
+            .into_iter()
+            .map(|item| hash_to_range(block_hash, item.as_ref(), range))
+            .collect::<Vec<_>>();
+        if queries.is_empty() {
+            return false;
+        }
+        queries.sort_unstable();
+
+        let mut reader = BitReader::new(&self.data);
+        let mut queries = queries.into_iter().peekable();
+        let mut value = 0u64;
+        for _ in 0..self.num_items {
+            let delta = match golomb_decode(&mut reader) {
+                Some(delta) => delta,
+                None => return true,
+            };
+            value = match value.checked_add(delta) {
+                Some(value) => value,
+                None => return true,
+            };
+            while let Some(query) = queries.peek() {
+                if *query == value {
+                    return true;
+                }
+                if *query > value {
+                    break;
+                }
+                queries.next();
+            }
+            if queries.peek().is_none() {
+                return false;
+            }
+        }
+        false
+    }
+}
+
+/// Hashes an item, keyed by the block hash, uniformly onto `[0, range)`
+fn hash_to_range(block_hash: &FixedHash, item: &[u8], range: u64) -> u64 {
+    let hash = DomainSeparatedHasher::<Blake2b<U64>, BlocksHashDomain>::new_with_label("block_filter")
+        .chain(block_hash.as_slice())
+        .chain(item)
+        .finalize();
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&hash.as_ref()[..8]);
+    let value = u64::from_le_bytes(bytes);
+    // The product is less than 2^64 * range, so the shifted result always fits in a u64
+    let product = u128::from(value) * u128::from(range);
+    u64::try_from(product >> 64).unwrap_or(u64::MAX)
+}
+
+fn golomb_encode(writer: &mut BitWriter, value: u64) {
+    let quotient = value >> FILTER_P;
+    for _ in 0..quotient {
+        writer.write_bit(true);
+    }
+    writer.write_bit(false);
+    writer.write_bits(value, FILTER_P);
+}
+
+fn golomb_decode(reader: &mut BitReader<'_>) -> Option<u64> {
+    let mut quotient = 0u64;
+    while reader.read_bit()? {
+        quotient += 1;
+    }
+    let remainder = reader.read_bits(FILTER_P)?;
+    quotient.checked_shl(u32::from(FILTER_P)).map(|q| q | remainder)
+}
+
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    num_bits: usize,
+}
+
+impl BitWriter {
+    fn write_bit(&mut self, bit: bool) {
+        if self.num_bits % 8 == 0 {
+            self.bytes.push(0);
+        }
+        if bit {
+            if let Some(byte) = self.bytes.last_mut() {
+                *byte |= 0x80 >> (self.num_bits % 8);
+            }
+        }
+        self.num_bits += 1;
+    }
+
+    fn write_bits(&mut self, value: u64, count: u8) {
+        for i in (0..count).rev() {
+            self.write_bit((value >> i) & 1 == 1);
+        }
+    }
+
+    fn finish(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+struct BitReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, position: 0 }
+    }
+
+    fn read_bit(&mut self) -> Option<bool> {
+        let byte = self.bytes.get(self.position / 8)?;
+        let bit = byte & (0x80 >> (self.position % 8)) != 0;
+        self.position += 1;
+        Some(bit)
+    }
+
+    fn read_bits(&mut self, count: u8) -> Option<u64> {
+        let mut value = 0u64;
+        for _ in 0..count {
+            value = (value << 1) | u64::from(self.read_bit()?);
+        }
+        Some(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn items(count: usize) -> Vec<Vec<u8>> {
+        (0..count).map(|i| format!("item {}", i).into_bytes()).collect()
+    }
+
+    #[test]
+    fn it_matches_every_item_in_the_filter() {
+        let block_hash = FixedHash::from([1u8; 32]);
+        let items = items(100);
+        let filter = BlockFilter::new(&block_hash, &items);
+        assert_eq!(filter.num_items(), 100);
+        for item in &items {
+            assert!(filter.matches_any(&block_hash, [item]));
+        }
+        assert!(filter.matches_any(&block_hash, [b"not in filter".to_vec(), items[42].clone()]));
+    }
+
+    #[test]
+    fn it_does_not_match_items_outside_the_filter() {
+        let block_hash = FixedHash::from([2u8; 32]);
+        let filter = BlockFilter::new(&block_hash, items(100));
+        let others = (0..100)
+            .map(|i| format!("other {}", i).into_bytes())
+            .collect::<Vec<_>>();
+        assert!(!filter.matches_any(&block_hash, &others));
+        assert!(!filter.matches_any(&block_hash, Vec::<Vec<u8>>::new()));
+    }
+
+    #[test]
+    fn it_is_keyed_by_the_block_hash() {
+        let filter = BlockFilter::new(&FixedHash::from([3u8; 32]), items(10));
+        let other = BlockFilter::new(&FixedHash::from([4u8; 32]), items(10));
+        assert_ne!(filter.data(), other.data());
+    }
+
+    #[test]
+    fn it_handles_empty_and_malformed_filters() {
+        let block_hash = FixedHash::from([5u8; 32]);
+        let empty = BlockFilter::new(&block_hash, Vec::<Vec<u8>>::new());
+        assert_eq!(empty.num_items(), 0);
+        assert!(!empty.matches_any(&block_hash, items(5)));
+
+        let truncated = BlockFilter::from_parts(50, vec![0xff; 2]);
+        assert!(truncated.matches_any(&block_hash, items(1)));
+    }
+}
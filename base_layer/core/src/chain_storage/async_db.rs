@@ -40,6 +40,7 @@ use crate::{
     blocks::{
         Block,
         BlockAccumulatedData,
+        BlockFilter,
         BlockHeader,
         BlockHeaderAccumulatedData,
         ChainBlock,
@@ -182,6 +183,8 @@ impl<B: BlockchainBackend + 'static> AsyncBlockchainDb<B> {
 
     make_async_fn!(fetch_inputs_in_block(header_hash: HashOutput) -> Vec<TransactionInput>, "fetch_inputs_in_block");
 
+    make_async_fn!(fetch_block_filter(header_hash: HashOutput) -> BlockFilter, "fetch_block_filter");
+
     make_async_fn!(utxo_count() -> usize, "utxo_count");
 
     //---------------------------------- Kernel --------------------------------------------//
@@ -8,7 +8,15 @@ use tari_common_types::{
 
 use super::TemplateRegistrationEntry;
 use crate::{
-    blocks::{Block, BlockAccumulatedData, BlockHeader, BlockHeaderAccumulatedData, ChainBlock, ChainHeader},
+    blocks::{
+        Block,
+        BlockAccumulatedData,
+        BlockFilter,
+        BlockHeader,
+        BlockHeaderAccumulatedData,
+        ChainBlock,
+        ChainHeader,
+    },
     chain_storage::{
         ChainStorageError,
         DbBasicStats,
@@ -113,6 +121,9 @@ pub trait BlockchainBackend: Send + Sync {
     /// Fetch all inputs in a block
     fn fetch_inputs_in_block(&self, header_hash: &HashOutput) -> Result<Vec<TransactionInput>, ChainStorageError>;
 
+    /// Fetch the compact block filter stored for a block, if any
+    fn fetch_block_filter(&self, header_hash: &HashOutput) -> Result<Option<BlockFilter>, ChainStorageError>;
+
     /// Fetches the total merkle mountain range node count upto the specified height.
     fn fetch_mmr_size(&self, tree: MmrTree) -> Result<u64, ChainStorageError>;
 
@@ -52,6 +52,7 @@ use crate::{
     blocks::{
         Block,
         BlockAccumulatedData,
+        BlockFilter,
         BlockHeader,
         BlockHeaderAccumulatedData,
         BlockHeaderValidationError,
@@ -511,6 +512,19 @@ where B: BlockchainBackend
         db.fetch_outputs_in_block(&header_hash)
     }
 
+    /// Returns the compact block filter for a block. Blocks stored before block filters were introduced do not have a
+    /// stored filter, so it is built from the outputs of the block instead.
+    pub fn fetch_block_filter(&self, header_hash: HashOutput) -> Result<BlockFilter, ChainStorageError> {
+        let db = self.db_read_access()?;
+        match db.fetch_block_filter(&header_hash)? {
+            Some(filter) => Ok(filter),
+            None => {
+                let outputs = db.fetch_outputs_in_block(&header_hash)?;
+                Ok(BlockFilter::from_outputs(&header_hash, &outputs))
+            },
+        }
+    }
+
     pub fn fetch_inputs_in_block(&self, header_hash: HashOutput) -> Result<Vec<TransactionInput>, ChainStorageError> {
         let db = self.db_read_access()?;
         db.fetch_inputs_in_block(&header_hash)
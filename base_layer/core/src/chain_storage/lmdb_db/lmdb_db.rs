@@ -54,6 +54,7 @@ use crate::{
     blocks::{
         Block,
         BlockAccumulatedData,
+        BlockFilter,
         BlockHeader,
         BlockHeaderAccumulatedData,
         ChainBlock,
@@ -149,6 +150,7 @@ const LMDB_DB_REORGS: &str = "reorgs";
 const LMDB_DB_VALIDATOR_NODES: &str = "validator_nodes";
 const LMDB_DB_VALIDATOR_NODES_MAPPING: &str = "validator_nodes_mapping";
 const LMDB_DB_TEMPLATE_REGISTRATIONS: &str = "template_registrations";
+const LMDB_DB_BLOCK_FILTERS: &str = "block_filters";
 
 /// HeaderHash(32), mmr_pos(8), hash(32)
 type KernelKey = CompositeKey<72>;
@@ -198,6 +200,7 @@ pub fn create_lmdb_database<P: AsRef<Path>>(
         .add_database(LMDB_DB_VALIDATOR_NODES, flags)
         .add_database(LMDB_DB_VALIDATOR_NODES_MAPPING, flags)
         .add_database(LMDB_DB_TEMPLATE_REGISTRATIONS, flags | db::DUPSORT)
+        .add_database(LMDB_DB_BLOCK_FILTERS, flags)
         .build()
         .map_err(|err| ChainStorageError::CriticalError(format!("Could not create LMDB store:{}", err)))?;
     debug!(target: LOG_TARGET, "LMDB database creation successful");
@@ -260,6 +263,8 @@ pub struct LMDBDatabase {
     validator_nodes_mapping: DatabaseRef,
     /// Maps CodeTemplateRegistration <block_height, hash> -> TemplateRegistration
     template_registrations: DatabaseRef,
+    /// Maps block_hash -> BlockFilter
+    block_filters_db: DatabaseRef,
     _file_lock: Arc<File>,
     consensus_manager: ConsensusManager,
 }
@@ -299,6 +304,7 @@ impl LMDBDatabase {
             validator_nodes: get_database(store, LMDB_DB_VALIDATOR_NODES)?,
             validator_nodes_mapping: get_database(store, LMDB_DB_VALIDATOR_NODES_MAPPING)?,
             template_registrations: get_database(store, LMDB_DB_TEMPLATE_REGISTRATIONS)?,
+            block_filters_db: get_database(store, LMDB_DB_BLOCK_FILTERS)?,
             env,
             env_config: store.env_config(),
             _file_lock: Arc::new(file_lock),
@@ -494,7 +500,7 @@ impl LMDBDatabase {
         Ok(())
     }
 
-    fn all_dbs(&self) -> [(&'static str, &DatabaseRef); 26] {
+    fn all_dbs(&self) -> [(&'static str, &DatabaseRef); 27] {
         [
             (LMDB_DB_METADATA, &self.metadata_db),
             (LMDB_DB_HEADERS, &self.headers_db),
@@ -528,6 +534,7 @@ impl LMDBDatabase {
             (LMDB_DB_VALIDATOR_NODES, &self.validator_nodes),
             (LMDB_DB_VALIDATOR_NODES_MAPPING, &self.validator_nodes_mapping),
             (LMDB_DB_TEMPLATE_REGISTRATIONS, &self.template_registrations),
+            (LMDB_DB_BLOCK_FILTERS, &self.block_filters_db),
         ]
     }
 
@@ -934,6 +941,16 @@ impl LMDBDatabase {
 
         self.delete_block_kernels(write_txn, block_hash.as_slice())?;
 
+        // Blocks stored before block filters were introduced do not have one
+        if lmdb_exists(write_txn, &self.block_filters_db, block_hash.as_slice())? {
+            lmdb_delete(
+                write_txn,
+                &self.block_filters_db,
+                block_hash.as_slice(),
+                "block_filters_db",
+            )?;
+        }
+
         Ok(())
     }
 
@@ -1214,6 +1231,9 @@ impl LMDBDatabase {
 
         let (inputs, outputs, kernels) = body.dissolve();
 
+        let block_filter = BlockFilter::from_outputs(&block_hash, &outputs);
+        lmdb_replace(txn, &self.block_filters_db, block_hash.as_slice(), &block_filter, None)?;
+
         let data = if header.height == 0 {
             BlockAccumulatedData::default()
         } else {
@@ -2098,6 +2118,11 @@ impl BlockchainBackend for LMDBDatabase {
         )
     }
 
+    fn fetch_block_filter(&self, header_hash: &HashOutput) -> Result<Option<BlockFilter>, ChainStorageError> {
+        let txn = self.read_transaction()?;
+        lmdb_get(&txn, &self.block_filters_db, header_hash.as_slice())
+    }
+
     fn fetch_mmr_size(&self, tree: MmrTree) -> Result<u64, ChainStorageError> {
         let txn = self.read_transaction()?;
         match tree {
@@ -93,7 +93,7 @@ impl MempoolInboundHandlers {
         &mut self,
         tx: Transaction,
         source_peer: Option<NodeId>,
-    ) -> Result<(), MempoolServiceError> {
+    ) -> Result<TxStorageResponse, MempoolServiceError> {
         let first_tx_kernel_excess_sig = tx
             .first_kernel_excess_sig()
             .ok_or(MempoolServiceError::TransactionNoKernels)?
@@ -108,8 +108,7 @@ impl MempoolInboundHandlers {
                 .map(|p| format!("remote peer: {}", p))
                 .unwrap_or_else(|| "local services".to_string())
         );
-        self.submit_transaction(tx, source_peer).await?;
-        Ok(())
+        self.submit_transaction(tx, source_peer).await
     }
 
     /// Submits a transaction to the mempool and propagate valid transactions.
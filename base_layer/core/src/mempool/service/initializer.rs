@@ -24,6 +24,7 @@ use std::{convert::TryFrom, sync::Arc};
 
 use futures::{Stream, StreamExt};
 use log::*;
+use tari_comms::connectivity::ConnectivityRequester;
 use tari_comms_dht::Dht;
 use tari_p2p::{
     comms_connector::{PeerMessage, SubscriptionFactory},
@@ -136,6 +137,7 @@ impl ServiceInitializer for MempoolServiceInitializer {
         context.spawn_until_shutdown(move |handles| {
             let outbound_message_service = handles.expect_handle::<Dht>().outbound_requester();
             let base_node = handles.expect_handle::<LocalNodeCommsInterface>();
+            let connectivity = handles.expect_handle::<ConnectivityRequester>();
 
             let streams = MempoolStreams {
                 outbound_tx_stream,
@@ -145,7 +147,7 @@ impl ServiceInitializer for MempoolServiceInitializer {
                 request_receiver,
             };
             debug!(target: LOG_TARGET, "Mempool service started");
-            MempoolService::new(outbound_message_service, inbound_handlers).start(streams)
+            MempoolService::new(outbound_message_service, inbound_handlers, connectivity).start(streams)
         });
 
         Ok(())
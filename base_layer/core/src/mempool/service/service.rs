@@ -24,7 +24,10 @@ use std::{convert::TryFrom, sync::Arc};
 
 use futures::{pin_mut, stream::StreamExt, Stream};
 use log::*;
-use tari_comms::peer_manager::NodeId;
+use tari_comms::{
+    connectivity::ConnectivityRequester,
+    peer_manager::{NodeId, ReputationEvent},
+};
 use tari_comms_dht::{
     domain_message::OutboundDomainMessage,
     envelope::NodeDestination,
@@ -37,11 +40,14 @@ use tokio::{sync::mpsc, task};
 
 use crate::{
     base_node::comms_interface::{BlockEvent, BlockEventReceiver},
-    mempool::service::{
-        error::MempoolServiceError,
-        inbound_handlers::MempoolInboundHandlers,
-        MempoolRequest,
-        MempoolResponse,
+    mempool::{
+        service::{
+            error::MempoolServiceError,
+            inbound_handlers::MempoolInboundHandlers,
+            MempoolRequest,
+            MempoolResponse,
+        },
+        TxStorageResponse,
     },
     proto,
     transactions::transaction_components::Transaction,
@@ -63,13 +69,19 @@ pub struct MempoolStreams<STxIn, SLocalReq> {
 pub struct MempoolService {
     outbound_message_service: OutboundMessageRequester,
     inbound_handlers: MempoolInboundHandlers,
+    connectivity: ConnectivityRequester,
 }
 
 impl MempoolService {
-    pub fn new(outbound_message_service: OutboundMessageRequester, inbound_handlers: MempoolInboundHandlers) -> Self {
+    pub fn new(
+        outbound_message_service: OutboundMessageRequester,
+        inbound_handlers: MempoolInboundHandlers,
+        connectivity: ConnectivityRequester,
+    ) -> Self {
         Self {
             outbound_message_service,
             inbound_handlers,
+            connectivity,
         }
     }
 
@@ -181,15 +193,28 @@ impl MempoolService {
             source_peer.public_key
         );
         let mut inbound_handlers = self.inbound_handlers.clone();
+        let mut connectivity = self.connectivity.clone();
         task::spawn(async move {
             let result = inbound_handlers
-                .handle_transaction(inner, Some(source_peer.node_id))
+                .handle_transaction(inner, Some(source_peer.node_id.clone()))
                 .await;
-            if let Err(e) = result {
-                error!(
-                    target: LOG_TARGET,
-                    "Failed to handle incoming transaction message: {:?}", e
-                );
+            let reputation_event = match result {
+                Ok(TxStorageResponse::UnconfirmedPool) => Some(ReputationEvent::ValidTransactionRelayed),
+                Ok(TxStorageResponse::NotStoredConsensus) => Some(ReputationEvent::InvalidTransaction),
+                Ok(_) => None,
+                Err(e) => {
+                    error!(
+                        target: LOG_TARGET,
+                        "Failed to handle incoming transaction message: {:?}", e
+                    );
+                    None
+                },
+            };
+            if let Some(event) = reputation_event {
+                let _drop = connectivity
+                    .record_reputation_event(source_peer.node_id, event)
+                    .await
+                    .map_err(|e| error!(target: LOG_TARGET, "Failed to record peer reputation: {:?}", e));
             }
         });
     }
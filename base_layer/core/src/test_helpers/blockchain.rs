@@ -41,7 +41,15 @@ use tari_utilities::ByteArray;
 
 use super::{create_block, mine_to_difficulty};
 use crate::{
-    blocks::{Block, BlockAccumulatedData, BlockHeader, BlockHeaderAccumulatedData, ChainBlock, ChainHeader},
+    blocks::{
+        Block,
+        BlockAccumulatedData,
+        BlockFilter,
+        BlockHeader,
+        BlockHeaderAccumulatedData,
+        ChainBlock,
+        ChainHeader,
+    },
     chain_storage::{
         create_lmdb_database,
         BlockAddResult,
@@ -318,6 +326,10 @@ impl BlockchainBackend for TempDatabase {
         self.db.as_ref().unwrap().fetch_inputs_in_block(header_hash)
     }
 
+    fn fetch_block_filter(&self, header_hash: &HashOutput) -> Result<Option<BlockFilter>, ChainStorageError> {
+        self.db.as_ref().unwrap().fetch_block_filter(header_hash)
+    }
+
     fn fetch_mmr_size(&self, tree: MmrTree) -> Result<u64, ChainStorageError> {
         self.db.as_ref().unwrap().fetch_mmr_size(tree)
     }
@@ -16,6 +16,10 @@ pub use coinbase_builder::{
 };
 
 pub mod fee;
+
+mod payment_proof;
+pub use payment_proof::PaymentProof;
+
 pub mod tari_amount;
 pub mod transaction_components;
 
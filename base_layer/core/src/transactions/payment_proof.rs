@@ -0,0 +1,187 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use blake2::Blake2b;
+use digest::consts::U64;
+use serde::{Deserialize, Serialize};
+use tari_common_types::{
+    tari_address::TariAddress,
+    types::{Commitment, CommitmentFactory, FixedHash, PrivateKey, PublicKey, Signature},
+};
+use tari_crypto::{commitment::HomomorphicCommitmentFactory, hash_domain, hashing::DomainSeparatedHasher};
+use tari_script::push_pubkey_script;
+use tari_utilities::ByteArray;
+
+use crate::transactions::{
+    tari_amount::MicroMinotari,
+    transaction_components::{encrypted_data::PaymentId, TransactionKernel, TransactionOutput},
+};
+
+hash_domain!(
+    PaymentProofHashDomain,
+    "com.tari.base_layer.core.transactions.payment_proof",
+    0
+);
+
+/// A statement that a payment of `amount` to `recipient` was made in the transaction with the given kernel, by way of
+/// the given output.
+///
+/// The statement is signed with the blinding factor `k` of the output commitment `C = k.G + v.H`, using `C - v.H` as
+/// the public key. A valid signature therefore proves that the output commits to exactly `amount` without revealing
+/// `k`, and that the proof was created by someone who knows the opening of the output, i.e. the sender of a one-sided
+/// payment or the receiver of the output. The recipient is only bound by the output's script, which for a one-sided
+/// payment locks the output to the recipient's public spend key. Whether the kernel and the output were mined
+/// together, and the script of the mined output, have to be checked against the chain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PaymentProof {
+    pub kernel_excess: Commitment,
+    pub kernel_signature: Signature,
+    pub output_hash: FixedHash,
+    pub output_commitment: Commitment,
+    pub amount: MicroMinotari,
+    pub recipient: TariAddress,
+    pub payment_id: PaymentId,
+    pub signature: Signature,
+}
+
+impl PaymentProof {
+    /// Creates an unsigned proof, the signature must be set using the challenge from [PaymentProof::challenge]
+    pub fn new(
+        kernel: &TransactionKernel,
+        output: &TransactionOutput,
+        amount: MicroMinotari,
+        recipient: TariAddress,
+        payment_id: PaymentId,
+    ) -> Self {
+        Self {
+            kernel_excess: kernel.excess.clone(),
+            kernel_signature: kernel.excess_sig.clone(),
+            output_hash: output.hash(),
+            output_commitment: output.commitment.clone(),
+            amount,
+            recipient,
+            payment_id,
+            signature: Signature::default(),
+        }
+    }
+
+    /// The public key `C - v.H` that the proof is signed with
+    pub fn public_key(&self) -> PublicKey {
+        let value_commitment = CommitmentFactory::default().commit_value(&PrivateKey::default(), self.amount.as_u64());
+        (&self.output_commitment - &value_commitment).as_public_key().clone()
+    }
+
+    /// The challenge that has to be signed for the given public nonce
+    pub fn challenge(&self, public_nonce: &PublicKey) -> [u8; 64] {
+        let hasher = DomainSeparatedHasher::<Blake2b<U64>, PaymentProofHashDomain>::new_with_label("payment_proof")
+            .chain(self.public_key().as_bytes())
+            .chain(public_nonce.as_bytes())
+            .chain(self.kernel_excess.as_bytes())
+            .chain(self.kernel_signature.get_public_nonce().as_bytes())
+            .chain(self.kernel_signature.get_signature().as_bytes())
+            .chain(self.output_hash.as_slice())
+            .chain(self.output_commitment.as_bytes())
+            .chain(self.amount.as_u64().to_le_bytes())
+            .chain(self.recipient.to_vec())
+            .chain(self.payment_id.to_bytes());
+        digest::Digest::finalize(hasher).into()
+    }
+
+    /// Returns true if the proof is signed by the opening of the output commitment to `amount`. This does not check
+    /// that the kernel or the output exist on chain.
+    pub fn is_valid_signature(&self) -> bool {
+        let challenge = self.challenge(self.signature.get_public_nonce());
+        self.signature.verify_raw_uniform(&self.public_key(), &challenge)
+    }
+
+    /// Returns true if the output is the output of this proof and can only be spent by the recipient, i.e. its script
+    /// is `PushPubKey` of the recipient's public spend key
+    pub fn is_locked_to_recipient(&self, output: &TransactionOutput) -> bool {
+        output.commitment == self.output_commitment &&
+            output.script == push_pubkey_script(self.recipient.public_spend_key())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::OsRng;
+    use tari_crypto::keys::{PublicKey as PublicKeyT, SecretKey};
+
+    use super::*;
+
+    fn signed_proof(mask: &PrivateKey, amount: MicroMinotari) -> PaymentProof {
+        let mut proof = PaymentProof {
+            kernel_excess: Commitment::default(),
+            kernel_signature: Signature::default(),
+            output_hash: FixedHash::zero(),
+            output_commitment: CommitmentFactory::default().commit_value(mask, amount.as_u64()),
+            amount,
+            recipient: TariAddress::default(),
+            payment_id: PaymentId::U64(1),
+            signature: Signature::default(),
+        };
+        let (nonce, public_nonce) = PublicKey::random_keypair(&mut OsRng);
+        let challenge = proof.challenge(&public_nonce);
+        proof.signature = Signature::sign_raw_uniform(mask, nonce, &challenge).unwrap();
+        proof
+    }
+
+    #[test]
+    fn it_verifies_a_proof_signed_with_the_commitment_opening() {
+        let mask = PrivateKey::random(&mut OsRng);
+        let proof = signed_proof(&mask, MicroMinotari(1_000));
+        assert_eq!(proof.public_key(), PublicKey::from_secret_key(&mask));
+        assert!(proof.is_valid_signature());
+    }
+
+    #[test]
+    fn it_rejects_a_tampered_proof() {
+        let mask = PrivateKey::random(&mut OsRng);
+        let proof = signed_proof(&mask, MicroMinotari(1_000));
+
+        let mut wrong_amount = proof.clone();
+        wrong_amount.amount = MicroMinotari(2_000);
+        assert!(!wrong_amount.is_valid_signature());
+
+        let mut wrong_payment_id = proof.clone();
+        wrong_payment_id.payment_id = PaymentId::U64(2);
+        assert!(!wrong_payment_id.is_valid_signature());
+
+        let wrong_mask = signed_proof(&PrivateKey::random(&mut OsRng), MicroMinotari(1_000));
+        let mut wrong_output = proof;
+        wrong_output.output_commitment = wrong_mask.output_commitment;
+        assert!(!wrong_output.is_valid_signature());
+    }
+
+    #[test]
+    fn it_checks_the_output_is_locked_to_the_recipient() {
+        let mask = PrivateKey::random(&mut OsRng);
+        let mut proof = signed_proof(&mask, MicroMinotari(1_000));
+        let recipient_spend_key = PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng));
+        proof.recipient = TariAddress::new_dual_address_with_default_features(
+            PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng)),
+            recipient_spend_key.clone(),
+            Default::default(),
+        );
+        let output = TransactionOutput {
+            commitment: proof.output_commitment.clone(),
+            script: push_pubkey_script(&recipient_spend_key),
+            ..Default::default()
+        };
+        assert!(proof.is_locked_to_recipient(&output));
+
+        let mut wrong_recipient = proof.clone();
+        wrong_recipient.recipient = TariAddress::new_dual_address_with_default_features(
+            PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng)),
+            PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng)),
+            Default::default(),
+        );
+        assert!(!wrong_recipient.is_locked_to_recipient(&output));
+
+        let other_output = TransactionOutput {
+            script: push_pubkey_script(&recipient_spend_key),
+            ..Default::default()
+        };
+        assert!(!proof.is_locked_to_recipient(&other_output));
+    }
+}
@@ -58,7 +58,7 @@
 //! #
 //!   activate Sender
 //!   Sender-->>+Receivers: [tx_id, amount_i]
-//!   note left of Sender: CollectingPubKeys
+//!   note left of Sender: CollectingMultiNonces
 //!   note right of Receivers: Initialization
 //!   Receivers-->>-Sender: [tx_id, Pi, Ri]
 //!   deactivate Sender
@@ -69,13 +69,13 @@
 //! #
 //!   activate Sender
 //!   Sender-->>+Receivers: [tx_id, ΣR, ΣP]
-//!   note left of Sender: CollectingSignatures
+//!   note left of Sender: CollectingMultiSignatures
 //!   note right of Receivers: Signing
 //!   Receivers-->>Receivers: create output and sign
 //!   Receivers-->>-Sender: [tx_id, Output_i, s_i]
 //!   deactivate Sender
 //! #
-//!   note left of Sender: Finalizing
+//!   note left of Sender: FinalizingMultiRecipient
 //!   alt is_valid()
 //!   Sender-->>Sender: Finalized
 //!   else invalid
@@ -93,6 +93,7 @@ use thiserror::Error;
 
 use crate::transactions::{tari_amount::*, transaction_components::TransactionError};
 
+pub mod multi_receiver;
 pub mod proto;
 pub mod recipient;
 pub mod sender;
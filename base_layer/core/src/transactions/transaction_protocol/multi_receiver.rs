@@ -0,0 +1,103 @@
+//  Copyright 2024, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use tari_common_types::key_branches::TransactionKeyManagerBranch;
+
+use crate::{
+    consensus::ConsensusConstants,
+    transactions::{
+        key_manager::TransactionKeyManagerInterface,
+        transaction_components::WalletOutput,
+        transaction_protocol::{
+            recipient::{MultiRoundRecipientData, RecipientSignedMessage},
+            sender::{MultiRoundSenderData, MultiRoundSignatureRequest},
+            single_receiver::SingleReceiverTransactionProtocol,
+            TransactionProtocolError as TPE,
+        },
+    },
+};
+
+/// MultiReceiverTransactionProtocol represents the actions taken by one of the receivers in the three-round Tari
+/// transaction protocol. Upon receiving the sender's information, the receiver:
+/// * Checks the input for validity
+/// * Chooses its kernel nonce and returns the public nonce and excess to the sender
+///
+/// Once the sender has collected the nonces of all the receivers, the receiver:
+/// * Constructs its output, range proof and partial signature over the total public nonce and excess
+/// * Constructs the reply
+///
+/// If any step fails, an error is returned.
+pub struct MultiReceiverTransactionProtocol {}
+
+impl MultiReceiverTransactionProtocol {
+    pub async fn create_nonce<KM: TransactionKeyManagerInterface>(
+        sender_info: &MultiRoundSenderData,
+        output: WalletOutput,
+        key_manager: &KM,
+        consensus_constants: &ConsensusConstants,
+    ) -> Result<MultiRoundRecipientData, TPE> {
+        SingleReceiverTransactionProtocol::validate_sender_data(&sender_info.sender_data, consensus_constants)?;
+        if sender_info.recipient_index >= sender_info.num_recipients {
+            return Err(TPE::ValidationError("Invalid recipient index".into()));
+        }
+        if sender_info.sender_data.metadata.kernel_features.is_burned() {
+            return Err(TPE::ValidationError(
+                "A burn transaction cannot have multiple recipients".into(),
+            ));
+        }
+
+        let kernel_nonce = key_manager
+            .get_next_key(TransactionKeyManagerBranch::KernelNonce.get_branch_key())
+            .await?;
+        let public_excess = key_manager
+            .get_txo_kernel_signature_excess_with_offset(&output.spending_key_id, &kernel_nonce.key_id)
+            .await?;
+        Ok(MultiRoundRecipientData {
+            sender_info: sender_info.clone(),
+            output,
+            kernel_nonce_key_id: kernel_nonce.key_id,
+            public_nonce: kernel_nonce.pub_key,
+            public_excess,
+        })
+    }
+
+    pub async fn sign<KM: TransactionKeyManagerInterface>(
+        data: &MultiRoundRecipientData,
+        request: &MultiRoundSignatureRequest,
+        key_manager: &KM,
+    ) -> Result<RecipientSignedMessage, TPE> {
+        if request.tx_id != data.sender_info.sender_data.tx_id {
+            return Err(TPE::ValidationError(
+                "Signature request is for a different transaction".into(),
+            ));
+        }
+        SingleReceiverTransactionProtocol::sign(
+            &data.sender_info.sender_data,
+            data.output.clone(),
+            &data.kernel_nonce_key_id,
+            &request.total_public_nonce,
+            &request.total_public_excess,
+            key_manager,
+        )
+        .await
+    }
+}
@@ -26,7 +26,7 @@ use tari_common_types::types::{PrivateKey, PublicKey};
 use tari_utilities::ByteArray;
 
 use super::protocol as proto;
-use crate::transactions::transaction_protocol::recipient::RecipientSignedMessage;
+use crate::transactions::transaction_protocol::recipient::{RecipientNonceMessage, RecipientSignedMessage};
 
 impl TryFrom<proto::RecipientSignedMessage> for RecipientSignedMessage {
     type Error = String;
@@ -76,3 +76,34 @@ impl TryFrom<RecipientSignedMessage> for proto::RecipientSignedMessage {
         })
     }
 }
+
+//---------------------------------- RecipientNonceMessage --------------------------------------------//
+
+impl TryFrom<proto::RecipientNonceMessage> for RecipientNonceMessage {
+    type Error = String;
+
+    fn try_from(message: proto::RecipientNonceMessage) -> Result<Self, Self::Error> {
+        let public_nonce =
+            PublicKey::from_canonical_bytes(&message.public_nonce).map_err(|err| format!("public_nonce: {}", err))?;
+        let public_excess =
+            PublicKey::from_canonical_bytes(&message.public_excess).map_err(|err| format!("public_excess: {}", err))?;
+
+        Ok(Self {
+            tx_id: message.tx_id.into(),
+            recipient_index: message.recipient_index,
+            public_nonce,
+            public_excess,
+        })
+    }
+}
+
+impl From<RecipientNonceMessage> for proto::RecipientNonceMessage {
+    fn from(message: RecipientNonceMessage) -> Self {
+        Self {
+            tx_id: message.tx_id.into(),
+            recipient_index: message.recipient_index,
+            public_nonce: message.public_nonce.to_vec(),
+            public_excess: message.public_excess.to_vec(),
+        }
+    }
+}
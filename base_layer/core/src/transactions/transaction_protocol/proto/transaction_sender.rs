@@ -32,7 +32,12 @@ use tari_script::TariScript;
 use tari_utilities::ByteArray;
 
 use super::{protocol as proto, protocol::transaction_sender_message::Message as ProtoTransactionSenderMessage};
-use crate::transactions::transaction_protocol::sender::{SingleRoundSenderData, TransactionSenderMessage};
+use crate::transactions::transaction_protocol::sender::{
+    MultiRoundSenderData,
+    MultiRoundSignatureRequest,
+    SingleRoundSenderData,
+    TransactionSenderMessage,
+};
 
 impl proto::TransactionSenderMessage {
     pub fn none() -> Self {
@@ -47,9 +52,15 @@ impl proto::TransactionSenderMessage {
         }
     }
 
-    pub fn multiple() -> Self {
+    pub fn multiple(data: proto::MultiRoundSenderData) -> Self {
+        proto::TransactionSenderMessage {
+            message: Some(ProtoTxnSenderMessage::Multiple(data)),
+        }
+    }
+
+    pub fn signature_request(data: proto::MultiRoundSignatureRequest) -> Self {
         proto::TransactionSenderMessage {
-            message: Some(ProtoTxnSenderMessage::Multiple(true)),
+            message: Some(ProtoTxnSenderMessage::SignatureRequest(data)),
         }
     }
 }
@@ -65,7 +76,10 @@ impl TryFrom<proto::TransactionSenderMessage> for TransactionSenderMessage {
         let sender_message = match inner_message {
             ProtoTxnSenderMessage::None(_) => TransactionSenderMessage::None,
             ProtoTxnSenderMessage::Single(data) => TransactionSenderMessage::Single(Box::new(data.try_into()?)),
-            ProtoTxnSenderMessage::Multiple(_) => TransactionSenderMessage::Multiple,
+            ProtoTxnSenderMessage::Multiple(data) => TransactionSenderMessage::Multiple(Box::new(data.try_into()?)),
+            ProtoTxnSenderMessage::SignatureRequest(data) => {
+                TransactionSenderMessage::SignatureRequest(Box::new(data.try_into()?))
+            },
         };
 
         Ok(sender_message)
@@ -81,7 +95,12 @@ impl TryFrom<TransactionSenderMessage> for proto::TransactionSenderMessage {
             TransactionSenderMessage::Single(sender_data) => {
                 ProtoTransactionSenderMessage::Single((*sender_data).try_into()?)
             },
-            TransactionSenderMessage::Multiple => ProtoTransactionSenderMessage::Multiple(true),
+            TransactionSenderMessage::Multiple(sender_data) => {
+                ProtoTransactionSenderMessage::Multiple((*sender_data).try_into()?)
+            },
+            TransactionSenderMessage::SignatureRequest(request) => {
+                ProtoTransactionSenderMessage::SignatureRequest((*request).into())
+            },
         };
 
         Ok(Self { message: Some(message) })
@@ -169,6 +188,64 @@ impl TryFrom<SingleRoundSenderData> for proto::SingleRoundSenderData {
     }
 }
 
+//---------------------------------- MultiRoundSenderData --------------------------------------------//
+
+impl TryFrom<proto::MultiRoundSenderData> for MultiRoundSenderData {
+    type Error = String;
+
+    fn try_from(data: proto::MultiRoundSenderData) -> Result<Self, Self::Error> {
+        let sender_data = data
+            .sender_data
+            .map(TryInto::try_into)
+            .ok_or_else(|| "Multi round sender data not provided".to_string())??;
+        Ok(Self {
+            recipient_index: data.recipient_index,
+            num_recipients: data.num_recipients,
+            sender_data,
+        })
+    }
+}
+
+impl TryFrom<MultiRoundSenderData> for proto::MultiRoundSenderData {
+    type Error = String;
+
+    fn try_from(data: MultiRoundSenderData) -> Result<Self, Self::Error> {
+        Ok(Self {
+            recipient_index: data.recipient_index,
+            num_recipients: data.num_recipients,
+            sender_data: Some(data.sender_data.try_into()?),
+        })
+    }
+}
+
+//---------------------------------- MultiRoundSignatureRequest --------------------------------------------//
+
+impl TryFrom<proto::MultiRoundSignatureRequest> for MultiRoundSignatureRequest {
+    type Error = String;
+
+    fn try_from(request: proto::MultiRoundSignatureRequest) -> Result<Self, Self::Error> {
+        let total_public_nonce =
+            PublicKey::from_canonical_bytes(&request.total_public_nonce).map_err(|err| err.to_string())?;
+        let total_public_excess =
+            PublicKey::from_canonical_bytes(&request.total_public_excess).map_err(|err| err.to_string())?;
+        Ok(Self {
+            tx_id: request.tx_id.into(),
+            total_public_nonce,
+            total_public_excess,
+        })
+    }
+}
+
+impl From<MultiRoundSignatureRequest> for proto::MultiRoundSignatureRequest {
+    fn from(request: MultiRoundSignatureRequest) -> Self {
+        Self {
+            tx_id: request.tx_id.into(),
+            total_public_nonce: request.total_public_nonce.to_vec(),
+            total_public_excess: request.total_public_excess.to_vec(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -182,8 +259,28 @@ mod test {
 
     #[test]
     fn test_from_multiple() {
-        let tsm = TransactionSenderMessage::Multiple;
+        let data = MultiRoundSenderData {
+            recipient_index: 1,
+            num_recipients: 2,
+            sender_data: SingleRoundSenderData::default(),
+        };
+        let tsm = TransactionSenderMessage::Multiple(Box::new(data.clone()));
+        let ptsm = proto::TransactionSenderMessage::try_from(tsm).unwrap();
+        assert_eq!(
+            ptsm.message,
+            proto::TransactionSenderMessage::multiple(data.try_into().unwrap()).message
+        );
+    }
+
+    #[test]
+    fn test_from_signature_request() {
+        let request = MultiRoundSignatureRequest {
+            tx_id: 7u64.into(),
+            ..Default::default()
+        };
+        let tsm = TransactionSenderMessage::SignatureRequest(Box::new(request.clone()));
         let ptsm = proto::TransactionSenderMessage::try_from(tsm).unwrap();
-        assert_eq!(ptsm.message, proto::TransactionSenderMessage::multiple().message);
+        let tsm = TransactionSenderMessage::try_from(ptsm).unwrap();
+        assert!(matches!(tsm, TransactionSenderMessage::SignatureRequest(r) if *r == request));
     }
 }
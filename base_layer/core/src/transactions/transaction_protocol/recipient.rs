@@ -31,10 +31,16 @@ use tari_common_types::{
 use crate::{
     consensus::ConsensusConstants,
     transactions::{
-        key_manager::TransactionKeyManagerInterface,
+        key_manager::{TariKeyId, TransactionKeyManagerInterface},
         transaction_components::{TransactionOutput, WalletOutput},
         transaction_protocol::{
-            sender::{SingleRoundSenderData, TransactionSenderMessage},
+            multi_receiver::MultiReceiverTransactionProtocol,
+            sender::{
+                MultiRoundSenderData,
+                MultiRoundSignatureRequest,
+                SingleRoundSenderData,
+                TransactionSenderMessage,
+            },
             single_receiver::SingleReceiverTransactionProtocol,
             TransactionMetadata,
             TransactionProtocolError,
@@ -47,11 +53,13 @@ use crate::{
 pub enum RecipientState {
     Finalized(Box<RecipientSignedMessage>),
     Failed(TransactionProtocolError),
+    // Appended so that the encoding of previously stored protocols is unchanged
+    AwaitingSignatureRequest(Box<MultiRoundRecipientData>),
 }
 
 impl fmt::Display for RecipientState {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        use RecipientState::{Failed, Finalized};
+        use RecipientState::{AwaitingSignatureRequest, Failed, Finalized};
         match self {
             Finalized(signed_message) => write!(
                 f,
@@ -59,6 +67,11 @@ impl fmt::Display for RecipientState {
                 signed_message.output.features.output_type, signed_message.output.features.maturity
             ),
             Failed(err) => write!(f, "Failed({:?})", err),
+            AwaitingSignatureRequest(data) => write!(
+                f,
+                "AwaitingSignatureRequest(recipient {} of {})",
+                data.sender_info.recipient_index, data.sender_info.num_recipients
+            ),
         }
     }
 }
@@ -74,6 +87,26 @@ pub struct RecipientSignedMessage {
     pub offset: PrivateKey,
 }
 
+/// This is the message containing the public nonce and excess that a Receiver in the multi-recipient protocol sends
+/// back to the Sender in the first round
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecipientNonceMessage {
+    pub tx_id: TxId,
+    pub recipient_index: u32,
+    pub public_nonce: PublicKey,
+    pub public_excess: PublicKey,
+}
+
+/// The data a Receiver in the multi-recipient protocol keeps between the first and second rounds
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct MultiRoundRecipientData {
+    pub sender_info: MultiRoundSenderData,
+    pub output: WalletOutput,
+    pub kernel_nonce_key_id: TariKeyId,
+    pub public_nonce: PublicKey,
+    pub public_excess: PublicKey,
+}
+
 /// The generalised transaction recipient protocol. A different state transition network is followed depending on
 /// whether this is a single recipient or one of many.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -88,7 +121,9 @@ pub struct ReceiverTransactionProtocol {
 /// transaction output.
 ///
 /// The function returns the protocol in the relevant state. If this is a single-round protocol, the state will
-/// already be finalised, and the return message will be accessible from the `get_signed_data` method.
+/// already be finalised, and the return message will be accessible from the `get_signed_data` method. If this is a
+/// multi-round protocol, the nonce message for the sender is accessible from the `get_nonce_message` method and the
+/// protocol is finalised by `add_signature_request`.
 impl ReceiverTransactionProtocol {
     pub async fn new<KM: TransactionKeyManagerInterface>(
         info: TransactionSenderMessage,
@@ -101,7 +136,12 @@ impl ReceiverTransactionProtocol {
             TransactionSenderMessage::Single(v) => {
                 ReceiverTransactionProtocol::single_round(output, &v, key_manager, consensus_constants).await
             },
-            TransactionSenderMessage::Multiple => Self::multi_round(),
+            TransactionSenderMessage::Multiple(v) => {
+                ReceiverTransactionProtocol::multi_round(output, &v, key_manager, consensus_constants).await
+            },
+            TransactionSenderMessage::SignatureRequest(_) => {
+                RecipientState::Failed(TransactionProtocolError::InvalidStateError)
+            },
         };
         ReceiverTransactionProtocol { state }
     }
@@ -111,6 +151,11 @@ impl ReceiverTransactionProtocol {
         matches!(self.state, RecipientState::Finalized(_))
     }
 
+    /// Returns true if the recipient is waiting for the sender's signature request in the multi-round protocol
+    pub fn is_awaiting_signature_request(&self) -> bool {
+        matches!(self.state, RecipientState::AwaitingSignatureRequest(_))
+    }
+
     /// Method to determine if the transaction protocol has failed
     pub fn is_failed(&self) -> bool {
         matches!(&self.state, RecipientState::Failed(_))
@@ -146,10 +191,57 @@ impl ReceiverTransactionProtocol {
         }
     }
 
-    fn multi_round() -> RecipientState {
-        RecipientState::Failed(TransactionProtocolError::UnsupportedError(
-            "Multiple recipients aren't supported yet".into(),
-        ))
+    /// Retrieve the public nonce and excess to be returned to the sender in the first round of the multi-round
+    /// protocol.
+    pub fn get_nonce_message(&self) -> Result<RecipientNonceMessage, TransactionProtocolError> {
+        match &self.state {
+            RecipientState::AwaitingSignatureRequest(data) => Ok(RecipientNonceMessage {
+                tx_id: data.sender_info.sender_data.tx_id,
+                recipient_index: data.sender_info.recipient_index,
+                public_nonce: data.public_nonce.clone(),
+                public_excess: data.public_excess.clone(),
+            }),
+            _ => Err(TransactionProtocolError::InvalidStateError),
+        }
+    }
+
+    /// Sign our output with the total public nonce and excess from the sender's second round message and move to the
+    /// Finalized state. If signing fails, the protocol moves to the Failed state.
+    pub async fn add_signature_request<KM: TransactionKeyManagerInterface>(
+        &mut self,
+        request: &MultiRoundSignatureRequest,
+        key_manager: &KM,
+    ) -> Result<(), TransactionProtocolError> {
+        let data = match &self.state {
+            RecipientState::AwaitingSignatureRequest(data) => data,
+            _ => return Err(TransactionProtocolError::InvalidStateError),
+        };
+        match MultiReceiverTransactionProtocol::sign(data, request, key_manager).await {
+            Ok(signed_data) => {
+                self.state = RecipientState::Finalized(Box::new(signed_data));
+                Ok(())
+            },
+            Err(e) => {
+                self.state = RecipientState::Failed(e.clone());
+                Err(e)
+            },
+        }
+    }
+
+    /// Run the first round of the multi-round recipient protocol, which creates our kernel nonce. The output can only
+    /// be signed once the sender has collected the nonces of all the recipients.
+    async fn multi_round<KM: TransactionKeyManagerInterface>(
+        output: WalletOutput,
+        data: &MultiRoundSenderData,
+        key_manager: &KM,
+        consensus_constants: &ConsensusConstants,
+    ) -> RecipientState {
+        let nonce =
+            MultiReceiverTransactionProtocol::create_nonce(data, output, key_manager, consensus_constants).await;
+        match nonce {
+            Ok(recipient_data) => RecipientState::AwaitingSignatureRequest(Box::new(recipient_data)),
+            Err(e) => RecipientState::Failed(e),
+        }
     }
 
     /// Create an empty SenderTransactionProtocol that can be used as a placeholder in data structures that do not
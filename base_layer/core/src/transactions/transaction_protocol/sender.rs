@@ -20,7 +20,7 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::fmt;
+use std::{convert::TryFrom, fmt};
 
 use serde::{Deserialize, Serialize};
 use tari_common_types::{
@@ -53,7 +53,7 @@ use crate::{
             MAX_TRANSACTION_OUTPUTS,
         },
         transaction_protocol::{
-            recipient::RecipientSignedMessage,
+            recipient::{RecipientNonceMessage, RecipientSignedMessage},
             transaction_initializer::{RecipientDetails, SenderTransactionInitializer},
             TransactionMetadata,
             TransactionProtocolError as TPE,
@@ -121,6 +121,67 @@ impl RawTransactionInfo {
     }
 }
 
+/// The sender's view of a single recipient in the multi-recipient protocol. The public nonce and excess are received
+/// in the first round and the signed message in the second.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub(super) struct MultiRecipientInfo {
+    /// Details for the construction of this recipient's output
+    pub data: RecipientDetails,
+    /// The recipient's address, used by the sender to route the later rounds of the protocol
+    pub address: TariAddress,
+    /// The recipient's public kernel nonce
+    pub public_nonce: Option<PublicKey>,
+    /// The recipient's public kernel excess
+    pub public_excess: Option<PublicKey>,
+    /// The recipient's signed output and partial kernel signature
+    pub signed_message: Option<RecipientSignedMessage>,
+}
+
+/// All the information the sender manages in the multi-recipient protocol. The per-recipient data is kept out of
+/// `RawTransactionInfo` so that stored single recipient protocols can still be read.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub(super) struct MultiRecipientTransactionInfo {
+    pub info: RawTransactionInfo,
+    pub recipients: Vec<MultiRecipientInfo>,
+}
+
+impl MultiRecipientTransactionInfo {
+    pub fn new(info: RawTransactionInfo, recipients: Vec<RecipientDetails>, addresses: Vec<TariAddress>) -> Self {
+        let mut addresses = addresses.into_iter();
+        let recipients = recipients
+            .into_iter()
+            .map(|data| MultiRecipientInfo {
+                data,
+                address: addresses.next().unwrap_or_default(),
+                public_nonce: None,
+                public_excess: None,
+                signed_message: None,
+            })
+            .collect();
+        Self { info, recipients }
+    }
+
+    fn total_amount(&self) -> MicroMinotari {
+        self.recipients.iter().map(|r| r.data.amount).sum()
+    }
+
+    /// The total public nonce and excess over the sender and every recipient. This can only be calculated once all
+    /// the recipient nonces have been received.
+    fn total_public_nonce_and_excess(&self) -> Result<(PublicKey, PublicKey), TPE> {
+        let mut total_public_nonce = self.info.total_sender_nonce.clone();
+        let mut total_public_excess = self.info.total_sender_excess.clone();
+        for recipient in &self.recipients {
+            let (nonce, excess) = match (&recipient.public_nonce, &recipient.public_excess) {
+                (Some(nonce), Some(excess)) => (nonce, excess),
+                _ => return Err(TPE::IncompleteStateError("Missing recipient public nonce".to_string())),
+            };
+            total_public_nonce = &total_public_nonce + nonce;
+            total_public_excess = &total_public_excess + excess;
+        }
+        Ok((total_public_nonce, total_public_excess))
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub struct SingleRoundSenderData {
     /// The transaction id generated by the sender for the recipient
@@ -155,11 +216,35 @@ pub struct SingleRoundSenderData {
     pub sender_address: TariAddress,
 }
 
+/// The message sent to each recipient in the first round of the multi-recipient protocol
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct MultiRoundSenderData {
+    /// The index of the recipient in this transaction, which the recipient returns with its public nonce
+    pub recipient_index: u32,
+    /// The total number of recipients in this transaction
+    pub num_recipients: u32,
+    /// The recipient's output details, along with the sender's public nonce and excess
+    pub sender_data: SingleRoundSenderData,
+}
+
+/// The message sent to every recipient in the second round of the multi-recipient protocol, once all the public
+/// nonces have been collected
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct MultiRoundSignatureRequest {
+    /// The transaction id generated by the sender
+    pub tx_id: TxId,
+    /// The sum of the public nonces of the sender and all recipients
+    pub total_public_nonce: PublicKey,
+    /// The sum of the public excesses of the sender and all recipients
+    pub total_public_excess: PublicKey,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum TransactionSenderMessage {
     None,
     Single(Box<SingleRoundSenderData>),
-    Multiple,
+    Multiple(Box<MultiRoundSenderData>),
+    SignatureRequest(Box<MultiRoundSignatureRequest>),
 }
 
 impl TransactionSenderMessage {
@@ -173,6 +258,15 @@ impl TransactionSenderMessage {
             _ => None,
         }
     }
+
+    /// The sender's data for the recipient of this message, for both the single and multi-recipient protocols
+    pub fn sender_data(&self) -> Option<&SingleRoundSenderData> {
+        match self {
+            TransactionSenderMessage::Single(m) => Some(m),
+            TransactionSenderMessage::Multiple(m) => Some(&m.sender_data),
+            _ => None,
+        }
+    }
 }
 
 //----------------------------------------  Sender State Protocol ----------------------------------------------------//
@@ -201,9 +295,32 @@ impl SenderTransactionProtocol {
         matches!(&self.state, SenderState::SingleRoundMessageReady(_))
     }
 
-    /// Method to determine if we are in the SenderState::Finalizing state
+    /// Convenience method to check whether we're waiting for the public nonces of multiple recipients
+    pub fn is_collecting_multi_nonces(&self) -> bool {
+        matches!(&self.state, SenderState::CollectingMultiNonces(_))
+    }
+
+    /// Convenience method to check whether we're waiting for the partial signatures of multiple recipients
+    pub fn is_collecting_multi_signatures(&self) -> bool {
+        matches!(&self.state, SenderState::CollectingMultiSignatures(_))
+    }
+
+    /// Returns true if this is a multi-recipient transaction that has not been finalized yet
+    pub fn is_multi_recipient(&self) -> bool {
+        matches!(
+            &self.state,
+            SenderState::CollectingMultiNonces(_) |
+                SenderState::CollectingMultiSignatures(_) |
+                SenderState::FinalizingMultiRecipient(_)
+        )
+    }
+
+    /// Method to determine if we are in the SenderState::Finalizing or SenderState::FinalizingMultiRecipient state
     pub fn is_finalizing(&self) -> bool {
-        matches!(&self.state, SenderState::Finalizing(_))
+        matches!(
+            &self.state,
+            SenderState::Finalizing(_) | SenderState::FinalizingMultiRecipient(_)
+        )
     }
 
     /// Method to determine if we are in the SenderState::FinalizedTransaction state
@@ -249,6 +366,9 @@ impl SenderTransactionProtocol {
             SenderState::Finalizing(info) |
             SenderState::SingleRoundMessageReady(info) |
             SenderState::CollectingSingleSignature(info) => info.tx_id == tx_id,
+            SenderState::CollectingMultiNonces(multi) |
+            SenderState::CollectingMultiSignatures(multi) |
+            SenderState::FinalizingMultiRecipient(multi) => multi.info.tx_id == tx_id,
             _ => false,
         }
     }
@@ -258,6 +378,9 @@ impl SenderTransactionProtocol {
             SenderState::Finalizing(info) |
             SenderState::SingleRoundMessageReady(info) |
             SenderState::CollectingSingleSignature(info) => Ok(info.tx_id),
+            SenderState::CollectingMultiNonces(multi) |
+            SenderState::CollectingMultiSignatures(multi) |
+            SenderState::FinalizingMultiRecipient(multi) => Ok(multi.info.tx_id),
             _ => Err(TPE::InvalidStateError),
         }
     }
@@ -272,6 +395,9 @@ impl SenderTransactionProtocol {
                 .as_ref()
                 .map(|data| data.amount)
                 .unwrap_or(MicroMinotari::zero())),
+            SenderState::CollectingMultiNonces(multi) |
+            SenderState::CollectingMultiSignatures(multi) |
+            SenderState::FinalizingMultiRecipient(multi) => Ok(multi.total_amount()),
             SenderState::FinalizedTransaction(_) => Err(TPE::InvalidStateError),
             SenderState::Failed(_) => Err(TPE::InvalidStateError),
         }
@@ -284,22 +410,27 @@ impl SenderTransactionProtocol {
             SenderState::Initializing(info) |
             SenderState::Finalizing(info) |
             SenderState::SingleRoundMessageReady(info) |
-            SenderState::CollectingSingleSignature(info) => {
-                let mut amount = info
-                    .change_output
-                    .as_ref()
-                    .map(|output| output.output.value)
-                    .unwrap_or(MicroMinotari::zero());
-                for output in &info.outputs {
-                    amount += output.output.value
-                }
-                Ok(amount)
-            },
+            SenderState::CollectingSingleSignature(info) => Ok(Self::amount_to_self(info)),
+            SenderState::CollectingMultiNonces(multi) |
+            SenderState::CollectingMultiSignatures(multi) |
+            SenderState::FinalizingMultiRecipient(multi) => Ok(Self::amount_to_self(&multi.info)),
             SenderState::FinalizedTransaction(_) => Err(TPE::InvalidStateError),
             SenderState::Failed(_) => Err(TPE::InvalidStateError),
         }
     }
 
+    fn amount_to_self(info: &RawTransactionInfo) -> MicroMinotari {
+        let mut amount = info
+            .change_output
+            .as_ref()
+            .map(|output| output.output.value)
+            .unwrap_or(MicroMinotari::zero());
+        for output in &info.outputs {
+            amount += output.output.value
+        }
+        amount
+    }
+
     /// This function will return the value of the change transaction
     pub fn get_change_amount(&self) -> Result<MicroMinotari, TPE> {
         match &self.state {
@@ -311,6 +442,14 @@ impl SenderTransactionProtocol {
                 .as_ref()
                 .map(|output| output.output.value)
                 .unwrap_or(MicroMinotari::zero())),
+            SenderState::CollectingMultiNonces(multi) |
+            SenderState::CollectingMultiSignatures(multi) |
+            SenderState::FinalizingMultiRecipient(multi) => Ok(multi
+                .info
+                .change_output
+                .as_ref()
+                .map(|output| output.output.value)
+                .unwrap_or(MicroMinotari::zero())),
             SenderState::FinalizedTransaction(_) => Err(TPE::InvalidStateError),
             SenderState::Failed(_) => Err(TPE::InvalidStateError),
         }
@@ -326,6 +465,11 @@ impl SenderTransactionProtocol {
             SenderState::CollectingSingleSignature(info) => {
                 Ok(info.change_output.as_ref().map(|output| output.output.clone()))
             },
+            SenderState::CollectingMultiNonces(multi) |
+            SenderState::CollectingMultiSignatures(multi) |
+            SenderState::FinalizingMultiRecipient(multi) => {
+                Ok(multi.info.change_output.as_ref().map(|output| output.output.clone()))
+            },
             SenderState::FinalizedTransaction(_) => Err(TPE::InvalidStateError),
             SenderState::Failed(_) => Err(TPE::InvalidStateError),
         }
@@ -342,6 +486,9 @@ impl SenderTransactionProtocol {
                     .as_ref()
                     .map(|data| data.recipient_sender_offset_key_id.clone())
             }),
+            SenderState::CollectingMultiNonces(_) |
+            SenderState::CollectingMultiSignatures(_) |
+            SenderState::FinalizingMultiRecipient(_) => Ok(None),
             SenderState::FinalizedTransaction(_) => Err(TPE::InvalidStateError),
             SenderState::Failed(_) => Err(TPE::InvalidStateError),
         }
@@ -357,7 +504,11 @@ impl SenderTransactionProtocol {
                     v.recipient_sender_offset_key_id = key_id;
                 }
             },
-            SenderState::FinalizedTransaction(_) | SenderState::Failed(_) => return Err(TPE::InvalidStateError),
+            SenderState::CollectingMultiNonces(_) |
+            SenderState::CollectingMultiSignatures(_) |
+            SenderState::FinalizingMultiRecipient(_) |
+            SenderState::FinalizedTransaction(_) |
+            SenderState::Failed(_) => return Err(TPE::InvalidStateError),
         }
 
         Ok(())
@@ -370,6 +521,9 @@ impl SenderTransactionProtocol {
             SenderState::Finalizing(info) |
             SenderState::SingleRoundMessageReady(info) |
             SenderState::CollectingSingleSignature(info) => Ok(info.metadata.fee),
+            SenderState::CollectingMultiNonces(multi) |
+            SenderState::CollectingMultiSignatures(multi) |
+            SenderState::FinalizingMultiRecipient(multi) => Ok(multi.info.metadata.fee),
             SenderState::FinalizedTransaction(info) => {
                 Ok(info.body.kernels().first().ok_or(TPE::InvalidStateError)?.fee)
             },
@@ -377,6 +531,18 @@ impl SenderTransactionProtocol {
         }
     }
 
+    /// Return the addresses of the recipients of a multi-recipient transaction, in recipient index order
+    pub fn get_multi_recipient_addresses(&self) -> Result<Vec<TariAddress>, TPE> {
+        match &self.state {
+            SenderState::CollectingMultiNonces(multi) |
+            SenderState::CollectingMultiSignatures(multi) |
+            SenderState::FinalizingMultiRecipient(multi) => {
+                Ok(multi.recipients.iter().map(|r| r.address.clone()).collect())
+            },
+            _ => Err(TPE::InvalidStateError),
+        }
+    }
+
     /// Build the sender's message for the single-round protocol (one recipient) and move to next State
     pub async fn build_single_round_message<KM: TransactionKeyManagerInterface>(
         &mut self,
@@ -412,54 +578,85 @@ impl SenderTransactionProtocol {
             SenderState::SingleRoundMessageReady(info) | SenderState::CollectingSingleSignature(info) => {
                 let recipient_data = info
                     .recipient_data
-                    .as_ref()
+                    .clone()
                     .ok_or_else(|| TPE::IncompleteStateError("Missing recipient data".to_string()))?;
-                let recipient_output_features = recipient_data.recipient_output_features.clone();
-                let recipient_script = recipient_data.recipient_script.clone();
-                let recipient_script_offset_secret_key_id = &recipient_data.recipient_sender_offset_key_id;
-                let recipient_covenant = recipient_data.recipient_covenant.clone();
-                let recipient_minimum_value_promise = recipient_data.recipient_minimum_value_promise;
-                let amount = recipient_data.amount;
-                let ephemeral_public_key_nonce = recipient_data.recipient_ephemeral_public_key_nonce.clone();
 
                 let (public_nonce, public_excess) =
                     SenderTransactionProtocol::calculate_total_nonce_and_total_public_excess(info, key_manager).await?;
-                let sender_offset_public_key = key_manager
-                    .get_public_key_at_key_id(recipient_script_offset_secret_key_id)
-                    .await?;
                 // we update this as we send this to what we sent.
-                info.total_sender_excess = public_excess.clone();
-                info.total_sender_nonce = public_nonce.clone();
-
-                let ephemeral_public_nonce = key_manager
-                    .get_public_key_at_key_id(&ephemeral_public_key_nonce)
-                    .await?;
+                info.total_sender_excess = public_excess;
+                info.total_sender_nonce = public_nonce;
 
-                let output_version = TransactionOutputVersion::get_current_version();
-                let kernel_version = TransactionKernelVersion::get_current_version();
+                Self::build_sender_data(info, &recipient_data, key_manager).await
+            },
+            _ => Err(TPE::InvalidStateError),
+        }
+    }
 
-                Ok(SingleRoundSenderData {
-                    tx_id: info.tx_id,
-                    amount,
-                    public_nonce,
-                    public_excess,
-                    metadata: info.metadata.clone(),
-                    message: info.text_message.clone(),
-                    features: recipient_output_features,
-                    script: recipient_script,
-                    sender_offset_public_key,
-                    ephemeral_public_nonce,
-                    covenant: recipient_covenant,
-                    minimum_value_promise: recipient_minimum_value_promise,
-                    output_version,
-                    kernel_version,
-                    sender_address: info.sender_address.clone(),
-                })
+    /// Return the first round messages of the multi-recipient protocol, one for each recipient
+    pub async fn get_multi_round_messages<KM: TransactionKeyManagerInterface>(
+        &mut self,
+        key_manager: &KM,
+    ) -> Result<Vec<MultiRoundSenderData>, TPE> {
+        match &mut self.state {
+            SenderState::CollectingMultiNonces(multi) => {
+                let (public_nonce, public_excess) =
+                    SenderTransactionProtocol::calculate_total_nonce_and_total_public_excess(&multi.info, key_manager)
+                        .await?;
+                multi.info.total_sender_excess = public_excess;
+                multi.info.total_sender_nonce = public_nonce;
+
+                let num_recipients = u32::try_from(multi.recipients.len())
+                    .map_err(|_| TPE::ValidationError("Too many recipients".to_string()))?;
+                let mut messages = Vec::with_capacity(multi.recipients.len());
+                for (recipient_index, recipient) in (0..num_recipients).zip(multi.recipients.iter()) {
+                    let sender_data = Self::build_sender_data(&multi.info, &recipient.data, key_manager).await?;
+                    messages.push(MultiRoundSenderData {
+                        recipient_index,
+                        num_recipients,
+                        sender_data,
+                    });
+                }
+                Ok(messages)
             },
             _ => Err(TPE::InvalidStateError),
         }
     }
 
+    async fn build_sender_data<KM: TransactionKeyManagerInterface>(
+        info: &RawTransactionInfo,
+        recipient_data: &RecipientDetails,
+        key_manager: &KM,
+    ) -> Result<SingleRoundSenderData, TPE> {
+        let sender_offset_public_key = key_manager
+            .get_public_key_at_key_id(&recipient_data.recipient_sender_offset_key_id)
+            .await?;
+        let ephemeral_public_nonce = key_manager
+            .get_public_key_at_key_id(&recipient_data.recipient_ephemeral_public_key_nonce)
+            .await?;
+
+        let output_version = TransactionOutputVersion::get_current_version();
+        let kernel_version = TransactionKernelVersion::get_current_version();
+
+        Ok(SingleRoundSenderData {
+            tx_id: info.tx_id,
+            amount: recipient_data.amount,
+            public_nonce: info.total_sender_nonce.clone(),
+            public_excess: info.total_sender_excess.clone(),
+            metadata: info.metadata.clone(),
+            message: info.text_message.clone(),
+            features: recipient_data.recipient_output_features.clone(),
+            script: recipient_data.recipient_script.clone(),
+            sender_offset_public_key,
+            ephemeral_public_nonce,
+            covenant: recipient_data.recipient_covenant.clone(),
+            minimum_value_promise: recipient_data.recipient_minimum_value_promise,
+            output_version,
+            kernel_version,
+            sender_address: info.sender_address.clone(),
+        })
+    }
+
     async fn calculate_total_nonce_and_total_public_excess<KM: TransactionKeyManagerInterface>(
         info: &RawTransactionInfo,
         key_manager: &KM,
@@ -502,8 +699,13 @@ impl SenderTransactionProtocol {
         match self.state {
             SenderState::CollectingSingleSignature(ref info) => {
                 let mut info = info.clone();
+                let recipient_data = info
+                    .recipient_data
+                    .as_ref()
+                    .ok_or_else(|| TPE::IncompleteStateError("Missing recipient data".to_string()))?;
                 // Add sender signature to recipient partial signature
-                rec.output.metadata_signature = self.add_sender_partial_signature(&rec, &info, key_manager).await?;
+                rec.output.metadata_signature =
+                    Self::add_sender_partial_signature(&rec, recipient_data, key_manager).await?;
                 // Consolidate transaction info
                 info.add_recipient_signed_message(rec);
                 self.state = SenderState::Finalizing(info);
@@ -530,10 +732,112 @@ impl SenderTransactionProtocol {
         }
     }
 
+    /// Add the public nonce and excess of one of the recipients in the multi-recipient protocol. Once every recipient
+    /// has replied, the protocol moves to the CollectingMultiSignatures state.
+    pub fn add_multi_recipient_nonce(&mut self, msg: RecipientNonceMessage) -> Result<(), TPE> {
+        match &mut self.state {
+            SenderState::CollectingMultiNonces(multi) => {
+                if msg.tx_id != multi.info.tx_id {
+                    return Err(TPE::ValidationError(
+                        "Recipient nonce is for a different transaction".into(),
+                    ));
+                }
+                let recipient = usize::try_from(msg.recipient_index)
+                    .ok()
+                    .and_then(|i| multi.recipients.get_mut(i))
+                    .ok_or_else(|| TPE::ValidationError("Invalid recipient index".into()))?;
+                recipient.public_nonce = Some(msg.public_nonce);
+                recipient.public_excess = Some(msg.public_excess);
+
+                if multi.recipients.iter().all(|r| r.public_nonce.is_some()) {
+                    self.state = SenderState::CollectingMultiSignatures(multi.clone());
+                }
+                Ok(())
+            },
+            _ => Err(TPE::InvalidStateError),
+        }
+    }
+
+    /// Return the second round message of the multi-recipient protocol, which is sent to every recipient
+    pub fn get_multi_round_signature_request(&self) -> Result<MultiRoundSignatureRequest, TPE> {
+        match &self.state {
+            SenderState::CollectingMultiSignatures(multi) => {
+                let (total_public_nonce, total_public_excess) = multi.total_public_nonce_and_excess()?;
+                Ok(MultiRoundSignatureRequest {
+                    tx_id: multi.info.tx_id,
+                    total_public_nonce,
+                    total_public_excess,
+                })
+            },
+            _ => Err(TPE::InvalidStateError),
+        }
+    }
+
+    /// Add the signed output and partial signature of one of the recipients in the multi-recipient protocol. The
+    /// reply is matched to a recipient by its public excess and nonce, and its partial kernel signature is checked
+    /// against the total nonce and excess so that a bad signature is attributed to the recipient that sent it rather
+    /// than failing the aggregate kernel signature. Once every recipient has replied, the protocol moves to the
+    /// FinalizingMultiRecipient state.
+    pub async fn add_multi_recipient_signature<KM: TransactionKeyManagerInterface>(
+        &mut self,
+        mut rec: RecipientSignedMessage,
+        key_manager: &KM,
+    ) -> Result<(), TPE> {
+        match &self.state {
+            SenderState::CollectingMultiSignatures(multi) => {
+                let mut multi = multi.clone();
+                if rec.tx_id != multi.info.tx_id {
+                    return Err(TPE::ValidationError(
+                        "Recipient reply is for a different transaction".into(),
+                    ));
+                }
+                let (total_public_nonce, total_public_excess) = multi.total_public_nonce_and_excess()?;
+                let (index, recipient) = multi
+                    .recipients
+                    .iter_mut()
+                    .enumerate()
+                    .find(|(_, r)| {
+                        r.public_excess.as_ref() == Some(&rec.public_spend_key) &&
+                            r.public_nonce.as_ref() == Some(rec.partial_signature.get_public_nonce())
+                    })
+                    .ok_or_else(|| TPE::ValidationError("Recipient reply does not match any recipient".into()))?;
+                if recipient.signed_message.is_some() {
+                    return Err(TPE::ValidationError("Recipient has already replied".into()));
+                }
+                let challenge = TransactionKernel::build_kernel_challenge_from_tx_meta(
+                    &TransactionKernelVersion::get_current_version(),
+                    &total_public_nonce,
+                    &total_public_excess,
+                    &multi.info.metadata,
+                );
+                if !rec
+                    .partial_signature
+                    .verify_raw_uniform(&rec.public_spend_key, &challenge)
+                {
+                    return Err(TPE::ValidationError(format!(
+                        "Recipient {} ({}) sent an invalid partial kernel signature",
+                        index, recipient.address
+                    )));
+                }
+                // Add sender signature to recipient partial signature
+                rec.output.metadata_signature =
+                    Self::add_sender_partial_signature(&rec, &recipient.data, key_manager).await?;
+                recipient.signed_message = Some(rec);
+
+                self.state = if multi.recipients.iter().all(|r| r.signed_message.is_some()) {
+                    SenderState::FinalizingMultiRecipient(multi)
+                } else {
+                    SenderState::CollectingMultiSignatures(multi)
+                };
+                Ok(())
+            },
+            _ => Err(TPE::InvalidStateError),
+        }
+    }
+
     async fn add_sender_partial_signature<KM: TransactionKeyManagerInterface>(
-        &self,
         rec: &RecipientSignedMessage,
-        info: &RawTransactionInfo,
+        recipient_data: &RecipientDetails,
         key_manager: &KM,
     ) -> Result<ComAndPubSignature, TPE> {
         let received_output = &rec.output;
@@ -542,41 +846,15 @@ impl SenderTransactionProtocol {
         let metadata_message = TransactionOutput::metadata_signature_message_from_parts(
             &version,
             &received_output.script, /* receiver chooses script here, can change fee per gram see issue: https://github.com/tari-project/tari/issues/5430 */
-            &info
-                .recipient_data
-                .as_ref()
-                .ok_or_else(|| TPE::IncompleteStateError("Missing data `recipient_output_features`".to_string()))?
-                .recipient_output_features,
-            &info
-                .recipient_data
-                .as_ref()
-                .ok_or_else(|| TPE::IncompleteStateError("Missing data `recipient_covenant`".to_string()))?
-                .recipient_covenant,
+            &recipient_data.recipient_output_features,
+            &recipient_data.recipient_covenant,
             &received_output.encrypted_data,
-            &info
-                .recipient_data
-                .as_ref()
-                .ok_or_else(|| TPE::IncompleteStateError("Missing data 'recipient_minimum_value_promise'".to_string()))?
-                .recipient_minimum_value_promise,
+            &recipient_data.recipient_minimum_value_promise,
         );
-        let ephemeral_public_key_nonce = info
-            .recipient_data
-            .as_ref()
-            .ok_or_else(|| {
-                TPE::IncompleteStateError("Missing data `recipient_ephemeral_public_key_nonce`".to_string())
-            })?
-            .recipient_ephemeral_public_key_nonce
-            .clone();
-        let recipient_sender_offset_key_id = info
-            .recipient_data
-            .as_ref()
-            .ok_or_else(|| TPE::IncompleteStateError("Missing data `recipient_sender_offset_key_id`".to_string()))?
-            .recipient_sender_offset_key_id
-            .clone();
         let sender_metadata_signature = key_manager
             .get_sender_partial_metadata_signature(
-                &ephemeral_public_key_nonce,
-                &recipient_sender_offset_key_id,
+                &recipient_data.recipient_ephemeral_public_key_nonce,
+                &recipient_data.recipient_sender_offset_key_id,
                 &received_output.commitment,
                 received_output.metadata_signature.ephemeral_commitment(),
                 &version,
@@ -588,14 +866,28 @@ impl SenderTransactionProtocol {
         Ok(metadata_signature)
     }
 
-    /// Attempts to build the final transaction.
+    /// Attempts to build the final transaction. `recipients` holds the recipient data of the multi-recipient protocol
+    /// and is empty otherwise.
     #[allow(clippy::too_many_lines)]
     async fn build_transaction<KM: TransactionKeyManagerInterface>(
         info: &RawTransactionInfo,
+        recipients: &[MultiRecipientInfo],
         key_manager: &KM,
     ) -> Result<Transaction, TPE> {
         let mut tx_builder = TransactionBuilder::new();
-        let (total_public_nonce, total_public_excess) = if info.recipient_data.is_none() {
+        let (total_public_nonce, total_public_excess) = if !recipients.is_empty() {
+            let mut total_public_nonce = info.total_sender_nonce.clone();
+            let mut total_public_excess = info.total_sender_excess.clone();
+            for recipient in recipients {
+                let rec = recipient
+                    .signed_message
+                    .as_ref()
+                    .ok_or_else(|| TPE::IncompleteStateError("Missing recipient signed message".to_string()))?;
+                total_public_nonce = &total_public_nonce + rec.partial_signature.get_public_nonce();
+                total_public_excess = &total_public_excess + &rec.public_spend_key;
+            }
+            (total_public_nonce, total_public_excess)
+        } else if info.recipient_data.is_none() {
             // we dont have a recipient and thus we have not yet calculated the sender_nonce and sender_offset_excess
             SenderTransactionProtocol::calculate_total_nonce_and_total_public_excess(info, key_manager).await?
         } else {
@@ -609,6 +901,14 @@ impl SenderTransactionProtocol {
         let mut signature = info.recipient_partial_kernel_signature.clone();
         let mut script_keys = Vec::new();
         let mut sender_offset_keys = Vec::new();
+        for recipient in recipients {
+            if let Some(rec) = &recipient.signed_message {
+                offset = offset + &rec.offset;
+                signature = &signature + &rec.partial_signature;
+                tx_builder.add_output(rec.output.clone());
+            }
+            sender_offset_keys.push(recipient.data.recipient_sender_offset_key_id.clone());
+        }
         let kernel_version = TransactionKernelVersion::get_current_version();
 
         let kernel_message = TransactionKernel::build_kernel_signature_message(
@@ -719,26 +1019,27 @@ impl SenderTransactionProtocol {
 
     /// Performs sanity checks on the collected transaction pieces prior to building the final Transaction instance
     fn validate(&self) -> Result<(), TPE> {
-        if let SenderState::Finalizing(info) = &self.state {
-            let fee = info.metadata.fee;
-            // The fee must be greater than MIN_FEE to prevent spam attacks
-            if fee < Fee::MINIMUM_TRANSACTION_FEE {
-                return Err(TPE::ValidationError("Fee is less than the minimum".into()));
-            }
-            // Prevent overflow attacks by imposing sane limits on some key parameters
-            if info.inputs.len() > MAX_TRANSACTION_INPUTS {
-                return Err(TPE::ValidationError("Too many inputs in transaction".into()));
-            }
-            if info.outputs.len() > MAX_TRANSACTION_OUTPUTS {
-                return Err(TPE::ValidationError("Too many outputs in transaction".into()));
-            }
-            if info.inputs.is_empty() {
-                return Err(TPE::ValidationError("A transaction cannot have zero inputs".into()));
-            }
-            Ok(())
-        } else {
-            Err(TPE::InvalidStateError)
+        let (info, num_recipient_outputs) = match &self.state {
+            SenderState::Finalizing(info) => (info.as_ref(), 0),
+            SenderState::FinalizingMultiRecipient(multi) => (&multi.info, multi.recipients.len()),
+            _ => return Err(TPE::InvalidStateError),
+        };
+        let fee = info.metadata.fee;
+        // The fee must be greater than MIN_FEE to prevent spam attacks
+        if fee < Fee::MINIMUM_TRANSACTION_FEE {
+            return Err(TPE::ValidationError("Fee is less than the minimum".into()));
         }
+        // Prevent overflow attacks by imposing sane limits on some key parameters
+        if info.inputs.len() > MAX_TRANSACTION_INPUTS {
+            return Err(TPE::ValidationError("Too many inputs in transaction".into()));
+        }
+        if info.outputs.len() + num_recipient_outputs > MAX_TRANSACTION_OUTPUTS {
+            return Err(TPE::ValidationError("Too many outputs in transaction".into()));
+        }
+        if info.inputs.is_empty() {
+            return Err(TPE::ValidationError("A transaction cannot have zero inputs".into()));
+        }
+        Ok(())
     }
 
     /// Try and finalise the transaction. If the current state is Finalizing, the result will be whether the
@@ -748,24 +1049,24 @@ impl SenderTransactionProtocol {
     /// First we validate against internal sanity checks, then try build the transaction. If any step fails,
     /// the transaction protocol moves to Failed state and we are done; you can't rescue the situation.
     pub async fn finalize<KM: TransactionKeyManagerInterface>(&mut self, key_manager: &KM) -> Result<(), TPE> {
-        match &self.state {
-            SenderState::Finalizing(info) => {
-                if let Err(e) = self.validate() {
-                    self.state = SenderState::Failed(e.clone());
-                    return Err(e);
-                }
-                match Self::build_transaction(info, key_manager).await {
-                    Ok(transaction) => {
-                        self.state = SenderState::FinalizedTransaction(transaction);
-                        Ok(())
-                    },
-                    Err(e) => {
-                        self.state = SenderState::Failed(e.clone());
-                        Err(e)
-                    },
-                }
+        let (info, recipients) = match &self.state {
+            SenderState::Finalizing(info) => (info.as_ref(), &[][..]),
+            SenderState::FinalizingMultiRecipient(multi) => (&multi.info, multi.recipients.as_slice()),
+            _ => return Err(TPE::InvalidStateError),
+        };
+        if let Err(e) = self.validate() {
+            self.state = SenderState::Failed(e.clone());
+            return Err(e);
+        }
+        match Self::build_transaction(info, recipients, key_manager).await {
+            Ok(transaction) => {
+                self.state = SenderState::FinalizedTransaction(transaction);
+                Ok(())
+            },
+            Err(e) => {
+                self.state = SenderState::Failed(e.clone());
+                Err(e)
             },
-            _ => Err(TPE::InvalidStateError),
         }
     }
 
@@ -813,6 +1114,13 @@ pub(super) enum SenderState {
     FinalizedTransaction(Transaction),
     /// An unrecoverable failure has occurred and the transaction must be abandoned
     Failed(TPE),
+    // The multi-recipient states are appended so that the encoding of previously stored protocols is unchanged
+    /// Waiting for the public nonce and excess of every recipient in the multi-recipient protocol
+    CollectingMultiNonces(Box<MultiRecipientTransactionInfo>),
+    /// Waiting for the partial signature of every recipient in the multi-recipient protocol
+    CollectingMultiSignatures(Box<MultiRecipientTransactionInfo>),
+    /// All the recipients in the multi-recipient protocol have replied and the final transaction can be built
+    FinalizingMultiRecipient(Box<MultiRecipientTransactionInfo>),
 }
 
 impl SenderState {
@@ -868,6 +1176,27 @@ impl fmt::Display for SenderState {
                 txn.body.outputs().len()
             ),
             Failed(err) => write!(f, "Failed({:?})", err),
+            CollectingMultiNonces(multi) => write!(
+                f,
+                "CollectingMultiNonces({} input(s), {} output(s), {} recipient(s))",
+                multi.info.inputs.len(),
+                multi.info.outputs.len(),
+                multi.recipients.len()
+            ),
+            CollectingMultiSignatures(multi) => write!(
+                f,
+                "CollectingMultiSignatures({} input(s), {} output(s), {} recipient(s))",
+                multi.info.inputs.len(),
+                multi.info.outputs.len(),
+                multi.recipients.len()
+            ),
+            FinalizingMultiRecipient(multi) => write!(
+                f,
+                "FinalizingMultiRecipient({} input(s), {} output(s), {} recipient(s))",
+                multi.info.inputs.len(),
+                multi.info.outputs.len(),
+                multi.recipients.len()
+            ),
         }
     }
 }
@@ -876,7 +1205,11 @@ impl fmt::Display for SenderState {
 
 #[cfg(test)]
 mod test {
-    use tari_common_types::{key_branches::TransactionKeyManagerBranch, tari_address::TariAddress, types::PrivateKey};
+    use tari_common_types::{
+        key_branches::TransactionKeyManagerBranch,
+        tari_address::TariAddress,
+        types::{PrivateKey, Signature},
+    };
     use tari_crypto::signatures::CommitmentAndPublicKeySignature;
     use tari_key_manager::key_manager_service::KeyManagerInterface;
     use tari_script::{inputs, script, ExecutionStack, TariScript};
@@ -900,6 +1233,7 @@ mod test {
                 WalletOutput,
             },
             transaction_protocol::{
+                recipient::ReceiverTransactionProtocol,
                 sender::{SenderTransactionProtocol, TransactionSenderMessage},
                 single_receiver::SingleReceiverTransactionProtocol,
                 TransactionProtocolError,
@@ -911,7 +1245,7 @@ mod test {
     #[test]
     fn test_not_single() {
         assert_eq!(TransactionSenderMessage::None.single(), None);
-        assert_eq!(TransactionSenderMessage::Multiple.single(), None);
+        assert_eq!(TransactionSenderMessage::Multiple(Box::default()).single(), None);
     }
 
     #[tokio::test]
@@ -1444,6 +1778,146 @@ mod test {
         assert!(validator.validate(tx, None, None, u64::MAX).is_ok());
     }
 
+    #[tokio::test]
+    async fn multiple_recipients_with_change() {
+        let rules = create_consensus_rules();
+        let key_manager = create_memory_db_key_manager().unwrap();
+        let factories = CryptoFactories::default();
+        let input = create_test_input(MicroMinotari(25000), 0, &key_manager, vec![]).await;
+        let consensus_constants = create_consensus_constants(0);
+        let mut builder = SenderTransactionProtocol::builder(consensus_constants.clone(), key_manager.clone());
+        let script = script!(Nop);
+        let change = TestParams::new(&key_manager).await;
+        let amounts = [MicroMinotari(5000), MicroMinotari(3000), MicroMinotari(2000)];
+        builder
+            .with_lock_height(0)
+            .with_fee_per_gram(MicroMinotari(20))
+            .with_change_data(
+                script.clone(),
+                inputs!(change.script_key_pk),
+                change.script_key_id.clone(),
+                change.commitment_mask_key_id.clone(),
+                Covenant::default(),
+                TariAddress::default(),
+            )
+            .with_input(input)
+            .await
+            .unwrap();
+        for amount in amounts {
+            builder
+                .with_recipient_data(
+                    script.clone(),
+                    OutputFeatures::default(),
+                    Covenant::default(),
+                    0.into(),
+                    amount,
+                )
+                .await
+                .unwrap();
+        }
+        let mut alice = builder.build().await.unwrap();
+        assert!(alice.is_collecting_multi_nonces());
+        assert_eq!(alice.get_amount_to_recipient().unwrap(), MicroMinotari(10000));
+        let msgs = alice.get_multi_round_messages(&key_manager).await.unwrap();
+        assert_eq!(msgs.len(), amounts.len());
+
+        // Round one: each receiver creates its output and nonce
+        let mut receivers = Vec::new();
+        for msg in msgs {
+            assert_eq!(msg.num_recipients, 3);
+            let receiver_key = TestParams::new(&key_manager).await;
+            let mut output = WalletOutput::new_current_version(
+                msg.sender_data.amount,
+                receiver_key.commitment_mask_key_id,
+                OutputFeatures::default(),
+                script.clone(),
+                ExecutionStack::default(),
+                receiver_key.script_key_id,
+                msg.sender_data.sender_offset_public_key.clone(),
+                CommitmentAndPublicKeySignature::default(),
+                0,
+                Covenant::default(),
+                EncryptedData::default(),
+                0.into(),
+                PaymentId::Empty,
+                &key_manager,
+            )
+            .await
+            .unwrap();
+            let metadata_message = TransactionOutput::metadata_signature_message(&output);
+            output.metadata_signature = key_manager
+                .get_receiver_partial_metadata_signature(
+                    &output.spending_key_id,
+                    &output.value.into(),
+                    &output.sender_offset_public_key,
+                    &msg.sender_data.ephemeral_public_nonce,
+                    &output.version,
+                    &metadata_message,
+                    output.features.range_proof_type,
+                )
+                .await
+                .unwrap();
+            let receiver = ReceiverTransactionProtocol::new(
+                TransactionSenderMessage::Multiple(Box::new(msg)),
+                output,
+                &key_manager,
+                &consensus_constants,
+            )
+            .await;
+            assert!(receiver.is_awaiting_signature_request());
+            receivers.push(receiver);
+        }
+        for receiver in &receivers {
+            assert!(!alice.is_collecting_multi_signatures());
+            alice
+                .add_multi_recipient_nonce(receiver.get_nonce_message().unwrap())
+                .unwrap();
+        }
+
+        // Round two: each receiver signs with the total nonce and excess
+        assert!(alice.is_collecting_multi_signatures());
+        let request = alice.get_multi_round_signature_request().unwrap();
+        for receiver in &mut receivers {
+            receiver.add_signature_request(&request, &key_manager).await.unwrap();
+            assert!(receiver.is_finalized());
+            let signed_data = receiver.get_signed_data().unwrap().clone();
+            // A reply with a bad partial signature is rejected and names the recipient
+            let mut bad_data = signed_data.clone();
+            bad_data.partial_signature = Signature::new(
+                signed_data.partial_signature.get_public_nonce().clone(),
+                signed_data.partial_signature.get_signature() + &PrivateKey::from(1u64),
+            );
+            let err = alice
+                .add_multi_recipient_signature(bad_data, &key_manager)
+                .await
+                .unwrap_err();
+            match err {
+                TransactionProtocolError::ValidationError(msg) => {
+                    assert!(msg.contains("invalid partial kernel signature"), "{}", msg)
+                },
+                err => panic!("Unexpected error {:?}", err),
+            }
+            alice
+                .add_multi_recipient_signature(signed_data.clone(), &key_manager)
+                .await
+                .unwrap();
+            // A repeated reply is rejected
+            assert!(alice
+                .add_multi_recipient_signature(signed_data, &key_manager)
+                .await
+                .is_err());
+        }
+
+        assert!(alice.is_finalizing());
+        alice.finalize(&key_manager).await.unwrap();
+        assert!(alice.is_finalized());
+        let tx = alice.get_transaction().unwrap();
+        assert_eq!(tx.body.inputs().len(), 1);
+        assert_eq!(tx.body.outputs().len(), 4);
+        let validator = TransactionInternalConsistencyValidator::new(false, rules, factories);
+        assert!(validator.validate(tx, None, None, u64::MAX).is_ok());
+    }
+
     #[tokio::test]
     async fn disallow_fee_larger_than_amount() {
         // Alice's parameters
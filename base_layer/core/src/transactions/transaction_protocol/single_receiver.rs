@@ -20,12 +20,12 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use tari_common_types::key_branches::TransactionKeyManagerBranch;
+use tari_common_types::{key_branches::TransactionKeyManagerBranch, types::PublicKey};
 
 use crate::{
     consensus::ConsensusConstants,
     transactions::{
-        key_manager::{TransactionKeyManagerInterface, TxoStage},
+        key_manager::{TariKeyId, TransactionKeyManagerInterface, TxoStage},
         transaction_components::{TransactionKernel, WalletOutput},
         transaction_protocol::{
             recipient::RecipientSignedMessage,
@@ -52,11 +52,35 @@ impl SingleReceiverTransactionProtocol {
         consensus_constants: &ConsensusConstants,
     ) -> Result<RecipientSignedMessage, TPE> {
         SingleReceiverTransactionProtocol::validate_sender_data(sender_info, consensus_constants)?;
-        let transaction_output = output.to_transaction_output(key_manager).await?;
-
         let public_nonce = key_manager
             .get_next_key(TransactionKeyManagerBranch::KernelNonce.get_branch_key())
             .await?;
+        let public_excess = key_manager
+            .get_txo_kernel_signature_excess_with_offset(&output.spending_key_id, &public_nonce.key_id)
+            .await?;
+        SingleReceiverTransactionProtocol::sign(
+            sender_info,
+            output,
+            &public_nonce.key_id,
+            &(&sender_info.public_nonce + &public_nonce.pub_key),
+            &(&sender_info.public_excess + &public_excess),
+            key_manager,
+        )
+        .await
+    }
+
+    /// Constructs the output and the partial kernel signature over the given total public nonce and excess. In the
+    /// single-round protocol the totals are the sender's values plus our own, in the multi-round protocol the sender
+    /// provides them once every recipient's nonce is known.
+    pub(super) async fn sign<KM: TransactionKeyManagerInterface>(
+        sender_info: &SingleRoundSenderData,
+        output: WalletOutput,
+        kernel_nonce_key_id: &TariKeyId,
+        total_public_nonce: &PublicKey,
+        total_public_excess: &PublicKey,
+        key_manager: &KM,
+    ) -> Result<RecipientSignedMessage, TPE> {
+        let transaction_output = output.to_transaction_output(key_manager).await?;
         let tx_meta = if output.is_burned() {
             let mut meta = sender_info.metadata.clone();
             meta.burn_commitment = Some(transaction_output.commitment().clone());
@@ -65,7 +89,7 @@ impl SingleReceiverTransactionProtocol {
             sender_info.metadata.clone()
         };
         let public_excess = key_manager
-            .get_txo_kernel_signature_excess_with_offset(&output.spending_key_id, &public_nonce.key_id)
+            .get_txo_kernel_signature_excess_with_offset(&output.spending_key_id, kernel_nonce_key_id)
             .await?;
 
         let kernel_message = TransactionKernel::build_kernel_signature_message(
@@ -78,9 +102,9 @@ impl SingleReceiverTransactionProtocol {
         let signature = key_manager
             .get_partial_txo_kernel_signature(
                 &output.spending_key_id,
-                &public_nonce.key_id,
-                &(&sender_info.public_nonce + &public_nonce.pub_key),
-                &(&sender_info.public_excess + &public_excess),
+                kernel_nonce_key_id,
+                total_public_nonce,
+                total_public_excess,
                 &sender_info.kernel_version,
                 &kernel_message,
                 &tx_meta.kernel_features,
@@ -88,7 +112,7 @@ impl SingleReceiverTransactionProtocol {
             )
             .await?;
         let offset = key_manager
-            .get_txo_private_kernel_offset(&output.spending_key_id, &public_nonce.key_id)
+            .get_txo_private_kernel_offset(&output.spending_key_id, kernel_nonce_key_id)
             .await?;
 
         let data = RecipientSignedMessage {
@@ -103,7 +127,7 @@ impl SingleReceiverTransactionProtocol {
     }
 
     /// Validates the sender info
-    fn validate_sender_data(
+    pub(super) fn validate_sender_data(
         sender_info: &SingleRoundSenderData,
         consensus_constants: &ConsensusConstants,
     ) -> Result<(), TPE> {
@@ -51,7 +51,13 @@ use crate::{
             MAX_TRANSACTION_OUTPUTS,
         },
         transaction_protocol::{
-            sender::{OutputPair, RawTransactionInfo, SenderState, SenderTransactionProtocol},
+            sender::{
+                MultiRecipientTransactionInfo,
+                OutputPair,
+                RawTransactionInfo,
+                SenderState,
+                SenderTransactionProtocol,
+            },
             KernelFeatures,
             TransactionMetadata,
         },
@@ -96,7 +102,8 @@ pub struct SenderTransactionInitializer<KM> {
     inputs: Vec<OutputPair>,
     sender_custom_outputs: Vec<OutputPair>,
     change: Option<ChangeDetails>,
-    recipient: Option<RecipientDetails>,
+    recipients: Vec<RecipientDetails>,
+    recipient_addresses: Vec<TariAddress>,
     recipient_text_message: Option<String>,
     prevent_fee_gt_amount: bool,
     tx_id: Option<TxId>,
@@ -131,7 +138,8 @@ where KM: TransactionKeyManagerInterface
             change: None,
             recipient_text_message: None,
             prevent_fee_gt_amount: true,
-            recipient: None,
+            recipients: Vec::new(),
+            recipient_addresses: Vec::new(),
             kernel_features: KernelFeatures::empty(),
             burn_commitment: None,
             tx_id: None,
@@ -153,8 +161,9 @@ where KM: TransactionKeyManagerInterface
         self
     }
 
-    /// Set the spending script of the ith recipient's output, a script offset will be generated for this recipient at
-    /// the same time. This method will silently fail if `receiver_index` >= num_receivers.
+    /// Add a recipient output with the given spending script, a script offset will be generated for this recipient at
+    /// the same time. Calling this more than once creates a multi-recipient transaction, which uses the three-round
+    /// protocol.
     pub async fn with_recipient_data(
         &mut self,
         recipient_script: TariScript,
@@ -180,10 +189,17 @@ where KM: TransactionKeyManagerInterface
             recipient_ephemeral_public_key_nonce: recipient_ephemeral_public_key_nonce.key_id,
             amount,
         };
-        self.recipient = Some(recipient_details);
+        self.recipients.push(recipient_details);
         Ok(self)
     }
 
+    /// Set the addresses of the recipients, in the same order that their recipient data was added. These are only
+    /// kept for multi-recipient transactions, so that the sender knows where to send the later protocol rounds.
+    pub fn with_recipient_addresses(&mut self, recipient_addresses: Vec<TariAddress>) -> &mut Self {
+        self.recipient_addresses = recipient_addresses;
+        self
+    }
+
     /// Sets the minimum block height that this transaction will be mined.
     pub fn with_lock_height(&mut self, lock_height: u64) -> &mut Self {
         self.lock_height = Some(lock_height);
@@ -284,7 +300,7 @@ where KM: TransactionKeyManagerInterface
                 )
             })
             .sum::<usize>();
-        if let Some(recipient_data) = &self.recipient {
+        for recipient_data in &self.recipients {
             size += self.fee.weighting().round_up_features_and_scripts_size(
                 self.get_recipient_output_features().get_serialized_size()? +
                     recipient_data.recipient_script.get_serialized_size()?,
@@ -306,7 +322,7 @@ where KM: TransactionKeyManagerInterface
         &mut self,
     ) -> Result<(MicroMinotari, MicroMinotari, Option<(WalletOutput, TariKeyId)>), String> {
         // The number of outputs excluding a possible residual change output
-        let num_outputs = self.sender_custom_outputs.len() + usize::from(self.recipient.is_some());
+        let num_outputs = self.sender_custom_outputs.len() + self.recipients.len();
         let num_inputs = self.inputs.len();
         let total_being_spent = self
             .inputs
@@ -322,10 +338,13 @@ where KM: TransactionKeyManagerInterface
             .try_fold(MicroMinotari::zero(), |acc, x| {
                 acc.checked_add(x).ok_or("Total outputs to self amount overflow")
             })?;
-        let total_amount = match &self.recipient {
-            Some(data) => data.amount,
-            None => 0.into(),
-        };
+        let total_amount = self
+            .recipients
+            .iter()
+            .map(|r| r.amount)
+            .try_fold(MicroMinotari::zero(), |acc, x| {
+                acc.checked_add(x).ok_or("Total amount to recipients overflow")
+            })?;
         let fee_per_gram = self.fee_per_gram.ok_or("Fee per gram was not provided")?;
 
         let features_and_scripts_size_without_change = self
@@ -511,6 +530,13 @@ where KM: TransactionKeyManagerInterface
         if self.inputs.len() > MAX_TRANSACTION_INPUTS {
             return self.build_err("Too many inputs in transaction");
         }
+        // The burn commitment is signed by the recipient, so there can only be one
+        if self.recipients.len() > 1 && self.kernel_features.is_burned() {
+            return self.build_err("A burn transaction cannot have multiple recipients");
+        }
+        if !self.recipient_addresses.is_empty() && self.recipient_addresses.len() != self.recipients.len() {
+            return self.build_err("The number of recipient addresses does not match the number of recipients");
+        }
         // Calculate the fee based on whether we need to add a residual change output or not
         let (total_fee, change, change_output) = match self.add_change_if_required().await {
             Ok((fee, change, output)) => (fee, change, output),
@@ -557,13 +583,14 @@ where KM: TransactionKeyManagerInterface
         // 99.999% of the time, however, always preventing this will also prevent spending dust in some edge
         // cases.
         // Don't care about the fees when we are sending token.
-        if let Some(data) = &self.recipient {
-            if total_fee > data.amount {
+        if !self.recipients.is_empty() {
+            let amount = self.recipients.iter().map(|r| r.amount).sum::<MicroMinotari>();
+            if total_fee > amount {
                 warn!(
                     target: LOG_TARGET,
                     "Fee ({}) is greater than amount ({}) being sent for Transaction (TxId: {}).",
                     total_fee,
-                    data.amount,
+                    amount,
                     tx_id
                 );
                 if self.prevent_fee_gt_amount {
@@ -572,12 +599,19 @@ where KM: TransactionKeyManagerInterface
             }
         }
 
+        // A single recipient is handled by the single-round protocol, several recipients by the multi-round protocol
+        let (recipient_data, multi_recipients) = if self.recipients.len() > 1 {
+            (None, self.recipients)
+        } else {
+            (self.recipients.pop(), Vec::new())
+        };
+
         // cached data
 
         // Everything is here. Let's send some Minotari!
         let sender_info = RawTransactionInfo {
             tx_id,
-            recipient_data: self.recipient,
+            recipient_data,
             recipient_output: None,
             recipient_partial_kernel_excess: PublicKey::default(),
             recipient_partial_kernel_signature: Signature::default(),
@@ -597,6 +631,10 @@ where KM: TransactionKeyManagerInterface
             sender_address: self.sender_address.clone(),
         };
 
+        if !multi_recipients.is_empty() {
+            let info = MultiRecipientTransactionInfo::new(sender_info, multi_recipients, self.recipient_addresses);
+            return Ok(SenderState::CollectingMultiNonces(Box::new(info)).into());
+        }
         let state = SenderState::Initializing(Box::new(sender_info));
         let state = state
             .initialize()
@@ -40,11 +40,16 @@ use tari_core::{
         state_machine_service::states::{ListeningInfo, StateInfo, StatusInfo},
         sync::rpc::BaseNodeSyncRpcService,
     },
-    blocks::ChainBlock,
+    blocks::{BlockFilter, ChainBlock},
     chain_storage::BlockchainDatabaseConfig,
     consensus::{ConsensusConstantsBuilder, ConsensusManager, ConsensusManagerBuilder, NetworkConsensus},
     proto::{
-        base_node::{FetchMatchingUtxos, Signatures as SignaturesProto, SyncUtxosByBlockRequest},
+        base_node::{
+            FetchMatchingUtxos,
+            GetBlockFiltersRequest,
+            Signatures as SignaturesProto,
+            SyncUtxosByBlockRequest,
+        },
         types::{Signature as SignatureProto, Transaction as TransactionProto},
     },
     test_helpers::blockchain::TempDatabase,
@@ -56,6 +61,7 @@ use tari_core::{
     },
     txn_schema,
 };
+use tari_script::Opcode;
 use tari_service_framework::reply_channel;
 use tari_test_utils::streams::convert_mpsc_to_stream;
 use tari_utilities::epoch_time::EpochTime;
@@ -461,3 +467,58 @@ async fn test_sync_utxos_by_block() {
             .collect::<Vec<(u64, Vec<u8>, usize)>>()
     );
 }
+
+#[tokio::test]
+async fn test_get_block_filters() {
+    let (service, _, mut base_node, request_mock, consensus_manager, block0, utxo0, _temp_dir, key_manager) =
+        setup().await;
+
+    let (txs1, _utxos1) = schema_to_transaction(
+        &[txn_schema!(from: vec![utxo0.clone()], to: vec![10 * T, 10 * T])],
+        &key_manager,
+    )
+    .await;
+    let tx1 = (*txs1[0]).clone();
+    let block1 = base_node
+        .blockchain_db
+        .prepare_new_block(chain_block(block0.block(), vec![tx1.clone()], &consensus_manager, &key_manager).await)
+        .unwrap();
+    base_node.local_nci.submit_block(block1.clone()).await.unwrap();
+
+    let msg = GetBlockFiltersRequest {
+        start_height: 0,
+        count: 10,
+    };
+    let req = request_mock.request_with_context(Default::default(), msg);
+    let filters = service.get_block_filters(req).await.unwrap().into_message().filters;
+    assert_eq!(
+        filters.iter().map(|f| f.height).collect::<Vec<_>>(),
+        vec![0, 1],
+        "Filters are returned up to the tip"
+    );
+
+    let block1_hash = block1.header.hash();
+    assert_eq!(filters[1].header_hash, block1_hash.to_vec());
+    let filter = BlockFilter::from_parts(filters[1].num_items, filters[1].data.clone());
+    for output in block1.body.outputs() {
+        assert!(filter.matches_any(&block1_hash, [BlockFilter::script_item(&output.script)]));
+    }
+    let genesis_filter = BlockFilter::from_parts(filters[0].num_items, filters[0].data.clone());
+    // The change output is locked to a fresh script key, so it cannot appear in the genesis block
+    let change_items = tx1
+        .body
+        .outputs()
+        .iter()
+        .filter(|o| matches!(o.script.as_slice(), [Opcode::PushPubKey(_)]))
+        .map(|o| BlockFilter::script_item(&o.script))
+        .collect::<Vec<_>>();
+    assert!(!change_items.is_empty());
+    assert!(!genesis_filter.matches_any(&block0.header().hash(), &change_items));
+
+    let msg = GetBlockFiltersRequest {
+        start_height: 0,
+        count: 0,
+    };
+    let req = request_mock.request_with_context(Default::default(), msg);
+    assert!(service.get_block_filters(req).await.is_err());
+}
@@ -79,6 +79,18 @@ impl SubConfigPath for PeerSeedsConfig {
     }
 }
 
+/// The backend used to store peers
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub enum PeerDatabaseBackend {
+    /// Peers are stored in an LMDB database in `datastore_path` and indexed in memory.
+    #[default]
+    Lmdb,
+    /// Peers are stored in `<peer_database_name>.sqlite` in `datastore_path`. If the SQLite database is empty, peers
+    /// are imported from an existing LMDB peer database on startup.
+    Sqlite,
+}
+
 /// Configuration for a comms node
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -95,6 +107,8 @@ pub struct P2pConfig {
     pub datastore_path: PathBuf,
     /// Name to use for the peer database
     pub peer_database_name: String,
+    /// The backend used to store peers
+    pub peer_database_backend: PeerDatabaseBackend,
     /// The maximum number of concurrent Inbound tasks allowed before back-pressure is applied to peers
     pub max_concurrent_inbound_tasks: usize,
     /// The maximum number of concurrent outbound tasks allowed before back-pressure is applied to outbound messaging
@@ -135,6 +149,7 @@ impl Default for P2pConfig {
             transport: Default::default(),
             datastore_path: PathBuf::from("peer_db"),
             peer_database_name: "peers".to_string(),
+            peer_database_backend: PeerDatabaseBackend::default(),
             max_concurrent_inbound_tasks: 4,
             max_concurrent_outbound_tasks: 4,
             dht: DhtConfig {
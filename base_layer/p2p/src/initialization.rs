@@ -42,7 +42,7 @@ use tari_common::{
 use tari_comms::{
     backoff::ConstantBackoff,
     multiaddr::multiaddr,
-    peer_manager::{NodeIdentity, Peer, PeerFeatures, PeerFlags, PeerManagerError},
+    peer_manager::{NodeIdentity, Peer, PeerDatabase, PeerFeatures, PeerFlags, PeerManagerError, SqlitePeerDatabase},
     pipeline,
     protocol::{
         messaging::{MessagingEventSender, MessagingProtocolExtension},
@@ -60,6 +60,7 @@ use tari_comms::{
         SocksTransport,
         TcpWithTorTransport,
     },
+    types::CommsDatabase,
     utils::cidr::parse_cidrs,
     CommsBuilder,
     CommsBuilderError,
@@ -80,7 +81,7 @@ use tower::ServiceBuilder;
 
 use crate::{
     comms_connector::{InboundDomainConnector, PubsubDomainConnector},
-    config::{P2pConfig, PeerSeedsConfig},
+    config::{P2pConfig, PeerDatabaseBackend, PeerSeedsConfig},
     peer_seeds::{DnsSeedResolver, SeedPeer},
     transport::{TorTransportConfig, TransportType},
     TransportConfig,
@@ -88,6 +89,8 @@ use crate::{
     MINOR_NETWORK_VERSION,
 };
 const LOG_TARGET: &str = "p2p::initialization";
+/// The data file of an LMDB environment
+const LMDB_DATA_FILE: &str = "data.mdb";
 
 /// ProtocolId for minotari messaging protocol
 pub static MESSAGING_PROTOCOL_ID: ProtocolId = ProtocolId::from_static(b"t/msg/0.1");
@@ -106,6 +109,8 @@ pub enum CommsInitializationError {
     InvalidLivenessCidrs(String),
     #[error("Could not add seed peers to comms layer: `{0}`")]
     FailedToAddSeedPeer(#[from] PeerManagerError),
+    #[error("Could not open the peer database: `{0}`")]
+    PeerDatabaseError(PeerManagerError),
     #[error("Cannot acquire exclusive file lock, another instance of the application is already running")]
     CannotAcquireFileLock,
     #[error("Invalid tor forward address: `{0}`")]
@@ -313,25 +318,19 @@ async fn configure_comms_and_dht(
 ) -> Result<(UnspawnedCommsNode, Dht), CommsInitializationError> {
     let file_lock = acquire_exclusive_file_lock(&config.datastore_path)?;
 
-    let datastore = LMDBBuilder::new()
-        .set_path(&config.datastore_path)
-        .set_env_flags(open::NOLOCK)
-        .set_env_config(LMDBConfig::default())
-        .set_max_number_of_databases(1)
-        .add_database(&config.peer_database_name, lmdb_zero::db::CREATE)
-        .build()
-        .unwrap();
-    let peer_database = datastore.get_handle(&config.peer_database_name).unwrap();
-    let peer_database = LMDBWrapper::new(Arc::new(peer_database));
-
     let listener_liveness_allowlist_cidrs = parse_cidrs(&config.listener_liveness_allowlist_cidrs)
         .map_err(CommsInitializationError::InvalidLivenessCidrs)?;
 
     let builder = builder
         .with_listener_liveness_max_sessions(config.listener_liveness_max_sessions)
         .with_listener_liveness_allowlist_cidrs(listener_liveness_allowlist_cidrs)
-        .with_dial_backoff(ConstantBackoff::new(Duration::from_millis(500)))
-        .with_peer_storage(peer_database, Some(file_lock));
+        .with_dial_backoff(ConstantBackoff::new(Duration::from_millis(500)));
+    let builder = match config.peer_database_backend {
+        PeerDatabaseBackend::Lmdb => builder.with_peer_storage(open_lmdb_peer_database(config), Some(file_lock)),
+        PeerDatabaseBackend::Sqlite => {
+            builder.with_peer_database(Box::new(open_sqlite_peer_database(config)?), Some(file_lock))
+        },
+    };
 
     let mut comms = match config.auxiliary_tcp_listener_address {
         Some(ref addr) => builder.with_auxiliary_tcp_listener_address(addr.clone()).build()?,
@@ -385,6 +384,40 @@ async fn configure_comms_and_dht(
     Ok((comms, dht))
 }
 
+fn open_lmdb_peer_database(config: &P2pConfig) -> CommsDatabase {
+    let datastore = LMDBBuilder::new()
+        .set_path(&config.datastore_path)
+        .set_env_flags(open::NOLOCK)
+        .set_env_config(LMDBConfig::default())
+        .set_max_number_of_databases(1)
+        .add_database(&config.peer_database_name, lmdb_zero::db::CREATE)
+        .build()
+        .unwrap();
+    let peer_database = datastore.get_handle(&config.peer_database_name).unwrap();
+    LMDBWrapper::new(Arc::new(peer_database))
+}
+
+/// Opens the SQLite peer database. If the database is empty and an LMDB peer database exists in the datastore path,
+/// its peers are imported so that switching backends does not lose known peers.
+fn open_sqlite_peer_database(config: &P2pConfig) -> Result<SqlitePeerDatabase, CommsInitializationError> {
+    let database_path = config
+        .datastore_path
+        .join(format!("{}.sqlite", config.peer_database_name));
+    let mut peer_database = SqlitePeerDatabase::connect_and_migrate(database_path.to_string_lossy())
+        .map_err(CommsInitializationError::PeerDatabaseError)?;
+    if peer_database.count() == 0 && config.datastore_path.join(LMDB_DATA_FILE).exists() {
+        info!(
+            target: LOG_TARGET,
+            "Importing peers from LMDB peer database at '{}'",
+            config.datastore_path.display()
+        );
+        peer_database
+            .import_lmdb_peers(open_lmdb_peer_database(config))
+            .map_err(CommsInitializationError::PeerDatabaseError)?;
+    }
+    Ok(peer_database)
+}
+
 /// Acquire an exclusive OS level write lock on a file in the provided path. This is used to check if another instance
 /// of this database has already been initialized in order to prevent two process from using it simultaneously
 /// ## Parameters
@@ -47,7 +47,7 @@ pub use tari_common::configuration::Network;
 pub use tor_authentication::TorControlAuthentication;
 pub use transport::{Socks5TransportConfig, TcpTransportConfig, TorTransportConfig, TransportConfig, TransportType};
 
-pub use self::config::{P2pConfig, PeerSeedsConfig};
+pub use self::config::{P2pConfig, PeerDatabaseBackend, PeerSeedsConfig};
 
 /// Default DNS resolver set to cloudflare's private 1.1.1.1 resolver
 pub const DEFAULT_DNS_NAME_SERVER: &str = "1.1.1.1:853/cloudflare-dns.com";
@@ -0,0 +1,9 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE outputs
+    DROP COLUMN frozen;
+
+ALTER TABLE outputs
+    DROP COLUMN note;
+
+ALTER TABLE outputs
+    DROP COLUMN label;
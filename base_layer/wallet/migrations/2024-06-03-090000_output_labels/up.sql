@@ -0,0 +1,8 @@
+ALTER TABLE outputs
+    ADD label TEXT NULL;
+
+ALTER TABLE outputs
+    ADD note TEXT NULL;
+
+ALTER TABLE outputs
+    ADD frozen BOOLEAN NOT NULL DEFAULT 0;
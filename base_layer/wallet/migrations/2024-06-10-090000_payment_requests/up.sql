@@ -0,0 +1,9 @@
+CREATE TABLE payment_requests (
+    payment_id BLOB PRIMARY KEY NOT NULL,
+    amount     BIGINT           NOT NULL,
+    expiry     BIGINT           NULL,
+    request    TEXT             NOT NULL,
+    created_at TIMESTAMP        NOT NULL,
+    paid_tx_id BIGINT           NULL,
+    paid_at    TIMESTAMP        NULL
+);
@@ -0,0 +1,2 @@
+DROP TABLE multisig_account_outputs;
+DROP TABLE multisig_accounts;
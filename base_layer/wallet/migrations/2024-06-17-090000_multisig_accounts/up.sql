@@ -0,0 +1,16 @@
+CREATE TABLE multisig_accounts (
+    name        TEXT PRIMARY KEY NOT NULL,
+    threshold   INTEGER          NOT NULL,
+    public_keys BLOB             NOT NULL,
+    key_index   BIGINT           NOT NULL,
+    created_at  TIMESTAMP        NOT NULL
+);
+
+CREATE TABLE multisig_account_outputs (
+    output_hash    BLOB PRIMARY KEY NOT NULL,
+    account_name   TEXT             NOT NULL,
+    commitment     BLOB             NOT NULL,
+    value          BIGINT           NOT NULL,
+    mined_height   BIGINT           NOT NULL,
+    spent_in_tx_id BIGINT           NULL
+);
@@ -0,0 +1 @@
+DROP TABLE sub_addresses;
@@ -0,0 +1,6 @@
+CREATE TABLE sub_addresses (
+    key_index  BIGINT PRIMARY KEY NOT NULL,
+    label      TEXT               NOT NULL,
+    address    BLOB               NOT NULL,
+    created_at TIMESTAMP          NOT NULL
+);
@@ -0,0 +1,2 @@
+DROP TABLE large_send_approvals;
+DROP TABLE spending_policy;
@@ -0,0 +1,11 @@
+CREATE TABLE spending_policy (
+    id         INTEGER PRIMARY KEY NOT NULL,
+    policy     TEXT                NOT NULL,
+    updated_at TIMESTAMP           NOT NULL
+);
+
+CREATE TABLE large_send_approvals (
+    approval_key TEXT PRIMARY KEY NOT NULL,
+    available_at TIMESTAMP        NOT NULL,
+    expires_at   TIMESTAMP        NOT NULL
+);
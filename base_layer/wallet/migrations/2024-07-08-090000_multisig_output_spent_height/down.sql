@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE multisig_account_outputs
+    DROP COLUMN spent_at_height;
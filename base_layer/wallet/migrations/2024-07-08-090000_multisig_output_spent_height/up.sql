@@ -0,0 +1,2 @@
+ALTER TABLE multisig_account_outputs
+    ADD spent_at_height BIGINT NULL;
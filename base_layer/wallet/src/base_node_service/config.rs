@@ -20,7 +20,7 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::time::Duration;
+use std::{path::PathBuf, time::Duration};
 
 use serde::{Deserialize, Serialize};
 use tari_common::configuration::serializers;
@@ -35,6 +35,9 @@ pub struct BaseNodeServiceConfig {
     pub base_node_rpc_pool_size: usize,
     /// This is the size of the event channel used to communicate base node events to the wallet
     pub event_channel_size: usize,
+    /// If set, every base node wallet RPC call made over the connection to the selected base node is appended to this
+    /// file so that the session can be replayed offline
+    pub rpc_recording_file: Option<PathBuf>,
 }
 
 impl Default for BaseNodeServiceConfig {
@@ -43,6 +46,7 @@ impl Default for BaseNodeServiceConfig {
             base_node_monitor_max_refresh_interval: Duration::from_secs(30),
             base_node_rpc_pool_size: 10,
             event_channel_size: 250,
+            rpc_recording_file: None,
         }
     }
 }
@@ -93,6 +93,15 @@ pub struct WalletConfig {
     /// transaction events are received by the console wallet .
     /// (see example at 'applications/minotari_console_wallet/src/notifier/notify_example.sh')
     pub notify_file: Option<PathBuf>,
+    /// URLs that the console wallet POSTs transaction and balance events to as signed JSON payloads
+    pub webhook_urls: StringList,
+    /// The secret used to sign webhook payloads with HMAC-SHA256, payloads are not signed if it is not set
+    #[serde(deserialize_with = "deserialize_safe_password_option")]
+    pub webhook_secret: Option<SafePassword>,
+    /// The number of delivery attempts for a webhook event before it is dropped from the outbox
+    pub webhook_max_attempts: u32,
+    /// The file that undelivered webhook events are persisted to
+    pub webhook_outbox_file: PathBuf,
     /// If true, a GRPC server will bind to the configured address and listen for incoming GRPC requests.
     pub grpc_enabled: bool,
     /// GRPC bind address of the wallet
@@ -107,6 +116,9 @@ pub struct WalletConfig {
     pub base_node_service_peers: StringList,
     /// The amount of times wallet recovery will be retried before being abandoned
     pub recovery_retry_limit: usize,
+    /// If true, the background scanner only downloads blocks whose compact block filter matches this wallet. Stealth
+    /// one-sided payments cannot be matched by a filter and are missed in the skipped blocks.
+    pub use_block_filters: bool,
     /// The default uT fee per gram to use for transaction fees
     pub fee_per_gram: u64,
     /// Number of required transaction confirmations used for UI purposes
@@ -148,6 +160,10 @@ impl Default for WalletConfig {
             command_send_wait_stage: TransactionStage::Broadcast,
             command_send_wait_timeout: Duration::from_secs(300),
             notify_file: None,
+            webhook_urls: StringList::default(),
+            webhook_secret: None,
+            webhook_max_attempts: 10,
+            webhook_outbox_file: PathBuf::from_str("webhook_outbox.json").unwrap(),
             grpc_enabled: false,
             grpc_address: None,
             grpc_authentication: GrpcAuthentication::default(),
@@ -155,6 +171,7 @@ impl Default for WalletConfig {
             custom_base_node: None,
             base_node_service_peers: StringList::default(),
             recovery_retry_limit: 3,
+            use_block_filters: false,
             fee_per_gram: 5,
             num_required_confirmations: 3,
             use_libtor: true,
@@ -181,6 +198,9 @@ impl WalletConfig {
         if !self.db_file.is_absolute() {
             self.db_file = self.data_dir.join(self.db_file.as_path());
         }
+        if !self.webhook_outbox_file.is_absolute() {
+            self.webhook_outbox_file = self.data_dir.join(self.webhook_outbox_file.as_path());
+        }
         self.p2p.set_base_path(base_path);
     }
 }
@@ -40,3 +40,6 @@ pub use mock::{create as create_wallet_connectivity_mock, WalletConnectivityMock
 
 mod interface;
 pub use interface::WalletConnectivityInterface;
+
+mod rpc_recorder;
+pub use rpc_recorder::{RecordedExchange, RecordedStatus, RpcFixture, RpcRecorder};
@@ -0,0 +1,162 @@
+//  Copyright 2024. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+
+use std::{
+    fs::{File, OpenOptions},
+    io,
+    io::{BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use log::*;
+use serde::{Deserialize, Serialize};
+use tari_comms::protocol::rpc::{ObservedRpcCall, RpcCallObserver, RpcStatus, RpcStatusCode};
+use tari_utilities::hex::to_hex;
+
+const LOG_TARGET: &str = "wallet::connectivity::rpc_recorder";
+
+/// An RPC status returned by the base node, stored so that it can be reconstructed on replay
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedStatus {
+    pub code: u32,
+    pub details: String,
+}
+
+impl From<&RpcStatus> for RecordedStatus {
+    fn from(status: &RpcStatus) -> Self {
+        Self {
+            code: status.as_code(),
+            details: status.details().to_string(),
+        }
+    }
+}
+
+impl From<&RecordedStatus> for RpcStatus {
+    fn from(status: &RecordedStatus) -> Self {
+        let details = status.details.as_str();
+        match RpcStatusCode::from(status.code) {
+            RpcStatusCode::BadRequest => RpcStatus::bad_request(details),
+            RpcStatusCode::UnsupportedMethod => RpcStatus::unsupported_method(details),
+            RpcStatusCode::NotImplemented => RpcStatus::not_implemented(details),
+            RpcStatusCode::Timeout => RpcStatus::timed_out(details),
+            RpcStatusCode::NotFound => RpcStatus::not_found(details),
+            RpcStatusCode::Forbidden => RpcStatus::forbidden(details),
+            RpcStatusCode::Conflict => RpcStatus::conflict(details),
+            _ => RpcStatus::general(details),
+        }
+    }
+}
+
+/// A single base node wallet RPC call and everything the base node sent back for it. The method is the RPC method
+/// number and messages are stored as hex encoded protobuf bytes, so that recordings do not depend on the Rust
+/// representation of the messages. Streaming methods record every streamed item in order and, if the stream ended with
+/// an error, the status it ended with.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedExchange {
+    pub method: u32,
+    pub request: String,
+    pub responses: Vec<String>,
+    pub status: Option<RecordedStatus>,
+}
+
+impl From<ObservedRpcCall> for RecordedExchange {
+    fn from(call: ObservedRpcCall) -> Self {
+        Self {
+            method: call.method,
+            request: to_hex(&call.request),
+            responses: call.responses.iter().map(|r| to_hex(r)).collect(),
+            status: call.status.as_ref().map(RecordedStatus::from),
+        }
+    }
+}
+
+/// An ordered list of recorded exchanges. Recordings are stored with one JSON encoded exchange per line.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RpcFixture {
+    pub exchanges: Vec<RecordedExchange>,
+}
+
+impl RpcFixture {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, io::Error> {
+        let reader = BufReader::new(File::open(path)?);
+        let mut exchanges = Vec::new();
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            exchanges.push(serde_json::from_str(&line)?);
+        }
+        Ok(Self { exchanges })
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), io::Error> {
+        let mut file = File::create(path)?;
+        for exchange in &self.exchanges {
+            serde_json::to_writer(&mut file, exchange)?;
+            writeln!(file)?;
+        }
+        Ok(())
+    }
+}
+
+/// Appends every base node wallet RPC call made by the wallet to a recording file, which can be replayed in a test to
+/// reproduce a recovery or validation session offline. Enabled by setting `rpc_recording_file` in the base node
+/// service config.
+#[derive(Debug)]
+pub struct RpcRecorder {
+    path: PathBuf,
+    file: Mutex<File>,
+}
+
+impl RpcRecorder {
+    pub fn open<P: Into<PathBuf>>(path: P) -> Result<Self, io::Error> {
+        let path = path.into();
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        Ok(Self {
+            path,
+            file: Mutex::new(file),
+        })
+    }
+
+    fn append(&self, exchange: &RecordedExchange) -> Result<(), io::Error> {
+        let mut line = serde_json::to_vec(exchange)?;
+        line.push(b'\n');
+        let mut file = self
+            .file
+            .lock()
+            .map_err(|_| io::Error::new(io::ErrorKind::Other, "RPC recording file lock poisoned"))?;
+        file.write_all(&line)
+    }
+}
+
+impl RpcCallObserver for RpcRecorder {
+    fn on_call_completed(&self, call: ObservedRpcCall) {
+        if let Err(err) = self.append(&call.into()) {
+            warn!(
+                target: LOG_TARGET,
+                "Could not record RPC call to '{}': {}",
+                self.path.display(),
+                err
+            );
+        }
+    }
+}
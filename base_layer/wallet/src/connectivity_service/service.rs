@@ -20,13 +20,13 @@
 //  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::{mem, time::Duration};
+use std::{mem, sync::Arc, time::Duration};
 
 use log::*;
 use tari_comms::{
     connectivity::{ConnectivityError, ConnectivityRequester},
     peer_manager::{NodeId, Peer},
-    protocol::rpc::{RpcClientLease, RpcClientPool},
+    protocol::rpc::{RpcClientBuilder, RpcClientLease, RpcClientPool},
     Minimized,
     PeerConnection,
 };
@@ -39,7 +39,7 @@ use tokio::{
 
 use crate::{
     base_node_service::config::BaseNodeServiceConfig,
-    connectivity_service::{error::WalletConnectivityError, handle::WalletConnectivityRequest},
+    connectivity_service::{error::WalletConnectivityError, handle::WalletConnectivityRequest, RpcRecorder},
     util::watch::Watch,
 };
 
@@ -62,6 +62,7 @@ pub struct WalletConnectivityService {
     pools: Option<ClientPoolContainer>,
     online_status_watch: Watch<OnlineStatus>,
     pending_requests: Vec<ReplyOneshot>,
+    rpc_recorder: Option<Arc<RpcRecorder>>,
 }
 
 struct ClientPoolContainer {
@@ -77,6 +78,28 @@ impl WalletConnectivityService {
         online_status_watch: Watch<OnlineStatus>,
         connectivity: ConnectivityRequester,
     ) -> Self {
+        let rpc_recorder = config
+            .rpc_recording_file
+            .as_ref()
+            .and_then(|path| match RpcRecorder::open(path) {
+                Ok(recorder) => {
+                    info!(
+                        target: LOG_TARGET,
+                        "Recording base node wallet RPC calls to '{}'",
+                        path.display()
+                    );
+                    Some(Arc::new(recorder))
+                },
+                Err(err) => {
+                    warn!(
+                        target: LOG_TARGET,
+                        "Could not open RPC recording file '{}', calls will not be recorded: {}",
+                        path.display(),
+                        err
+                    );
+                    None
+                },
+            });
         Self {
             config,
             request_receiver,
@@ -85,6 +108,7 @@ impl WalletConnectivityService {
             pools: None,
             pending_requests: Vec::new(),
             online_status_watch,
+            rpc_recorder,
         }
     }
 
@@ -305,10 +329,14 @@ impl WalletConnectivityService {
             "Successfully established peer connection to base node {}",
             conn.peer_node_id()
         );
+        let mut wallet_rpc_builder = RpcClientBuilder::default();
+        if let Some(recorder) = self.rpc_recorder.clone() {
+            wallet_rpc_builder = wallet_rpc_builder.with_call_observer(recorder);
+        }
         self.pools = Some(ClientPoolContainer {
             base_node_sync_rpc_client: conn.create_rpc_client_pool(1, Default::default()),
             base_node_wallet_rpc_client: conn
-                .create_rpc_client_pool(self.config.base_node_rpc_pool_size, Default::default()),
+                .create_rpc_client_pool(self.config.base_node_rpc_pool_size, wallet_rpc_builder),
         });
         self.notify_pending_requests().await?;
         debug!(target: LOG_TARGET, "Successfully established RPC connection {}", peer);
@@ -25,6 +25,7 @@ use log::SetLoggerError;
 use serde_json::Error as SerdeJsonError;
 use tari_common::exit_codes::{ExitCode, ExitError};
 use tari_common_sqlite::error::SqliteStorageError;
+use tari_common_types::payment_request::PaymentRequestError;
 use tari_comms::{
     connectivity::ConnectivityError,
     multiaddr,
@@ -103,6 +104,10 @@ pub enum WalletError {
     UnexpectedApiResponse { method: String, api: String },
     #[error("Public address not set for this wallet")]
     PublicAddressNotSet,
+    #[error("Payment request error: `{0}`")]
+    PaymentRequestError(#[from] PaymentRequestError),
+    #[error("This is a watch-only wallet and cannot sign transactions")]
+    WatchOnlyWallet,
 }
 
 pub const LOG_TARGET: &str = "minotari::application";
@@ -183,6 +188,8 @@ pub enum WalletStorageError {
     RecoverySeedError(String),
     #[error("Bad encryption version: `{0}`")]
     BadEncryptionVersion(String),
+    #[error("Invalid wallet backup: {0}")]
+    InvalidBackup(String),
 }
 
 impl From<HexError> for WalletStorageError {
@@ -20,7 +20,15 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use std::time::Duration;
+
+use log::*;
 use serde::{Deserialize, Serialize};
+use tari_common::configuration::serializers;
+
+use crate::output_manager_service::error::OutputManagerError;
+
+const LOG_TARGET: &str = "wallet::output_manager_service::config";
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -45,6 +53,18 @@ pub struct OutputManagerServiceConfig {
     pub autoignore_onesided_utxos: bool,
     /// The number of seconds that have to pass for the wallet to run revalidation of invalid UTXOs on startup.
     pub num_of_seconds_to_revalidate_invalid_utxos: u64,
+    /// If set to `true`, the wallet periodically consolidates small outputs into a single output
+    pub auto_consolidation_enabled: bool,
+    /// The interval at which the wallet checks whether outputs should be consolidated
+    #[serde(with = "serializers::seconds")]
+    pub auto_consolidation_interval: Duration,
+    /// Outputs are only consolidated once the wallet holds more than this many unspent outputs
+    pub auto_consolidation_max_utxo_count: usize,
+    /// Unspent outputs below this value, in micro MinoTari, are consolidated
+    pub auto_consolidation_min_output_value: u64,
+    /// Consolidation is skipped while the minimum fee per gram needed to get into the next block is above this value,
+    /// in micro MinoTari
+    pub auto_consolidation_max_fee_per_gram: u64,
 }
 
 impl Default for OutputManagerServiceConfig {
@@ -57,6 +77,25 @@ impl Default for OutputManagerServiceConfig {
             tx_validator_batch_size: 100,
             autoignore_onesided_utxos: false,
             num_of_seconds_to_revalidate_invalid_utxos: 60 * 60 * 24 * 3,
+            auto_consolidation_enabled: false,
+            auto_consolidation_interval: Duration::from_secs(60 * 60 * 6),
+            auto_consolidation_max_utxo_count: 500,
+            auto_consolidation_min_output_value: 100_000,
+            auto_consolidation_max_fee_per_gram: 5,
+        }
+    }
+}
+
+impl OutputManagerServiceConfig {
+    /// Checks that the config values can be used by the Output Manager Service
+    pub fn validate(&self) -> Result<(), OutputManagerError> {
+        if self.auto_consolidation_enabled && self.auto_consolidation_interval.is_zero() {
+            error!(
+                target: LOG_TARGET,
+                "`auto_consolidation_interval` must be greater than zero when auto consolidation is enabled"
+            );
+            return Err(OutputManagerError::InvalidConfig);
         }
+        Ok(())
     }
 }
@@ -152,6 +152,12 @@ pub enum OutputManagerError {
     TooManyInputsToFulfillTransaction(String),
     #[error("Std I/O error: {0}")]
     StdIoError(#[from] std::io::Error),
+    #[error("Invalid multisig account: {0}")]
+    InvalidMultisigAccount(String),
+    #[error("Multisig account not found: {0}")]
+    MultisigAccountNotFound(String),
+    #[error("Sub-address not found: {0}")]
+    SubAddressNotFound(u64),
 }
 
 impl From<RangeProofError> for OutputManagerError {
@@ -210,6 +216,8 @@ pub enum OutputManagerStorageError {
     AeadError(String),
     #[error("Tried to insert a script that already exists in the database")]
     DuplicateScript,
+    #[error("Tried to insert a multisig account with a name that already exists in the database")]
+    DuplicateMultisigAccount,
     #[error("Tari script error: {0}")]
     ScriptError(#[from] ScriptError),
     #[error("Binary not stored as valid hex:{0}")]
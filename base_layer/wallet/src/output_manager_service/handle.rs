@@ -30,6 +30,7 @@ use tari_common_types::{
 use tari_core::{
     covenants::Covenant,
     transactions::{
+        key_manager::TariKeyId,
         tari_amount::MicroMinotari,
         transaction_components::{OutputFeatures, Transaction, TransactionOutput, WalletOutput, WalletOutputBuilder},
         transaction_protocol::{sender::TransactionSenderMessage, TransactionMetadata},
@@ -46,8 +47,11 @@ use tower::Service;
 
 use crate::output_manager_service::{
     error::OutputManagerError,
+    multisig_account::{MultisigAccount, MultisigAccountOutput},
     service::{Balance, OutputInfoByTxId},
     storage::models::{DbWalletOutput, KnownOneSidedPaymentScript, SpendingPriority},
+    sub_address::SubAddress,
+    unsigned_transaction::UnsignedTransaction,
     UtxoSelectionCriteria,
 };
 
@@ -92,6 +96,20 @@ pub enum OutputManagerRequest {
         covenant: Covenant,
         minimum_value_promise: MicroMinotari,
     },
+    PrepareBatchToSend {
+        tx_id: TxId,
+        outputs: Vec<(WalletOutput, TariKeyId)>,
+        selection_criteria: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+        message: String,
+    },
+    PrepareMultiRecipientToSend {
+        tx_id: TxId,
+        recipients: Vec<(TariAddress, MicroMinotari)>,
+        selection_criteria: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+        message: String,
+    },
     CreatePayToSelfTransaction {
         tx_id: TxId,
         amount: MicroMinotari,
@@ -126,6 +144,13 @@ pub enum OutputManagerRequest {
         num_kernels: usize,
         num_outputs: usize,
     },
+    PrepareUnsignedTransaction {
+        recipient_address: TariAddress,
+        amount: MicroMinotari,
+        selection_criteria: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+        message: String,
+    },
 
     ScanForRecoverableOutputs(Vec<TransactionOutput>),
     ScanOutputs(Vec<TransactionOutput>),
@@ -139,6 +164,31 @@ pub enum OutputManagerRequest {
     CreateClaimShaAtomicSwapTransaction(HashOutput, PublicKey, MicroMinotari),
     CreateHtlcRefundTransaction(HashOutput, MicroMinotari),
     GetOutputInfoByTxId(TxId),
+    SetOutputLabel {
+        commitment: Commitment,
+        label: Option<String>,
+        note: Option<String>,
+    },
+    SetOutputFrozen {
+        commitment: Commitment,
+        frozen: bool,
+    },
+    CreateMultisigAccount {
+        name: String,
+        threshold: u8,
+        public_keys: Vec<PublicKey>,
+        own_public_key: PublicKey,
+    },
+    GetMultisigAccounts,
+    GetMultisigAccountOutputs(String),
+    ScanOutputsForMultisigAccounts {
+        outputs: Vec<TransactionOutput>,
+        mined_height: u64,
+    },
+    CreateSubAddress(String),
+    GetSubAddresses,
+    GetSubAddressOutputs(u64),
+    GetBlockFilterItems,
 }
 
 impl fmt::Display for OutputManagerRequest {
@@ -189,6 +239,17 @@ impl fmt::Display for OutputManagerRequest {
             GetRecipientTransaction(_) => write!(f, "GetRecipientTransaction"),
             ConfirmPendingTransaction(v) => write!(f, "ConfirmPendingTransaction ({})", v),
             PrepareToSendTransaction { message, .. } => write!(f, "PrepareToSendTransaction ({})", message),
+            PrepareBatchToSend { tx_id, outputs, .. } => {
+                write!(f, "PrepareBatchToSend ({}: {} outputs)", tx_id, outputs.len())
+            },
+            PrepareMultiRecipientToSend { tx_id, recipients, .. } => {
+                write!(
+                    f,
+                    "PrepareMultiRecipientToSend ({}: {} recipients)",
+                    tx_id,
+                    recipients.len()
+                )
+            },
             CreatePayToSelfTransaction { .. } => write!(f, "CreatePayToSelfTransaction",),
             CancelTransaction(v) => write!(f, "CancelTransaction ({})", v),
             GetSpentOutputs => write!(f, "GetSpentOutputs"),
@@ -227,6 +288,16 @@ impl fmt::Display for OutputManagerRequest {
                 "FeeEstimate(amount: {}, fee_per_gram: {}, num_kernels: {}, num_outputs: {}, selection_criteria: {:?})",
                 amount, fee_per_gram, num_kernels, num_outputs, selection_criteria
             ),
+            PrepareUnsignedTransaction {
+                recipient_address,
+                amount,
+                fee_per_gram,
+                ..
+            } => write!(
+                f,
+                "PrepareUnsignedTransaction(recipient: {}, amount: {}, fee_per_gram: {})",
+                recipient_address, amount, fee_per_gram
+            ),
             ScanForRecoverableOutputs(_) => write!(f, "ScanForRecoverableOutputs"),
             ScanOutputs(_) => write!(f, "ScanOutputs"),
             AddKnownOneSidedPaymentScript(_) => write!(f, "AddKnownOneSidedPaymentScript"),
@@ -247,6 +318,39 @@ impl fmt::Display for OutputManagerRequest {
             ),
 
             GetOutputInfoByTxId(t) => write!(f, "GetOutputInfoByTxId: {}", t),
+            SetOutputLabel { commitment, label, .. } => write!(
+                f,
+                "SetOutputLabel(commitment: {}, label: {})",
+                commitment.to_hex(),
+                label.as_deref().unwrap_or("<none>")
+            ),
+            SetOutputFrozen { commitment, frozen } => write!(
+                f,
+                "SetOutputFrozen(commitment: {}, frozen: {})",
+                commitment.to_hex(),
+                frozen
+            ),
+            CreateMultisigAccount {
+                name,
+                threshold,
+                public_keys,
+                ..
+            } => write!(
+                f,
+                "CreateMultisigAccount(name: {}, threshold: {}-of-{})",
+                name,
+                threshold,
+                public_keys.len()
+            ),
+            GetMultisigAccounts => write!(f, "GetMultisigAccounts"),
+            GetMultisigAccountOutputs(name) => write!(f, "GetMultisigAccountOutputs({})", name),
+            ScanOutputsForMultisigAccounts { mined_height, .. } => {
+                write!(f, "ScanOutputsForMultisigAccounts(height: {})", mined_height)
+            },
+            CreateSubAddress(label) => write!(f, "CreateSubAddress({})", label),
+            GetSubAddresses => write!(f, "GetSubAddresses"),
+            GetSubAddressOutputs(index) => write!(f, "GetSubAddressOutputs({})", index),
+            GetBlockFilterItems => write!(f, "GetBlockFilterItems"),
         }
     }
 }
@@ -285,6 +389,7 @@ pub enum OutputManagerResponse {
     PublicRewindKeys(Box<PublicRewindKeys>),
     RecoveryByte(u8),
     FeeEstimate(MicroMinotari),
+    UnsignedTransaction(Box<UnsignedTransaction>),
     RewoundOutputs(Vec<RecoveredOutput>),
     ScanOutputs(Vec<RecoveredOutput>),
     AddKnownOneSidedPaymentScript,
@@ -299,6 +404,15 @@ pub enum OutputManagerResponse {
     ClaimHtlcTransaction((TxId, MicroMinotari, MicroMinotari, Transaction)),
     OutputInfoByTxId(OutputInfoByTxId),
     CoinPreview((Vec<MicroMinotari>, MicroMinotari)),
+    OutputLabelSet,
+    OutputFrozenSet,
+    MultisigAccount(Box<MultisigAccount>),
+    MultisigAccounts(Vec<MultisigAccount>),
+    MultisigAccountOutputs(Vec<MultisigAccountOutput>),
+    SubAddress(Box<SubAddress>),
+    SubAddresses(Vec<SubAddress>),
+    SubAddressOutputs(Vec<DbWalletOutput>),
+    BlockFilterItems(Vec<Vec<u8>>),
 }
 
 pub type OutputManagerEventSender = broadcast::Sender<Arc<OutputManagerEvent>>;
@@ -311,6 +425,13 @@ pub enum OutputManagerEvent {
     TxoValidationInternalFailure(u64),
     TxoValidationCommunicationFailure(u64),
     TxoValidationAlreadyBusy(u64),
+    /// An automatic consolidation transaction was created and submitted to the transaction service
+    UtxoConsolidationCreated {
+        tx_id: TxId,
+        num_inputs: usize,
+        value: MicroMinotari,
+    },
+    UtxoConsolidationFailed(String),
 }
 
 impl fmt::Display for OutputManagerEvent {
@@ -328,6 +449,21 @@ impl fmt::Display for OutputManagerEvent {
             OutputManagerEvent::TxoValidationAlreadyBusy(tx) => {
                 write!(f, "Txo is already running, stopping {}", tx)
             },
+            OutputManagerEvent::UtxoConsolidationCreated {
+                tx_id,
+                num_inputs,
+                value,
+                ..
+            } => {
+                write!(
+                    f,
+                    "UtxoConsolidationCreated for {} ({} inputs, {})",
+                    tx_id, num_inputs, value
+                )
+            },
+            OutputManagerEvent::UtxoConsolidationFailed(reason) => {
+                write!(f, "UtxoConsolidationFailed: {}", reason)
+            },
         }
     }
 }
@@ -514,6 +650,59 @@ impl OutputManagerHandle {
         }
     }
 
+    /// Prepare a transaction that pays to each of the given pre-built outputs. Each output is paired with the key id
+    /// of the sender offset private key that was used to sign it. The returned protocol has no recipients and is ready
+    /// to be finalized.
+    pub async fn prepare_batch_transaction_to_send(
+        &mut self,
+        tx_id: TxId,
+        outputs: Vec<(WalletOutput, TariKeyId)>,
+        utxo_selection: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+        message: String,
+    ) -> Result<SenderTransactionProtocol, OutputManagerError> {
+        match self
+            .handle
+            .call(OutputManagerRequest::PrepareBatchToSend {
+                tx_id,
+                outputs,
+                selection_criteria: utxo_selection,
+                fee_per_gram,
+                message,
+            })
+            .await??
+        {
+            OutputManagerResponse::TransactionToSend(stp) => Ok(stp),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Prepare a transaction that pays each of the given interactive recipients the paired amount. The returned
+    /// protocol is waiting for the public nonces of the recipients.
+    pub async fn prepare_multi_recipient_transaction_to_send(
+        &mut self,
+        tx_id: TxId,
+        recipients: Vec<(TariAddress, MicroMinotari)>,
+        utxo_selection: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+        message: String,
+    ) -> Result<SenderTransactionProtocol, OutputManagerError> {
+        match self
+            .handle
+            .call(OutputManagerRequest::PrepareMultiRecipientToSend {
+                tx_id,
+                recipients,
+                selection_criteria: utxo_selection,
+                fee_per_gram,
+                message,
+            })
+            .await??
+        {
+            OutputManagerResponse::TransactionToSend(stp) => Ok(stp),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
     /// Get a fee estimate for an amount of MicroMinotari, at a specified fee per gram and given number of kernels and
     /// outputs.
     pub async fn fee_estimate(
@@ -540,6 +729,32 @@ impl OutputManagerHandle {
         }
     }
 
+    /// Select inputs for a payment and describe the transaction without signing it, so that it can be exported from a
+    /// watch-only wallet to a signer. The selected outputs are not encumbered.
+    pub async fn prepare_unsigned_transaction(
+        &mut self,
+        recipient_address: TariAddress,
+        amount: MicroMinotari,
+        selection_criteria: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+        message: String,
+    ) -> Result<UnsignedTransaction, OutputManagerError> {
+        match self
+            .handle
+            .call(OutputManagerRequest::PrepareUnsignedTransaction {
+                recipient_address,
+                amount,
+                selection_criteria,
+                fee_per_gram,
+                message,
+            })
+            .await??
+        {
+            OutputManagerResponse::UnsignedTransaction(transaction) => Ok(*transaction),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
     pub async fn confirm_pending_transaction(&mut self, tx_id: TxId) -> Result<(), OutputManagerError> {
         match self
             .handle
@@ -577,6 +792,39 @@ impl OutputManagerHandle {
         }
     }
 
+    /// Attach a label and note to an output, or clear them by passing `None`
+    pub async fn set_output_label(
+        &mut self,
+        commitment: Commitment,
+        label: Option<String>,
+        note: Option<String>,
+    ) -> Result<(), OutputManagerError> {
+        match self
+            .handle
+            .call(OutputManagerRequest::SetOutputLabel {
+                commitment,
+                label,
+                note,
+            })
+            .await??
+        {
+            OutputManagerResponse::OutputLabelSet => Ok(()),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Frozen outputs are excluded from input selection for all transactions until they are unfrozen
+    pub async fn set_output_frozen(&mut self, commitment: Commitment, frozen: bool) -> Result<(), OutputManagerError> {
+        match self
+            .handle
+            .call(OutputManagerRequest::SetOutputFrozen { commitment, frozen })
+            .await??
+        {
+            OutputManagerResponse::OutputFrozenSet => Ok(()),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
     pub async fn get_invalid_outputs(&mut self) -> Result<Vec<WalletOutput>, OutputManagerError> {
         match self.handle.call(OutputManagerRequest::GetInvalidOutputs).await?? {
             OutputManagerResponse::InvalidOutputs(s) => Ok(s),
@@ -911,4 +1159,105 @@ impl OutputManagerHandle {
             _ => Err(OutputManagerError::UnexpectedApiResponse),
         }
     }
+
+    /// Creates an m-of-n account from the cosigner keys. `own_public_key` is this wallet's cosigner key, which must be
+    /// one of `public_keys` and must have been derived on the multisig key branch.
+    pub async fn create_multisig_account(
+        &mut self,
+        name: String,
+        threshold: u8,
+        public_keys: Vec<PublicKey>,
+        own_public_key: PublicKey,
+    ) -> Result<MultisigAccount, OutputManagerError> {
+        match self
+            .handle
+            .call(OutputManagerRequest::CreateMultisigAccount {
+                name,
+                threshold,
+                public_keys,
+                own_public_key,
+            })
+            .await??
+        {
+            OutputManagerResponse::MultisigAccount(account) => Ok(*account),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
+    pub async fn get_multisig_accounts(&mut self) -> Result<Vec<MultisigAccount>, OutputManagerError> {
+        match self.handle.call(OutputManagerRequest::GetMultisigAccounts).await?? {
+            OutputManagerResponse::MultisigAccounts(accounts) => Ok(accounts),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
+    pub async fn get_multisig_account_outputs(
+        &mut self,
+        name: String,
+    ) -> Result<Vec<MultisigAccountOutput>, OutputManagerError> {
+        match self
+            .handle
+            .call(OutputManagerRequest::GetMultisigAccountOutputs(name))
+            .await??
+        {
+            OutputManagerResponse::MultisigAccountOutputs(outputs) => Ok(outputs),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Starts tracking the outputs locked to one of this wallet's multisig accounts and returns the newly found ones
+    pub async fn scan_outputs_for_multisig_accounts(
+        &mut self,
+        outputs: Vec<TransactionOutput>,
+        mined_height: u64,
+    ) -> Result<Vec<MultisigAccountOutput>, OutputManagerError> {
+        match self
+            .handle
+            .call(OutputManagerRequest::ScanOutputsForMultisigAccounts { outputs, mined_height })
+            .await??
+        {
+            OutputManagerResponse::MultisigAccountOutputs(outputs) => Ok(outputs),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Derives the next sub-address of this wallet and starts scanning for one-sided payments to it
+    pub async fn create_sub_address(&mut self, label: String) -> Result<SubAddress, OutputManagerError> {
+        match self
+            .handle
+            .call(OutputManagerRequest::CreateSubAddress(label))
+            .await??
+        {
+            OutputManagerResponse::SubAddress(sub_address) => Ok(*sub_address),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
+    pub async fn get_sub_addresses(&mut self) -> Result<Vec<SubAddress>, OutputManagerError> {
+        match self.handle.call(OutputManagerRequest::GetSubAddresses).await?? {
+            OutputManagerResponse::SubAddresses(sub_addresses) => Ok(sub_addresses),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Returns the outputs received on the sub-address at `index`, including those that have since been spent
+    pub async fn get_sub_address_outputs(&mut self, index: u64) -> Result<Vec<DbWalletOutput>, OutputManagerError> {
+        match self
+            .handle
+            .call(OutputManagerRequest::GetSubAddressOutputs(index))
+            .await??
+        {
+            OutputManagerResponse::SubAddressOutputs(outputs) => Ok(outputs),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Returns the items to test compact block filters against: the script keys of one-sided payments to this wallet
+    /// and the scripts of its multisig accounts. Outputs that can only be found by trial decryption have no item.
+    pub async fn get_block_filter_items(&mut self) -> Result<Vec<Vec<u8>>, OutputManagerError> {
+        match self.handle.call(OutputManagerRequest::GetBlockFilterItems).await?? {
+            OutputManagerResponse::BlockFilterItems(items) => Ok(items),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
 }
@@ -23,9 +23,19 @@
 use std::{
     fmt,
     fmt::{Display, Formatter},
+    str::FromStr,
 };
 
-use tari_common_types::types::Commitment;
+use tari_common_types::{transaction::TxId, types::Commitment};
+
+use crate::output_manager_service::storage::models::DbWalletOutput;
+
+/// The maximum number of branches the branch-and-bound search explores before giving up
+const BRANCH_AND_BOUND_MAX_TRIES: usize = 100_000;
+/// The maximum number of inputs the consolidation ordering sweeps into a single transaction
+pub(crate) const CONSOLIDATION_INPUTS_LIMIT: usize = 500;
+/// The maximum number of transactions followed back from a change output to the output that funded it
+pub(crate) const PRIVACY_GROUP_MAX_DEPTH: usize = 100;
 
 #[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
 pub enum UtxoSelectionMode {
@@ -34,6 +44,8 @@ pub enum UtxoSelectionMode {
     ListingOnly,
 }
 
+/// Criteria used to select the UTXOs that fund a transaction. Outputs the user has frozen are never selected,
+/// regardless of the filter.
 #[derive(Debug, Clone, Default)]
 pub struct UtxoSelectionCriteria {
     pub mode: UtxoSelectionMode,
@@ -92,6 +104,15 @@ pub enum UtxoSelectionOrdering {
     SmallestFirst,
     /// A strategy that selects the largest UTXOs first. Preferred when the amount is large
     LargestFirst,
+    /// Searches for a set of UTXOs that covers the amount and fee without needing a change output, wasting at most
+    /// the cost of a change output in fees. Falls back to the Default ordering when no such set exists.
+    BranchAndBound,
+    /// Only selects UTXOs that were received with the same payment id, or in the same transaction, so that funds
+    /// from different senders are never linked in one transaction.
+    Privacy,
+    /// Selects the smallest UTXOs first and keeps adding economical UTXOs after the amount is covered, folding them
+    /// into the change output. Intended for periods when fees are low.
+    Consolidation,
 }
 
 impl Display for UtxoSelectionOrdering {
@@ -100,6 +121,25 @@ impl Display for UtxoSelectionOrdering {
             UtxoSelectionOrdering::SmallestFirst => write!(f, "Smallest"),
             UtxoSelectionOrdering::LargestFirst => write!(f, "Largest"),
             UtxoSelectionOrdering::Default => write!(f, "Default"),
+            UtxoSelectionOrdering::BranchAndBound => write!(f, "BranchAndBound"),
+            UtxoSelectionOrdering::Privacy => write!(f, "Privacy"),
+            UtxoSelectionOrdering::Consolidation => write!(f, "Consolidation"),
+        }
+    }
+}
+
+impl FromStr for UtxoSelectionOrdering {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace(['-', '_'], "").as_str() {
+            "default" => Ok(UtxoSelectionOrdering::Default),
+            "smallest" | "smallestfirst" => Ok(UtxoSelectionOrdering::SmallestFirst),
+            "largest" | "largestfirst" => Ok(UtxoSelectionOrdering::LargestFirst),
+            "branchandbound" | "bnb" => Ok(UtxoSelectionOrdering::BranchAndBound),
+            "privacy" => Ok(UtxoSelectionOrdering::Privacy),
+            "consolidation" | "consolidate" => Ok(UtxoSelectionOrdering::Consolidation),
+            _ => Err(format!("Unknown UTXO selection ordering '{}'", s)),
         }
     }
 }
@@ -130,3 +170,133 @@ impl Display for UtxoSelectionFilter {
         }
     }
 }
+
+/// Outputs sharing a key were received from the same sender, either with the same payment id or in the same
+/// transaction. The privacy ordering never mixes outputs with different keys. Change is grouped by the output that
+/// funded the transaction it was created in, not by its own payment id.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum PrivacyGroup {
+    PaymentId(Vec<u8>),
+    Transaction(Option<TxId>),
+}
+
+impl From<&DbWalletOutput> for PrivacyGroup {
+    fn from(output: &DbWalletOutput) -> Self {
+        let payment_id = output.payment_id.to_bytes();
+        if payment_id.is_empty() {
+            PrivacyGroup::Transaction(output.received_in_tx_id)
+        } else {
+            PrivacyGroup::PaymentId(payment_id)
+        }
+    }
+}
+
+/// Searches for a subset of `effective_values` whose sum lies in `[target, target + tolerance]`, preferring the
+/// subset that exceeds the target the least. Effective values are input values less the fee each input adds to the
+/// transaction. Returns the indexes of the selected values, or `None` if no such subset was found.
+pub(crate) fn select_changeless(effective_values: &[u64], target: u64, tolerance: u64) -> Option<Vec<usize>> {
+    let mut order = (0..effective_values.len())
+        .filter(|&i| effective_values[i] > 0)
+        .collect::<Vec<_>>();
+    order.sort_by(|a, b| effective_values[*b].cmp(&effective_values[*a]));
+    let values = order.iter().map(|&i| effective_values[i]).collect::<Vec<_>>();
+
+    let mut remaining = vec![0u64; values.len() + 1];
+    for i in (0..values.len()).rev() {
+        remaining[i] = remaining[i + 1].saturating_add(values[i]);
+    }
+    if remaining[0] < target {
+        return None;
+    }
+
+    let mut search = BranchAndBound {
+        values: &values,
+        remaining: &remaining,
+        target,
+        upper_bound: target.saturating_add(tolerance),
+        tries: 0,
+        selection: Vec::new(),
+        best: None,
+    };
+    search.search(0, 0);
+    search
+        .best
+        .map(|(_, selection)| selection.into_iter().map(|i| order[i]).collect())
+}
+
+struct BranchAndBound<'a> {
+    values: &'a [u64],
+    remaining: &'a [u64],
+    target: u64,
+    upper_bound: u64,
+    tries: usize,
+    selection: Vec<usize>,
+    best: Option<(u64, Vec<usize>)>,
+}
+
+impl BranchAndBound<'_> {
+    fn search(&mut self, depth: usize, total: u64) {
+        if self.tries >= BRANCH_AND_BOUND_MAX_TRIES || total > self.upper_bound {
+            return;
+        }
+        self.tries += 1;
+        if total >= self.target {
+            let waste = total - self.target;
+            if self.best.as_ref().map_or(true, |(best, _)| waste < *best) {
+                self.best = Some((waste, self.selection.clone()));
+            }
+            return;
+        }
+        if depth == self.values.len() || total.saturating_add(self.remaining[depth]) < self.target {
+            return;
+        }
+
+        self.selection.push(depth);
+        self.search(depth + 1, total.saturating_add(self.values[depth]));
+        self.selection.pop();
+        if matches!(self.best, Some((0, _))) {
+            return;
+        }
+        self.search(depth + 1, total);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_finds_an_exact_changeless_selection() {
+        let values = [50, 20, 30, 7, 3];
+        let mut selection = select_changeless(&values, 60, 0).unwrap();
+        selection.sort_unstable();
+        assert_eq!(selection.iter().map(|&i| values[i]).sum::<u64>(), 60);
+    }
+
+    #[test]
+    fn it_accepts_a_selection_within_the_tolerance() {
+        let values = [40, 25, 11];
+        let selection = select_changeless(&values, 50, 2).unwrap();
+        let total = selection.iter().map(|&i| values[i]).sum::<u64>();
+        assert!((50..=52).contains(&total));
+    }
+
+    #[test]
+    fn it_gives_up_when_no_changeless_selection_exists() {
+        assert!(select_changeless(&[40, 25, 11], 50, 0).is_none());
+        assert!(select_changeless(&[10, 10], 30, 5).is_none());
+    }
+
+    #[test]
+    fn it_parses_orderings() {
+        assert_eq!(
+            "branch-and-bound".parse::<UtxoSelectionOrdering>().unwrap(),
+            UtxoSelectionOrdering::BranchAndBound
+        );
+        assert_eq!(
+            "Privacy".parse::<UtxoSelectionOrdering>().unwrap(),
+            UtxoSelectionOrdering::Privacy
+        );
+        assert!("random".parse::<UtxoSelectionOrdering>().is_err());
+    }
+}
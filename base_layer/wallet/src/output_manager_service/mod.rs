@@ -27,11 +27,14 @@ pub mod handle;
 mod input_selection;
 pub use input_selection::{UtxoSelectionCriteria, UtxoSelectionFilter, UtxoSelectionOrdering};
 
+pub mod multisig_account;
 mod recovery;
 pub mod resources;
 pub mod service;
 pub mod storage;
+pub mod sub_address;
 mod tasks;
+pub mod unsigned_transaction;
 
 use std::marker::PhantomData;
 
@@ -62,6 +65,7 @@ use crate::{
         service::OutputManagerService,
         storage::database::{OutputManagerBackend, OutputManagerDatabase},
     },
+    transaction_service::handle::TransactionServiceHandle,
 };
 
 /// The maximum number of transaction inputs that can be created in a single transaction, slightly less than the maximum
@@ -117,6 +121,7 @@ where
             .take()
             .expect("Cannot start Output Manager Service without setting a storage backend");
         let factories = self.factories.clone();
+        self.config.validate()?;
         let config = self.config.clone();
         let constants = self.network.create_consensus_constants().pop().unwrap();
         let network = self.network.as_network();
@@ -124,6 +129,7 @@ where
             let base_node_service_handle = handles.expect_handle::<BaseNodeServiceHandle>();
             let connectivity = handles.expect_handle::<WalletConnectivityHandle>();
             let key_manager = handles.expect_handle::<TKeyManagerInterface>();
+            let transaction_service = handles.expect_handle::<TransactionServiceHandle>();
 
             let service = OutputManagerService::new(
                 config,
@@ -134,6 +140,7 @@ where
                 constants,
                 handles.get_shutdown_signal(),
                 base_node_service_handle,
+                transaction_service,
                 network,
                 connectivity,
                 key_manager,
@@ -0,0 +1,226 @@
+//  Copyright 2024. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use blake2::Blake2b;
+use digest::consts::U32;
+use serde::{Deserialize, Serialize};
+use tari_common_types::{
+    key_branches::TransactionKeyManagerBranch,
+    transaction::TxId,
+    types::{Commitment, HashOutput, PrivateKey, PublicKey},
+};
+use tari_core::{one_sided::public_key_to_output_encryption_key, transactions::tari_amount::MicroMinotari};
+use tari_crypto::{hash_domain, hashing::DomainSeparatedHasher};
+use tari_key_manager::key_manager_service::KeyId;
+use tari_script::{script, Message, Opcode, TariScript};
+use tari_utilities::ByteArray;
+
+use crate::output_manager_service::error::OutputManagerError;
+
+/// The maximum number of cosigners the `CheckMultiSigVerifyAggregatePubKey` opcode supports
+pub const MAX_MULTISIG_COSIGNERS: usize = 32;
+
+hash_domain!(MultisigAccountDomain, "com.tari.base_layer.wallet.multisig_account", 1);
+
+/// An m-of-n account shared between cosigners. Funds sent to the account are locked to a script that requires
+/// `threshold` of the cosigners to sign before they can be spent. The account stores the index of this wallet's own
+/// cosigner key on the multisig key branch.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MultisigAccount {
+    pub name: String,
+    pub threshold: u8,
+    pub public_keys: Vec<PublicKey>,
+    pub key_index: u64,
+}
+
+impl MultisigAccount {
+    pub fn new(
+        name: String,
+        threshold: u8,
+        public_keys: Vec<PublicKey>,
+        key_index: u64,
+    ) -> Result<Self, OutputManagerError> {
+        if name.trim().is_empty() {
+            return Err(OutputManagerError::InvalidMultisigAccount(
+                "Account name cannot be empty".to_string(),
+            ));
+        }
+        let public_keys = sorted_cosigner_keys(threshold, public_keys)?;
+        Ok(Self {
+            name,
+            threshold,
+            public_keys,
+            key_index,
+        })
+    }
+
+    /// The key id of this wallet's own cosigner key
+    pub fn key_id(&self) -> KeyId<PublicKey> {
+        KeyId::Managed {
+            branch: TransactionKeyManagerBranch::Multisig.get_branch_key(),
+            index: self.key_index,
+        }
+    }
+
+    pub fn script(&self) -> TariScript {
+        multisig_script(self.threshold, &self.public_keys)
+    }
+
+    /// The message every signing cosigner signs to satisfy the account script
+    pub fn message(&self) -> Message {
+        multisig_message(self.threshold, &self.public_keys)
+    }
+
+    pub fn encryption_key(&self) -> Result<PrivateKey, OutputManagerError> {
+        multisig_encryption_key(&self.public_keys)
+    }
+}
+
+/// An output locked to a multisig account script
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MultisigAccountOutput {
+    pub account_name: String,
+    pub output_hash: HashOutput,
+    pub commitment: Commitment,
+    pub value: MicroMinotari,
+    pub mined_height: u64,
+    /// Set when this wallet led the spend of the output, and cleared if that transaction is cancelled
+    pub spent_in_tx_id: Option<TxId>,
+    /// Set when the spend of the output is seen on chain, whichever cosigner led it
+    pub spent_at_height: Option<u64>,
+}
+
+/// Validates the threshold against the cosigner keys and returns the keys in canonical order, so that every cosigner
+/// derives the same script regardless of the order in which the keys were exchanged.
+pub fn sorted_cosigner_keys(
+    threshold: u8,
+    mut public_keys: Vec<PublicKey>,
+) -> Result<Vec<PublicKey>, OutputManagerError> {
+    if public_keys.is_empty() || public_keys.len() > MAX_MULTISIG_COSIGNERS {
+        return Err(OutputManagerError::InvalidMultisigAccount(format!(
+            "An account needs between 1 and {} cosigners, {} were provided",
+            MAX_MULTISIG_COSIGNERS,
+            public_keys.len()
+        )));
+    }
+    if threshold == 0 || usize::from(threshold) > public_keys.len() {
+        return Err(OutputManagerError::InvalidMultisigAccount(format!(
+            "Threshold {} is not valid for {} cosigners",
+            threshold,
+            public_keys.len()
+        )));
+    }
+    public_keys.sort_by(|a, b| a.as_bytes().cmp(b.as_bytes()));
+    if public_keys.windows(2).any(|w| w[0] == w[1]) {
+        return Err(OutputManagerError::InvalidMultisigAccount(
+            "Cosigner keys must be unique".to_string(),
+        ));
+    }
+    Ok(public_keys)
+}
+
+/// The script locking funds to an m-of-n account. The keys must already be in canonical order.
+pub fn multisig_script(threshold: u8, public_keys: &[PublicKey]) -> TariScript {
+    #[allow(clippy::cast_possible_truncation)]
+    let n = public_keys.len() as u8;
+    let message = multisig_message(threshold, public_keys);
+    script!(CheckMultiSigVerifyAggregatePubKey(
+        threshold,
+        n,
+        public_keys.to_vec(),
+        Box::new(message)
+    ))
+}
+
+/// The account outputs are not tied to a single commitment, so the cosigners sign a message that commits to the
+/// account itself. Replaying these signatures is harmless, as the input must also carry a script signature made with
+/// the aggregate of the signing cosigners' keys.
+pub fn multisig_message(threshold: u8, public_keys: &[PublicKey]) -> Message {
+    let hasher = public_keys.iter().fold(
+        DomainSeparatedHasher::<Blake2b<U32>, MultisigAccountDomain>::new_with_label("message").chain([threshold]),
+        |hasher, key| hasher.chain(key.as_bytes()),
+    );
+    let mut message = [0u8; 32];
+    message.copy_from_slice(hasher.finalize().as_ref());
+    message
+}
+
+/// All cosigners can decrypt the value and commitment mask of the account outputs with a key derived from the sum of
+/// the cosigner keys, as is done for the pre-mine outputs.
+pub fn multisig_encryption_key(public_keys: &[PublicKey]) -> Result<PrivateKey, OutputManagerError> {
+    let sum_public_keys = public_keys.iter().fold(PublicKey::default(), |acc, x| acc + x);
+    Ok(public_key_to_output_encryption_key(&sum_public_keys)?)
+}
+
+/// Returns the cosigner keys, threshold and message of the first `CheckMultiSigVerifyAggregatePubKey` in the script
+pub fn multisig_script_components(script: &TariScript) -> Option<(Vec<PublicKey>, u8, Message)> {
+    script.as_slice().iter().find_map(|op| match op {
+        Opcode::CheckMultiSigVerifyAggregatePubKey(m, _n, keys, msg) => Some((keys.clone(), *m, **msg)),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::OsRng;
+    use tari_crypto::keys::PublicKey as PublicKeyTrait;
+
+    use super::*;
+
+    fn keys(n: usize) -> Vec<PublicKey> {
+        (0..n).map(|_| PublicKey::random_keypair(&mut OsRng).1).collect()
+    }
+
+    #[test]
+    fn it_derives_the_same_script_for_any_key_order() {
+        let public_keys = keys(3);
+        let mut reversed = public_keys.clone();
+        reversed.reverse();
+        let a = MultisigAccount::new("a".to_string(), 2, public_keys, 0).unwrap();
+        let b = MultisigAccount::new("b".to_string(), 2, reversed, 5).unwrap();
+        assert_eq!(a.script(), b.script());
+        assert_eq!(a.message(), b.message());
+        assert_eq!(a.encryption_key().unwrap(), b.encryption_key().unwrap());
+
+        let (script_keys, threshold, message) = multisig_script_components(&a.script()).unwrap();
+        assert_eq!(script_keys, a.public_keys);
+        assert_eq!(threshold, 2);
+        assert_eq!(message, a.message());
+    }
+
+    #[test]
+    fn it_rejects_invalid_accounts() {
+        let public_keys = keys(2);
+        assert!(MultisigAccount::new("a".to_string(), 0, public_keys.clone(), 0).is_err());
+        assert!(MultisigAccount::new("a".to_string(), 3, public_keys.clone(), 0).is_err());
+        assert!(MultisigAccount::new(" ".to_string(), 1, public_keys.clone(), 0).is_err());
+        let duplicated = vec![public_keys[0].clone(), public_keys[0].clone()];
+        assert!(MultisigAccount::new("a".to_string(), 1, duplicated, 0).is_err());
+        assert!(MultisigAccount::new("a".to_string(), 1, keys(MAX_MULTISIG_COSIGNERS + 1), 0).is_err());
+    }
+
+    #[test]
+    fn it_binds_the_message_to_the_threshold() {
+        let public_keys = sorted_cosigner_keys(1, keys(3)).unwrap();
+        assert_ne!(multisig_message(1, &public_keys), multisig_message(2, &public_keys));
+    }
+}
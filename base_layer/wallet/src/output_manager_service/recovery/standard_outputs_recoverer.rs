@@ -23,22 +23,27 @@
 use std::{str::FromStr, time::Instant};
 
 use log::*;
+use tari_common::configuration::Network;
 use tari_common_types::{
     transaction::TxId,
-    types::{FixedHash, PrivateKey},
+    types::{CommitmentFactory, FixedHash, PrivateKey},
 };
-use tari_core::transactions::{
-    key_manager::{TariKeyId, TransactionKeyManagerInterface},
-    tari_amount::MicroMinotari,
-    transaction_components::{
-        encrypted_data::PaymentId,
-        OutputType,
-        TransactionError,
-        TransactionOutput,
-        WalletOutput,
+use tari_core::{
+    one_sided::shared_secret_to_output_encryption_key,
+    transactions::{
+        key_manager::{TariKeyId, TransactionKeyManagerInterface},
+        tari_amount::MicroMinotari,
+        transaction_components::{
+            encrypted_data::PaymentId,
+            EncryptedData,
+            OutputType,
+            TransactionError,
+            TransactionOutput,
+            WalletOutput,
+        },
     },
 };
-use tari_crypto::keys::SecretKey;
+use tari_crypto::{commitment::HomomorphicCommitmentFactory, keys::SecretKey};
 use tari_key_manager::key_manager_service::KeyId;
 use tari_script::{inputs, script, ExecutionStack, Opcode, TariScript};
 use tari_utilities::hex::Hex;
@@ -51,6 +56,7 @@ use crate::output_manager_service::{
         models::{DbWalletOutput, KnownOneSidedPaymentScript},
         OutputSource,
     },
+    sub_address::{fetch_sub_address_scan_keys, recover_sub_address, sub_address_key_id, SubAddressScanKey},
 };
 
 const LOG_TARGET: &str = "wallet::output_manager_service::recovery";
@@ -58,6 +64,7 @@ const LOG_TARGET: &str = "wallet::output_manager_service::recovery";
 pub(crate) struct StandardUtxoRecoverer<TBackend: OutputManagerBackend + 'static, TKeyManagerInterface> {
     master_key_manager: TKeyManagerInterface,
     db: OutputManagerDatabase<TBackend>,
+    network: Network,
 }
 
 impl<TBackend, TKeyManagerInterface> StandardUtxoRecoverer<TBackend, TKeyManagerInterface>
@@ -65,12 +72,21 @@ where
     TBackend: OutputManagerBackend + 'static,
     TKeyManagerInterface: TransactionKeyManagerInterface,
 {
-    pub fn new(master_key_manager: TKeyManagerInterface, db: OutputManagerDatabase<TBackend>) -> Self {
-        Self { master_key_manager, db }
+    pub fn new(
+        master_key_manager: TKeyManagerInterface,
+        db: OutputManagerDatabase<TBackend>,
+        network: Network,
+    ) -> Self {
+        Self {
+            master_key_manager,
+            db,
+            network,
+        }
     }
 
     /// Attempt to rewind all of the given transaction outputs into key_manager outputs. If they can be rewound then add
-    /// them to the database and increment the key manager index
+    /// them to the database and increment the key manager index. Sub-addresses that payments are found for are
+    /// registered.
     pub async fn scan_and_recover_outputs(
         &mut self,
         outputs: Vec<TransactionOutput>,
@@ -79,10 +95,31 @@ where
         let outputs_length = outputs.len();
 
         let known_scripts = self.db.get_all_known_one_sided_payment_scripts()?;
+        let sub_address_keys = fetch_sub_address_scan_keys(&self.db, &self.master_key_manager).await?;
+        let mut recovered_sub_addresses: Vec<&SubAddressScanKey> = Vec::new();
 
         let mut rewound_outputs: Vec<(WalletOutput, bool, FixedHash)> = Vec::new();
         let push_pub_key_script = script!(PushPubKey(Box::default()));
         for output in outputs {
+            let sub_address_key = match output.script.as_slice() {
+                [Opcode::PushPubKey(public_key)] => {
+                    sub_address_keys.iter().find(|k| &k.public_key == public_key.as_ref())
+                },
+                _ => None,
+            };
+            if let Some(sub_address_key) = sub_address_key {
+                if let Some(uo) = self
+                    .attempt_sub_address_output_recovery(&output, sub_address_key.index)
+                    .await?
+                {
+                    if !sub_address_key.is_registered && !recovered_sub_addresses.contains(&sub_address_key) {
+                        recovered_sub_addresses.push(sub_address_key);
+                    }
+                    rewound_outputs.push((uo, true, output.hash()));
+                }
+                continue;
+            }
+
             let known_script_index = known_scripts.iter().position(|s| s.script == output.script);
             if output.script != script!(Nop) &&
                 known_script_index.is_none() &&
@@ -133,6 +170,17 @@ where
             rewind_time.as_millis(),
         );
 
+        for sub_address_key in recovered_sub_addresses {
+            recover_sub_address(
+                &self.db,
+                &self.master_key_manager,
+                self.network,
+                sub_address_key.index,
+                sub_address_key.public_key.clone(),
+            )
+            .await?;
+        }
+
         let mut rewound_outputs_with_tx_id: Vec<RecoveredOutput> = Vec::new();
         for (output, has_known_script, hash) in &mut rewound_outputs {
             let db_output = DbWalletOutput::from_wallet_output(
@@ -241,6 +289,53 @@ where
         Ok(Some((input_data, script_key)))
     }
 
+    /// One-sided payments to a sub-address are encrypted with the shared secret of the wallet view key and the sender
+    /// offset key, and are spent with the key at the sub-address index.
+    async fn attempt_sub_address_output_recovery(
+        &self,
+        output: &TransactionOutput,
+        index: u64,
+    ) -> Result<Option<WalletOutput>, OutputManagerError> {
+        match self.db.fetch_by_commitment(output.commitment().clone()) {
+            Ok(_) => return Ok(None),
+            Err(OutputManagerStorageError::ValueNotFound) => {},
+            Err(e) => return Err(e.into()),
+        };
+        let view_key = self.master_key_manager.get_view_key().await?;
+        let shared_secret = self
+            .master_key_manager
+            .get_diffie_hellman_shared_secret(&view_key.key_id, &output.sender_offset_public_key)
+            .await?;
+        let encryption_key = shared_secret_to_output_encryption_key(&shared_secret)?;
+        let Ok((committed_value, spending_key, payment_id)) =
+            EncryptedData::decrypt_data(&encryption_key, output.commitment(), output.encrypted_data())
+        else {
+            return Ok(None);
+        };
+        if !CommitmentFactory::default().open_value(&spending_key, committed_value.as_u64(), output.commitment()) {
+            return Ok(None);
+        }
+        let spending_key_id = self.master_key_manager.import_key(spending_key).await?;
+
+        Ok(Some(WalletOutput::new_with_rangeproof(
+            output.version,
+            committed_value,
+            spending_key_id,
+            output.features.clone(),
+            output.script.clone(),
+            ExecutionStack::default(),
+            sub_address_key_id(index),
+            output.sender_offset_public_key.clone(),
+            output.metadata_signature.clone(),
+            0,
+            output.covenant.clone(),
+            output.encrypted_data.clone(),
+            output.minimum_value_promise,
+            output.proof.clone(),
+            payment_id,
+        )))
+    }
+
     async fn attempt_output_recovery(
         &self,
         output: &TransactionOutput,
@@ -23,7 +23,7 @@
 use std::{collections::HashMap, convert::TryInto, fmt, sync::Arc};
 
 use diesel::result::{DatabaseErrorKind, Error as DieselError};
-use futures::{pin_mut, StreamExt};
+use futures::{future, pin_mut, StreamExt};
 use log::*;
 use rand::{rngs::OsRng, RngCore};
 use tari_common::configuration::Network;
@@ -35,6 +35,7 @@ use tari_common_types::{
 };
 use tari_comms::types::CommsDHKE;
 use tari_core::{
+    blocks::BlockFilter,
     borsh::SerializedSize,
     consensus::ConsensusConstants,
     covenants::Covenant,
@@ -43,7 +44,7 @@ use tari_core::{
         shared_secret_to_output_encryption_key,
         shared_secret_to_output_spending_key,
     },
-    proto::base_node::FetchMatchingUtxos,
+    proto::base_node::{FetchMatchingUtxos, GetMempoolFeePerGramStatsRequest},
     transactions::{
         fee::Fee,
         key_manager::{TariKeyId, TransactionKeyManagerInterface},
@@ -75,6 +76,7 @@ use tari_script::{
     script,
     CheckSigSchnorrSignature,
     ExecutionStack,
+    Message,
     Opcode,
     StackItem,
     TariScript,
@@ -82,7 +84,11 @@ use tari_script::{
 use tari_service_framework::reply_channel;
 use tari_shutdown::ShutdownSignal;
 use tari_utilities::{hex::Hex, ByteArray};
-use tokio::{sync::Mutex, time::Instant};
+use tokio::{
+    sync::{mpsc, Mutex},
+    time,
+    time::{Instant, MissedTickBehavior},
+};
 
 use crate::{
     base_node_service::handle::{BaseNodeEvent, BaseNodeServiceHandle},
@@ -97,7 +103,15 @@ use crate::{
             OutputManagerResponse,
             RecoveredOutput,
         },
-        input_selection::UtxoSelectionCriteria,
+        input_selection::{
+            select_changeless,
+            PrivacyGroup,
+            UtxoSelectionCriteria,
+            UtxoSelectionOrdering,
+            CONSOLIDATION_INPUTS_LIMIT,
+            PRIVACY_GROUP_MAX_DEPTH,
+        },
+        multisig_account::{multisig_script_components, MultisigAccount, MultisigAccountOutput},
         recovery::StandardUtxoRecoverer,
         resources::OutputManagerResources,
         storage::{
@@ -106,9 +120,12 @@ use crate::{
             OutputSource,
             OutputStatus,
         },
+        sub_address::{fetch_sub_address_scan_keys, is_sub_address_key_id, register_sub_address, SubAddress},
         tasks::TxoValidationTask,
+        unsigned_transaction::{UnsignedTransaction, UnsignedTransactionInput, UNSIGNED_TRANSACTION_VERSION},
         TRANSACTION_INPUTS_LIMIT,
     },
+    transaction_service::handle::TransactionServiceHandle,
 };
 
 const LOG_TARGET: &str = "wallet::output_manager_service";
@@ -122,6 +139,7 @@ pub struct OutputManagerService<TBackend, TWalletConnectivity, TKeyManagerInterf
     request_stream:
         Option<reply_channel::Receiver<OutputManagerRequest, Result<OutputManagerResponse, OutputManagerError>>>,
     base_node_service: BaseNodeServiceHandle,
+    transaction_service: TransactionServiceHandle,
     last_seen_tip_height: Option<u64>,
     validation_in_progress: Arc<Mutex<()>>,
 }
@@ -145,10 +163,12 @@ where
         consensus_constants: ConsensusConstants,
         shutdown_signal: ShutdownSignal,
         base_node_service: BaseNodeServiceHandle,
+        transaction_service: TransactionServiceHandle,
         network: Network,
         connectivity: TWalletConnectivity,
         key_manager: TKeyManagerInterface,
     ) -> Result<Self, OutputManagerError> {
+        config.validate()?;
         let view_key = key_manager.get_view_key().await?;
         let spend_key = key_manager.get_spend_key().await?;
         let comms_key = key_manager.get_comms_key().await?;
@@ -182,6 +202,7 @@ where
             resources,
             request_stream: Some(request_stream),
             base_node_service,
+            transaction_service,
             last_seen_tip_height: None,
             validation_in_progress: Arc::new(Mutex::new(())),
         })
@@ -202,6 +223,18 @@ where
         debug!(target: LOG_TARGET, "Output Manager Service started");
         // Outputs marked as shorttermencumbered are not yet stored as transactions in the TMS, so lets clear them
         self.resources.db.clear_short_term_encumberances()?;
+
+        let mut consolidation_interval = if self.resources.config.auto_consolidation_enabled {
+            let mut interval = time::interval(self.resources.config.auto_consolidation_interval);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            // The first tick completes immediately, consolidation should only start after the first full interval
+            interval.tick().await;
+            Some(interval)
+        } else {
+            None
+        };
+        let (consolidation_failed_tx, mut consolidation_failed_rx) = mpsc::unbounded_channel();
+
         loop {
             tokio::select! {
                 event = base_node_service_event_stream.recv() => {
@@ -221,6 +254,23 @@ where
                         warn!(target: LOG_TARGET, "Failed to send reply");
                     });
                 },
+                _ = async {
+                    match consolidation_interval.as_mut() {
+                        Some(interval) => interval.tick().await,
+                        None => future::pending().await,
+                    }
+                } => {
+                    self.run_auto_consolidation(&consolidation_failed_tx).await;
+                },
+                Some(tx_id) = consolidation_failed_rx.recv() => {
+                    // The consolidation was never recorded by the transaction service, so release its inputs
+                    if let Err(e) = self.cancel_transaction(tx_id) {
+                        warn!(
+                            target: LOG_TARGET,
+                            "Could not cancel failed UTXO consolidation transaction (TxId: {}): {}", tx_id, e
+                        );
+                    }
+                },
                 _ = shutdown.wait() => {
                     info!(target: LOG_TARGET, "Output manager service shutting down because it received the shutdown signal");
                     break;
@@ -342,6 +392,32 @@ where
                 )
                 .await
                 .map(OutputManagerResponse::TransactionToSend),
+            OutputManagerRequest::PrepareBatchToSend {
+                tx_id,
+                outputs,
+                selection_criteria,
+                fee_per_gram,
+                message,
+            } => self
+                .prepare_batch_transaction_to_send(tx_id, outputs, selection_criteria, fee_per_gram, message)
+                .await
+                .map(OutputManagerResponse::TransactionToSend),
+            OutputManagerRequest::PrepareMultiRecipientToSend {
+                tx_id,
+                recipients,
+                selection_criteria,
+                fee_per_gram,
+                message,
+            } => self
+                .prepare_multi_recipient_transaction_to_send(
+                    tx_id,
+                    recipients,
+                    selection_criteria,
+                    fee_per_gram,
+                    message,
+                )
+                .await
+                .map(OutputManagerResponse::TransactionToSend),
             OutputManagerRequest::CreatePayToSelfTransaction {
                 tx_id,
                 amount,
@@ -370,6 +446,16 @@ where
                 .fee_estimate(amount, selection_criteria, fee_per_gram, num_kernels, num_outputs)
                 .await
                 .map(OutputManagerResponse::FeeEstimate),
+            OutputManagerRequest::PrepareUnsignedTransaction {
+                recipient_address,
+                amount,
+                selection_criteria,
+                fee_per_gram,
+                message,
+            } => self
+                .prepare_unsigned_transaction(recipient_address, amount, selection_criteria, fee_per_gram, message)
+                .await
+                .map(|tx| OutputManagerResponse::UnsignedTransaction(Box::new(tx))),
             OutputManagerRequest::ConfirmPendingTransaction(tx_id) => self
                 .confirm_encumberance(tx_id)
                 .map(|_| OutputManagerResponse::PendingTransactionConfirmed),
@@ -384,6 +470,50 @@ where
                 let outputs = self.fetch_unspent_outputs()?;
                 Ok(OutputManagerResponse::UnspentOutputs(outputs))
             },
+            OutputManagerRequest::SetOutputLabel {
+                commitment,
+                label,
+                note,
+            } => {
+                self.resources.db.set_output_label(commitment, label, note)?;
+                Ok(OutputManagerResponse::OutputLabelSet)
+            },
+            OutputManagerRequest::SetOutputFrozen { commitment, frozen } => {
+                self.resources.db.set_output_frozen(commitment, frozen)?;
+                Ok(OutputManagerResponse::OutputFrozenSet)
+            },
+            OutputManagerRequest::CreateMultisigAccount {
+                name,
+                threshold,
+                public_keys,
+                own_public_key,
+            } => self
+                .create_multisig_account(name, threshold, public_keys, own_public_key)
+                .await
+                .map(|account| OutputManagerResponse::MultisigAccount(Box::new(account))),
+            OutputManagerRequest::GetMultisigAccounts => Ok(OutputManagerResponse::MultisigAccounts(
+                self.resources.db.fetch_multisig_accounts()?,
+            )),
+            OutputManagerRequest::GetMultisigAccountOutputs(name) => Ok(OutputManagerResponse::MultisigAccountOutputs(
+                self.resources.db.fetch_multisig_account_outputs(&name)?,
+            )),
+            OutputManagerRequest::ScanOutputsForMultisigAccounts { outputs, mined_height } => self
+                .scan_outputs_for_multisig_accounts(outputs, mined_height)
+                .map(OutputManagerResponse::MultisigAccountOutputs),
+            OutputManagerRequest::CreateSubAddress(label) => self
+                .create_sub_address(label)
+                .await
+                .map(|sub_address| OutputManagerResponse::SubAddress(Box::new(sub_address))),
+            OutputManagerRequest::GetSubAddresses => Ok(OutputManagerResponse::SubAddresses(
+                self.resources.db.fetch_sub_addresses()?,
+            )),
+            OutputManagerRequest::GetSubAddressOutputs(index) => self
+                .get_sub_address_outputs(index)
+                .map(OutputManagerResponse::SubAddressOutputs),
+            OutputManagerRequest::GetBlockFilterItems => self
+                .get_block_filter_items()
+                .await
+                .map(OutputManagerResponse::BlockFilterItems),
             OutputManagerRequest::ValidateUtxos => {
                 self.validate_outputs().map(OutputManagerResponse::TxoValidationStarted)
             },
@@ -441,12 +571,14 @@ where
                 .await
                 .map(OutputManagerResponse::Transaction),
 
-            OutputManagerRequest::ScanForRecoverableOutputs(outputs) => {
-                StandardUtxoRecoverer::new(self.resources.key_manager.clone(), self.resources.db.clone())
-                    .scan_and_recover_outputs(outputs)
-                    .await
-                    .map(OutputManagerResponse::RewoundOutputs)
-            },
+            OutputManagerRequest::ScanForRecoverableOutputs(outputs) => StandardUtxoRecoverer::new(
+                self.resources.key_manager.clone(),
+                self.resources.db.clone(),
+                self.resources.one_sided_tari_address.network(),
+            )
+            .scan_and_recover_outputs(outputs)
+            .await
+            .map(OutputManagerResponse::RewoundOutputs),
             OutputManagerRequest::ScanOutputs(outputs) => self
                 .scan_outputs_for_one_sided_payments(outputs)
                 .await
@@ -752,7 +884,7 @@ where
         &mut self,
         sender_message: TransactionSenderMessage,
     ) -> Result<ReceiverTransactionProtocol, OutputManagerError> {
-        let single_round_sender_data = match sender_message.single() {
+        let single_round_sender_data = match sender_message.sender_data() {
             Some(data) => data,
             _ => return Err(OutputManagerError::InvalidSenderMessage),
         };
@@ -946,6 +1078,69 @@ where
         Ok(fee)
     }
 
+    /// Select the inputs for a single recipient payment without building or signing any outputs, for use by watch-only
+    /// wallets. Nothing is encumbered, so the same outputs remain spendable until the signed transaction is broadcast.
+    async fn prepare_unsigned_transaction(
+        &mut self,
+        recipient_address: TariAddress,
+        amount: MicroMinotari,
+        selection_criteria: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+        message: String,
+    ) -> Result<UnsignedTransaction, OutputManagerError> {
+        debug!(
+            target: LOG_TARGET,
+            "Preparing unsigned transaction. Amount: {}. Fee per gram: {}", amount, fee_per_gram
+        );
+        // We assume that default OutputFeatures and PushPubKey TariScript is used for the recipient output
+        let features_and_scripts_byte_size = self
+            .resources
+            .consensus_constants
+            .transaction_weight_params()
+            .round_up_features_and_scripts_size(
+                OutputFeatures::default()
+                    .get_serialized_size()
+                    .map_err(|e| OutputManagerError::ConversionError(e.to_string()))? +
+                    TariScript::default()
+                        .get_serialized_size()
+                        .map_err(|e| OutputManagerError::ConversionError(e.to_string()))? +
+                    Covenant::new()
+                        .get_serialized_size()
+                        .map_err(|e| OutputManagerError::ConversionError(e.to_string()))?,
+            );
+
+        let utxo_selection = self
+            .select_utxos(
+                amount,
+                selection_criteria,
+                fee_per_gram,
+                1,
+                features_and_scripts_byte_size,
+            )
+            .await?;
+
+        let fee = utxo_selection.as_final_fee();
+        let change = if utxo_selection.requires_change_output() {
+            utxo_selection.total_value() - amount - fee
+        } else {
+            MicroMinotari::zero()
+        };
+        // Without a change output any remainder below the dust threshold is absorbed into the fee
+        let fee = utxo_selection.total_value() - amount - change;
+
+        Ok(UnsignedTransaction {
+            version: UNSIGNED_TRANSACTION_VERSION,
+            tx_id: TxId::new_random(),
+            recipient_address,
+            amount,
+            fee_per_gram,
+            fee,
+            change,
+            message,
+            inputs: utxo_selection.iter().map(UnsignedTransactionInput::from).collect(),
+        })
+    }
+
     /// Prepare a Sender Transaction Protocol for the amount and fee_per_gram specified. If required a change output
     /// will be produced.
     #[allow(clippy::too_many_lines)]
@@ -1077,6 +1272,261 @@ where
         Ok(stp)
     }
 
+    /// Prepare a Sender Transaction Protocol that spends to each of the given outputs. The outputs must already be
+    /// signed with the paired sender offset key. If required a change output will be produced.
+    #[allow(clippy::too_many_lines)]
+    pub async fn prepare_batch_transaction_to_send(
+        &mut self,
+        tx_id: TxId,
+        outputs: Vec<(WalletOutput, TariKeyId)>,
+        selection_criteria: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+        message: String,
+    ) -> Result<SenderTransactionProtocol, OutputManagerError> {
+        if outputs.is_empty() {
+            return Err(OutputManagerError::BuildError(
+                "A batch transaction requires at least one output".to_string(),
+            ));
+        }
+        let total_value = outputs.iter().map(|(o, _)| o.value).sum();
+        debug!(
+            target: LOG_TARGET,
+            "Preparing to send batch transaction. Outputs: {}. Total amount: {}. UTXO Selection: {}. Fee per gram: {}. ",
+            outputs.len(),
+            total_value,
+            selection_criteria,
+            fee_per_gram,
+        );
+        let weighting = self.resources.consensus_constants.transaction_weight_params();
+        let mut features_and_scripts_byte_size = 0;
+        for (output, _) in &outputs {
+            features_and_scripts_byte_size += weighting.round_up_features_and_scripts_size(
+                output
+                    .features
+                    .get_serialized_size()
+                    .map_err(|e| OutputManagerError::ConversionError(e.to_string()))? +
+                    output
+                        .script
+                        .get_serialized_size()
+                        .map_err(|e| OutputManagerError::ConversionError(e.to_string()))? +
+                    output
+                        .covenant
+                        .get_serialized_size()
+                        .map_err(|e| OutputManagerError::ConversionError(e.to_string()))?,
+            );
+        }
+
+        let input_selection = self
+            .select_utxos(
+                total_value,
+                selection_criteria,
+                fee_per_gram,
+                outputs.len(),
+                features_and_scripts_byte_size,
+            )
+            .await?;
+
+        let mut builder = SenderTransactionProtocol::builder(
+            self.resources.consensus_constants.clone(),
+            self.resources.key_manager.clone(),
+        );
+        builder
+            .with_fee_per_gram(fee_per_gram)
+            .with_sender_address(self.resources.interactive_tari_address.clone())
+            .with_message(message)
+            .with_prevent_fee_gt_amount(self.resources.config.prevent_fee_gt_amount)
+            .with_lock_height(0)
+            .with_kernel_features(KernelFeatures::empty())
+            .with_tx_id(tx_id);
+
+        for uo in input_selection.iter() {
+            builder.with_input(uo.wallet_output.clone()).await?;
+        }
+        for (output, sender_offset_key_id) in outputs {
+            builder
+                .with_output(output, sender_offset_key_id)
+                .await
+                .map_err(|e| OutputManagerError::BuildError(e.to_string()))?;
+        }
+
+        if input_selection.requires_change_output() {
+            let (change_commitment_mask_key, change_script_key) = self
+                .resources
+                .key_manager
+                .get_next_commitment_mask_and_script_key()
+                .await?;
+            builder.with_change_data(
+                script!(PushPubKey(Box::new(change_script_key.pub_key))),
+                ExecutionStack::default(),
+                change_script_key.key_id,
+                change_commitment_mask_key.key_id,
+                Covenant::default(),
+                self.resources.interactive_tari_address.clone(),
+            );
+        }
+
+        let stp = builder
+            .build()
+            .await
+            .map_err(|e| OutputManagerError::BuildError(e.message))?;
+
+        let mut change_output = Vec::<DbWalletOutput>::new();
+        if let Some(wallet_output) = stp.get_change_output()? {
+            change_output.push(
+                DbWalletOutput::from_wallet_output(
+                    wallet_output,
+                    &self.resources.key_manager,
+                    None,
+                    OutputSource::default(),
+                    Some(tx_id),
+                    None,
+                )
+                .await?,
+            );
+        }
+
+        // The recipient outputs belong to other wallets, so only the inputs and change are tracked here
+        self.resources
+            .db
+            .encumber_outputs(tx_id, input_selection.into_selected(), change_output)?;
+
+        debug!(target: LOG_TARGET, "Prepared batch transaction (TxId: {}) to send", tx_id);
+
+        Ok(stp)
+    }
+
+    /// Prepare a Sender Transaction Protocol that pays each of the given interactive recipients. The returned protocol
+    /// is waiting for the recipients' public nonces. If required a change output will be produced.
+    #[allow(clippy::too_many_lines)]
+    pub async fn prepare_multi_recipient_transaction_to_send(
+        &mut self,
+        tx_id: TxId,
+        recipients: Vec<(TariAddress, MicroMinotari)>,
+        selection_criteria: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+        message: String,
+    ) -> Result<SenderTransactionProtocol, OutputManagerError> {
+        if recipients.len() < 2 {
+            return Err(OutputManagerError::BuildError(
+                "A multi-recipient transaction requires at least two recipients".to_string(),
+            ));
+        }
+        let total_value = recipients.iter().map(|(_, amount)| *amount).sum();
+        debug!(
+            target: LOG_TARGET,
+            "Preparing to send multi-recipient transaction. Recipients: {}. Total amount: {}. UTXO Selection: {}. Fee \
+             per gram: {}. ",
+            recipients.len(),
+            total_value,
+            selection_criteria,
+            fee_per_gram,
+        );
+        let recipient_output_features = OutputFeatures::default();
+        let recipient_script = TariScript::default();
+        let recipient_covenant = Covenant::default();
+        let features_and_scripts_byte_size = self
+            .resources
+            .consensus_constants
+            .transaction_weight_params()
+            .round_up_features_and_scripts_size(
+                recipient_output_features
+                    .get_serialized_size()
+                    .map_err(|e| OutputManagerError::ConversionError(e.to_string()))? +
+                    recipient_script
+                        .get_serialized_size()
+                        .map_err(|e| OutputManagerError::ConversionError(e.to_string()))? +
+                    recipient_covenant
+                        .get_serialized_size()
+                        .map_err(|e| OutputManagerError::ConversionError(e.to_string()))?,
+            );
+
+        let input_selection = self
+            .select_utxos(
+                total_value,
+                selection_criteria,
+                fee_per_gram,
+                recipients.len(),
+                features_and_scripts_byte_size * recipients.len(),
+            )
+            .await?;
+
+        let mut builder = SenderTransactionProtocol::builder(
+            self.resources.consensus_constants.clone(),
+            self.resources.key_manager.clone(),
+        );
+        builder
+            .with_fee_per_gram(fee_per_gram)
+            .with_sender_address(self.resources.interactive_tari_address.clone())
+            .with_message(message)
+            .with_prevent_fee_gt_amount(self.resources.config.prevent_fee_gt_amount)
+            .with_lock_height(0)
+            .with_kernel_features(KernelFeatures::empty())
+            .with_tx_id(tx_id);
+        let mut addresses = Vec::with_capacity(recipients.len());
+        for (address, amount) in recipients {
+            builder
+                .with_recipient_data(
+                    recipient_script.clone(),
+                    recipient_output_features.clone(),
+                    recipient_covenant.clone(),
+                    MicroMinotari::zero(),
+                    amount,
+                )
+                .await?;
+            addresses.push(address);
+        }
+        builder.with_recipient_addresses(addresses);
+
+        for uo in input_selection.iter() {
+            builder.with_input(uo.wallet_output.clone()).await?;
+        }
+
+        let (change_commitment_mask_key, change_script_key) = self
+            .resources
+            .key_manager
+            .get_next_commitment_mask_and_script_key()
+            .await?;
+        builder.with_change_data(
+            script!(PushPubKey(Box::new(change_script_key.pub_key))),
+            ExecutionStack::default(),
+            change_script_key.key_id,
+            change_commitment_mask_key.key_id,
+            Covenant::default(),
+            self.resources.interactive_tari_address.clone(),
+        );
+
+        let stp = builder
+            .build()
+            .await
+            .map_err(|e| OutputManagerError::BuildError(e.message))?;
+
+        let mut change_output = Vec::<DbWalletOutput>::new();
+        if let Some(wallet_output) = stp.get_change_output()? {
+            change_output.push(
+                DbWalletOutput::from_wallet_output(
+                    wallet_output,
+                    &self.resources.key_manager,
+                    None,
+                    OutputSource::default(),
+                    Some(tx_id),
+                    None,
+                )
+                .await?,
+            );
+        }
+
+        self.resources
+            .db
+            .encumber_outputs(tx_id, input_selection.into_selected(), change_output)?;
+
+        debug!(
+            target: LOG_TARGET,
+            "Prepared multi-recipient transaction (TxId: {}) to send", tx_id
+        );
+
+        Ok(stp)
+    }
+
     #[allow(clippy::too_many_lines)]
     async fn create_pay_to_self_containing_outputs(
         &mut self,
@@ -1281,8 +1731,16 @@ where
         }
         trace!(target: LOG_TARGET, "encumber_aggregate_utxo: fetched outputs");
         // Retrieve the list of n public keys from the script
-        let (multi_sig_public_keys, threshold) = get_multi_sig_script_components(&output.script, tx_id)?;
+        let (multi_sig_public_keys, threshold, message) = get_multi_sig_script_components(&output.script, tx_id)?;
         trace!(target: LOG_TARGET, "encumber_aggregate_utxo: retrieved public keys from script");
+        // Outputs of a multisig account are signed with this wallet's cosigner key, pre-mine outputs with the key
+        // indexed by their payment id
+        let multisig_account = self
+            .resources
+            .db
+            .fetch_multisig_accounts()?
+            .into_iter()
+            .find(|account| account.script() == output.script);
         // Create a deterministic encryption key from the sum of the public keys
         let sum_public_keys = multi_sig_public_keys
             .iter()
@@ -1295,15 +1753,25 @@ where
             EncryptedData::decrypt_data(&encryption_private_key, &output.commitment, &output.encrypted_data)
         {
             if output.verify_mask(&self.resources.factories.range_proof, &commitment_mask, amount.as_u64())? {
-                let script_key = self
-                    .pre_mine_script_key_from_payment_id(payment_id.clone(), tx_id)
-                    .await?;
+                let script_key = match &multisig_account {
+                    Some(account) => {
+                        let key_id = account.key_id();
+                        KeyAndId::<PublicKey> {
+                            pub_key: self.resources.key_manager.get_public_key_at_key_id(&key_id).await?,
+                            key_id,
+                        }
+                    },
+                    None => {
+                        self.pre_mine_script_key_from_payment_id(payment_id.clone(), tx_id)
+                            .await?
+                    },
+                };
                 let mut script_signatures = Vec::new();
                 // lets add our own signature to the list
                 let self_signature = self
                     .resources
                     .key_manager
-                    .sign_script_message(&script_key.key_id, output.commitment.as_bytes())
+                    .sign_script_message(&script_key.key_id, &message)
                     .await?;
                 script_input_shares.insert(script_key.pub_key.clone(), self_signature);
 
@@ -1564,6 +2032,11 @@ where
         trace!(target: LOG_TARGET, "encumber_aggregate_utxo: updated script signature");
 
         let fee = stp.get_fee_amount()?;
+        if multisig_account.is_some() {
+            self.resources
+                .db
+                .mark_multisig_account_output_spent(&output_hash, tx_id)?;
+        }
 
         Ok((
             tx,
@@ -1957,7 +2430,12 @@ where
             target: LOG_TARGET,
             "Cancelling pending transaction outputs for TxId: {}", tx_id
         );
-        Ok(self.resources.db.cancel_pending_transaction_outputs(tx_id)?)
+        // A multisig account spend encumbers no outputs of this wallet, only the account output it spends
+        let released_multisig_outputs = self.resources.db.clear_multisig_account_outputs_spent_in_tx(tx_id)?;
+        match self.resources.db.cancel_pending_transaction_outputs(tx_id) {
+            Err(OutputManagerStorageError::ValueNotFound) if released_multisig_outputs > 0 => Ok(()),
+            result => Ok(result?),
+        }
     }
 
     /// Restore the pending transaction encumberance and output for an inbound transaction that was previously
@@ -2045,11 +2523,114 @@ where
 
         trace!(target: LOG_TARGET, "We found {} UTXOs to select from", uo_len);
 
+        if selection_criteria.ordering == UtxoSelectionOrdering::BranchAndBound {
+            let base_fee = fee_calc.calculate(
+                fee_per_gram,
+                1,
+                0,
+                num_outputs,
+                total_output_features_and_scripts_byte_size,
+            );
+            let input_fee = fee_calc.calculate(fee_per_gram, 0, 1, 0, 0);
+            let change_fee = fee_calc.calculate(fee_per_gram, 0, 0, 1, default_features_and_scripts_size);
+            let effective_values = uo
+                .iter()
+                .map(|o| o.wallet_output.value.as_u64().saturating_sub(input_fee.as_u64()))
+                .collect::<Vec<_>>();
+            if let Some(indexes) =
+                select_changeless(&effective_values, (amount + base_fee).as_u64(), change_fee.as_u64())
+            {
+                let utxos = indexes.into_iter().map(|i| uo[i].clone()).collect::<Vec<_>>();
+                let total_value = utxos.iter().map(|o| o.wallet_output.value).sum::<MicroMinotari>();
+                let fee_with_change = fee_calc.calculate(
+                    fee_per_gram,
+                    1,
+                    utxos.len(),
+                    num_outputs + 1,
+                    total_output_features_and_scripts_byte_size + default_features_and_scripts_size,
+                );
+                trace!(
+                    target: LOG_TARGET,
+                    "select_utxos profile - changeless selection: {} outputs from {}, {} ms (at {} ms)",
+                    utxos.len(),
+                    uo_len,
+                    start_new.elapsed().as_millis(),
+                    start.elapsed().as_millis(),
+                );
+                // Any excess over the fee is less than the cost of a change output and goes to the fee
+                return Ok(UtxoSelection {
+                    utxos,
+                    requires_change_output: false,
+                    total_value,
+                    fee_without_change: total_value - amount,
+                    fee_with_change,
+                });
+            }
+            debug!(
+                target: LOG_TARGET,
+                "select_utxos found no changeless selection, falling back to the default ordering"
+            );
+        }
+
+        let uo = if selection_criteria.ordering == UtxoSelectionOrdering::Privacy {
+            let mut groups: HashMap<PrivacyGroup, Vec<DbWalletOutput>> = HashMap::new();
+            let mut funding_inputs = HashMap::new();
+            for o in uo {
+                let group = self.privacy_group(&o, &mut funding_inputs)?;
+                groups.entry(group).or_default().push(o);
+            }
+            let num_groups = groups.len();
+            let group = groups
+                .into_values()
+                .map(|group| {
+                    let total = group.iter().map(|o| o.wallet_output.value).sum::<MicroMinotari>();
+                    (total, group)
+                })
+                .filter(|(total, group)| {
+                    let fee_without_change = fee_calc.calculate(
+                        fee_per_gram,
+                        1,
+                        group.len(),
+                        num_outputs,
+                        total_output_features_and_scripts_byte_size,
+                    );
+                    let fee_with_change = fee_calc.calculate(
+                        fee_per_gram,
+                        1,
+                        group.len(),
+                        num_outputs + 1,
+                        total_output_features_and_scripts_byte_size + default_features_and_scripts_size,
+                    );
+                    *total == amount + fee_without_change || *total > amount + fee_with_change
+                })
+                .min_by_key(|(total, _)| *total)
+                .map(|(_, group)| group);
+            match group {
+                Some(group) => {
+                    trace!(
+                        target: LOG_TARGET,
+                        "select_utxos selecting from a privacy group of {} outputs ({} groups)",
+                        group.len(),
+                        num_groups
+                    );
+                    group
+                },
+                None => {
+                    return Err(OutputManagerError::NoUtxosSelected {
+                        criteria: selection_criteria,
+                    })
+                },
+            }
+        } else {
+            uo
+        };
+
         let mut requires_change_output = false;
         let mut utxos_total_value = MicroMinotari::from(0);
         let mut fee_without_change = MicroMinotari::from(0);
         let mut fee_with_change = MicroMinotari::from(0);
-        for o in uo {
+        let mut candidates = uo.into_iter();
+        for o in candidates.by_ref() {
             utxos_total_value += o.wallet_output.value;
 
             trace!(target: LOG_TARGET, "-- utxos_total_value = {:?}", utxos_total_value);
@@ -2080,6 +2661,35 @@ where
             }
         }
 
+        // Sweep the remaining economical outputs into the change output
+        if requires_change_output && selection_criteria.ordering == UtxoSelectionOrdering::Consolidation {
+            let input_fee = fee_calc.calculate(fee_per_gram, 0, 1, 0, 0);
+            for o in candidates {
+                if utxos.len() >= CONSOLIDATION_INPUTS_LIMIT {
+                    break;
+                }
+                if o.wallet_output.value <= input_fee {
+                    continue;
+                }
+                utxos_total_value += o.wallet_output.value;
+                utxos.push(o);
+            }
+            fee_without_change = fee_calc.calculate(
+                fee_per_gram,
+                1,
+                utxos.len(),
+                num_outputs,
+                total_output_features_and_scripts_byte_size,
+            );
+            fee_with_change = fee_calc.calculate(
+                fee_per_gram,
+                1,
+                utxos.len(),
+                num_outputs + 1,
+                total_output_features_and_scripts_byte_size + default_features_and_scripts_size,
+            );
+        }
+
         let perfect_utxo_selection = utxos_total_value == amount + fee_without_change;
         let enough_spendable = utxos_total_value > amount + fee_with_change;
         trace!(
@@ -2117,6 +2727,41 @@ where
         })
     }
 
+    /// Returns the privacy group of an output. Change, and any other output created by a transaction that spent this
+    /// wallet's funds, belongs to the group of that transaction's inputs, so that it is only ever spent with funds
+    /// from the same sender. `funding_inputs` caches the input that is followed for each transaction.
+    fn privacy_group(
+        &self,
+        output: &DbWalletOutput,
+        funding_inputs: &mut HashMap<TxId, Option<DbWalletOutput>>,
+    ) -> Result<PrivacyGroup, OutputManagerError> {
+        let mut origin = output.clone();
+        for _ in 0..PRIVACY_GROUP_MAX_DEPTH {
+            let Some(tx_id) = origin.received_in_tx_id else {
+                break;
+            };
+            let input = match funding_inputs.get(&tx_id) {
+                Some(input) => input.clone(),
+                None => {
+                    let input = self
+                        .resources
+                        .db
+                        .fetch_outputs_by_tx_id(tx_id)?
+                        .into_iter()
+                        .filter(|o| o.spent_in_tx_id == Some(tx_id))
+                        .min_by(|a, b| a.commitment.as_bytes().cmp(b.commitment.as_bytes()));
+                    funding_inputs.insert(tx_id, input.clone());
+                    input
+                },
+            };
+            match input {
+                Some(input) => origin = input,
+                None => break,
+            }
+        }
+        Ok(PrivacyGroup::from(&origin))
+    }
+
     pub fn fetch_spent_outputs(&self) -> Result<Vec<DbWalletOutput>, OutputManagerError> {
         Ok(self.resources.db.fetch_spent_outputs()?)
     }
@@ -2675,6 +3320,139 @@ where
         Ok((output, sender_offset.key_id))
     }
 
+    /// Runs a single round of automatic UTXO consolidation. A created consolidation transaction is submitted to the
+    /// transaction service and the outcome is reported on the event stream. If the submission fails, the transaction
+    /// id is sent on `consolidation_failed` so that its inputs can be released.
+    async fn run_auto_consolidation(&mut self, consolidation_failed: &mpsc::UnboundedSender<TxId>) {
+        let (tx_id, transaction, num_inputs, value) = match self.create_auto_consolidation().await {
+            Ok(Some(consolidation)) => consolidation,
+            Ok(None) => return,
+            Err(e) => {
+                warn!(target: LOG_TARGET, "Automatic UTXO consolidation failed: {}", e);
+                publish_event(
+                    &self.resources.event_publisher,
+                    OutputManagerEvent::UtxoConsolidationFailed(e.to_string()),
+                );
+                return;
+            },
+        };
+        info!(
+            target: LOG_TARGET,
+            "Created automatic UTXO consolidation transaction (TxId: {}) joining {} outputs worth {}",
+            tx_id,
+            num_inputs,
+            value
+        );
+
+        // The transaction service may itself be waiting on a reply from this service, so the transaction is submitted
+        // from a separate task
+        let mut transaction_service = self.transaction_service.clone();
+        let event_publisher = self.resources.event_publisher.clone();
+        let consolidation_failed = consolidation_failed.clone();
+        tokio::spawn(async move {
+            let event = match transaction_service
+                .submit_transaction(tx_id, transaction, value, "Automatic UTXO consolidation".to_string())
+                .await
+            {
+                Ok(()) => OutputManagerEvent::UtxoConsolidationCreated {
+                    tx_id,
+                    num_inputs,
+                    value,
+                },
+                Err(e) => {
+                    error!(
+                        target: LOG_TARGET,
+                        "Failed to submit automatic UTXO consolidation transaction (TxId: {}): {}", tx_id, e
+                    );
+                    let _result = consolidation_failed.send(tx_id);
+                    OutputManagerEvent::UtxoConsolidationFailed(e.to_string())
+                },
+            };
+            publish_event(&event_publisher, event);
+        });
+    }
+
+    /// Creates a coin join of the wallet's small outputs if the consolidation policy in the config calls for it.
+    /// Returns `None` when there are too few outputs to bother or when the mempool fees are above the configured
+    /// threshold.
+    async fn create_auto_consolidation(
+        &mut self,
+    ) -> Result<Option<(TxId, Transaction, usize, MicroMinotari)>, OutputManagerError> {
+        let max_utxo_count = self.resources.config.auto_consolidation_max_utxo_count;
+        let min_output_value = MicroMinotari::from(self.resources.config.auto_consolidation_min_output_value);
+        let max_fee_per_gram = MicroMinotari::from(self.resources.config.auto_consolidation_max_fee_per_gram);
+
+        let num_unspent = self.resources.db.fetch_all_unspent_outputs()?.len();
+        if num_unspent <= max_utxo_count {
+            trace!(
+                target: LOG_TARGET,
+                "Skipping UTXO consolidation, {} unspent outputs is within the limit of {}",
+                num_unspent,
+                max_utxo_count
+            );
+            return Ok(None);
+        }
+
+        let tip_height = self
+            .base_node_service
+            .get_chain_metadata()
+            .await?
+            .map(|m| m.best_block_height());
+        let commitments = self
+            .resources
+            .db
+            .fetch_unspent_outputs_for_spending(
+                &UtxoSelectionCriteria::smallest_first(self.resources.config.dust_ignore_value),
+                MicroMinotari::zero(),
+                tip_height,
+            )?
+            .into_iter()
+            .filter(|o| o.wallet_output.value < min_output_value)
+            .take(CONSOLIDATION_INPUTS_LIMIT)
+            .map(|o| o.commitment)
+            .collect::<Vec<_>>();
+        if commitments.len() < 2 {
+            debug!(
+                target: LOG_TARGET,
+                "Skipping UTXO consolidation, only {} outputs are below {}",
+                commitments.len(),
+                min_output_value
+            );
+            return Ok(None);
+        }
+
+        let stats = self
+            .resources
+            .connectivity
+            .obtain_base_node_wallet_rpc_client()
+            .await
+            .ok_or_else(|| {
+                OutputManagerError::InvalidResponseError("Could not connect to base node rpc client".to_string())
+            })?
+            .get_mempool_fee_per_gram_stats(GetMempoolFeePerGramStatsRequest { count: 1 })
+            .await?
+            .stats;
+        // An empty mempool means the minimum fee will make it into the next block
+        let fee_per_gram = stats
+            .first()
+            .map(|s| MicroMinotari::from(s.min_fee_per_gram))
+            .unwrap_or_else(|| MicroMinotari::from(1))
+            .max(MicroMinotari::from(1));
+        if fee_per_gram > max_fee_per_gram {
+            debug!(
+                target: LOG_TARGET,
+                "Skipping UTXO consolidation, mempool fee per gram {} is above the threshold of {}",
+                fee_per_gram,
+                max_fee_per_gram
+            );
+            return Ok(None);
+        }
+
+        let num_inputs = commitments.len();
+        let (tx_id, transaction, value) = self.create_coin_join(commitments, fee_per_gram).await?;
+        Ok(Some((tx_id, transaction, num_inputs, value)))
+    }
+
     #[allow(clippy::too_many_lines)]
     pub async fn create_coin_join(
         &mut self,
@@ -3019,15 +3797,153 @@ where
         Ok(())
     }
 
-    // Scanning outputs addressed to this wallet
-    #[allow(clippy::too_many_lines)]
-    async fn scan_outputs_for_one_sided_payments(
+    /// Creates a multisig account from the cosigner keys, recording the index of this wallet's own cosigner key on the
+    /// multisig key branch so that it can sign for the account later.
+    async fn create_multisig_account(
+        &mut self,
+        name: String,
+        threshold: u8,
+        public_keys: Vec<PublicKey>,
+        own_public_key: PublicKey,
+    ) -> Result<MultisigAccount, OutputManagerError> {
+        if !public_keys.contains(&own_public_key) {
+            return Err(OutputManagerError::InvalidMultisigAccount(
+                "The cosigner keys must include this wallet's own key".to_string(),
+            ));
+        }
+        let key_index = self
+            .resources
+            .key_manager
+            .find_key_index(TransactionKeyManagerBranch::Multisig.get_branch_key(), &own_public_key)
+            .await
+            .map_err(|_| {
+                OutputManagerError::InvalidMultisigAccount(format!(
+                    "Key {} was not derived by this wallet",
+                    own_public_key.to_hex()
+                ))
+            })?;
+        let account = MultisigAccount::new(name, threshold, public_keys, key_index)?;
+        self.resources.db.add_multisig_account(&account)?;
+        info!(
+            target: LOG_TARGET,
+            "Created {}-of-{} multisig account '{}'",
+            account.threshold,
+            account.public_keys.len(),
+            account.name
+        );
+        Ok(account)
+    }
+
+    /// Tracks the outputs locked to one of this wallet's multisig accounts. These outputs cannot be spent by this
+    /// wallet alone, so they are kept apart from its own outputs and do not count towards its balance.
+    fn scan_outputs_for_multisig_accounts(
         &mut self,
         outputs: Vec<TransactionOutput>,
-    ) -> Result<Vec<RecoveredOutput>, OutputManagerError> {
+        mined_height: u64,
+    ) -> Result<Vec<MultisigAccountOutput>, OutputManagerError> {
+        let accounts = self.resources.db.fetch_multisig_accounts()?;
+        if accounts.is_empty() {
+            return Ok(Vec::new());
+        }
+        let account_scripts = accounts
+            .iter()
+            .map(|account| (account, account.script()))
+            .collect::<Vec<_>>();
+
+        let mut found_outputs = Vec::new();
+        for output in outputs {
+            let account = match account_scripts.iter().find(|(_, script)| script == &output.script) {
+                Some((account, _)) => account,
+                None => continue,
+            };
+            let encryption_key = account.encryption_key()?;
+            let value = match EncryptedData::decrypt_data(&encryption_key, &output.commitment, &output.encrypted_data) {
+                Ok((value, commitment_mask, _)) => {
+                    if !output.verify_mask(&self.resources.factories.range_proof, &commitment_mask, value.as_u64())? {
+                        warn!(
+                            target: LOG_TARGET,
+                            "Output {} of multisig account '{}' has an invalid commitment mask",
+                            output.hash(),
+                            account.name
+                        );
+                        continue;
+                    }
+                    value
+                },
+                Err(e) => {
+                    warn!(
+                        target: LOG_TARGET,
+                        "Could not decrypt output {} of multisig account '{}': {}",
+                        output.hash(),
+                        account.name,
+                        e
+                    );
+                    continue;
+                },
+            };
+            let account_output = MultisigAccountOutput {
+                account_name: account.name.clone(),
+                output_hash: output.hash(),
+                commitment: output.commitment.clone(),
+                value,
+                mined_height,
+                spent_in_tx_id: None,
+                spent_at_height: None,
+            };
+            if self.resources.db.add_multisig_account_output(&account_output)? {
+                found_outputs.push(account_output);
+            }
+        }
+        Ok(found_outputs)
+    }
+
+    /// Derives the next sub-address on the sub-address key branch
+    async fn create_sub_address(&mut self, label: String) -> Result<SubAddress, OutputManagerError> {
+        let key = self
+            .resources
+            .key_manager
+            .get_next_key(TransactionKeyManagerBranch::SubAddress.get_branch_key())
+            .await?;
+        let index = key
+            .key_id
+            .managed_index()
+            .ok_or_else(|| OutputManagerError::ServiceError("Sub-address key is not a managed key".to_string()))?;
+        let sub_address = register_sub_address(
+            &self.resources.db,
+            &self.resources.key_manager,
+            self.resources.one_sided_tari_address.network(),
+            index,
+            label,
+            key.pub_key,
+        )
+        .await?;
+        info!(
+            target: LOG_TARGET,
+            "Created sub-address {} '{}': {}", sub_address.index, sub_address.label, sub_address.address
+        );
+        Ok(sub_address)
+    }
+
+    fn get_sub_address_outputs(&self, index: u64) -> Result<Vec<DbWalletOutput>, OutputManagerError> {
+        let sub_address = self
+            .resources
+            .db
+            .fetch_sub_addresses()?
+            .into_iter()
+            .find(|s| s.index == index)
+            .ok_or(OutputManagerError::SubAddressNotFound(index))?;
+        Ok(self.resources.db.fetch_outputs_by_script(&sub_address.script())?)
+    }
+
+    /// Returns the script public keys that one-sided payments to this wallet are locked to, excluding those of
+    /// sub-addresses
+    async fn fetch_one_sided_script_keys(&self) -> Result<Vec<(PublicKey, TariKeyId)>, OutputManagerError> {
         let mut known_keys = Vec::new();
         let known_scripts = self.resources.db.get_all_known_one_sided_payment_scripts()?;
         for known_script in known_scripts {
+            if is_sub_address_key_id(&known_script.script_key_id) {
+                continue;
+            }
             known_keys.push((
                 self.resources
                     .key_manager
@@ -3036,6 +3952,43 @@ where
                 known_script.script_key_id.clone(),
             ));
         }
+        Ok(known_keys)
+    }
+
+    /// Returns the block filter items of the outputs that the wallet can detect without trial decryption
+    async fn get_block_filter_items(&self) -> Result<Vec<Vec<u8>>, OutputManagerError> {
+        let mut items = self
+            .fetch_one_sided_script_keys()
+            .await?
+            .iter()
+            .map(|(public_key, _)| public_key.as_bytes().to_vec())
+            .collect::<Vec<_>>();
+        // Sub-addresses past the last registered one are included, as they may have been created before the wallet
+        // was restored from its seed
+        items.extend(
+            fetch_sub_address_scan_keys(&self.resources.db, &self.resources.key_manager)
+                .await?
+                .iter()
+                .map(|key| key.public_key.as_bytes().to_vec()),
+        );
+        items.extend(
+            self.resources
+                .db
+                .fetch_multisig_accounts()?
+                .iter()
+                .map(|account| BlockFilter::script_item(&account.script())),
+        );
+        Ok(items)
+    }
+
+    // Scanning outputs addressed to this wallet
+    #[allow(clippy::too_many_lines)]
+    async fn scan_outputs_for_one_sided_payments(
+        &mut self,
+        outputs: Vec<TransactionOutput>,
+    ) -> Result<Vec<RecoveredOutput>, OutputManagerError> {
+        // Payments to sub-addresses are recovered by the `StandardUtxoRecoverer`
+        let known_keys = self.fetch_one_sided_script_keys().await?;
 
         let view_key = self.resources.key_manager.get_view_key().await?;
 
@@ -3215,14 +4168,17 @@ where
 fn get_multi_sig_script_components(
     script: &TariScript,
     tx_id: TxId,
-) -> Result<(Vec<PublicKey>, u8), OutputManagerError> {
-    if let Some(Opcode::CheckMultiSigVerifyAggregatePubKey(m, _n, keys, _msg)) = script.as_slice().get(3) {
-        Ok((keys.clone(), *m))
-    } else {
-        Err(OutputManagerError::ServiceError(format!(
-            "Invalid script (TxId: {})",
-            tx_id
-        )))
+) -> Result<(Vec<PublicKey>, u8, Message), OutputManagerError> {
+    multisig_script_components(script)
+        .ok_or_else(|| OutputManagerError::ServiceError(format!("Invalid script (TxId: {})", tx_id)))
+}
+
+fn publish_event(event_publisher: &OutputManagerEventSender, event: OutputManagerEvent) {
+    if let Err(e) = event_publisher.send(Arc::new(event)) {
+        debug!(
+            target: LOG_TARGET,
+            "Error sending event because there are no subscribers: {:?}", e
+        );
     }
 }
 
@@ -6,16 +6,19 @@ use tari_common_types::{
     types::{Commitment, FixedHash},
 };
 use tari_core::transactions::transaction_components::{OutputType, TransactionOutput};
+use tari_script::TariScript;
 
 use crate::output_manager_service::{
     error::OutputManagerStorageError,
     input_selection::UtxoSelectionCriteria,
+    multisig_account::{MultisigAccount, MultisigAccountOutput},
     service::Balance,
     storage::{
         database::{DbKey, DbValue, OutputBackendQuery, WriteOperation},
         models::DbWalletOutput,
         sqlite_db::{ReceivedOutputInfoForBatch, SpentOutputInfoForBatch},
     },
+    sub_address::SubAddress,
 };
 
 /// This trait defines the required behaviour that a storage backend must provide for the Output Manager service.
@@ -76,6 +79,16 @@ pub trait OutputManagerBackend: Send + Sync + Clone {
     /// If an invalid output is found to be valid this function will turn it back into an unspent output
     fn revalidate_unspent_output(&self, spending_key: &Commitment) -> Result<(), OutputManagerStorageError>;
 
+    /// Set or clear the user label and note attached to an output
+    fn set_output_label(
+        &self,
+        commitment: &Commitment,
+        label: Option<String>,
+        note: Option<String>,
+    ) -> Result<(), OutputManagerStorageError>;
+    /// Freeze or unfreeze an output. Frozen outputs are never selected as transaction inputs.
+    fn set_output_frozen(&self, commitment: &Commitment, frozen: bool) -> Result<(), OutputManagerStorageError>;
+
     /// Get the output that was most recently mined, ordered descending by mined height
     fn get_last_mined_output(&self) -> Result<Option<DbWalletOutput>, OutputManagerStorageError>;
     /// Get the output that was most recently spent, ordered descending by mined height
@@ -94,4 +107,36 @@ pub trait OutputManagerBackend: Send + Sync + Clone {
     ) -> Result<Vec<DbWalletOutput>, OutputManagerStorageError>;
     fn fetch_outputs_by_tx_id(&self, tx_id: TxId) -> Result<Vec<DbWalletOutput>, OutputManagerStorageError>;
     fn fetch_outputs_by_query(&self, q: OutputBackendQuery) -> Result<Vec<DbWalletOutput>, OutputManagerStorageError>;
+    /// Store a multisig account, failing if an account with the same name already exists
+    fn add_multisig_account(&self, account: &MultisigAccount) -> Result<(), OutputManagerStorageError>;
+    fn fetch_multisig_accounts(&self) -> Result<Vec<MultisigAccount>, OutputManagerStorageError>;
+    /// Start tracking an output locked to a multisig account. Returns false if the output was already tracked.
+    fn add_multisig_account_output(&self, output: &MultisigAccountOutput) -> Result<bool, OutputManagerStorageError>;
+    fn fetch_multisig_account_outputs(
+        &self,
+        account_name: &str,
+    ) -> Result<Vec<MultisigAccountOutput>, OutputManagerStorageError>;
+    fn mark_multisig_account_output_spent(
+        &self,
+        output_hash: &FixedHash,
+        tx_id: TxId,
+    ) -> Result<(), OutputManagerStorageError>;
+    /// Forget that this wallet led the spend of the multisig account outputs spent in the transaction, e.g. when the
+    /// transaction is cancelled. Returns the number of outputs that were released.
+    fn clear_multisig_account_outputs_spent_in_tx(&self, tx_id: TxId) -> Result<usize, OutputManagerStorageError>;
+    /// Fetch the outputs of all multisig accounts whose spend has not been seen on chain
+    fn fetch_multisig_account_outputs_not_spent_on_chain(
+        &self,
+    ) -> Result<Vec<MultisigAccountOutput>, OutputManagerStorageError>;
+    /// Record the height at which a multisig account output was spent on chain
+    fn mark_multisig_account_output_spent_at_height(
+        &self,
+        output_hash: &FixedHash,
+        height: u64,
+    ) -> Result<(), OutputManagerStorageError>;
+    /// Store a sub-address. Sub-addresses are keyed by their index on the sub-address key branch.
+    fn add_sub_address(&self, sub_address: &SubAddress) -> Result<(), OutputManagerStorageError>;
+    fn fetch_sub_addresses(&self) -> Result<Vec<SubAddress>, OutputManagerStorageError>;
+    /// Fetch all outputs, spent or unspent, that are locked to the given script
+    fn fetch_outputs_by_script(&self, script: &TariScript) -> Result<Vec<DbWalletOutput>, OutputManagerStorageError>;
 }
@@ -36,17 +36,20 @@ use tari_core::transactions::{
     tari_amount::MicroMinotari,
     transaction_components::{OutputType, TransactionOutput},
 };
+use tari_script::TariScript;
 use tari_utilities::hex::Hex;
 
 use crate::output_manager_service::{
     error::OutputManagerStorageError,
     input_selection::UtxoSelectionCriteria,
+    multisig_account::{MultisigAccount, MultisigAccountOutput},
     service::Balance,
     storage::{
         models::{DbWalletOutput, KnownOneSidedPaymentScript},
         sqlite_db::{ReceivedOutputInfoForBatch, SpentOutputInfoForBatch},
         OutputStatus,
     },
+    sub_address::SubAddress,
 };
 
 const LOG_TARGET: &str = "wallet::output_manager_service::database";
@@ -317,10 +320,87 @@ where T: OutputManagerBackend + 'static
         self.db.revalidate_unspent_output(&commitment)
     }
 
+    pub fn set_output_label(
+        &self,
+        commitment: Commitment,
+        label: Option<String>,
+        note: Option<String>,
+    ) -> Result<(), OutputManagerStorageError> {
+        self.db.set_output_label(&commitment, label, note)
+    }
+
+    pub fn set_output_frozen(&self, commitment: Commitment, frozen: bool) -> Result<(), OutputManagerStorageError> {
+        self.db.set_output_frozen(&commitment, frozen)
+    }
+
     pub fn reinstate_cancelled_inbound_output(&self, tx_id: TxId) -> Result<(), OutputManagerStorageError> {
         self.db.reinstate_cancelled_inbound_output(tx_id)
     }
 
+    pub fn add_multisig_account(&self, account: &MultisigAccount) -> Result<(), OutputManagerStorageError> {
+        self.db.add_multisig_account(account)
+    }
+
+    pub fn fetch_multisig_accounts(&self) -> Result<Vec<MultisigAccount>, OutputManagerStorageError> {
+        self.db.fetch_multisig_accounts()
+    }
+
+    pub fn add_multisig_account_output(
+        &self,
+        output: &MultisigAccountOutput,
+    ) -> Result<bool, OutputManagerStorageError> {
+        self.db.add_multisig_account_output(output)
+    }
+
+    pub fn fetch_multisig_account_outputs(
+        &self,
+        account_name: &str,
+    ) -> Result<Vec<MultisigAccountOutput>, OutputManagerStorageError> {
+        self.db.fetch_multisig_account_outputs(account_name)
+    }
+
+    pub fn mark_multisig_account_output_spent(
+        &self,
+        output_hash: &FixedHash,
+        tx_id: TxId,
+    ) -> Result<(), OutputManagerStorageError> {
+        self.db.mark_multisig_account_output_spent(output_hash, tx_id)
+    }
+
+    pub fn clear_multisig_account_outputs_spent_in_tx(&self, tx_id: TxId) -> Result<usize, OutputManagerStorageError> {
+        self.db.clear_multisig_account_outputs_spent_in_tx(tx_id)
+    }
+
+    pub fn fetch_multisig_account_outputs_not_spent_on_chain(
+        &self,
+    ) -> Result<Vec<MultisigAccountOutput>, OutputManagerStorageError> {
+        self.db.fetch_multisig_account_outputs_not_spent_on_chain()
+    }
+
+    pub fn mark_multisig_account_output_spent_at_height(
+        &self,
+        output_hash: &FixedHash,
+        height: u64,
+    ) -> Result<(), OutputManagerStorageError> {
+        self.db
+            .mark_multisig_account_output_spent_at_height(output_hash, height)
+    }
+
+    pub fn add_sub_address(&self, sub_address: &SubAddress) -> Result<(), OutputManagerStorageError> {
+        self.db.add_sub_address(sub_address)
+    }
+
+    pub fn fetch_sub_addresses(&self) -> Result<Vec<SubAddress>, OutputManagerStorageError> {
+        self.db.fetch_sub_addresses()
+    }
+
+    pub fn fetch_outputs_by_script(
+        &self,
+        script: &TariScript,
+    ) -> Result<Vec<DbWalletOutput>, OutputManagerStorageError> {
+        self.db.fetch_outputs_by_script(script)
+    }
+
     pub fn get_all_known_one_sided_payment_scripts(
         &self,
     ) -> Result<Vec<KnownOneSidedPaymentScript>, OutputManagerStorageError> {
@@ -30,7 +30,7 @@ use tari_common_types::{
 };
 use tari_core::transactions::{
     key_manager::{TariKeyId, TransactionKeyManagerInterface},
-    transaction_components::{encrypted_data::PaymentId, WalletOutput},
+    transaction_components::{encrypted_data::PaymentId, TransactionOutput, WalletOutput},
 };
 use tari_script::{ExecutionStack, TariScript};
 
@@ -57,6 +57,9 @@ pub struct DbWalletOutput {
     pub received_in_tx_id: Option<TxId>,
     pub spent_in_tx_id: Option<TxId>,
     pub payment_id: PaymentId,
+    pub label: Option<String>,
+    pub note: Option<String>,
+    pub frozen: bool,
 }
 
 impl DbWalletOutput {
@@ -85,8 +88,28 @@ impl DbWalletOutput {
             received_in_tx_id,
             spent_in_tx_id,
             payment_id,
+            label: None,
+            note: None,
+            frozen: false,
         })
     }
+
+    /// The output as it appears on the blockchain, built from the stored commitment so that no keys are needed
+    pub fn to_transaction_output(&self) -> TransactionOutput {
+        let output = &self.wallet_output;
+        TransactionOutput::new(
+            output.version,
+            output.features.clone(),
+            self.commitment.clone(),
+            output.range_proof.clone(),
+            output.script.clone(),
+            output.sender_offset_public_key.clone(),
+            output.metadata_signature.clone(),
+            output.covenant.clone(),
+            output.encrypted_data.clone(),
+            output.minimum_value_promise,
+        )
+    }
 }
 
 impl From<DbWalletOutput> for WalletOutput {
@@ -31,8 +31,10 @@ use diesel::{
     result::Error as DieselError,
 };
 use log::*;
+use multisig_account_sql::{MultisigAccountOutputSql, MultisigAccountSql};
 pub use new_output_sql::NewOutputSql;
 pub use output_sql::OutputSql;
+use sub_address_sql::SubAddressSql;
 use tari_common_sqlite::{sqlite_connection_pool::PooledDbConnection, util::diesel_ext::ExpectedRowsExtension};
 use tari_common_types::{
     transaction::TxId,
@@ -49,20 +51,24 @@ use tokio::time::Instant;
 use crate::{
     output_manager_service::{
         error::OutputManagerStorageError,
+        multisig_account::{MultisigAccount, MultisigAccountOutput},
         service::Balance,
         storage::{
             database::{DbKey, DbKeyValuePair, DbValue, OutputBackendQuery, OutputManagerBackend, WriteOperation},
             models::{DbWalletOutput, KnownOneSidedPaymentScript},
             OutputStatus,
         },
+        sub_address::SubAddress,
         UtxoSelectionCriteria,
     },
     schema::{known_one_sided_payment_scripts, outputs},
     storage::sqlite_utilities::wallet_db_connection::WalletDbConnection,
 };
 
+mod multisig_account_sql;
 mod new_output_sql;
 mod output_sql;
+mod sub_address_sql;
 const LOG_TARGET: &str = "wallet::output_manager_service::database::wallet";
 
 /// A Sqlite backend for the Output Manager Service. The Backend is accessed via a connection pool to the Sqlite file.
@@ -1099,6 +1105,62 @@ impl OutputManagerBackend for OutputManagerSqliteDatabase {
         Ok(())
     }
 
+    fn set_output_label(
+        &self,
+        commitment: &Commitment,
+        label: Option<String>,
+        note: Option<String>,
+    ) -> Result<(), OutputManagerStorageError> {
+        let start = Instant::now();
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        let acquire_lock = start.elapsed();
+
+        let output = OutputSql::find_by_commitment(&commitment.to_vec(), &mut conn)?;
+        output.update(
+            UpdateOutput {
+                label: Some(label),
+                note: Some(note),
+                ..Default::default()
+            },
+            &mut conn,
+        )?;
+        if start.elapsed().as_millis() > 0 {
+            trace!(
+                target: LOG_TARGET,
+                "sqlite profile - set_output_label: lock {} + db_op {} = {} ms",
+                acquire_lock.as_millis(),
+                (start.elapsed() - acquire_lock).as_millis(),
+                start.elapsed().as_millis()
+            );
+        }
+        Ok(())
+    }
+
+    fn set_output_frozen(&self, commitment: &Commitment, frozen: bool) -> Result<(), OutputManagerStorageError> {
+        let start = Instant::now();
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        let acquire_lock = start.elapsed();
+
+        let output = OutputSql::find_by_commitment(&commitment.to_vec(), &mut conn)?;
+        output.update(
+            UpdateOutput {
+                frozen: Some(frozen),
+                ..Default::default()
+            },
+            &mut conn,
+        )?;
+        if start.elapsed().as_millis() > 0 {
+            trace!(
+                target: LOG_TARGET,
+                "sqlite profile - set_output_frozen: lock {} + db_op {} = {} ms",
+                acquire_lock.as_millis(),
+                (start.elapsed() - acquire_lock).as_millis(),
+                start.elapsed().as_millis()
+            );
+        }
+        Ok(())
+    }
+
     fn reinstate_cancelled_inbound_output(&self, tx_id: TxId) -> Result<(), OutputManagerStorageError> {
         let start = Instant::now();
         let mut conn = self.database_connection.get_pooled_connection()?;
@@ -1199,6 +1261,94 @@ impl OutputManagerBackend for OutputManagerSqliteDatabase {
             })
             .collect())
     }
+
+    fn add_multisig_account(&self, account: &MultisigAccount) -> Result<(), OutputManagerStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        if MultisigAccountSql::find(&account.name, &mut conn)?.is_some() {
+            return Err(OutputManagerStorageError::DuplicateMultisigAccount);
+        }
+        MultisigAccountSql::new(account).commit(&mut conn)
+    }
+
+    fn fetch_multisig_accounts(&self) -> Result<Vec<MultisigAccount>, OutputManagerStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        MultisigAccountSql::index(&mut conn)?
+            .into_iter()
+            .map(MultisigAccount::try_from)
+            .collect()
+    }
+
+    fn add_multisig_account_output(&self, output: &MultisigAccountOutput) -> Result<bool, OutputManagerStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        MultisigAccountOutputSql::from(output).commit_if_new(&mut conn)
+    }
+
+    fn fetch_multisig_account_outputs(
+        &self,
+        account_name: &str,
+    ) -> Result<Vec<MultisigAccountOutput>, OutputManagerStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        MultisigAccountOutputSql::index_by_account(account_name, &mut conn)?
+            .into_iter()
+            .map(MultisigAccountOutput::try_from)
+            .collect()
+    }
+
+    fn mark_multisig_account_output_spent(
+        &self,
+        output_hash: &FixedHash,
+        tx_id: TxId,
+    ) -> Result<(), OutputManagerStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        MultisigAccountOutputSql::mark_spent(output_hash.as_slice(), tx_id, &mut conn)
+    }
+
+    fn clear_multisig_account_outputs_spent_in_tx(&self, tx_id: TxId) -> Result<usize, OutputManagerStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        MultisigAccountOutputSql::clear_spent_in_tx(tx_id, &mut conn)
+    }
+
+    fn fetch_multisig_account_outputs_not_spent_on_chain(
+        &self,
+    ) -> Result<Vec<MultisigAccountOutput>, OutputManagerStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        MultisigAccountOutputSql::index_not_spent_on_chain(&mut conn)?
+            .into_iter()
+            .map(MultisigAccountOutput::try_from)
+            .collect()
+    }
+
+    fn mark_multisig_account_output_spent_at_height(
+        &self,
+        output_hash: &FixedHash,
+        height: u64,
+    ) -> Result<(), OutputManagerStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        MultisigAccountOutputSql::mark_spent_at_height(output_hash.as_slice(), height, &mut conn)
+    }
+
+    fn add_sub_address(&self, sub_address: &SubAddress) -> Result<(), OutputManagerStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        SubAddressSql::new(sub_address).commit(&mut conn)
+    }
+
+    fn fetch_sub_addresses(&self) -> Result<Vec<SubAddress>, OutputManagerStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        SubAddressSql::index(&mut conn)?
+            .into_iter()
+            .map(SubAddress::try_from)
+            .collect()
+    }
+
+    fn fetch_outputs_by_script(&self, script: &TariScript) -> Result<Vec<DbWalletOutput>, OutputManagerStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        let outputs = OutputSql::find_by_script(&script.to_bytes(), &mut conn)?;
+
+        outputs
+            .into_iter()
+            .map(|o| o.to_db_wallet_output())
+            .collect::<Result<Vec<_>, _>>()
+    }
 }
 
 /// These are the fields to be set for the received outputs batch mode update
@@ -1264,6 +1414,9 @@ pub struct UpdateOutput {
     mined_height: Option<Option<u64>>,
     mined_in_block: Option<Option<Vec<u8>>>,
     last_validation_timestamp: Option<Option<NaiveDateTime>>,
+    label: Option<Option<String>>,
+    note: Option<Option<String>>,
+    frozen: Option<bool>,
 }
 
 #[derive(AsChangeset)]
@@ -1281,6 +1434,9 @@ pub struct UpdateOutputSql {
     mined_height: Option<Option<i64>>,
     mined_in_block: Option<Option<Vec<u8>>>,
     last_validation_timestamp: Option<Option<NaiveDateTime>>,
+    label: Option<Option<String>>,
+    note: Option<Option<String>>,
+    frozen: Option<bool>,
 }
 
 /// Map a Rust friendly UpdateOutput to the Sql data type form
@@ -1299,6 +1455,9 @@ impl From<UpdateOutput> for UpdateOutputSql {
             mined_height: u.mined_height.map(|t| t.map(|h| h as i64)),
             mined_in_block: u.mined_in_block,
             last_validation_timestamp: u.last_validation_timestamp,
+            label: u.label,
+            note: u.note,
+            frozen: u.frozen,
         }
     }
 }
@@ -0,0 +1,214 @@
+//  Copyright 2024. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::convert::TryFrom;
+
+use chrono::{NaiveDateTime, Utc};
+use diesel::{prelude::*, SqliteConnection};
+use tari_common_types::{
+    transaction::TxId,
+    types::{Commitment, FixedHash, PublicKey},
+};
+use tari_core::transactions::tari_amount::MicroMinotari;
+use tari_crypto::tari_utilities::ByteArray;
+
+use crate::{
+    output_manager_service::{
+        error::OutputManagerStorageError,
+        multisig_account::{MultisigAccount, MultisigAccountOutput},
+    },
+    schema::{multisig_account_outputs, multisig_accounts},
+};
+
+/// The cosigner keys are stored concatenated in canonical order
+const PUBLIC_KEY_LENGTH: usize = 32;
+
+#[derive(Clone, Debug, Queryable, Insertable, PartialEq)]
+#[diesel(table_name = multisig_accounts)]
+pub struct MultisigAccountSql {
+    name: String,
+    threshold: i32,
+    public_keys: Vec<u8>,
+    key_index: i64,
+    created_at: NaiveDateTime,
+}
+
+impl MultisigAccountSql {
+    pub fn new(account: &MultisigAccount) -> Self {
+        Self {
+            name: account.name.clone(),
+            threshold: i32::from(account.threshold),
+            public_keys: account.public_keys.iter().flat_map(|k| k.to_vec()).collect(),
+            key_index: account.key_index as i64,
+            created_at: Utc::now().naive_utc(),
+        }
+    }
+
+    pub fn commit(&self, conn: &mut SqliteConnection) -> Result<(), OutputManagerStorageError> {
+        diesel::insert_into(multisig_accounts::table)
+            .values(self.clone())
+            .execute(conn)?;
+        Ok(())
+    }
+
+    pub fn find(
+        name: &str,
+        conn: &mut SqliteConnection,
+    ) -> Result<Option<MultisigAccountSql>, OutputManagerStorageError> {
+        Ok(multisig_accounts::table
+            .filter(multisig_accounts::name.eq(name))
+            .first::<MultisigAccountSql>(conn)
+            .optional()?)
+    }
+
+    pub fn index(conn: &mut SqliteConnection) -> Result<Vec<MultisigAccountSql>, OutputManagerStorageError> {
+        Ok(multisig_accounts::table
+            .order(multisig_accounts::created_at.asc())
+            .load::<MultisigAccountSql>(conn)?)
+    }
+}
+
+impl TryFrom<MultisigAccountSql> for MultisigAccount {
+    type Error = OutputManagerStorageError;
+
+    fn try_from(sql: MultisigAccountSql) -> Result<Self, Self::Error> {
+        let public_keys = sql
+            .public_keys
+            .chunks(PUBLIC_KEY_LENGTH)
+            .map(PublicKey::from_canonical_bytes)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| OutputManagerStorageError::ConversionError { reason: e.to_string() })?;
+        Ok(Self {
+            name: sql.name,
+            threshold: u8::try_from(sql.threshold)
+                .map_err(|e| OutputManagerStorageError::ConversionError { reason: e.to_string() })?,
+            public_keys,
+            key_index: sql.key_index as u64,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Queryable, Insertable, PartialEq)]
+#[diesel(table_name = multisig_account_outputs)]
+pub struct MultisigAccountOutputSql {
+    output_hash: Vec<u8>,
+    account_name: String,
+    commitment: Vec<u8>,
+    value: i64,
+    mined_height: i64,
+    spent_in_tx_id: Option<i64>,
+    spent_at_height: Option<i64>,
+}
+
+impl MultisigAccountOutputSql {
+    /// Inserts the output unless it is already tracked, in which case nothing changes. Returns true if the output was
+    /// inserted.
+    pub fn commit_if_new(&self, conn: &mut SqliteConnection) -> Result<bool, OutputManagerStorageError> {
+        let num_inserted = diesel::insert_or_ignore_into(multisig_account_outputs::table)
+            .values(self.clone())
+            .execute(conn)?;
+        Ok(num_inserted > 0)
+    }
+
+    pub fn index_by_account(
+        account_name: &str,
+        conn: &mut SqliteConnection,
+    ) -> Result<Vec<MultisigAccountOutputSql>, OutputManagerStorageError> {
+        Ok(multisig_account_outputs::table
+            .filter(multisig_account_outputs::account_name.eq(account_name))
+            .order(multisig_account_outputs::mined_height.asc())
+            .load::<MultisigAccountOutputSql>(conn)?)
+    }
+
+    pub fn mark_spent(
+        output_hash: &[u8],
+        tx_id: TxId,
+        conn: &mut SqliteConnection,
+    ) -> Result<(), OutputManagerStorageError> {
+        diesel::update(multisig_account_outputs::table.filter(multisig_account_outputs::output_hash.eq(output_hash)))
+            .set(multisig_account_outputs::spent_in_tx_id.eq(tx_id.as_i64_wrapped()))
+            .execute(conn)?;
+        Ok(())
+    }
+
+    /// Clears the spending transaction of the outputs spent in `tx_id`, returning the number of outputs updated
+    pub fn clear_spent_in_tx(tx_id: TxId, conn: &mut SqliteConnection) -> Result<usize, OutputManagerStorageError> {
+        Ok(diesel::update(
+            multisig_account_outputs::table.filter(multisig_account_outputs::spent_in_tx_id.eq(tx_id.as_i64_wrapped())),
+        )
+        .set(multisig_account_outputs::spent_in_tx_id.eq::<Option<i64>>(None))
+        .execute(conn)?)
+    }
+
+    /// The outputs of all accounts whose spend has not been seen on chain yet
+    pub fn index_not_spent_on_chain(
+        conn: &mut SqliteConnection,
+    ) -> Result<Vec<MultisigAccountOutputSql>, OutputManagerStorageError> {
+        Ok(multisig_account_outputs::table
+            .filter(multisig_account_outputs::spent_at_height.is_null())
+            .order(multisig_account_outputs::mined_height.asc())
+            .load::<MultisigAccountOutputSql>(conn)?)
+    }
+
+    pub fn mark_spent_at_height(
+        output_hash: &[u8],
+        height: u64,
+        conn: &mut SqliteConnection,
+    ) -> Result<(), OutputManagerStorageError> {
+        diesel::update(multisig_account_outputs::table.filter(multisig_account_outputs::output_hash.eq(output_hash)))
+            .set(multisig_account_outputs::spent_at_height.eq(height as i64))
+            .execute(conn)?;
+        Ok(())
+    }
+}
+
+impl From<&MultisigAccountOutput> for MultisigAccountOutputSql {
+    fn from(output: &MultisigAccountOutput) -> Self {
+        Self {
+            output_hash: output.output_hash.to_vec(),
+            account_name: output.account_name.clone(),
+            commitment: output.commitment.to_vec(),
+            value: output.value.as_u64() as i64,
+            mined_height: output.mined_height as i64,
+            spent_in_tx_id: output.spent_in_tx_id.map(TxId::as_i64_wrapped),
+            spent_at_height: output.spent_at_height.map(|h| h as i64),
+        }
+    }
+}
+
+impl TryFrom<MultisigAccountOutputSql> for MultisigAccountOutput {
+    type Error = OutputManagerStorageError;
+
+    fn try_from(sql: MultisigAccountOutputSql) -> Result<Self, Self::Error> {
+        Ok(Self {
+            account_name: sql.account_name,
+            output_hash: FixedHash::try_from(sql.output_hash)
+                .map_err(|e| OutputManagerStorageError::ConversionError { reason: e.to_string() })?,
+            commitment: Commitment::from_canonical_bytes(&sql.commitment)
+                .map_err(|e| OutputManagerStorageError::ConversionError { reason: e.to_string() })?,
+            value: MicroMinotari::from(sql.value as u64),
+            mined_height: sql.mined_height as u64,
+            spent_in_tx_id: sql.spent_in_tx_id.map(|id| TxId::from(id as u64)),
+            spent_at_height: sql.spent_at_height.map(|h| h as u64),
+        })
+    }
+}
@@ -110,6 +110,9 @@ pub struct OutputSql {
     pub source: i32,
     pub last_validation_timestamp: Option<NaiveDateTime>,
     pub payment_id: Option<Vec<u8>>,
+    pub label: Option<String>,
+    pub note: Option<String>,
+    pub frozen: bool,
 }
 
 impl OutputSql {
@@ -211,6 +214,7 @@ impl OutputSql {
             .into_boxed()
             .filter(outputs::status.eq(OutputStatus::Unspent as i32))
             .filter(outputs::value.gt(i64_value))
+            .filter(outputs::frozen.eq(false))
             .order_by(outputs::spending_priority.desc());
 
         // NOTE: Safe mode presets `script_lock_height` and `maturity` filters for all queries
@@ -249,13 +253,16 @@ impl OutputSql {
         }
 
         query = match selection_criteria.ordering {
-            UtxoSelectionOrdering::SmallestFirst => query.then_order_by(outputs::value.asc()),
+            UtxoSelectionOrdering::SmallestFirst |
+            UtxoSelectionOrdering::Privacy |
+            UtxoSelectionOrdering::Consolidation => query.then_order_by(outputs::value.asc()),
             UtxoSelectionOrdering::LargestFirst => query.then_order_by(outputs::value.desc()),
-            UtxoSelectionOrdering::Default => {
+            UtxoSelectionOrdering::Default | UtxoSelectionOrdering::BranchAndBound => {
                 // NOTE: keeping filtering by `script_lock_height` and `maturity` for all modes
                 // lets get the max value for all utxos
                 let max: Option<i64> = outputs::table
                     .filter(outputs::status.eq(OutputStatus::Unspent as i32))
+                    .filter(outputs::frozen.eq(false))
                     .filter(outputs::script_lock_height.le(i64_tip_height))
                     .filter(outputs::maturity.le(i64_tip_height))
                     .order(outputs::value.desc())
@@ -388,6 +395,14 @@ impl OutputSql {
             .load(conn)?)
     }
 
+    /// Find all outputs locked to the given serialized script
+    pub fn find_by_script(
+        script: &[u8],
+        conn: &mut SqliteConnection,
+    ) -> Result<Vec<OutputSql>, OutputManagerStorageError> {
+        Ok(outputs::table.filter(outputs::script.eq(script)).load(conn)?)
+    }
+
     /// Verify that outputs with specified commitments exist in the database
     pub fn verify_outputs_exist(
         commitments: &[Commitment],
@@ -823,6 +838,9 @@ impl OutputSql {
             received_in_tx_id: self.received_in_tx_id.map(|d| (d as u64).into()),
             spent_in_tx_id: self.spent_in_tx_id.map(|d| (d as u64).into()),
             payment_id,
+            label: self.label,
+            note: self.note,
+            frozen: self.frozen,
         })
     }
 }
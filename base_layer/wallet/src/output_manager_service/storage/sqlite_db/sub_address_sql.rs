@@ -0,0 +1,78 @@
+//  Copyright 2024. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::convert::TryFrom;
+
+use chrono::{NaiveDateTime, Utc};
+use diesel::{prelude::*, SqliteConnection};
+use tari_common_types::tari_address::TariAddress;
+
+use crate::{
+    output_manager_service::{error::OutputManagerStorageError, sub_address::SubAddress},
+    schema::sub_addresses,
+};
+
+#[derive(Clone, Debug, Queryable, Insertable, PartialEq)]
+#[diesel(table_name = sub_addresses)]
+pub struct SubAddressSql {
+    key_index: i64,
+    label: String,
+    address: Vec<u8>,
+    created_at: NaiveDateTime,
+}
+
+impl SubAddressSql {
+    pub fn new(sub_address: &SubAddress) -> Self {
+        Self {
+            key_index: sub_address.index as i64,
+            label: sub_address.label.clone(),
+            address: sub_address.address.to_vec(),
+            created_at: Utc::now().naive_utc(),
+        }
+    }
+
+    pub fn commit(&self, conn: &mut SqliteConnection) -> Result<(), OutputManagerStorageError> {
+        diesel::insert_into(sub_addresses::table)
+            .values(self.clone())
+            .execute(conn)?;
+        Ok(())
+    }
+
+    pub fn index(conn: &mut SqliteConnection) -> Result<Vec<SubAddressSql>, OutputManagerStorageError> {
+        Ok(sub_addresses::table
+            .order(sub_addresses::key_index.asc())
+            .load::<SubAddressSql>(conn)?)
+    }
+}
+
+impl TryFrom<SubAddressSql> for SubAddress {
+    type Error = OutputManagerStorageError;
+
+    fn try_from(sql: SubAddressSql) -> Result<Self, Self::Error> {
+        Ok(Self {
+            index: sql.key_index as u64,
+            label: sql.label,
+            address: TariAddress::from_bytes(&sql.address)
+                .map_err(|e| OutputManagerStorageError::ConversionError { reason: e.to_string() })?,
+        })
+    }
+}
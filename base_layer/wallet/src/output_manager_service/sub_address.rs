@@ -0,0 +1,235 @@
+//  Copyright 2024. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::collections::HashSet;
+
+use blake2::Blake2b;
+use diesel::result::{DatabaseErrorKind, Error as DieselError};
+use digest::consts::U32;
+use log::*;
+use serde::{Deserialize, Serialize};
+use tari_common::configuration::Network;
+use tari_common_types::{key_branches::TransactionKeyManagerBranch, tari_address::TariAddress, types::PublicKey};
+use tari_core::transactions::key_manager::TransactionKeyManagerInterface;
+use tari_key_manager::key_manager_service::KeyId;
+use tari_script::{push_pubkey_script, ExecutionStack, TariScript};
+
+use crate::output_manager_service::{
+    error::{OutputManagerError, OutputManagerStorageError},
+    storage::{
+        database::{OutputManagerBackend, OutputManagerDatabase},
+        models::KnownOneSidedPaymentScript,
+    },
+};
+
+const LOG_TARGET: &str = "wallet::output_manager_service::sub_address";
+
+/// The number of sub-address indexes past the last known one that are checked while scanning, so that payments to
+/// sub-addresses created before a wallet was restored from its seed are still found.
+pub const SUB_ADDRESS_LOOKAHEAD: u64 = 20;
+
+/// A receiving address derived from the wallet seed. Sub-addresses share the wallet's view key, so no extra work is
+/// needed to decrypt payments to them; the spend key is the key at `index` on the sub-address key branch. One-sided
+/// payments to a sub-address are locked to that spend key, which is how they are attributed to it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SubAddress {
+    pub index: u64,
+    pub label: String,
+    pub address: TariAddress,
+}
+
+impl SubAddress {
+    pub fn key_id(&self) -> KeyId<PublicKey> {
+        sub_address_key_id(self.index)
+    }
+
+    /// The script of one-sided payments to this sub-address
+    pub fn script(&self) -> TariScript {
+        push_pubkey_script(self.address.public_spend_key())
+    }
+}
+
+pub fn sub_address_key_id(index: u64) -> KeyId<PublicKey> {
+    KeyId::Managed {
+        branch: TransactionKeyManagerBranch::SubAddress.get_branch_key(),
+        index,
+    }
+}
+
+/// Returns true if the key id is on the sub-address key branch
+pub fn is_sub_address_key_id(key_id: &KeyId<PublicKey>) -> bool {
+    matches!(
+        key_id,
+        KeyId::Managed { branch, .. } if *branch == TransactionKeyManagerBranch::SubAddress.get_branch_key()
+    )
+}
+
+/// A sub-address spend key that one-sided payments are scanned for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubAddressScanKey {
+    pub index: u64,
+    pub public_key: PublicKey,
+    /// `false` for lookahead keys of sub-addresses that the wallet does not know about yet
+    pub is_registered: bool,
+}
+
+/// Returns the unregistered sub-address indexes that are checked while scanning: every unregistered index below the
+/// highest registered one, followed by `SUB_ADDRESS_LOOKAHEAD` indexes past it. Indexes that were skipped over when a
+/// higher sub-address was recovered remain in the set until a payment to them is found.
+pub fn sub_address_lookahead_indexes(registered_indexes: &[u64]) -> Vec<u64> {
+    let registered = registered_indexes.iter().copied().collect::<HashSet<_>>();
+    let next_index = registered_indexes.iter().max().map(|index| index + 1).unwrap_or(0);
+    (0..next_index + SUB_ADDRESS_LOOKAHEAD)
+        .filter(|index| !registered.contains(index))
+        .collect()
+}
+
+/// Returns the spend keys of the registered sub-addresses, followed by those of the lookahead indexes
+pub async fn fetch_sub_address_scan_keys<TBackend, TKeyManagerInterface>(
+    db: &OutputManagerDatabase<TBackend>,
+    key_manager: &TKeyManagerInterface,
+) -> Result<Vec<SubAddressScanKey>, OutputManagerError>
+where
+    TBackend: OutputManagerBackend + 'static,
+    TKeyManagerInterface: TransactionKeyManagerInterface,
+{
+    let sub_addresses = db.fetch_sub_addresses()?;
+    let mut keys = sub_addresses
+        .iter()
+        .map(|sub_address| SubAddressScanKey {
+            index: sub_address.index,
+            public_key: sub_address.address.public_spend_key().clone(),
+            is_registered: true,
+        })
+        .collect::<Vec<_>>();
+    let registered_indexes = sub_addresses.iter().map(|s| s.index).collect::<Vec<_>>();
+    for index in sub_address_lookahead_indexes(&registered_indexes) {
+        keys.push(SubAddressScanKey {
+            index,
+            public_key: key_manager.get_public_key_at_key_id(&sub_address_key_id(index)).await?,
+            is_registered: false,
+        });
+    }
+    Ok(keys)
+}
+
+/// Stores a sub-address and persists its one-sided payment script, so that payments to it are found while scanning
+pub async fn register_sub_address<TBackend, TKeyManagerInterface>(
+    db: &OutputManagerDatabase<TBackend>,
+    key_manager: &TKeyManagerInterface,
+    network: Network,
+    index: u64,
+    label: String,
+    spend_key: PublicKey,
+) -> Result<SubAddress, OutputManagerError>
+where
+    TBackend: OutputManagerBackend + 'static,
+    TKeyManagerInterface: TransactionKeyManagerInterface,
+{
+    let view_key = key_manager.get_view_key().await?;
+    let sub_address = SubAddress {
+        index,
+        label,
+        address: TariAddress::new_sub_address(view_key.pub_key, spend_key, network),
+    };
+    let script = sub_address.script();
+    let known_script = KnownOneSidedPaymentScript {
+        script_hash: script.as_hash::<Blake2b<U32>>()?.to_vec(),
+        script_key_id: sub_address.key_id(),
+        script,
+        input: ExecutionStack::default(),
+        script_lock_height: 0,
+    };
+    // It is not a problem if the script has already been persisted
+    match db.add_known_script(known_script) {
+        Ok(_) |
+        Err(OutputManagerStorageError::DuplicateScript) |
+        Err(OutputManagerStorageError::DieselError(DieselError::DatabaseError(
+            DatabaseErrorKind::UniqueViolation,
+            _,
+        ))) => {},
+        Err(e) => return Err(e.into()),
+    }
+    db.add_sub_address(&sub_address)?;
+    Ok(sub_address)
+}
+
+/// Registers a sub-address that a payment was found for while scanning, and moves the sub-address key branch past it
+/// so that it is not handed out again
+pub async fn recover_sub_address<TBackend, TKeyManagerInterface>(
+    db: &OutputManagerDatabase<TBackend>,
+    key_manager: &TKeyManagerInterface,
+    network: Network,
+    index: u64,
+    spend_key: PublicKey,
+) -> Result<SubAddress, OutputManagerError>
+where
+    TBackend: OutputManagerBackend + 'static,
+    TKeyManagerInterface: TransactionKeyManagerInterface,
+{
+    key_manager
+        .update_current_key_index_if_higher(TransactionKeyManagerBranch::SubAddress.get_branch_key(), index)
+        .await?;
+    let sub_address = register_sub_address(
+        db,
+        key_manager,
+        network,
+        index,
+        format!("Recovered sub-address {}", index),
+        spend_key,
+    )
+    .await?;
+    info!(
+        target: LOG_TARGET,
+        "Recovered sub-address {}: {}", sub_address.index, sub_address.address
+    );
+    Ok(sub_address)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_scans_past_the_highest_registered_index() {
+        assert_eq!(
+            sub_address_lookahead_indexes(&[]),
+            (0..SUB_ADDRESS_LOOKAHEAD).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            sub_address_lookahead_indexes(&[0, 1, 2]),
+            (3..3 + SUB_ADDRESS_LOOKAHEAD).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn it_keeps_skipped_indexes_in_the_lookahead() {
+        let indexes = sub_address_lookahead_indexes(&[30, 1]);
+        assert!(indexes.contains(&0));
+        assert!(!indexes.contains(&1));
+        assert!(indexes.contains(&2));
+        assert!(indexes.contains(&29));
+        assert!(!indexes.contains(&30));
+        assert_eq!(indexes.last(), Some(&(30 + SUB_ADDRESS_LOOKAHEAD)));
+        assert_eq!(indexes.len() as u64, 31 + SUB_ADDRESS_LOOKAHEAD - 2);
+    }
+}
@@ -117,6 +117,9 @@ where
         self.update_spent_outputs(&mut base_node_client, last_mined_header)
             .await?;
 
+        self.update_spent_multisig_account_outputs(&mut base_node_client, last_mined_header)
+            .await?;
+
         self.update_invalid_outputs(&mut base_node_client).await?;
 
         self.publish_event(OutputManagerEvent::TxoValidationSuccess(self.operation_id));
@@ -297,6 +300,66 @@ where
         Ok(())
     }
 
+    /// Any cosigner may lead the spend of a multisig account output, so every cosigner learns that it was spent from
+    /// the chain rather than from its own transactions
+    async fn update_spent_multisig_account_outputs(
+        &self,
+        wallet_client: &mut BaseNodeWalletRpcClient,
+        last_mined_header_hash: Option<BlockHash>,
+    ) -> Result<(), OutputManagerProtocolError> {
+        let account_outputs = self
+            .db
+            .fetch_multisig_account_outputs_not_spent_on_chain()
+            .for_protocol(self.operation_id)?;
+        if account_outputs.is_empty() {
+            return Ok(());
+        }
+
+        for batch in account_outputs.chunks(self.config.tx_validator_batch_size) {
+            debug!(
+                target: LOG_TARGET,
+                "Asking base node for status of {} multisig account outputs (Operation ID: {})",
+                batch.len(),
+                self.operation_id
+            );
+
+            let response = wallet_client
+                .query_deleted(QueryDeletedRequest {
+                    chain_must_include_header: last_mined_header_hash.map(|v| v.to_vec()).unwrap_or_default(),
+                    hashes: batch.iter().map(|o| o.output_hash.to_vec()).collect(),
+                })
+                .await
+                .for_protocol(self.operation_id)?;
+
+            if response.data.len() != batch.len() {
+                return Err(OutputManagerProtocolError::new(
+                    self.operation_id,
+                    OutputManagerError::InconsistentBaseNodeDataError(
+                        "Base node did not send back information for all multisig account outputs",
+                    ),
+                ));
+            }
+
+            for (output, data) in batch.iter().zip(response.data.iter()) {
+                if data.height_deleted_at == 0 {
+                    continue;
+                }
+                info!(
+                    target: LOG_TARGET,
+                    "Updating output {} of multisig account '{}' as spent at height {} (Operation ID: {})",
+                    output.output_hash.to_hex(),
+                    output.account_name,
+                    data.height_deleted_at,
+                    self.operation_id
+                );
+                self.db
+                    .mark_multisig_account_output_spent_at_height(&output.output_hash, data.height_deleted_at)
+                    .for_protocol(self.operation_id)?;
+            }
+        }
+        Ok(())
+    }
+
     async fn update_unconfirmed_outputs(
         &self,
         wallet_client: &mut BaseNodeWalletRpcClient,
@@ -0,0 +1,117 @@
+//  Copyright 2024. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use serde::{Deserialize, Serialize};
+use tari_common_types::{
+    tari_address::TariAddress,
+    transaction::TxId,
+    types::{Commitment, HashOutput},
+};
+use tari_core::transactions::{tari_amount::MicroMinotari, transaction_components::OutputFeatures};
+use tari_script::TariScript;
+
+use crate::output_manager_service::storage::models::DbWalletOutput;
+
+/// The current version of the unsigned transaction format
+pub const UNSIGNED_TRANSACTION_VERSION: u8 = 1;
+
+/// An input selected to fund an unsigned transaction, carrying the data a signer needs to spend it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UnsignedTransactionInput {
+    pub commitment: Commitment,
+    pub output_hash: HashOutput,
+    pub value: MicroMinotari,
+    pub features: OutputFeatures,
+    pub script: TariScript,
+    pub mined_height: Option<u64>,
+}
+
+impl From<&DbWalletOutput> for UnsignedTransactionInput {
+    fn from(output: &DbWalletOutput) -> Self {
+        Self {
+            commitment: output.commitment.clone(),
+            output_hash: output.hash,
+            value: output.wallet_output.value,
+            features: output.wallet_output.features.clone(),
+            script: output.wallet_output.script.clone(),
+            mined_height: output.mined_height,
+        }
+    }
+}
+
+/// A transaction constructed by a watch-only wallet. It records the selected inputs, the payment and the fee, but
+/// holds no secrets, so it must be handed to a wallet with the spend key to be signed.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UnsignedTransaction {
+    pub version: u8,
+    pub tx_id: TxId,
+    pub recipient_address: TariAddress,
+    pub amount: MicroMinotari,
+    pub fee_per_gram: MicroMinotari,
+    pub fee: MicroMinotari,
+    pub change: MicroMinotari,
+    pub message: String,
+    pub inputs: Vec<UnsignedTransactionInput>,
+}
+
+impl UnsignedTransaction {
+    /// The total value of all the inputs, which always equals `amount + fee + change`
+    pub fn total_input_value(&self) -> MicroMinotari {
+        self.inputs.iter().map(|i| i.value).sum()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn input(value: u64) -> UnsignedTransactionInput {
+        UnsignedTransactionInput {
+            commitment: Commitment::default(),
+            output_hash: HashOutput::zero(),
+            value: MicroMinotari::from(value),
+            features: OutputFeatures::default(),
+            script: TariScript::default(),
+            mined_height: Some(10),
+        }
+    }
+
+    #[test]
+    fn it_round_trips_through_json() {
+        let tx = UnsignedTransaction {
+            version: UNSIGNED_TRANSACTION_VERSION,
+            tx_id: TxId::from(42u64),
+            recipient_address: TariAddress::default(),
+            amount: MicroMinotari::from(7_000),
+            fee_per_gram: MicroMinotari::from(5),
+            fee: MicroMinotari::from(1_000),
+            change: MicroMinotari::from(2_000),
+            message: "watch-only".to_string(),
+            inputs: vec![input(4_000), input(6_000)],
+        };
+        assert_eq!(tx.total_input_value(), tx.amount + tx.fee + tx.change);
+
+        let json = serde_json::to_string(&tx).unwrap();
+        let decoded: UnsignedTransaction = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, tx);
+    }
+}
@@ -66,6 +66,36 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    large_send_approvals (approval_key) {
+        approval_key -> Text,
+        available_at -> Timestamp,
+        expires_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    multisig_account_outputs (output_hash) {
+        output_hash -> Binary,
+        account_name -> Text,
+        commitment -> Binary,
+        value -> BigInt,
+        mined_height -> BigInt,
+        spent_in_tx_id -> Nullable<BigInt>,
+        spent_at_height -> Nullable<BigInt>,
+    }
+}
+
+diesel::table! {
+    multisig_accounts (name) {
+        name -> Text,
+        threshold -> Integer,
+        public_keys -> Binary,
+        key_index -> BigInt,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     outbound_transactions (tx_id) {
         tx_id -> BigInt,
@@ -119,6 +149,21 @@ diesel::table! {
         source -> Integer,
         last_validation_timestamp -> Nullable<Timestamp>,
         payment_id -> Nullable<Binary>,
+        label -> Nullable<Text>,
+        note -> Nullable<Text>,
+        frozen -> Bool,
+    }
+}
+
+diesel::table! {
+    payment_requests (payment_id) {
+        payment_id -> Binary,
+        amount -> BigInt,
+        expiry -> Nullable<BigInt>,
+        request -> Text,
+        created_at -> Timestamp,
+        paid_tx_id -> Nullable<BigInt>,
+        paid_at -> Nullable<Timestamp>,
     }
 }
 
@@ -132,6 +177,23 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    spending_policy (id) {
+        id -> Integer,
+        policy -> Text,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    sub_addresses (key_index) {
+        key_index -> BigInt,
+        label -> Text,
+        address -> Binary,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     wallet_settings (key) {
         key -> Text,
@@ -145,8 +207,14 @@ diesel::allow_tables_to_appear_in_same_query!(
     completed_transactions,
     inbound_transactions,
     known_one_sided_payment_scripts,
+    large_send_approvals,
+    multisig_account_outputs,
+    multisig_accounts,
     outbound_transactions,
     outputs,
+    payment_requests,
     scanned_blocks,
+    spending_policy,
+    sub_addresses,
     wallet_settings,
 );
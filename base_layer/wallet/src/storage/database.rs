@@ -27,12 +27,18 @@ use std::{
 
 use chrono::NaiveDateTime;
 use log::*;
-use tari_common_types::{chain_metadata::ChainMetadata, wallet_types::WalletType};
+use tari_common_types::{
+    chain_metadata::ChainMetadata,
+    payment_request::PaymentRequest,
+    transaction::TxId,
+    wallet_types::WalletType,
+};
 use tari_comms::{
     multiaddr::Multiaddr,
     peer_manager::{IdentitySignature, PeerFeatures},
     tor::TorIdentity,
 };
+use tari_core::transactions::tari_amount::MicroMinotari;
 use tari_key_manager::cipher_seed::CipherSeed;
 use tari_utilities::SafePassword;
 
@@ -62,6 +68,8 @@ pub trait WalletBackend: Send + Sync + Clone {
 
     /// Change the passphrase used to encrypt the database
     fn change_passphrase(&self, existing: &SafePassword, new: &SafePassword) -> Result<(), WalletStorageError>;
+    /// Create an encrypted, versioned archive of the complete wallet database
+    fn create_backup(&self) -> Result<Vec<u8>, WalletStorageError>;
 
     fn create_burnt_proof(
         &self,
@@ -72,6 +80,34 @@ pub trait WalletBackend: Send + Sync + Clone {
     fn fetch_burnt_proof(&self, id: u32) -> Result<(u32, String, String, NaiveDateTime), WalletStorageError>;
     fn fetch_burnt_proofs(&self) -> Result<Vec<(u32, String, String, NaiveDateTime)>, WalletStorageError>;
     fn delete_burnt_proof(&self, id: u32) -> Result<(), WalletStorageError>;
+
+    /// Store a payment request issued by this wallet so that incoming payments can be matched against it
+    fn save_invoice(&self, request: &PaymentRequest) -> Result<(), WalletStorageError>;
+    fn fetch_invoices(&self) -> Result<Vec<Invoice>, WalletStorageError>;
+    /// Mark the unpaid invoice with the given payment id as paid if `amount` covers it and it had not expired at
+    /// `received_at`. Returns true if an invoice was marked as paid.
+    fn mark_invoice_paid(
+        &self,
+        payment_id: &[u8],
+        amount: MicroMinotari,
+        tx_id: TxId,
+        received_at: NaiveDateTime,
+    ) -> Result<bool, WalletStorageError>;
+}
+
+/// A payment request issued by this wallet along with its payment status
+#[derive(Debug, Clone, PartialEq)]
+pub struct Invoice {
+    pub request: PaymentRequest,
+    pub created_at: NaiveDateTime,
+    pub paid_tx_id: Option<TxId>,
+    pub paid_at: Option<NaiveDateTime>,
+}
+
+impl Invoice {
+    pub fn is_paid(&self) -> bool {
+        self.paid_tx_id.is_some()
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -169,6 +205,11 @@ where T: WalletBackend + 'static
         Ok(())
     }
 
+    /// Create an encrypted archive of the wallet database that can be restored with the current wallet passphrase
+    pub fn create_backup(&self) -> Result<Vec<u8>, WalletStorageError> {
+        self.db.create_backup()
+    }
+
     pub fn get_master_seed(&self) -> Result<Option<CipherSeed>, WalletStorageError> {
         let c = match self.db.fetch(&DbKey::MasterSeed) {
             Ok(None) => Ok(None),
@@ -389,6 +430,24 @@ where T: WalletBackend + 'static
         self.db.delete_burnt_proof(id)
     }
 
+    pub fn save_invoice(&self, request: &PaymentRequest) -> Result<(), WalletStorageError> {
+        self.db.save_invoice(request)
+    }
+
+    pub fn fetch_invoices(&self) -> Result<Vec<Invoice>, WalletStorageError> {
+        self.db.fetch_invoices()
+    }
+
+    pub fn mark_invoice_paid(
+        &self,
+        payment_id: &[u8],
+        amount: MicroMinotari,
+        tx_id: TxId,
+        received_at: NaiveDateTime,
+    ) -> Result<bool, WalletStorageError> {
+        self.db.mark_invoice_paid(payment_id, amount, tx_id, received_at)
+    }
+
     pub fn get_wallet_type(&self) -> Result<Option<WalletType>, WalletStorageError> {
         match self.db.fetch(&DbKey::WalletType) {
             Ok(None) => Ok(None),
@@ -0,0 +1,110 @@
+//  Copyright 2024, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{convert::TryFrom, str::FromStr};
+
+use chrono::{NaiveDateTime, Utc};
+use diesel::{BoolExpressionMethods, ExpressionMethods, QueryDsl, RunQueryDsl, SqliteConnection};
+use tari_common_types::{payment_request::PaymentRequest, transaction::TxId};
+use tari_core::transactions::tari_amount::MicroMinotari;
+
+use crate::{error::WalletStorageError, schema::payment_requests, storage::database::Invoice};
+
+#[derive(Clone, Debug, Queryable, Insertable, PartialEq)]
+#[diesel(table_name = payment_requests)]
+pub struct PaymentRequestSql {
+    payment_id: Vec<u8>,
+    amount: i64,
+    expiry: Option<i64>,
+    request: String,
+    created_at: NaiveDateTime,
+    paid_tx_id: Option<i64>,
+    paid_at: Option<NaiveDateTime>,
+}
+
+impl PaymentRequestSql {
+    pub fn index(conn: &mut SqliteConnection) -> Result<Vec<PaymentRequestSql>, WalletStorageError> {
+        Ok(payment_requests::table
+            .order(payment_requests::created_at.desc())
+            .load::<PaymentRequestSql>(conn)?)
+    }
+
+    pub fn new(request: &PaymentRequest) -> Self {
+        Self {
+            payment_id: request.payment_id().to_vec(),
+            amount: request.amount() as i64,
+            expiry: request.expiry().map(|e| e as i64),
+            request: request.to_uri(),
+            created_at: Utc::now().naive_utc(),
+            paid_tx_id: None,
+            paid_at: None,
+        }
+    }
+
+    pub fn commit(&self, conn: &mut SqliteConnection) -> Result<(), WalletStorageError> {
+        diesel::insert_into(payment_requests::table)
+            .values(self.clone())
+            .execute(conn)?;
+        Ok(())
+    }
+
+    /// Marks the unpaid request with the given payment id as paid if `amount` covers the requested amount and the
+    /// request had not expired at `received_at`. Returns true if a request was updated.
+    pub fn mark_paid(
+        payment_id: &[u8],
+        amount: MicroMinotari,
+        tx_id: TxId,
+        received_at: NaiveDateTime,
+        conn: &mut SqliteConnection,
+    ) -> Result<bool, WalletStorageError> {
+        let num_updated = diesel::update(
+            payment_requests::table
+                .filter(payment_requests::payment_id.eq(payment_id))
+                .filter(payment_requests::paid_tx_id.is_null())
+                .filter(payment_requests::amount.le(amount.as_u64() as i64))
+                .filter(
+                    payment_requests::expiry
+                        .is_null()
+                        .or(payment_requests::expiry.gt(received_at.timestamp())),
+                ),
+        )
+        .set((
+            payment_requests::paid_tx_id.eq(tx_id.as_i64_wrapped()),
+            payment_requests::paid_at.eq(Utc::now().naive_utc()),
+        ))
+        .execute(conn)?;
+        Ok(num_updated > 0)
+    }
+}
+
+impl TryFrom<PaymentRequestSql> for Invoice {
+    type Error = String;
+
+    fn try_from(sql: PaymentRequestSql) -> Result<Self, Self::Error> {
+        Ok(Self {
+            request: PaymentRequest::from_str(&sql.request).map_err(|err| err.to_string())?,
+            created_at: sql.created_at,
+            paid_tx_id: sql.paid_tx_id.map(|id| TxId::from(id as u64)),
+            paid_at: sql.paid_at,
+        })
+    }
+}
@@ -22,7 +22,9 @@
 
 use std::{
     convert::TryFrom,
+    fs,
     mem::size_of,
+    path::Path,
     str::{from_utf8, FromStr},
     sync::{Arc, RwLock},
 };
@@ -34,20 +36,24 @@ use argon2::password_hash::{
 use blake2::Blake2b;
 use chacha20poly1305::{Key, KeyInit, XChaCha20Poly1305};
 use chrono::NaiveDateTime;
-use diesel::{prelude::*, result::Error};
+use diesel::{prelude::*, result::Error, sql_types::Text};
 use digest::{consts::U32, generic_array::GenericArray, FixedOutput};
 use itertools::Itertools;
 use log::*;
+use serde::{Deserialize, Serialize};
 use tari_common_sqlite::sqlite_connection_pool::PooledDbConnection;
 use tari_common_types::{
     chain_metadata::ChainMetadata,
     encryption::{decrypt_bytes_integral_nonce, encrypt_bytes_integral_nonce, Encryptable},
+    payment_request::PaymentRequest,
+    transaction::TxId,
 };
 use tari_comms::{
     multiaddr::Multiaddr,
     peer_manager::{IdentitySignature, PeerFeatures},
     tor::TorIdentity,
 };
+use tari_core::transactions::tari_amount::MicroMinotari;
 use tari_crypto::{hash_domain, hashing::DomainSeparatedHasher};
 use tari_key_manager::cipher_seed::CipherSeed;
 use tari_utilities::{
@@ -65,8 +71,8 @@ use crate::{
     error::WalletStorageError,
     schema::{burnt_proofs, client_key_values, wallet_settings},
     storage::{
-        database::{DbKey, DbKeyValuePair, DbValue, WalletBackend, WriteOperation},
-        sqlite_db::scanned_blocks::ScannedBlockSql,
+        database::{DbKey, DbKeyValuePair, DbValue, Invoice, WalletBackend, WriteOperation},
+        sqlite_db::{payment_requests::PaymentRequestSql, scanned_blocks::ScannedBlockSql},
         sqlite_utilities::wallet_db_connection::WalletDbConnection,
     },
     utxo_scanner_service::service::ScannedBlock,
@@ -96,6 +102,18 @@ hash_domain!(
     0
 );
 
+// Every wallet backup archive starts with these bytes
+const WALLET_BACKUP_MAGIC: &[u8] = b"TARI_WALLET_BACKUP";
+
+/// The version of the wallet backup archive format
+pub const WALLET_BACKUP_VERSION: u8 = 1;
+
+// Authenticated data prefix for wallet backup encryption; append the backup version later
+const WALLET_BACKUP_AAD_PREFIX: &str = "wallet_backup_v";
+
+// The header of every SQLite database file
+const SQLITE_FILE_HEADER: &[u8] = b"SQLite format 3\0";
+
 /// A structure to hold `Argon2` parameter versions, which may change over time and must be supported
 #[derive(Clone)]
 pub struct Argon2Parameters {
@@ -220,6 +238,72 @@ impl DatabaseEncryptionFields {
     }
 }
 
+/// A wallet backup archive. The database snapshot is encrypted with the main database key, which can only be recovered
+/// with the wallet passphrase at the time of the backup using the key-related fields stored alongside it.
+#[derive(Serialize, Deserialize)]
+struct WalletBackup {
+    version: u8,
+    secondary_key_version: u8,
+    secondary_key_salt: String,
+    secondary_key_hash: Vec<u8>,
+    encrypted_main_key: Vec<u8>,
+    encrypted_database: Vec<u8>,
+}
+
+impl WalletBackup {
+    fn aad(version: u8) -> Vec<u8> {
+        let mut aad = WALLET_BACKUP_AAD_PREFIX.as_bytes().to_owned();
+        aad.push(version);
+        aad
+    }
+
+    fn to_bytes(&self) -> Result<Vec<u8>, WalletStorageError> {
+        let mut bytes = WALLET_BACKUP_MAGIC.to_vec();
+        bincode::serialize_into(&mut bytes, self).map_err(|e| WalletStorageError::ConversionError(e.to_string()))?;
+        Ok(bytes)
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, WalletStorageError> {
+        let data = bytes
+            .strip_prefix(WALLET_BACKUP_MAGIC)
+            .ok_or_else(|| WalletStorageError::InvalidBackup("Not a wallet backup file".to_string()))?;
+        let backup: Self = bincode::deserialize(data)
+            .map_err(|e| WalletStorageError::InvalidBackup(format!("Malformed wallet backup: {}", e)))?;
+        if backup.version == 0 || backup.version > WALLET_BACKUP_VERSION {
+            return Err(WalletStorageError::InvalidBackup(format!(
+                "Unsupported wallet backup version {}",
+                backup.version
+            )));
+        }
+        Ok(backup)
+    }
+}
+
+/// Decrypt a wallet backup archive created with `WalletBackend::create_backup` and return the SQLite database it
+/// contains. The passphrase must be the wallet passphrase at the time the backup was created.
+pub fn restore_wallet_backup(archive: &[u8], passphrase: &SafePassword) -> Result<Vec<u8>, WalletStorageError> {
+    let backup = WalletBackup::from_bytes(archive)?;
+
+    // Recover the main key exactly as when opening the wallet database
+    let argon2_params = Argon2Parameters::from_version(Some(backup.secondary_key_version))?;
+    let (secondary_key, secondary_key_hash) =
+        derive_secondary_key(passphrase, argon2_params, &backup.secondary_key_salt)?;
+    if backup.secondary_key_hash != secondary_key_hash {
+        return Err(WalletStorageError::InvalidPassphrase);
+    }
+    let main_key = decrypt_main_key(&secondary_key, &backup.encrypted_main_key, backup.secondary_key_version)?;
+    let cipher = XChaCha20Poly1305::new(Key::from_slice(main_key.reveal()));
+
+    let database = decrypt_bytes_integral_nonce(&cipher, WalletBackup::aad(backup.version), &backup.encrypted_database)
+        .map_err(|_| WalletStorageError::InvalidBackup("The wallet backup could not be authenticated".to_string()))?;
+    if !database.starts_with(SQLITE_FILE_HEADER) {
+        return Err(WalletStorageError::InvalidBackup(
+            "The wallet backup does not contain a wallet database".to_string(),
+        ));
+    }
+    Ok(database)
+}
+
 /// A Sqlite backend for the Output Manager Service. The Backend is accessed via a connection pool to the Sqlite file.
 #[derive(Clone)]
 pub struct WalletSqliteDatabase {
@@ -634,6 +718,57 @@ impl WalletBackend for WalletSqliteDatabase {
         Ok(())
     }
 
+    fn create_backup(&self) -> Result<Vec<u8>, WalletStorageError> {
+        let start = Instant::now();
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        let data = DatabaseEncryptionFields::read(&mut conn)?.ok_or_else(|| {
+            WalletStorageError::UnexpectedResult("Unable to get valid key-related data from database".into())
+        })?;
+
+        #[derive(QueryableByName)]
+        struct DatabaseFile {
+            #[diesel(sql_type = Text)]
+            file: String,
+        }
+        // The plaintext snapshot is kept next to the wallet database rather than in the shared system temp directory.
+        // In-memory databases have no file and fall back to the system temp directory.
+        let database_file = diesel::sql_query("SELECT file FROM pragma_database_list WHERE name = 'main'")
+            .get_result::<DatabaseFile>(&mut conn)?
+            .file;
+        let snapshot_dir = match Path::new(&database_file).parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => tempfile::tempdir_in(dir)?,
+            _ => tempfile::tempdir()?,
+        };
+        // `VACUUM INTO` writes a consistent snapshot of the database without blocking other connections for long
+        let snapshot_path = snapshot_dir.path().join("wallet_backup.sqlite3");
+        let snapshot_path_str = snapshot_path.to_str().ok_or(WalletStorageError::InvalidUnicodePath)?;
+        diesel::sql_query("VACUUM INTO ?")
+            .bind::<Text, _>(snapshot_path_str)
+            .execute(&mut conn)?;
+        let database = Hidden::hide(fs::read(&snapshot_path)?);
+
+        let cipher = acquire_read_lock!(self.cipher);
+        let encrypted_database =
+            encrypt_bytes_integral_nonce(&cipher, WalletBackup::aad(WALLET_BACKUP_VERSION), database)
+                .map_err(|e| WalletStorageError::AeadError(format!("Encryption Error:{}", e)))?;
+        let archive = WalletBackup {
+            version: WALLET_BACKUP_VERSION,
+            secondary_key_version: data.secondary_key_version,
+            secondary_key_salt: data.secondary_key_salt,
+            secondary_key_hash: data.secondary_key_hash,
+            encrypted_main_key: data.encrypted_main_key,
+            encrypted_database,
+        }
+        .to_bytes()?;
+        debug!(
+            target: LOG_TARGET,
+            "Created wallet backup of {} bytes in {} ms",
+            archive.len(),
+            start.elapsed().as_millis()
+        );
+        Ok(archive)
+    }
+
     fn create_burnt_proof(
         &self,
         id: u32,
@@ -726,6 +861,31 @@ impl WalletBackend for WalletSqliteDatabase {
         BurntProofSql::delete(id, &mut conn)?;
         Ok(())
     }
+
+    fn save_invoice(&self, request: &PaymentRequest) -> Result<(), WalletStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        PaymentRequestSql::new(request).commit(&mut conn)
+    }
+
+    fn fetch_invoices(&self) -> Result<Vec<Invoice>, WalletStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        PaymentRequestSql::index(&mut conn)?
+            .into_iter()
+            .map(Invoice::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(WalletStorageError::ConversionError)
+    }
+
+    fn mark_invoice_paid(
+        &self,
+        payment_id: &[u8],
+        amount: MicroMinotari,
+        tx_id: TxId,
+        received_at: NaiveDateTime,
+    ) -> Result<bool, WalletStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        PaymentRequestSql::mark_paid(payment_id, amount, tx_id, received_at, &mut conn)
+    }
 }
 
 /// Derive a secondary database key and associated commitment
@@ -1092,8 +1252,19 @@ impl Encryptable<XChaCha20Poly1305> for BurntProofSql {
 
 #[cfg(test)]
 mod test {
+    use chrono::NaiveDateTime;
+    use rand::rngs::OsRng;
+    use tari_common::configuration::Network;
     use tari_common_sqlite::sqlite_connection_pool::PooledDbConnection;
-    use tari_common_types::encryption::{decrypt_bytes_integral_nonce, Encryptable};
+    use tari_common_types::{
+        encryption::{decrypt_bytes_integral_nonce, Encryptable},
+        payment_request::PaymentRequest,
+        tari_address::TariAddress,
+        transaction::TxId,
+        types::{PrivateKey, PublicKey},
+    };
+    use tari_core::transactions::tari_amount::MicroMinotari;
+    use tari_crypto::keys::{PublicKey as PublicKeyTrait, SecretKey};
     use tari_key_manager::cipher_seed::CipherSeed;
     use tari_test_utils::random::string;
     use tari_utilities::{
@@ -1103,10 +1274,13 @@ mod test {
     };
     use tempfile::tempdir;
 
-    use crate::storage::{
-        database::{DbKey, DbValue, WalletBackend},
-        sqlite_db::wallet::{ClientKeyValueSql, WalletSettingSql, WalletSqliteDatabase},
-        sqlite_utilities::run_migration_and_create_sqlite_connection,
+    use crate::{
+        error::WalletStorageError,
+        storage::{
+            database::{DbKey, DbValue, WalletBackend},
+            sqlite_db::wallet::{restore_wallet_backup, ClientKeyValueSql, WalletSettingSql, WalletSqliteDatabase},
+            sqlite_utilities::run_migration_and_create_sqlite_connection,
+        },
     };
     #[test]
     fn test_passphrase() {
@@ -1152,6 +1326,46 @@ mod test {
         assert!(WalletSqliteDatabase::new(connection, "new passphrase".to_string().into()).is_ok());
     }
 
+    #[test]
+    fn test_backup_and_restore() {
+        let db_tempdir = tempdir().unwrap();
+        let db_folder = db_tempdir.path().to_str().unwrap().to_string();
+        let db_path = format!("{}/{}.sqlite3", db_folder, string(8).as_str());
+        let connection = run_migration_and_create_sqlite_connection(db_path, 16).unwrap();
+
+        let seed = CipherSeed::new();
+        let db = WalletSqliteDatabase::new(connection.clone(), "passphrase".to_string().into()).unwrap();
+        db.set_master_seed(&seed, &mut connection.get_pooled_connection().unwrap())
+            .unwrap();
+        let archive = db.create_backup().unwrap();
+
+        // Changing the passphrase afterwards does not affect the backup
+        db.change_passphrase(&"passphrase".to_string().into(), &"new passphrase".to_string().into())
+            .unwrap();
+        assert!(matches!(
+            restore_wallet_backup(&archive, &"new passphrase".to_string().into()),
+            Err(WalletStorageError::InvalidPassphrase)
+        ));
+
+        // Tampering is detected
+        let mut tampered = archive.clone();
+        let last = tampered.len() - 1;
+        tampered[last] ^= 1;
+        assert!(restore_wallet_backup(&tampered, &"passphrase".to_string().into()).is_err());
+        assert!(restore_wallet_backup(&archive[1..], &"passphrase".to_string().into()).is_err());
+
+        // The restored database opens with the passphrase at the time of the backup
+        let database = restore_wallet_backup(&archive, &"passphrase".to_string().into()).unwrap();
+        let restored_path = format!("{}/{}.sqlite3", db_folder, string(8).as_str());
+        std::fs::write(&restored_path, database).unwrap();
+        let restored_connection = run_migration_and_create_sqlite_connection(restored_path, 16).unwrap();
+        let restored = WalletSqliteDatabase::new(restored_connection, "passphrase".to_string().into()).unwrap();
+        match restored.fetch(&DbKey::MasterSeed).unwrap().unwrap() {
+            DbValue::MasterSeed(restored_seed) => assert_eq!(restored_seed, seed),
+            _ => panic!("Should be able to read the master seed"),
+        }
+    }
+
     #[test]
     #[allow(unused_must_use)]
     fn test_malleated_secondary_key_hash() {
@@ -1342,4 +1556,63 @@ mod test {
 
         assert_eq!(decrypted_db_seed, seed_bytes);
     }
+
+    #[test]
+    fn test_invoice_store() {
+        let db_name = format!("{}.sqlite3", string(8).as_str());
+        let db_tempdir = tempdir().unwrap();
+        let db_folder = db_tempdir.path().to_str().unwrap().to_string();
+        let connection = run_migration_and_create_sqlite_connection(format!("{}{}", db_folder, db_name), 16).unwrap();
+        let passphrase = SafePassword::from("an example very very secret key.".to_string());
+        let db = WalletSqliteDatabase::new(connection, passphrase).unwrap();
+
+        let address = TariAddress::new_single_address_with_interactive_only(
+            PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng)),
+            Network::LocalNet,
+        );
+        let request = PaymentRequest::new(
+            address.clone(),
+            10_000,
+            b"invoice-1".to_vec(),
+            Some(1_000_000),
+            Some("test".to_string()),
+        );
+        db.save_invoice(&request).unwrap();
+        db.save_invoice(&PaymentRequest::new(address, 500, b"invoice-2".to_vec(), None, None))
+            .unwrap();
+
+        let invoices = db.fetch_invoices().unwrap();
+        assert_eq!(invoices.len(), 2);
+        assert!(invoices.iter().all(|i| !i.is_paid()));
+        assert!(invoices.iter().any(|i| i.request == request));
+
+        let before_expiry = NaiveDateTime::from_timestamp_opt(999_999, 0).unwrap();
+        let after_expiry = NaiveDateTime::from_timestamp_opt(1_000_000, 0).unwrap();
+        // Underpayments, unknown payment ids and late payments don't settle the invoice
+        assert!(!db
+            .mark_invoice_paid(b"invoice-1", MicroMinotari(9_999), TxId::from(1u64), before_expiry)
+            .unwrap());
+        assert!(!db
+            .mark_invoice_paid(b"invoice-3", MicroMinotari(10_000), TxId::from(1u64), before_expiry)
+            .unwrap());
+        assert!(!db
+            .mark_invoice_paid(b"invoice-1", MicroMinotari(10_000), TxId::from(1u64), after_expiry)
+            .unwrap());
+        assert!(db
+            .mark_invoice_paid(b"invoice-1", MicroMinotari(10_000), TxId::from(1u64), before_expiry)
+            .unwrap());
+        // An invoice can only be settled once
+        assert!(!db
+            .mark_invoice_paid(b"invoice-1", MicroMinotari(10_000), TxId::from(2u64), before_expiry)
+            .unwrap());
+
+        let paid = db
+            .fetch_invoices()
+            .unwrap()
+            .into_iter()
+            .find(|i| i.request == request)
+            .unwrap();
+        assert_eq!(paid.paid_tx_id, Some(TxId::from(1u64)));
+        assert!(paid.paid_at.is_some());
+    }
 }
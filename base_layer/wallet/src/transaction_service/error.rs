@@ -47,6 +47,8 @@ use crate::{
     error::WalletStorageError,
     output_manager_service::error::OutputManagerError,
     transaction_service::{
+        partially_signed_transaction::PartiallySignedTransactionError,
+        spending_policy::SpendingPolicyViolation,
         storage::{database::DbKey, sqlite_db::CompletedTransactionConversionError},
         utc::NegativeDurationError,
     },
@@ -60,6 +62,10 @@ pub enum TransactionServiceError {
     InvalidNetwork,
     #[error("One-sided transaction error: `{0}`")]
     OneSidedTransactionError(String),
+    #[error("Multi-recipient transaction error: `{0}`")]
+    MultiRecipientTransactionError(String),
+    #[error("Payment proof error: `{0}`")]
+    PaymentProofError(String),
     #[error("Transaction Protocol Error: `{0}`")]
     TransactionProtocolError(#[from] TransactionProtocolError),
     #[error("The message being processed is not recognized by the Transaction Manager")]
@@ -195,6 +201,12 @@ pub enum TransactionServiceError {
     InvalidAddress(String),
     #[error("Transaction is not supported: `{0}`")]
     NotSupported(String),
+    #[error("This is a watch-only wallet and cannot sign transactions, export an unsigned transaction instead")]
+    WatchOnlyWallet,
+    #[error("Partially signed transaction error: `{0}`")]
+    PartiallySignedTransactionError(#[from] PartiallySignedTransactionError),
+    #[error("Spending policy violation: {0}")]
+    SpendingPolicyViolation(#[from] SpendingPolicyViolation),
 }
 
 impl From<RangeProofError> for TransactionServiceError {
@@ -32,7 +32,7 @@ use tari_common_types::{
     burnt_proof::BurntProof,
     tari_address::TariAddress,
     transaction::{ImportStatus, TxId},
-    types::{FixedHash, HashOutput, PrivateKey, PublicKey, Signature},
+    types::{BlockHash, FixedHash, HashOutput, PrivateKey, PublicKey, Signature},
     MaxSizeBytes,
     MaxSizeString,
 };
@@ -51,6 +51,7 @@ use tari_core::{
             Transaction,
             TransactionOutput,
         },
+        PaymentProof,
     },
 };
 use tari_crypto::ristretto::pedersen::PedersenCommitment;
@@ -64,10 +65,14 @@ use crate::{
     output_manager_service::UtxoSelectionCriteria,
     transaction_service::{
         error::TransactionServiceError,
+        partially_signed_transaction::PartiallySignedTransaction,
+        spending_policy::{PolicySpend, SpendDestination, SpendingPolicy},
         storage::models::{
             CompletedTransaction,
             InboundTransaction,
             OutboundTransaction,
+            TransactionExportFilter,
+            TransactionExportRecord,
             TxCancellationReason,
             WalletTransaction,
         },
@@ -87,6 +92,7 @@ pub enum TransactionServiceRequest {
     GetCancelledCompletedTransactions,
     GetCompletedTransaction(TxId),
     GetAnyTransaction(TxId),
+    ExportTransactions(TransactionExportFilter),
     ImportTransaction(WalletTransaction),
     SendTransaction {
         destination: TariAddress,
@@ -157,6 +163,18 @@ pub enum TransactionServiceRequest {
         message: String,
         payment_id: PaymentId,
     },
+    SendBatchOneSided {
+        recipients: Vec<OneSidedRecipient>,
+        selection_criteria: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+        message: String,
+    },
+    SendTransactionToMultipleRecipients {
+        recipients: Vec<(TariAddress, MicroMinotari)>,
+        selection_criteria: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+        message: String,
+    },
     SendOneSidedToStealthAddressTransaction {
         destination: TariAddress,
         amount: MicroMinotari,
@@ -167,6 +185,24 @@ pub enum TransactionServiceRequest {
         payment_id: PaymentId,
     },
     SendShaAtomicSwapTransaction(TariAddress, MicroMinotari, UtxoSelectionCriteria, MicroMinotari, String),
+    SendToMultisigAccount {
+        threshold: u8,
+        public_keys: Vec<PublicKey>,
+        amount: MicroMinotari,
+        selection_criteria: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+        message: String,
+    },
+    CreatePartiallySignedTransaction {
+        destination: TariAddress,
+        amount: MicroMinotari,
+        selection_criteria: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+        message: String,
+        payment_id: PaymentId,
+    },
+    SignPartiallySignedTransaction(Box<PartiallySignedTransaction>),
+    FinalizePartiallySignedTransaction(Box<PartiallySignedTransaction>),
     CancelTransaction(TxId),
     ImportUtxoWithStatus {
         amount: MicroMinotari,
@@ -192,6 +228,92 @@ pub enum TransactionServiceRequest {
     GetFeePerGramStatsPerBlock {
         count: usize,
     },
+    GetSpendingPolicy,
+    SetSpendingPolicy(SpendingPolicy),
+    GeneratePaymentProof {
+        tx_id: TxId,
+        recipient: Option<TariAddress>,
+    },
+    VerifyPaymentProof(Box<PaymentProof>),
+}
+
+impl TransactionServiceRequest {
+    /// Returns true if handling the request requires the wallet to sign for its own funds
+    pub fn spends_funds(&self) -> bool {
+        matches!(
+            self,
+            Self::SendTransaction { .. } |
+                Self::BurnTari { .. } |
+                Self::EncumberAggregateUtxo { .. } |
+                Self::SpendBackupPreMineUtxo { .. } |
+                Self::FinalizeSentAggregateTransaction { .. } |
+                Self::RegisterValidatorNode { .. } |
+                Self::RegisterCodeTemplate { .. } |
+                Self::SendOneSidedTransaction { .. } |
+                Self::SendBatchOneSided { .. } |
+                Self::SendTransactionToMultipleRecipients { .. } |
+                Self::SendOneSidedToStealthAddressTransaction { .. } |
+                Self::SendShaAtomicSwapTransaction(..) |
+                Self::SendToMultisigAccount { .. } |
+                Self::SignPartiallySignedTransaction(..) |
+                Self::SubmitTransactionToSelf(..)
+        )
+    }
+
+    /// Returns the funds the request sends out of the wallet, or `None` if the request is not subject to the spending
+    /// policy. Transactions the wallet submits to itself are not policed.
+    pub fn policy_spend(&self) -> Option<PolicySpend> {
+        match self {
+            Self::SendTransaction {
+                destination, amount, ..
+            } |
+            Self::SendOneSidedTransaction {
+                destination, amount, ..
+            } |
+            Self::SendOneSidedToStealthAddressTransaction {
+                destination, amount, ..
+            } |
+            Self::SendShaAtomicSwapTransaction(destination, amount, ..) => Some(PolicySpend::new(
+                *amount,
+                SpendDestination::Address(destination.clone()),
+            )),
+            Self::SendBatchOneSided { recipients, .. } => Some(PolicySpend {
+                amount: Some(recipients.iter().map(|r| r.amount).sum()),
+                destinations: recipients
+                    .iter()
+                    .map(|r| SpendDestination::Address(r.destination.clone()))
+                    .collect(),
+            }),
+            Self::SendTransactionToMultipleRecipients { recipients, .. } => Some(PolicySpend {
+                amount: Some(recipients.iter().map(|(_, amount)| *amount).sum()),
+                destinations: recipients
+                    .iter()
+                    .map(|(destination, _)| SpendDestination::Address(destination.clone()))
+                    .collect(),
+            }),
+            Self::BurnTari { amount, .. } => Some(PolicySpend::new(*amount, SpendDestination::Burn)),
+            Self::SendToMultisigAccount { amount, .. } => {
+                Some(PolicySpend::new(*amount, SpendDestination::MultisigAccount))
+            },
+            Self::RegisterValidatorNode { amount, .. } => {
+                Some(PolicySpend::new(*amount, SpendDestination::ValidatorNodeRegistration))
+            },
+            Self::RegisterCodeTemplate { .. } => Some(PolicySpend::new(
+                MicroMinotari::zero(),
+                SpendDestination::CodeTemplateRegistration,
+            )),
+            Self::SignPartiallySignedTransaction(tx) => Some(PolicySpend::new(
+                tx.amount,
+                SpendDestination::Address(tx.recipient_address.clone()),
+            )),
+            Self::EncumberAggregateUtxo { recipient_address, .. } |
+            Self::SpendBackupPreMineUtxo { recipient_address, .. } => Some(PolicySpend {
+                amount: None,
+                destinations: vec![SpendDestination::Address(recipient_address.clone())],
+            }),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for TransactionServiceRequest {
@@ -206,6 +328,7 @@ impl fmt::Display for TransactionServiceRequest {
             Self::GetCancelledPendingOutboundTransactions => write!(f, "GetCancelledPendingOutboundTransactions"),
             Self::GetCancelledCompletedTransactions => write!(f, "GetCancelledCompletedTransactions"),
             Self::GetCompletedTransaction(t) => write!(f, "GetCompletedTransaction({})", t),
+            Self::ExportTransactions(filter) => write!(f, "ExportTransactions({:?})", filter),
             Self::SendTransaction {
                 destination,
                 amount,
@@ -312,6 +435,24 @@ impl fmt::Display for TransactionServiceRequest {
                 "SendOneSidedTransaction (to {}, {}, {})",
                 destination, amount, message
             ),
+            Self::SendBatchOneSided {
+                recipients, message, ..
+            } => write!(
+                f,
+                "SendBatchOneSided (to {} recipients, {}, {})",
+                recipients.len(),
+                recipients.iter().map(|r| r.amount).sum::<MicroMinotari>(),
+                message
+            ),
+            Self::SendTransactionToMultipleRecipients {
+                recipients, message, ..
+            } => write!(
+                f,
+                "SendTransactionToMultipleRecipients (to {} recipients, {}, {})",
+                recipients.len(),
+                recipients.iter().map(|(_, amount)| *amount).sum::<MicroMinotari>(),
+                message
+            ),
             Self::SendOneSidedToStealthAddressTransaction {
                 destination,
                 amount,
@@ -325,6 +466,25 @@ impl fmt::Display for TransactionServiceRequest {
             Self::SendShaAtomicSwapTransaction(k, _, v, _, msg) => {
                 write!(f, "SendShaAtomicSwapTransaction (to {}, {}, {})", k, v, msg)
             },
+            Self::SendToMultisigAccount {
+                threshold,
+                public_keys,
+                amount,
+                ..
+            } => write!(
+                f,
+                "SendToMultisigAccount ({}-of-{}, {})",
+                threshold,
+                public_keys.len(),
+                amount
+            ),
+            Self::CreatePartiallySignedTransaction {
+                destination, amount, ..
+            } => write!(f, "CreatePartiallySignedTransaction (to {}, {})", destination, amount),
+            Self::SignPartiallySignedTransaction(tx) => write!(f, "SignPartiallySignedTransaction ({})", tx.tx_id),
+            Self::FinalizePartiallySignedTransaction(tx) => {
+                write!(f, "FinalizePartiallySignedTransaction ({})", tx.tx_id)
+            },
             Self::CancelTransaction(t) => write!(f, "CancelTransaction ({})", t),
             Self::ImportUtxoWithStatus {
                 amount,
@@ -357,6 +517,10 @@ impl fmt::Display for TransactionServiceRequest {
             TransactionServiceRequest::RegisterCodeTemplate { template_name, .. } => {
                 write!(f, "RegisterCodeTemplate: {}", template_name)
             },
+            Self::GetSpendingPolicy => write!(f, "GetSpendingPolicy"),
+            Self::SetSpendingPolicy(_) => write!(f, "SetSpendingPolicy"),
+            Self::GeneratePaymentProof { tx_id, .. } => write!(f, "GeneratePaymentProof({})", tx_id),
+            Self::VerifyPaymentProof(proof) => write!(f, "VerifyPaymentProof({})", proof.output_hash),
         }
     }
 }
@@ -389,12 +553,18 @@ pub enum TransactionServiceResponse {
     NormalPowerModeSet,
     ProtocolsRestarted,
     AnyTransaction(Box<Option<WalletTransaction>>),
+    ExportedTransactions(Vec<TransactionExportRecord>),
+    PartiallySignedTransaction(Box<PartiallySignedTransaction>),
     NumConfirmationsRequired(u64),
     NumConfirmationsSet,
     ValidationStarted(OperationId),
     CompletedTransactionValidityChanged,
     ShaAtomicSwapTransactionSent(Box<(TxId, PublicKey, TransactionOutput)>),
     FeePerGramStatsPerBlock(FeePerGramStatsResponse),
+    SpendingPolicy(Box<SpendingPolicy>),
+    SpendingPolicySet,
+    PaymentProof(Box<PaymentProof>),
+    PaymentProofVerified(PaymentProofVerification),
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq, Default)]
@@ -414,6 +584,28 @@ impl Display for TransactionSendStatus {
     }
 }
 
+/// A single recipient of a batched one-sided transaction
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OneSidedRecipient {
+    pub destination: TariAddress,
+    pub amount: MicroMinotari,
+    pub payment_id: PaymentId,
+}
+
+impl Display for OneSidedRecipient {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(fmt, "{} to {} ({})", self.amount, self.destination, self.payment_id)
+    }
+}
+
+/// Where the kernel and output of a verified payment proof were mined
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PaymentProofVerification {
+    pub mined_height: u64,
+    pub block_hash: BlockHash,
+    pub confirmations: u64,
+}
+
 /// Events that can be published on the Text Message Service Event Stream
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub enum TransactionEvent {
@@ -683,6 +875,53 @@ impl TransactionServiceHandle {
         }
     }
 
+    /// Sends a single transaction containing a one-sided output for each of the recipients
+    pub async fn send_batch_one_sided_transaction(
+        &mut self,
+        recipients: Vec<OneSidedRecipient>,
+        selection_criteria: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+        message: String,
+    ) -> Result<TxId, TransactionServiceError> {
+        match self
+            .handle
+            .call(TransactionServiceRequest::SendBatchOneSided {
+                recipients,
+                selection_criteria,
+                fee_per_gram,
+                message,
+            })
+            .await??
+        {
+            TransactionServiceResponse::TransactionSent(tx_id) => Ok(tx_id),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Send a single interactive transaction that pays each of the recipients the paired amount. The recipients take
+    /// part in a multi-round exchange with this wallet before the transaction can be finalized.
+    pub async fn send_transaction_to_multiple_recipients(
+        &mut self,
+        recipients: Vec<(TariAddress, MicroMinotari)>,
+        selection_criteria: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+        message: String,
+    ) -> Result<TxId, TransactionServiceError> {
+        match self
+            .handle
+            .call(TransactionServiceRequest::SendTransactionToMultipleRecipients {
+                recipients,
+                selection_criteria,
+                fee_per_gram,
+                message,
+            })
+            .await??
+        {
+            TransactionServiceResponse::TransactionSent(tx_id) => Ok(tx_id),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
     /// Burns the given amount of Tari from the wallet
     pub async fn burn_tari(
         &mut self,
@@ -839,6 +1078,33 @@ impl TransactionServiceHandle {
         }
     }
 
+    /// Sends funds to the m-of-n account of the given cosigner keys
+    pub async fn send_to_multisig_account(
+        &mut self,
+        threshold: u8,
+        public_keys: Vec<PublicKey>,
+        amount: MicroMinotari,
+        selection_criteria: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+        message: String,
+    ) -> Result<TxId, TransactionServiceError> {
+        match self
+            .handle
+            .call(TransactionServiceRequest::SendToMultisigAccount {
+                threshold,
+                public_keys,
+                amount,
+                selection_criteria,
+                fee_per_gram,
+                message,
+            })
+            .await??
+        {
+            TransactionServiceResponse::TransactionSent(tx_id) => Ok(tx_id),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
     pub async fn cancel_transaction(&mut self, tx_id: TxId) -> Result<(), TransactionServiceError> {
         match self
             .handle
@@ -928,6 +1194,83 @@ impl TransactionServiceHandle {
         }
     }
 
+    pub async fn export_transactions(
+        &mut self,
+        filter: TransactionExportFilter,
+    ) -> Result<Vec<TransactionExportRecord>, TransactionServiceError> {
+        match self
+            .handle
+            .call(TransactionServiceRequest::ExportTransactions(filter))
+            .await??
+        {
+            TransactionServiceResponse::ExportedTransactions(records) => Ok(records),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Select inputs for a one-sided payment and wrap them in a partially signed transaction for an offline signer.
+    /// This does not need the spend key, so it is how a watch-only wallet spends.
+    pub async fn create_partially_signed_transaction(
+        &mut self,
+        destination: TariAddress,
+        amount: MicroMinotari,
+        selection_criteria: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+        message: String,
+        payment_id: PaymentId,
+    ) -> Result<PartiallySignedTransaction, TransactionServiceError> {
+        match self
+            .handle
+            .call(TransactionServiceRequest::CreatePartiallySignedTransaction {
+                destination,
+                amount,
+                selection_criteria,
+                fee_per_gram,
+                message,
+                payment_id,
+            })
+            .await??
+        {
+            TransactionServiceResponse::PartiallySignedTransaction(tx) => Ok(*tx),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Sign a partially signed transaction with this wallet's keys without broadcasting it. The inputs must be unspent
+    /// outputs this wallet already knows.
+    pub async fn sign_partially_signed_transaction(
+        &mut self,
+        transaction: PartiallySignedTransaction,
+    ) -> Result<PartiallySignedTransaction, TransactionServiceError> {
+        match self
+            .handle
+            .call(TransactionServiceRequest::SignPartiallySignedTransaction(Box::new(
+                transaction,
+            )))
+            .await??
+        {
+            TransactionServiceResponse::PartiallySignedTransaction(tx) => Ok(*tx),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Validate a fully signed transaction and broadcast it to the network
+    pub async fn finalize_partially_signed_transaction(
+        &mut self,
+        transaction: PartiallySignedTransaction,
+    ) -> Result<TxId, TransactionServiceError> {
+        match self
+            .handle
+            .call(TransactionServiceRequest::FinalizePartiallySignedTransaction(Box::new(
+                transaction,
+            )))
+            .await??
+        {
+            TransactionServiceResponse::TransactionSent(tx_id) => Ok(tx_id),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
     pub async fn get_completed_transaction(
         &mut self,
         tx_id: TxId,
@@ -1070,6 +1413,59 @@ impl TransactionServiceHandle {
         }
     }
 
+    pub async fn get_spending_policy(&mut self) -> Result<SpendingPolicy, TransactionServiceError> {
+        match self.handle.call(TransactionServiceRequest::GetSpendingPolicy).await?? {
+            TransactionServiceResponse::SpendingPolicy(policy) => Ok(*policy),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Replaces the spending policy of the wallet, the default policy removes all limits
+    pub async fn set_spending_policy(&mut self, policy: SpendingPolicy) -> Result<(), TransactionServiceError> {
+        match self
+            .handle
+            .call(TransactionServiceRequest::SetSpendingPolicy(policy))
+            .await??
+        {
+            TransactionServiceResponse::SpendingPolicySet => Ok(()),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Creates a proof of the payment made by a mined transaction to the recipient, which defaults to the destination
+    /// of the transaction. The recipient must be given for transactions that pay several recipients. Only plain
+    /// one-sided payments can be proven.
+    pub async fn generate_payment_proof(
+        &mut self,
+        tx_id: TxId,
+        recipient: Option<TariAddress>,
+    ) -> Result<PaymentProof, TransactionServiceError> {
+        match self
+            .handle
+            .call(TransactionServiceRequest::GeneratePaymentProof { tx_id, recipient })
+            .await??
+        {
+            TransactionServiceResponse::PaymentProof(proof) => Ok(*proof),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Checks the signature of a payment proof and asks the base node whether its kernel and output were mined in the
+    /// same block
+    pub async fn verify_payment_proof(
+        &mut self,
+        proof: PaymentProof,
+    ) -> Result<PaymentProofVerification, TransactionServiceError> {
+        match self
+            .handle
+            .call(TransactionServiceRequest::VerifyPaymentProof(Box::new(proof)))
+            .await??
+        {
+            TransactionServiceResponse::PaymentProofVerified(verification) => Ok(verification),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
     pub async fn restart_transaction_protocols(&mut self) -> Result<(), TransactionServiceError> {
         match self
             .handle
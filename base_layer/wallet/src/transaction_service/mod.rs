@@ -68,8 +68,10 @@ use crate::{
 pub mod config;
 pub mod error;
 pub mod handle;
+pub mod partially_signed_transaction;
 pub mod protocols;
 pub mod service;
+pub mod spending_policy;
 pub mod storage;
 pub mod tasks;
 mod utc;
@@ -155,6 +157,20 @@ where
             .map(map_decode::<proto::RecipientSignedMessage>)
     }
 
+    fn transaction_nonce_reply_stream(
+        &self,
+    ) -> impl Stream<Item = DomainMessage<Result<proto::RecipientNonceMessage, prost::DecodeError>>> {
+        trace!(
+            target: LOG_TARGET,
+            "Subscription '{}' for topic '{:?}' created.",
+            SUBSCRIPTION_LABEL,
+            TariMessageType::ReceiverPartialTransactionNonce
+        );
+        self.subscription_factory
+            .get_subscription(TariMessageType::ReceiverPartialTransactionNonce, SUBSCRIPTION_LABEL)
+            .map(map_decode::<proto::RecipientNonceMessage>)
+    }
+
     fn transaction_finalized_stream(
         &self,
     ) -> impl Stream<Item = DomainMessage<Result<proto::TransactionFinalizedMessage, prost::DecodeError>>> {
@@ -209,6 +225,7 @@ where
         let (sender, receiver) = reply_channel::unbounded();
         let transaction_stream = self.transaction_stream();
         let transaction_reply_stream = self.transaction_reply_stream();
+        let transaction_nonce_reply_stream = self.transaction_nonce_reply_stream();
         let transaction_finalized_stream = self.transaction_finalized_stream();
         let base_node_response_stream = self.base_node_response_stream();
         let transaction_cancelled_stream = self.transaction_cancelled_stream();
@@ -251,6 +268,7 @@ where
                 receiver,
                 transaction_stream,
                 transaction_reply_stream,
+                transaction_nonce_reply_stream,
                 transaction_finalized_stream,
                 base_node_response_stream,
                 transaction_cancelled_stream,
@@ -0,0 +1,415 @@
+//  Copyright 2024. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use serde::{Deserialize, Serialize};
+use tari_common_types::{
+    tari_address::TariAddress,
+    transaction::TxId,
+    types::{ComAndPubSignature, Commitment, FixedHash, PrivateKey, PublicKey},
+};
+use tari_core::transactions::{
+    tari_amount::MicroMinotari,
+    transaction_components::{
+        encrypted_data::PaymentId,
+        SpentOutput,
+        Transaction,
+        TransactionInput,
+        TransactionKernel,
+        TransactionOutput,
+    },
+};
+use tari_script::ExecutionStack;
+use tari_utilities::hex::Hex;
+use thiserror::Error;
+
+use crate::output_manager_service::unsigned_transaction::UnsignedTransaction;
+
+/// The current version of the partially signed transaction format
+pub const PARTIALLY_SIGNED_TRANSACTION_VERSION: u8 = 1;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum PartiallySignedTransactionError {
+    #[error("Unsupported partially signed transaction version `{0}`")]
+    UnsupportedVersion(u8),
+    #[error("Input `{0}` is not an unspent output of this wallet")]
+    UnknownInput(String),
+    #[error("The transaction has already been signed")]
+    AlreadySigned,
+    #[error("The transaction is missing signatures and cannot be finalized")]
+    NotFullySigned,
+    #[error("The signed transaction does not spend input `{0}`")]
+    InputMismatch(String),
+    #[error("The signed transaction spends {actual} inputs, expected {expected}")]
+    InputCountMismatch { expected: usize, actual: usize },
+    #[error("The signed transaction must have exactly one kernel, found {0}")]
+    InvalidKernelCount(usize),
+    #[error("The kernel nonce has not been committed, sign the transaction once to commit it")]
+    KernelNonceNotCommitted,
+    #[error("The kernel nonce has already been committed")]
+    KernelNonceAlreadyCommitted,
+    #[error("The kernel nonce of transaction `{0}` was not committed by this wallet")]
+    NotCommittedByThisWallet(TxId),
+    #[error("The kernel signature was not made with the committed kernel nonce")]
+    KernelNonceMismatch,
+    #[error("The finalized transaction is invalid: `{0}`")]
+    InvalidTransaction(String),
+}
+
+/// An input of a partially signed transaction: the output being spent, exactly as it appears on the blockchain, and a
+/// slot for the script signature the signer produces when spending it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PartiallySignedInput {
+    pub output: TransactionOutput,
+    pub mined_height: Option<u64>,
+    pub input_data: Option<ExecutionStack>,
+    pub script_signature: Option<ComAndPubSignature>,
+}
+
+impl PartiallySignedInput {
+    pub fn new(output: TransactionOutput, mined_height: Option<u64>) -> Self {
+        Self {
+            output,
+            mined_height,
+            input_data: None,
+            script_signature: None,
+        }
+    }
+
+    pub fn commitment(&self) -> &Commitment {
+        &self.output.commitment
+    }
+
+    pub fn is_signed(&self) -> bool {
+        self.input_data.is_some() && self.script_signature.is_some()
+    }
+
+    fn to_transaction_input(&self) -> Option<TransactionInput> {
+        let output = &self.output;
+        let rangeproof_hash = match &output.proof {
+            Some(rp) => rp.hash(),
+            None => FixedHash::zero(),
+        };
+        Some(TransactionInput::new_current_version(
+            SpentOutput::OutputData {
+                features: output.features.clone(),
+                commitment: output.commitment.clone(),
+                script: output.script.clone(),
+                sender_offset_public_key: output.sender_offset_public_key.clone(),
+                covenant: output.covenant.clone(),
+                version: output.version,
+                encrypted_data: output.encrypted_data.clone(),
+                metadata_signature: output.metadata_signature.clone(),
+                rangeproof_hash,
+                minimum_value_promise: output.minimum_value_promise,
+            },
+            self.input_data.clone()?,
+            self.script_signature.clone()?,
+        ))
+    }
+}
+
+/// A versioned container that carries a payment between wallets for offline signing. A watch-only wallet creates it
+/// with only the inputs filled in, a wallet holding the spend key (including a Ledger wallet) fills in the signature
+/// slots, the outputs and the kernel, and any wallet can then finalize and broadcast it. Signing takes two rounds: the
+/// first commits to the kernel nonce, the second fills in signatures that must have been made with that nonce.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PartiallySignedTransaction {
+    pub version: u8,
+    pub tx_id: TxId,
+    pub recipient_address: TariAddress,
+    pub amount: MicroMinotari,
+    pub fee_per_gram: MicroMinotari,
+    pub fee: MicroMinotari,
+    pub payment_id: PaymentId,
+    pub message: String,
+    pub inputs: Vec<PartiallySignedInput>,
+    /// The recipient and change outputs, each carrying its metadata signature, filled in by the signer
+    pub outputs: Vec<TransactionOutput>,
+    /// The public nonce the kernel excess signature commits to, recorded in the first signing round
+    pub kernel_nonce: Option<PublicKey>,
+    pub kernel: Option<TransactionKernel>,
+    pub offset: Option<PrivateKey>,
+    pub script_offset: Option<PrivateKey>,
+}
+
+impl PartiallySignedTransaction {
+    /// Wraps an unsigned transaction; `inputs` must hold the on-chain outputs it spends
+    pub fn new(unsigned: UnsignedTransaction, inputs: Vec<PartiallySignedInput>, payment_id: PaymentId) -> Self {
+        Self {
+            version: PARTIALLY_SIGNED_TRANSACTION_VERSION,
+            tx_id: unsigned.tx_id,
+            recipient_address: unsigned.recipient_address,
+            amount: unsigned.amount,
+            fee_per_gram: unsigned.fee_per_gram,
+            fee: unsigned.fee,
+            payment_id,
+            message: unsigned.message,
+            inputs,
+            outputs: Vec::new(),
+            kernel_nonce: None,
+            kernel: None,
+            offset: None,
+            script_offset: None,
+        }
+    }
+
+    pub fn check_version(&self) -> Result<(), PartiallySignedTransactionError> {
+        if self.version != PARTIALLY_SIGNED_TRANSACTION_VERSION {
+            return Err(PartiallySignedTransactionError::UnsupportedVersion(self.version));
+        }
+        Ok(())
+    }
+
+    pub fn input_commitments(&self) -> Vec<Commitment> {
+        self.inputs.iter().map(|i| i.commitment().clone()).collect()
+    }
+
+    pub fn is_fully_signed(&self) -> bool {
+        self.inputs.iter().all(PartiallySignedInput::is_signed) &&
+            !self.outputs.is_empty() &&
+            self.kernel_nonce.is_some() &&
+            self.kernel.is_some() &&
+            self.offset.is_some() &&
+            self.script_offset.is_some()
+    }
+
+    pub fn is_kernel_nonce_committed(&self) -> bool {
+        self.kernel_nonce.is_some()
+    }
+
+    /// Records the public kernel nonce the signer will sign with, before any signature is released
+    pub fn commit_kernel_nonce(&mut self, kernel_nonce: PublicKey) -> Result<(), PartiallySignedTransactionError> {
+        self.check_version()?;
+        if self.is_kernel_nonce_committed() {
+            return Err(PartiallySignedTransactionError::KernelNonceAlreadyCommitted);
+        }
+        self.kernel_nonce = Some(kernel_nonce);
+        Ok(())
+    }
+
+    /// Fills in the signature slots from a transaction the signer built over exactly these inputs with the committed
+    /// kernel nonce
+    pub fn apply_signed_transaction(
+        &mut self,
+        transaction: &Transaction,
+    ) -> Result<(), PartiallySignedTransactionError> {
+        self.check_version()?;
+        if self.is_fully_signed() {
+            return Err(PartiallySignedTransactionError::AlreadySigned);
+        }
+        let kernel_nonce = self
+            .kernel_nonce
+            .as_ref()
+            .ok_or(PartiallySignedTransactionError::KernelNonceNotCommitted)?;
+        let kernels = transaction.body.kernels();
+        if kernels.len() != 1 {
+            return Err(PartiallySignedTransactionError::InvalidKernelCount(kernels.len()));
+        }
+        if kernels[0].excess_sig.get_public_nonce() != kernel_nonce {
+            return Err(PartiallySignedTransactionError::KernelNonceMismatch);
+        }
+        let signed_inputs = transaction.body.inputs();
+        if signed_inputs.len() != self.inputs.len() {
+            return Err(PartiallySignedTransactionError::InputCountMismatch {
+                expected: self.inputs.len(),
+                actual: signed_inputs.len(),
+            });
+        }
+        for input in &mut self.inputs {
+            let signed = signed_inputs
+                .iter()
+                .find(|i| i.commitment().ok() == Some(input.commitment()))
+                .ok_or_else(|| PartiallySignedTransactionError::InputMismatch(input.commitment().to_hex()))?;
+            input.input_data = Some(signed.input_data.clone());
+            input.script_signature = Some(signed.script_signature.clone());
+        }
+        self.outputs = transaction.body.outputs().clone();
+        self.kernel = Some(kernels[0].clone());
+        self.offset = Some(transaction.offset.clone());
+        self.script_offset = Some(transaction.script_offset.clone());
+        Ok(())
+    }
+
+    /// Assembles the final transaction from the filled signature slots and checks the kernel
+    pub fn to_transaction(&self) -> Result<Transaction, PartiallySignedTransactionError> {
+        self.check_version()?;
+        if !self.is_fully_signed() {
+            return Err(PartiallySignedTransactionError::NotFullySigned);
+        }
+        let kernel = self
+            .kernel
+            .clone()
+            .ok_or(PartiallySignedTransactionError::NotFullySigned)?;
+        if self.kernel_nonce.as_ref() != Some(kernel.excess_sig.get_public_nonce()) {
+            return Err(PartiallySignedTransactionError::KernelNonceMismatch);
+        }
+        let inputs = self
+            .inputs
+            .iter()
+            .map(PartiallySignedInput::to_transaction_input)
+            .collect::<Option<Vec<_>>>()
+            .ok_or(PartiallySignedTransactionError::NotFullySigned)?;
+        let offset = self
+            .offset
+            .clone()
+            .ok_or(PartiallySignedTransactionError::NotFullySigned)?;
+        let script_offset = self
+            .script_offset
+            .clone()
+            .ok_or(PartiallySignedTransactionError::NotFullySigned)?;
+        let transaction = Transaction::new(inputs, self.outputs.clone(), vec![kernel], offset, script_offset);
+
+        // Catch tampering or a mismatched signer early, the base node performs full validation on submission
+        transaction
+            .body
+            .verify_kernel_signatures()
+            .map_err(|e| PartiallySignedTransactionError::InvalidTransaction(e.to_string()))?;
+        if transaction.body.contains_duplicated_inputs() || transaction.body.contains_duplicated_outputs() {
+            return Err(PartiallySignedTransactionError::InvalidTransaction(
+                "Duplicated inputs or outputs".to_string(),
+            ));
+        }
+        let fee = transaction
+            .body
+            .get_total_fee()
+            .map_err(|e| PartiallySignedTransactionError::InvalidTransaction(e.to_string()))?;
+        if fee != self.fee {
+            return Err(PartiallySignedTransactionError::InvalidTransaction(format!(
+                "Kernel fee {} does not match the recorded fee {}",
+                fee, self.fee
+            )));
+        }
+        Ok(transaction)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::OsRng;
+    use tari_crypto::keys::PublicKey as PublicKeyTrait;
+
+    use super::*;
+    use crate::output_manager_service::unsigned_transaction::UNSIGNED_TRANSACTION_VERSION;
+
+    fn unsigned_transaction() -> PartiallySignedTransaction {
+        let unsigned = UnsignedTransaction {
+            version: UNSIGNED_TRANSACTION_VERSION,
+            tx_id: TxId::from(7u64),
+            recipient_address: TariAddress::default(),
+            amount: MicroMinotari::from(5_000),
+            fee_per_gram: MicroMinotari::from(5),
+            fee: MicroMinotari::from(500),
+            change: MicroMinotari::from(4_500),
+            message: "offline".to_string(),
+            inputs: vec![],
+        };
+        let inputs = vec![PartiallySignedInput::new(TransactionOutput::default(), Some(3))];
+        PartiallySignedTransaction::new(unsigned, inputs, PaymentId::Empty)
+    }
+
+    #[test]
+    fn it_round_trips_through_json() {
+        let pst = unsigned_transaction();
+        let json = serde_json::to_string(&pst).unwrap();
+        let decoded: PartiallySignedTransaction = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, pst);
+    }
+
+    #[test]
+    fn it_refuses_to_finalize_without_signatures() {
+        let pst = unsigned_transaction();
+        assert!(!pst.is_fully_signed());
+        assert_eq!(
+            pst.to_transaction().unwrap_err(),
+            PartiallySignedTransactionError::NotFullySigned
+        );
+    }
+
+    #[test]
+    fn it_rejects_unknown_versions() {
+        let mut pst = unsigned_transaction();
+        pst.version = PARTIALLY_SIGNED_TRANSACTION_VERSION + 1;
+        assert_eq!(
+            pst.to_transaction().unwrap_err(),
+            PartiallySignedTransactionError::UnsupportedVersion(PARTIALLY_SIGNED_TRANSACTION_VERSION + 1)
+        );
+    }
+
+    #[test]
+    fn it_rejects_a_transaction_with_the_wrong_inputs() {
+        let mut pst = unsigned_transaction();
+        pst.commit_kernel_nonce(PublicKey::default()).unwrap();
+        let tx = Transaction::new(
+            vec![],
+            vec![],
+            vec![TransactionKernel::default()],
+            PrivateKey::default(),
+            PrivateKey::default(),
+        );
+        assert_eq!(
+            pst.apply_signed_transaction(&tx).unwrap_err(),
+            PartiallySignedTransactionError::InputCountMismatch { expected: 1, actual: 0 }
+        );
+    }
+
+    #[test]
+    fn it_requires_a_committed_kernel_nonce() {
+        let mut pst = unsigned_transaction();
+        let tx = Transaction::new(
+            vec![],
+            vec![],
+            vec![TransactionKernel::default()],
+            PrivateKey::default(),
+            PrivateKey::default(),
+        );
+        assert_eq!(
+            pst.apply_signed_transaction(&tx).unwrap_err(),
+            PartiallySignedTransactionError::KernelNonceNotCommitted
+        );
+        pst.commit_kernel_nonce(PublicKey::default()).unwrap();
+        assert_eq!(
+            pst.commit_kernel_nonce(PublicKey::default()).unwrap_err(),
+            PartiallySignedTransactionError::KernelNonceAlreadyCommitted
+        );
+    }
+
+    #[test]
+    fn it_rejects_a_signature_with_a_different_kernel_nonce() {
+        let mut pst = unsigned_transaction();
+        let (_, committed_nonce) = PublicKey::random_keypair(&mut OsRng);
+        pst.commit_kernel_nonce(committed_nonce.clone()).unwrap();
+        // The default kernel is signed with the default nonce, not the committed one
+        let tx = Transaction::new(
+            vec![],
+            vec![],
+            vec![TransactionKernel::default()],
+            PrivateKey::default(),
+            PrivateKey::default(),
+        );
+        assert_eq!(
+            pst.apply_signed_transaction(&tx).unwrap_err(),
+            PartiallySignedTransactionError::KernelNonceMismatch
+        );
+        assert_eq!(pst.kernel_nonce, Some(committed_nonce));
+        assert!(pst.kernel.is_none());
+    }
+}
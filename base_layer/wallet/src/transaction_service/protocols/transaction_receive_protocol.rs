@@ -32,8 +32,9 @@ use tari_common_types::{
 use tari_core::transactions::{
     key_manager::TransactionKeyManagerInterface,
     transaction_components::Transaction,
-    transaction_protocol::{recipient::RecipientState, sender::TransactionSenderMessage},
+    transaction_protocol::{proto::protocol as proto, recipient::RecipientState, sender::TransactionSenderMessage},
 };
+use tari_p2p::tari_message::TariMessageType;
 use tokio::{
     sync::{mpsc, oneshot},
     time::sleep,
@@ -50,7 +51,7 @@ use crate::{
             database::TransactionBackend,
             models::{CompletedTransaction, InboundTransaction, TxCancellationReason},
         },
-        tasks::send_transaction_reply::send_transaction_reply,
+        tasks::{send_multi_round_message::send_multi_round_message, send_transaction_reply::send_transaction_reply},
         utc::utc_duration_since,
     },
 };
@@ -119,8 +120,13 @@ where
     }
 
     async fn accept_transaction(&mut self) -> Result<(), TransactionServiceProtocolError<TxId>> {
-        // Currently we will only reply to a Single sender transaction protocol
-        if let TransactionSenderMessage::Single(data) = self.sender_message.clone() {
+        // We reply to a Single sender transaction protocol, or to the first round of a multi-recipient protocol
+        let data = match self.sender_message.clone() {
+            TransactionSenderMessage::Single(data) => Some(*data),
+            TransactionSenderMessage::Multiple(data) => Some(data.sender_data),
+            _ => None,
+        };
+        if let Some(data) = data {
             // Check this is not a repeat message i.e. tx_id doesn't already exist in our pending or completed
             // transactions
             if self
@@ -170,14 +176,10 @@ where
                 .add_pending_inbound_transaction(inbound_transaction.tx_id, inbound_transaction.clone())
                 .map_err(|e| TransactionServiceProtocolError::new(self.id, TransactionServiceError::from(e)))?;
 
-            let send_result = send_transaction_reply(
-                inbound_transaction,
-                self.resources.outbound_message_service.clone(),
-                self.resources.config.direct_send_timeout,
-                self.resources.config.transaction_routing_mechanism,
-            )
-            .await
-            .map_err(|e| TransactionServiceProtocolError::new(self.id, e))?;
+            let send_result = self
+                .send_reply(inbound_transaction)
+                .await
+                .map_err(|e| TransactionServiceProtocolError::new(self.id, e))?;
 
             self.resources
                 .db
@@ -236,7 +238,7 @@ where
             .ok_or_else(|| TransactionServiceProtocolError::new(self.id, TransactionServiceError::InvalidStateError))?
             .fuse();
 
-        let inbound_tx = match self.resources.db.get_pending_inbound_transaction(self.id) {
+        let mut inbound_tx = match self.resources.db.get_pending_inbound_transaction(self.id) {
             Ok(tx) => tx,
             Err(_e) => {
                 debug!(
@@ -284,14 +286,7 @@ where
         };
 
         if resend {
-            if let Err(e) = send_transaction_reply(
-                inbound_tx.clone(),
-                self.resources.outbound_message_service.clone(),
-                self.resources.config.direct_send_timeout,
-                self.resources.config.transaction_routing_mechanism,
-            )
-            .await
-            {
+            if let Err(e) = self.send_reply(inbound_tx.clone()).await {
                 warn!(
                     target: LOG_TARGET,
                     "Error resending Transaction Reply (TxId: {}): {:?}", self.id, e
@@ -333,13 +328,8 @@ where
                         ));
                     },
                     _ = resend_timeout => {
-                        match send_transaction_reply(
-                            inbound_tx.clone(),
-                            self.resources.outbound_message_service.clone(),
-                            self.resources.config.direct_send_timeout,
-                            self.resources.config.transaction_routing_mechanism,
-                        )
-                        .await {
+                        self.refresh_multi_recipient_state(&mut inbound_tx);
+                        match self.send_reply(inbound_tx.clone()).await {
                             Ok(_) => self.resources
                                         .db
                                         .increment_send_count(self.id)
@@ -371,10 +361,11 @@ where
                 self.source_address.clone()
             );
 
+            self.refresh_multi_recipient_state(&mut inbound_tx);
             // Find your own output in the transaction
             let rtp_output = match inbound_tx.receiver_protocol.state.clone() {
                 RecipientState::Finalized(s) => s.output,
-                RecipientState::Failed(_) => {
+                RecipientState::Failed(_) | RecipientState::AwaitingSignatureRequest(_) => {
                     warn!(
                         target: LOG_TARGET,
                         "Finalized Transaction TxId: {} is not in the correct state to be completed", self.id
@@ -476,6 +467,39 @@ where
         Ok(())
     }
 
+    /// Send our reply to the sender. While a multi-recipient transaction is waiting for the sender's signature
+    /// request the reply is our public nonce, otherwise it is our signed output.
+    async fn send_reply(&self, inbound_tx: InboundTransaction) -> Result<bool, TransactionServiceError> {
+        if inbound_tx.receiver_protocol.is_awaiting_signature_request() {
+            let nonce_message = proto::RecipientNonceMessage::from(inbound_tx.receiver_protocol.get_nonce_message()?);
+            send_multi_round_message(
+                TariMessageType::ReceiverPartialTransactionNonce,
+                nonce_message,
+                inbound_tx.source_address.comms_public_key().clone(),
+                self.resources.outbound_message_service.clone(),
+            )
+            .await?;
+            return Ok(true);
+        }
+        send_transaction_reply(
+            inbound_tx,
+            self.resources.outbound_message_service.clone(),
+            self.resources.config.direct_send_timeout,
+            self.resources.config.transaction_routing_mechanism,
+        )
+        .await
+    }
+
+    /// The signature request of a multi-recipient transaction is applied to the stored receiver protocol by the
+    /// service, so reload it if our copy is still waiting for it.
+    fn refresh_multi_recipient_state(&self, inbound_tx: &mut InboundTransaction) {
+        if inbound_tx.receiver_protocol.is_awaiting_signature_request() {
+            if let Ok(tx) = self.resources.db.get_pending_inbound_transaction(self.id) {
+                *inbound_tx = tx;
+            }
+        }
+    }
+
     async fn timeout_transaction(&mut self) -> Result<(), TransactionServiceProtocolError<TxId>> {
         info!(
             target: LOG_TARGET,
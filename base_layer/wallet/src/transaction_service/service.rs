@@ -22,7 +22,7 @@
 
 use std::{
     collections::{HashMap, HashSet},
-    convert::TryInto,
+    convert::{TryFrom, TryInto},
     sync::Arc,
     time::{Duration, Instant},
 };
@@ -45,13 +45,17 @@ use tari_common_types::{
 use tari_comms::{types::CommsPublicKey, NodeIdentity};
 use tari_comms_dht::outbound::OutboundMessageRequester;
 use tari_core::{
+    base_node::proto::wallet_rpc::{TxLocation, TxQueryResponse},
     consensus::ConsensusManager,
     covenants::Covenant,
     mempool::FeePerGramStat,
     one_sided::{shared_secret_to_output_encryption_key, shared_secret_to_output_spending_key},
-    proto::{base_node as base_node_proto, base_node::FetchMatchingUtxos},
+    proto::{
+        base_node as base_node_proto,
+        base_node::{FetchMatchingUtxos, UtxoQueryRequest},
+    },
     transactions::{
-        key_manager::TransactionKeyManagerInterface,
+        key_manager::{TariKeyId, TransactionKeyManagerInterface},
         tari_amount::MicroMinotari,
         transaction_components::{
             encrypted_data::PaymentId,
@@ -60,25 +64,27 @@ use tari_core::{
             OutputFeatures,
             Transaction,
             TransactionOutput,
+            WalletOutput,
             WalletOutputBuilder,
         },
         transaction_protocol::{
             proto::protocol as proto,
-            recipient::RecipientSignedMessage,
-            sender::TransactionSenderMessage,
+            recipient::{RecipientNonceMessage, RecipientSignedMessage},
+            sender::{MultiRoundSignatureRequest, TransactionSenderMessage},
             TransactionMetadata,
         },
         CryptoFactories,
+        PaymentProof,
         ReceiverTransactionProtocol,
     },
 };
 use tari_crypto::{
     keys::{PublicKey as PKtrait, SecretKey},
     ristretto::pedersen::PedersenCommitment,
-    tari_utilities::ByteArray,
+    tari_utilities::{hex::Hex, ByteArray},
 };
 use tari_key_manager::key_manager_service::KeyId;
-use tari_p2p::domain_message::DomainMessage;
+use tari_p2p::{domain_message::DomainMessage, tari_message::TariMessageType};
 use tari_script::{push_pubkey_script, script, CheckSigSchnorrSignature, ExecutionStack, ScriptContext, TariScript};
 use tari_service_framework::{reply_channel, reply_channel::Receiver};
 use tari_shutdown::ShutdownSignal;
@@ -92,6 +98,7 @@ use crate::{
     connectivity_service::WalletConnectivityInterface,
     output_manager_service::{
         handle::{OutputManagerEvent, OutputManagerHandle},
+        multisig_account::{multisig_encryption_key, multisig_script, sorted_cosigner_keys},
         storage::models::SpendingPriority,
         UtxoSelectionCriteria,
     },
@@ -101,11 +108,18 @@ use crate::{
         error::{TransactionServiceError, TransactionServiceProtocolError},
         handle::{
             FeePerGramStatsResponse,
+            OneSidedRecipient,
+            PaymentProofVerification,
             TransactionEvent,
             TransactionEventSender,
             TransactionServiceRequest,
             TransactionServiceResponse,
         },
+        partially_signed_transaction::{
+            PartiallySignedInput,
+            PartiallySignedTransaction,
+            PartiallySignedTransactionError,
+        },
         protocols::{
             check_transaction_size,
             transaction_broadcast_protocol::TransactionBroadcastProtocol,
@@ -113,17 +127,21 @@ use crate::{
             transaction_send_protocol::{TransactionSendProtocol, TransactionSendProtocolStage},
             transaction_validation_protocol::TransactionValidationProtocol,
         },
+        spending_policy::{daily_period_start, LargeSendApproval, PolicySpend, SpendingPolicyViolation},
         storage::{
             database::{TransactionBackend, TransactionDatabase},
             models::{
                 CompletedTransaction,
+                OutboundTransaction,
                 TxCancellationReason,
                 WalletTransaction::{Completed, PendingInbound, PendingOutbound},
             },
         },
         tasks::{
             check_faux_transaction_status::check_detected_transactions,
+            multi_recipient_timeout::multi_recipient_transaction_timeout,
             send_finalized_transaction::send_finalized_transaction_message,
+            send_multi_round_message::send_multi_round_message,
             send_transaction_cancelled::send_transaction_cancelled_message,
             send_transaction_reply::send_transaction_reply,
         },
@@ -136,6 +154,9 @@ use crate::{
 
 const LOG_TARGET: &str = "wallet::transaction_service::service";
 
+/// Prefix of the client key under which a partially signed transaction is kept between the two signing rounds
+const PENDING_PARTIALLY_SIGNED_TRANSACTION_KEY: &str = "pending_partially_signed_transaction";
+
 /// TransactionService allows for the management of multiple inbound and outbound transaction protocols
 /// which are uniquely identified by a tx_id. The TransactionService generates and accepts the various protocol
 /// messages and applies them to the appropriate protocol instances based on the tx_id.
@@ -152,6 +173,7 @@ const LOG_TARGET: &str = "wallet::transaction_service::service";
 pub struct TransactionService<
     TTxStream,
     TTxReplyStream,
+    TTxNonceReplyStream,
     TTxFinalizedStream,
     BNResponseStream,
     TBackend,
@@ -164,6 +186,7 @@ pub struct TransactionService<
     db: TransactionDatabase<TBackend>,
     transaction_stream: Option<TTxStream>,
     transaction_reply_stream: Option<TTxReplyStream>,
+    transaction_nonce_reply_stream: Option<TTxNonceReplyStream>,
     transaction_finalized_stream: Option<TTxFinalizedStream>,
     base_node_response_stream: Option<BNResponseStream>,
     transaction_cancelled_stream: Option<TTxCancelledStream>,
@@ -190,6 +213,7 @@ pub struct TransactionService<
 impl<
         TTxStream,
         TTxReplyStream,
+        TTxNonceReplyStream,
         TTxFinalizedStream,
         BNResponseStream,
         TBackend,
@@ -201,6 +225,7 @@ impl<
     TransactionService<
         TTxStream,
         TTxReplyStream,
+        TTxNonceReplyStream,
         TTxFinalizedStream,
         BNResponseStream,
         TBackend,
@@ -212,6 +237,7 @@ impl<
 where
     TTxStream: Stream<Item = DomainMessage<Result<proto::TransactionSenderMessage, prost::DecodeError>>>,
     TTxReplyStream: Stream<Item = DomainMessage<Result<proto::RecipientSignedMessage, prost::DecodeError>>>,
+    TTxNonceReplyStream: Stream<Item = DomainMessage<Result<proto::RecipientNonceMessage, prost::DecodeError>>>,
     TTxFinalizedStream: Stream<Item = DomainMessage<Result<proto::TransactionFinalizedMessage, prost::DecodeError>>>,
     BNResponseStream:
         Stream<Item = DomainMessage<Result<base_node_proto::BaseNodeServiceResponse, prost::DecodeError>>>,
@@ -231,6 +257,7 @@ where
         >,
         transaction_stream: TTxStream,
         transaction_reply_stream: TTxReplyStream,
+        transaction_nonce_reply_stream: TTxNonceReplyStream,
         transaction_finalized_stream: TTxFinalizedStream,
         base_node_response_stream: BNResponseStream,
         transaction_cancelled_stream: TTxCancelledStream,
@@ -293,6 +320,7 @@ where
             db,
             transaction_stream: Some(transaction_stream),
             transaction_reply_stream: Some(transaction_reply_stream),
+            transaction_nonce_reply_stream: Some(transaction_nonce_reply_stream),
             transaction_finalized_stream: Some(transaction_finalized_stream),
             base_node_response_stream: Some(base_node_response_stream),
             transaction_cancelled_stream: Some(transaction_cancelled_stream),
@@ -334,6 +362,12 @@ where
             .expect("Transaction Service initialized without transaction_reply_stream")
             .fuse();
         pin_mut!(transaction_reply_stream);
+        let transaction_nonce_reply_stream = self
+            .transaction_nonce_reply_stream
+            .take()
+            .expect("Transaction Service initialized without transaction_nonce_reply_stream")
+            .fuse();
+        pin_mut!(transaction_nonce_reply_stream);
         let transaction_finalized_stream = self
             .transaction_finalized_stream
             .take()
@@ -419,7 +453,7 @@ where
                     trace!(target: LOG_TARGET, "Handling Transaction Message, Trace: {}", msg.dht_header.message_tag);
 
                     let result  = self.accept_transaction(origin_public_key, inner_msg,
-                        msg.dht_header.message_tag.as_value(), &mut receive_transaction_protocol_handles);
+                        msg.dht_header.message_tag.as_value(), &mut receive_transaction_protocol_handles).await;
 
                     match result {
                         Err(TransactionServiceError::RepeatedMessageError) => {
@@ -445,7 +479,7 @@ where
                     let start = Instant::now();
                     let (origin_public_key, inner_msg) = msg.clone().into_origin_and_inner();
                     trace!(target: LOG_TARGET, "Handling Transaction Reply Message, Trace: {}", msg.dht_header.message_tag);
-                    let result = self.accept_recipient_reply(origin_public_key, inner_msg).await;
+                    let result = self.accept_recipient_reply(origin_public_key, inner_msg, &mut transaction_broadcast_protocol_handles).await;
 
                     match result {
                         Err(TransactionServiceError::TransactionDoesNotExistError) => {
@@ -469,6 +503,39 @@ where
                         start.elapsed().as_millis(),
                     );
                 },
+                // Incoming multi-recipient Transaction Nonce messages from the Comms layer
+                Some(msg) = transaction_nonce_reply_stream.next() => {
+                    let start = Instant::now();
+                    let (origin_public_key, inner_msg) = msg.clone().into_origin_and_inner();
+                    trace!(target: LOG_TARGET, "Handling Transaction Nonce Message, Trace: {}", msg.dht_header.message_tag);
+                    let result = self.accept_recipient_nonce_reply(origin_public_key, inner_msg).await;
+
+                    match result {
+                        Err(TransactionServiceError::RepeatedMessageError) => {
+                            trace!(target: LOG_TARGET, "A repeated Transaction Nonce message was received, Trace: {}",
+                            msg.dht_header.message_tag);
+                        },
+                        Err(TransactionServiceError::TransactionDoesNotExistError) => {
+                            trace!(target: LOG_TARGET, "Unable to handle incoming Transaction Nonce message from NodeId: \
+                            {} due to Transaction not existing. This usually means the message was a repeated message \
+                            from Store and Forward, Trace: {}", self.resources.node_identity.node_id().short_str(),
+                            msg.dht_header.message_tag);
+                        },
+                        Err(e) => {
+                            warn!(target: LOG_TARGET, "Failed to handle incoming Transaction Nonce message: {} \
+                            for NodeId: {}, Trace: {}", e, self.resources.node_identity.node_id().short_str(),
+                            msg.dht_header.message_tag);
+                            let _size = self.event_publisher.send(Arc::new(TransactionEvent::Error("Error handling \
+                            Transaction Recipient Nonce message".to_string())));
+                        },
+                        Ok(_) => (),
+                    }
+                    trace!(target: LOG_TARGET,
+                        "Handling Transaction Nonce Message, Trace: {}, processed in {}ms",
+                        msg.dht_header.message_tag,
+                        start.elapsed().as_millis(),
+                    );
+                },
                // Incoming Finalized Transaction messages from the Comms layer
                 Some(msg) = transaction_finalized_stream.next() => {
                     let start = Instant::now();
@@ -602,9 +669,26 @@ where
         >,
         reply_channel: oneshot::Sender<Result<TransactionServiceResponse, TransactionServiceError>>,
     ) -> Result<(), TransactionServiceError> {
+        trace!(target: LOG_TARGET, "Handling Service Request: {}", request);
+        if request.spends_funds() && self.resources.wallet_type.is_watch_only() {
+            let _result = reply_channel
+                .send(Err(TransactionServiceError::WatchOnlyWallet))
+                .inspect_err(|_| {
+                    warn!(target: LOG_TARGET, "Failed to send reply");
+                });
+            return Ok(());
+        }
+        if let Some(spend) = request.policy_spend() {
+            if let Err(e) = self.check_spending_policy(&spend) {
+                warn!(target: LOG_TARGET, "Rejected request {}: {}", request, e);
+                let _result = reply_channel.send(Err(e)).inspect_err(|_| {
+                    warn!(target: LOG_TARGET, "Failed to send reply");
+                });
+                return Ok(());
+            }
+        }
         let mut reply_channel = Some(reply_channel);
 
-        trace!(target: LOG_TARGET, "Handling Service Request: {}", request);
         let response = match request {
             TransactionServiceRequest::SendTransaction {
                 destination,
@@ -651,6 +735,30 @@ where
                 )
                 .await
                 .map(TransactionServiceResponse::TransactionSent),
+            TransactionServiceRequest::SendBatchOneSided {
+                recipients,
+                selection_criteria,
+                fee_per_gram,
+                message,
+            } => self
+                .send_batch_one_sided_transaction(
+                    recipients,
+                    selection_criteria,
+                    fee_per_gram,
+                    message,
+                    transaction_broadcast_join_handles,
+                )
+                .await
+                .map(TransactionServiceResponse::TransactionSent),
+            TransactionServiceRequest::SendTransactionToMultipleRecipients {
+                recipients,
+                selection_criteria,
+                fee_per_gram,
+                message,
+            } => self
+                .send_transaction_to_multiple_recipients(recipients, selection_criteria, fee_per_gram, message)
+                .await
+                .map(TransactionServiceResponse::TransactionSent),
             TransactionServiceRequest::SendOneSidedToStealthAddressTransaction {
                 destination,
                 amount,
@@ -672,6 +780,25 @@ where
                 )
                 .await
                 .map(TransactionServiceResponse::TransactionSent),
+            TransactionServiceRequest::SendToMultisigAccount {
+                threshold,
+                public_keys,
+                amount,
+                selection_criteria,
+                fee_per_gram,
+                message,
+            } => self
+                .send_to_multisig_account(
+                    threshold,
+                    public_keys,
+                    amount,
+                    selection_criteria,
+                    fee_per_gram,
+                    message,
+                    transaction_broadcast_join_handles,
+                )
+                .await
+                .map(TransactionServiceResponse::TransactionSent),
             TransactionServiceRequest::BurnTari {
                 amount,
                 selection_criteria,
@@ -827,6 +954,32 @@ where
                 )
                 .await?,
             )),
+            TransactionServiceRequest::CreatePartiallySignedTransaction {
+                destination,
+                amount,
+                selection_criteria,
+                fee_per_gram,
+                message,
+                payment_id,
+            } => self
+                .create_partially_signed_transaction(
+                    destination,
+                    amount,
+                    selection_criteria,
+                    fee_per_gram,
+                    message,
+                    payment_id,
+                )
+                .await
+                .map(|tx| TransactionServiceResponse::PartiallySignedTransaction(Box::new(tx))),
+            TransactionServiceRequest::SignPartiallySignedTransaction(tx) => self
+                .sign_partially_signed_transaction(*tx)
+                .await
+                .map(|tx| TransactionServiceResponse::PartiallySignedTransaction(Box::new(tx))),
+            TransactionServiceRequest::FinalizePartiallySignedTransaction(tx) => self
+                .finalize_partially_signed_transaction(*tx, transaction_broadcast_join_handles)
+                .await
+                .map(TransactionServiceResponse::TransactionSent),
             TransactionServiceRequest::CancelTransaction(tx_id) => self
                 .cancel_pending_transaction(tx_id)
                 .await
@@ -860,6 +1013,9 @@ where
             TransactionServiceRequest::GetAnyTransaction(tx_id) => Ok(TransactionServiceResponse::AnyTransaction(
                 Box::new(self.db.get_any_transaction(tx_id)?),
             )),
+            TransactionServiceRequest::ExportTransactions(filter) => Ok(
+                TransactionServiceResponse::ExportedTransactions(self.db.export_transactions(&filter)?),
+            ),
             TransactionServiceRequest::ImportTransaction(tx) => {
                 let tx_id = match tx {
                     PendingInbound(inbound_tx) => {
@@ -948,6 +1104,25 @@ where
                 self.handle_get_fee_per_gram_stats_per_block_request(count, reply_channel);
                 return Ok(());
             },
+            TransactionServiceRequest::GetSpendingPolicy => self
+                .db
+                .get_spending_policy()
+                .map(|policy| TransactionServiceResponse::SpendingPolicy(Box::new(policy)))
+                .map_err(TransactionServiceError::from),
+            TransactionServiceRequest::SetSpendingPolicy(policy) => self
+                .db
+                .set_spending_policy(&policy)
+                .map(|_| TransactionServiceResponse::SpendingPolicySet)
+                .map_err(TransactionServiceError::from),
+            TransactionServiceRequest::GeneratePaymentProof { tx_id, recipient } => self
+                .generate_payment_proof(tx_id, recipient)
+                .await
+                .map(|proof| TransactionServiceResponse::PaymentProof(Box::new(proof))),
+            TransactionServiceRequest::VerifyPaymentProof(proof) => {
+                let reply_channel = reply_channel.take().expect("reply_channel is Some");
+                self.handle_verify_payment_proof_request(*proof, reply_channel);
+                return Ok(());
+            },
         };
 
         // If the individual handlers did not already send the API response then do it here.
@@ -959,6 +1134,182 @@ where
         Ok(())
     }
 
+    /// Creates a proof of the payment made by a mined transaction to the recipient, which defaults to the destination
+    /// of the transaction. The proof is for the output locked to the recipient's public spend key, i.e. a one-sided
+    /// payment, and is signed with the commitment mask of that output. The mask and the amount are decrypted from the
+    /// output with the Diffie-Hellman shared secret, which only the sender and the recipient of the payment can derive.
+    /// Stealth one-sided outputs are locked to a one-time key rather than the recipient's public spend key, so they
+    /// cannot be proven.
+    async fn generate_payment_proof(
+        &self,
+        tx_id: TxId,
+        recipient: Option<TariAddress>,
+    ) -> Result<PaymentProof, TransactionServiceError> {
+        let completed_tx = self.db.get_completed_transaction(tx_id)?;
+        if completed_tx.mined_in_block.is_none() {
+            return Err(TransactionServiceError::PaymentProofError(format!(
+                "Transaction {} has not been mined",
+                tx_id
+            )));
+        }
+        let kernel = completed_tx.transaction.body.kernels().first().ok_or_else(|| {
+            TransactionServiceError::PaymentProofError(format!("Transaction {} has no kernel", tx_id))
+        })?;
+        let recipient = recipient.unwrap_or_else(|| completed_tx.destination_address.clone());
+        let recipient_script = push_pubkey_script(recipient.public_spend_key());
+        let key_manager = &self.resources.transaction_key_manager_service;
+
+        for output in completed_tx.transaction.body.outputs() {
+            if output.script != recipient_script {
+                continue;
+            }
+            let shared_secret = match completed_tx.direction {
+                TransactionDirection::Outbound => {
+                    let recipient_view_key = match recipient.public_view_key() {
+                        Some(key) => key,
+                        None => continue,
+                    };
+                    let index = match key_manager
+                        .find_key_index(
+                            TransactionKeyManagerBranch::OneSidedSenderOffset.get_branch_key(),
+                            &output.sender_offset_public_key,
+                        )
+                        .await
+                    {
+                        Ok(index) => index,
+                        Err(_) => continue,
+                    };
+                    let sender_offset_key_id = KeyId::Managed {
+                        branch: TransactionKeyManagerBranch::OneSidedSenderOffset.get_branch_key(),
+                        index,
+                    };
+                    key_manager
+                        .get_diffie_hellman_shared_secret(&sender_offset_key_id, recipient_view_key)
+                        .await?
+                },
+                _ => {
+                    let view_key = key_manager.get_view_key().await?;
+                    key_manager
+                        .get_diffie_hellman_shared_secret(&view_key.key_id, &output.sender_offset_public_key)
+                        .await?
+                },
+            };
+            let encryption_key_id = key_manager
+                .import_key(shared_secret_to_output_encryption_key(&shared_secret)?)
+                .await?;
+            let (commitment_mask_key_id, amount, payment_id) = match key_manager
+                .try_output_key_recovery(output, Some(&encryption_key_id))
+                .await
+            {
+                Ok(recovered) => recovered,
+                Err(_) => continue,
+            };
+
+            let mut proof = PaymentProof::new(kernel, output, amount, recipient.clone(), payment_id);
+            let nonce = key_manager.get_random_key().await?;
+            let challenge = proof.challenge(&nonce.pub_key);
+            proof.signature = key_manager
+                .sign_with_nonce_and_challenge(&commitment_mask_key_id, &nonce.key_id, &challenge)
+                .await?;
+            return Ok(proof);
+        }
+
+        Err(TransactionServiceError::PaymentProofError(format!(
+            "Transaction {} has no one-sided output to {} that this wallet can open. Payment proofs are only \
+             supported for plain one-sided payments, not for stealth one-sided or interactive payments",
+            tx_id, recipient
+        )))
+    }
+
+    /// Checks the signature of a payment proof and asks the base node where its kernel and output were mined. The proof
+    /// is only accepted if both were mined in the same block.
+    fn handle_verify_payment_proof_request(
+        &self,
+        proof: PaymentProof,
+        reply_channel: oneshot::Sender<Result<TransactionServiceResponse, TransactionServiceError>>,
+    ) {
+        let mut connectivity = self.resources.connectivity.clone();
+
+        let query_base_node_fut = async move {
+            if !proof.is_valid_signature() {
+                return Err(TransactionServiceError::PaymentProofError(
+                    "The proof signature is invalid".to_string(),
+                ));
+            }
+            let mut client = connectivity
+                .obtain_base_node_wallet_rpc_client()
+                .await
+                .ok_or(TransactionServiceError::Shutdown)?;
+
+            let kernel_response =
+                TxQueryResponse::try_from(client.transaction_query(proof.kernel_signature.clone().into()).await?)
+                    .map_err(TransactionServiceError::ProtobufConversionError)?;
+            let block_hash = match (kernel_response.location, kernel_response.best_block_hash) {
+                (TxLocation::Mined, Some(block_hash)) => block_hash,
+                _ => {
+                    return Err(TransactionServiceError::PaymentProofError(
+                        "The transaction kernel has not been mined".to_string(),
+                    ))
+                },
+            };
+
+            let output_response = client
+                .utxo_query(UtxoQueryRequest {
+                    output_hashes: vec![proof.output_hash.to_vec()],
+                })
+                .await?
+                .responses
+                .into_iter()
+                .find(|r| r.output_hash == proof.output_hash.as_slice())
+                .ok_or_else(|| {
+                    TransactionServiceError::PaymentProofError("The output has not been mined".to_string())
+                })?;
+            let output = output_response
+                .output
+                .map(TransactionOutput::try_from)
+                .transpose()
+                .map_err(TransactionServiceError::ProtobufConversionError)?
+                .ok_or_else(|| {
+                    TransactionServiceError::PaymentProofError("The output has not been mined".to_string())
+                })?;
+            if output.commitment != proof.output_commitment {
+                return Err(TransactionServiceError::PaymentProofError(
+                    "The mined output does not match the proof commitment".to_string(),
+                ));
+            }
+            if !proof.is_locked_to_recipient(&output) {
+                return Err(TransactionServiceError::PaymentProofError(
+                    "The mined output is not locked to the recipient's public spend key".to_string(),
+                ));
+            }
+            if output_response.mined_in_block != block_hash.as_slice() {
+                return Err(TransactionServiceError::PaymentProofError(
+                    "The output and the transaction kernel were not mined in the same block".to_string(),
+                ));
+            }
+
+            Ok(TransactionServiceResponse::PaymentProofVerified(
+                PaymentProofVerification {
+                    mined_height: kernel_response
+                        .best_block_height
+                        .saturating_sub(kernel_response.confirmations),
+                    block_hash,
+                    confirmations: kernel_response.confirmations,
+                },
+            ))
+        };
+
+        tokio::spawn(async move {
+            let resp = query_base_node_fut.await;
+            if reply_channel.send(resp).is_err() {
+                warn!(
+                    target: LOG_TARGET,
+                    "handle_verify_payment_proof_request: service reply cancelled"
+                );
+            }
+        });
+    }
+
     fn handle_get_fee_per_gram_stats_per_block_request(
         &self,
         count: usize,
@@ -1632,7 +1983,6 @@ where
         Ok(Box::new((tx_id, pre_image, tx_output)))
     }
 
-    #[allow(clippy::too_many_lines)]
     async fn send_one_sided_or_stealth(
         &mut self,
         dest_address: TariAddress,
@@ -1645,9 +1995,78 @@ where
             JoinHandle<Result<TxId, TransactionServiceProtocolError<TxId>>>,
         >,
         recipient_script: Option<TariScript>,
+        recipient_encryption_key: Option<PrivateKey>,
         payment_id: PaymentId,
     ) -> Result<TxId, TransactionServiceError> {
         let tx_id = TxId::new_random();
+        let (tx, fee, payment_id) = self
+            .create_one_sided_or_stealth_transaction(
+                tx_id,
+                dest_address.clone(),
+                amount,
+                selection_criteria,
+                output_features,
+                fee_per_gram,
+                message.clone(),
+                recipient_script,
+                recipient_encryption_key,
+                payment_id,
+            )
+            .await?;
+
+        // This event being sent is important, but not critical to the protocol being successful. Send only fails if
+        // there are no subscribers.
+        let _result = self
+            .event_publisher
+            .send(Arc::new(TransactionEvent::TransactionCompletedImmediately(tx_id)));
+
+        // Broadcast one-sided transaction
+
+        self.resources
+            .output_manager_service
+            .confirm_pending_transaction(tx_id)
+            .await
+            .map_err(|e| TransactionServiceProtocolError::new(tx_id, e.into()))?;
+        self.submit_transaction(
+            transaction_broadcast_join_handles,
+            CompletedTransaction::new(
+                tx_id,
+                self.resources.one_sided_tari_address.clone(),
+                dest_address,
+                amount,
+                fee,
+                tx,
+                TransactionStatus::Completed,
+                message,
+                Utc::now().naive_utc(),
+                TransactionDirection::Outbound,
+                None,
+                None,
+                Some(payment_id),
+            )?,
+        )
+        .await?;
+
+        Ok(tx_id)
+    }
+
+    /// Builds and signs a one-sided or stealth payment without broadcasting it. The inputs stay encumbered against
+    /// `tx_id` until the caller confirms or cancels the pending transaction. If `recipient_encryption_key` is given,
+    /// the output's encrypted data is locked to it instead of to the key shared with the destination address.
+    #[allow(clippy::too_many_lines)]
+    async fn create_one_sided_or_stealth_transaction(
+        &mut self,
+        tx_id: TxId,
+        dest_address: TariAddress,
+        amount: MicroMinotari,
+        selection_criteria: UtxoSelectionCriteria,
+        output_features: OutputFeatures,
+        fee_per_gram: MicroMinotari,
+        message: String,
+        recipient_script: Option<TariScript>,
+        recipient_encryption_key: Option<PrivateKey>,
+        payment_id: PaymentId,
+    ) -> Result<(Transaction, MicroMinotari, PaymentId), TransactionServiceError> {
         let payment_id = match payment_id {
             PaymentId::Open(v) => PaymentId::AddressAndData(self.resources.interactive_tari_address.clone(), v),
             PaymentId::Empty => PaymentId::Address(self.resources.interactive_tari_address.clone()),
@@ -1662,6 +2081,12 @@ where
             Some(s) => (s, false),
             None => (push_pubkey_script(&Default::default()), true),
         };
+        // Stealth payments are derived from the wallet's main spend key, so the receiver would not find them
+        if use_stealth_address && dest_address.is_sub_address() {
+            return Err(TransactionServiceError::InvalidAddress(
+                "Stealth payments cannot be sent to a sub-address".to_string(),
+            ));
+        }
         // Prepare sender part of the transaction
         let mut stp = self
             .resources
@@ -1742,7 +2167,10 @@ where
                 .await?,
         );
 
-        let encryption_private_key = shared_secret_to_output_encryption_key(&shared_secret)?;
+        let encryption_private_key = match recipient_encryption_key {
+            Some(key) => key,
+            None => shared_secret_to_output_encryption_key(&shared_secret)?,
+        };
         let encryption_key = self
             .resources
             .transaction_key_manager_service
@@ -1822,14 +2250,6 @@ where
             })?;
         info!(target: LOG_TARGET, "Finalized one-side transaction TxId: {}", tx_id);
 
-        // This event being sent is important, but not critical to the protocol being successful. Send only fails if
-        // there are no subscribers.
-        let _result = self
-            .event_publisher
-            .send(Arc::new(TransactionEvent::TransactionCompletedImmediately(tx_id)));
-
-        // Broadcast one-sided transaction
-
         let tx = stp
             .get_transaction()
             .map_err(|e| TransactionServiceProtocolError::new(tx_id, e.into()))?;
@@ -1837,32 +2257,7 @@ where
             .get_fee_amount()
             .map_err(|e| TransactionServiceProtocolError::new(tx_id, e.into()))?;
 
-        self.resources
-            .output_manager_service
-            .confirm_pending_transaction(tx_id)
-            .await
-            .map_err(|e| TransactionServiceProtocolError::new(tx_id, e.into()))?;
-        self.submit_transaction(
-            transaction_broadcast_join_handles,
-            CompletedTransaction::new(
-                tx_id,
-                self.resources.one_sided_tari_address.clone(),
-                dest_address,
-                amount,
-                fee,
-                tx.clone(),
-                TransactionStatus::Completed,
-                message.clone(),
-                Utc::now().naive_utc(),
-                TransactionDirection::Outbound,
-                None,
-                None,
-                Some(payment_id),
-            )?,
-        )
-        .await?;
-
-        Ok(tx_id)
+        Ok((tx.clone(), fee, payment_id))
     }
 
     /// Sends a one side payment transaction to a recipient
@@ -1893,15 +2288,425 @@ where
             message,
             transaction_broadcast_join_handles,
             Some(push_pubkey_script(&dest_pubkey)),
+            None,
             payment_id,
         )
         .await
     }
 
-    /// Creates a transaction to burn some Minotari. The optional _claim public key_ parameter is used in the challenge
-    /// of the
-    // corresponding optional _ownership proof_ return value. Burn commitments and ownership proofs will exclusively be
-    // used in the 2nd layer (DAN layer). When such an _ownership proof_ is presented later on as part of some
+    /// Sends a single transaction that pays a one-sided output to each of the recipients. Each output carries its own
+    /// payment id and encrypted data, so every recipient is able to detect and spend their output independently.
+    pub async fn send_batch_one_sided_transaction(
+        &mut self,
+        recipients: Vec<OneSidedRecipient>,
+        selection_criteria: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+        message: String,
+        transaction_broadcast_join_handles: &mut FuturesUnordered<
+            JoinHandle<Result<TxId, TransactionServiceProtocolError<TxId>>>,
+        >,
+    ) -> Result<TxId, TransactionServiceError> {
+        let tx_id = TxId::new_random();
+        // The completed transaction can only record a single destination, so the first recipient is used
+        let destination = recipients.first().map(|r| r.destination.clone()).ok_or_else(|| {
+            TransactionServiceError::OneSidedTransactionError("A batch requires at least one recipient".to_string())
+        })?;
+        let amount = recipients.iter().map(|r| r.amount).sum::<MicroMinotari>();
+
+        let mut outputs = Vec::with_capacity(recipients.len());
+        for recipient in recipients {
+            outputs.push(self.create_one_sided_output(tx_id, recipient).await?);
+        }
+
+        // Prepare sender part of the transaction, the recipient outputs are already complete
+        let mut stp = self
+            .resources
+            .output_manager_service
+            .prepare_batch_transaction_to_send(tx_id, outputs, selection_criteria, fee_per_gram, message.clone())
+            .await?;
+
+        stp.finalize(&self.resources.transaction_key_manager_service)
+            .await
+            .map_err(|e| {
+                error!(
+                    target: LOG_TARGET,
+                    "Transaction (TxId: {}) could not be finalized. Failure error: {:?}", tx_id, e,
+                );
+                TransactionServiceProtocolError::new(tx_id, e.into())
+            })?;
+        info!(target: LOG_TARGET, "Finalized batch one-sided transaction TxId: {}", tx_id);
+
+        // This event being sent is important, but not critical to the protocol being successful. Send only fails if
+        // there are no subscribers.
+        let _result = self
+            .event_publisher
+            .send(Arc::new(TransactionEvent::TransactionCompletedImmediately(tx_id)));
+
+        let tx = stp
+            .get_transaction()
+            .map_err(|e| TransactionServiceProtocolError::new(tx_id, e.into()))?;
+        let fee = stp
+            .get_fee_amount()
+            .map_err(|e| TransactionServiceProtocolError::new(tx_id, e.into()))?;
+
+        self.resources
+            .output_manager_service
+            .confirm_pending_transaction(tx_id)
+            .await
+            .map_err(|e| TransactionServiceProtocolError::new(tx_id, e.into()))?;
+        self.submit_transaction(
+            transaction_broadcast_join_handles,
+            CompletedTransaction::new(
+                tx_id,
+                self.resources.one_sided_tari_address.clone(),
+                destination,
+                amount,
+                fee,
+                tx.clone(),
+                TransactionStatus::Completed,
+                message,
+                Utc::now().naive_utc(),
+                TransactionDirection::Outbound,
+                None,
+                None,
+                None,
+            )?,
+        )
+        .await?;
+
+        Ok(tx_id)
+    }
+
+    /// Builds and signs a one-sided output for the recipient, returning it with the key id of the sender offset
+    /// private key that was used to sign it.
+    async fn create_one_sided_output(
+        &self,
+        tx_id: TxId,
+        recipient: OneSidedRecipient,
+    ) -> Result<(WalletOutput, TariKeyId), TransactionServiceError> {
+        let OneSidedRecipient {
+            destination,
+            amount,
+            payment_id,
+        } = recipient;
+        let payment_id = match payment_id {
+            PaymentId::Open(v) => PaymentId::AddressAndData(self.resources.interactive_tari_address.clone(), v),
+            PaymentId::Empty => PaymentId::Address(self.resources.interactive_tari_address.clone()),
+            _ => payment_id,
+        };
+        self.verify_send(&destination, TariAddressFeatures::create_one_sided_only())?;
+
+        let sender_offset_key = self
+            .resources
+            .transaction_key_manager_service
+            .get_next_key(TransactionKeyManagerBranch::OneSidedSenderOffset.get_branch_key())
+            .await?;
+
+        // Diffie-Hellman shared secret `k_Ob * K_Sb = K_Ob * k_Sb` results in a public key, which is fed into
+        // KDFs to produce the spending and encryption keys
+        let shared_secret = self
+            .resources
+            .transaction_key_manager_service
+            .get_diffie_hellman_shared_secret(
+                &sender_offset_key.key_id,
+                destination
+                    .public_view_key()
+                    .ok_or(TransactionServiceProtocolError::new(
+                        tx_id,
+                        TransactionServiceError::OneSidedTransactionError("Missing public view key".to_string()),
+                    ))?,
+            )
+            .await?;
+        let spending_key_id = self
+            .resources
+            .transaction_key_manager_service
+            .import_key(
+                shared_secret_to_output_spending_key(&shared_secret)
+                    .map_err(|e| TransactionServiceProtocolError::new(tx_id, e.into()))?,
+            )
+            .await?;
+        let encryption_key = self
+            .resources
+            .transaction_key_manager_service
+            .import_key(shared_secret_to_output_encryption_key(&shared_secret)?)
+            .await?;
+
+        let output = WalletOutputBuilder::new(amount, spending_key_id)
+            .with_features(OutputFeatures::default())
+            .with_script(push_pubkey_script(destination.public_spend_key()))
+            .encrypt_data_for_recovery(
+                &self.resources.transaction_key_manager_service,
+                Some(&encryption_key),
+                payment_id,
+            )
+            .await?
+            .with_input_data(Default::default())
+            .with_sender_offset_public_key(sender_offset_key.pub_key)
+            .with_script_key(KeyId::Zero)
+            .with_minimum_value_promise(MicroMinotari::zero())
+            .sign_as_sender_and_receiver_verified(
+                &self.resources.transaction_key_manager_service,
+                &sender_offset_key.key_id,
+                &destination,
+            )
+            .await?
+            .try_build(&self.resources.transaction_key_manager_service)
+            .await?;
+
+        Ok((output, sender_offset_key.key_id))
+    }
+
+    /// Starts a single interactive transaction that pays each of the recipients. The first round of the
+    /// multi-recipient protocol is sent to every recipient here, the remaining rounds are driven by their replies. The
+    /// pending outbound transaction records the first recipient as its destination and the total amount sent.
+    pub async fn send_transaction_to_multiple_recipients(
+        &mut self,
+        recipients: Vec<(TariAddress, MicroMinotari)>,
+        selection_criteria: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+        message: String,
+    ) -> Result<TxId, TransactionServiceError> {
+        let tx_id = TxId::new_random();
+        if recipients.len() < 2 {
+            return Err(TransactionServiceError::MultiRecipientTransactionError(
+                "At least two recipients are required".to_string(),
+            ));
+        }
+        for (destination, _) in &recipients {
+            self.verify_send(destination, TariAddressFeatures::create_interactive_only())?;
+        }
+        let destination = recipients[0].0.clone();
+        let amount = recipients.iter().map(|(_, amount)| *amount).sum::<MicroMinotari>();
+
+        let mut stp = self
+            .resources
+            .output_manager_service
+            .prepare_multi_recipient_transaction_to_send(
+                tx_id,
+                recipients,
+                selection_criteria,
+                fee_per_gram,
+                message.clone(),
+            )
+            .await?;
+        let messages = stp
+            .get_multi_round_messages(&self.resources.transaction_key_manager_service)
+            .await?;
+        let addresses = stp.get_multi_recipient_addresses()?;
+        let fee = stp.get_fee_amount()?;
+
+        self.resources
+            .output_manager_service
+            .confirm_pending_transaction(tx_id)
+            .await?;
+        let outbound_tx = OutboundTransaction::new(
+            tx_id,
+            destination,
+            amount,
+            fee,
+            stp,
+            TransactionStatus::Pending,
+            message,
+            Utc::now().naive_utc(),
+            false,
+        );
+        self.db.add_pending_outbound_transaction(tx_id, outbound_tx)?;
+        self.start_multi_recipient_timeout(
+            tx_id,
+            self.resources.config.pending_transaction_cancellation_timeout,
+            &addresses,
+        );
+
+        for (address, msg) in addresses.into_iter().zip(messages) {
+            let msg = proto::MultiRoundSenderData::try_from(msg).map_err(TransactionServiceError::ServiceError)?;
+            tokio::spawn(send_multi_round_message(
+                TariMessageType::SenderPartialTransaction,
+                proto::TransactionSenderMessage::multiple(msg),
+                address.comms_public_key().clone(),
+                self.resources.outbound_message_service.clone(),
+            ));
+        }
+        info!(
+            target: LOG_TARGET,
+            "Multi-recipient transaction (TxId: {}) sent to recipients, waiting for their nonces", tx_id
+        );
+
+        Ok(tx_id)
+    }
+
+    /// Multi-recipient transactions are driven by the recipients' replies rather than a send protocol, this starts the
+    /// task that cancels the transaction if it is not completed within the timeout
+    fn start_multi_recipient_timeout(&mut self, tx_id: TxId, timeout: Duration, recipients: &[TariAddress]) {
+        let (cancellation_sender, cancellation_receiver) = oneshot::channel();
+        self.send_transaction_cancellation_senders
+            .insert(tx_id, cancellation_sender);
+        tokio::spawn(multi_recipient_transaction_timeout(
+            tx_id,
+            timeout,
+            recipients.iter().map(|a| a.comms_public_key().clone()).collect(),
+            cancellation_receiver,
+            self.db.clone(),
+            self.resources.output_manager_service.clone(),
+            self.resources.outbound_message_service.clone(),
+            self.event_publisher.clone(),
+            self.resources.shutdown_signal.clone(),
+        ));
+    }
+
+    /// Selects the inputs for a one-sided payment and wraps them in a partially signed transaction. No keys are used,
+    /// so a watch-only wallet can create it and hand it to an offline signer.
+    pub async fn create_partially_signed_transaction(
+        &mut self,
+        destination: TariAddress,
+        amount: MicroMinotari,
+        selection_criteria: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+        message: String,
+        payment_id: PaymentId,
+    ) -> Result<PartiallySignedTransaction, TransactionServiceError> {
+        self.verify_send(&destination, TariAddressFeatures::create_one_sided_only())?;
+        let unsigned = self
+            .resources
+            .output_manager_service
+            .prepare_unsigned_transaction(destination, amount, selection_criteria, fee_per_gram, message)
+            .await?;
+        let unspent_outputs = self.resources.output_manager_service.get_unspent_outputs().await?;
+        let inputs = unsigned
+            .inputs
+            .iter()
+            .map(|input| {
+                unspent_outputs
+                    .iter()
+                    .find(|o| o.commitment == input.commitment)
+                    .map(|o| PartiallySignedInput::new(o.to_transaction_output(), o.mined_height))
+                    .ok_or_else(|| PartiallySignedTransactionError::UnknownInput(input.commitment.to_hex()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(PartiallySignedTransaction::new(unsigned, inputs, payment_id))
+    }
+
+    /// Signs a partially signed transaction with this wallet's keys in two rounds. The first round builds the
+    /// transaction, keeps it in the wallet database and commits to its kernel nonce; the inputs are marked as spent so
+    /// that they are not selected again. The second round releases the signatures, which must have been made with the
+    /// committed nonce. The inputs must be unspent outputs this wallet has already found by scanning and validating the
+    /// chain; the outputs carried in the transaction are not trusted, since they have not been checked against the
+    /// chain, and are never imported. The transaction is not broadcast.
+    pub async fn sign_partially_signed_transaction(
+        &mut self,
+        mut transaction: PartiallySignedTransaction,
+    ) -> Result<PartiallySignedTransaction, TransactionServiceError> {
+        transaction.check_version()?;
+        if transaction.is_fully_signed() {
+            return Err(PartiallySignedTransactionError::AlreadySigned.into());
+        }
+        let tx_id = transaction.tx_id;
+        let pending_key = format!("{}_{}", PENDING_PARTIALLY_SIGNED_TRANSACTION_KEY, tx_id);
+        if transaction.is_kernel_nonce_committed() {
+            let pending = self
+                .wallet_db
+                .get_client_key_value(pending_key.clone())?
+                .ok_or(PartiallySignedTransactionError::NotCommittedByThisWallet(tx_id))?;
+            let tx: Transaction = serde_json::from_str(&pending)
+                .map_err(|e| TransactionServiceError::SerializationError(e.to_string()))?;
+            transaction.apply_signed_transaction(&tx)?;
+            self.wallet_db.clear_client_value(pending_key)?;
+            info!(target: LOG_TARGET, "Signed partially signed transaction TxId: {}", tx_id);
+            return Ok(transaction);
+        }
+
+        let unspent_outputs = self.resources.output_manager_service.get_unspent_outputs().await?;
+        if let Some(input) = transaction
+            .inputs
+            .iter()
+            .find(|i| !unspent_outputs.iter().any(|o| &o.commitment == i.commitment()))
+        {
+            return Err(PartiallySignedTransactionError::UnknownInput(input.commitment().to_hex()).into());
+        }
+
+        let (tx, fee, payment_id) = self
+            .create_one_sided_or_stealth_transaction(
+                tx_id,
+                transaction.recipient_address.clone(),
+                transaction.amount,
+                UtxoSelectionCriteria::specific(transaction.input_commitments()),
+                OutputFeatures::default(),
+                transaction.fee_per_gram,
+                transaction.message.clone(),
+                None,
+                None,
+                transaction.payment_id.clone(),
+            )
+            .await?;
+        let committed = match tx.body.kernels().as_slice() {
+            [kernel] => transaction.commit_kernel_nonce(kernel.excess_sig.get_public_nonce().clone()),
+            kernels => Err(PartiallySignedTransactionError::InvalidKernelCount(kernels.len())),
+        };
+        if let Err(e) = committed {
+            self.resources.output_manager_service.cancel_transaction(tx_id).await?;
+            return Err(e.into());
+        }
+        if fee != transaction.fee {
+            debug!(
+                target: LOG_TARGET,
+                "Partially signed transaction (TxId: {}) fee changed from {} to {} when signing", tx_id, transaction.fee, fee
+            );
+        }
+        transaction.fee = fee;
+        transaction.payment_id = payment_id;
+        let pending =
+            serde_json::to_string(&tx).map_err(|e| TransactionServiceError::SerializationError(e.to_string()))?;
+        self.wallet_db.set_client_key_value(pending_key, pending)?;
+        self.resources
+            .output_manager_service
+            .confirm_pending_transaction(tx_id)
+            .await?;
+        info!(
+            target: LOG_TARGET,
+            "Committed the kernel nonce of partially signed transaction TxId: {}", tx_id
+        );
+
+        Ok(transaction)
+    }
+
+    /// Assembles a fully signed transaction and broadcasts it; the base node performs full validation
+    pub async fn finalize_partially_signed_transaction(
+        &mut self,
+        transaction: PartiallySignedTransaction,
+        transaction_broadcast_join_handles: &mut FuturesUnordered<
+            JoinHandle<Result<TxId, TransactionServiceProtocolError<TxId>>>,
+        >,
+    ) -> Result<TxId, TransactionServiceError> {
+        let tx = transaction.to_transaction()?;
+
+        let tx_id = transaction.tx_id;
+        self.submit_transaction(
+            transaction_broadcast_join_handles,
+            CompletedTransaction::new(
+                tx_id,
+                self.resources.one_sided_tari_address.clone(),
+                transaction.recipient_address,
+                transaction.amount,
+                transaction.fee,
+                tx,
+                TransactionStatus::Completed,
+                transaction.message,
+                Utc::now().naive_utc(),
+                TransactionDirection::Outbound,
+                None,
+                None,
+                Some(transaction.payment_id),
+            )?,
+        )
+        .await?;
+        info!(target: LOG_TARGET, "Broadcasting partially signed transaction TxId: {}", tx_id);
+
+        Ok(tx_id)
+    }
+
+    /// Creates a transaction to burn some Minotari. The optional _claim public key_ parameter is used in the challenge
+    /// of the
+    // corresponding optional _ownership proof_ return value. Burn commitments and ownership proofs will exclusively be
+    // used in the 2nd layer (DAN layer). When such an _ownership proof_ is presented later on as part of some
     // transaction metadata, the _claim public key_ can be revealed to enable verification of the _ownership proof_
     // and the transaction can be signed with the private key corresponding to the claim public key.
     #[allow(clippy::too_many_lines)]
@@ -2221,11 +3026,46 @@ where
             message,
             transaction_broadcast_join_handles,
             None, // The stealth address for the script will be calculated in the next step
+            None,
             payment_id,
         )
         .await
     }
 
+    /// Sends funds to the m-of-n account of the given cosigner keys. The output is locked to the account script and
+    /// its value and commitment mask are encrypted with the account key, so that any of the cosigners can recover it.
+    pub async fn send_to_multisig_account(
+        &mut self,
+        threshold: u8,
+        public_keys: Vec<PublicKey>,
+        amount: MicroMinotari,
+        selection_criteria: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+        message: String,
+        transaction_broadcast_join_handles: &mut FuturesUnordered<
+            JoinHandle<Result<TxId, TransactionServiceProtocolError<TxId>>>,
+        >,
+    ) -> Result<TxId, TransactionServiceError> {
+        let public_keys = sorted_cosigner_keys(threshold, public_keys)?;
+        let script = multisig_script(threshold, &public_keys);
+        let encryption_key = multisig_encryption_key(&public_keys)?;
+        // The account has no address of its own, so the one-sided payment is addressed to this wallet, which cannot
+        // decrypt the output as it is locked to the account encryption key
+        self.send_one_sided_or_stealth(
+            self.resources.one_sided_tari_address.clone(),
+            amount,
+            selection_criteria,
+            OutputFeatures::default(),
+            fee_per_gram,
+            message,
+            transaction_broadcast_join_handles,
+            Some(script),
+            Some(encryption_key),
+            PaymentId::Empty,
+        )
+        .await
+    }
+
     /// Accept the public reply from a recipient and apply the reply to the relevant transaction protocol
     /// # Arguments
     /// 'recipient_reply' - The public response from a recipient with data required to complete the transaction
@@ -2234,6 +3074,9 @@ where
         &mut self,
         source_pubkey: CommsPublicKey,
         recipient_reply: Result<proto::RecipientSignedMessage, prost::DecodeError>,
+        transaction_broadcast_join_handles: &mut FuturesUnordered<
+            JoinHandle<Result<TxId, TransactionServiceProtocolError<TxId>>>,
+        >,
     ) -> Result<(), TransactionServiceError> {
         // Check if a wallet recovery is in progress, if it is we will ignore this request
         self.check_recovery_status()?;
@@ -2253,6 +3096,21 @@ where
 
         let tx_id = recipient_reply.tx_id;
 
+        // Replies for a multi-recipient transaction are applied to the stored sender protocol, as there is no send
+        // protocol waiting for them
+        if let Ok(outbound_tx) = self.db.get_pending_outbound_transaction(tx_id) {
+            if outbound_tx.sender_protocol.is_collecting_multi_signatures() {
+                return self
+                    .accept_multi_recipient_reply(
+                        source_pubkey,
+                        outbound_tx,
+                        recipient_reply,
+                        transaction_broadcast_join_handles,
+                    )
+                    .await;
+            }
+        }
+
         // First we check if this Reply is for a cancelled Pending Outbound Tx or a Completed Tx
         let cancelled_outbound_tx = self.db.get_cancelled_pending_outbound_transaction(tx_id);
         let completed_tx = self.db.get_completed_transaction_cancelled_or_not(tx_id);
@@ -2366,6 +3224,145 @@ where
         Ok(())
     }
 
+    /// Apply a recipient's signed reply to a multi-recipient transaction we are sending. Once every recipient has
+    /// replied the transaction is finalized, sent to all of the recipients and broadcast.
+    async fn accept_multi_recipient_reply(
+        &mut self,
+        source_pubkey: CommsPublicKey,
+        mut outbound_tx: OutboundTransaction,
+        recipient_reply: RecipientSignedMessage,
+        transaction_broadcast_join_handles: &mut FuturesUnordered<
+            JoinHandle<Result<TxId, TransactionServiceProtocolError<TxId>>>,
+        >,
+    ) -> Result<(), TransactionServiceError> {
+        let tx_id = outbound_tx.tx_id;
+        let addresses = outbound_tx.sender_protocol.get_multi_recipient_addresses()?;
+        if !addresses.iter().any(|a| a.comms_public_key() == &source_pubkey) {
+            return Err(TransactionServiceError::InvalidSourcePublicKey);
+        }
+
+        outbound_tx
+            .sender_protocol
+            .add_multi_recipient_signature(recipient_reply, &self.resources.transaction_key_manager_service)
+            .await?;
+        if !outbound_tx.sender_protocol.is_finalizing() {
+            self.db
+                .update_pending_outbound_sender_protocol(tx_id, outbound_tx.sender_protocol)?;
+            return Ok(());
+        }
+
+        outbound_tx
+            .sender_protocol
+            .finalize(&self.resources.transaction_key_manager_service)
+            .await
+            .map_err(|e| {
+                error!(
+                    target: LOG_TARGET,
+                    "Multi-recipient transaction (TxId: {}) could not be finalized. Failure error: {:?}", tx_id, e,
+                );
+                e
+            })?;
+        let tx = outbound_tx.sender_protocol.get_transaction()?.clone();
+        let completed_tx = CompletedTransaction::new(
+            tx_id,
+            self.resources.interactive_tari_address.clone(),
+            outbound_tx.destination_address,
+            outbound_tx.amount,
+            outbound_tx.fee,
+            tx.clone(),
+            TransactionStatus::Completed,
+            outbound_tx.message,
+            Utc::now().naive_utc(),
+            TransactionDirection::Outbound,
+            None,
+            None,
+            None,
+        )?;
+        self.db.complete_outbound_transaction(tx_id, completed_tx.clone())?;
+        // Stops the timeout task
+        let _sender = self.send_transaction_cancellation_senders.remove(&tx_id);
+        info!(
+            target: LOG_TARGET,
+            "All recipient replies for multi-recipient transaction (TxId: {}) received", tx_id
+        );
+
+        for address in addresses {
+            tokio::spawn(send_finalized_transaction_message(
+                tx_id,
+                tx.clone(),
+                address.comms_public_key().clone(),
+                self.resources.outbound_message_service.clone(),
+                self.resources.config.direct_send_timeout,
+                self.resources.config.transaction_routing_mechanism,
+            ));
+        }
+
+        let _size = self
+            .event_publisher
+            .send(Arc::new(TransactionEvent::ReceivedTransactionReply(tx_id)));
+
+        self.broadcast_completed_transaction(completed_tx, transaction_broadcast_join_handles)
+    }
+
+    /// Accept the public nonce and excess of one of the recipients of a multi-recipient transaction we are sending.
+    /// Once every recipient has replied the signature request is sent to all of them.
+    pub async fn accept_recipient_nonce_reply(
+        &mut self,
+        source_pubkey: CommsPublicKey,
+        nonce_reply: Result<proto::RecipientNonceMessage, prost::DecodeError>,
+    ) -> Result<(), TransactionServiceError> {
+        // Check if a wallet recovery is in progress, if it is we will ignore this request
+        self.check_recovery_status()?;
+
+        let nonce_reply: RecipientNonceMessage = nonce_reply
+            .map_err(|e| {
+                TransactionServiceError::InvalidMessageError(format!("Could not decode RecipientNonceMessage: {:?}", e))
+            })?
+            .try_into()
+            .map_err(TransactionServiceError::InvalidMessageError)?;
+        let tx_id = nonce_reply.tx_id;
+
+        let mut outbound_tx = self
+            .db
+            .get_pending_outbound_transaction(tx_id)
+            .map_err(|_| TransactionServiceError::TransactionDoesNotExistError)?;
+        if !outbound_tx.sender_protocol.is_collecting_multi_nonces() {
+            return Err(TransactionServiceError::RepeatedMessageError);
+        }
+        let addresses = outbound_tx.sender_protocol.get_multi_recipient_addresses()?;
+        let expected_address = usize::try_from(nonce_reply.recipient_index)
+            .ok()
+            .and_then(|i| addresses.get(i))
+            .ok_or_else(|| TransactionServiceError::InvalidMessageError("Invalid recipient index".to_string()))?;
+        if expected_address.comms_public_key() != &source_pubkey {
+            return Err(TransactionServiceError::InvalidSourcePublicKey);
+        }
+
+        outbound_tx.sender_protocol.add_multi_recipient_nonce(nonce_reply)?;
+        self.db
+            .update_pending_outbound_sender_protocol(tx_id, outbound_tx.sender_protocol.clone())?;
+
+        if outbound_tx.sender_protocol.is_collecting_multi_signatures() {
+            let request = proto::MultiRoundSignatureRequest::from(
+                outbound_tx.sender_protocol.get_multi_round_signature_request()?,
+            );
+            for address in addresses {
+                tokio::spawn(send_multi_round_message(
+                    TariMessageType::SenderPartialTransaction,
+                    proto::TransactionSenderMessage::signature_request(request.clone()),
+                    address.comms_public_key().clone(),
+                    self.resources.outbound_message_service.clone(),
+                ));
+            }
+            info!(
+                target: LOG_TARGET,
+                "All recipient nonces for multi-recipient transaction (TxId: {}) received, signature request sent", tx_id
+            );
+        }
+
+        Ok(())
+    }
+
     /// Handle the final clean up after a Send Transaction protocol completes
     fn complete_send_transaction_protocol(
         &mut self,
@@ -2512,6 +3509,21 @@ where
     ) -> Result<(), TransactionServiceError> {
         let outbound_txs = self.db.get_pending_outbound_transactions()?;
         for (tx_id, tx) in outbound_txs {
+            // Multi-recipient transactions are driven by the recipients' replies rather than a send protocol, they
+            // only need their timeout restarted
+            if tx.sender_protocol.is_multi_recipient() {
+                if !self.send_transaction_cancellation_senders.contains_key(&tx_id) {
+                    let addresses = tx.sender_protocol.get_multi_recipient_addresses()?;
+                    let elapsed_time = utc_duration_since(&tx.timestamp)?;
+                    let timeout = self
+                        .resources
+                        .config
+                        .pending_transaction_cancellation_timeout
+                        .saturating_sub(elapsed_time);
+                    self.start_multi_recipient_timeout(tx_id, timeout, &addresses);
+                }
+                continue;
+            }
             let (sender_protocol, stage) = if tx.send_count > 0 {
                 (None, TransactionSendProtocolStage::WaitForReply)
             } else {
@@ -2573,7 +3585,7 @@ where
     /// 'source_pubkey' - The pubkey from which the message was sent and to which the reply will be sent.
     /// 'sender_message' - Message from a sender containing the setup of the transaction being sent to you
     #[allow(clippy::too_many_lines)]
-    pub fn accept_transaction(
+    pub async fn accept_transaction(
         &mut self,
         source_pubkey: CommsPublicKey,
         sender_message: Result<proto::TransactionSenderMessage, prost::DecodeError>,
@@ -2582,6 +3594,10 @@ where
     ) -> Result<(), TransactionServiceError> {
         // Check if a wallet recovery is in progress, if it is we will ignore this request
         self.check_recovery_status()?;
+        // Replying to an interactive transaction requires signing with the spend key
+        if self.resources.wallet_type.is_watch_only() {
+            return Err(TransactionServiceError::WatchOnlyWallet);
+        }
 
         if let Err(e) = sender_message {
             return Err(TransactionServiceError::InvalidMessageError(format!(
@@ -2594,8 +3610,12 @@ where
             .try_into()
             .map_err(TransactionServiceError::InvalidMessageError)?;
 
-        // Currently we will only reply to a Single sender transaction protocol
-        if let TransactionSenderMessage::Single(data) = sender_message.clone() {
+        if let TransactionSenderMessage::SignatureRequest(request) = sender_message {
+            return self.accept_signature_request(source_pubkey, *request).await;
+        }
+
+        // We reply to a Single sender transaction protocol, or to the first round of a multi-recipient protocol
+        if let Some(data) = sender_message.sender_data().cloned() {
             trace!(
                 target: LOG_TARGET,
                 "Transaction (TxId: {}) received from {}, Trace: {}",
@@ -2715,6 +3735,54 @@ where
         }
     }
 
+    /// Handle the second round of a multi-recipient transaction we are receiving. The sender's total public nonce and
+    /// excess are used to sign our output, the signed reply is sent back and the receive protocol, which is already
+    /// waiting for the finalized transaction, picks up the updated state.
+    async fn accept_signature_request(
+        &mut self,
+        source_pubkey: CommsPublicKey,
+        request: MultiRoundSignatureRequest,
+    ) -> Result<(), TransactionServiceError> {
+        let tx_id = request.tx_id;
+        let mut inbound_tx = self
+            .db
+            .get_pending_inbound_transaction(tx_id)
+            .map_err(|_| TransactionServiceError::TransactionDoesNotExistError)?;
+        if inbound_tx.source_address.comms_public_key() != &source_pubkey {
+            return Err(TransactionServiceError::InvalidSourcePublicKey);
+        }
+        if !inbound_tx.receiver_protocol.is_awaiting_signature_request() {
+            // Our signed reply is resent by the receive protocol
+            return Err(TransactionServiceError::RepeatedMessageError);
+        }
+
+        inbound_tx
+            .receiver_protocol
+            .add_signature_request(&request, &self.resources.transaction_key_manager_service)
+            .await?;
+        self.db
+            .update_pending_inbound_receiver_protocol(tx_id, inbound_tx.receiver_protocol.clone())?;
+        debug!(
+            target: LOG_TARGET,
+            "Signature request for multi-recipient transaction (TxId: {}) received, reply is being sent", tx_id
+        );
+
+        tokio::spawn(send_transaction_reply(
+            inbound_tx,
+            self.resources.outbound_message_service.clone(),
+            self.resources.config.direct_send_timeout,
+            self.resources.config.transaction_routing_mechanism,
+        ));
+        if let Err(e) = self.resources.db.increment_send_count(tx_id) {
+            warn!(
+                target: LOG_TARGET,
+                "Could not increment send count for inbound transaction TxId {}: {:?}", tx_id, e
+            );
+        }
+
+        Ok(())
+    }
+
     /// Accept the public reply from a recipient and apply the reply to the relevant transaction protocol
     /// # Arguments
     /// 'recipient_reply' - The public response from a recipient with data required to complete the transaction
@@ -3345,6 +4413,51 @@ where
         }
     }
 
+    /// Checks a send against the spending policy of the wallet. A large send is rejected the first time it is
+    /// requested and allowed when the same send is repeated after the configured delay.
+    fn check_spending_policy(&self, spend: &PolicySpend) -> Result<(), TransactionServiceError> {
+        let policy = self.db.get_spending_policy()?;
+        if policy.is_unrestricted() {
+            return Ok(());
+        }
+        let now = Utc::now().naive_utc();
+        let spent_in_period = if policy.daily_limit.is_some() {
+            self.db.get_outbound_amount_since(daily_period_start(now))?
+        } else {
+            MicroMinotari::zero()
+        };
+        policy.check(spend, spent_in_period)?;
+
+        if !policy.requires_delay(spend) {
+            return Ok(());
+        }
+        let approval_key = spend.approval_key();
+        match self.db.get_large_send_approval(&approval_key)? {
+            Some(approval) if approval.is_available(now) => {
+                self.db.remove_large_send_approval(&approval_key)?;
+                Ok(())
+            },
+            Some(approval) if !approval.is_expired(now) => Err(SpendingPolicyViolation::DelayRequired {
+                available_at: approval.available_at,
+                expires_at: approval.expires_at,
+            }
+            .into()),
+            _ => {
+                let approval = LargeSendApproval::new(approval_key, now, policy.large_send_delay);
+                self.db.save_large_send_approval(&approval)?;
+                info!(
+                    target: LOG_TARGET,
+                    "Large send delayed until {}, the same request must be repeated to proceed", approval.available_at
+                );
+                Err(SpendingPolicyViolation::DelayRequired {
+                    available_at: approval.available_at,
+                    expires_at: approval.expires_at,
+                }
+                .into())
+            },
+        }
+    }
+
     fn connectivity(&self) -> &TWalletConnectivity {
         &self.resources.connectivity
     }
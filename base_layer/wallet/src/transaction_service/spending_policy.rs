@@ -0,0 +1,426 @@
+//  Copyright 2024. The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{
+    fmt,
+    fmt::{Display, Formatter},
+    time::Duration,
+};
+
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use tari_common::configuration::serializers;
+use tari_common_types::tari_address::TariAddress;
+use tari_core::transactions::tari_amount::MicroMinotari;
+use thiserror::Error;
+
+/// The period over which `SpendingPolicy::daily_limit` is enforced
+pub const SPENDING_POLICY_DAILY_PERIOD: Duration = Duration::from_secs(24 * 60 * 60);
+/// How long a large send remains approved once its delay has passed
+pub const LARGE_SEND_APPROVAL_WINDOW: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Limits applied to every request that sends funds out of the wallet. A field that is not set is not enforced.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SpendingPolicy {
+    /// The largest amount a single request may send
+    pub max_amount_per_transaction: Option<MicroMinotari>,
+    /// The largest amount that may be sent over any rolling 24 hour period
+    pub daily_limit: Option<MicroMinotari>,
+    /// When set, funds may only be sent to these addresses. Burns and other sends without a recipient address are
+    /// rejected.
+    #[serde(with = "serde_addresses")]
+    pub allowed_destinations: Option<Vec<TariAddress>>,
+    /// Requests sending at least this amount are only processed when repeated `large_send_delay` after the first
+    /// attempt
+    pub large_send_threshold: Option<MicroMinotari>,
+    #[serde(with = "serializers::seconds")]
+    pub large_send_delay: Duration,
+}
+
+impl SpendingPolicy {
+    /// Returns true if the policy does not restrict any sends
+    pub fn is_unrestricted(&self) -> bool {
+        self.max_amount_per_transaction.is_none() &&
+            self.daily_limit.is_none() &&
+            self.allowed_destinations.is_none() &&
+            self.large_send_threshold.is_none()
+    }
+
+    /// Checks the amount and destination limits of the policy. `spent_in_period` is the amount already sent in the
+    /// last 24 hours.
+    pub fn check(&self, spend: &PolicySpend, spent_in_period: MicroMinotari) -> Result<(), SpendingPolicyViolation> {
+        if let Some(allowed) = &self.allowed_destinations {
+            for destination in &spend.destinations {
+                let is_allowed = match destination {
+                    SpendDestination::Address(address) => allowed.iter().any(|a| same_recipient(a, address)),
+                    _ => false,
+                };
+                if !is_allowed {
+                    return Err(SpendingPolicyViolation::DestinationNotAllowed(destination.to_string()));
+                }
+            }
+        }
+
+        let amount = match spend.amount {
+            Some(amount) => amount,
+            None if self.limits_amounts() => return Err(SpendingPolicyViolation::UnknownAmount),
+            None => return Ok(()),
+        };
+        if let Some(max) = self.max_amount_per_transaction {
+            if amount > max {
+                return Err(SpendingPolicyViolation::TransactionLimitExceeded { amount, max });
+            }
+        }
+        if let Some(limit) = self.daily_limit {
+            if spent_in_period.saturating_add(amount) > limit {
+                return Err(SpendingPolicyViolation::DailyLimitExceeded {
+                    amount,
+                    spent: spent_in_period,
+                    limit,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns true if the spend must wait out `large_send_delay` before it is processed
+    pub fn requires_delay(&self, spend: &PolicySpend) -> bool {
+        match (self.large_send_threshold, spend.amount) {
+            (Some(threshold), Some(amount)) => amount >= threshold,
+            (Some(_), None) => true,
+            (None, _) => false,
+        }
+    }
+
+    fn limits_amounts(&self) -> bool {
+        self.max_amount_per_transaction.is_some() || self.daily_limit.is_some() || self.large_send_threshold.is_some()
+    }
+}
+
+/// Addresses are compared by their keys and network so that the interactive and one-sided variants of an allowed
+/// address are both accepted
+fn same_recipient(a: &TariAddress, b: &TariAddress) -> bool {
+    a.network() == b.network() &&
+        a.public_spend_key() == b.public_spend_key() &&
+        a.public_view_key() == b.public_view_key()
+}
+
+/// The start of the period whose sends count towards `SpendingPolicy::daily_limit`
+pub fn daily_period_start(now: NaiveDateTime) -> NaiveDateTime {
+    now.checked_sub_signed(to_chrono(SPENDING_POLICY_DAILY_PERIOD))
+        .unwrap_or(NaiveDateTime::MIN)
+}
+
+fn to_chrono(duration: Duration) -> chrono::Duration {
+    chrono::Duration::from_std(duration).unwrap_or_else(|_| chrono::Duration::max_value())
+}
+
+/// Stores allow-listed addresses in their base58 form, any address format is accepted when deserializing
+mod serde_addresses {
+    use std::str::FromStr;
+
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+    use tari_common_types::tari_address::TariAddress;
+
+    pub fn serialize<S: Serializer>(addresses: &Option<Vec<TariAddress>>, serializer: S) -> Result<S::Ok, S::Error> {
+        addresses
+            .as_ref()
+            .map(|a| a.iter().map(TariAddress::to_base58).collect::<Vec<_>>())
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Vec<TariAddress>>, D::Error> {
+        Option::<Vec<String>>::deserialize(deserializer)?
+            .map(|addresses| {
+                addresses
+                    .iter()
+                    .map(|a| TariAddress::from_str(a).map_err(|e| D::Error::custom(format!("`{}`: {}", a, e))))
+                    .collect()
+            })
+            .transpose()
+    }
+}
+
+/// Where the funds of a send request go
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpendDestination {
+    Address(TariAddress),
+    Burn,
+    MultisigAccount,
+    ValidatorNodeRegistration,
+    CodeTemplateRegistration,
+}
+
+impl Display for SpendDestination {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Address(address) => write!(f, "{}", address),
+            Self::Burn => write!(f, "burn"),
+            Self::MultisigAccount => write!(f, "multisig account"),
+            Self::ValidatorNodeRegistration => write!(f, "validator node registration"),
+            Self::CodeTemplateRegistration => write!(f, "code template registration"),
+        }
+    }
+}
+
+/// The funds a send request moves out of the wallet, as seen by the spending policy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolicySpend {
+    /// The total amount sent excluding fees, `None` if it is only known once the request has been processed
+    pub amount: Option<MicroMinotari>,
+    pub destinations: Vec<SpendDestination>,
+}
+
+impl PolicySpend {
+    pub fn new(amount: MicroMinotari, destination: SpendDestination) -> Self {
+        Self {
+            amount: Some(amount),
+            destinations: vec![destination],
+        }
+    }
+
+    /// A key identifying the spend, used to match a repeated large send with its pending approval
+    pub fn approval_key(&self) -> String {
+        let amount = self.amount.map(|a| a.as_u64().to_string()).unwrap_or_default();
+        let destinations = self
+            .destinations
+            .iter()
+            .map(|d| match d {
+                SpendDestination::Address(address) => address.to_base58(),
+                other => other.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        format!("{}:{}", amount, destinations)
+    }
+}
+
+/// A large send that was attempted and may be repeated between `available_at` and `expires_at`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LargeSendApproval {
+    pub approval_key: String,
+    pub available_at: NaiveDateTime,
+    pub expires_at: NaiveDateTime,
+}
+
+impl LargeSendApproval {
+    pub fn new(approval_key: String, now: NaiveDateTime, delay: Duration) -> Self {
+        let available_at = now.checked_add_signed(to_chrono(delay)).unwrap_or(NaiveDateTime::MAX);
+        let expires_at = available_at
+            .checked_add_signed(to_chrono(LARGE_SEND_APPROVAL_WINDOW))
+            .unwrap_or(NaiveDateTime::MAX);
+        Self {
+            approval_key,
+            available_at,
+            expires_at,
+        }
+    }
+
+    pub fn is_available(&self, now: NaiveDateTime) -> bool {
+        now >= self.available_at && !self.is_expired(now)
+    }
+
+    pub fn is_expired(&self, now: NaiveDateTime) -> bool {
+        now >= self.expires_at
+    }
+}
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum SpendingPolicyViolation {
+    #[error("Amount {amount} exceeds the limit of {max} per transaction")]
+    TransactionLimitExceeded { amount: MicroMinotari, max: MicroMinotari },
+    #[error("Sending {amount} exceeds the daily limit of {limit}, {spent} has been sent in the last 24 hours")]
+    DailyLimitExceeded {
+        amount: MicroMinotari,
+        spent: MicroMinotari,
+        limit: MicroMinotari,
+    },
+    #[error("Destination `{0}` is not on the allow-list")]
+    DestinationNotAllowed(String),
+    #[error("The amount sent by this request is not known in advance and cannot be checked against the limits")]
+    UnknownAmount,
+    #[error("Large sends are delayed, repeat the request between {available_at} and {expires_at} (UTC)")]
+    DelayRequired {
+        available_at: NaiveDateTime,
+        expires_at: NaiveDateTime,
+    },
+}
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::OsRng;
+    use tari_common::configuration::Network;
+    use tari_common_types::{tari_address::TariAddressFeatures, types::PublicKey};
+    use tari_crypto::keys::PublicKey as PublicKeyTrait;
+
+    use super::*;
+
+    fn address() -> TariAddress {
+        let (_, view_key) = PublicKey::random_keypair(&mut OsRng);
+        let (_, spend_key) = PublicKey::random_keypair(&mut OsRng);
+        TariAddress::new_dual_address_with_default_features(view_key, spend_key, Network::LocalNet)
+    }
+
+    fn send(amount: u64, address: &TariAddress) -> PolicySpend {
+        PolicySpend::new(MicroMinotari::from(amount), SpendDestination::Address(address.clone()))
+    }
+
+    #[test]
+    fn it_allows_everything_by_default() {
+        let policy = SpendingPolicy::default();
+        assert!(policy.is_unrestricted());
+        assert!(policy.check(&send(u64::MAX, &address()), MicroMinotari::zero()).is_ok());
+        assert!(!policy.requires_delay(&send(u64::MAX, &address())));
+    }
+
+    #[test]
+    fn it_enforces_the_transaction_limit() {
+        let policy = SpendingPolicy {
+            max_amount_per_transaction: Some(MicroMinotari::from(1_000)),
+            ..Default::default()
+        };
+        let recipient = address();
+        assert!(policy.check(&send(1_000, &recipient), MicroMinotari::zero()).is_ok());
+        assert_eq!(
+            policy.check(&send(1_001, &recipient), MicroMinotari::zero()),
+            Err(SpendingPolicyViolation::TransactionLimitExceeded {
+                amount: MicroMinotari::from(1_001),
+                max: MicroMinotari::from(1_000)
+            })
+        );
+    }
+
+    #[test]
+    fn it_enforces_the_daily_limit() {
+        let policy = SpendingPolicy {
+            daily_limit: Some(MicroMinotari::from(10_000)),
+            ..Default::default()
+        };
+        let recipient = address();
+        assert!(policy
+            .check(&send(4_000, &recipient), MicroMinotari::from(6_000))
+            .is_ok());
+        assert!(matches!(
+            policy.check(&send(4_001, &recipient), MicroMinotari::from(6_000)),
+            Err(SpendingPolicyViolation::DailyLimitExceeded { .. })
+        ));
+    }
+
+    #[test]
+    fn it_enforces_the_allow_list() {
+        let allowed = address();
+        let policy = SpendingPolicy {
+            allowed_destinations: Some(vec![allowed.clone()]),
+            ..Default::default()
+        };
+        assert!(policy.check(&send(1, &allowed), MicroMinotari::zero()).is_ok());
+        let one_sided = TariAddress::new_dual_address(
+            allowed.public_view_key().unwrap().clone(),
+            allowed.public_spend_key().clone(),
+            Network::LocalNet,
+            TariAddressFeatures::create_one_sided_only(),
+        );
+        assert!(policy.check(&send(1, &one_sided), MicroMinotari::zero()).is_ok());
+        assert!(matches!(
+            policy.check(&send(1, &address()), MicroMinotari::zero()),
+            Err(SpendingPolicyViolation::DestinationNotAllowed(_))
+        ));
+        let burn = PolicySpend::new(MicroMinotari::from(1), SpendDestination::Burn);
+        assert!(matches!(
+            policy.check(&burn, MicroMinotari::zero()),
+            Err(SpendingPolicyViolation::DestinationNotAllowed(_))
+        ));
+    }
+
+    #[test]
+    fn it_rejects_unknown_amounts_when_amounts_are_limited() {
+        let spend = PolicySpend {
+            amount: None,
+            destinations: vec![SpendDestination::Address(address())],
+        };
+        assert!(SpendingPolicy::default().check(&spend, MicroMinotari::zero()).is_ok());
+        let policy = SpendingPolicy {
+            daily_limit: Some(MicroMinotari::from(10_000)),
+            ..Default::default()
+        };
+        assert_eq!(
+            policy.check(&spend, MicroMinotari::zero()),
+            Err(SpendingPolicyViolation::UnknownAmount)
+        );
+    }
+
+    #[test]
+    fn it_delays_large_sends() {
+        let policy = SpendingPolicy {
+            large_send_threshold: Some(MicroMinotari::from(5_000)),
+            large_send_delay: Duration::from_secs(3600),
+            ..Default::default()
+        };
+        let recipient = address();
+        assert!(!policy.requires_delay(&send(4_999, &recipient)));
+        assert!(policy.requires_delay(&send(5_000, &recipient)));
+
+        let now = chrono::Utc::now().naive_utc();
+        let approval = LargeSendApproval::new(send(5_000, &recipient).approval_key(), now, policy.large_send_delay);
+        assert!(!approval.is_available(now));
+        assert!(approval.is_available(now + chrono::Duration::hours(1)));
+        assert!(approval.is_expired(now + chrono::Duration::hours(25)));
+        assert!(!approval.is_available(now + chrono::Duration::hours(25)));
+    }
+
+    #[test]
+    fn it_keys_approvals_by_amount_and_destination() {
+        let recipient = address();
+        assert_eq!(
+            send(5_000, &recipient).approval_key(),
+            send(5_000, &recipient).approval_key()
+        );
+        assert_ne!(
+            send(5_000, &recipient).approval_key(),
+            send(5_001, &recipient).approval_key()
+        );
+        assert_ne!(
+            send(5_000, &recipient).approval_key(),
+            send(5_000, &address()).approval_key()
+        );
+    }
+
+    #[test]
+    fn it_round_trips_through_json() {
+        let allowed = address();
+        let policy = SpendingPolicy {
+            max_amount_per_transaction: Some(MicroMinotari::from(1_000)),
+            daily_limit: Some(MicroMinotari::from(10_000)),
+            allowed_destinations: Some(vec![allowed.clone()]),
+            large_send_threshold: Some(MicroMinotari::from(500)),
+            large_send_delay: Duration::from_secs(600),
+        };
+        let json = serde_json::to_string(&policy).unwrap();
+        assert!(json.contains(&allowed.to_base58()));
+        assert_eq!(serde_json::from_str::<SpendingPolicy>(&json).unwrap(), policy);
+        assert_eq!(
+            serde_json::from_str::<SpendingPolicy>("{}").unwrap(),
+            SpendingPolicy::default()
+        );
+    }
+}
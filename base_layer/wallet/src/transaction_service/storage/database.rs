@@ -38,15 +38,20 @@ use tari_common_types::{
 use tari_core::transactions::{
     tari_amount::MicroMinotari,
     transaction_components::{encrypted_data::PaymentId, Transaction, TransactionOutput},
+    ReceiverTransactionProtocol,
+    SenderTransactionProtocol,
 };
 
 use crate::transaction_service::{
     error::TransactionStorageError,
+    spending_policy::{LargeSendApproval, SpendingPolicy},
     storage::{
         models::{
             CompletedTransaction,
             InboundTransaction,
             OutboundTransaction,
+            TransactionExportFilter,
+            TransactionExportRecord,
             TxCancellationReason,
             WalletTransaction,
         },
@@ -122,6 +127,20 @@ pub trait TransactionBackend: Send + Sync + Clone {
         &self,
         tx_id: TxId,
     ) -> Result<TariAddress, TransactionStorageError>;
+    /// Replace the sender protocol of a pending outbound transaction, used to persist the state of the
+    /// multi-recipient protocol between rounds
+    fn update_pending_outbound_sender_protocol(
+        &self,
+        tx_id: TxId,
+        sender_protocol: SenderTransactionProtocol,
+    ) -> Result<(), TransactionStorageError>;
+    /// Replace the receiver protocol of a pending inbound transaction, used to persist the state of the
+    /// multi-recipient protocol between rounds
+    fn update_pending_inbound_receiver_protocol(
+        &self,
+        tx_id: TxId,
+        receiver_protocol: ReceiverTransactionProtocol,
+    ) -> Result<(), TransactionStorageError>;
     /// Mark a pending transaction direct send attempt as a success
     fn mark_direct_send_success(&self, tx_id: TxId) -> Result<(), TransactionStorageError>;
     /// Increment the send counter and timestamp of a transaction
@@ -157,6 +176,19 @@ pub trait TransactionBackend: Send + Sync + Clone {
         &self,
         height: u64,
     ) -> Result<Vec<CompletedTransaction>, TransactionStorageError>;
+    /// Retrieve the spending policy of the wallet, `None` if one was never set
+    fn fetch_spending_policy(&self) -> Result<Option<SpendingPolicy>, TransactionStorageError>;
+    /// Replace the spending policy of the wallet
+    fn set_spending_policy(&self, policy: &SpendingPolicy) -> Result<(), TransactionStorageError>;
+    /// Sum the amounts of all outbound transactions created since `since` that have not been cancelled
+    fn fetch_outbound_amount_since(&self, since: NaiveDateTime) -> Result<MicroMinotari, TransactionStorageError>;
+    fn fetch_large_send_approval(
+        &self,
+        approval_key: &str,
+    ) -> Result<Option<LargeSendApproval>, TransactionStorageError>;
+    /// Insert or replace the approval with the same key
+    fn save_large_send_approval(&self, approval: &LargeSendApproval) -> Result<(), TransactionStorageError>;
+    fn remove_large_send_approval(&self, approval_key: &str) -> Result<(), TransactionStorageError>;
 }
 
 #[derive(Clone, PartialEq)]
@@ -599,6 +631,35 @@ where T: TransactionBackend + 'static
         self.get_completed_transactions_by_cancelled(true)
     }
 
+    /// Fetches all pending, completed and cancelled transactions matching the filter, ordered by the time they were
+    /// mined or created.
+    pub fn export_transactions(
+        &self,
+        filter: &TransactionExportFilter,
+    ) -> Result<Vec<TransactionExportRecord>, TransactionStorageError> {
+        let mut records = Vec::new();
+        for cancelled in [false, true] {
+            let completed = self.get_completed_transactions_by_cancelled(cancelled)?.into_values();
+            let inbound = self
+                .get_pending_inbound_transactions_by_cancelled(cancelled)?
+                .into_values()
+                .map(CompletedTransaction::from);
+            let outbound = self
+                .get_pending_outbound_transactions_by_cancelled(cancelled)?
+                .into_values()
+                .map(CompletedTransaction::from);
+            records.extend(
+                completed
+                    .chain(inbound)
+                    .chain(outbound)
+                    .map(TransactionExportRecord::from)
+                    .filter(|record| filter.matches(record)),
+            );
+        }
+        records.sort_by_key(|record| (record.effective_timestamp(), u64::from(record.tx_id)));
+        Ok(records)
+    }
+
     pub fn get_any_transaction(&self, tx_id: TxId) -> Result<Option<WalletTransaction>, TransactionStorageError> {
         let key = DbKey::AnyTransaction(tx_id);
         let t = match self.db.fetch(&key) {
@@ -674,6 +735,23 @@ where T: TransactionBackend + 'static
         self.db.set_pending_transaction_cancellation_status(tx_id, false)
     }
 
+    pub fn update_pending_outbound_sender_protocol(
+        &self,
+        tx_id: TxId,
+        sender_protocol: SenderTransactionProtocol,
+    ) -> Result<(), TransactionStorageError> {
+        self.db.update_pending_outbound_sender_protocol(tx_id, sender_protocol)
+    }
+
+    pub fn update_pending_inbound_receiver_protocol(
+        &self,
+        tx_id: TxId,
+        receiver_protocol: ReceiverTransactionProtocol,
+    ) -> Result<(), TransactionStorageError> {
+        self.db
+            .update_pending_inbound_receiver_protocol(tx_id, receiver_protocol)
+    }
+
     pub fn mark_direct_send_success(&self, tx_id: TxId) -> Result<(), TransactionStorageError> {
         self.db.mark_direct_send_success(tx_id)
     }
@@ -773,6 +851,33 @@ where T: TransactionBackend + 'static
         }?;
         Ok(t)
     }
+
+    pub fn get_spending_policy(&self) -> Result<SpendingPolicy, TransactionStorageError> {
+        Ok(self.db.fetch_spending_policy()?.unwrap_or_default())
+    }
+
+    pub fn set_spending_policy(&self, policy: &SpendingPolicy) -> Result<(), TransactionStorageError> {
+        self.db.set_spending_policy(policy)
+    }
+
+    pub fn get_outbound_amount_since(&self, since: NaiveDateTime) -> Result<MicroMinotari, TransactionStorageError> {
+        self.db.fetch_outbound_amount_since(since)
+    }
+
+    pub fn get_large_send_approval(
+        &self,
+        approval_key: &str,
+    ) -> Result<Option<LargeSendApproval>, TransactionStorageError> {
+        self.db.fetch_large_send_approval(approval_key)
+    }
+
+    pub fn save_large_send_approval(&self, approval: &LargeSendApproval) -> Result<(), TransactionStorageError> {
+        self.db.save_large_send_approval(approval)
+    }
+
+    pub fn remove_large_send_approval(&self, approval_key: &str) -> Result<(), TransactionStorageError> {
+        self.db.remove_large_send_approval(approval_key)
+    }
 }
 
 impl Display for DbKey {
@@ -30,7 +30,7 @@ use serde::{Deserialize, Serialize};
 use tari_common_types::{
     tari_address::TariAddress,
     transaction::{TransactionConversionError, TransactionDirection, TransactionStatus, TxId},
-    types::{BlockHash, PrivateKey, Signature},
+    types::{BlockHash, Commitment, PrivateKey, Signature},
 };
 use tari_core::transactions::{
     tari_amount::MicroMinotari,
@@ -326,6 +326,102 @@ impl From<WalletTransaction> for CompletedTransaction {
     }
 }
 
+/// A flattened view of a wallet transaction, pending or completed, as used for accounting exports
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransactionExportRecord {
+    pub tx_id: TxId,
+    pub direction: TransactionDirection,
+    pub status: TransactionStatus,
+    pub cancelled: bool,
+    pub amount: MicroMinotari,
+    pub fee: MicroMinotari,
+    pub payment_id: Option<PaymentId>,
+    pub counterparty: TariAddress,
+    pub message: String,
+    pub timestamp: NaiveDateTime,
+    pub mined_height: Option<u64>,
+    pub mined_timestamp: Option<NaiveDateTime>,
+    pub confirmations: Option<u64>,
+    pub kernel_excess: Option<Commitment>,
+}
+
+impl TransactionExportRecord {
+    /// The signed change to the wallet balance in micro Minotari. Received amounts are credits, sent amounts and their
+    /// fees are debits and cancelled transactions do not change the balance.
+    pub fn balance_change(&self) -> i64 {
+        if self.cancelled {
+            return 0;
+        }
+        let to_i64 = |v: MicroMinotari| i64::try_from(v.as_u64()).unwrap_or(i64::MAX);
+        match self.direction {
+            TransactionDirection::Inbound => to_i64(self.amount),
+            TransactionDirection::Outbound => -to_i64(self.amount.saturating_add(self.fee)),
+            TransactionDirection::Unknown => 0,
+        }
+    }
+
+    /// The time the transaction was mined, or the time it was created if it has not been mined
+    pub fn effective_timestamp(&self) -> NaiveDateTime {
+        self.mined_timestamp.unwrap_or(self.timestamp)
+    }
+}
+
+impl From<CompletedTransaction> for TransactionExportRecord {
+    fn from(tx: CompletedTransaction) -> Self {
+        let counterparty = match tx.direction {
+            TransactionDirection::Outbound => tx.destination_address,
+            _ => tx.source_address,
+        };
+        Self {
+            tx_id: tx.tx_id,
+            kernel_excess: tx.transaction.body().kernels().first().map(|k| k.excess.clone()),
+            direction: tx.direction,
+            status: tx.status,
+            cancelled: tx.cancelled.is_some(),
+            amount: tx.amount,
+            fee: tx.fee,
+            payment_id: tx.payment_id,
+            counterparty,
+            message: tx.message,
+            timestamp: tx.timestamp,
+            mined_height: tx.mined_height,
+            mined_timestamp: tx.mined_timestamp,
+            confirmations: tx.confirmations,
+        }
+    }
+}
+
+/// Restricts a transaction export to a time and/or mined height range. All bounds are inclusive. Transactions are
+/// matched on their mined timestamp, or their creation time if they are not mined yet, and unmined transactions are
+/// excluded when a height bound is set.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TransactionExportFilter {
+    pub from_timestamp: Option<NaiveDateTime>,
+    pub to_timestamp: Option<NaiveDateTime>,
+    pub from_height: Option<u64>,
+    pub to_height: Option<u64>,
+}
+
+impl TransactionExportFilter {
+    pub fn matches(&self, record: &TransactionExportRecord) -> bool {
+        let timestamp = record.effective_timestamp();
+        if self.from_timestamp.map_or(false, |from| timestamp < from) ||
+            self.to_timestamp.map_or(false, |to| timestamp > to)
+        {
+            return false;
+        }
+        if self.from_height.is_none() && self.to_height.is_none() {
+            return true;
+        }
+        match record.mined_height {
+            Some(height) => {
+                self.from_height.map_or(true, |from| height >= from) && self.to_height.map_or(true, |to| height <= to)
+            },
+            None => false,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum TxCancellationReason {
     Unknown,            // 0
@@ -43,17 +43,29 @@ use tari_common_types::{
     },
     types::{BlockHash, PrivateKey, PublicKey, Signature},
 };
-use tari_core::transactions::{tari_amount::MicroMinotari, transaction_components::encrypted_data::PaymentId};
+use tari_core::transactions::{
+    tari_amount::MicroMinotari,
+    transaction_components::encrypted_data::PaymentId,
+    ReceiverTransactionProtocol,
+    SenderTransactionProtocol,
+};
 use tari_utilities::{hex::Hex, ByteArray, Hidden};
 use thiserror::Error;
 use tokio::time::Instant;
 use zeroize::Zeroize;
 
 use crate::{
-    schema::{completed_transactions, inbound_transactions, outbound_transactions},
+    schema::{
+        completed_transactions,
+        inbound_transactions,
+        large_send_approvals,
+        outbound_transactions,
+        spending_policy,
+    },
     storage::sqlite_utilities::wallet_db_connection::WalletDbConnection,
     transaction_service::{
         error::{TransactionKeyError, TransactionStorageError},
+        spending_policy::{LargeSendApproval, SpendingPolicy},
         storage::{
             database::{DbKey, DbKeyValuePair, DbValue, TransactionBackend, WriteOperation},
             models::{
@@ -781,6 +793,66 @@ impl TransactionBackend for TransactionServiceSqliteDatabase {
         Ok(())
     }
 
+    fn update_pending_outbound_sender_protocol(
+        &self,
+        tx_id: TxId,
+        sender_protocol: SenderTransactionProtocol,
+    ) -> Result<(), TransactionStorageError> {
+        let start = Instant::now();
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        let acquire_lock = start.elapsed();
+        let cipher = acquire_read_lock!(self.cipher);
+
+        let mut outbound_tx = OutboundTransaction::try_from(
+            OutboundTransactionSql::find_by_cancelled(tx_id, false, &mut conn)?,
+            &cipher,
+        )?;
+        outbound_tx.sender_protocol = sender_protocol;
+        OutboundTransactionSql::try_from(outbound_tx, &cipher)?.update_sender_protocol(&mut conn)?;
+
+        if start.elapsed().as_millis() > 0 {
+            trace!(
+                target: LOG_TARGET,
+                "sqlite profile - update_pending_outbound_sender_protocol: lock {} + db_op {} = {} ms",
+                acquire_lock.as_millis(),
+                (start.elapsed() - acquire_lock).as_millis(),
+                start.elapsed().as_millis()
+            );
+        }
+
+        Ok(())
+    }
+
+    fn update_pending_inbound_receiver_protocol(
+        &self,
+        tx_id: TxId,
+        receiver_protocol: ReceiverTransactionProtocol,
+    ) -> Result<(), TransactionStorageError> {
+        let start = Instant::now();
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        let acquire_lock = start.elapsed();
+        let cipher = acquire_read_lock!(self.cipher);
+
+        let mut inbound_tx = InboundTransaction::try_from(
+            InboundTransactionSql::find_by_cancelled(tx_id, false, &mut conn)?,
+            &cipher,
+        )?;
+        inbound_tx.receiver_protocol = receiver_protocol;
+        InboundTransactionSql::try_from(inbound_tx, &cipher)?.update_receiver_protocol(&mut conn)?;
+
+        if start.elapsed().as_millis() > 0 {
+            trace!(
+                target: LOG_TARGET,
+                "sqlite profile - update_pending_inbound_receiver_protocol: lock {} + db_op {} = {} ms",
+                acquire_lock.as_millis(),
+                (start.elapsed() - acquire_lock).as_millis(),
+                start.elapsed().as_millis()
+            );
+        }
+
+        Ok(())
+    }
+
     fn increment_send_count(&self, tx_id: TxId) -> Result<(), TransactionStorageError> {
         let start = Instant::now();
         let mut conn = self.database_connection.get_pooled_connection()?;
@@ -1120,6 +1192,110 @@ impl TransactionBackend for TransactionServiceSqliteDatabase {
         coinbases.append(&mut one_sided);
         Ok(coinbases)
     }
+
+    fn fetch_spending_policy(&self) -> Result<Option<SpendingPolicy>, TransactionStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        let policy = spending_policy::table
+            .select(spending_policy::policy)
+            .filter(spending_policy::id.eq(SPENDING_POLICY_ROW_ID))
+            .first::<String>(&mut conn)
+            .optional()?;
+        policy
+            .map(|p| serde_json::from_str(&p).map_err(TransactionStorageError::from))
+            .transpose()
+    }
+
+    fn set_spending_policy(&self, policy: &SpendingPolicy) -> Result<(), TransactionStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        diesel::replace_into(spending_policy::table)
+            .values((
+                spending_policy::id.eq(SPENDING_POLICY_ROW_ID),
+                spending_policy::policy.eq(serde_json::to_string(policy)?),
+                spending_policy::updated_at.eq(Utc::now().naive_utc()),
+            ))
+            .execute(&mut conn)?;
+        Ok(())
+    }
+
+    fn fetch_outbound_amount_since(&self, since: NaiveDateTime) -> Result<MicroMinotari, TransactionStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        // Pending outbound transactions are moved to the completed table once finalized, so a send is only ever
+        // counted once
+        let pending = outbound_transactions::table
+            .select(outbound_transactions::amount)
+            .filter(outbound_transactions::cancelled.eq(i32::from(false)))
+            .filter(outbound_transactions::timestamp.ge(since))
+            .load::<i64>(&mut conn)?;
+        let completed = completed_transactions::table
+            .select(completed_transactions::amount)
+            .filter(completed_transactions::cancelled.is_null())
+            .filter(completed_transactions::direction.eq(TransactionDirection::Outbound as i32))
+            .filter(completed_transactions::timestamp.ge(since))
+            .load::<i64>(&mut conn)?;
+        Ok(pending
+            .into_iter()
+            .chain(completed)
+            .map(|amount| MicroMinotari::from(amount as u64))
+            .fold(MicroMinotari::zero(), |total, amount| total.saturating_add(amount)))
+    }
+
+    fn fetch_large_send_approval(
+        &self,
+        approval_key: &str,
+    ) -> Result<Option<LargeSendApproval>, TransactionStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        let approval = large_send_approvals::table
+            .filter(large_send_approvals::approval_key.eq(approval_key))
+            .first::<LargeSendApprovalSql>(&mut conn)
+            .optional()?;
+        Ok(approval.map(LargeSendApproval::from))
+    }
+
+    fn save_large_send_approval(&self, approval: &LargeSendApproval) -> Result<(), TransactionStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        diesel::replace_into(large_send_approvals::table)
+            .values(LargeSendApprovalSql::from(approval.clone()))
+            .execute(&mut conn)?;
+        Ok(())
+    }
+
+    fn remove_large_send_approval(&self, approval_key: &str) -> Result<(), TransactionStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        diesel::delete(large_send_approvals::table.filter(large_send_approvals::approval_key.eq(approval_key)))
+            .execute(&mut conn)?;
+        Ok(())
+    }
+}
+
+/// The spending policy table holds a single row
+const SPENDING_POLICY_ROW_ID: i32 = 1;
+
+#[derive(Clone, Debug, Queryable, Insertable, PartialEq)]
+#[diesel(table_name = large_send_approvals)]
+struct LargeSendApprovalSql {
+    approval_key: String,
+    available_at: NaiveDateTime,
+    expires_at: NaiveDateTime,
+}
+
+impl From<LargeSendApproval> for LargeSendApprovalSql {
+    fn from(approval: LargeSendApproval) -> Self {
+        Self {
+            approval_key: approval.approval_key,
+            available_at: approval.available_at,
+            expires_at: approval.expires_at,
+        }
+    }
+}
+
+impl From<LargeSendApprovalSql> for LargeSendApproval {
+    fn from(sql: LargeSendApprovalSql) -> Self {
+        Self {
+            approval_key: sql.approval_key,
+            available_at: sql.available_at,
+            expires_at: sql.expires_at,
+        }
+    }
 }
 
 #[derive(Debug, PartialEq)]
@@ -1328,6 +1504,19 @@ impl InboundTransactionSql {
         Ok(())
     }
 
+    pub fn update_receiver_protocol(&self, conn: &mut SqliteConnection) -> Result<(), TransactionStorageError> {
+        self.update(
+            UpdateInboundTransactionSql {
+                cancelled: None,
+                direct_send_success: None,
+                receiver_protocol: Some(self.receiver_protocol.clone()),
+                send_count: None,
+                last_send_timestamp: None,
+            },
+            conn,
+        )
+    }
+
     #[allow(dead_code)]
     pub fn update_encryption(&self, conn: &mut SqliteConnection) -> Result<(), TransactionStorageError> {
         self.update(
@@ -1576,6 +1765,19 @@ impl OutboundTransactionSql {
         Ok(())
     }
 
+    pub fn update_sender_protocol(&self, conn: &mut SqliteConnection) -> Result<(), TransactionStorageError> {
+        self.update(
+            UpdateOutboundTransactionSql {
+                cancelled: None,
+                direct_send_success: None,
+                sender_protocol: Some(self.sender_protocol.clone()),
+                send_count: None,
+                last_send_timestamp: None,
+            },
+            conn,
+        )
+    }
+
     #[allow(dead_code)]
     pub fn update_encryption(&self, conn: &mut SqliteConnection) -> Result<(), TransactionStorageError> {
         self.update(
@@ -2231,17 +2433,20 @@ mod test {
 
     use crate::{
         storage::sqlite_utilities::wallet_db_connection::WalletDbConnection,
-        test_utils::create_consensus_constants,
-        transaction_service::storage::{
-            database::{DbKey, TransactionBackend},
-            models::{CompletedTransaction, InboundTransaction, OutboundTransaction, TxCancellationReason},
-            sqlite_db::{
-                CompletedTransactionSql,
-                InboundTransactionSenderInfo,
-                InboundTransactionSql,
-                OutboundTransactionSql,
-                TransactionServiceSqliteDatabase,
-                UpdateCompletedTransactionSql,
+        test_utils::{create_consensus_constants, make_wallet_database_memory_connection},
+        transaction_service::{
+            spending_policy::{LargeSendApproval, SpendingPolicy},
+            storage::{
+                database::{DbKey, TransactionBackend},
+                models::{CompletedTransaction, InboundTransaction, OutboundTransaction, TxCancellationReason},
+                sqlite_db::{
+                    CompletedTransactionSql,
+                    InboundTransactionSenderInfo,
+                    InboundTransactionSql,
+                    OutboundTransactionSql,
+                    TransactionServiceSqliteDatabase,
+                    UpdateCompletedTransactionSql,
+                },
             },
         },
     };
@@ -3088,4 +3293,37 @@ mod test {
         assert_eq!(info_list.len(), 941);
         assert_eq!(info_list, info_list_reference);
     }
+
+    #[test]
+    fn it_stores_the_spending_policy_and_large_send_approvals() {
+        let mut key = [0u8; size_of::<Key>()];
+        OsRng.fill_bytes(&mut key);
+        let cipher = XChaCha20Poly1305::new(Key::from_slice(&key));
+        let db = TransactionServiceSqliteDatabase::new(make_wallet_database_memory_connection(), cipher);
+
+        assert_eq!(db.fetch_spending_policy().unwrap(), None);
+        let policy = SpendingPolicy {
+            daily_limit: Some(MicroMinotari::from(10_000)),
+            large_send_threshold: Some(MicroMinotari::from(5_000)),
+            large_send_delay: Duration::from_secs(3600),
+            ..Default::default()
+        };
+        db.set_spending_policy(&policy).unwrap();
+        db.set_spending_policy(&policy).unwrap();
+        assert_eq!(db.fetch_spending_policy().unwrap(), Some(policy));
+        assert_eq!(
+            db.fetch_outbound_amount_since(Utc::now().naive_utc()).unwrap(),
+            MicroMinotari::zero()
+        );
+
+        let approval = LargeSendApproval::new("5000:burn".to_string(), Utc::now().naive_utc(), Duration::from_secs(60));
+        assert_eq!(db.fetch_large_send_approval(&approval.approval_key).unwrap(), None);
+        db.save_large_send_approval(&approval).unwrap();
+        assert_eq!(
+            db.fetch_large_send_approval(&approval.approval_key).unwrap(),
+            Some(approval.clone())
+        );
+        db.remove_large_send_approval(&approval.approval_key).unwrap();
+        assert_eq!(db.fetch_large_send_approval(&approval.approval_key).unwrap(), None);
+    }
 }
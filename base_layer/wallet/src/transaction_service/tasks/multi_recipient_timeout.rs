@@ -0,0 +1,112 @@
+//  Copyright 2024, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{sync::Arc, time::Duration};
+
+use log::*;
+use tari_common_types::transaction::TxId;
+use tari_comms::types::CommsPublicKey;
+use tari_comms_dht::outbound::OutboundMessageRequester;
+use tari_shutdown::ShutdownSignal;
+use tokio::{sync::oneshot, time::sleep};
+
+use crate::{
+    output_manager_service::handle::OutputManagerHandle,
+    transaction_service::{
+        handle::{TransactionEvent, TransactionEventSender},
+        storage::{
+            database::{TransactionBackend, TransactionDatabase},
+            models::TxCancellationReason,
+        },
+        tasks::send_transaction_cancelled::send_transaction_cancelled_message,
+    },
+};
+
+const LOG_TARGET: &str = "wallet::transaction_service::tasks::multi_recipient_timeout";
+
+/// Watch over a pending multi-recipient transaction, which has no send protocol of its own. If the transaction is
+/// still pending when the timeout expires it is cancelled and its inputs are released. The recipients are told about
+/// the cancellation, whether it was due to the timeout or requested via the cancellation channel. Dropping the
+/// cancellation sender, which the service does once the transaction completes, ends this task.
+#[allow(clippy::too_many_arguments)]
+pub async fn multi_recipient_transaction_timeout<TBackend: 'static + TransactionBackend>(
+    tx_id: TxId,
+    timeout: Duration,
+    recipients: Vec<CommsPublicKey>,
+    cancellation_receiver: oneshot::Receiver<()>,
+    db: TransactionDatabase<TBackend>,
+    mut output_manager_service: OutputManagerHandle,
+    outbound_message_service: OutboundMessageRequester,
+    event_publisher: TransactionEventSender,
+    mut shutdown_signal: ShutdownSignal,
+) {
+    tokio::select! {
+        result = cancellation_receiver => {
+            if result.is_err() {
+                trace!(target: LOG_TARGET, "Multi-recipient transaction (TxId: {}) no longer pending", tx_id);
+                return;
+            }
+            // The service has already cancelled the transaction, only the recipients still need to be told
+            info!(target: LOG_TARGET, "Multi-recipient transaction (TxId: {}) cancelled", tx_id);
+        },
+        () = sleep(timeout) => {
+            // The transaction may have completed while we were waiting
+            if db.get_pending_outbound_transaction(tx_id).is_err() {
+                return;
+            }
+            info!(
+                target: LOG_TARGET,
+                "Cancelling multi-recipient transaction (TxId: {}) due to timeout after no response from all recipients",
+                tx_id
+            );
+            if let Err(e) = db.cancel_pending_transaction(tx_id) {
+                warn!(
+                    target: LOG_TARGET,
+                    "Pending multi-recipient transaction (TxId: {}) could not be cancelled: {:?}", tx_id, e
+                );
+                return;
+            }
+            if let Err(e) = output_manager_service.cancel_transaction(tx_id).await {
+                error!(
+                    target: LOG_TARGET,
+                    "Outputs of multi-recipient transaction (TxId: {}) could not be released: {:?}", tx_id, e
+                );
+            }
+            let _size = event_publisher.send(Arc::new(TransactionEvent::TransactionCancelled(
+                tx_id,
+                TxCancellationReason::Timeout,
+            )));
+        },
+        _ = shutdown_signal.wait() => {
+            return;
+        },
+    }
+
+    for recipient in recipients {
+        if let Err(e) = send_transaction_cancelled_message(tx_id, recipient, outbound_message_service.clone()).await {
+            warn!(
+                target: LOG_TARGET,
+                "Error sending Transaction Cancelled (TxId: {}) message: {:?}", tx_id, e
+            );
+        }
+    }
+}
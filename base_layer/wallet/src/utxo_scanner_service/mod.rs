@@ -31,8 +31,11 @@
 pub mod error;
 pub mod handle;
 pub mod initializer;
+pub mod recovery_chunks;
 pub mod service;
 mod utxo_scanner_task;
 pub mod uxto_scanner_service_builder;
 
 pub const RECOVERY_KEY: &str = "recovery_data";
+/// The chunks of the height range that a parallel recovery in progress is split into
+pub const RECOVERY_CHUNKS_KEY: &str = "recovery_chunks";
@@ -0,0 +1,153 @@
+//  Copyright 2024, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use serde::{Deserialize, Serialize};
+
+use crate::utxo_scanner_service::service::ScannedBlock;
+
+/// The number of blocks in each chunk of a parallel recovery
+pub const RECOVERY_CHUNK_SIZE: u64 = 2_000;
+
+/// A range of block heights that is scanned by a single base node during a parallel recovery
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecoveryChunk {
+    pub start_height: u64,
+    pub end_height: u64,
+}
+
+impl RecoveryChunk {
+    /// Splits the inclusive height range into consecutive chunks of at most `chunk_size` blocks
+    pub fn plan(start_height: u64, end_height: u64, chunk_size: u64) -> Vec<RecoveryChunk> {
+        let chunk_size = chunk_size.max(1);
+        let mut chunks = Vec::new();
+        let mut height = start_height;
+        while height <= end_height {
+            let chunk_end = height.saturating_add(chunk_size - 1).min(end_height);
+            chunks.push(RecoveryChunk {
+                start_height: height,
+                end_height: chunk_end,
+            });
+            match chunk_end.checked_add(1) {
+                Some(next) => height = next,
+                None => break,
+            }
+        }
+        chunks
+    }
+
+    /// Returns the height that scanning of this chunk resumes from, given the blocks that have been scanned so far, or
+    /// `None` if the chunk has been scanned up to its last block
+    pub fn resume_height(&self, scanned_blocks: &[ScannedBlock]) -> Option<u64> {
+        let last_scanned = scanned_blocks
+            .iter()
+            .map(|b| b.height)
+            .filter(|h| *h >= self.start_height && *h <= self.end_height)
+            .max();
+        match last_scanned {
+            Some(height) if height >= self.end_height => None,
+            Some(height) => Some(height + 1),
+            None => Some(self.start_height),
+        }
+    }
+
+    /// Returns the height up to which every block of the planned chunks has been scanned, or `None` if the first
+    /// block has not been scanned yet. Chunks finish out of order, so this is what a recovery reports as progress.
+    pub fn contiguous_scanned_height(chunks: &[RecoveryChunk], scanned_blocks: &[ScannedBlock]) -> Option<u64> {
+        let mut scanned_height = None;
+        for chunk in chunks {
+            match chunk.resume_height(scanned_blocks) {
+                None => scanned_height = Some(chunk.end_height),
+                Some(height) if height > chunk.start_height => return Some(height - 1),
+                Some(_) => return scanned_height,
+            }
+        }
+        scanned_height
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::Utc;
+    use tari_common_types::types::FixedHash;
+
+    use super::*;
+
+    fn scanned_block(height: u64) -> ScannedBlock {
+        ScannedBlock {
+            header_hash: FixedHash::zero(),
+            height,
+            num_outputs: Some(0),
+            amount: Some(0.into()),
+            timestamp: Utc::now().naive_utc(),
+        }
+    }
+
+    #[test]
+    fn it_splits_the_range_into_chunks() {
+        let chunks = RecoveryChunk::plan(10, 34, 10);
+        assert_eq!(
+            chunks
+                .iter()
+                .map(|c| (c.start_height, c.end_height))
+                .collect::<Vec<_>>(),
+            vec![(10, 19), (20, 29), (30, 34)]
+        );
+        assert_eq!(RecoveryChunk::plan(5, 5, 10).len(), 1);
+        assert!(RecoveryChunk::plan(6, 5, 10).is_empty());
+        assert_eq!(RecoveryChunk::plan(0, 3, 0).len(), 4);
+    }
+
+    #[test]
+    fn it_resumes_each_chunk_from_its_own_checkpoint() {
+        let chunks = RecoveryChunk::plan(0, 29, 10);
+        let scanned_blocks = vec![scanned_block(4), scanned_block(3), scanned_block(19), scanned_block(25)];
+        assert_eq!(chunks[0].resume_height(&scanned_blocks), Some(5));
+        assert_eq!(chunks[1].resume_height(&scanned_blocks), None);
+        assert_eq!(chunks[2].resume_height(&scanned_blocks), Some(26));
+        assert_eq!(chunks[0].resume_height(&[]), Some(0));
+    }
+
+    #[test]
+    fn it_reports_the_highest_contiguous_scanned_height() {
+        let chunks = RecoveryChunk::plan(10, 39, 10);
+        assert_eq!(RecoveryChunk::contiguous_scanned_height(&chunks, &[]), None);
+
+        // The later chunks finishing first does not move the progress past the unscanned first chunk
+        let mut scanned_blocks = vec![scanned_block(29), scanned_block(39)];
+        assert_eq!(RecoveryChunk::contiguous_scanned_height(&chunks, &scanned_blocks), None);
+        scanned_blocks.push(scanned_block(14));
+        assert_eq!(
+            RecoveryChunk::contiguous_scanned_height(&chunks, &scanned_blocks),
+            Some(14)
+        );
+        scanned_blocks.push(scanned_block(19));
+        assert_eq!(
+            RecoveryChunk::contiguous_scanned_height(&chunks, &scanned_blocks),
+            Some(29)
+        );
+        scanned_blocks.push(scanned_block(30));
+        assert_eq!(
+            RecoveryChunk::contiguous_scanned_height(&chunks, &scanned_blocks),
+            Some(39)
+        );
+    }
+}
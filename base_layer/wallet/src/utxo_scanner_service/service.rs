@@ -197,6 +197,8 @@ pub struct UtxoScannerResources<TBackend, TWalletConnectivity> {
     pub factories: CryptoFactories,
     pub recovery_message: String,
     pub one_sided_payment_message: String,
+    pub use_block_filters: bool,
+    pub recovery_chunk_size: u64,
 }
 
 #[derive(Debug, Clone)]
@@ -21,12 +21,14 @@
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use std::{
+    collections::VecDeque,
     convert::{TryFrom, TryInto},
+    sync::Arc,
     time::{Duration, Instant},
 };
 
 use chrono::{NaiveDateTime, Utc};
-use futures::StreamExt;
+use futures::{future, StreamExt};
 use log::*;
 use tari_common_types::{
     tari_address::TariAddress,
@@ -43,8 +45,8 @@ use tari_comms::{
 };
 use tari_core::{
     base_node::rpc::BaseNodeWalletRpcClient,
-    blocks::BlockHeader,
-    proto::base_node::SyncUtxosByBlockRequest,
+    blocks::{BlockFilter, BlockHeader},
+    proto::base_node::{GetBlockFiltersRequest, SyncUtxosByBlockRequest},
     transactions::{
         tari_amount::MicroMinotari,
         transaction_components::{encrypted_data::PaymentId, TransactionOutput, WalletOutput},
@@ -53,7 +55,7 @@ use tari_core::{
 use tari_key_manager::get_birthday_from_unix_epoch_in_seconds;
 use tari_shutdown::ShutdownSignal;
 use tari_utilities::hex::Hex;
-use tokio::sync::broadcast;
+use tokio::sync::{broadcast, Mutex};
 
 use crate::{
     connectivity_service::WalletConnectivityInterface,
@@ -63,13 +65,19 @@ use crate::{
     utxo_scanner_service::{
         error::UtxoScannerError,
         handle::UtxoScannerEvent,
+        recovery_chunks::RecoveryChunk,
         service::{ScannedBlock, UtxoScannerResources, SCANNED_BLOCK_CACHE_SIZE},
         uxto_scanner_service_builder::UtxoScannerMode,
+        RECOVERY_CHUNKS_KEY,
         RECOVERY_KEY,
     },
 };
 
 pub const LOG_TARGET: &str = "wallet::utxo_scanning";
+// The number of block filters requested from the base node at once
+const BLOCK_FILTER_BATCH_SIZE: u64 = 1000;
+// The maximum number of base nodes that recovery chunks are scanned from at once
+const MAX_RECOVERY_WORKERS: usize = 8;
 
 pub struct UtxoScannerTask<TBackend, TWalletConnectivity> {
     pub(crate) resources: UtxoScannerResources<TBackend, TWalletConnectivity>,
@@ -100,6 +108,12 @@ where
             }
         }
 
+        // With more than one base node the bulk of a recovery is scanned in parallel, the blocks mined since are then
+        // scanned sequentially below
+        if self.mode == UtxoScannerMode::Recovery && self.peer_seeds.len() > 1 {
+            self.parallel_recovery().await?;
+        }
+
         loop {
             if self.shutdown_signal.is_triggered() {
                 return Ok(());
@@ -178,6 +192,237 @@ where
         Ok(())
     }
 
+    /// Scans the height range of a recovery in chunks, with each base node scanning one chunk at a time. The range
+    /// is planned once and persisted, and each chunk resumes from its own last scanned block, so an interrupted
+    /// recovery neither rescans nor skips blocks.
+    async fn parallel_recovery(&mut self) -> Result<(), UtxoScannerError> {
+        let chunks = match self.resources.db.get_client_key_value(RECOVERY_CHUNKS_KEY.to_owned())? {
+            Some(chunks) => serde_json::from_str::<Vec<RecoveryChunk>>(&chunks)?,
+            None => {
+                let chunks = self.plan_recovery_chunks().await?;
+                self.resources
+                    .db
+                    .set_client_key_value(RECOVERY_CHUNKS_KEY.to_owned(), serde_json::to_string(&chunks)?)?;
+                chunks
+            },
+        };
+        let tip_height = chunks.last().map(|c| c.end_height).unwrap_or(0);
+        let reported_height = Arc::new(Mutex::new(None));
+
+        let mut last_remaining = None;
+        let mut last_error = None;
+        loop {
+            if self.shutdown_signal.is_triggered() {
+                return Ok(());
+            }
+            let scanned_blocks = self.resources.db.get_scanned_blocks()?;
+            let pending = chunks
+                .iter()
+                .filter_map(|chunk| {
+                    chunk
+                        .resume_height(&scanned_blocks)
+                        .map(|height| (chunk.clone(), height))
+                })
+                .collect::<VecDeque<_>>();
+            if pending.is_empty() {
+                debug!(target: LOG_TARGET, "All {} recovery chunk(s) scanned", chunks.len());
+                return Ok(());
+            }
+
+            // A round in which no blocks were scanned has failed, chunks left over by a base node that failed are
+            // otherwise picked up by the others in the next round
+            let remaining = pending
+                .iter()
+                .map(|(chunk, height)| chunk.end_height.saturating_sub(*height) + 1)
+                .sum::<u64>();
+            if last_remaining.map(|last| remaining >= last).unwrap_or(false) {
+                self.publish_event(UtxoScannerEvent::ScanningRoundFailed {
+                    num_retries: self.num_retries,
+                    retry_limit: self.retry_limit,
+                    error: last_error.take().unwrap_or_default(),
+                });
+                if self.num_retries >= self.retry_limit {
+                    self.publish_event(UtxoScannerEvent::ScanningFailed);
+                    return Err(UtxoScannerError::UtxoScanningError(format!(
+                        "Failed to scan recovery chunks after {} attempt(s) using sync peer(s). Aborting...",
+                        self.num_retries,
+                    )));
+                }
+                self.num_retries += 1;
+            }
+            last_remaining = Some(remaining);
+            debug!(
+                target: LOG_TARGET,
+                "Scanning {} of {} recovery chunk(s), {} block(s) remaining",
+                pending.len(),
+                chunks.len(),
+                remaining
+            );
+
+            // Each round after a failed one moves on to the next base nodes in the list
+            let num_workers = self.peer_seeds.len().min(MAX_RECOVERY_WORKERS);
+            let offset = (self.num_retries.saturating_sub(1) * num_workers) % self.peer_seeds.len();
+            let queue = Arc::new(Mutex::new(pending));
+            let workers = self
+                .peer_seeds
+                .iter()
+                .cycle()
+                .skip(offset)
+                .take(num_workers)
+                .map(|public_key| {
+                    let mut worker = self.chunk_worker();
+                    let queue = queue.clone();
+                    let reported_height = reported_height.clone();
+                    let chunks = &chunks;
+                    let peer = NodeId::from_public_key(public_key);
+                    async move {
+                        let result = worker
+                            .scan_recovery_chunks(peer.clone(), queue, chunks, reported_height, tip_height)
+                            .await;
+                        (peer, result)
+                    }
+                })
+                .collect::<Vec<_>>();
+            for (peer, result) in future::join_all(workers).await {
+                if let Err(e) = result {
+                    warn!(
+                        target: LOG_TARGET,
+                        "Failed to scan recovery chunks from base node {}: {}", peer, e
+                    );
+                    last_error = Some(e.to_string());
+                }
+            }
+        }
+    }
+
+    /// Plans the chunks of a new parallel recovery using the first base node that responds. The range starts after
+    /// the last scanned block that is still in the chain, or at the wallet birthday.
+    async fn plan_recovery_chunks(&mut self) -> Result<Vec<RecoveryChunk>, UtxoScannerError> {
+        let mut last_error = None;
+        for public_key in self.peer_seeds.clone() {
+            let peer = NodeId::from_public_key(&public_key);
+            match self.plan_recovery_chunks_with_peer(&peer).await {
+                Ok(chunks) => return Ok(chunks),
+                Err(e) => {
+                    warn!(
+                        target: LOG_TARGET,
+                        "Failed to plan recovery with base node {}: {}", peer, e
+                    );
+                    last_error = Some(e);
+                },
+            }
+        }
+        Err(last_error
+            .unwrap_or_else(|| UtxoScannerError::UtxoScanningError("No base nodes to recover from".to_string())))
+    }
+
+    async fn plan_recovery_chunks_with_peer(&mut self, peer: &NodeId) -> Result<Vec<RecoveryChunk>, UtxoScannerError> {
+        let mut client = self.establish_new_rpc_connection(peer).await?;
+        let tip_header = self.get_chain_tip_header(&mut client).await?;
+        let start_height = match self.get_last_scanned_block(tip_header.height, &mut client).await? {
+            Some(last_scanned_block) => last_scanned_block.height + 1,
+            None => {
+                self.resources.db.clear_scanned_blocks()?;
+                self.get_birthday_header_height_hash(&mut client).await?.height
+            },
+        };
+        let chunks = RecoveryChunk::plan(start_height, tip_header.height, self.resources.recovery_chunk_size);
+        info!(
+            target: LOG_TARGET,
+            "Recovering blocks {} to {} in {} chunk(s)",
+            start_height,
+            tip_header.height,
+            chunks.len()
+        );
+        Ok(chunks)
+    }
+
+    /// A task that scans recovery chunks from a single base node
+    fn chunk_worker(&self) -> Self {
+        Self {
+            resources: self.resources.clone(),
+            event_sender: self.event_sender.clone(),
+            retry_limit: self.retry_limit,
+            num_retries: self.num_retries,
+            peer_seeds: Vec::new(),
+            peer_index: 0,
+            mode: self.mode.clone(),
+            shutdown_signal: self.shutdown_signal.clone(),
+        }
+    }
+
+    /// Scans chunks from the queue using the given base node until the queue is empty. A chunk that fails is put back
+    /// on the queue from its last scanned block. Progress is reported as the height up to which all chunks have been
+    /// scanned, shared between the workers so that it never goes backwards.
+    async fn scan_recovery_chunks(
+        &mut self,
+        peer: NodeId,
+        queue: Arc<Mutex<VecDeque<(RecoveryChunk, u64)>>>,
+        chunks: &[RecoveryChunk],
+        reported_height: Arc<Mutex<Option<u64>>>,
+        tip_height: u64,
+    ) -> Result<(), UtxoScannerError> {
+        self.publish_event(UtxoScannerEvent::ConnectingToBaseNode(peer.clone()));
+        let mut client = self.establish_new_rpc_connection(&peer).await?;
+        self.publish_event(UtxoScannerEvent::ConnectedToBaseNode(
+            peer,
+            client.get_last_request_latency().unwrap_or_default(),
+        ));
+
+        loop {
+            if self.shutdown_signal.is_triggered() {
+                return Ok(());
+            }
+            let next = queue.lock().await.pop_front();
+            let (chunk, start_height) = match next {
+                Some(next) => next,
+                None => return Ok(()),
+            };
+            if let Err(e) = self
+                .scan_recovery_chunk(&mut client, &chunk, start_height, tip_height)
+                .await
+            {
+                let scanned_blocks = self.resources.db.get_scanned_blocks()?;
+                if let Some(resume_height) = chunk.resume_height(&scanned_blocks) {
+                    queue.lock().await.push_back((chunk, resume_height));
+                }
+                return Err(e);
+            }
+
+            let mut last_reported = reported_height.lock().await;
+            let scanned_blocks = self.resources.db.get_scanned_blocks()?;
+            if let Some(current_height) = RecoveryChunk::contiguous_scanned_height(chunks, &scanned_blocks) {
+                if last_reported.map(|height| current_height > height).unwrap_or(true) {
+                    *last_reported = Some(current_height);
+                    self.publish_event(UtxoScannerEvent::Progress {
+                        current_height,
+                        tip_height,
+                    });
+                }
+            }
+        }
+    }
+
+    async fn scan_recovery_chunk(
+        &mut self,
+        client: &mut BaseNodeWalletRpcClient,
+        chunk: &RecoveryChunk,
+        start_height: u64,
+        tip_height: u64,
+    ) -> Result<(), UtxoScannerError> {
+        let start_header = BlockHeader::try_from(client.get_header_by_height(start_height).await?)
+            .map_err(UtxoScannerError::ConversionError)?;
+        let end_header = BlockHeader::try_from(client.get_header_by_height(chunk.end_height).await?)
+            .map_err(UtxoScannerError::ConversionError)?;
+        debug!(
+            target: LOG_TARGET,
+            "Scanning recovery chunk from height {} to {}", start_height, chunk.end_height
+        );
+        self.scan_utxos(client, start_header.hash(), end_header.hash(), tip_height, true)
+            .await?;
+        Ok(())
+    }
+
     async fn connect_to_peer(&mut self, peer: NodeId) -> Result<PeerConnection, UtxoScannerError> {
         debug!(
             target: LOG_TARGET,
@@ -292,14 +537,20 @@ where
                 next_block_to_scan.header_hash.to_hex(),
             );
 
-            let (num_recovered, num_scanned, amount) = self
-                .scan_utxos(
-                    &mut client,
-                    next_block_to_scan.header_hash,
-                    tip_header_hash,
-                    tip_header.height,
-                )
-                .await?;
+            let (num_recovered, num_scanned, amount) =
+                if self.resources.use_block_filters && self.mode != UtxoScannerMode::Recovery {
+                    self.scan_utxos_with_filters(&mut client, next_block_to_scan.height, tip_header.height)
+                        .await?
+                } else {
+                    self.scan_utxos(
+                        &mut client,
+                        next_block_to_scan.header_hash,
+                        tip_header_hash,
+                        tip_header.height,
+                        false,
+                    )
+                    .await?
+                };
             if num_scanned == 0 {
                 return Err(UtxoScannerError::UtxoScanningError(
                     "Peer returned 0 UTXOs to scan".to_string(),
@@ -437,12 +688,16 @@ where
     #[allow(clippy::too_many_lines)]
     // converting u64 to i64 is its only used for timestamps
     #[allow(clippy::cast_possible_wrap)]
+    /// Scans the blocks from `start_header_hash` to `end_header_hash`. When scanning a chunk of a parallel recovery,
+    /// older scanned blocks are kept as they are the checkpoints of the other chunks, and progress is reported per
+    /// chunk rather than per block.
     async fn scan_utxos(
         &mut self,
         client: &mut BaseNodeWalletRpcClient,
         start_header_hash: HashOutput,
         end_header_hash: HashOutput,
         tip_height: u64,
+        recovery_chunk: bool,
     ) -> Result<(u64, u64, MicroMinotari), UtxoScannerError> {
         // Setting how often the progress event and log should occur during scanning. Defined in blocks
         const PROGRESS_REPORT_INTERVAL: u64 = 100;
@@ -504,12 +759,14 @@ where
                     amount += scanned_block.amount.unwrap_or_else(|| 0.into())
                 } else {
                     self.resources.db.save_scanned_block(scanned_block)?;
-                    self.resources.db.clear_scanned_blocks_before_height(
-                        current_height.saturating_sub(SCANNED_BLOCK_CACHE_SIZE),
-                        true,
-                    )?;
+                    if !recovery_chunk {
+                        self.resources.db.clear_scanned_blocks_before_height(
+                            current_height.saturating_sub(SCANNED_BLOCK_CACHE_SIZE),
+                            true,
+                        )?;
+                    }
 
-                    if current_height % PROGRESS_REPORT_INTERVAL == 0 {
+                    if !recovery_chunk && current_height % PROGRESS_REPORT_INTERVAL == 0 {
                         debug!(
                             target: LOG_TARGET,
                             "Scanned up to block {} with a current tip_height of {}", current_height, tip_height
@@ -534,10 +791,12 @@ where
         }
         // We need to update the last one
         if let Some(scanned_block) = prev_scanned_block {
-            self.resources.db.clear_scanned_blocks_before_height(
-                scanned_block.height.saturating_sub(SCANNED_BLOCK_CACHE_SIZE),
-                true,
-            )?;
+            if !recovery_chunk {
+                self.resources.db.clear_scanned_blocks_before_height(
+                    scanned_block.height.saturating_sub(SCANNED_BLOCK_CACHE_SIZE),
+                    true,
+                )?;
+            }
             self.resources.db.save_scanned_block(scanned_block)?;
         }
         trace!(
@@ -556,6 +815,130 @@ where
         Ok((num_recovered, total_scanned as u64, total_amount))
     }
 
+    /// Scans the blocks from `start_height` up to the tip, downloading only the blocks whose compact block filter
+    /// matches one of the wallet's filter items. Returns the number of blocks checked rather than outputs scanned.
+    /// Stealth one-sided payments have no filter item and are only found if they are in a block that matches.
+    async fn scan_utxos_with_filters(
+        &mut self,
+        client: &mut BaseNodeWalletRpcClient,
+        start_height: u64,
+        tip_height: u64,
+    ) -> Result<(u64, u64, MicroMinotari), UtxoScannerError> {
+        let filter_items = self.resources.output_manager_service.get_block_filter_items().await?;
+
+        let mut num_recovered = 0u64;
+        let mut total_amount = MicroMinotari::from(0);
+        let mut num_checked = 0u64;
+        let mut num_matched = 0u64;
+        let mut next_height = start_height;
+        while next_height <= tip_height {
+            let request = GetBlockFiltersRequest {
+                start_height: next_height,
+                count: (tip_height - next_height + 1).min(BLOCK_FILTER_BATCH_SIZE),
+            };
+            let filters = client.get_block_filters(request).await?.filters;
+            if filters.is_empty() {
+                return Err(UtxoScannerError::UtxoScanningError(format!(
+                    "Peer returned no block filters from height {}",
+                    next_height
+                )));
+            }
+
+            for filter in filters {
+                if self.shutdown_signal.is_triggered() {
+                    return Ok((num_recovered, num_checked, total_amount));
+                }
+                if filter.height != next_height {
+                    return Err(UtxoScannerError::UtxoScanningError(format!(
+                        "Peer returned the block filter for height {} instead of {}",
+                        filter.height, next_height
+                    )));
+                }
+                let header_hash = HashOutput::try_from(filter.header_hash)?;
+                // The filter is only meaningful for the block it was built for, so its hash is checked against the
+                // header of the chain being scanned
+                let header = BlockHeader::try_from(client.get_header_by_height(filter.height).await?)
+                    .map_err(UtxoScannerError::ConversionError)?;
+                if header.hash() != header_hash {
+                    return Err(UtxoScannerError::UtxoScanningError(format!(
+                        "Peer returned the block filter for block {} at height {}, but the block at that height is {}",
+                        header_hash.to_hex(),
+                        filter.height,
+                        header.hash().to_hex()
+                    )));
+                }
+                let block_filter = BlockFilter::from_parts(filter.num_items, filter.data);
+                let (count, amount) = if block_filter.matches_any(&header_hash, &filter_items) {
+                    num_matched += 1;
+                    self.scan_block(client, header_hash, filter.height).await?
+                } else {
+                    (0, MicroMinotari::from(0))
+                };
+                self.resources.db.save_scanned_block(ScannedBlock {
+                    header_hash,
+                    height: filter.height,
+                    num_outputs: Some(count),
+                    amount: Some(amount),
+                    timestamp: Utc::now().naive_utc(),
+                })?;
+                num_recovered = num_recovered.saturating_add(count);
+                total_amount += amount;
+                num_checked += 1;
+                next_height += 1;
+            }
+
+            self.resources
+                .db
+                .clear_scanned_blocks_before_height(next_height.saturating_sub(SCANNED_BLOCK_CACHE_SIZE), true)?;
+            self.publish_event(UtxoScannerEvent::Progress {
+                current_height: next_height - 1,
+                tip_height,
+            });
+        }
+        debug!(
+            target: LOG_TARGET,
+            "Checked {} block filters, downloaded {} matching block(s)", num_checked, num_matched
+        );
+
+        Ok((num_recovered, num_checked, total_amount))
+    }
+
+    /// Downloads and scans the outputs of a single block, returning the number and value of the outputs found
+    async fn scan_block(
+        &mut self,
+        client: &mut BaseNodeWalletRpcClient,
+        header_hash: HashOutput,
+        height: u64,
+    ) -> Result<(u64, MicroMinotari), UtxoScannerError> {
+        let request = SyncUtxosByBlockRequest {
+            start_header_hash: header_hash.to_vec(),
+            end_header_hash: header_hash.to_vec(),
+        };
+        let mut utxo_stream = client.sync_utxos_by_block(request).await?;
+
+        let mut count = 0u64;
+        let mut amount = MicroMinotari::from(0);
+        while let Some(response) = utxo_stream.next().await {
+            let response = response.map_err(|e| UtxoScannerError::RpcStatus(e.to_string()))?;
+            let mined_timestamp = i64::try_from(response.mined_timestamp)
+                .ok()
+                .and_then(|timestamp| NaiveDateTime::from_timestamp_opt(timestamp, 0))
+                .unwrap_or(NaiveDateTime::MIN);
+            let outputs = response
+                .outputs
+                .into_iter()
+                .map(|utxo| TransactionOutput::try_from(utxo).map_err(UtxoScannerError::ConversionError))
+                .collect::<Result<Vec<_>, _>>()?;
+            let found_outputs = self.scan_for_outputs(outputs, height).await?;
+            let (found_count, found_amount) = self
+                .import_utxos_to_transaction_service(found_outputs, height, mined_timestamp)
+                .await?;
+            count = count.saturating_add(found_count);
+            amount += found_amount;
+        }
+        Ok((count, amount))
+    }
+
     async fn scan_for_outputs(
         &mut self,
         outputs: Vec<TransactionOutput>,
@@ -563,12 +946,21 @@ where
     ) -> Result<Vec<(WalletOutput, String, ImportStatus, TxId, TransactionOutput)>, UtxoScannerError> {
         let mut found_outputs: Vec<(WalletOutput, String, ImportStatus, TxId, TransactionOutput)> = Vec::new();
         let start = Instant::now();
+        let recovered_outputs = self
+            .resources
+            .output_manager_service
+            .scan_for_recoverable_outputs(outputs.clone())
+            .await?;
+        // Payments to a sub-address are recovered with the wallet's other outputs, and are attributed to the
+        // sub-address in the imported transaction's message. Sub-addresses that a payment was found for have been
+        // registered by the recovery.
+        let sub_addresses = if recovered_outputs.is_empty() {
+            Vec::new()
+        } else {
+            self.resources.output_manager_service.get_sub_addresses().await?
+        };
         found_outputs.append(
-            &mut self
-                .resources
-                .output_manager_service
-                .scan_for_recoverable_outputs(outputs.clone())
-                .await?
+            &mut recovered_outputs
                 .into_iter()
                 .map(|ro| -> Result<_, UtxoScannerError> {
                     let (message, status) = if ro.output.features.is_coinbase() {
@@ -577,7 +969,16 @@ where
                             ImportStatus::CoinbaseUnconfirmed,
                         )
                     } else {
-                        (self.resources.recovery_message.clone(), ImportStatus::Imported)
+                        match sub_addresses.iter().find(|s| s.script() == ro.output.script) {
+                            Some(sub_address) => (
+                                format!(
+                                    "{} (sub-address {}: {})",
+                                    self.resources.recovery_message, sub_address.index, sub_address.label
+                                ),
+                                ImportStatus::OneSidedUnconfirmed,
+                            ),
+                            None => (self.resources.recovery_message.clone(), ImportStatus::Imported),
+                        }
                     };
                     let output = outputs.iter().find(|o| o.hash() == ro.hash).ok_or_else(|| {
                         UtxoScannerError::UtxoScanningError(format!("Output '{}' not found", ro.hash.to_hex()))
@@ -616,6 +1017,22 @@ where
                 .collect::<Result<Vec<_>, _>>()?,
         );
         let one_sided_time = start.elapsed();
+
+        // Outputs of multisig accounts are tracked by the output manager, but are not imported as they cannot be spent
+        // by this wallet alone
+        let multisig_outputs = self
+            .resources
+            .output_manager_service
+            .scan_outputs_for_multisig_accounts(outputs, height)
+            .await?;
+        if !multisig_outputs.is_empty() {
+            debug!(
+                target: LOG_TARGET,
+                "Found {} multisig account output(s) at height {}",
+                multisig_outputs.len(),
+                height
+            );
+        }
         trace!(
             target: LOG_TARGET,
             "Scanned for outputs: outputs took {} ms , one-sided took {} ms",
@@ -656,9 +1073,12 @@ where
                 )
                 .await
             {
-                Ok(_) => {
+                Ok(tx_id) => {
                     num_recovered = num_recovered.saturating_add(1);
                     total_amount += wo.value;
+                    if !wo.features.is_coinbase() {
+                        self.mark_invoice_paid(&wo, tx_id, mined_timestamp);
+                    }
                 },
                 Err(WalletError::TransactionServiceError(TransactionServiceError::TransactionStorageError(
                     TransactionStorageError::DuplicateOutput,
@@ -676,6 +1096,34 @@ where
         Ok((num_recovered, total_amount))
     }
 
+    /// Settles the invoice issued by this wallet, if any, that the payment id of a scanned output refers to
+    fn mark_invoice_paid(&self, output: &WalletOutput, tx_id: TxId, received_at: NaiveDateTime) {
+        let payment_id = match &output.payment_id {
+            PaymentId::Open(data) | PaymentId::AddressAndData(_, data) => data.clone(),
+            PaymentId::U64(_) | PaymentId::U256(_) => output.payment_id.to_bytes(),
+            PaymentId::Empty | PaymentId::Address(_) => return,
+        };
+        match self
+            .resources
+            .db
+            .mark_invoice_paid(&payment_id, output.value, tx_id, received_at)
+        {
+            Ok(true) => info!(
+                target: LOG_TARGET,
+                "Invoice with payment id {} paid by transaction {}",
+                payment_id.to_hex(),
+                tx_id
+            ),
+            Ok(false) => {},
+            Err(e) => warn!(
+                target: LOG_TARGET,
+                "Could not update invoice for payment id {}: {}",
+                payment_id.to_hex(),
+                e
+            ),
+        }
+    }
+
     fn set_recovery_mode(&self) -> Result<(), UtxoScannerError> {
         self.resources
             .db
@@ -692,6 +1140,7 @@ where
     }
 
     fn clear_recovery_mode(&self) -> Result<(), UtxoScannerError> {
+        let _ = self.resources.db.clear_client_value(RECOVERY_CHUNKS_KEY.to_owned())?;
         let _ = self.resources.db.clear_client_value(RECOVERY_KEY.to_owned())?;
         Ok(())
     }
@@ -38,6 +38,7 @@ use crate::{
     transaction_service::handle::TransactionServiceHandle,
     utxo_scanner_service::{
         handle::UtxoScannerEvent,
+        recovery_chunks::RECOVERY_CHUNK_SIZE,
         service::{UtxoScannerResources, UtxoScannerService},
     },
     WalletSqlite,
@@ -57,6 +58,8 @@ pub struct UtxoScannerServiceBuilder {
     mode: Option<UtxoScannerMode>,
     one_sided_message: String,
     recovery_message: String,
+    use_block_filters: bool,
+    recovery_chunk_size: u64,
 }
 
 impl Default for UtxoScannerServiceBuilder {
@@ -67,6 +70,8 @@ impl Default for UtxoScannerServiceBuilder {
             mode: None,
             one_sided_message: "Detected one-sided payment on blockchain".to_string(),
             recovery_message: "Output found on blockchain during Wallet Recovery".to_string(),
+            use_block_filters: false,
+            recovery_chunk_size: RECOVERY_CHUNK_SIZE,
         }
     }
 }
@@ -99,6 +104,20 @@ impl UtxoScannerServiceBuilder {
         self
     }
 
+    /// Only download the blocks whose compact block filter matches the wallet's one-sided payment and multisig
+    /// scripts. Payments that can only be found by trial decryption, such as stealth one-sided payments, are not
+    /// detected in the blocks that are skipped. Block filters are never used in recovery mode.
+    pub fn with_block_filters(&mut self, use_block_filters: bool) -> &mut Self {
+        self.use_block_filters = use_block_filters;
+        self
+    }
+
+    /// Set the number of blocks in each chunk of a recovery that is shared between several base nodes
+    pub fn with_recovery_chunk_size(&mut self, recovery_chunk_size: u64) -> &mut Self {
+        self.recovery_chunk_size = recovery_chunk_size;
+        self
+    }
+
     pub async fn build_with_wallet(
         &mut self,
         wallet: &WalletSqlite,
@@ -116,6 +135,8 @@ impl UtxoScannerServiceBuilder {
             factories: wallet.factories.clone(),
             recovery_message: self.recovery_message.clone(),
             one_sided_payment_message: self.one_sided_message.clone(),
+            use_block_filters: self.use_block_filters,
+            recovery_chunk_size: self.recovery_chunk_size,
         };
 
         let (event_sender, _) = broadcast::channel(200);
@@ -163,6 +184,8 @@ impl UtxoScannerServiceBuilder {
             factories,
             recovery_message: self.recovery_message.clone(),
             one_sided_payment_message: self.one_sided_message.clone(),
+            use_block_filters: self.use_block_filters,
+            recovery_chunk_size: self.recovery_chunk_size,
         };
 
         UtxoScannerService::new(
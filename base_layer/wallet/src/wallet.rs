@@ -29,6 +29,7 @@ use log::*;
 use rand::rngs::OsRng;
 use tari_common::configuration::bootstrap::ApplicationType;
 use tari_common_types::{
+    payment_request::PaymentRequest,
     tari_address::{TariAddress, TariAddressFeatures},
     transaction::{ImportStatus, TxId},
     types::{ComAndPubSignature, Commitment, PrivateKey, PublicKey, RangeProof, SignatureWithDomain},
@@ -188,6 +189,21 @@ where
             config.transaction_service_config,
             config.buffer_size,
         );
+        let mut output_manager_service_config = config.output_manager_service_config;
+        if wallet_type.is_watch_only() && output_manager_service_config.auto_consolidation_enabled {
+            warn!(
+                target: LOG_TARGET,
+                "Automatic UTXO consolidation is not available for a watch-only wallet and has been disabled"
+            );
+            output_manager_service_config.auto_consolidation_enabled = false;
+        }
+        if config.use_block_filters {
+            warn!(
+                target: LOG_TARGET,
+                "Block filter scanning is enabled, stealth one-sided payments to this wallet will only be found by a \
+                 wallet recovery"
+            );
+        }
         let stack = StackBuilder::new(shutdown_signal)
             .add_initializer(P2pInitializer::new(
                 config.p2p.clone(),
@@ -198,7 +214,7 @@ where
                 publisher,
             ))
             .add_initializer(OutputManagerServiceInitializer::<V, TKeyManagerInterface>::new(
-                config.output_manager_service_config,
+                output_manager_service_config,
                 output_manager_backend.clone(),
                 factories.clone(),
                 config.network.into(),
@@ -244,6 +260,7 @@ where
                 wallet_database.clone(),
                 factories.clone(),
                 config.network,
+                config.use_block_filters,
             ));
 
         // Check if we have update config. FFI wallets don't do this, the update on mobile is done differently.
@@ -508,6 +525,27 @@ where
         ))
     }
 
+    /// Creates a payment request for `amount` to this wallet's one-sided address and stores it as an invoice so that
+    /// the payment is matched when it is scanned. The request is signed if the wallet holds its spend key.
+    pub async fn create_payment_request(
+        &self,
+        amount: MicroMinotari,
+        payment_id: Vec<u8>,
+        expiry: Option<u64>,
+        memo: Option<String>,
+    ) -> Result<PaymentRequest, WalletError> {
+        let address = self.get_wallet_one_sided_address().await?;
+        let mut request = PaymentRequest::new(address, amount.as_u64(), payment_id, expiry, memo);
+        let spend_key = self.key_manager_service.get_spend_key().await?;
+        match self.key_manager_service.get_private_key(&spend_key.key_id).await {
+            Ok(secret_key) => request.sign(&secret_key)?,
+            // Hardware wallets do not expose the spend key, so their requests are left unsigned
+            Err(e) => debug!(target: LOG_TARGET, "Payment request was not signed: {}", e),
+        }
+        self.db.save_invoice(&request)?;
+        Ok(request)
+    }
+
     pub async fn get_wallet_id(&self) -> Result<WalletIdentity, WalletError> {
         let address_interactive = self.get_wallet_interactive_address().await?;
         let address_one_sided = self.get_wallet_one_sided_address().await?;
@@ -655,6 +693,7 @@ where
         fee_per_gram: MicroMinotari,
         message: String,
     ) -> Result<TxId, WalletError> {
+        self.ensure_can_spend()?;
         let coin_split_tx = self
             .output_manager_service
             .create_coin_split(commitments, amount_per_split, split_count, fee_per_gram)
@@ -683,6 +722,7 @@ where
         fee_per_gram: MicroMinotari,
         message: String,
     ) -> Result<TxId, WalletError> {
+        self.ensure_can_spend()?;
         let coin_split_tx = self
             .output_manager_service
             .create_coin_split_even(commitments, split_count, fee_per_gram)
@@ -711,6 +751,7 @@ where
         fee_per_gram: MicroMinotari,
         message: String,
     ) -> Result<TxId, WalletError> {
+        self.ensure_can_spend()?;
         let coin_split_tx = self
             .output_manager_service
             .create_coin_split_even(commitments, split_count, fee_per_gram)
@@ -737,6 +778,7 @@ where
         fee_per_gram: MicroMinotari,
         msg: Option<String>,
     ) -> Result<TxId, WalletError> {
+        self.ensure_can_spend()?;
         let coin_join_tx = self
             .output_manager_service
             .create_coin_join(commitments, fee_per_gram)
@@ -758,6 +800,18 @@ where
         }
     }
 
+    /// Returns true if this wallet only holds the view key and public spend key, and so cannot sign transactions
+    pub fn is_watch_only(&self) -> bool {
+        self.wallet_type.is_watch_only()
+    }
+
+    fn ensure_can_spend(&self) -> Result<(), WalletError> {
+        if self.is_watch_only() {
+            return Err(WalletError::WatchOnlyWallet);
+        }
+        Ok(())
+    }
+
     /// Utility function to find out if there is data in the database indicating that there is an incomplete recovery
     /// process in progress
     pub fn is_recovery_in_progress(&self) -> Result<bool, WalletError> {
@@ -136,6 +136,7 @@ async fn create_wallet(
         }),
         datastore_path: data_path.to_path_buf(),
         peer_database_name: random::string(8),
+        peer_database_backend: Default::default(),
         max_concurrent_inbound_tasks: 10,
         max_concurrent_outbound_tasks: 10,
         dht: DhtConfig {
@@ -682,6 +683,7 @@ async fn test_import_utxo() {
         }),
         datastore_path: temp_dir.path().to_path_buf(),
         peer_database_name: random::string(8),
+        peer_database_backend: Default::default(),
         max_concurrent_inbound_tasks: 10,
         max_concurrent_outbound_tasks: 10,
         dht: Default::default(),
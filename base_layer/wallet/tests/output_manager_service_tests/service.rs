@@ -22,6 +22,7 @@
 
 use std::{collections::HashMap, convert::TryInto, sync::Arc, time::Duration};
 
+use futures::StreamExt;
 use minotari_wallet::{
     base_node_service::handle::{BaseNodeEvent, BaseNodeServiceHandle},
     connectivity_service::{create_wallet_connectivity_mock, WalletConnectivityMock},
@@ -29,6 +30,7 @@ use minotari_wallet::{
         config::OutputManagerServiceConfig,
         error::{OutputManagerError, OutputManagerStorageError},
         handle::{OutputManagerEvent, OutputManagerHandle},
+        multisig_account::MultisigAccount,
         service::OutputManagerService,
         storage::{
             database::{OutputManagerBackend, OutputManagerDatabase},
@@ -36,10 +38,15 @@ use minotari_wallet::{
             sqlite_db::OutputManagerSqliteDatabase,
             OutputStatus,
         },
+        sub_address::{sub_address_key_id, SUB_ADDRESS_LOOKAHEAD},
         UtxoSelectionCriteria,
+        UtxoSelectionOrdering,
     },
     test_utils::create_consensus_constants,
-    transaction_service::handle::TransactionServiceHandle,
+    transaction_service::{
+        error::TransactionServiceError,
+        handle::{TransactionServiceHandle, TransactionServiceRequest, TransactionServiceResponse},
+    },
 };
 use rand::{rngs::OsRng, RngCore};
 use tari_common::configuration::Network;
@@ -59,6 +66,7 @@ use tari_core::{
     blocks::BlockHeader,
     borsh::SerializedSize,
     covenants::Covenant,
+    one_sided::{shared_secret_to_output_encryption_key, shared_secret_to_output_spending_key},
     proto::base_node::{QueryDeletedData, QueryDeletedResponse, UtxoQueryResponse, UtxoQueryResponses},
     transactions::{
         fee::Fee,
@@ -73,13 +81,14 @@ use tari_core::{
     },
 };
 use tari_key_manager::key_manager_service::{KeyId, KeyManagerInterface};
-use tari_script::{inputs, script, TariScript};
+use tari_script::{inputs, push_pubkey_script, script, ExecutionStack, TariScript};
 use tari_service_framework::reply_channel;
 use tari_shutdown::Shutdown;
+use tari_utilities::ByteArray;
 use tokio::{
     sync::{broadcast, broadcast::channel},
     task,
-    time::sleep,
+    time::{sleep, timeout},
 };
 
 use crate::support::{
@@ -100,6 +109,7 @@ struct TestOmsService {
     pub wallet_connectivity_mock: WalletConnectivityMock,
     pub _shutdown: Shutdown,
     pub _transaction_service_handle: TransactionServiceHandle,
+    pub transaction_service_requests: TransactionServiceRequestReceiver,
     pub mock_rpc_service: MockRpcServer<BaseNodeWalletRpcServer<BaseNodeWalletRpcMockService>>,
     pub node_id: Arc<NodeIdentity>,
     pub base_node_wallet_rpc_mock_state: BaseNodeWalletRpcMockState,
@@ -107,11 +117,22 @@ struct TestOmsService {
     pub key_manager_handle: MemoryDbKeyManager,
 }
 
+type TransactionServiceRequestReceiver =
+    reply_channel::Receiver<TransactionServiceRequest, Result<TransactionServiceResponse, TransactionServiceError>>;
+
 #[allow(clippy::type_complexity)]
-#[allow(clippy::too_many_lines)]
 async fn setup_output_manager_service<T: OutputManagerBackend + 'static>(
     backend: T,
     with_connection: bool,
+) -> TestOmsService {
+    setup_output_manager_service_with_config(backend, with_connection, OutputManagerServiceConfig::default()).await
+}
+
+#[allow(clippy::too_many_lines)]
+async fn setup_output_manager_service_with_config<T: OutputManagerBackend + 'static>(
+    backend: T,
+    with_connection: bool,
+    config: OutputManagerServiceConfig,
 ) -> TestOmsService {
     let shutdown = Shutdown::new();
     let factories = CryptoFactories::default();
@@ -119,7 +140,7 @@ async fn setup_output_manager_service<T: OutputManagerBackend + 'static>(
     let (oms_request_sender, oms_request_receiver) = reply_channel::unbounded();
     let (oms_event_publisher, _) = broadcast::channel(200);
 
-    let (ts_request_sender, _ts_request_receiver) = reply_channel::unbounded();
+    let (ts_request_sender, ts_request_receiver) = reply_channel::unbounded();
     let (event_publisher, _) = channel(100);
     let ts_handle = TransactionServiceHandle::new(ts_request_sender, event_publisher);
 
@@ -158,7 +179,7 @@ async fn setup_output_manager_service<T: OutputManagerBackend + 'static>(
     let key_manager = create_memory_db_key_manager().unwrap();
 
     let output_manager_service = OutputManagerService::new(
-        OutputManagerServiceConfig { ..Default::default() },
+        config,
         oms_request_receiver,
         OutputManagerDatabase::new(backend),
         oms_event_publisher.clone(),
@@ -166,6 +187,7 @@ async fn setup_output_manager_service<T: OutputManagerBackend + 'static>(
         constants,
         shutdown.to_signal(),
         basenode_service_handle,
+        ts_handle.clone(),
         Network::LocalNet,
         wallet_connectivity_mock.clone(),
         key_manager.clone(),
@@ -181,6 +203,7 @@ async fn setup_output_manager_service<T: OutputManagerBackend + 'static>(
         wallet_connectivity_mock,
         _shutdown: shutdown,
         _transaction_service_handle: ts_handle,
+        transaction_service_requests: ts_request_receiver,
         mock_rpc_service: mock_server,
         node_id: server_node_identity,
         base_node_wallet_rpc_mock_state: rpc_service_state,
@@ -230,6 +253,7 @@ pub async fn setup_oms_with_bn_state<T: OutputManagerBackend + 'static>(
         constants,
         shutdown.to_signal(),
         base_node_service_handle.clone(),
+        ts_handle.clone(),
         Network::LocalNet,
         connectivity,
         key_manager.clone(),
@@ -750,6 +774,157 @@ async fn test_utxo_selection_with_tx_priority() {
     assert_ne!(utxos[1].wallet_output.spending_key_id, uo_high.spending_key_id);
 }
 
+async fn add_unspent_output_with_payment_id(
+    oms: &mut OutputManagerHandle,
+    backend: &OutputManagerSqliteDatabase,
+    key_manager: &MemoryDbKeyManager,
+    value: MicroMinotari,
+    payment_id: PaymentId,
+) -> WalletOutput {
+    let mut uo = make_input(&mut OsRng.clone(), value, &OutputFeatures::default(), key_manager).await;
+    uo.payment_id = payment_id;
+    oms.add_output(uo.clone(), None).await.unwrap();
+    backend
+        .mark_outputs_as_unspent(vec![(uo.hash(key_manager).await.unwrap(), true)])
+        .unwrap();
+    uo
+}
+
+#[tokio::test]
+async fn test_utxo_selection_with_privacy_ordering() {
+    let (connection, _tempdir) = get_temp_sqlite_database_connection();
+    let backend = OutputManagerSqliteDatabase::new(connection);
+    let (mut oms, _shutdown, _, _, _, key_manager) = setup_oms_with_bn_state(backend.clone(), Some(6)).await;
+    let fee_per_gram = MicroMinotari::from(2);
+    let sender_a = PaymentId::Open(b"sender a".to_vec());
+    let sender_b = PaymentId::Open(b"sender b".to_vec());
+    let criteria = UtxoSelectionCriteria {
+        ordering: UtxoSelectionOrdering::Privacy,
+        ..Default::default()
+    };
+
+    for _ in 0..2 {
+        add_unspent_output_with_payment_id(
+            &mut oms,
+            &backend,
+            &key_manager,
+            MicroMinotari::from(3000),
+            sender_a.clone(),
+        )
+        .await;
+    }
+    let uo_b =
+        add_unspent_output_with_payment_id(&mut oms, &backend, &key_manager, MicroMinotari::from(10_000), sender_b)
+            .await;
+
+    // Neither output from sender a covers the amount on its own, so both are spent together rather than mixing in
+    // the output from sender b
+    let tx_id = TxId::new_random();
+    oms.prepare_transaction_to_send(
+        tx_id,
+        MicroMinotari::from(5000),
+        criteria.clone(),
+        OutputFeatures::default(),
+        fee_per_gram,
+        TransactionMetadata::default(),
+        String::new(),
+        script!(Nop),
+        Covenant::default(),
+        MicroMinotari::zero(),
+    )
+    .await
+    .unwrap();
+    let utxos = oms.get_unspent_outputs().await.unwrap();
+    assert_eq!(utxos.len(), 1);
+    assert_eq!(utxos[0].wallet_output.spending_key_id, uo_b.spending_key_id);
+
+    // The change is in the group of sender a. Neither it nor the next output from sender a covers the amount on its
+    // own, but together they do.
+    let change = backend
+        .fetch_outputs_by_tx_id(tx_id)
+        .unwrap()
+        .into_iter()
+        .find(|o| o.received_in_tx_id == Some(tx_id))
+        .unwrap();
+    assert!(change.wallet_output.value < MicroMinotari::from(1000));
+    backend.mark_outputs_as_unspent(vec![(change.hash, true)]).unwrap();
+    add_unspent_output_with_payment_id(&mut oms, &backend, &key_manager, MicroMinotari::from(1500), sender_a).await;
+
+    oms.prepare_transaction_to_send(
+        TxId::new_random(),
+        MicroMinotari::from(1800),
+        criteria,
+        OutputFeatures::default(),
+        fee_per_gram,
+        TransactionMetadata::default(),
+        String::new(),
+        script!(Nop),
+        Covenant::default(),
+        MicroMinotari::zero(),
+    )
+    .await
+    .unwrap();
+    let utxos = oms.get_unspent_outputs().await.unwrap();
+    assert_eq!(utxos.len(), 1);
+    assert_eq!(utxos[0].wallet_output.spending_key_id, uo_b.spending_key_id);
+}
+
+#[tokio::test]
+async fn test_utxo_selection_with_consolidation_ordering() {
+    let (connection, _tempdir) = get_temp_sqlite_database_connection();
+    let backend = OutputManagerSqliteDatabase::new(connection);
+    let (mut oms, _shutdown, _, _, _, key_manager) = setup_oms_with_bn_state(backend.clone(), Some(6)).await;
+    let fee_calc = Fee::new(*create_consensus_constants(0).transaction_weight_params());
+    let fee_per_gram = MicroMinotari::from(2);
+    let criteria = UtxoSelectionCriteria {
+        ordering: UtxoSelectionOrdering::Consolidation,
+        ..Default::default()
+    };
+
+    for value in [1000u64, 2000, 3000, 4000] {
+        add_unspent_output_with_payment_id(
+            &mut oms,
+            &backend,
+            &key_manager,
+            MicroMinotari::from(value),
+            PaymentId::Empty,
+        )
+        .await;
+    }
+
+    // The two smallest outputs cover the amount, and the other outputs are swept into the change
+    let stp = oms
+        .prepare_transaction_to_send(
+            TxId::new_random(),
+            MicroMinotari::from(1500),
+            criteria,
+            OutputFeatures::default(),
+            fee_per_gram,
+            TransactionMetadata::default(),
+            String::new(),
+            script!(Nop),
+            Covenant::default(),
+            MicroMinotari::zero(),
+        )
+        .await
+        .unwrap();
+    assert!(oms.get_unspent_outputs().await.unwrap().is_empty());
+
+    let fee = stp.get_fee_amount().unwrap();
+    assert_eq!(
+        fee,
+        fee_calc.calculate(
+            fee_per_gram,
+            1,
+            4,
+            2,
+            default_features_and_scripts_size_byte_size().unwrap() * 2
+        )
+    );
+    let change = stp.get_change_amount().unwrap();
+    assert_eq!(change, MicroMinotari::from(10_000 - 1500) - fee);
+}
+
 #[tokio::test]
 async fn send_not_enough_funds() {
     let (connection, _tempdir) = get_temp_sqlite_database_connection();
@@ -2298,3 +2473,497 @@ async fn recovered_output_key_not_in_keychain() {
         "It should not reach an error condition or return an output"
     );
 }
+
+/// Creates a one-sided payment from an unrelated sender to the wallet's sub-address at `index`
+async fn make_sub_address_payment(oms: &TestOmsService, index: u64, amount: MicroMinotari) -> TransactionOutput {
+    let sender_key_manager = create_memory_db_key_manager().unwrap();
+    let view_key = oms.key_manager_handle.get_view_key().await.unwrap();
+    let spend_key = oms
+        .key_manager_handle
+        .get_public_key_at_key_id(&sub_address_key_id(index))
+        .await
+        .unwrap();
+
+    let sender_offset_key = sender_key_manager
+        .get_next_key(TransactionKeyManagerBranch::OneSidedSenderOffset.get_branch_key())
+        .await
+        .unwrap();
+    let shared_secret = sender_key_manager
+        .get_diffie_hellman_shared_secret(&sender_offset_key.key_id, &view_key.pub_key)
+        .await
+        .unwrap();
+    let spending_key_id = sender_key_manager
+        .import_key(shared_secret_to_output_spending_key(&shared_secret).unwrap())
+        .await
+        .unwrap();
+    let encryption_key_id = sender_key_manager
+        .import_key(shared_secret_to_output_encryption_key(&shared_secret).unwrap())
+        .await
+        .unwrap();
+    let encrypted_data = sender_key_manager
+        .encrypt_data_for_recovery(
+            &spending_key_id,
+            Some(&encryption_key_id),
+            amount.as_u64(),
+            PaymentId::Empty,
+        )
+        .await
+        .unwrap();
+    let script_key = sender_key_manager.get_random_key().await.unwrap();
+
+    let uo = WalletOutput::new_current_version(
+        amount,
+        spending_key_id,
+        OutputFeatures::default(),
+        push_pubkey_script(&spend_key),
+        ExecutionStack::default(),
+        script_key.key_id,
+        sender_offset_key.pub_key,
+        ComAndPubSignature::default(),
+        0,
+        Covenant::new(),
+        encrypted_data,
+        MicroMinotari::zero(),
+        PaymentId::Empty,
+        &sender_key_manager,
+    )
+    .await
+    .unwrap();
+    uo.to_transaction_output(&sender_key_manager).await.unwrap()
+}
+
+#[tokio::test]
+async fn scan_for_sub_address_payments() {
+    let (connection, _tempdir) = get_temp_sqlite_database_connection();
+    let backend = OutputManagerSqliteDatabase::new(connection);
+    let mut oms = setup_output_manager_service(backend, true).await;
+
+    // Nothing is registered yet, so a payment to any index within the lookahead is found
+    let payment = make_sub_address_payment(&oms, 5, MicroMinotari::from(5_000)).await;
+    let recovered = oms
+        .output_manager_handle
+        .scan_for_recoverable_outputs(vec![payment.clone()])
+        .await
+        .unwrap();
+    assert_eq!(recovered.len(), 1);
+    assert_eq!(recovered[0].output.value, MicroMinotari::from(5_000));
+    assert_eq!(recovered[0].output.script_key_id, sub_address_key_id(5));
+    let sub_addresses = oms.output_manager_handle.get_sub_addresses().await.unwrap();
+    assert_eq!(sub_addresses.len(), 1);
+    assert_eq!(sub_addresses[0].index, 5);
+    assert_eq!(sub_addresses[0].script(), payment.script);
+
+    // The output is attributed to the sub-address
+    let outputs = oms.output_manager_handle.get_sub_address_outputs(5).await.unwrap();
+    assert_eq!(outputs.len(), 1);
+    assert_eq!(outputs[0].commitment, payment.commitment);
+
+    // An output that is already known is not recovered again
+    let recovered = oms
+        .output_manager_handle
+        .scan_for_recoverable_outputs(vec![payment])
+        .await
+        .unwrap();
+    assert!(recovered.is_empty());
+}
+
+#[tokio::test]
+async fn scan_for_sub_address_payments_below_the_highest_recovered_index() {
+    let (connection, _tempdir) = get_temp_sqlite_database_connection();
+    let backend = OutputManagerSqliteDatabase::new(connection);
+    let mut oms = setup_output_manager_service(backend, true).await;
+
+    let highest = SUB_ADDRESS_LOOKAHEAD - 1;
+    let payment = make_sub_address_payment(&oms, highest, MicroMinotari::from(1_000)).await;
+    let recovered = oms
+        .output_manager_handle
+        .scan_for_recoverable_outputs(vec![payment])
+        .await
+        .unwrap();
+    assert_eq!(recovered.len(), 1);
+
+    // Outputs to skipped indexes and to the lookahead past the highest index match the block filter
+    let filter_items = oms.output_manager_handle.get_block_filter_items().await.unwrap();
+    for index in [0, 2, highest + SUB_ADDRESS_LOOKAHEAD] {
+        let spend_key = oms
+            .key_manager_handle
+            .get_public_key_at_key_id(&sub_address_key_id(index))
+            .await
+            .unwrap();
+        assert!(filter_items.contains(&spend_key.as_bytes().to_vec()));
+    }
+
+    // Indexes that were skipped over, and the lookahead past the highest one, are still scanned for. Indexes past the
+    // lookahead are not.
+    let skipped = make_sub_address_payment(&oms, 2, MicroMinotari::from(2_000)).await;
+    let lookahead = make_sub_address_payment(&oms, highest + SUB_ADDRESS_LOOKAHEAD, MicroMinotari::from(3_000)).await;
+    let too_far = make_sub_address_payment(&oms, highest + 3 * SUB_ADDRESS_LOOKAHEAD, MicroMinotari::from(4_000)).await;
+    let recovered = oms
+        .output_manager_handle
+        .scan_for_recoverable_outputs(vec![skipped, lookahead, too_far])
+        .await
+        .unwrap();
+    assert_eq!(recovered.len(), 2);
+    assert!(recovered
+        .iter()
+        .any(|ro| ro.output.script_key_id == sub_address_key_id(2)));
+    assert!(recovered
+        .iter()
+        .any(|ro| ro.output.script_key_id == sub_address_key_id(highest + SUB_ADDRESS_LOOKAHEAD)));
+
+    let mut indexes = oms
+        .output_manager_handle
+        .get_sub_addresses()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|s| s.index)
+        .collect::<Vec<_>>();
+    indexes.sort_unstable();
+    assert_eq!(indexes, vec![2, highest, highest + SUB_ADDRESS_LOOKAHEAD]);
+
+    // New sub-addresses are derived past the recovered ones
+    let sub_address = oms
+        .output_manager_handle
+        .create_sub_address("new".to_string())
+        .await
+        .unwrap();
+    assert_eq!(sub_address.index, highest + SUB_ADDRESS_LOOKAHEAD + 1);
+}
+
+async fn add_unspent_outputs(
+    oms: &mut TestOmsService,
+    backend: &OutputManagerSqliteDatabase,
+    num_outputs: usize,
+    value: MicroMinotari,
+) {
+    for _ in 0..num_outputs {
+        let uo = make_input(
+            &mut OsRng.clone(),
+            value,
+            &OutputFeatures::default(),
+            &oms.key_manager_handle,
+        )
+        .await;
+        oms.output_manager_handle.add_output(uo.clone(), None).await.unwrap();
+        backend
+            .mark_outputs_as_unspent(vec![(uo.hash(&oms.key_manager_handle).await.unwrap(), true)])
+            .unwrap();
+    }
+}
+
+fn auto_consolidation_config() -> OutputManagerServiceConfig {
+    OutputManagerServiceConfig {
+        auto_consolidation_enabled: true,
+        auto_consolidation_interval: Duration::from_millis(200),
+        auto_consolidation_max_utxo_count: 3,
+        auto_consolidation_min_output_value: 10_000,
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_auto_consolidation_over_utxo_threshold() {
+    let (connection, _tempdir) = get_temp_sqlite_database_connection();
+    let backend = OutputManagerSqliteDatabase::new(connection);
+    let mut oms = setup_output_manager_service_with_config(backend.clone(), true, auto_consolidation_config()).await;
+    let mut event_stream = oms.output_manager_handle.get_event_stream();
+
+    // The wallet is within the UTXO limit, so nothing is consolidated
+    add_unspent_outputs(&mut oms, &backend, 3, MicroMinotari::from(5_000)).await;
+    assert!(timeout(Duration::from_secs(1), oms.transaction_service_requests.next())
+        .await
+        .is_err());
+
+    add_unspent_outputs(&mut oms, &backend, 1, MicroMinotari::from(5_000)).await;
+    let (request, reply_tx) = timeout(Duration::from_secs(10), oms.transaction_service_requests.next())
+        .await
+        .expect("Consolidation was not submitted to the transaction service")
+        .unwrap()
+        .split();
+    let tx_id = match request {
+        TransactionServiceRequest::SubmitTransactionToSelf(tx_id, transaction, _, _, _) => {
+            assert_eq!(transaction.body.inputs().len(), 4);
+            assert_eq!(transaction.body.outputs().len(), 1);
+            tx_id
+        },
+        request => panic!("Unexpected request: {}", request),
+    };
+    reply_tx
+        .send(Ok(TransactionServiceResponse::TransactionSubmitted))
+        .unwrap();
+
+    let event = timeout(Duration::from_secs(10), async {
+        loop {
+            let event = event_stream.recv().await.unwrap();
+            if let OutputManagerEvent::UtxoConsolidationCreated { .. } = &*event {
+                return event;
+            }
+        }
+    })
+    .await
+    .unwrap();
+    match &*event {
+        OutputManagerEvent::UtxoConsolidationCreated {
+            tx_id: id, num_inputs, ..
+        } => {
+            assert_eq!(*id, tx_id);
+            assert_eq!(*num_inputs, 4);
+        },
+        _ => unreachable!(),
+    }
+    let balance = oms.output_manager_handle.get_balance().await.unwrap();
+    assert_eq!(balance.available_balance, MicroMinotari::zero());
+}
+
+#[tokio::test]
+async fn test_auto_consolidation_submission_failure_releases_inputs() {
+    let (connection, _tempdir) = get_temp_sqlite_database_connection();
+    let backend = OutputManagerSqliteDatabase::new(connection);
+    let mut oms = setup_output_manager_service_with_config(backend.clone(), true, auto_consolidation_config()).await;
+
+    add_unspent_outputs(&mut oms, &backend, 4, MicroMinotari::from(5_000)).await;
+    let (_, reply_tx) = timeout(Duration::from_secs(10), oms.transaction_service_requests.next())
+        .await
+        .expect("Consolidation was not submitted to the transaction service")
+        .unwrap()
+        .split();
+    reply_tx.send(Err(TransactionServiceError::InvalidStateError)).unwrap();
+
+    // The inputs of the consolidation that could not be submitted are released
+    let mut balance = oms.output_manager_handle.get_balance().await.unwrap();
+    for _ in 0..50 {
+        if balance.available_balance == MicroMinotari::from(20_000) {
+            break;
+        }
+        sleep(Duration::from_millis(20)).await;
+        balance = oms.output_manager_handle.get_balance().await.unwrap();
+    }
+    assert_eq!(balance.available_balance, MicroMinotari::from(20_000));
+}
+
+#[tokio::test]
+async fn test_auto_consolidation_disabled() {
+    // A zero interval is rejected when consolidation is enabled...
+    let config = OutputManagerServiceConfig {
+        auto_consolidation_interval: Duration::ZERO,
+        ..auto_consolidation_config()
+    };
+    assert!(matches!(config.validate(), Err(OutputManagerError::InvalidConfig)));
+
+    // ...but is not used when consolidation is disabled
+    let (connection, _tempdir) = get_temp_sqlite_database_connection();
+    let backend = OutputManagerSqliteDatabase::new(connection);
+    let config = OutputManagerServiceConfig {
+        auto_consolidation_enabled: false,
+        ..config
+    };
+    let mut oms = setup_output_manager_service_with_config(backend.clone(), true, config).await;
+
+    add_unspent_outputs(&mut oms, &backend, 5, MicroMinotari::from(5_000)).await;
+    assert!(timeout(Duration::from_secs(2), oms.transaction_service_requests.next())
+        .await
+        .is_err());
+    let balance = oms.output_manager_handle.get_balance().await.unwrap();
+    assert_eq!(balance.available_balance, MicroMinotari::from(25_000));
+}
+
+async fn create_multisig_account(oms: &mut TestOmsService) -> MultisigAccount {
+    let own_key = oms
+        .key_manager_handle
+        .get_next_key(TransactionKeyManagerBranch::Multisig.get_branch_key())
+        .await
+        .unwrap();
+    let cosigner_key = create_memory_db_key_manager()
+        .unwrap()
+        .get_next_key(TransactionKeyManagerBranch::Multisig.get_branch_key())
+        .await
+        .unwrap();
+    oms.output_manager_handle
+        .create_multisig_account(
+            "treasury".to_string(),
+            2,
+            vec![own_key.pub_key.clone(), cosigner_key.pub_key],
+            own_key.pub_key,
+        )
+        .await
+        .unwrap()
+}
+
+async fn make_multisig_account_payment(account: &MultisigAccount, amount: MicroMinotari) -> TransactionOutput {
+    let sender_key_manager = create_memory_db_key_manager().unwrap();
+    let spending_key = sender_key_manager.get_random_key().await.unwrap();
+    let encryption_key_id = sender_key_manager
+        .import_key(account.encryption_key().unwrap())
+        .await
+        .unwrap();
+    let encrypted_data = sender_key_manager
+        .encrypt_data_for_recovery(
+            &spending_key.key_id,
+            Some(&encryption_key_id),
+            amount.as_u64(),
+            PaymentId::Empty,
+        )
+        .await
+        .unwrap();
+    let script_key = sender_key_manager.get_random_key().await.unwrap();
+    let sender_offset_key = sender_key_manager.get_random_key().await.unwrap();
+
+    let uo = WalletOutput::new_current_version(
+        amount,
+        spending_key.key_id,
+        OutputFeatures::default(),
+        account.script(),
+        ExecutionStack::default(),
+        script_key.key_id,
+        sender_offset_key.pub_key,
+        ComAndPubSignature::default(),
+        0,
+        Covenant::new(),
+        encrypted_data,
+        MicroMinotari::zero(),
+        PaymentId::Empty,
+        &sender_key_manager,
+    )
+    .await
+    .unwrap();
+    uo.to_transaction_output(&sender_key_manager).await.unwrap()
+}
+
+#[tokio::test]
+async fn scan_for_multisig_account_outputs() {
+    let (connection, _tempdir) = get_temp_sqlite_database_connection();
+    let backend = OutputManagerSqliteDatabase::new(connection);
+    let mut oms = setup_output_manager_service(backend, true).await;
+    let account = create_multisig_account(&mut oms).await;
+
+    let payment = make_multisig_account_payment(&account, MicroMinotari::from(5_000)).await;
+    let unrelated = make_input(
+        &mut OsRng,
+        MicroMinotari::from(1_000),
+        &OutputFeatures::default(),
+        &oms.key_manager_handle,
+    )
+    .await
+    .to_transaction_output(&oms.key_manager_handle)
+    .await
+    .unwrap();
+    let found = oms
+        .output_manager_handle
+        .scan_outputs_for_multisig_accounts(vec![payment.clone(), unrelated], 5)
+        .await
+        .unwrap();
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].account_name, account.name);
+    assert_eq!(found[0].output_hash, payment.hash());
+    assert_eq!(found[0].value, MicroMinotari::from(5_000));
+    assert_eq!(found[0].mined_height, 5);
+    assert_eq!(found[0].spent_in_tx_id, None);
+    assert_eq!(found[0].spent_at_height, None);
+
+    // Account outputs cannot be spent by this wallet alone, so they are not part of its balance
+    let balance = oms.output_manager_handle.get_balance().await.unwrap();
+    assert_eq!(balance.available_balance, MicroMinotari::zero());
+
+    // An output that is already tracked is not reported again
+    assert!(oms
+        .output_manager_handle
+        .scan_outputs_for_multisig_accounts(vec![payment], 5)
+        .await
+        .unwrap()
+        .is_empty());
+    let outputs = oms
+        .output_manager_handle
+        .get_multisig_account_outputs(account.name)
+        .await
+        .unwrap();
+    assert_eq!(outputs, found);
+}
+
+#[tokio::test]
+async fn validation_marks_multisig_account_outputs_spent_by_another_cosigner() {
+    let (connection, _tempdir) = get_temp_sqlite_database_connection();
+    let backend = OutputManagerSqliteDatabase::new(connection);
+    let mut oms = setup_output_manager_service(backend, true).await;
+    let account = create_multisig_account(&mut oms).await;
+
+    let payment = make_multisig_account_payment(&account, MicroMinotari::from(5_000)).await;
+    oms.output_manager_handle
+        .scan_outputs_for_multisig_accounts(vec![payment.clone()], 1)
+        .await
+        .unwrap();
+
+    // Another cosigner led the spend, so this wallet only learns about it from the base node
+    let mut block1_header = BlockHeader::new(1);
+    block1_header.height = 1;
+    let mut block3_header = BlockHeader::new(1);
+    block3_header.height = 3;
+    let mut block4_header = BlockHeader::new(1);
+    block4_header.height = 4;
+    oms.base_node_wallet_rpc_mock_state
+        .set_query_deleted_response(QueryDeletedResponse {
+            best_block_hash: block4_header.hash().to_vec(),
+            best_block_height: 4,
+            data: vec![QueryDeletedData {
+                mined_at_height: 1,
+                block_mined_in: block1_header.hash().to_vec(),
+                height_deleted_at: 3,
+                block_deleted_in: block3_header.hash().to_vec(),
+            }],
+        });
+
+    let mut event_stream = oms.output_manager_handle.get_event_stream();
+    oms.output_manager_handle.validate_txos().await.unwrap();
+    let query_deleted_calls = oms
+        .base_node_wallet_rpc_mock_state
+        .wait_pop_query_deleted(1, Duration::from_secs(60))
+        .await
+        .unwrap();
+    assert_eq!(query_deleted_calls[0].hashes, vec![payment.hash().to_vec()]);
+    timeout(Duration::from_secs(10), async {
+        loop {
+            if let OutputManagerEvent::TxoValidationSuccess(_) = &*event_stream.recv().await.unwrap() {
+                break;
+            }
+        }
+    })
+    .await
+    .unwrap();
+
+    let outputs = oms
+        .output_manager_handle
+        .get_multisig_account_outputs(account.name)
+        .await
+        .unwrap();
+    assert_eq!(outputs.len(), 1);
+    assert_eq!(outputs[0].spent_at_height, Some(3));
+    assert_eq!(outputs[0].spent_in_tx_id, None);
+}
+
+#[tokio::test]
+async fn cancelling_a_multisig_account_spend_releases_the_account_output() {
+    let (connection, _tempdir) = get_temp_sqlite_database_connection();
+    let backend = OutputManagerSqliteDatabase::new(connection);
+    let mut oms = setup_output_manager_service(backend.clone(), true).await;
+    let account = create_multisig_account(&mut oms).await;
+
+    let payment = make_multisig_account_payment(&account, MicroMinotari::from(5_000)).await;
+    oms.output_manager_handle
+        .scan_outputs_for_multisig_accounts(vec![payment.clone()], 1)
+        .await
+        .unwrap();
+    let tx_id = TxId::new_random();
+    backend
+        .mark_multisig_account_output_spent(&payment.hash(), tx_id)
+        .unwrap();
+
+    oms.output_manager_handle.cancel_transaction(tx_id).await.unwrap();
+    let outputs = oms
+        .output_manager_handle
+        .get_multisig_account_outputs(account.name)
+        .await
+        .unwrap();
+    assert_eq!(outputs.len(), 1);
+    assert_eq!(outputs[0].spent_in_tx_id, None);
+    assert_eq!(outputs[0].spent_at_height, None);
+}
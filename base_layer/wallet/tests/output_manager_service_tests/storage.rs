@@ -32,6 +32,7 @@ use minotari_wallet::output_manager_service::{
         OutputSource,
         OutputStatus,
     },
+    UtxoSelectionCriteria,
 };
 use rand::{rngs::OsRng, RngCore};
 use tari_common_types::{
@@ -762,3 +763,68 @@ pub async fn test_mark_as_unmined() {
     }
     assert_eq!(batch_invalid_count, batch_count);
 }
+
+#[tokio::test]
+pub async fn test_frozen_outputs_are_not_selected() {
+    let (connection, _tempdir) = get_temp_sqlite_database_connection();
+    let backend = OutputManagerSqliteDatabase::new(connection);
+    let db = OutputManagerDatabase::new(backend);
+
+    let key_manager = create_memory_db_key_manager().unwrap();
+    let mut unspent_outputs = Vec::new();
+    for _ in 0..3 {
+        let uo = make_input(
+            &mut OsRng,
+            MicroMinotari::from(1000),
+            &OutputFeatures::default(),
+            &key_manager,
+        )
+        .await;
+        let kmo = DbWalletOutput::from_wallet_output(uo, &key_manager, None, OutputSource::Standard, None, None)
+            .await
+            .unwrap();
+        db.add_unspent_output(kmo.clone()).unwrap();
+        db.mark_outputs_as_unspent(vec![(kmo.hash, true)]).unwrap();
+        unspent_outputs.push(kmo);
+    }
+
+    let frozen = unspent_outputs[0].commitment.clone();
+    db.set_output_frozen(frozen.clone(), true).unwrap();
+    db.set_output_label(
+        frozen.clone(),
+        Some("treasury".to_string()),
+        Some("cold reserve".to_string()),
+    )
+    .unwrap();
+
+    let stored = db
+        .fetch_sorted_unspent_outputs()
+        .unwrap()
+        .into_iter()
+        .find(|o| o.commitment == frozen)
+        .unwrap();
+    assert!(stored.frozen);
+    assert_eq!(stored.label.as_deref(), Some("treasury"));
+    assert_eq!(stored.note.as_deref(), Some("cold reserve"));
+
+    let selected = db
+        .fetch_unspent_outputs_for_spending(&UtxoSelectionCriteria::default(), MicroMinotari::from(100), None)
+        .unwrap();
+    assert_eq!(selected.len(), 2);
+    assert!(selected.iter().all(|o| o.commitment != frozen));
+
+    let selected = db
+        .fetch_unspent_outputs_for_spending(
+            &UtxoSelectionCriteria::specific(vec![frozen.clone()]),
+            MicroMinotari::from(100),
+            None,
+        )
+        .unwrap();
+    assert!(selected.is_empty());
+
+    db.set_output_frozen(frozen, false).unwrap();
+    let selected = db
+        .fetch_unspent_outputs_for_spending(&UtxoSelectionCriteria::default(), MicroMinotari::from(100), None)
+        .unwrap();
+    assert_eq!(selected.len(), 3);
+}
@@ -41,13 +41,16 @@ use tari_core::{
         proto::wallet_rpc::{TxLocation, TxQueryResponse, TxSubmissionRejectionReason, TxSubmissionResponse},
         rpc::BaseNodeWalletService,
     },
-    blocks::BlockHeader,
+    blocks::{BlockFilter, BlockHeader},
     proto,
     proto::{
         base_node::{
+            BlockFilter as BlockFilterProto,
             ChainMetadata as ChainMetadataProto,
             FetchMatchingUtxos,
             FetchUtxosResponse,
+            GetBlockFiltersRequest,
+            GetBlockFiltersResponse,
             GetMempoolFeePerGramStatsRequest,
             GetMempoolFeePerGramStatsResponse,
             QueryDeletedRequest,
@@ -850,6 +853,31 @@ impl BaseNodeWalletService for BaseNodeWalletRpcMockService {
             acquire_lock!(self.state.get_mempool_fee_per_gram_stats).clone(),
         ))
     }
+
+    async fn get_block_filters(
+        &self,
+        request: Request<GetBlockFiltersRequest>,
+    ) -> Result<Response<GetBlockFiltersResponse>, RpcStatus> {
+        let message = request.into_message();
+        let end_height = message.start_height.saturating_add(message.count);
+        let utxos_by_block = acquire_lock!(self.state.utxos_by_block).clone();
+        let mut filters = Vec::new();
+        for block in utxos_by_block
+            .iter()
+            .filter(|b| b.height >= message.start_height && b.height < end_height)
+        {
+            let header_hash = FixedHash::try_from(block.header_hash.clone())
+                .map_err(|_| RpcStatus::bad_request("Malformed header hash"))?;
+            let filter = BlockFilter::from_outputs(&header_hash, &block.utxos);
+            filters.push(BlockFilterProto {
+                height: block.height,
+                header_hash: block.header_hash.clone(),
+                num_items: filter.num_items(),
+                data: filter.data().to_vec(),
+            });
+        }
+        Ok(Response::new(GetBlockFiltersResponse { filters }))
+    }
 }
 
 #[derive(Clone, Debug)]
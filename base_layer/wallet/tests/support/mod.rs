@@ -26,4 +26,5 @@ pub mod comms_and_services;
 pub mod comms_rpc;
 pub mod data;
 pub mod output_manager_service_mock;
+pub mod rpc_replay;
 pub mod transaction_service_mock;
@@ -144,6 +144,14 @@ impl OutputManagerServiceMock {
                         warn!(target: LOG_TARGET, "Failed to send reply");
                     });
             },
+            OutputManagerRequest::GetBlockFilterItems => {
+                let items = acquire_lock!(self.state.block_filter_items).clone();
+                let _result = reply_tx
+                    .send(Ok(OutputManagerResponse::BlockFilterItems(items)))
+                    .inspect_err(|_| {
+                        warn!(target: LOG_TARGET, "Failed to send reply");
+                    });
+            },
             OutputManagerRequest::ValidateUtxos => {},
             _ => panic!("Output Manager Service Mock does not support this call"),
         }
@@ -154,6 +162,7 @@ impl OutputManagerServiceMock {
 pub struct OutputManagerMockState {
     pub recoverable_outputs: Arc<Mutex<Vec<DbWalletOutput>>>,
     pub one_sided_payments: Arc<Mutex<Vec<DbWalletOutput>>>,
+    pub block_filter_items: Arc<Mutex<Vec<Vec<u8>>>>,
 }
 
 impl OutputManagerMockState {
@@ -161,6 +170,7 @@ impl OutputManagerMockState {
         Self {
             recoverable_outputs: Arc::new(Mutex::new(Vec::new())),
             one_sided_payments: Arc::new(Mutex::new(Vec::new())),
+            block_filter_items: Arc::new(Mutex::new(Vec::new())),
         }
     }
 
@@ -173,6 +183,11 @@ impl OutputManagerMockState {
         let mut lock = acquire_lock!(self.one_sided_payments);
         *lock = outputs;
     }
+
+    pub fn set_block_filter_items(&self, items: Vec<Vec<u8>>) {
+        let mut lock = acquire_lock!(self.block_filter_items);
+        *lock = items;
+    }
 }
 
 impl Default for OutputManagerMockState {
@@ -0,0 +1,402 @@
+// Copyright 2024. The Tari Project
+//
+// Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+// following conditions are met:
+//
+// 1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+// disclaimer.
+//
+// 2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+// following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+// 3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+// products derived from this software without specific prior written permission.
+//
+// THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+// DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+// SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+// SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+// WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Deterministic replay of base node wallet RPC exchanges.
+//!
+//! A wallet with `rpc_recording_file` set in its base node service config records every call it makes to its base
+//! node. [BaseNodeWalletRpcReplayService] serves such a recording, which allows a wallet recovery or validation
+//! session against a live network to be reproduced offline in a test. The base node sync RPC is not covered since the
+//! wallet never calls it.
+
+use std::{
+    collections::HashMap,
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use minotari_wallet::connectivity_service::{RecordedExchange, RpcFixture};
+use prost::Message;
+use tari_comms::{
+    peer_manager::PeerFeatures,
+    protocol::rpc::{
+        mock::MockRpcServer,
+        NamedProtocolService,
+        Request,
+        Response,
+        RpcClientBuilder,
+        RpcStatus,
+        Streaming,
+    },
+    test_utils::node_identity::build_node_identity,
+    PeerConnection,
+};
+use tari_core::{
+    base_node::rpc::{BaseNodeWalletRpcClient, BaseNodeWalletRpcServer, BaseNodeWalletService},
+    proto,
+    proto::{
+        base_node::{
+            FetchMatchingUtxos,
+            FetchUtxosResponse,
+            GetBlockFiltersRequest,
+            GetBlockFiltersResponse,
+            GetMempoolFeePerGramStatsRequest,
+            GetMempoolFeePerGramStatsResponse,
+            QueryDeletedRequest,
+            QueryDeletedResponse,
+            Signatures as SignaturesProto,
+            SyncUtxosByBlockRequest,
+            SyncUtxosByBlockResponse,
+            TipInfoResponse,
+            TxQueryBatchResponses as TxQueryBatchResponsesProto,
+            TxQueryResponse as TxQueryResponseProto,
+            TxSubmissionResponse as TxSubmissionResponseProto,
+            UtxoQueryRequest,
+            UtxoQueryResponses,
+        },
+        types::{Signature as SignatureProto, Transaction as TransactionProto},
+    },
+};
+use tari_utilities::hex::{from_hex, to_hex};
+use tokio::sync::mpsc;
+
+/// Serves `service` as a base node and returns a client connected to it using `builder`. The server and connection
+/// must be kept alive for as long as the client is used.
+pub async fn serve_base_node_wallet_rpc<T: BaseNodeWalletService>(
+    service: T,
+    builder: RpcClientBuilder<BaseNodeWalletRpcClient>,
+) -> (
+    MockRpcServer<BaseNodeWalletRpcServer<T>>,
+    PeerConnection,
+    BaseNodeWalletRpcClient,
+) {
+    let server = BaseNodeWalletRpcServer::new(service);
+    let protocol_name = server.as_protocol_name();
+    let mut mock_server = MockRpcServer::new(server, build_node_identity(PeerFeatures::COMMUNICATION_NODE));
+    mock_server.serve();
+
+    let mut connection = mock_server
+        .create_connection(
+            build_node_identity(PeerFeatures::COMMUNICATION_NODE).to_peer(),
+            protocol_name.into(),
+        )
+        .await;
+    let client = connection.connect_rpc_using_builder(builder).await.unwrap();
+    (mock_server, connection, client)
+}
+
+/// Serves the exchanges in an [RpcFixture]. A call is answered with the next recorded exchange for the same method and
+/// request bytes. Once all exchanges for a request have been served the last one is repeated, so that polling calls
+/// such as `get_tip_info` settle on the final recorded state. Requests that were never recorded fail with a not found
+/// status.
+#[derive(Clone)]
+pub struct BaseNodeWalletRpcReplayService {
+    fixture: Arc<RpcFixture>,
+    served: Arc<Mutex<HashMap<(u32, String), usize>>>,
+    unmatched_calls: Arc<Mutex<Vec<u32>>>,
+}
+
+impl BaseNodeWalletRpcReplayService {
+    pub fn new(fixture: RpcFixture) -> Self {
+        Self {
+            fixture: Arc::new(fixture),
+            served: Arc::new(Mutex::new(HashMap::new())),
+            unmatched_calls: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Self {
+        Self::new(RpcFixture::load(path).expect("Could not load RPC recording"))
+    }
+
+    /// Returns the method numbers of all calls that had no recorded exchange
+    pub fn get_unmatched_calls(&self) -> Vec<u32> {
+        acquire_lock!(self.unmatched_calls).clone()
+    }
+
+    fn next_exchange<Req: Message>(&self, method: u32, request: &Req) -> Result<&RecordedExchange, RpcStatus> {
+        let request = to_hex(&request.encode_to_vec());
+        let matching = self
+            .fixture
+            .exchanges
+            .iter()
+            .filter(|e| e.method == method && e.request == request)
+            .collect::<Vec<_>>();
+        if matching.is_empty() {
+            acquire_lock!(self.unmatched_calls).push(method);
+            return Err(RpcStatus::not_found(&format!(
+                "No recorded exchange for method {} with request {}",
+                method, request
+            )));
+        }
+
+        let mut served = acquire_lock!(self.served);
+        let count = served.entry((method, request)).or_insert(0);
+        let exchange = matching[(*count).min(matching.len() - 1)];
+        *count += 1;
+        Ok(exchange)
+    }
+
+    fn replay<Req: Message, Resp: Message + Default>(
+        &self,
+        method: u32,
+        request: Request<Req>,
+    ) -> Result<Response<Resp>, RpcStatus> {
+        let exchange = self.next_exchange(method, request.message())?;
+        if let Some(status) = &exchange.status {
+            return Err(status.into());
+        }
+        let response = exchange
+            .responses
+            .first()
+            .ok_or_else(|| RpcStatus::general("Recorded exchange has no response"))?;
+        decode_response(response).map(Response::new)
+    }
+}
+
+fn decode_response<Resp: Message + Default>(response: &str) -> Result<Resp, RpcStatus> {
+    let bytes = from_hex(response).map_err(|_| RpcStatus::general("Recorded response is not valid hex"))?;
+    Resp::decode(bytes.as_slice()).map_err(|_| RpcStatus::general("Recorded response could not be decoded"))
+}
+
+fn streaming_from<T: Send + 'static>(items: Vec<T>, end_status: Option<RpcStatus>) -> Streaming<T> {
+    let (tx, rx) = mpsc::channel(items.len() + 1);
+    for item in items {
+        // The channel is large enough to hold every item so this cannot fail
+        let _result = tx.try_send(Ok(item));
+    }
+    if let Some(status) = end_status {
+        let _result = tx.try_send(Err(status));
+    }
+    Streaming::new(rx)
+}
+
+// The method numbers are those of the BaseNodeWalletService RPC definition
+#[tari_comms::async_trait]
+impl BaseNodeWalletService for BaseNodeWalletRpcReplayService {
+    async fn submit_transaction(
+        &self,
+        request: Request<TransactionProto>,
+    ) -> Result<Response<TxSubmissionResponseProto>, RpcStatus> {
+        self.replay(1, request)
+    }
+
+    async fn transaction_query(
+        &self,
+        request: Request<SignatureProto>,
+    ) -> Result<Response<TxQueryResponseProto>, RpcStatus> {
+        self.replay(2, request)
+    }
+
+    async fn transaction_batch_query(
+        &self,
+        request: Request<SignaturesProto>,
+    ) -> Result<Response<TxQueryBatchResponsesProto>, RpcStatus> {
+        self.replay(3, request)
+    }
+
+    async fn fetch_matching_utxos(
+        &self,
+        request: Request<FetchMatchingUtxos>,
+    ) -> Result<Response<FetchUtxosResponse>, RpcStatus> {
+        self.replay(4, request)
+    }
+
+    async fn get_tip_info(&self, request: Request<()>) -> Result<Response<TipInfoResponse>, RpcStatus> {
+        self.replay(5, request)
+    }
+
+    async fn get_header(&self, request: Request<u64>) -> Result<Response<proto::core::BlockHeader>, RpcStatus> {
+        self.replay(6, request)
+    }
+
+    async fn utxo_query(&self, request: Request<UtxoQueryRequest>) -> Result<Response<UtxoQueryResponses>, RpcStatus> {
+        self.replay(7, request)
+    }
+
+    async fn query_deleted(
+        &self,
+        request: Request<QueryDeletedRequest>,
+    ) -> Result<Response<QueryDeletedResponse>, RpcStatus> {
+        self.replay(8, request)
+    }
+
+    async fn get_header_by_height(
+        &self,
+        request: Request<u64>,
+    ) -> Result<Response<proto::core::BlockHeader>, RpcStatus> {
+        self.replay(9, request)
+    }
+
+    async fn get_height_at_time(&self, request: Request<u64>) -> Result<Response<u64>, RpcStatus> {
+        self.replay(10, request)
+    }
+
+    async fn sync_utxos_by_block(
+        &self,
+        request: Request<SyncUtxosByBlockRequest>,
+    ) -> Result<Streaming<SyncUtxosByBlockResponse>, RpcStatus> {
+        let exchange = self.next_exchange(11, request.message())?;
+        // A status with no streamed items means the call itself was rejected
+        if exchange.responses.is_empty() {
+            if let Some(status) = &exchange.status {
+                return Err(status.into());
+            }
+        }
+        let items = exchange
+            .responses
+            .iter()
+            .map(|r| decode_response(r))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(streaming_from(items, exchange.status.as_ref().map(RpcStatus::from)))
+    }
+
+    async fn get_mempool_fee_per_gram_stats(
+        &self,
+        request: Request<GetMempoolFeePerGramStatsRequest>,
+    ) -> Result<Response<GetMempoolFeePerGramStatsResponse>, RpcStatus> {
+        self.replay(12, request)
+    }
+
+    async fn get_block_filters(
+        &self,
+        request: Request<GetBlockFiltersRequest>,
+    ) -> Result<Response<GetBlockFiltersResponse>, RpcStatus> {
+        self.replay(13, request)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use futures::StreamExt;
+    use minotari_wallet::connectivity_service::{RpcFixture, RpcRecorder};
+    use tari_comms::protocol::rpc::{RpcError, RpcStatusCode};
+    use tari_core::{
+        base_node::rpc::BaseNodeWalletRpcClient,
+        proto::base_node::{ChainMetadata, SyncUtxosByBlockRequest, TipInfoResponse},
+    };
+    use tempfile::tempdir;
+    use tokio::time::Duration;
+
+    use crate::support::{
+        comms_rpc::{BaseNodeWalletRpcMockService, UtxosByBlock},
+        rpc_replay::{serve_base_node_wallet_rpc, BaseNodeWalletRpcReplayService},
+    };
+
+    #[tokio::test]
+    async fn test_record_and_replay() {
+        let base_node = BaseNodeWalletRpcMockService::new();
+        let base_node_state = base_node.get_state();
+        base_node_state.set_tip_info_response(TipInfoResponse {
+            metadata: Some(ChainMetadata {
+                best_block_height: 10,
+                best_block_hash: vec![],
+                accumulated_difficulty: vec![],
+                pruned_height: 0,
+                timestamp: 0,
+            }),
+            is_synced: true,
+        });
+        base_node_state.set_utxos_by_block(vec![
+            UtxosByBlock {
+                height: 1,
+                header_hash: vec![1u8; 32],
+                utxos: vec![],
+            },
+            UtxosByBlock {
+                height: 2,
+                header_hash: vec![2u8; 32],
+                utxos: vec![],
+            },
+        ]);
+        let sync_request = SyncUtxosByBlockRequest {
+            start_header_hash: vec![1u8; 32],
+            end_header_hash: vec![2u8; 32],
+        };
+
+        // The client is built the same way the wallet connectivity service builds its clients when recording is on
+        let temp_dir = tempdir().unwrap();
+        let recording_path = temp_dir.path().join("recording.jsonl");
+        let recorder = Arc::new(RpcRecorder::open(&recording_path).unwrap());
+        let (_base_node_server, _base_node_connection, mut client) = serve_base_node_wallet_rpc(
+            base_node,
+            BaseNodeWalletRpcClient::builder()
+                .with_deadline(Duration::from_secs(5))
+                .with_call_observer(recorder),
+        )
+        .await;
+        let recorded_tip = client.get_tip_info().await.unwrap();
+        let recorded_blocks = client
+            .sync_utxos_by_block(sync_request.clone())
+            .await
+            .unwrap()
+            .map(Result::unwrap)
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(recorded_blocks.len(), 2);
+
+        // An invalid signature is rejected by the base node and the rejection is recorded as well
+        assert!(client.transaction_query(Default::default()).await.is_err());
+
+        let fixture = RpcFixture::load(&recording_path).unwrap();
+        assert_eq!(fixture.exchanges.iter().map(|e| e.method).collect::<Vec<_>>(), vec![
+            5, 11, 2
+        ]);
+        let fixture_path = temp_dir.path().join("fixture.jsonl");
+        fixture.save(&fixture_path).unwrap();
+        assert_eq!(RpcFixture::load(&fixture_path).unwrap(), fixture);
+
+        let replay = BaseNodeWalletRpcReplayService::from_file(&recording_path);
+        let (_replay_server, _replay_connection, mut client) = serve_base_node_wallet_rpc(
+            replay.clone(),
+            BaseNodeWalletRpcClient::builder().with_deadline(Duration::from_secs(5)),
+        )
+        .await;
+        for _ in 0..2 {
+            assert_eq!(client.get_tip_info().await.unwrap(), recorded_tip);
+        }
+        let replayed_blocks = client
+            .sync_utxos_by_block(sync_request)
+            .await
+            .unwrap()
+            .map(Result::unwrap)
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(replayed_blocks, recorded_blocks);
+
+        match client.transaction_query(Default::default()).await {
+            Err(RpcError::RequestFailed(status)) => {
+                assert_eq!(status.as_status_code(), RpcStatusCode::BadRequest);
+                assert_eq!(status.details(), "Signature was invalid");
+            },
+            result => panic!("Unexpected result {:?}", result),
+        }
+        assert!(replay.get_unmatched_calls().is_empty());
+
+        match client.get_header_by_height(5).await {
+            Err(RpcError::RequestFailed(status)) => assert!(status.is_not_found()),
+            result => panic!("Unexpected result {:?}", result),
+        }
+        assert_eq!(replay.get_unmatched_calls(), vec![9]);
+    }
+}
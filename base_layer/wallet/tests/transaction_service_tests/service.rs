@@ -71,8 +71,11 @@ use minotari_wallet::{
     },
     transaction_service::{
         config::TransactionServiceConfig,
+        error::TransactionServiceError,
         handle::{TransactionEvent, TransactionSendStatus, TransactionServiceHandle},
+        partially_signed_transaction::{PartiallySignedInput, PartiallySignedTransactionError},
         service::TransactionService,
+        spending_policy::{SpendingPolicy, SpendingPolicyViolation},
         storage::{
             database::{DbKeyValuePair, TransactionBackend, TransactionDatabase, WriteOperation},
             models::{CompletedTransaction, InboundTransaction, OutboundTransaction, WalletTransaction},
@@ -137,7 +140,7 @@ use tari_core::{
         },
         transaction_protocol::{
             proto::protocol as proto,
-            recipient::RecipientSignedMessage,
+            recipient::{RecipientNonceMessage, RecipientSignedMessage},
             sender::TransactionSenderMessage,
             TransactionMetadata,
         },
@@ -299,6 +302,7 @@ pub struct TransactionServiceNoCommsInterface {
     transaction_send_message_channel:
         Sender<DomainMessage<Result<proto::TransactionSenderMessage, prost::DecodeError>>>,
     transaction_ack_message_channel: Sender<DomainMessage<Result<proto::RecipientSignedMessage, prost::DecodeError>>>,
+    transaction_nonce_message_channel: Sender<DomainMessage<Result<proto::RecipientNonceMessage, prost::DecodeError>>>,
     transaction_finalize_message_channel:
         Sender<DomainMessage<Result<proto::TransactionFinalizedMessage, prost::DecodeError>>>,
     _base_node_response_message_channel:
@@ -323,6 +327,18 @@ async fn setup_transaction_service_no_comms(
     factories: CryptoFactories,
     db_connection: WalletDbConnection,
     config: Option<TransactionServiceConfig>,
+) -> TransactionServiceNoCommsInterface {
+    setup_transaction_service_no_comms_with_wallet_type(factories, db_connection, config, None).await
+}
+
+/// As `setup_transaction_service_no_comms`, but the transaction service runs as the given wallet type instead of the
+/// key manager's
+#[allow(clippy::type_complexity)]
+async fn setup_transaction_service_no_comms_with_wallet_type(
+    factories: CryptoFactories,
+    db_connection: WalletDbConnection,
+    config: Option<TransactionServiceConfig>,
+    wallet_type: Option<Arc<WalletType>>,
 ) -> TransactionServiceNoCommsInterface {
     let (oms_request_sender, oms_request_receiver) = reply_channel::unbounded();
 
@@ -334,6 +350,7 @@ async fn setup_transaction_service_no_comms(
     let transaction_service_handle = TransactionServiceHandle::new(ts_request_sender, event_publisher.clone());
     let (transaction_send_message_channel, tx_receiver) = mpsc::channel(20);
     let (transaction_ack_message_channel, tx_ack_receiver) = mpsc::channel(20);
+    let (transaction_nonce_message_channel, tx_nonce_receiver) = mpsc::channel(20);
     let (transaction_finalize_message_channel, tx_finalized_receiver) = mpsc::channel(20);
     let (base_node_response_message_channel, base_node_response_receiver) = mpsc::channel(20);
     let (transaction_cancelled_message_channel, tx_cancelled_receiver) = mpsc::channel(20);
@@ -396,6 +413,7 @@ async fn setup_transaction_service_no_comms(
         constants,
         shutdown.to_signal(),
         base_node_service_handle.clone(),
+        transaction_service_handle.clone(),
         Network::LocalNet,
         wallet_connectivity_service_mock.clone(),
         key_manager.clone(),
@@ -426,6 +444,7 @@ async fn setup_transaction_service_no_comms(
         ts_request_receiver,
         tx_receiver,
         tx_ack_receiver,
+        tx_nonce_receiver,
         tx_finalized_receiver,
         base_node_response_receiver,
         tx_cancelled_receiver,
@@ -440,7 +459,10 @@ async fn setup_transaction_service_no_comms(
         factories,
         shutdown.to_signal(),
         base_node_service_handle,
-        key_manager.get_wallet_type().await,
+        match wallet_type {
+            Some(wallet_type) => wallet_type,
+            None => key_manager.get_wallet_type().await,
+        },
     )
     .await
     .unwrap();
@@ -453,6 +475,7 @@ async fn setup_transaction_service_no_comms(
         outbound_service_mock_state,
         transaction_send_message_channel,
         transaction_ack_message_channel,
+        transaction_nonce_message_channel,
         transaction_finalize_message_channel,
         _base_node_response_message_channel: base_node_response_message_channel,
         transaction_cancelled_message_channel,
@@ -1631,45 +1654,512 @@ async fn send_one_sided_transaction_to_other() {
             bob_address,
             value,
             UtxoSelectionCriteria::default(),
-            OutputFeatures::default(),
+            OutputFeatures::default(),
+            20.into(),
+            message.clone(),
+            PaymentId::Empty,
+        )
+        .await
+        .expect("Alice sending one-sided tx to Bob");
+
+    let completed_tx = alice_ts
+        .get_completed_transaction(tx_id)
+        .await
+        .expect("Could not find completed one-sided tx");
+
+    let fees = completed_tx.fee;
+
+    assert_eq!(
+        alice_oms.get_balance().await.unwrap().pending_incoming_balance,
+        initial_wallet_value - value - fees
+    );
+
+    let delay = sleep(Duration::from_secs(30));
+    tokio::pin!(delay);
+    let mut found = false;
+    loop {
+        tokio::select! {
+            event = alice_event_stream.recv() => {
+                if let TransactionEvent::TransactionCompletedImmediately(id) = &*event.unwrap() {
+                    if id == &tx_id {
+                        found = true;
+                        break;
+                    }
+                }
+            },
+            () = &mut delay => {
+                break;
+            },
+        }
+    }
+    assert!(found, "'TransactionCompletedImmediately(_)' event not found");
+}
+
+#[tokio::test]
+async fn watch_only_wallet_refuses_to_send() {
+    let factories = CryptoFactories::default();
+    let connection = make_wallet_database_memory_connection();
+    let (_, public_spend_key) = PublicKey::random_keypair(&mut OsRng);
+    let wallet_type = Arc::new(WalletType::ProvidedKeys(ProvidedKeysWallet {
+        public_spend_key,
+        private_spend_key: None,
+        view_key: PrivateKey::random(&mut OsRng),
+        private_comms_key: None,
+    }));
+    let mut alice_ts_interface =
+        setup_transaction_service_no_comms_with_wallet_type(factories, connection, None, Some(wallet_type)).await;
+
+    let initial_wallet_value = 2_500_000 * uT;
+    let uo = make_input(
+        &mut OsRng,
+        initial_wallet_value,
+        &OutputFeatures::default(),
+        &alice_ts_interface.key_manager_handle,
+    )
+    .await;
+    alice_ts_interface
+        .output_manager_service_handle
+        .add_output(uo.clone(), None)
+        .await
+        .unwrap();
+    alice_ts_interface
+        .oms_db
+        .mark_outputs_as_unspent(vec![(
+            uo.hash(&alice_ts_interface.key_manager_handle).await.unwrap(),
+            true,
+        )])
+        .unwrap();
+
+    let bob_address = TariAddress::new_dual_address_with_default_features(
+        PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng)),
+        PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng)),
+        Network::LocalNet,
+    );
+    let result = alice_ts_interface
+        .transaction_service_handle
+        .send_transaction(
+            bob_address.clone(),
+            100_000 * uT,
+            UtxoSelectionCriteria::default(),
+            OutputFeatures::default(),
+            20 * uT,
+            "".to_string(),
+        )
+        .await;
+    assert!(matches!(result, Err(TransactionServiceError::WatchOnlyWallet)));
+    let result = alice_ts_interface
+        .transaction_service_handle
+        .send_one_sided_transaction(
+            bob_address,
+            100_000 * uT,
+            UtxoSelectionCriteria::default(),
+            OutputFeatures::default(),
+            20 * uT,
+            "".to_string(),
+            PaymentId::Empty,
+        )
+        .await;
+    assert!(matches!(result, Err(TransactionServiceError::WatchOnlyWallet)));
+
+    // Nothing was sent and no funds were locked
+    assert_eq!(alice_ts_interface.outbound_service_mock_state.call_count().await, 0);
+    assert!(alice_ts_interface
+        .transaction_service_handle
+        .get_pending_outbound_transactions()
+        .await
+        .unwrap()
+        .is_empty());
+    let balance = alice_ts_interface
+        .output_manager_service_handle
+        .get_balance()
+        .await
+        .unwrap();
+    assert_eq!(balance.available_balance, initial_wallet_value);
+}
+
+/// Adds `num_outputs` unspent outputs of `value` each to the wallet, so that several sends can be pending at once
+async fn add_unspent_outputs(
+    ts_interface: &mut TransactionServiceNoCommsInterface,
+    num_outputs: usize,
+    value: MicroMinotari,
+) {
+    for _ in 0..num_outputs {
+        let uo = make_input(
+            &mut OsRng,
+            value,
+            &OutputFeatures::default(),
+            &ts_interface.key_manager_handle,
+        )
+        .await;
+        ts_interface
+            .output_manager_service_handle
+            .add_output(uo.clone(), None)
+            .await
+            .unwrap();
+        ts_interface
+            .oms_db
+            .mark_outputs_as_unspent(vec![(uo.hash(&ts_interface.key_manager_handle).await.unwrap(), true)])
+            .unwrap();
+    }
+}
+
+fn random_address() -> TariAddress {
+    TariAddress::new_dual_address_with_default_features(
+        PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng)),
+        PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng)),
+        Network::LocalNet,
+    )
+}
+
+#[tokio::test]
+async fn spending_policy_rejects_send_over_transaction_limit() {
+    let factories = CryptoFactories::default();
+    let connection = make_wallet_database_memory_connection();
+    let mut alice_ts_interface = setup_transaction_service_no_comms(factories, connection, None).await;
+    add_unspent_outputs(&mut alice_ts_interface, 1, 2_500_000 * uT).await;
+    alice_ts_interface
+        .transaction_service_handle
+        .set_spending_policy(SpendingPolicy {
+            max_amount_per_transaction: Some(100_000 * uT),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    let bob_address = random_address();
+    let result = alice_ts_interface
+        .transaction_service_handle
+        .send_transaction(
+            bob_address.clone(),
+            100_001 * uT,
+            UtxoSelectionCriteria::default(),
+            OutputFeatures::default(),
+            20 * uT,
+            "".to_string(),
+        )
+        .await;
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        TransactionServiceError::from(SpendingPolicyViolation::TransactionLimitExceeded {
+            amount: 100_001 * uT,
+            max: 100_000 * uT,
+        })
+        .to_string()
+    );
+    // The rejected send did not lock any funds
+    assert!(alice_ts_interface
+        .transaction_service_handle
+        .get_pending_outbound_transactions()
+        .await
+        .unwrap()
+        .is_empty());
+
+    alice_ts_interface
+        .transaction_service_handle
+        .send_transaction(
+            bob_address,
+            100_000 * uT,
+            UtxoSelectionCriteria::default(),
+            OutputFeatures::default(),
+            20 * uT,
+            "".to_string(),
+        )
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn spending_policy_daily_limit_counts_pending_and_completed_sends() {
+    let factories = CryptoFactories::default();
+    let connection = make_wallet_database_memory_connection();
+    let mut alice_ts_interface = setup_transaction_service_no_comms(factories, connection, None).await;
+    add_unspent_outputs(&mut alice_ts_interface, 5, 500_000 * uT).await;
+    alice_ts_interface
+        .transaction_service_handle
+        .set_spending_policy(SpendingPolicy {
+            daily_limit: Some(350_000 * uT),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    let bob_address = random_address();
+    let mut ts = alice_ts_interface.transaction_service_handle.clone();
+    // A pending interactive send, a completed one-sided send and a cancelled send
+    let pending_tx_id = ts
+        .send_transaction(
+            bob_address.clone(),
+            100_000 * uT,
+            UtxoSelectionCriteria::default(),
+            OutputFeatures::default(),
+            20 * uT,
+            "".to_string(),
+        )
+        .await
+        .unwrap();
+    let completed_tx_id = ts
+        .send_one_sided_transaction(
+            bob_address.clone(),
+            100_000 * uT,
+            UtxoSelectionCriteria::default(),
+            OutputFeatures::default(),
+            20 * uT,
+            "".to_string(),
+            PaymentId::Empty,
+        )
+        .await
+        .unwrap();
+    let cancelled_tx_id = ts
+        .send_transaction(
+            bob_address.clone(),
+            100_000 * uT,
+            UtxoSelectionCriteria::default(),
+            OutputFeatures::default(),
+            20 * uT,
+            "".to_string(),
+        )
+        .await
+        .unwrap();
+    ts.cancel_transaction(cancelled_tx_id).await.unwrap();
+    assert!(ts
+        .get_pending_outbound_transactions()
+        .await
+        .unwrap()
+        .contains_key(&pending_tx_id));
+    ts.get_completed_transaction(completed_tx_id).await.unwrap();
+
+    // The cancelled send no longer counts, so exactly the rest of the limit can be sent
+    ts.send_transaction(
+        bob_address.clone(),
+        150_000 * uT,
+        UtxoSelectionCriteria::default(),
+        OutputFeatures::default(),
+        20 * uT,
+        "".to_string(),
+    )
+    .await
+    .unwrap();
+
+    let result = ts
+        .send_transaction(
+            bob_address,
+            MicroMinotari::from(1),
+            UtxoSelectionCriteria::default(),
+            OutputFeatures::default(),
+            20 * uT,
+            "".to_string(),
+        )
+        .await;
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        TransactionServiceError::from(SpendingPolicyViolation::DailyLimitExceeded {
+            amount: MicroMinotari::from(1),
+            spent: 350_000 * uT,
+            limit: 350_000 * uT,
+        })
+        .to_string()
+    );
+}
+
+#[tokio::test]
+async fn spending_policy_delays_large_sends() {
+    let factories = CryptoFactories::default();
+    let connection = make_wallet_database_memory_connection();
+    let mut alice_ts_interface = setup_transaction_service_no_comms(factories, connection, None).await;
+    add_unspent_outputs(&mut alice_ts_interface, 2, 500_000 * uT).await;
+    alice_ts_interface
+        .transaction_service_handle
+        .set_spending_policy(SpendingPolicy {
+            large_send_threshold: Some(200_000 * uT),
+            large_send_delay: Duration::from_secs(1),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    let bob_address = random_address();
+    let mut ts = alice_ts_interface.transaction_service_handle.clone();
+    let send = |amount: MicroMinotari| {
+        let mut ts = ts.clone();
+        let bob_address = bob_address.clone();
+        async move {
+            ts.send_transaction(
+                bob_address,
+                amount,
+                UtxoSelectionCriteria::default(),
+                OutputFeatures::default(),
+                20 * uT,
+                "".to_string(),
+            )
+            .await
+        }
+    };
+
+    // Sends below the threshold are not delayed
+    send(100_000 * uT).await.unwrap();
+
+    // The first large send is rejected and so is a repeat before the delay has passed
+    for _ in 0..2 {
+        let result = send(300_000 * uT).await;
+        assert!(
+            matches!(
+                result,
+                Err(TransactionServiceError::SpendingPolicyViolation(
+                    SpendingPolicyViolation::DelayRequired { .. }
+                ))
+            ),
+            "unexpected result: {:?}",
+            result
+        );
+    }
+    // A different large send needs its own approval
+    assert!(matches!(
+        send(300_001 * uT).await,
+        Err(TransactionServiceError::SpendingPolicyViolation(
+            SpendingPolicyViolation::DelayRequired { .. }
+        ))
+    ));
+
+    sleep(Duration::from_secs(2)).await;
+    send(300_000 * uT).await.unwrap();
+    // The approval is used up by the send
+    assert!(matches!(
+        send(300_000 * uT).await,
+        Err(TransactionServiceError::SpendingPolicyViolation(
+            SpendingPolicyViolation::DelayRequired { .. }
+        ))
+    ));
+    assert_eq!(ts.get_pending_outbound_transactions().await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn partially_signed_transaction_round_trip() {
+    let network = Network::LocalNet;
+    let consensus_manager = ConsensusManager::builder(network).build().unwrap();
+    let factories = CryptoFactories::default();
+    let alice_node_identity = Arc::new(NodeIdentity::random(
+        &mut OsRng,
+        get_next_memory_address(),
+        PeerFeatures::COMMUNICATION_NODE,
+    ));
+    let bob_node_identity = Arc::new(NodeIdentity::random(
+        &mut OsRng,
+        get_next_memory_address(),
+        PeerFeatures::COMMUNICATION_NODE,
+    ));
+
+    let temp_dir = tempdir().unwrap();
+    let database_path = temp_dir.path().to_str().unwrap().to_string();
+    let db_connection = make_wallet_database_memory_connection();
+
+    let shutdown = Shutdown::new();
+    let (mut alice_ts, mut alice_oms, _alice_comms, _alice_connectivity, key_manager_handle, alice_db) =
+        setup_transaction_service(
+            alice_node_identity,
+            vec![],
+            consensus_manager,
+            factories.clone(),
+            db_connection,
+            database_path,
+            Duration::from_secs(0),
+            shutdown.to_signal(),
+        )
+        .await;
+
+    let initial_wallet_value = 25000.into();
+    let uo1 = make_input(
+        &mut OsRng,
+        initial_wallet_value,
+        &OutputFeatures::default(),
+        &key_manager_handle,
+    )
+    .await;
+    alice_oms.add_output(uo1.clone(), None).await.unwrap();
+    alice_db
+        .mark_outputs_as_unspent(vec![(uo1.hash(&key_manager_handle).await.unwrap(), true)])
+        .unwrap();
+
+    let random_pvt_key = PrivateKey::random(&mut OsRng);
+    let bob_address = TariAddress::new_dual_address_with_default_features(
+        PublicKey::from_secret_key(&random_pvt_key),
+        bob_node_identity.public_key().clone(),
+        network,
+    );
+    let value = 10000.into();
+    let unsigned = alice_ts
+        .create_partially_signed_transaction(
+            bob_address,
+            value,
+            UtxoSelectionCriteria::default(),
             20.into(),
-            message.clone(),
+            "offline".to_string(),
             PaymentId::Empty,
         )
         .await
-        .expect("Alice sending one-sided tx to Bob");
-
-    let completed_tx = alice_ts
-        .get_completed_transaction(tx_id)
-        .await
-        .expect("Could not find completed one-sided tx");
-
-    let fees = completed_tx.fee;
+        .unwrap();
+    assert_eq!(unsigned.inputs.len(), 1);
+    assert!(!unsigned.is_fully_signed());
+    // Creating the transaction must not lock the inputs
+    assert_eq!(
+        alice_oms.get_balance().await.unwrap().available_balance,
+        initial_wallet_value
+    );
 
+    // Inputs this wallet has not found on the chain are rejected rather than imported from the transaction
+    let unknown_input = make_input(&mut OsRng, 5000.into(), &OutputFeatures::default(), &key_manager_handle).await;
+    let mut with_unknown_input = unsigned.clone();
+    with_unknown_input.inputs.push(PartiallySignedInput::new(
+        unknown_input.to_transaction_output(&key_manager_handle).await.unwrap(),
+        Some(1),
+    ));
+    match alice_ts.sign_partially_signed_transaction(with_unknown_input).await {
+        Err(TransactionServiceError::PartiallySignedTransactionError(
+            PartiallySignedTransactionError::UnknownInput(_),
+        )) => {},
+        r => panic!("Expected an unknown input, got {:?}", r),
+    }
     assert_eq!(
-        alice_oms.get_balance().await.unwrap().pending_incoming_balance,
-        initial_wallet_value - value - fees
+        alice_oms.get_balance().await.unwrap().available_balance,
+        initial_wallet_value
     );
 
-    let delay = sleep(Duration::from_secs(30));
-    tokio::pin!(delay);
-    let mut found = false;
-    loop {
-        tokio::select! {
-            event = alice_event_stream.recv() => {
-                if let TransactionEvent::TransactionCompletedImmediately(id) = &*event.unwrap() {
-                    if id == &tx_id {
-                        found = true;
-                        break;
-                    }
-                }
-            },
-            () = &mut delay => {
-                break;
-            },
-        }
+    let committed = alice_ts
+        .sign_partially_signed_transaction(unsigned.clone())
+        .await
+        .unwrap();
+    assert!(committed.is_kernel_nonce_committed());
+    assert!(!committed.is_fully_signed());
+
+    // The signatures are only released for the nonce committed in the first round
+    let mut tampered = committed.clone();
+    tampered.kernel_nonce = Some(PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng)));
+    match alice_ts.sign_partially_signed_transaction(tampered).await {
+        Err(TransactionServiceError::PartiallySignedTransactionError(
+            PartiallySignedTransactionError::KernelNonceMismatch,
+        )) => {},
+        r => panic!("Expected a kernel nonce mismatch, got {:?}", r),
     }
-    assert!(found, "'TransactionCompletedImmediately(_)' event not found");
+
+    let signed = alice_ts
+        .sign_partially_signed_transaction(committed.clone())
+        .await
+        .unwrap();
+    assert!(signed.is_fully_signed());
+    assert_eq!(signed.kernel_nonce, committed.kernel_nonce);
+    assert_eq!(signed.tx_id, unsigned.tx_id);
+    assert!(alice_ts
+        .sign_partially_signed_transaction(signed.clone())
+        .await
+        .is_err());
+
+    let tx_id = alice_ts
+        .finalize_partially_signed_transaction(signed.clone())
+        .await
+        .unwrap();
+    assert_eq!(tx_id, signed.tx_id);
+    let completed_tx = alice_ts.get_completed_transaction(tx_id).await.unwrap();
+    assert_eq!(completed_tx.fee, signed.fee);
+    assert_eq!(completed_tx.amount, value);
 }
 
 #[tokio::test]
@@ -5306,6 +5796,332 @@ async fn test_transaction_timeout_cancellation() {
     assert!(transaction_cancelled, "Transaction must be cancelled");
 }
 
+/// A multi-recipient transaction that does not receive every recipient's signature is cancelled when it times out, the
+/// recipients are told about it and the inputs are released.
+#[tokio::test]
+async fn test_multi_recipient_transaction_timeout_cancellation() {
+    let factories = CryptoFactories::default();
+    let alice_connection = make_wallet_database_memory_connection();
+
+    let mut alice_ts_interface = setup_transaction_service_no_comms(
+        factories,
+        alice_connection,
+        Some(TransactionServiceConfig {
+            transaction_resend_period: Duration::from_secs(10),
+            resend_response_cooldown: Duration::from_secs(5),
+            pending_transaction_cancellation_timeout: Duration::from_secs(15),
+            ..Default::default()
+        }),
+    )
+    .await;
+    let mut alice_event_stream = alice_ts_interface.transaction_service_handle.get_event_stream();
+
+    let alice_total_available = 250000 * uT;
+    let uo = make_input(
+        &mut OsRng,
+        alice_total_available,
+        &OutputFeatures::default(),
+        &alice_ts_interface.key_manager_handle,
+    )
+    .await;
+    alice_ts_interface
+        .output_manager_service_handle
+        .add_output(uo.clone(), None)
+        .await
+        .unwrap();
+    alice_ts_interface
+        .oms_db
+        .mark_outputs_as_unspent(vec![(
+            uo.hash(&alice_ts_interface.key_manager_handle).await.unwrap(),
+            true,
+        )])
+        .unwrap();
+
+    let recipients = (0..2)
+        .map(|_| NodeIdentity::random(&mut OsRng, get_next_memory_address(), PeerFeatures::COMMUNICATION_NODE))
+        .collect::<Vec<_>>();
+    let tx_id = alice_ts_interface
+        .transaction_service_handle
+        .send_transaction_to_multiple_recipients(
+            recipients
+                .iter()
+                .map(|node| {
+                    (
+                        TariAddress::new_single_address_with_interactive_only(
+                            node.public_key().clone(),
+                            Network::LocalNet,
+                        ),
+                        10000 * uT,
+                    )
+                })
+                .collect(),
+            UtxoSelectionCriteria::default(),
+            20 * uT,
+            "Testing Message".to_string(),
+        )
+        .await
+        .unwrap();
+    let balance = alice_ts_interface
+        .output_manager_service_handle
+        .get_balance()
+        .await
+        .unwrap();
+    assert_eq!(balance.available_balance, MicroMinotari::zero());
+
+    // Every recipient gets the first round directly and via store and forward
+    alice_ts_interface
+        .outbound_service_mock_state
+        .wait_call_count(4, Duration::from_secs(60))
+        .await
+        .expect("Alice call wait 1");
+    let calls = alice_ts_interface.outbound_service_mock_state.take_calls().await;
+    for call in calls {
+        match try_decode_sender_message(call.1.to_vec()) {
+            Some(TransactionSenderMessage::Multiple(data)) => {
+                assert_eq!(data.sender_data.tx_id, tx_id);
+                assert_eq!(data.num_recipients, 2);
+            },
+            _ => panic!("Should be a Multiple Transaction Sender Message"),
+        }
+    }
+
+    // Once all of the recipients' nonces are in, the signature request is sent to all of them
+    for (index, node) in recipients.iter().enumerate() {
+        let nonce_message = RecipientNonceMessage {
+            tx_id,
+            recipient_index: index as u32,
+            public_nonce: PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng)),
+            public_excess: PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng)),
+        };
+        alice_ts_interface
+            .transaction_nonce_message_channel
+            .send(create_dummy_message(
+                proto::RecipientNonceMessage::from(nonce_message),
+                node.public_key(),
+            ))
+            .await
+            .unwrap();
+    }
+    alice_ts_interface
+        .outbound_service_mock_state
+        .wait_call_count(4, Duration::from_secs(60))
+        .await
+        .expect("Alice call wait 2");
+    let calls = alice_ts_interface.outbound_service_mock_state.take_calls().await;
+    for call in calls {
+        match try_decode_sender_message(call.1.to_vec()) {
+            Some(TransactionSenderMessage::SignatureRequest(request)) => assert_eq!(request.tx_id, tx_id),
+            _ => panic!("Should be a Signature Request Sender Message"),
+        }
+    }
+
+    // The recipients never sign, so the transaction times out
+    let delay = sleep(Duration::from_secs(60));
+    tokio::pin!(delay);
+    let mut transaction_cancelled = false;
+    loop {
+        tokio::select! {
+            event = alice_event_stream.recv() => {
+                 if let TransactionEvent::TransactionCancelled(t, _) = &*event.unwrap() {
+                    if t == &tx_id {
+                        transaction_cancelled = true;
+                        break;
+                    }
+                 }
+            },
+            () = &mut delay => {
+                break;
+            },
+        }
+    }
+    assert!(transaction_cancelled, "Transaction must be cancelled");
+
+    alice_ts_interface
+        .outbound_service_mock_state
+        .wait_call_count(4, Duration::from_secs(60))
+        .await
+        .expect("Alice call wait 3");
+    let calls = alice_ts_interface.outbound_service_mock_state.take_calls().await;
+    for call in calls {
+        let cancelled_message = try_decode_transaction_cancelled_message(call.1.to_vec()).unwrap();
+        assert_eq!(cancelled_message.tx_id, tx_id.as_u64());
+    }
+
+    let pending_outbound = alice_ts_interface
+        .transaction_service_handle
+        .get_pending_outbound_transactions()
+        .await
+        .unwrap();
+    assert!(!pending_outbound.contains_key(&tx_id));
+    let balance = alice_ts_interface
+        .output_manager_service_handle
+        .get_balance()
+        .await
+        .unwrap();
+    assert_eq!(balance.available_balance, alice_total_available);
+    assert_eq!(balance.pending_incoming_balance, MicroMinotari::zero());
+}
+
+/// The sender of a one-sided payment can prove the payment to its recipient, and the proof only verifies against the
+/// chain for that recipient.
+#[tokio::test]
+async fn test_payment_proof_generation_and_verification() {
+    let factories = CryptoFactories::default();
+    let alice_connection = make_wallet_database_memory_connection();
+    let mut alice_ts_interface = setup_transaction_service_no_comms(factories, alice_connection, None).await;
+
+    let uo = make_input(
+        &mut OsRng,
+        250000 * uT,
+        &OutputFeatures::default(),
+        &alice_ts_interface.key_manager_handle,
+    )
+    .await;
+    alice_ts_interface
+        .output_manager_service_handle
+        .add_output(uo.clone(), None)
+        .await
+        .unwrap();
+    alice_ts_interface
+        .oms_db
+        .mark_outputs_as_unspent(vec![(
+            uo.hash(&alice_ts_interface.key_manager_handle).await.unwrap(),
+            true,
+        )])
+        .unwrap();
+
+    let bob_address = TariAddress::new_dual_address_with_default_features(
+        PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng)),
+        PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng)),
+        Network::LocalNet,
+    );
+    let amount = 10000 * uT;
+    let tx_id = alice_ts_interface
+        .transaction_service_handle
+        .send_one_sided_transaction(
+            bob_address.clone(),
+            amount,
+            UtxoSelectionCriteria::default(),
+            OutputFeatures::default(),
+            20 * uT,
+            "Payment proof".to_string(),
+            PaymentId::Empty,
+        )
+        .await
+        .unwrap();
+
+    // A proof can only be created once the transaction is mined
+    assert!(alice_ts_interface
+        .transaction_service_handle
+        .generate_payment_proof(tx_id, None)
+        .await
+        .is_err());
+    let block_hash = FixedHash::from([7u8; 32]);
+    alice_ts_interface
+        .ts_db
+        .update_mined_height(tx_id, 5, block_hash, 0, 3, true, &TransactionStatus::MinedConfirmed)
+        .unwrap();
+
+    let proof = alice_ts_interface
+        .transaction_service_handle
+        .generate_payment_proof(tx_id, None)
+        .await
+        .unwrap();
+    assert_eq!(proof.amount, amount);
+    assert_eq!(proof.recipient, bob_address);
+    assert!(proof.is_valid_signature());
+
+    let completed_tx = alice_ts_interface
+        .transaction_service_handle
+        .get_completed_transaction(tx_id)
+        .await
+        .unwrap();
+    let output = completed_tx
+        .transaction
+        .body
+        .outputs()
+        .iter()
+        .find(|o| o.commitment == proof.output_commitment)
+        .unwrap()
+        .clone();
+    assert_eq!(output.script, push_pubkey_script(bob_address.public_spend_key()));
+    alice_ts_interface
+        .base_node_rpc_mock_state
+        .set_transaction_query_response(TxQueryResponse {
+            location: TxLocation::Mined,
+            best_block_hash: Some(block_hash),
+            confirmations: 3,
+            is_synced: true,
+            best_block_height: 8,
+            mined_timestamp: None,
+        });
+    alice_ts_interface
+        .base_node_rpc_mock_state
+        .set_utxo_query_response(base_node_proto::UtxoQueryResponses {
+            responses: vec![base_node_proto::UtxoQueryResponse {
+                output: Some(output.clone().try_into().unwrap()),
+                mined_at_height: 5,
+                mined_in_block: block_hash.to_vec(),
+                output_hash: output.hash().to_vec(),
+                mined_timestamp: 0,
+            }],
+            best_block_hash: block_hash.to_vec(),
+            best_block_height: 8,
+        });
+
+    let verification = alice_ts_interface
+        .transaction_service_handle
+        .verify_payment_proof(proof.clone())
+        .await
+        .unwrap();
+    assert_eq!(verification.block_hash, block_hash);
+    assert_eq!(verification.mined_height, 5);
+    assert_eq!(verification.confirmations, 3);
+
+    // Changing the recipient invalidates the signature
+    let carol_address = TariAddress::new_dual_address_with_default_features(
+        PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng)),
+        PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng)),
+        Network::LocalNet,
+    );
+    let mut tampered = proof.clone();
+    tampered.recipient = carol_address.clone();
+    assert!(alice_ts_interface
+        .transaction_service_handle
+        .verify_payment_proof(tampered)
+        .await
+        .is_err());
+
+    // The sender knows the commitment mask, so could sign a proof claiming another recipient, but the mined output is
+    // not locked to that recipient
+    let mut resigned = proof;
+    resigned.recipient = carol_address;
+    let nonce = alice_ts_interface.key_manager_handle.get_random_key().await.unwrap();
+    let challenge = resigned.challenge(&nonce.pub_key);
+    resigned.signature = alice_ts_interface
+        .key_manager_handle
+        .sign_with_nonce_and_challenge(
+            &KeyId::Imported {
+                key: resigned.public_key(),
+            },
+            &nonce.key_id,
+            &challenge,
+        )
+        .await
+        .unwrap();
+    assert!(resigned.is_valid_signature());
+    assert!(alice_ts_interface
+        .transaction_service_handle
+        .verify_payment_proof(resigned)
+        .await
+        .is_err());
+    assert!(alice_ts_interface
+        .transaction_service_handle
+        .generate_payment_proof(tx_id, Some(TariAddress::default()))
+        .await
+        .is_err());
+}
+
 /// This test will check that the Transaction Service starts the tx broadcast protocol correctly and reacts correctly
 /// to a tx being broadcast and to a tx being rejected.
 #[tokio::test]
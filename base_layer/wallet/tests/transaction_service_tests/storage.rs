@@ -33,6 +33,7 @@ use minotari_wallet::{
             CompletedTransaction,
             InboundTransaction,
             OutboundTransaction,
+            TransactionExportFilter,
             TxCancellationReason,
             WalletTransaction,
         },
@@ -684,3 +685,87 @@ async fn import_tx_and_read_it_from_db() {
     assert_eq!(db_tx.first().unwrap().tx_id, TxId::from(3u64));
     assert_eq!(db_tx.first().unwrap().mined_height, Some(7));
 }
+
+#[tokio::test]
+async fn export_transactions_with_filters() {
+    let db_name = format!("{}.sqlite3", random::string(8));
+    let db_tempdir = tempdir().unwrap();
+    let db_folder = db_tempdir.path().to_str().unwrap().to_string();
+    let db_path = format!("{}/{}", db_folder, db_name);
+    let connection = run_migration_and_create_sqlite_connection(db_path, 16).unwrap();
+
+    let mut key = [0u8; size_of::<Key>()];
+    OsRng.fill_bytes(&mut key);
+    let key_ga = Key::from_slice(&key);
+    let cipher = XChaCha20Poly1305::new(key_ga);
+    let sqlite_db = TransactionServiceSqliteDatabase::new(connection, cipher);
+
+    let timestamp = |secs: i64| NaiveDateTime::from_timestamp_opt(secs, 0).unwrap();
+    let txs = [
+        (1u64, TransactionDirection::Outbound, Some(10), Some(timestamp(1_000))),
+        (2u64, TransactionDirection::Inbound, Some(20), Some(timestamp(2_000))),
+        (3u64, TransactionDirection::Inbound, None, None),
+    ];
+    for (tx_id, direction, mined_height, mined_timestamp) in txs {
+        let transaction = CompletedTransaction::new(
+            TxId::from(tx_id),
+            TariAddress::default(),
+            TariAddress::default(),
+            MicroMinotari::from(1000),
+            MicroMinotari::from(10),
+            Transaction::new(
+                Vec::new(),
+                Vec::new(),
+                Vec::new(),
+                PrivateKey::random(&mut OsRng),
+                PrivateKey::random(&mut OsRng),
+            ),
+            TransactionStatus::MinedConfirmed,
+            "message".to_string(),
+            timestamp(3_000),
+            direction,
+            mined_height,
+            mined_timestamp,
+            Some(PaymentId::Open(b"invoice".to_vec())),
+        )
+        .unwrap();
+        sqlite_db
+            .write(WriteOperation::Insert(DbKeyValuePair::CompletedTransaction(
+                TxId::from(tx_id),
+                Box::new(transaction),
+            )))
+            .unwrap();
+    }
+    sqlite_db
+        .reject_completed_transaction(TxId::from(2u64), TxCancellationReason::Orphan)
+        .unwrap();
+    let db = TransactionDatabase::new(sqlite_db);
+
+    // Cancelled and unmined transactions are included and records are ordered by mined or creation time
+    let records = db.export_transactions(&TransactionExportFilter::default()).unwrap();
+    let tx_ids = records.iter().map(|r| u64::from(r.tx_id)).collect::<Vec<_>>();
+    assert_eq!(tx_ids, vec![1, 2, 3]);
+    assert_eq!(records[0].balance_change(), -1010);
+    assert!(records[1].cancelled);
+    assert_eq!(records[1].balance_change(), 0);
+    assert_eq!(records[2].balance_change(), 1000);
+    assert_eq!(records[0].payment_id, Some(PaymentId::Open(b"invoice".to_vec())));
+
+    // Height bounds exclude unmined transactions
+    let filter = TransactionExportFilter {
+        from_height: Some(15),
+        ..Default::default()
+    };
+    let records = db.export_transactions(&filter).unwrap();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].tx_id, TxId::from(2u64));
+
+    let filter = TransactionExportFilter {
+        from_timestamp: Some(timestamp(1_500)),
+        to_timestamp: Some(timestamp(2_500)),
+        ..Default::default()
+    };
+    let records = db.export_transactions(&filter).unwrap();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].tx_id, TxId::from(2u64));
+}
@@ -30,7 +30,7 @@ use std::{
 use chrono::{Duration as ChronoDuration, Utc};
 use minotari_wallet::{
     base_node_service::handle::{BaseNodeEvent, BaseNodeServiceHandle},
-    connectivity_service::{create_wallet_connectivity_mock, WalletConnectivityMock},
+    connectivity_service::{create_wallet_connectivity_mock, RpcRecorder, WalletConnectivityMock},
     output_manager_service::storage::{models::DbWalletOutput, OutputSource},
     storage::{
         database::WalletDatabase,
@@ -42,12 +42,16 @@ use minotari_wallet::{
     utxo_scanner_service::{
         handle::{UtxoScannerEvent, UtxoScannerHandle},
         service::{ScannedBlock, UtxoScannerService},
-        uxto_scanner_service_builder::UtxoScannerMode,
+        uxto_scanner_service_builder::{UtxoScannerMode, UtxoScannerServiceBuilder},
     },
 };
 use rand::{rngs::OsRng, RngCore};
 use tari_common::configuration::Network;
-use tari_common_types::tari_address::TariAddress;
+use tari_common_types::{
+    payment_request::PaymentRequest,
+    tari_address::TariAddress,
+    types::{PrivateKey, PublicKey},
+};
 use tari_comms::{
     peer_manager::PeerFeatures,
     protocol::rpc::{mock::MockRpcServer, NamedProtocolService},
@@ -57,17 +61,19 @@ use tari_comms::{
     },
 };
 use tari_core::{
-    base_node::rpc::BaseNodeWalletRpcServer,
+    base_node::rpc::{BaseNodeWalletRpcClient, BaseNodeWalletRpcServer},
     blocks::BlockHeader,
     proto::base_node::{ChainMetadata, TipInfoResponse},
     transactions::{
         key_manager::{create_memory_db_key_manager, MemoryDbKeyManager, TransactionKeyManagerInterface},
         tari_amount::MicroMinotari,
-        transaction_components::{OutputFeatures, WalletOutput},
+        transaction_components::{encrypted_data::PaymentId, OutputFeatures, WalletOutput},
         CryptoFactories,
     },
 };
+use tari_crypto::keys::{PublicKey as PublicKeyTrait, SecretKey};
 use tari_key_manager::{cipher_seed::CipherSeed, get_birthday_from_unix_epoch_in_seconds};
+use tari_script::push_pubkey_script;
 use tari_service_framework::reply_channel;
 use tari_shutdown::Shutdown;
 use tari_test_utils::random;
@@ -83,6 +89,7 @@ use crate::support::{
     base_node_service_mock::MockBaseNodeService,
     comms_rpc::{BaseNodeWalletRpcMockService, BaseNodeWalletRpcMockState, UtxosByBlock},
     output_manager_service_mock::{make_output_manager_service_mock, OutputManagerMockState},
+    rpc_replay::{serve_base_node_wallet_rpc, BaseNodeWalletRpcReplayService},
     transaction_service_mock::{make_transaction_service_mock, TransactionServiceMockState},
     utils::make_input,
 };
@@ -93,8 +100,10 @@ pub struct UtxoScannerTestInterface {
     wallet_db: WalletDatabase<WalletSqliteDatabase>,
     base_node_service_event_publisher: broadcast::Sender<Arc<BaseNodeEvent>>,
     rpc_service_state: BaseNodeWalletRpcMockState,
-    _rpc_mock_server: MockRpcServer<BaseNodeWalletRpcServer<BaseNodeWalletRpcMockService>>,
+    rpc_service_states: Vec<BaseNodeWalletRpcMockState>,
+    _rpc_mock_servers: Vec<MockRpcServer<BaseNodeWalletRpcServer<BaseNodeWalletRpcMockService>>>,
     _comms_connectivity_mock_state: ConnectivityManagerMockState,
+    wallet_connectivity_mock: WalletConnectivityMock,
     transaction_service_mock_state: TransactionServiceMockState,
     oms_mock_state: OutputManagerMockState,
     shutdown_signal: Shutdown,
@@ -107,6 +116,29 @@ async fn setup(
     previous_db: Option<WalletDatabase<WalletSqliteDatabase>>,
     recovery_message: Option<String>,
     one_sided_message: Option<String>,
+) -> UtxoScannerTestInterface {
+    setup_with_base_nodes(
+        key_manager,
+        mode,
+        previous_db,
+        recovery_message,
+        one_sided_message,
+        1,
+        |_| {},
+    )
+    .await
+}
+
+/// Sets up a scanner that syncs from `num_base_nodes` mock base nodes, `configure_scanner` can change the remaining
+/// settings of the scanner before it is built
+async fn setup_with_base_nodes(
+    key_manager: MemoryDbKeyManager,
+    mode: UtxoScannerMode,
+    previous_db: Option<WalletDatabase<WalletSqliteDatabase>>,
+    recovery_message: Option<String>,
+    one_sided_message: Option<String>,
+    num_base_nodes: usize,
+    configure_scanner: impl FnOnce(&mut UtxoScannerServiceBuilder),
 ) -> UtxoScannerTestInterface {
     let shutdown = Shutdown::new();
     let factories = CryptoFactories::default();
@@ -119,26 +151,33 @@ async fn setup(
     mock_base_node_service.set_default_base_node_state();
     task::spawn(mock_base_node_service.run());
 
-    // BaseNodeRpcService Mock
-    let service = BaseNodeWalletRpcMockService::new();
-    let rpc_service_state = service.get_state();
-    let server = BaseNodeWalletRpcServer::new(service);
-    let protocol_name = server.as_protocol_name();
-    let server_node_identity = build_node_identity(PeerFeatures::COMMUNICATION_NODE);
-    let mut mock_server = MockRpcServer::new(server, server_node_identity.clone());
-    mock_server.serve();
-
-    let rpc_server_connection = mock_server
-        .create_connection(server_node_identity.to_peer(), protocol_name.into())
-        .await;
-
     let (comms_connectivity, connectivity_mock) = create_connectivity_mock();
     let comms_connectivity_mock_state = connectivity_mock.get_shared_state();
-    comms_connectivity_mock_state
-        .add_active_connection(rpc_server_connection)
-        .await;
     task::spawn(connectivity_mock.run());
 
+    // BaseNodeRpcService Mocks
+    let mut rpc_service_states = Vec::with_capacity(num_base_nodes);
+    let mut mock_servers = Vec::with_capacity(num_base_nodes);
+    let mut base_node_public_keys = Vec::with_capacity(num_base_nodes);
+    for _ in 0..num_base_nodes {
+        let service = BaseNodeWalletRpcMockService::new();
+        rpc_service_states.push(service.get_state());
+        let server = BaseNodeWalletRpcServer::new(service);
+        let protocol_name = server.as_protocol_name();
+        let server_node_identity = build_node_identity(PeerFeatures::COMMUNICATION_NODE);
+        let mut mock_server = MockRpcServer::new(server, server_node_identity.clone());
+        mock_server.serve();
+
+        let rpc_server_connection = mock_server
+            .create_connection(server_node_identity.to_peer(), protocol_name.into())
+            .await;
+        comms_connectivity_mock_state
+            .add_active_connection(rpc_server_connection)
+            .await;
+        mock_servers.push(mock_server);
+        base_node_public_keys.push(server_node_identity.public_key().clone());
+    }
+
     let wallet_connectivity_mock = create_wallet_connectivity_mock();
 
     let (ts_mock, ts_handle) = make_transaction_service_mock(shutdown.to_signal());
@@ -181,10 +220,12 @@ async fn setup(
     let mut scanner_service_builder = UtxoScannerService::<WalletSqliteDatabase, WalletConnectivityMock>::builder();
 
     scanner_service_builder
-        .with_peers(vec![server_node_identity.public_key().clone()])
+        .with_peers(base_node_public_keys)
         .with_retry_limit(1)
         .with_mode(mode);
 
+    configure_scanner(&mut scanner_service_builder);
+
     if let Some(message) = one_sided_message {
         scanner_service_builder.with_one_sided_message(message);
     }
@@ -203,7 +244,7 @@ async fn setup(
         .build_with_resources::<WalletSqliteDatabase, WalletConnectivityMock, MemoryDbKeyManager>(
             wallet_db.clone(),
             comms_connectivity,
-            wallet_connectivity_mock,
+            wallet_connectivity_mock.clone(),
             oms_handle,
             ts_handle,
             tari_address,
@@ -221,9 +262,11 @@ async fn setup(
         scanner_handle,
         wallet_db,
         base_node_service_event_publisher: event_publisher_bns,
-        rpc_service_state,
-        _rpc_mock_server: mock_server,
+        rpc_service_state: rpc_service_states[0].clone(),
+        rpc_service_states,
+        _rpc_mock_servers: mock_servers,
         _comms_connectivity_mock_state: comms_connectivity_mock_state,
+        wallet_connectivity_mock,
         transaction_service_mock_state,
         oms_mock_state,
         shutdown_signal: shutdown,
@@ -385,6 +428,165 @@ async fn test_utxo_scanner_recovery() {
         }
     }
 }
+#[tokio::test]
+#[allow(clippy::too_many_lines)]
+async fn test_utxo_scanner_parallel_recovery_with_failing_base_node() {
+    const NUM_BASE_NODES: usize = 3;
+    const NUM_BLOCKS: u64 = 30;
+    const BIRTHDAY_OFFSET: u64 = 25;
+    // The last base node only has the blocks below this height, so it fails partway through the recovery
+    const FAILING_NODE_HEIGHT: u64 = 15;
+
+    let key_manager = create_memory_db_key_manager().unwrap();
+    let mut test_interface = setup_with_base_nodes(
+        key_manager.clone(),
+        UtxoScannerMode::Recovery,
+        None,
+        None,
+        None,
+        NUM_BASE_NODES,
+        |builder| {
+            builder.with_recovery_chunk_size(2);
+        },
+    )
+    .await;
+
+    let cipher_seed = CipherSeed::new();
+    // get birthday duration, in seconds, from unix epoch
+    let birthday_epoch_time = get_birthday_from_unix_epoch_in_seconds(cipher_seed.birthday(), 14u16);
+    test_interface.wallet_db.set_master_seed(cipher_seed).unwrap();
+
+    let TestBlockData {
+        block_headers,
+        wallet_outputs,
+        utxos_by_block,
+    } = generate_block_headers_and_utxos(0, NUM_BLOCKS, birthday_epoch_time, BIRTHDAY_OFFSET, true, &key_manager).await;
+
+    let chain_metadata = ChainMetadata {
+        best_block_height: NUM_BLOCKS - 1,
+        best_block_hash: block_headers.get(&(NUM_BLOCKS - 1)).unwrap().clone().hash().to_vec(),
+        accumulated_difficulty: Vec::new(),
+        pruned_height: 0,
+        timestamp: 0,
+    };
+    let (failing_node, base_nodes) = test_interface.rpc_service_states.split_last().unwrap();
+    let mut triggers = Vec::new();
+    for rpc_service_state in base_nodes {
+        rpc_service_state.set_utxos_by_block(utxos_by_block.clone());
+        rpc_service_state.set_blocks(block_headers.clone());
+        rpc_service_state.set_tip_info_response(TipInfoResponse {
+            metadata: Some(chain_metadata.clone()),
+            is_synced: true,
+        });
+        // Hold the first chunk of each of the other base nodes until the failing base node has failed
+        let (tx, rx) = mpsc::channel(100);
+        rpc_service_state.set_utxos_by_block_trigger_channel(rx);
+        triggers.push(tx);
+    }
+    failing_node.set_utxos_by_block(
+        utxos_by_block
+            .iter()
+            .filter(|b| b.height < FAILING_NODE_HEIGHT)
+            .cloned()
+            .collect(),
+    );
+    failing_node.set_blocks(
+        block_headers
+            .iter()
+            .filter(|(height, _)| **height < FAILING_NODE_HEIGHT)
+            .map(|(height, header)| (*height, header.clone()))
+            .collect(),
+    );
+    failing_node.set_tip_info_response(TipInfoResponse {
+        metadata: Some(chain_metadata),
+        is_synced: true,
+    });
+
+    let mut db_wallet_outputs = Vec::new();
+    let mut total_outputs_to_recover = 0;
+    let mut total_amount_to_recover = MicroMinotari::from(0);
+    for (h, outputs) in &wallet_outputs {
+        for output in outputs {
+            let dbo = DbWalletOutput::from_wallet_output(
+                output.clone(),
+                &key_manager,
+                None,
+                OutputSource::Standard,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+            // Only the outputs in blocks after the birthday should be recovered
+            if *h >= NUM_BLOCKS.saturating_sub(BIRTHDAY_OFFSET).saturating_sub(2) {
+                total_outputs_to_recover += 1;
+                total_amount_to_recover += dbo.wallet_output.value;
+            }
+            db_wallet_outputs.push(dbo);
+        }
+    }
+    test_interface.oms_mock_state.set_recoverable_outputs(db_wallet_outputs);
+
+    let mut scanner_event_stream = test_interface.scanner_handle.get_event_receiver();
+
+    tokio::spawn(test_interface.scanner_service.take().unwrap().run());
+
+    // The failing base node scans chunks from the queue until it is asked for a block it does not have
+    loop {
+        let calls = failing_node
+            .wait_pop_get_header_by_height_calls(1, Duration::from_secs(30))
+            .await
+            .unwrap();
+        if calls[0] >= FAILING_NODE_HEIGHT {
+            break;
+        }
+    }
+    for tx in triggers {
+        tx.send(usize::try_from(NUM_BLOCKS).unwrap()).await.unwrap();
+    }
+
+    let mut progress_heights = Vec::new();
+    let delay = time::sleep(Duration::from_secs(60));
+    tokio::pin!(delay);
+    loop {
+        tokio::select! {
+            _ = &mut delay => {
+                panic!("Completed event should have arrived by now.");
+            }
+            event = scanner_event_stream.recv() => {
+                match event.unwrap() {
+                    UtxoScannerEvent::Progress { current_height, tip_height: _ } => {
+                        progress_heights.push(current_height);
+                    },
+                    UtxoScannerEvent::Completed { final_height, .. } => {
+                        assert_eq!(final_height, NUM_BLOCKS - 1);
+                        break;
+                    },
+                    _ => {},
+                }
+            }
+        }
+    }
+
+    // Chunks finish out of order, but the progress only ever moves forward
+    assert!(!progress_heights.is_empty());
+    assert!(progress_heights.windows(2).all(|w| w[0] <= w[1]));
+    assert_eq!(progress_heights.last(), Some(&(NUM_BLOCKS - 1)));
+
+    // The chunk that the failing base node could not scan was scanned by another base node, and no block was
+    // scanned twice
+    let mut num_imported = 0;
+    let mut amount_imported = MicroMinotari::from(0);
+    for req in test_interface.transaction_service_mock_state.drain_requests() {
+        if let TransactionServiceRequest::ImportUtxoWithStatus { amount, .. } = req {
+            num_imported += 1;
+            amount_imported += amount;
+        }
+    }
+    assert_eq!(num_imported, total_outputs_to_recover);
+    assert_eq!(amount_imported, total_amount_to_recover);
+}
+
 #[tokio::test]
 #[allow(clippy::too_many_lines)]
 async fn test_utxo_scanner_recovery_with_restart() {
@@ -1082,6 +1284,380 @@ async fn test_utxo_scanner_one_sided_payments() {
     }
 }
 
+/// Sets up a scanner that uses block filters, with a chain of `num_blocks` blocks of which the block at
+/// `payment_height` also contains a one-sided payment to the wallet. The scan starts at height 1, which is the last
+/// block before the wallet birthday. Returns the payment.
+async fn setup_block_filter_scan(
+    key_manager: &MemoryDbKeyManager,
+    num_blocks: u64,
+    payment_height: u64,
+) -> (UtxoScannerTestInterface, TestBlockData, DbWalletOutput) {
+    let test_interface = setup_with_base_nodes(
+        key_manager.clone(),
+        UtxoScannerMode::Scanning,
+        None,
+        None,
+        None,
+        1,
+        |builder| {
+            builder.with_block_filters(true);
+        },
+    )
+    .await;
+
+    let cipher_seed = CipherSeed::new();
+    let birthday_epoch_time = get_birthday_from_unix_epoch_in_seconds(cipher_seed.birthday(), 14u16);
+    test_interface.wallet_db.set_master_seed(cipher_seed).unwrap();
+
+    let mut block_data =
+        generate_block_headers_and_utxos(0, num_blocks, birthday_epoch_time, 2, true, key_manager).await;
+
+    // The payment is locked to a one-sided script key of the wallet, which is the wallet's filter item
+    let script_key = PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng));
+    let mut payment = make_input(
+        &mut OsRng,
+        MicroMinotari::from(5000),
+        &OutputFeatures::default(),
+        key_manager,
+    )
+    .await;
+    payment.script = push_pubkey_script(&script_key);
+    block_data
+        .utxos_by_block
+        .iter_mut()
+        .find(|b| b.height == payment_height)
+        .unwrap()
+        .utxos
+        .push(payment.to_transaction_output(key_manager).await.unwrap());
+    let payment = DbWalletOutput::from_wallet_output(payment, key_manager, None, OutputSource::OneSided, None, None)
+        .await
+        .unwrap();
+    test_interface
+        .oms_mock_state
+        .set_one_sided_payments(vec![payment.clone()]);
+    test_interface
+        .oms_mock_state
+        .set_block_filter_items(vec![script_key.as_bytes().to_vec()]);
+
+    test_interface
+        .rpc_service_state
+        .set_utxos_by_block(block_data.utxos_by_block.clone());
+    test_interface
+        .rpc_service_state
+        .set_blocks(block_data.block_headers.clone());
+    test_interface.rpc_service_state.set_tip_info_response(TipInfoResponse {
+        metadata: Some(ChainMetadata {
+            best_block_height: num_blocks - 1,
+            best_block_hash: block_data.block_headers.get(&(num_blocks - 1)).unwrap().hash().to_vec(),
+            accumulated_difficulty: Vec::new(),
+            pruned_height: 0,
+            timestamp: 0,
+        }),
+        is_synced: true,
+    });
+
+    (test_interface, block_data, payment)
+}
+
+#[tokio::test]
+async fn test_utxo_scanner_block_filters() {
+    const NUM_BLOCKS: u64 = 10;
+    const PAYMENT_HEIGHT: u64 = 7;
+
+    let key_manager = create_memory_db_key_manager().unwrap();
+    let (mut test_interface, block_data, payment) =
+        setup_block_filter_scan(&key_manager, NUM_BLOCKS, PAYMENT_HEIGHT).await;
+
+    let mut scanner_event_stream = test_interface.scanner_handle.get_event_receiver();
+    tokio::spawn(test_interface.scanner_service.take().unwrap().run());
+
+    let delay = time::sleep(Duration::from_secs(60));
+    tokio::pin!(delay);
+    loop {
+        tokio::select! {
+            _ = &mut delay => {
+                panic!("Completed event should have arrived by now.");
+            }
+            event = scanner_event_stream.recv() => {
+                if let UtxoScannerEvent::Completed {
+                    final_height,
+                    num_recovered,
+                    value_recovered,
+                    ..
+                } = event.unwrap() {
+                    assert_eq!(final_height, NUM_BLOCKS - 1);
+                    assert_eq!(num_recovered, 1);
+                    assert_eq!(value_recovered, payment.wallet_output.value);
+                    break;
+                }
+            }
+        }
+    }
+
+    // Only the block with the payment was downloaded, the other blocks were skipped on their filters
+    let payment_block_hash = block_data.block_headers.get(&PAYMENT_HEIGHT).unwrap().hash();
+    assert_eq!(test_interface.rpc_service_state.take_sync_utxos_by_block_calls(), vec![
+        (payment_block_hash, payment_block_hash)
+    ]);
+    let requests = test_interface.transaction_service_mock_state.drain_requests();
+    let imported = requests
+        .iter()
+        .filter(|req| matches!(req, TransactionServiceRequest::ImportUtxoWithStatus { .. }))
+        .count();
+    assert_eq!(imported, 1);
+}
+
+#[tokio::test]
+async fn test_utxo_scanner_block_filters_reject_filter_for_other_block() {
+    const NUM_BLOCKS: u64 = 10;
+    const PAYMENT_HEIGHT: u64 = 7;
+
+    let key_manager = create_memory_db_key_manager().unwrap();
+    let (mut test_interface, mut block_data, _payment) =
+        setup_block_filter_scan(&key_manager, NUM_BLOCKS, PAYMENT_HEIGHT).await;
+
+    // The peer serves the filter of the payment block under a header hash that is not on the chain
+    let mut other_header = BlockHeader::new(0);
+    other_header.height = PAYMENT_HEIGHT;
+    block_data
+        .utxos_by_block
+        .iter_mut()
+        .find(|b| b.height == PAYMENT_HEIGHT)
+        .unwrap()
+        .header_hash = other_header.hash().to_vec();
+    test_interface
+        .rpc_service_state
+        .set_utxos_by_block(block_data.utxos_by_block);
+
+    let mut scanner_event_stream = test_interface.scanner_handle.get_event_receiver();
+    tokio::spawn(test_interface.scanner_service.take().unwrap().run());
+
+    let delay = time::sleep(Duration::from_secs(60));
+    tokio::pin!(delay);
+    loop {
+        tokio::select! {
+            _ = &mut delay => {
+                panic!("ScanningRoundFailed event should have arrived by now.");
+            }
+            event = scanner_event_stream.recv() => {
+                match event.unwrap() {
+                    UtxoScannerEvent::ScanningRoundFailed { error, .. } => {
+                        assert!(error.contains("block filter"), "unexpected error: {}", error);
+                        break;
+                    },
+                    UtxoScannerEvent::Completed { .. } => panic!("Scan should not complete with a bad block filter"),
+                    _ => {},
+                }
+            }
+        }
+    }
+    assert!(test_interface
+        .rpc_service_state
+        .take_sync_utxos_by_block_calls()
+        .is_empty());
+}
+
+#[tokio::test]
+async fn test_utxo_scanner_marks_payment_request_paid() {
+    let key_manager = create_memory_db_key_manager().unwrap();
+    let mut test_interface = setup(key_manager.clone(), UtxoScannerMode::Scanning, None, None, None).await;
+
+    let cipher_seed = CipherSeed::new();
+    let birthday_epoch_time = get_birthday_from_unix_epoch_in_seconds(cipher_seed.birthday(), 14u16);
+    test_interface.wallet_db.set_master_seed(cipher_seed).unwrap();
+
+    const NUM_BLOCKS: u64 = 5;
+    let TestBlockData {
+        block_headers,
+        wallet_outputs,
+        utxos_by_block,
+    } = generate_block_headers_and_utxos(0, NUM_BLOCKS, birthday_epoch_time, 0, false, &key_manager).await;
+
+    test_interface.rpc_service_state.set_utxos_by_block(utxos_by_block);
+    test_interface.rpc_service_state.set_blocks(block_headers.clone());
+    test_interface.rpc_service_state.set_tip_info_response(TipInfoResponse {
+        metadata: Some(ChainMetadata {
+            best_block_height: NUM_BLOCKS - 1,
+            best_block_hash: block_headers.get(&(NUM_BLOCKS - 1)).unwrap().hash().to_vec(),
+            accumulated_difficulty: Vec::new(),
+            pruned_height: 0,
+            timestamp: 0,
+        }),
+        is_synced: true,
+    });
+
+    // The first payment covers its request, the second one pays less than was requested
+    let mut payments = wallet_outputs.get(&(NUM_BLOCKS - 1)).unwrap().iter().take(2).cloned();
+    let mut paid = payments.next().unwrap();
+    paid.payment_id = PaymentId::Open(b"order_1".to_vec());
+    let mut underpaid = payments.next().unwrap();
+    underpaid.payment_id = PaymentId::Open(b"order_2".to_vec());
+    for (payment_id, amount) in [
+        (b"order_1".to_vec(), paid.value.as_u64()),
+        (b"order_2".to_vec(), underpaid.value.as_u64() + 1),
+    ] {
+        let request = PaymentRequest::new(TariAddress::default(), amount, payment_id, None, None);
+        test_interface.wallet_db.save_invoice(&request).unwrap();
+    }
+
+    let mut db_wallet_outputs = Vec::new();
+    for output in [paid, underpaid] {
+        db_wallet_outputs.push(
+            DbWalletOutput::from_wallet_output(output, &key_manager, None, OutputSource::OneSided, None, None)
+                .await
+                .unwrap(),
+        );
+    }
+    test_interface.oms_mock_state.set_one_sided_payments(db_wallet_outputs);
+
+    let mut scanner_event_stream = test_interface.scanner_handle.get_event_receiver();
+    tokio::spawn(test_interface.scanner_service.take().unwrap().run());
+
+    let delay = time::sleep(Duration::from_secs(60));
+    tokio::pin!(delay);
+    loop {
+        tokio::select! {
+            _ = &mut delay => {
+                panic!("Completed event should have arrived by now.");
+            }
+            event = scanner_event_stream.recv() => {
+                if let UtxoScannerEvent::Completed { num_recovered, .. } = event.unwrap() {
+                    assert_eq!(num_recovered, 2);
+                    break;
+                }
+            }
+        }
+    }
+
+    let invoices = test_interface.wallet_db.fetch_invoices().unwrap();
+    assert_eq!(invoices.len(), 2);
+    let order_1 = invoices
+        .iter()
+        .find(|invoice| invoice.request.payment_id() == b"order_1")
+        .unwrap();
+    // The transaction service mock imports every output as transaction 42
+    assert_eq!(order_1.paid_tx_id, Some(42u64.into()));
+    assert!(order_1.paid_at.is_some());
+    let order_2 = invoices
+        .iter()
+        .find(|invoice| invoice.request.payment_id() == b"order_2")
+        .unwrap();
+    assert!(!order_2.is_paid());
+}
+
+/// Scans for `payments` from the wallet's selected base node, for which the wallet connectivity service hands out
+/// `client`. Returns the final height, number and value of the outputs found.
+async fn scan_using_selected_base_node(
+    key_manager: &MemoryDbKeyManager,
+    cipher_seed: CipherSeed,
+    payments: Vec<DbWalletOutput>,
+    client: BaseNodeWalletRpcClient,
+) -> (u64, u64, MicroMinotari) {
+    let base_node_identity = build_node_identity(PeerFeatures::COMMUNICATION_NODE);
+    let mut test_interface = setup_with_base_nodes(
+        key_manager.clone(),
+        UtxoScannerMode::Scanning,
+        None,
+        None,
+        None,
+        1,
+        |builder| {
+            builder.with_peers(vec![base_node_identity.public_key().clone()]);
+        },
+    )
+    .await;
+    test_interface.wallet_db.set_master_seed(cipher_seed).unwrap();
+    test_interface.oms_mock_state.set_one_sided_payments(payments);
+    test_interface
+        .wallet_connectivity_mock
+        .notify_base_node_set(base_node_identity.to_peer());
+    test_interface
+        .wallet_connectivity_mock
+        .set_base_node_wallet_rpc_client(client);
+
+    let mut scanner_event_stream = test_interface.scanner_handle.get_event_receiver();
+    tokio::spawn(test_interface.scanner_service.take().unwrap().run());
+
+    let delay = time::sleep(Duration::from_secs(60));
+    tokio::pin!(delay);
+    loop {
+        tokio::select! {
+            _ = &mut delay => {
+                panic!("Completed event should have arrived by now.");
+            }
+            event = scanner_event_stream.recv() => {
+                if let UtxoScannerEvent::Completed {
+                    final_height,
+                    num_recovered,
+                    value_recovered,
+                    ..
+                } = event.unwrap() {
+                    return (final_height, num_recovered, value_recovered);
+                }
+            }
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_utxo_scanner_replays_recorded_session() {
+    let key_manager = create_memory_db_key_manager().unwrap();
+    let cipher_seed = CipherSeed::new();
+    let birthday_epoch_time = get_birthday_from_unix_epoch_in_seconds(cipher_seed.birthday(), 14u16);
+
+    const NUM_BLOCKS: u64 = 5;
+    let TestBlockData {
+        block_headers,
+        wallet_outputs,
+        utxos_by_block,
+    } = generate_block_headers_and_utxos(0, NUM_BLOCKS, birthday_epoch_time, 0, false, &key_manager).await;
+
+    let base_node = BaseNodeWalletRpcMockService::new();
+    let base_node_state = base_node.get_state();
+    base_node_state.set_utxos_by_block(utxos_by_block);
+    base_node_state.set_blocks(block_headers.clone());
+    base_node_state.set_tip_info_response(TipInfoResponse {
+        metadata: Some(ChainMetadata {
+            best_block_height: NUM_BLOCKS - 1,
+            best_block_hash: block_headers.get(&(NUM_BLOCKS - 1)).unwrap().hash().to_vec(),
+            accumulated_difficulty: Vec::new(),
+            pruned_height: 0,
+            timestamp: 0,
+        }),
+        is_synced: true,
+    });
+
+    let mut payments = Vec::new();
+    for output in wallet_outputs.get(&(NUM_BLOCKS - 1)).unwrap().iter().take(2) {
+        payments.push(
+            DbWalletOutput::from_wallet_output(output.clone(), &key_manager, None, OutputSource::OneSided, None, None)
+                .await
+                .unwrap(),
+        );
+    }
+    let payments_value = payments.iter().map(|p| p.wallet_output.value).sum::<MicroMinotari>();
+
+    // The scan is recorded with the client the wallet connectivity service builds when `rpc_recording_file` is set
+    let temp_dir = tempdir().unwrap();
+    let recording_path = temp_dir.path().join("recording.jsonl");
+    let recorder = Arc::new(RpcRecorder::open(&recording_path).unwrap());
+    let (_base_node_server, _base_node_connection, client) = serve_base_node_wallet_rpc(
+        base_node,
+        BaseNodeWalletRpcClient::builder().with_call_observer(recorder),
+    )
+    .await;
+    let recorded = scan_using_selected_base_node(&key_manager, cipher_seed.clone(), payments.clone(), client).await;
+    assert_eq!(recorded, (NUM_BLOCKS - 1, 2, payments_value));
+
+    // A new wallet with the same seed finds the same outputs from the recording alone
+    let replay = BaseNodeWalletRpcReplayService::from_file(&recording_path);
+    let (_replay_server, _replay_connection, client) =
+        serve_base_node_wallet_rpc(replay.clone(), BaseNodeWalletRpcClient::builder()).await;
+    let replayed = scan_using_selected_base_node(&key_manager, cipher_seed, payments, client).await;
+    assert_eq!(replayed, recorded);
+    assert!(replay.get_unmatched_calls().is_empty());
+}
+
 #[tokio::test]
 async fn test_birthday_timestamp_over_chain() {
     let key_manager = create_memory_db_key_manager().unwrap();
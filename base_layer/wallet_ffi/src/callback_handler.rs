@@ -339,6 +339,12 @@ where TBackend: TransactionBackend + 'static
                                 OutputManagerEvent::TxoValidationCommunicationFailure(request_key) => {
                                     self.output_validation_complete_event(request_key,  3);
                                 },
+                                OutputManagerEvent::UtxoConsolidationCreated { .. } => {
+                                    self.trigger_balance_refresh().await;
+                                },
+                                OutputManagerEvent::UtxoConsolidationFailed(e) => {
+                                    warn!(target: LOG_TARGET, "Automatic UTXO consolidation failed: {}", e);
+                                },
                             }
                         },
                         Err(_e) => error!(target: LOG_TARGET, "Error reading from Output Manager Service event broadcast channel"),
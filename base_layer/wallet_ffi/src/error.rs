@@ -235,6 +235,10 @@ impl From<WalletError> for LibWalletError {
                 code: 212,
                 message: format!("{:?}", w),
             },
+            WalletError::TransactionServiceError(TransactionServiceError::SpendingPolicyViolation(_)) => Self {
+                code: 213,
+                message: format!("{:?}", w),
+            },
             WalletError::TransactionServiceError(_) => Self {
                 code: 211,
                 message: format!("{:?}", w),
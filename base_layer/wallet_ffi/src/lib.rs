@@ -90,6 +90,7 @@ use minotari_wallet::{
             models::DbWalletOutput,
             OutputStatus,
         },
+        sub_address::SubAddress,
         UtxoSelectionCriteria,
     },
     storage::{
@@ -100,6 +101,8 @@ use minotari_wallet::{
     transaction_service::{
         config::TransactionServiceConfig,
         error::TransactionServiceError,
+        handle::OneSidedRecipient,
+        spending_policy::SpendingPolicy,
         storage::{
             database::TransactionDatabase,
             models::{CompletedTransaction, InboundTransaction, OutboundTransaction},
@@ -254,6 +257,10 @@ pub struct TariSeedWords(SeedWords);
 #[derive(Debug, PartialEq)]
 pub struct TariPublicKeys(Vec<TariPublicKey>);
 
+pub struct TariOneSidedRecipients(Vec<OneSidedRecipient>);
+
+pub struct TariSubAddresses(Vec<SubAddress>);
+
 pub struct TariWallet {
     wallet: WalletSqlite,
     runtime: Runtime,
@@ -309,11 +316,21 @@ pub struct TariUtxo {
     pub status: u8,
     pub coinbase_extra: *const c_char,
     pub payment_id: *const c_char,
+    pub frozen: bool,
+    pub label: *const c_char,
+    pub note: *const c_char,
 }
 
-impl From<DbWalletOutput> for TariUtxo {
-    fn from(x: DbWalletOutput) -> Self {
-        Self {
+impl TryFrom<DbWalletOutput> for TariUtxo {
+    type Error = InterfaceError;
+
+    fn try_from(x: DbWalletOutput) -> Result<Self, Self::Error> {
+        // Labels and notes are free text that may contain a NUL byte, which a C string cannot hold
+        let label = CString::new(x.label.unwrap_or_default())
+            .map_err(|_| InterfaceError::InvalidArgument("label contains a NUL byte".to_string()))?;
+        let note = CString::new(x.note.unwrap_or_default())
+            .map_err(|_| InterfaceError::InvalidArgument("note contains a NUL byte".to_string()))?;
+        Ok(Self {
             commitment: CString::new(x.commitment.to_hex())
                 .expect("failed to obtain hex from a commitment")
                 .into_raw(),
@@ -345,7 +362,10 @@ impl From<DbWalletOutput> for TariUtxo {
             )
             .expect("failed to obtain string from a payment id")
             .into_raw(),
-        }
+            frozen: x.frozen,
+            label: label.into_raw(),
+            note: note.into_raw(),
+        })
     }
 }
 
@@ -420,16 +440,18 @@ impl From<Vec<Commitment>> for TariVector {
     }
 }
 
-impl From<Vec<DbWalletOutput>> for TariVector {
-    fn from(v: Vec<DbWalletOutput>) -> TariVector {
-        let mut v = ManuallyDrop::new(v.into_iter().map(TariUtxo::from).collect_vec());
+impl TryFrom<Vec<DbWalletOutput>> for TariVector {
+    type Error = InterfaceError;
 
-        Self {
+    fn try_from(v: Vec<DbWalletOutput>) -> Result<Self, Self::Error> {
+        let mut v = ManuallyDrop::new(v.into_iter().map(TariUtxo::try_from).collect::<Result<Vec<_>, _>>()?);
+
+        Ok(Self {
             tag: TariTypeTag::Utxo,
             len: v.len(),
             cap: v.capacity(),
             ptr: v.as_mut_ptr() as *mut c_void,
-        }
+        })
     }
 }
 
@@ -3331,6 +3353,266 @@ pub unsafe extern "C" fn contacts_destroy(contacts: *mut TariContacts) {
 
 /// -------------------------------------------------------------------------------------------- ///
 
+/// ----------------------------------- OneSidedRecipients ------------------------------------- ///
+
+/// Create an empty instance of TariOneSidedRecipients
+///
+/// ## Arguments
+/// None
+///
+/// ## Returns
+/// `*mut TariOneSidedRecipients` - Returns a pointer to an empty TariOneSidedRecipients.
+///
+/// # Safety
+/// The `one_sided_recipients_destroy` method must be called when finished with a TariOneSidedRecipients to prevent a
+/// memory leak
+#[no_mangle]
+pub unsafe extern "C" fn one_sided_recipients_create() -> *mut TariOneSidedRecipients {
+    Box::into_raw(Box::new(TariOneSidedRecipients(Vec::new())))
+}
+
+/// Adds a recipient to a TariOneSidedRecipients
+///
+/// ## Arguments
+/// `recipients` - The pointer to a TariOneSidedRecipients
+/// `destination` - The TariWalletAddress pointer of the recipient
+/// `amount` - The amount to send to the recipient
+/// `payment_id_string` - The pointer to a char array containing the payment id of this recipient's output, may be null
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `()` - Does not return a value, equivalent to void in C
+///
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn one_sided_recipients_push(
+    recipients: *mut TariOneSidedRecipients,
+    destination: *mut TariWalletAddress,
+    amount: c_ulonglong,
+    payment_id_string: *const c_char,
+    error_out: *mut c_int,
+) {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if recipients.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("recipients".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return;
+    }
+    if destination.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("destination".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return;
+    }
+
+    let payment_id = if payment_id_string.is_null() {
+        PaymentId::Empty
+    } else {
+        match CStr::from_ptr(payment_id_string).to_str() {
+            Ok(v) => PaymentId::Open(v.as_bytes().to_vec()),
+            _ => {
+                error = LibWalletError::from(InterfaceError::NullError("payment_id".to_string())).code;
+                ptr::swap(error_out, &mut error as *mut c_int);
+                return;
+            },
+        }
+    };
+
+    (*recipients).0.push(OneSidedRecipient {
+        destination: (*destination).clone(),
+        amount: MicroMinotari::from(amount),
+        payment_id,
+    });
+}
+
+/// Gets the length of TariOneSidedRecipients
+///
+/// ## Arguments
+/// `recipients` - The pointer to a TariOneSidedRecipients
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `c_uint` - Returns number of elements in recipients, zero if recipients is null
+///
+/// # Safety
+/// None
+// casting here is okay as a batch will never have more than u32 recipients
+#[allow(clippy::cast_possible_truncation)]
+#[no_mangle]
+pub unsafe extern "C" fn one_sided_recipients_get_length(
+    recipients: *const TariOneSidedRecipients,
+    error_out: *mut c_int,
+) -> c_uint {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    let mut len = 0;
+    if recipients.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("recipients".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+    } else {
+        len = (*recipients).0.len();
+    }
+    len as c_uint
+}
+
+/// Frees memory for a TariOneSidedRecipients
+///
+/// ## Arguments
+/// `recipients` - The pointer to a TariOneSidedRecipients
+///
+/// ## Returns
+/// `()` - Does not return a value, equivalent to void in C
+///
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn one_sided_recipients_destroy(recipients: *mut TariOneSidedRecipients) {
+    if !recipients.is_null() {
+        drop(Box::from_raw(recipients))
+    }
+}
+
+/// -------------------------------------------------------------------------------------------- ///
+
+/// ----------------------------------- Sub-addresses ------------------------------------------ ///
+
+/// Gets the length of TariSubAddresses
+///
+/// ## Arguments
+/// `sub_addresses` - The pointer to a TariSubAddresses
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `c_uint` - Returns number of elements in sub_addresses, zero if sub_addresses is null
+///
+/// # Safety
+/// None
+// casting here is okay as a wallet will never have more than u32 sub-addresses
+#[allow(clippy::cast_possible_truncation)]
+#[no_mangle]
+pub unsafe extern "C" fn sub_addresses_get_length(
+    sub_addresses: *const TariSubAddresses,
+    error_out: *mut c_int,
+) -> c_uint {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    let mut len = 0;
+    if sub_addresses.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("sub_addresses".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+    } else {
+        len = (*sub_addresses).0.len();
+    }
+    len as c_uint
+}
+
+/// Gets the TariWalletAddress of the sub-address in TariSubAddresses at position
+///
+/// ## Arguments
+/// `sub_addresses` - The pointer to a TariSubAddresses
+/// `position` - The integer position
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `*mut TariWalletAddress` - Returns a TariWalletAddress, note that it returns ptr::null_mut() if sub_addresses is
+/// null or position is invalid
+///
+/// # Safety
+/// The ```tari_address_destroy``` method must be called when finished with a TariWalletAddress to prevent a memory leak
+// converting between here is fine as its used to clamp the array to length
+#[allow(clippy::cast_possible_wrap)]
+#[no_mangle]
+pub unsafe extern "C" fn sub_addresses_get_address_at(
+    sub_addresses: *mut TariSubAddresses,
+    position: c_uint,
+    error_out: *mut c_int,
+) -> *mut TariWalletAddress {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if sub_addresses.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("sub_addresses".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return ptr::null_mut();
+    }
+    let len = sub_addresses_get_length(sub_addresses, error_out) as c_int - 1;
+    if len < 0 || position > len as c_uint {
+        error = LibWalletError::from(InterfaceError::PositionInvalidError).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return ptr::null_mut();
+    }
+    Box::into_raw(Box::new((*sub_addresses).0[position as usize].address.clone()))
+}
+
+/// Gets the label of the sub-address in TariSubAddresses at position
+///
+/// ## Arguments
+/// `sub_addresses` - The pointer to a TariSubAddresses
+/// `position` - The integer position
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `*mut c_char` - Returns a pointer to a char array. Note that it returns an empty char array if sub_addresses is
+/// null or position is invalid
+///
+/// # Safety
+/// The ```string_destroy``` method must be called when finished with a string from rust to prevent a memory leak
+// converting between here is fine as its used to clamp the array to length
+#[allow(clippy::cast_possible_wrap)]
+#[no_mangle]
+pub unsafe extern "C" fn sub_addresses_get_label_at(
+    sub_addresses: *mut TariSubAddresses,
+    position: c_uint,
+    error_out: *mut c_int,
+) -> *mut c_char {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    let mut label = CString::new("").expect("Blank CString will not fail.");
+    if sub_addresses.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("sub_addresses".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return CString::into_raw(label);
+    }
+    let len = sub_addresses_get_length(sub_addresses, error_out) as c_int - 1;
+    if len < 0 || position > len as c_uint {
+        error = LibWalletError::from(InterfaceError::PositionInvalidError).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return CString::into_raw(label);
+    }
+    match CString::new((*sub_addresses).0[position as usize].label.clone()) {
+        Ok(v) => label = v,
+        _ => {
+            error = LibWalletError::from(InterfaceError::PointerError("sub_addresses".to_string())).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+        },
+    }
+    CString::into_raw(label)
+}
+
+/// Frees memory for a TariSubAddresses
+///
+/// ## Arguments
+/// `sub_addresses` - The pointer to a TariSubAddresses
+///
+/// ## Returns
+/// `()` - Does not return a value, equivalent to void in C
+///
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn sub_addresses_destroy(sub_addresses: *mut TariSubAddresses) {
+    if !sub_addresses.is_null() {
+        drop(Box::from_raw(sub_addresses))
+    }
+}
+
+/// -------------------------------------------------------------------------------------------- ///
+
 /// ----------------------------------- Contacts Liveness Data ----------------------------------///
 
 /// Gets the public_key from a TariContactsLivenessData
@@ -6128,67 +6410,156 @@ pub unsafe extern "C" fn wallet_get_balance(wallet: *mut TariWallet, error_out:
     }
 }
 
-/// This function returns a list of unspent UTXO values and commitments.
+/// Derives a new sub-address from the wallet seed. One-sided payments to the sub-address are received by this wallet
+/// and attributed to the sub-address.
 ///
 /// ## Arguments
-/// * `wallet` - The TariWallet pointer,
-/// * `page` - Page offset,
-/// * `page_size` - A number of items per page,
-/// * `sorting` - An enum representing desired sorting,
-/// * `dust_threshold` - A value filtering threshold. Outputs whose values are <= `dust_threshold` are not listed in the
-///   result.
-/// * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null.
-///   Functions as an out parameter.
+/// `wallet` - The TariWallet pointer.
+/// `label` - The label of the sub-address, may be null
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
 ///
 /// ## Returns
-/// `*mut TariVector` - Returns a struct with an array pointer, length and capacity (needed for proper destruction
-/// after use).
+/// `*mut TariWalletAddress` - Returns the pointer to the new sub-address or null if an error occurs
 ///
 /// # Safety
-/// `destroy_tari_vector()` must be called after use.
-/// Items that fail to produce `.as_transaction_output()` are omitted from the list and a `warn!()` message is logged to
-/// LOG_TARGET.
-// casting here is okay as we wont have more than u32 utxos
-#[allow(clippy::cast_possible_truncation)]
+/// The ```tari_address_destroy``` method must be called when finished with a TariWalletAddress to prevent a memory leak
 #[no_mangle]
-pub unsafe extern "C" fn wallet_get_utxos(
+pub unsafe extern "C" fn wallet_create_sub_address(
     wallet: *mut TariWallet,
-    page: usize,
-    page_size: usize,
-    sorting: TariUtxoSort,
-    states: *mut TariVector,
-    dust_threshold: u64,
-    error_ptr: *mut i32,
-) -> *mut TariVector {
+    label: *const c_char,
+    error_out: *mut c_int,
+) -> *mut TariWalletAddress {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
     if wallet.is_null() {
-        error!(target: LOG_TARGET, "wallet pointer is null");
-        ptr::replace(
-            error_ptr,
-            LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code,
-        );
+        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
         return ptr::null_mut();
     }
-
-    let page = i64::from_usize(page).unwrap_or(i64::MAX);
-    let page_size = i64::from_usize(page_size).unwrap_or(i64::MAX);
-    let dust_threshold = i64::from_u64(dust_threshold).unwrap_or(0);
-
-    let status = {
-        if states.is_null() {
-            vec![]
-        } else {
-            Vec::from_raw_parts((*states).ptr as *mut u64, (*states).len, (*states).cap)
-                .into_iter()
-                .map(|x| OutputStatus::try_from(x as i32).unwrap())
-                .collect_vec()
+    let label = if label.is_null() {
+        String::new()
+    } else {
+        match CStr::from_ptr(label).to_str() {
+            Ok(v) => v.to_owned(),
+            _ => {
+                error = LibWalletError::from(InterfaceError::PointerError("label".to_string())).code;
+                ptr::swap(error_out, &mut error as *mut c_int);
+                return ptr::null_mut();
+            },
         }
     };
-
-    use SortDirection::{Asc, Desc};
-    let q = OutputBackendQuery {
-        tip_height: i64::MAX,
-        status,
-        commitments: vec![],
+    match (*wallet)
+        .runtime
+        .block_on((*wallet).wallet.output_manager_service.create_sub_address(label))
+    {
+        Ok(sub_address) => Box::into_raw(Box::new(sub_address.address)),
+        Err(e) => {
+            error = LibWalletError::from(WalletError::OutputManagerError(e)).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            ptr::null_mut()
+        },
+    }
+}
+
+/// Retrieves the sub-addresses of a wallet
+///
+/// ## Arguments
+/// `wallet` - The TariWallet pointer.
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `*mut TariSubAddresses` - Returns the pointer to the TariSubAddresses or null if an error occurs
+///
+/// # Safety
+/// The ```sub_addresses_destroy``` method must be called when finished with a TariSubAddresses to prevent a memory leak
+#[no_mangle]
+pub unsafe extern "C" fn wallet_get_sub_addresses(
+    wallet: *mut TariWallet,
+    error_out: *mut c_int,
+) -> *mut TariSubAddresses {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if wallet.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return ptr::null_mut();
+    }
+    match (*wallet)
+        .runtime
+        .block_on((*wallet).wallet.output_manager_service.get_sub_addresses())
+    {
+        Ok(sub_addresses) => Box::into_raw(Box::new(TariSubAddresses(sub_addresses))),
+        Err(e) => {
+            error = LibWalletError::from(WalletError::OutputManagerError(e)).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            ptr::null_mut()
+        },
+    }
+}
+
+/// This function returns a list of unspent UTXO values and commitments.
+///
+/// ## Arguments
+/// * `wallet` - The TariWallet pointer,
+/// * `page` - Page offset,
+/// * `page_size` - A number of items per page,
+/// * `sorting` - An enum representing desired sorting,
+/// * `dust_threshold` - A value filtering threshold. Outputs whose values are <= `dust_threshold` are not listed in the
+///   result.
+/// * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null.
+///   Functions as an out parameter.
+///
+/// ## Returns
+/// `*mut TariVector` - Returns a struct with an array pointer, length and capacity (needed for proper destruction
+/// after use).
+///
+/// # Safety
+/// `destroy_tari_vector()` must be called after use.
+/// Items that fail to produce `.as_transaction_output()` are omitted from the list and a `warn!()` message is logged to
+/// LOG_TARGET.
+// casting here is okay as we wont have more than u32 utxos
+#[allow(clippy::cast_possible_truncation)]
+#[no_mangle]
+pub unsafe extern "C" fn wallet_get_utxos(
+    wallet: *mut TariWallet,
+    page: usize,
+    page_size: usize,
+    sorting: TariUtxoSort,
+    states: *mut TariVector,
+    dust_threshold: u64,
+    error_ptr: *mut i32,
+) -> *mut TariVector {
+    if wallet.is_null() {
+        error!(target: LOG_TARGET, "wallet pointer is null");
+        ptr::replace(
+            error_ptr,
+            LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code,
+        );
+        return ptr::null_mut();
+    }
+
+    let page = i64::from_usize(page).unwrap_or(i64::MAX);
+    let page_size = i64::from_usize(page_size).unwrap_or(i64::MAX);
+    let dust_threshold = i64::from_u64(dust_threshold).unwrap_or(0);
+
+    let status = {
+        if states.is_null() {
+            vec![]
+        } else {
+            Vec::from_raw_parts((*states).ptr as *mut u64, (*states).len, (*states).cap)
+                .into_iter()
+                .map(|x| OutputStatus::try_from(x as i32).unwrap())
+                .collect_vec()
+        }
+    };
+
+    use SortDirection::{Asc, Desc};
+    let q = OutputBackendQuery {
+        tip_height: i64::MAX,
+        status,
+        commitments: vec![],
         pagination: Some((page, page_size)),
         value_min: Some((dust_threshold, false)),
         value_max: None,
@@ -6201,9 +6572,16 @@ pub unsafe extern "C" fn wallet_get_utxos(
     };
 
     match (*wallet).wallet.output_db.fetch_outputs_by_query(q) {
-        Ok(outputs) => {
-            ptr::replace(error_ptr, 0);
-            Box::into_raw(Box::new(TariVector::from(outputs)))
+        Ok(outputs) => match TariVector::try_from(outputs) {
+            Ok(outputs) => {
+                ptr::replace(error_ptr, 0);
+                Box::into_raw(Box::new(outputs))
+            },
+            Err(e) => {
+                error!(target: LOG_TARGET, "failed to convert outputs: {:#?}", e);
+                ptr::replace(error_ptr, LibWalletError::from(e).code);
+                ptr::null_mut()
+            },
         },
 
         Err(e) => {
@@ -6271,9 +6649,16 @@ pub unsafe extern "C" fn wallet_get_all_utxos(wallet: *mut TariWallet, error_ptr
     };
 
     match (*wallet).wallet.output_db.fetch_outputs_by_query(q) {
-        Ok(outputs) => {
-            ptr::replace(error_ptr, 0);
-            Box::into_raw(Box::new(TariVector::from(outputs)))
+        Ok(outputs) => match TariVector::try_from(outputs) {
+            Ok(outputs) => {
+                ptr::replace(error_ptr, 0);
+                Box::into_raw(Box::new(outputs))
+            },
+            Err(e) => {
+                error!(target: LOG_TARGET, "failed to convert outputs: {:#?}", e);
+                ptr::replace(error_ptr, LibWalletError::from(e).code);
+                ptr::null_mut()
+            },
         },
 
         Err(e) => {
@@ -6592,6 +6977,153 @@ pub unsafe extern "C" fn wallet_preview_coin_split(
     }
 }
 
+/// Freezes or unfreezes an output. Frozen outputs are never selected as inputs for any transaction.
+///
+/// ## Arguments
+/// `wallet` - The TariWallet pointer.
+/// `commitment` - The pointer to a char array containing the commitment's hex value (see `Commitment::to_hex()`)
+/// `frozen` - Whether the output should be frozen
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `bool` - Return a boolean value indicating the operation's success or failure. The error_ptr will hold the error
+/// code if there was a failure
+///
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn wallet_set_output_frozen(
+    wallet: *mut TariWallet,
+    commitment: *const c_char,
+    frozen: bool,
+    error_out: *mut c_int,
+) -> bool {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+
+    if wallet.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return false;
+    }
+
+    let commitment = match commitment_from_c_char(commitment) {
+        Ok(c) => c,
+        Err(e) => {
+            error = LibWalletError::from(e).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            return false;
+        },
+    };
+
+    let mut output_manager_service = (*wallet).wallet.output_manager_service.clone();
+    match (*wallet)
+        .runtime
+        .block_on(output_manager_service.set_output_frozen(commitment, frozen))
+    {
+        Ok(()) => true,
+        Err(e) => {
+            error = LibWalletError::from(WalletError::OutputManagerError(e)).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            false
+        },
+    }
+}
+
+/// Sets the label and note of an output
+///
+/// ## Arguments
+/// `wallet` - The TariWallet pointer.
+/// `commitment` - The pointer to a char array containing the commitment's hex value (see `Commitment::to_hex()`)
+/// `label` - The pointer to a Utf8 string for the label, a null pointer clears the label
+/// `note` - The pointer to a Utf8 string for the note, a null pointer clears the note
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `bool` - Return a boolean value indicating the operation's success or failure. The error_ptr will hold the error
+/// code if there was a failure
+///
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn wallet_set_output_label(
+    wallet: *mut TariWallet,
+    commitment: *const c_char,
+    label: *const c_char,
+    note: *const c_char,
+    error_out: *mut c_int,
+) -> bool {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+
+    if wallet.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return false;
+    }
+
+    let commitment = match commitment_from_c_char(commitment) {
+        Ok(c) => c,
+        Err(e) => {
+            error = LibWalletError::from(e).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            return false;
+        },
+    };
+
+    let label = if label.is_null() {
+        None
+    } else {
+        match CStr::from_ptr(label).to_str() {
+            Ok(v) => Some(v.to_owned()),
+            _ => {
+                error = LibWalletError::from(InterfaceError::PointerError("label".to_string())).code;
+                ptr::swap(error_out, &mut error as *mut c_int);
+                return false;
+            },
+        }
+    };
+
+    let note = if note.is_null() {
+        None
+    } else {
+        match CStr::from_ptr(note).to_str() {
+            Ok(v) => Some(v.to_owned()),
+            _ => {
+                error = LibWalletError::from(InterfaceError::PointerError("note".to_string())).code;
+                ptr::swap(error_out, &mut error as *mut c_int);
+                return false;
+            },
+        }
+    };
+
+    let mut output_manager_service = (*wallet).wallet.output_manager_service.clone();
+    match (*wallet)
+        .runtime
+        .block_on(output_manager_service.set_output_label(commitment, label, note))
+    {
+        Ok(()) => true,
+        Err(e) => {
+            error = LibWalletError::from(WalletError::OutputManagerError(e)).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            false
+        },
+    }
+}
+
+unsafe fn commitment_from_c_char(commitment: *const c_char) -> Result<Commitment, InterfaceError> {
+    if commitment.is_null() {
+        return Err(InterfaceError::NullError("commitment".to_string()));
+    }
+    let hex = CStr::from_ptr(commitment)
+        .to_str()
+        .map_err(|_| InterfaceError::PointerError("commitment".to_string()))?;
+    Commitment::from_hex(hex)
+        .map_err(|e| InterfaceError::PointerError(format!("failed to convert hex to commitment: {:?}", e)))
+}
+
 /// Signs a message using the public key of the TariWallet
 ///
 /// ## Arguments
@@ -7211,6 +7743,87 @@ pub unsafe extern "C" fn wallet_send_transaction(
     }
 }
 
+/// Sends a single one-sided transaction containing an output for each of the recipients
+///
+/// ## Arguments
+/// `wallet` - The TariWallet pointer
+/// `recipients` - The TariOneSidedRecipients pointer, must contain at least one recipient
+/// `commitments` - A `TariVector` of "strings", tagged as `TariTypeTag::String`, containing commitment's hex values
+///   (see `Commitment::to_hex()`)
+/// `fee_per_gram` - The transaction fee
+/// `message` - The pointer to a char array
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `unsigned long long` - Returns 0 if unsuccessful or the TxId of the sent transaction if successful
+///
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn wallet_send_batch_one_sided_transaction(
+    wallet: *mut TariWallet,
+    recipients: *mut TariOneSidedRecipients,
+    commitments: *mut TariVector,
+    fee_per_gram: c_ulonglong,
+    message: *const c_char,
+    error_out: *mut c_int,
+) -> c_ulonglong {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if wallet.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return 0;
+    }
+    if recipients.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("recipients".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return 0;
+    }
+
+    let selection_criteria = match commitments.as_ref() {
+        None => UtxoSelectionCriteria::default(),
+        Some(cs) => match cs.to_commitment_vec() {
+            Ok(cs) => UtxoSelectionCriteria::specific(cs),
+            Err(e) => {
+                error!(target: LOG_TARGET, "failed to convert from tari vector: {:?}", e);
+                ptr::replace(error_out, LibWalletError::from(e).code as c_int);
+                return 0;
+            },
+        },
+    };
+
+    let message_string = if message.is_null() {
+        String::new()
+    } else {
+        match CStr::from_ptr(message).to_str() {
+            Ok(v) => v.to_owned(),
+            _ => {
+                error = LibWalletError::from(InterfaceError::NullError("message".to_string())).code;
+                ptr::swap(error_out, &mut error as *mut c_int);
+                return 0;
+            },
+        }
+    };
+
+    match (*wallet)
+        .runtime
+        .block_on((*wallet).wallet.transaction_service.send_batch_one_sided_transaction(
+            (*recipients).0.clone(),
+            selection_criteria,
+            MicroMinotari::from(fee_per_gram),
+            message_string,
+        )) {
+        Ok(tx_id) => tx_id.as_u64(),
+        Err(e) => {
+            error = LibWalletError::from(WalletError::TransactionServiceError(e)).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            0
+        },
+    }
+}
+
 /// Gets a fee estimate for an amount
 ///
 /// ## Arguments
@@ -7353,6 +7966,108 @@ pub unsafe extern "C" fn wallet_set_num_confirmations_required(
     }
 }
 
+/// Gets the spending policy that limits sends from the wallet as a json string
+///
+/// ## Arguments
+/// `wallet` - The TariWallet pointer
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `*mut c_char` - Returns a pointer to a char array of the json policy. Note that it returns an empty char array if
+/// wallet is null or an error occurred
+///
+/// # Safety
+/// The ```string_destroy``` method must be called when finished with a string from rust to prevent a memory leak
+#[no_mangle]
+pub unsafe extern "C" fn wallet_get_spending_policy(wallet: *mut TariWallet, error_out: *mut c_int) -> *mut c_char {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    let mut result = CString::new("").expect("Blank CString will not fail.");
+    if wallet.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return CString::into_raw(result);
+    }
+
+    match (*wallet)
+        .runtime
+        .block_on((*wallet).wallet.transaction_service.get_spending_policy())
+    {
+        Ok(policy) => match serde_json::to_string(&policy).map(CString::new) {
+            Ok(Ok(v)) => result = v,
+            _ => {
+                error = LibWalletError::from(InterfaceError::PointerError("policy".to_string())).code;
+                ptr::swap(error_out, &mut error as *mut c_int);
+            },
+        },
+        Err(e) => {
+            error = LibWalletError::from(WalletError::TransactionServiceError(e)).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+        },
+    }
+    CString::into_raw(result)
+}
+
+/// Replaces the spending policy that limits sends from the wallet. The policy is a json object with the optional
+/// fields `max_amount_per_transaction`, `daily_limit` and `large_send_threshold` in MicroMinotari,
+/// `allowed_destinations` as a list of addresses and `large_send_delay` in seconds. An empty object removes all
+/// limits. Sends that violate the policy fail with error code 213.
+///
+/// ## Arguments
+/// `wallet` - The TariWallet pointer
+/// `policy_json` - The pointer to a char array with the json policy
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `()` - Does not return a value, equivalent to void in C
+///
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn wallet_set_spending_policy(
+    wallet: *mut TariWallet,
+    policy_json: *const c_char,
+    error_out: *mut c_int,
+) {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+    if wallet.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return;
+    }
+    if policy_json.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("policy_json".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return;
+    }
+    let policy = match CStr::from_ptr(policy_json).to_str() {
+        Ok(v) => match serde_json::from_str::<SpendingPolicy>(v) {
+            Ok(policy) => policy,
+            Err(e) => {
+                error = LibWalletError::from(InterfaceError::InvalidArgument(format!("policy_json: {}", e))).code;
+                ptr::swap(error_out, &mut error as *mut c_int);
+                return;
+            },
+        },
+        _ => {
+            error = LibWalletError::from(InterfaceError::PointerError("policy_json".to_string())).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            return;
+        },
+    };
+
+    if let Err(e) = (*wallet)
+        .runtime
+        .block_on((*wallet).wallet.transaction_service.set_spending_policy(policy))
+    {
+        error = LibWalletError::from(WalletError::TransactionServiceError(e)).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+    }
+}
+
 /// Get the TariContacts from a TariWallet
 ///
 /// ## Arguments
@@ -10811,6 +11526,32 @@ mod test {
             assert_eq!(utxos.len(), 0);
             destroy_tari_vector(outputs);
 
+            // a label that cannot be represented as a C string is reported as an error instead of panicking
+            let commitment = alice_wallet_runtime
+                .block_on(test_outputs[9].commitment(key_manager))
+                .unwrap();
+            alice_wallet_runtime
+                .block_on((*alice_wallet).wallet.output_manager_service.set_output_label(
+                    commitment,
+                    Some("nul\0label".to_string()),
+                    None,
+                ))
+                .unwrap();
+            let outputs = wallet_get_utxos(
+                alice_wallet,
+                0,
+                20,
+                TariUtxoSort::ValueAsc,
+                ptr::null_mut(),
+                3000,
+                error_ptr,
+            );
+            assert!(outputs.is_null());
+            assert_eq!(
+                error,
+                LibWalletError::from(InterfaceError::InvalidArgument(String::new())).code
+            );
+
             string_destroy(network_str as *mut c_char);
             string_destroy(db_name_alice_str as *mut c_char);
             string_destroy(db_path_alice_str as *mut c_char);
@@ -11549,6 +12290,44 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_one_sided_recipients() {
+        unsafe {
+            let mut error = 0;
+            let error_ptr = &mut error as *mut c_int;
+
+            let recipients = one_sided_recipients_create();
+            assert_eq!(one_sided_recipients_get_length(recipients, error_ptr), 0);
+            assert_eq!(*error_ptr, 0, "No error expected");
+
+            let address = Box::into_raw(Box::new(TariAddress::new_dual_address(
+                PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng)),
+                PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng)),
+                Network::Esmeralda,
+                TariAddressFeatures::create_one_sided_only(),
+            )));
+            let payment_id = CString::new("invoice 1").unwrap();
+            one_sided_recipients_push(recipients, address, 1000, payment_id.as_ptr(), error_ptr);
+            assert_eq!(*error_ptr, 0, "No error expected");
+            one_sided_recipients_push(recipients, address, 2000, ptr::null(), error_ptr);
+            assert_eq!(*error_ptr, 0, "No error expected");
+            assert_eq!(one_sided_recipients_get_length(recipients, error_ptr), 2);
+            assert_eq!((*recipients).0[0].payment_id, PaymentId::Open(b"invoice 1".to_vec()));
+            assert_eq!((*recipients).0[1].payment_id, PaymentId::Empty);
+            assert_eq!((*recipients).0[1].amount, MicroMinotari::from(2000));
+
+            one_sided_recipients_push(recipients, ptr::null_mut(), 3000, ptr::null(), error_ptr);
+            assert_eq!(
+                *error_ptr,
+                LibWalletError::from(InterfaceError::NullError("destination".to_string())).code
+            );
+            assert_eq!(one_sided_recipients_get_length(recipients, error_ptr), 2);
+
+            one_sided_recipients_destroy(recipients);
+            tari_address_destroy(address);
+        }
+    }
+
     #[test]
     fn test_com_pub_sig_create() {
         unsafe {
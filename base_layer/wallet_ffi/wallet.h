@@ -166,6 +166,8 @@ struct TariCompletedTransactions;
 
 struct TariContacts;
 
+struct TariOneSidedRecipients;
+
 struct TariPendingInboundTransactions;
 
 struct TariPendingOutboundTransactions;
@@ -174,6 +176,8 @@ struct TariPublicKeys;
 
 struct TariSeedWords;
 
+struct TariSubAddresses;
+
 struct TariUnblindedOutputs;
 
 struct TariWallet;
@@ -358,6 +362,9 @@ struct TariUtxo {
   uint8_t status;
   const char *coinbase_extra;
   const char *payment_id;
+  bool frozen;
+  const char *label;
+  const char *note;
 };
 
 #ifdef __cplusplus
@@ -1735,6 +1742,150 @@ TariContact *contacts_get_at(struct TariContacts *contacts,
  */
 void contacts_destroy(struct TariContacts *contacts);
 
+/**
+ * -------------------------------------------------------------------------------------------- ///
+ * ----------------------------------- OneSidedRecipients ------------------------------------- ///
+ * Create an empty instance of TariOneSidedRecipients
+ *
+ * ## Arguments
+ * None
+ *
+ * ## Returns
+ * `*mut TariOneSidedRecipients` - Returns a pointer to an empty TariOneSidedRecipients.
+ *
+ * # Safety
+ * The `one_sided_recipients_destroy` method must be called when finished with a TariOneSidedRecipients to prevent a
+ * memory leak
+ */
+struct TariOneSidedRecipients *one_sided_recipients_create(void);
+
+/**
+ * Adds a recipient to a TariOneSidedRecipients
+ *
+ * ## Arguments
+ * `recipients` - The pointer to a TariOneSidedRecipients
+ * `destination` - The TariWalletAddress pointer of the recipient
+ * `amount` - The amount to send to the recipient
+ * `payment_id_string` - The pointer to a char array containing the payment id of this recipient's output, may be null
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `()` - Does not return a value, equivalent to void in C
+ *
+ * # Safety
+ * None
+ */
+void one_sided_recipients_push(struct TariOneSidedRecipients *recipients,
+                               TariWalletAddress *destination,
+                               unsigned long long amount,
+                               const char *payment_id_string,
+                               int *error_out);
+
+/**
+ * Gets the length of TariOneSidedRecipients
+ *
+ * ## Arguments
+ * `recipients` - The pointer to a TariOneSidedRecipients
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `c_uint` - Returns number of elements in recipients, zero if recipients is null
+ *
+ * # Safety
+ * None
+ */
+unsigned int one_sided_recipients_get_length(const struct TariOneSidedRecipients *recipients,
+                                             int *error_out);
+
+/**
+ * Frees memory for a TariOneSidedRecipients
+ *
+ * ## Arguments
+ * `recipients` - The pointer to a TariOneSidedRecipients
+ *
+ * ## Returns
+ * `()` - Does not return a value, equivalent to void in C
+ *
+ * # Safety
+ * None
+ */
+void one_sided_recipients_destroy(struct TariOneSidedRecipients *recipients);
+
+/**
+ * -------------------------------------------------------------------------------------------- ///
+ * ----------------------------------- Sub-addresses ------------------------------------------ ///
+ * Gets the length of TariSubAddresses
+ *
+ * ## Arguments
+ * `sub_addresses` - The pointer to a TariSubAddresses
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `c_uint` - Returns number of elements in sub_addresses, zero if sub_addresses is null
+ *
+ * # Safety
+ * None
+ */
+unsigned int sub_addresses_get_length(const struct TariSubAddresses *sub_addresses,
+                                      int *error_out);
+
+/**
+ * Gets the TariWalletAddress of the sub-address in TariSubAddresses at position
+ *
+ * ## Arguments
+ * `sub_addresses` - The pointer to a TariSubAddresses
+ * `position` - The integer position
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `*mut TariWalletAddress` - Returns a TariWalletAddress, note that it returns ptr::null_mut() if sub_addresses is
+ * null or position is invalid
+ *
+ * # Safety
+ * The ```tari_address_destroy``` method must be called when finished with a TariWalletAddress to prevent a memory leak
+ */
+TariWalletAddress *sub_addresses_get_address_at(struct TariSubAddresses *sub_addresses,
+                                                unsigned int position,
+                                                int *error_out);
+
+/**
+ * Gets the label of the sub-address in TariSubAddresses at position
+ *
+ * ## Arguments
+ * `sub_addresses` - The pointer to a TariSubAddresses
+ * `position` - The integer position
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `*mut c_char` - Returns a pointer to a char array. Note that it returns an empty char array if sub_addresses is
+ * null or position is invalid
+ *
+ * # Safety
+ * The ```string_destroy``` method must be called when finished with a string from rust to prevent a memory leak
+ */
+char *sub_addresses_get_label_at(struct TariSubAddresses *sub_addresses,
+                                 unsigned int position,
+                                 int *error_out);
+
+/**
+ * Frees memory for a TariSubAddresses
+ *
+ * ## Arguments
+ * `sub_addresses` - The pointer to a TariSubAddresses
+ *
+ * ## Returns
+ * `()` - Does not return a value, equivalent to void in C
+ *
+ * # Safety
+ * None
+ */
+void sub_addresses_destroy(struct TariSubAddresses *sub_addresses);
+
 /**
  * -------------------------------------------------------------------------------------------- ///
  * ----------------------------------- Contacts Liveness Data ----------------------------------///
@@ -3007,6 +3158,43 @@ char *wallet_get_last_network(TariCommsConfig *config,
 TariBalance *wallet_get_balance(struct TariWallet *wallet,
                                 int *error_out);
 
+/**
+ * Derives a new sub-address from the wallet seed. One-sided payments to the sub-address are received by this wallet
+ * and attributed to the sub-address.
+ *
+ * ## Arguments
+ * `wallet` - The TariWallet pointer.
+ * `label` - The label of the sub-address, may be null
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `*mut TariWalletAddress` - Returns the pointer to the new sub-address or null if an error occurs
+ *
+ * # Safety
+ * The ```tari_address_destroy``` method must be called when finished with a TariWalletAddress to prevent a memory leak
+ */
+TariWalletAddress *wallet_create_sub_address(struct TariWallet *wallet,
+                                             const char *label,
+                                             int *error_out);
+
+/**
+ * Retrieves the sub-addresses of a wallet
+ *
+ * ## Arguments
+ * `wallet` - The TariWallet pointer.
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `*mut TariSubAddresses` - Returns the pointer to the TariSubAddresses or null if an error occurs
+ *
+ * # Safety
+ * The ```sub_addresses_destroy``` method must be called when finished with a TariSubAddresses to prevent a memory leak
+ */
+struct TariSubAddresses *wallet_get_sub_addresses(struct TariWallet *wallet,
+                                                  int *error_out);
+
 /**
  * This function returns a list of unspent UTXO values and commitments.
  *
@@ -3164,6 +3352,52 @@ struct TariCoinPreview *wallet_preview_coin_split(struct TariWallet *wallet,
                                                   uint64_t fee_per_gram,
                                                   int32_t *error_ptr);
 
+/**
+ * Freezes or unfreezes an output. Frozen outputs are never selected as inputs for any transaction.
+ *
+ * ## Arguments
+ * `wallet` - The TariWallet pointer.
+ * `commitment` - The pointer to a char array containing the commitment's hex value (see `Commitment::to_hex()`)
+ * `frozen` - Whether the output should be frozen
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `bool` - Return a boolean value indicating the operation's success or failure. The error_ptr will hold the error
+ * code if there was a failure
+ *
+ * # Safety
+ * None
+ */
+bool wallet_set_output_frozen(struct TariWallet *wallet,
+                              const char *commitment,
+                              bool frozen,
+                              int *error_out);
+
+/**
+ * Sets the label and note of an output
+ *
+ * ## Arguments
+ * `wallet` - The TariWallet pointer.
+ * `commitment` - The pointer to a char array containing the commitment's hex value (see `Commitment::to_hex()`)
+ * `label` - The pointer to a Utf8 string for the label, a null pointer clears the label
+ * `note` - The pointer to a Utf8 string for the note, a null pointer clears the note
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `bool` - Return a boolean value indicating the operation's success or failure. The error_ptr will hold the error
+ * code if there was a failure
+ *
+ * # Safety
+ * None
+ */
+bool wallet_set_output_label(struct TariWallet *wallet,
+                             const char *commitment,
+                             const char *label,
+                             const char *note,
+                             int *error_out);
+
 /**
  * Signs a message using the public key of the TariWallet
  *
@@ -3395,6 +3629,32 @@ unsigned long long wallet_send_transaction(struct TariWallet *wallet,
                                            const char *payment_id_string,
                                            int *error_out);
 
+/**
+ * Sends a single one-sided transaction containing an output for each of the recipients
+ *
+ * ## Arguments
+ * `wallet` - The TariWallet pointer
+ * `recipients` - The TariOneSidedRecipients pointer, must contain at least one recipient
+ * `commitments` - A `TariVector` of "strings", tagged as `TariTypeTag::String`, containing commitment's hex values
+ *   (see `Commitment::to_hex()`)
+ * `fee_per_gram` - The transaction fee
+ * `message` - The pointer to a char array
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `unsigned long long` - Returns 0 if unsuccessful or the TxId of the sent transaction if successful
+ *
+ * # Safety
+ * None
+ */
+unsigned long long wallet_send_batch_one_sided_transaction(struct TariWallet *wallet,
+                                                           struct TariOneSidedRecipients *recipients,
+                                                           struct TariVector *commitments,
+                                                           unsigned long long fee_per_gram,
+                                                           const char *message,
+                                                           int *error_out);
+
 /**
  * Gets a fee estimate for an amount
  *
@@ -3459,6 +3719,46 @@ void wallet_set_num_confirmations_required(struct TariWallet *wallet,
                                            unsigned long long num,
                                            int *error_out);
 
+/**
+ * Gets the spending policy that limits sends from the wallet as a json string
+ *
+ * ## Arguments
+ * `wallet` - The TariWallet pointer
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `*mut c_char` - Returns a pointer to a char array of the json policy. Note that it returns an empty char array if
+ * wallet is null or an error occurred
+ *
+ * # Safety
+ * The ```string_destroy``` method must be called when finished with a string from rust to prevent a memory leak
+ */
+char *wallet_get_spending_policy(struct TariWallet *wallet,
+                                 int *error_out);
+
+/**
+ * Replaces the spending policy that limits sends from the wallet. The policy is a json object with the optional
+ * fields `max_amount_per_transaction`, `daily_limit` and `large_send_threshold` in MicroMinotari,
+ * `allowed_destinations` as a list of addresses and `large_send_delay` in seconds. An empty object removes all
+ * limits. Sends that violate the policy fail with error code 213.
+ *
+ * ## Arguments
+ * `wallet` - The TariWallet pointer
+ * `policy_json` - The pointer to a char array with the json policy
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `()` - Does not return a value, equivalent to void in C
+ *
+ * # Safety
+ * None
+ */
+void wallet_set_spending_policy(struct TariWallet *wallet,
+                                const char *policy_json,
+                                int *error_out);
+
 /**
  * Get the TariContacts from a TariWallet
  *
@@ -0,0 +1,17 @@
+CREATE TABLE peers (
+    id INTEGER NOT NULL PRIMARY KEY,
+    public_key BLOB NOT NULL,
+    node_id BLOB NOT NULL,
+    features BIGINT NOT NULL,
+    last_seen TIMESTAMP,
+    offline_at TIMESTAMP,
+    banned_until TIMESTAMP,
+    deleted_at TIMESTAMP,
+    peer BLOB NOT NULL
+);
+
+CREATE UNIQUE INDEX uidx_peers_public_key ON peers (public_key);
+CREATE UNIQUE INDEX uidx_peers_node_id ON peers (node_id);
+CREATE INDEX idx_peers_features_last_seen ON peers (features, last_seen);
+CREATE INDEX idx_peers_last_seen ON peers (last_seen);
+CREATE INDEX idx_peers_banned_until ON peers (banned_until);
@@ -45,7 +45,7 @@ use crate::{
     connection_manager::{ConnectionManagerConfig, ConnectionManagerRequester},
     connectivity::{ConnectivityConfig, ConnectivityRequester},
     multiaddr::Multiaddr,
-    peer_manager::{NodeIdentity, PeerManager},
+    peer_manager::{NodeIdentity, PeerDatabase, PeerManager},
     peer_validator::PeerValidatorConfig,
     protocol::{NodeNetworkInfo, ProtocolExtensions},
     tor,
@@ -120,6 +120,7 @@ use crate::{
 /// [CommsBuilder]: crate::CommsBuilder
 pub struct CommsBuilder {
     peer_storage: Option<CommsDatabase>,
+    peer_database: Option<Box<dyn PeerDatabase>>,
     peer_storage_file_lock: Option<File>,
     node_identity: Option<Arc<NodeIdentity>>,
     dial_backoff: BoxedBackoff,
@@ -134,6 +135,7 @@ impl Default for CommsBuilder {
     fn default() -> Self {
         Self {
             peer_storage: None,
+            peer_database: None,
             peer_storage_file_lock: None,
             node_identity: None,
             dial_backoff: Box::new(ConstantBackoff::new(Duration::from_millis(500))),
@@ -281,6 +283,13 @@ impl CommsBuilder {
         self
     }
 
+    /// Set the [PeerDatabase] backend to use. This takes precedence over `with_peer_storage`.
+    pub fn with_peer_database(mut self, peer_database: Box<dyn PeerDatabase>, file_lock: Option<File>) -> Self {
+        self.peer_database = Some(peer_database);
+        self.peer_storage_file_lock = file_lock;
+        self
+    }
+
     /// Set the backoff to use when a dial to a remote peer fails. This is optional. If omitted the default
     /// [ConstantBackoff](crate::backoff::ConstantBackoff) of 500ms is used.
     pub fn with_dial_backoff<T>(mut self, backoff: T) -> Self
@@ -309,6 +318,10 @@ impl CommsBuilder {
     fn make_peer_manager(&mut self) -> Result<Arc<PeerManager>, CommsBuilderError> {
         let file_lock = self.peer_storage_file_lock.take();
 
+        if let Some(peer_database) = self.peer_database.take() {
+            return Ok(Arc::new(PeerManager::with_database(peer_database, file_lock)));
+        }
+
         match self.peer_storage.take() {
             Some(storage) => {
                 #[cfg(not(test))]
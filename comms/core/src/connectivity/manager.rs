@@ -53,7 +53,7 @@ use crate::{
         ConnectionManagerEvent,
         ConnectionManagerRequester,
     },
-    peer_manager::NodeId,
+    peer_manager::{NodeId, ReputationEvent},
     utils::datetime::format_duration,
     Minimized,
     NodeIdentity,
@@ -272,6 +272,18 @@ impl ConnectivityManagerActor {
                     // we banned the peer
                 }
             },
+            RecordReputationEvent(node_id, event) => {
+                match self.peer_manager.record_reputation_event(&node_id, event).await {
+                    Ok(score) => trace!(
+                        target: LOG_TARGET,
+                        "Recorded reputation event {} for peer {}. New score: {}", event, node_id, score
+                    ),
+                    Err(err) => debug!(
+                        target: LOG_TARGET,
+                        "Failed to record reputation event {} for peer {}: {}", event, node_id, err
+                    ),
+                }
+            },
             AddPeerToAllowList(node_id) => {
                 if !self.allow_list.contains(&node_id) {
                     self.allow_list.push(node_id.clone());
@@ -531,7 +543,18 @@ impl ConnectivityManagerActor {
             self.pool.count_connected_nodes()
         );
 
-        let conns = selection.select(&self.pool);
+        let reputations = if selection.requires_reputation() {
+            let node_ids = self
+                .pool
+                .filter_connection_states(|s| s.is_connected())
+                .into_iter()
+                .map(|conn| conn.peer_node_id().clone())
+                .collect::<Vec<_>>();
+            self.peer_manager.get_reputation_scores(&node_ids).await?
+        } else {
+            HashMap::new()
+        };
+        let conns = selection.select(&self.pool, &reputations);
         debug!(target: LOG_TARGET, "Selected {} connections(s)", conns.len());
 
         Ok(conns.into_iter().cloned().collect())
@@ -956,6 +979,9 @@ impl ConnectivityManagerActor {
         );
 
         self.peer_manager.ban_peer_by_node_id(node_id, duration, reason).await?;
+        self.peer_manager
+            .record_reputation_event(node_id, ReputationEvent::ProtocolViolation)
+            .await?;
 
         #[cfg(feature = "metrics")]
         super::metrics::banned_peers_counter(node_id).inc();
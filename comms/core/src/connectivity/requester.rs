@@ -40,7 +40,7 @@ use super::{
 };
 use crate::{
     connection_manager::ConnectionManagerError,
-    peer_manager::{NodeId, Peer},
+    peer_manager::{NodeId, Peer, ReputationEvent},
     Minimized,
     NodeIdentity,
     PeerConnection,
@@ -101,6 +101,7 @@ pub enum ConnectivityRequest {
     GetMinimizeConnectionsThreshold(oneshot::Sender<Option<usize>>),
     GetActiveConnections(oneshot::Sender<Vec<PeerConnection>>),
     BanPeer(NodeId, Duration, String),
+    RecordReputationEvent(NodeId, ReputationEvent),
     AddPeerToAllowList(NodeId),
     RemovePeerFromAllowList(NodeId),
     GetAllowList(oneshot::Sender<Vec<NodeId>>),
@@ -278,6 +279,19 @@ impl ConnectivityRequester {
             .await
     }
 
+    /// Records a reputation event for the peer. Peers with a better reputation are preferred when selecting peers.
+    pub async fn record_reputation_event(
+        &mut self,
+        node_id: NodeId,
+        event: ReputationEvent,
+    ) -> Result<(), ConnectivityError> {
+        self.sender
+            .send(ConnectivityRequest::RecordReputationEvent(node_id, event))
+            .await
+            .map_err(|_| ConnectivityError::ActorDisconnected)?;
+        Ok(())
+    }
+
     /// Adds a peer to an allow list, preventing it from being banned.
     pub async fn add_peer_to_allow_list(&mut self, node_id: NodeId) -> Result<(), ConnectivityError> {
         self.sender
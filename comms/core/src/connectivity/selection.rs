@@ -20,7 +20,7 @@
 //  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::{fmt, fmt::Display};
+use std::{collections::HashMap, fmt, fmt::Display};
 
 use rand::{rngs::OsRng, seq::SliceRandom};
 
@@ -60,7 +60,7 @@ impl ConnectivitySelection {
     }
 
     /// Returns a query that will return `n` connections for peers with `PeerFeatures::COMMUNICATION_NODES` excluding
-    /// the given [NodeId]s.
+    /// the given [NodeId]s. Peers with a poor reputation are only selected if there are not enough other peers.
     ///
     /// [NodeId](crate::peer_manager::NodeId)
     pub fn random_nodes(n: usize, exclude: Vec<NodeId>) -> Self {
@@ -80,12 +80,18 @@ impl ConnectivitySelection {
         }
     }
 
-    /// Select peers from the pool according to the ConnectivitySelection
-    pub fn select<'a>(&self, pool: &'a ConnectionPool) -> Vec<&'a PeerConnection> {
+    /// Returns true if this selection takes peer reputation into account
+    pub fn requires_reputation(&self) -> bool {
+        matches!(self.selection_mode, SelectionMode::RandomNodes(_))
+    }
+
+    /// Select peers from the pool according to the ConnectivitySelection. Peers that do not have an entry in
+    /// `reputations` are considered to have a neutral reputation.
+    pub fn select<'a>(&self, pool: &'a ConnectionPool, reputations: &HashMap<NodeId, i64>) -> Vec<&'a PeerConnection> {
         use SelectionMode::{AllNodes, ClosestTo, RandomNodes};
         match &self.selection_mode {
             AllNodes => select_connected_nodes(pool, &self.excluded_peers),
-            RandomNodes(n) => select_random_nodes(pool, *n, &self.excluded_peers),
+            RandomNodes(n) => select_random_nodes(pool, *n, &self.excluded_peers, reputations),
             ClosestTo(dest_node_id, n) => {
                 let mut connections = select_closest(pool, dest_node_id, &self.excluded_peers);
                 connections.truncate(*n);
@@ -119,9 +125,24 @@ fn select_closest<'a>(pool: &'a ConnectionPool, node_id: &NodeId, exclude: &[Nod
     nodes
 }
 
-fn select_random_nodes<'a>(pool: &'a ConnectionPool, n: usize, exclude: &[NodeId]) -> Vec<&'a PeerConnection> {
-    let nodes = select_connected_nodes(pool, exclude);
-    nodes.choose_multiple(&mut OsRng, n).copied().collect()
+fn select_random_nodes<'a>(
+    pool: &'a ConnectionPool,
+    n: usize,
+    exclude: &[NodeId],
+    reputations: &HashMap<NodeId, i64>,
+) -> Vec<&'a PeerConnection> {
+    let (poor, good): (Vec<_>, Vec<_>) = select_connected_nodes(pool, exclude)
+        .into_iter()
+        .partition(|conn| reputation_of(reputations, conn) < 0);
+    let mut nodes = good.choose_multiple(&mut OsRng, n).copied().collect::<Vec<_>>();
+    if nodes.len() < n {
+        nodes.extend(poor.choose_multiple(&mut OsRng, n - nodes.len()).copied());
+    }
+    nodes
+}
+
+fn reputation_of(reputations: &HashMap<NodeId, i64>, conn: &PeerConnection) -> i64 {
+    reputations.get(conn.peer_node_id()).copied().unwrap_or(0)
 }
 
 impl Display for ConnectivitySelection {
@@ -175,15 +196,40 @@ mod test {
     #[test]
     fn select_random() {
         let (pool, _receivers) = create_pool_with_connections(10);
-        let conns = select_random_nodes(&pool, 500, &[]);
+        let conns = select_random_nodes(&pool, 500, &[], &HashMap::new());
         assert_eq!(conns.len(), 10);
 
         let first_node = conns.first().unwrap().peer_node_id().clone();
-        let conns = select_random_nodes(&pool, 10, &[first_node.clone()]);
+        let conns = select_random_nodes(&pool, 10, &[first_node.clone()], &HashMap::new());
         assert_eq!(conns.len(), 9);
         assert!(conns.iter().all(|c| c.peer_node_id() != &first_node));
     }
 
+    #[test]
+    fn select_random_avoids_poor_reputation() {
+        let (pool, _receivers) = create_pool_with_connections(10);
+        let reputations = select_connected_nodes(&pool, &[])
+            .iter()
+            .take(5)
+            .map(|c| (c.peer_node_id().clone(), -10))
+            .collect::<HashMap<_, _>>();
+
+        let conns = select_random_nodes(&pool, 5, &[], &reputations);
+        assert_eq!(conns.len(), 5);
+        assert!(conns.iter().all(|c| !reputations.contains_key(c.peer_node_id())));
+
+        // Poor peers are used if there are not enough other peers
+        let conns = select_random_nodes(&pool, 8, &[], &reputations);
+        assert_eq!(conns.len(), 8);
+        assert_eq!(
+            conns
+                .iter()
+                .filter(|c| reputations.contains_key(c.peer_node_id()))
+                .count(),
+            3
+        );
+    }
+
     #[test]
     fn select_closest_ordering() {
         let (pool, _receivers) = create_pool_with_connections(10);
@@ -55,6 +55,8 @@ pub enum PeerManagerError {
     AddressNotFoundError { address: Multiaddr, node_id: NodeId },
     #[error("Protocol error: {0}")]
     ProtocolError(String),
+    #[error("Peer database error: {0}")]
+    PeerDatabaseError(String),
 }
 
 impl PeerManagerError {
@@ -69,3 +71,10 @@ impl<T> From<PoisonError<T>> for PeerManagerError {
         PeerManagerError::DatabaseError(KeyValStoreError::PoisonedAccess)
     }
 }
+
+#[cfg(feature = "sqlite")]
+impl From<diesel::result::Error> for PeerManagerError {
+    fn from(err: diesel::result::Error) -> Self {
+        PeerManagerError::PeerDatabaseError(err.to_string())
+    }
+}
@@ -20,7 +20,7 @@
 //  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::{fmt, fs::File, time::Duration};
+use std::{collections::HashMap, fmt, fs::File, time::Duration};
 
 use multiaddr::Multiaddr;
 use tari_storage::{lmdb_store::LMDBDatabase, CachedStore, IterationResult};
@@ -38,9 +38,11 @@ use crate::{
         wrapper::KeyValueWrapper,
         NodeDistance,
         NodeId,
+        PeerDatabase,
         PeerFeatures,
         PeerManagerError,
         PeerQuery,
+        ReputationEvent,
     },
     types::{CommsDatabase, CommsPublicKey},
 };
@@ -48,19 +50,24 @@ use crate::{
 /// The PeerManager consist of a routing table of previously discovered peers.
 /// It also provides functionality to add, find and delete peers.
 pub struct PeerManager {
-    // yo dawg, I heard you like wrappers, so I wrapped your wrapper in a wrapper so you can wrap while you wrap
-    peer_storage: RwLock<PeerStorage<CachedStore<PeerId, Peer, KeyValueWrapper<CommsDatabase>>>>,
+    peer_storage: RwLock<Box<dyn PeerDatabase>>,
     _file_lock: Option<File>,
 }
 
 impl PeerManager {
-    /// Constructs a new empty PeerManager
+    /// Constructs a new PeerManager backed by the given key-value (LMDB) database
     pub fn new(database: CommsDatabase, file_lock: Option<File>) -> Result<PeerManager, PeerManagerError> {
+        // yo dawg, I heard you like wrappers, so I wrapped your wrapper in a wrapper so you can wrap while you wrap
         let storage = PeerStorage::new_indexed(CachedStore::new(KeyValueWrapper::new(database)))?;
-        Ok(Self {
-            peer_storage: RwLock::new(storage),
+        Ok(Self::with_database(Box::new(storage), file_lock))
+    }
+
+    /// Constructs a new PeerManager using the given [PeerDatabase] backend
+    pub fn with_database(database: Box<dyn PeerDatabase>, file_lock: Option<File>) -> PeerManager {
+        Self {
+            peer_storage: RwLock::new(database),
             _file_lock: file_lock,
-        })
+        }
     }
 
     /// Migrate the peer database, this only applies to the LMDB database
@@ -238,9 +245,9 @@ impl PeerManager {
         self.peer_storage.read().await.flood_peers()
     }
 
-    pub async fn for_each<F>(&self, f: F) -> Result<(), PeerManagerError>
+    pub async fn for_each<F>(&self, mut f: F) -> Result<(), PeerManagerError>
     where F: FnMut(Peer) -> IterationResult {
-        self.peer_storage.read().await.for_each(f)
+        self.peer_storage.read().await.for_each(&mut f)
     }
 
     /// Fetch n nearest neighbours. If features are supplied, the function will return the closest peers matching that
@@ -320,11 +327,25 @@ impl PeerManager {
         self.peer_storage.read().await.is_peer_banned(node_id)
     }
 
+    /// Records a reputation event for the peer, returning the peer's new reputation score
+    pub async fn record_reputation_event(
+        &self,
+        node_id: &NodeId,
+        event: ReputationEvent,
+    ) -> Result<i64, PeerManagerError> {
+        self.peer_storage.write().await.record_reputation_event(node_id, event)
+    }
+
+    /// Returns the current reputation scores for the given peers. Peers that are not known are omitted.
+    pub async fn get_reputation_scores(&self, node_ids: &[NodeId]) -> Result<HashMap<NodeId, i64>, PeerManagerError> {
+        self.peer_storage.read().await.get_reputation_scores(node_ids)
+    }
+
     pub async fn update_each<F>(&self, mut f: F) -> Result<usize, PeerManagerError>
     where F: FnMut(Peer) -> Option<Peer> {
         let mut lock = self.peer_storage.write().await;
         let mut peers_to_update = Vec::new();
-        lock.for_each(|peer| {
+        lock.for_each(&mut |peer| {
             if let Some(peer) = (f)(peer) {
                 peers_to_update.push(peer);
             }
@@ -0,0 +1,149 @@
+//  Copyright 2020, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::collections::HashMap;
+
+use chrono::NaiveDateTime;
+use log::*;
+use serde::{Deserialize, Serialize};
+use tari_storage::{
+    lmdb_store::{LMDBDatabase, LMDBError},
+    IterationResult,
+};
+use tari_utilities::hex::serialize_to_hex;
+
+use crate::{
+    net_address::MultiaddressesWithStats,
+    peer_manager::{
+        migrations::MIGRATION_VERSION_KEY,
+        node_id::{deserialize_node_id_from_hex, NodeId},
+        Peer,
+        PeerFeatures,
+        PeerFlags,
+        PeerId,
+        PeerReputation,
+    },
+    protocol::ProtocolId,
+    types::CommsPublicKey,
+};
+
+const LOG_TARGET: &str = "comms::peer_manager::migrations::v8";
+
+/// Adds the `reputation` field to peers
+pub struct Migration;
+
+impl super::Migration<LMDBDatabase> for Migration {
+    type Error = LMDBError;
+
+    fn get_version(&self) -> u32 {
+        8
+    }
+
+    fn migrate(&self, db: &LMDBDatabase) -> Result<(), Self::Error> {
+        // The version key is not a peer and cannot be deserialized as one. It is set again once this migration
+        // completes.
+        db.remove(&MIGRATION_VERSION_KEY)?;
+
+        let mut peers = Vec::new();
+        db.for_each::<PeerId, PeerV7, _>(|old_peer| {
+            match old_peer {
+                Ok((key, peer)) => peers.push((key, peer)),
+                Err(err) => {
+                    error!(
+                        target: LOG_TARGET,
+                        "Failed to deserialize peer: {} ** Database may be corrupt **", err
+                    );
+                },
+            }
+            IterationResult::Continue
+        })?;
+
+        debug!(target: LOG_TARGET, "Migrating {} peer(s)", peers.len());
+        for (key, peer) in peers {
+            db.insert(&key, &Peer::from(peer))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The peer as persisted prior to v8
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub(in crate::peer_manager) struct PeerV7 {
+    id: Option<PeerId>,
+    public_key: CommsPublicKey,
+    #[serde(serialize_with = "serialize_to_hex")]
+    #[serde(deserialize_with = "deserialize_node_id_from_hex")]
+    node_id: NodeId,
+    addresses: MultiaddressesWithStats,
+    flags: PeerFlags,
+    banned_until: Option<NaiveDateTime>,
+    banned_reason: String,
+    features: PeerFeatures,
+    supported_protocols: Vec<ProtocolId>,
+    added_at: NaiveDateTime,
+    user_agent: String,
+    metadata: HashMap<u8, Vec<u8>>,
+    deleted_at: Option<NaiveDateTime>,
+}
+
+impl From<PeerV7> for Peer {
+    fn from(peer: PeerV7) -> Self {
+        Peer {
+            id: peer.id,
+            public_key: peer.public_key,
+            node_id: peer.node_id,
+            addresses: peer.addresses,
+            flags: peer.flags,
+            banned_until: peer.banned_until,
+            banned_reason: peer.banned_reason,
+            features: peer.features,
+            supported_protocols: peer.supported_protocols,
+            added_at: peer.added_at,
+            user_agent: peer.user_agent,
+            metadata: peer.metadata,
+            deleted_at: peer.deleted_at,
+            reputation: PeerReputation::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+impl From<Peer> for PeerV7 {
+    fn from(peer: Peer) -> Self {
+        PeerV7 {
+            id: peer.id,
+            public_key: peer.public_key,
+            node_id: peer.node_id,
+            addresses: peer.addresses,
+            flags: peer.flags,
+            banned_until: peer.banned_until,
+            banned_reason: peer.banned_reason,
+            features: peer.features,
+            supported_protocols: peer.supported_protocols,
+            added_at: peer.added_at,
+            user_agent: peer.user_agent,
+            metadata: peer.metadata,
+            deleted_at: peer.deleted_at,
+        }
+    }
+}
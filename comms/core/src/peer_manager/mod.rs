@@ -99,12 +99,23 @@ pub(crate) use peer_id::PeerId;
 mod manager;
 pub use manager::PeerManager;
 
+mod peer_reputation;
+pub use peer_reputation::{PeerReputation, ReputationEvent, MAX_REPUTATION_SCORE, REPUTATION_HALF_LIFE};
+
 mod peer_query;
 pub use peer_query::{PeerQuery, PeerQuerySortBy};
 
+mod peer_database;
+pub use peer_database::PeerDatabase;
+
 mod peer_storage;
 pub use peer_storage::PeerStorage;
 
+#[cfg(feature = "sqlite")]
+mod sqlite;
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqlitePeerDatabase;
+
 mod peer_identity_claim;
 pub use peer_identity_claim::PeerIdentityClaim;
 
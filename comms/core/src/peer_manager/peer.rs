@@ -38,6 +38,7 @@ use tari_utilities::hex::serialize_to_hex;
 use super::{
     node_id::{deserialize_node_id_from_hex, NodeId},
     peer_id::PeerId,
+    peer_reputation::{PeerReputation, ReputationEvent},
     PeerFeatures,
 };
 use crate::{
@@ -89,6 +90,8 @@ pub struct Peer {
     pub metadata: HashMap<u8, Vec<u8>>,
     /// If this peer has been deleted.
     pub deleted_at: Option<NaiveDateTime>,
+    /// The accumulated reputation of this peer
+    pub reputation: PeerReputation,
 }
 
 impl Peer {
@@ -116,6 +119,7 @@ impl Peer {
             user_agent,
             metadata: HashMap::new(),
             deleted_at: None,
+            reputation: PeerReputation::default(),
         }
     }
 
@@ -148,6 +152,9 @@ impl Peer {
         if !other.user_agent.is_empty() {
             self.user_agent = other.user_agent.clone();
         }
+        if other.reputation.last_updated() > self.reputation.last_updated() {
+            self.reputation = other.reputation;
+        }
     }
 
     pub fn is_persisted(&self) -> bool {
@@ -246,6 +253,16 @@ impl Peer {
         self.banned_until.as_ref().filter(|dt| *dt > &Utc::now().naive_utc())
     }
 
+    /// Returns the current (decayed) reputation score of the peer
+    pub fn reputation_score(&self) -> i64 {
+        self.reputation.score()
+    }
+
+    /// Records a reputation event for this peer, returning the new reputation score
+    pub fn record_reputation_event(&mut self, event: ReputationEvent) -> i64 {
+        self.reputation.record(event)
+    }
+
     /// This will store metadata inside of the metadata field in the peer.
     /// It will return None if the value was empty and the old value if the value was updated
     pub fn set_metadata(&mut self, key: u8, data: Vec<u8>) -> Option<Vec<u8>> {
@@ -300,6 +317,11 @@ impl Display for Peer {
                 s.push(format!("Offline since: {}", format_local_datetime(&offline_at)));
             }
 
+            let score = self.reputation_score();
+            if score != 0 {
+                s.push(format!("Reputation: {}", score));
+            }
+
             if let Some(dt) = self.banned_until() {
                 if is_max_datetime(dt) {
                     s.push("Banned permanently".to_string());
@@ -382,6 +404,34 @@ mod test {
         assert!(!peer.is_banned());
     }
 
+    #[test]
+    fn merge_keeps_latest_reputation() {
+        let mut rng = rand::rngs::OsRng;
+        let (_sk, pk) = RistrettoPublicKey::random_keypair(&mut rng);
+        let node_id = NodeId::from_key(&pk);
+        let mut peer = Peer::new(
+            pk,
+            node_id,
+            MultiaddressesWithStats::default(),
+            PeerFlags::default(),
+            PeerFeatures::COMMUNICATION_NODE,
+            Default::default(),
+            Default::default(),
+        );
+        let fresh = peer.clone();
+        peer.record_reputation_event(ReputationEvent::ValidBlockRelayed);
+        assert_eq!(peer.reputation_score(), 5);
+
+        // A peer without reputation information does not overwrite the existing reputation
+        peer.merge(&fresh);
+        assert_eq!(peer.reputation_score(), 5);
+
+        let mut updated = peer.clone();
+        updated.record_reputation_event(ReputationEvent::InvalidTransaction);
+        peer.merge(&updated);
+        assert_eq!(peer.reputation_score(), -15);
+    }
+
     #[test]
     fn json_ser_der() {
         let expected_pk_hex = "02622ace8f7303a31cafc63f8fc48fdc16e1c8c8d234b2f0d6685282a9076031";
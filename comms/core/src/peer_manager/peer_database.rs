@@ -0,0 +1,194 @@
+//  Copyright 2024, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{collections::HashMap, time::Duration};
+
+use tari_storage::IterationResult;
+
+use crate::{
+    peer_manager::{
+        peer_id::PeerId,
+        NodeDistance,
+        NodeId,
+        Peer,
+        PeerFeatures,
+        PeerManagerError,
+        PeerQuery,
+        ReputationEvent,
+    },
+    types::CommsPublicKey,
+};
+
+/// The storage backend used by the [PeerManager](super::PeerManager).
+///
+/// The default implementation is [PeerStorage](super::PeerStorage) which keeps in-memory indexes over a key-value
+/// store (LMDB). With the `sqlite` feature, [SqlitePeerDatabase](super::SqlitePeerDatabase) stores peers in an indexed
+/// SQLite table which avoids scanning the full peer list for common queries.
+pub trait PeerDatabase: Send + Sync {
+    /// Returns the number of peers in the database
+    fn count(&self) -> usize;
+
+    /// Adds a peer if it does not already exist. When a peer already exists, the stored version is merged with the
+    /// newly provided peer.
+    fn add_peer(&mut self, peer: Peer) -> Result<PeerId, PeerManagerError>;
+
+    /// Marks the peer with the given node id as deleted
+    fn delete_peer(&mut self, node_id: &NodeId) -> Result<(), PeerManagerError>;
+
+    /// Find the peer with the provided NodeID
+    fn find_by_node_id(&self, node_id: &NodeId) -> Result<Option<Peer>, PeerManagerError>;
+
+    /// Find the peer with the provided PublicKey
+    fn find_by_public_key(&self, public_key: &CommsPublicKey) -> Result<Option<Peer>, PeerManagerError>;
+
+    /// Find all peers whose node id starts with the given bytes
+    fn find_all_starts_with(&self, partial: &[u8]) -> Result<Vec<Peer>, PeerManagerError>;
+
+    /// Check if a peer exist using the specified public_key
+    fn exists(&self, public_key: &CommsPublicKey) -> bool;
+
+    /// Check if a peer exist using the specified node_id
+    fn exists_node_id(&self, node_id: &NodeId) -> bool;
+
+    /// Returns the peer corresponding to the provided NodeId if it is not banned
+    fn direct_identity_node_id(&self, node_id: &NodeId) -> Result<Peer, PeerManagerError> {
+        let peer = self
+            .find_by_node_id(node_id)?
+            .ok_or(PeerManagerError::PeerNotFoundError)?;
+
+        if peer.is_banned() {
+            Err(PeerManagerError::BannedPeer)
+        } else {
+            Ok(peer)
+        }
+    }
+
+    /// Returns the peer corresponding to the provided public key if it is not banned
+    fn direct_identity_public_key(&self, public_key: &CommsPublicKey) -> Result<Peer, PeerManagerError> {
+        let peer = self
+            .find_by_public_key(public_key)?
+            .ok_or(PeerManagerError::PeerNotFoundError)?;
+
+        if peer.is_banned() {
+            Err(PeerManagerError::BannedPeer)
+        } else {
+            Ok(peer)
+        }
+    }
+
+    /// Perform an ad-hoc query on the peer database.
+    fn perform_query(&self, query: PeerQuery<'_>) -> Result<Vec<Peer>, PeerManagerError>;
+
+    /// Return all peers
+    fn all(&self) -> Result<Vec<Peer>, PeerManagerError>;
+
+    /// Return up to `n` active peers for syncing that are not banned, not deleted and have been seen within the last
+    /// week. If `n` is zero, a default maximum is used.
+    fn discovery_syncing(
+        &self,
+        n: usize,
+        excluded_peers: &[NodeId],
+        features: Option<PeerFeatures>,
+    ) -> Result<Vec<Peer>, PeerManagerError>;
+
+    /// Return a bounded list of peers that are not banned
+    fn flood_peers(&self) -> Result<Vec<Peer>, PeerManagerError>;
+
+    /// Call `f` for each peer in the database until `IterationResult::Break` is returned
+    fn for_each(&self, f: &mut dyn FnMut(Peer) -> IterationResult) -> Result<(), PeerManagerError>;
+
+    /// Return the `n` active peers closest to `node_id`
+    fn closest_peers(
+        &self,
+        node_id: &NodeId,
+        n: usize,
+        excluded_peers: &[NodeId],
+        features: Option<PeerFeatures>,
+    ) -> Result<Vec<Peer>, PeerManagerError>;
+
+    /// Return a random list of communication node peers of size _n_ that are not banned or offline
+    fn random_peers(&self, n: usize, exclude_peers: &[NodeId]) -> Result<Vec<Peer>, PeerManagerError>;
+
+    /// Check if a specific node_id is in the network region of the N nearest neighbours of the region specified by
+    /// region_node_id. If there are less than N known peers, this will _always_ return true
+    fn in_network_region(&self, node_id: &NodeId, region_node_id: &NodeId, n: usize) -> Result<bool, PeerManagerError> {
+        let region_node_distance = region_node_id.distance(node_id);
+        let node_threshold = self.calc_region_threshold(region_node_id, n, PeerFeatures::COMMUNICATION_NODE)?;
+        // Is node ID in the base node threshold?
+        if region_node_distance <= node_threshold {
+            return Ok(true);
+        }
+        let client_threshold = self.calc_region_threshold(region_node_id, n, PeerFeatures::COMMUNICATION_CLIENT)?;
+        // Is node ID in the base client threshold?
+        Ok(region_node_distance <= client_threshold)
+    }
+
+    /// Returns the distance of the n-th closest online, unbanned peer with the given features, or the maximum distance
+    /// if there are fewer than `n` such peers
+    fn calc_region_threshold(
+        &self,
+        region_node_id: &NodeId,
+        n: usize,
+        features: PeerFeatures,
+    ) -> Result<NodeDistance, PeerManagerError>;
+
+    /// Unban the peer
+    fn unban_peer(&mut self, node_id: &NodeId) -> Result<(), PeerManagerError>;
+
+    /// Ban the peer for the given duration
+    fn ban_peer(
+        &mut self,
+        public_key: &CommsPublicKey,
+        duration: Duration,
+        reason: String,
+    ) -> Result<NodeId, PeerManagerError>;
+
+    /// Ban the peer for the given duration
+    fn ban_peer_by_node_id(
+        &mut self,
+        node_id: &NodeId,
+        duration: Duration,
+        reason: String,
+    ) -> Result<NodeId, PeerManagerError>;
+
+    /// Returns true if the peer is currently banned
+    fn is_peer_banned(&self, node_id: &NodeId) -> Result<bool, PeerManagerError> {
+        let peer = self
+            .find_by_node_id(node_id)?
+            .ok_or(PeerManagerError::PeerNotFoundError)?;
+        Ok(peer.is_banned())
+    }
+
+    /// Records a reputation event for the peer with the given node id, returning the new reputation score
+    fn record_reputation_event(&mut self, node_id: &NodeId, event: ReputationEvent) -> Result<i64, PeerManagerError>;
+
+    /// Returns the current reputation scores of the given peers. Unknown peers are omitted.
+    fn get_reputation_scores(&self, node_ids: &[NodeId]) -> Result<HashMap<NodeId, i64>, PeerManagerError>;
+
+    /// Stores metadata for the peer, returning the previous value if one was set
+    fn set_peer_metadata(
+        &mut self,
+        node_id: &NodeId,
+        key: u8,
+        data: Vec<u8>,
+    ) -> Result<Option<Vec<u8>>, PeerManagerError>;
+}
@@ -82,16 +82,49 @@ impl<'a> PeerQuery<'a> {
         PeerQueryExecutor::new(self, store)
     }
 
+    /// Returns the result limit of this query, if any
+    #[cfg_attr(not(feature = "sqlite"), allow(dead_code))]
+    pub(super) fn get_limit(&self) -> Option<usize> {
+        self.limit
+    }
+
+    /// Returns the sort criteria of this query
+    #[cfg_attr(not(feature = "sqlite"), allow(dead_code))]
+    pub(super) fn get_sort_by(&self) -> &PeerQuerySortBy<'a> {
+        &self.sort_by
+    }
+
+    /// Sorts the given peers, which have already been selected by this query, and applies the limit. This is used by
+    /// backends that select peers themselves rather than through a `PeerQueryExecutor`.
+    #[cfg_attr(not(feature = "sqlite"), allow(dead_code))]
+    pub(super) fn sort_and_limit(&self, mut selected_peers: Vec<Peer>) -> Vec<Peer> {
+        match self.sort_by {
+            PeerQuerySortBy::None => {},
+            PeerQuerySortBy::DistanceFrom(node_id) | PeerQuerySortBy::DistanceFromLastConnected(node_id) => {
+                selected_peers.sort_by(|a, b| a.node_id.distance(node_id).cmp(&b.node_id.distance(node_id)));
+            },
+            PeerQuerySortBy::LastConnected => selected_peers.sort_by(last_seen_compare_desc),
+        }
+        if let Some(limit) = self.limit {
+            selected_peers.truncate(limit);
+        }
+        if let PeerQuerySortBy::DistanceFromLastConnected(_) = self.sort_by {
+            selected_peers.sort_by(last_seen_compare_desc);
+        }
+
+        selected_peers
+    }
+
     /// Returns true if the given limit is within the specified limit. If the limit
     /// was not specified, this always returns true
-    fn within_limit(&self, limit: usize) -> bool {
+    pub(super) fn within_limit(&self, limit: usize) -> bool {
         self.limit.map(|inner_limit| inner_limit > limit).unwrap_or(true)
     }
 
     /// Returns true if the specified select predicate returns true. If the
     /// select predicate was not specified, this always returns true.
     #[allow(clippy::wrong_self_convention)]
-    fn is_selected(&mut self, peer: &Peer) -> bool {
+    pub(super) fn is_selected(&mut self, peer: &Peer) -> bool {
         self.select_predicate
             .as_mut()
             .map(|predicate| (predicate)(peer))
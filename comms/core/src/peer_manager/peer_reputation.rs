@@ -0,0 +1,182 @@
+//  Copyright 2024, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{fmt, time::Duration};
+
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Scores decay exponentially towards zero with this half-life so that old behaviour is eventually forgiven (or
+/// forgotten).
+pub const REPUTATION_HALF_LIFE: Duration = Duration::from_secs(24 * 60 * 60);
+/// The maximum absolute value of a reputation score
+pub const MAX_REPUTATION_SCORE: i64 = 1000;
+
+/// Events that affect the reputation of a peer. Each event has a weight that is added to the peer's score.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReputationEvent {
+    /// The peer relayed a block that was valid and added to our chain
+    ValidBlockRelayed,
+    /// The peer relayed a transaction that was accepted into the mempool
+    ValidTransactionRelayed,
+    /// A sync session with the peer completed successfully
+    SuccessfulSync,
+    /// An RPC request to the peer was slow or exceeded the maximum latency
+    SlowRpc,
+    /// The peer relayed a transaction that failed consensus validation
+    InvalidTransaction,
+    /// The peer relayed or served an invalid block
+    InvalidBlock,
+    /// The peer violated a protocol, typically resulting in a ban
+    ProtocolViolation,
+}
+
+impl ReputationEvent {
+    /// The amount by which the peer's reputation score changes when this event is recorded
+    pub fn weight(self) -> i64 {
+        #[allow(clippy::enum_glob_use)]
+        use ReputationEvent::*;
+        match self {
+            ValidBlockRelayed => 5,
+            ValidTransactionRelayed => 1,
+            SuccessfulSync => 10,
+            SlowRpc => -5,
+            InvalidTransaction => -20,
+            InvalidBlock => -50,
+            ProtocolViolation => -50,
+        }
+    }
+}
+
+impl fmt::Display for ReputationEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}({:+})", self, self.weight())
+    }
+}
+
+/// The accumulated reputation of a peer. The score is decayed lazily i.e. the stored score is the score at
+/// `last_updated` and the current score is calculated on read.
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct PeerReputation {
+    score: i64,
+    last_updated: Option<NaiveDateTime>,
+}
+
+impl PeerReputation {
+    /// Returns the current decayed reputation score. Positive scores indicate a well-behaved peer, negative scores
+    /// indicate a misbehaving peer and zero means that the peer is unknown or has been forgotten.
+    pub fn score(&self) -> i64 {
+        self.score_at(Utc::now().naive_utc())
+    }
+
+    /// Returns the decayed reputation score at the given time
+    // The decayed score magnitude is always less than or equal to the stored score so the cast cannot truncate
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    pub fn score_at(&self, now: NaiveDateTime) -> i64 {
+        let last_updated = match self.last_updated {
+            Some(dt) => dt,
+            None => return self.score,
+        };
+        let elapsed = now.signed_duration_since(last_updated).num_seconds();
+        if elapsed <= 0 || self.score == 0 {
+            return self.score;
+        }
+        let factor = 0.5f64.powf(elapsed as f64 / REPUTATION_HALF_LIFE.as_secs_f64());
+        (self.score as f64 * factor).trunc() as i64
+    }
+
+    /// Returns the time that the reputation was last updated, or None if no events have been recorded
+    pub fn last_updated(&self) -> Option<NaiveDateTime> {
+        self.last_updated
+    }
+
+    /// Records a reputation event, returning the new score
+    pub fn record(&mut self, event: ReputationEvent) -> i64 {
+        self.record_at(event, Utc::now().naive_utc())
+    }
+
+    /// Records a reputation event at the given time, returning the new score
+    pub fn record_at(&mut self, event: ReputationEvent, now: NaiveDateTime) -> i64 {
+        self.score = self
+            .score_at(now)
+            .saturating_add(event.weight())
+            .clamp(-MAX_REPUTATION_SCORE, MAX_REPUTATION_SCORE);
+        self.last_updated = Some(now);
+        self.score
+    }
+}
+
+impl fmt::Display for PeerReputation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.score())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::Duration as ChronoDuration;
+
+    use super::*;
+
+    #[test]
+    fn it_accumulates_weighted_events() {
+        let now = Utc::now().naive_utc();
+        let mut reputation = PeerReputation::default();
+        assert_eq!(reputation.score_at(now), 0);
+        reputation.record_at(ReputationEvent::ValidBlockRelayed, now);
+        reputation.record_at(ReputationEvent::ValidBlockRelayed, now);
+        assert_eq!(reputation.score_at(now), 10);
+        reputation.record_at(ReputationEvent::InvalidTransaction, now);
+        assert_eq!(reputation.score_at(now), -10);
+        assert_eq!(reputation.last_updated(), Some(now));
+    }
+
+    #[test]
+    fn it_decays_towards_zero() {
+        let now = Utc::now().naive_utc();
+        let mut reputation = PeerReputation::default();
+        reputation.record_at(ReputationEvent::ProtocolViolation, now);
+        reputation.record_at(ReputationEvent::ProtocolViolation, now);
+        assert_eq!(reputation.score_at(now), -100);
+
+        let half_life = ChronoDuration::from_std(REPUTATION_HALF_LIFE).unwrap();
+        assert_eq!(reputation.score_at(now + half_life), -50);
+        assert_eq!(reputation.score_at(now + half_life * 2), -25);
+        assert_eq!(reputation.score_at(now + half_life * 100), 0);
+        // Time going backwards does not change the score
+        assert_eq!(reputation.score_at(now - half_life), -100);
+    }
+
+    #[test]
+    fn it_clamps_the_score() {
+        let now = Utc::now().naive_utc();
+        let mut reputation = PeerReputation::default();
+        for _ in 0..100 {
+            reputation.record_at(ReputationEvent::InvalidBlock, now);
+        }
+        assert_eq!(reputation.score_at(now), -MAX_REPUTATION_SCORE);
+        for _ in 0..1000 {
+            reputation.record_at(ReputationEvent::SuccessfulSync, now);
+        }
+        assert_eq!(reputation.score_at(now), MAX_REPUTATION_SCORE);
+    }
+}
@@ -34,18 +34,20 @@ use crate::{
         peer_id::{generate_peer_key, PeerId},
         NodeDistance,
         NodeId,
+        PeerDatabase,
         PeerFeatures,
         PeerManagerError,
         PeerQuery,
         PeerQuerySortBy,
+        ReputationEvent,
     },
     types::{CommsDatabase, CommsPublicKey},
 };
 
 const LOG_TARGET: &str = "comms::peer_manager::peer_storage";
 /// The maximum number of peers to return in peer manager
-const PEER_MANAGER_SYNC_PEERS: usize = 100;
-const PEER_ACTIVE_WITHIN_DURATION: u64 = 7 * 24 * 60 * 60; // 7 days, 24h, 60m, 60s = 1 week
+pub(super) const PEER_MANAGER_SYNC_PEERS: usize = 100;
+pub(super) const PEER_ACTIVE_WITHIN_DURATION: u64 = 7 * 24 * 60 * 60; // 7 days, 24h, 60m, 60s = 1 week
 
 /// PeerStorage provides a mechanism to keep a datastore and a local copy of all peers in sync and allow fast searches
 /// using the node_id, public key or net_address of a peer.
@@ -484,6 +486,44 @@ where DS: KeyValueStore<PeerId, Peer>
         Ok(peer.is_banned())
     }
 
+    /// Records a reputation event for the peer with the given node id, returning the new reputation score
+    pub fn record_reputation_event(
+        &mut self,
+        node_id: &NodeId,
+        event: ReputationEvent,
+    ) -> Result<i64, PeerManagerError> {
+        let peer_key = *self
+            .node_id_index
+            .get(node_id)
+            .ok_or(PeerManagerError::PeerNotFoundError)?;
+        let mut peer: Peer = self
+            .peer_db
+            .get(&peer_key)
+            .map_err(PeerManagerError::DatabaseError)?
+            .expect("node_id_index is out of sync with peer db");
+        let score = peer.record_reputation_event(event);
+        self.peer_db
+            .insert(peer_key, peer)
+            .map_err(PeerManagerError::DatabaseError)?;
+        Ok(score)
+    }
+
+    /// Returns the current reputation scores of the given peers. Unknown peers are omitted.
+    pub fn get_reputation_scores(&self, node_ids: &[NodeId]) -> Result<HashMap<NodeId, i64>, PeerManagerError> {
+        let keys = node_ids
+            .iter()
+            .filter_map(|node_id| self.node_id_index.get(node_id).copied())
+            .collect::<Vec<_>>();
+        let peers = self.peer_db.get_many(&keys).map_err(PeerManagerError::DatabaseError)?;
+        Ok(peers
+            .into_iter()
+            .map(|peer| {
+                let score = peer.reputation_score();
+                (peer.node_id, score)
+            })
+            .collect())
+    }
+
     /// This will store metadata inside of the metadata field in the peer provided by the nodeID.
     /// It will return None if the value was empty and the old value if the value was updated
     pub fn set_peer_metadata(
@@ -509,6 +549,129 @@ where DS: KeyValueStore<PeerId, Peer>
     }
 }
 
+impl<DS> PeerDatabase for PeerStorage<DS>
+where DS: KeyValueStore<PeerId, Peer> + Send + Sync
+{
+    fn count(&self) -> usize {
+        PeerStorage::count(self)
+    }
+
+    fn add_peer(&mut self, peer: Peer) -> Result<PeerId, PeerManagerError> {
+        PeerStorage::add_peer(self, peer)
+    }
+
+    fn delete_peer(&mut self, node_id: &NodeId) -> Result<(), PeerManagerError> {
+        PeerStorage::delete_peer(self, node_id)
+    }
+
+    fn find_by_node_id(&self, node_id: &NodeId) -> Result<Option<Peer>, PeerManagerError> {
+        PeerStorage::find_by_node_id(self, node_id)
+    }
+
+    fn find_by_public_key(&self, public_key: &CommsPublicKey) -> Result<Option<Peer>, PeerManagerError> {
+        PeerStorage::find_by_public_key(self, public_key)
+    }
+
+    fn find_all_starts_with(&self, partial: &[u8]) -> Result<Vec<Peer>, PeerManagerError> {
+        PeerStorage::find_all_starts_with(self, partial)
+    }
+
+    fn exists(&self, public_key: &CommsPublicKey) -> bool {
+        PeerStorage::exists(self, public_key)
+    }
+
+    fn exists_node_id(&self, node_id: &NodeId) -> bool {
+        PeerStorage::exists_node_id(self, node_id)
+    }
+
+    fn perform_query(&self, query: PeerQuery<'_>) -> Result<Vec<Peer>, PeerManagerError> {
+        PeerStorage::perform_query(self, query)
+    }
+
+    fn all(&self) -> Result<Vec<Peer>, PeerManagerError> {
+        PeerStorage::all(self)
+    }
+
+    fn discovery_syncing(
+        &self,
+        n: usize,
+        excluded_peers: &[NodeId],
+        features: Option<PeerFeatures>,
+    ) -> Result<Vec<Peer>, PeerManagerError> {
+        PeerStorage::discovery_syncing(self, n, excluded_peers, features)
+    }
+
+    fn flood_peers(&self) -> Result<Vec<Peer>, PeerManagerError> {
+        PeerStorage::flood_peers(self)
+    }
+
+    fn for_each(&self, f: &mut dyn FnMut(Peer) -> IterationResult) -> Result<(), PeerManagerError> {
+        PeerStorage::for_each(self, f)
+    }
+
+    fn closest_peers(
+        &self,
+        node_id: &NodeId,
+        n: usize,
+        excluded_peers: &[NodeId],
+        features: Option<PeerFeatures>,
+    ) -> Result<Vec<Peer>, PeerManagerError> {
+        PeerStorage::closest_peers(self, node_id, n, excluded_peers, features)
+    }
+
+    fn random_peers(&self, n: usize, exclude_peers: &[NodeId]) -> Result<Vec<Peer>, PeerManagerError> {
+        PeerStorage::random_peers(self, n, exclude_peers)
+    }
+
+    fn calc_region_threshold(
+        &self,
+        region_node_id: &NodeId,
+        n: usize,
+        features: PeerFeatures,
+    ) -> Result<NodeDistance, PeerManagerError> {
+        PeerStorage::calc_region_threshold(self, region_node_id, n, features)
+    }
+
+    fn unban_peer(&mut self, node_id: &NodeId) -> Result<(), PeerManagerError> {
+        PeerStorage::unban_peer(self, node_id)
+    }
+
+    fn ban_peer(
+        &mut self,
+        public_key: &CommsPublicKey,
+        duration: Duration,
+        reason: String,
+    ) -> Result<NodeId, PeerManagerError> {
+        PeerStorage::ban_peer(self, public_key, duration, reason)
+    }
+
+    fn ban_peer_by_node_id(
+        &mut self,
+        node_id: &NodeId,
+        duration: Duration,
+        reason: String,
+    ) -> Result<NodeId, PeerManagerError> {
+        PeerStorage::ban_peer_by_node_id(self, node_id, duration, reason)
+    }
+
+    fn record_reputation_event(&mut self, node_id: &NodeId, event: ReputationEvent) -> Result<i64, PeerManagerError> {
+        PeerStorage::record_reputation_event(self, node_id, event)
+    }
+
+    fn get_reputation_scores(&self, node_ids: &[NodeId]) -> Result<HashMap<NodeId, i64>, PeerManagerError> {
+        PeerStorage::get_reputation_scores(self, node_ids)
+    }
+
+    fn set_peer_metadata(
+        &mut self,
+        node_id: &NodeId,
+        key: u8,
+        data: Vec<u8>,
+    ) -> Result<Option<Vec<u8>>, PeerManagerError> {
+        PeerStorage::set_peer_metadata(self, node_id, key, data)
+    }
+}
+
 #[allow(clippy::from_over_into)]
 impl Into<CommsDatabase> for PeerStorage<CommsDatabase> {
     fn into(self) -> CommsDatabase {
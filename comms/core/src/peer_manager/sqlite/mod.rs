@@ -0,0 +1,795 @@
+//  Copyright 2024, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! A [PeerDatabase](super::PeerDatabase) implementation backed by SQLite. Peers are stored as serialized blobs
+//! alongside indexed columns for features, last seen, offline and ban status and the node id, so that common queries
+//! (e.g. closest peers, discovery) are answered by the database rather than by scanning every peer.
+
+mod peer_sql;
+mod schema;
+
+use std::{collections::HashMap, convert::TryFrom, time::Duration};
+
+use chrono::{Duration as ChronoDuration, Utc};
+use diesel::{
+    dsl::sql,
+    r2d2::{ConnectionManager, PooledConnection},
+    sql_types::{BigInt, Binary},
+    sqlite::Sqlite,
+    BoolExpressionMethods,
+    Connection,
+    ExpressionMethods,
+    OptionalExtension,
+    QueryDsl,
+    RunQueryDsl,
+    SqliteConnection,
+};
+use diesel_migrations::{EmbeddedMigrations, MigrationHarness};
+use log::*;
+use tari_common_sqlite::sqlite_connection_pool::SqliteConnectionPool;
+use tari_storage::{IterationResult, KeyValueStore, LMDBWrapper};
+use tari_utilities::ByteArray;
+
+use self::{
+    peer_sql::{PeerRow, PeerSql},
+    schema::peers,
+};
+use crate::{
+    peer_manager::{
+        peer_id::PeerId,
+        peer_storage::{PEER_ACTIVE_WITHIN_DURATION, PEER_MANAGER_SYNC_PEERS},
+        wrapper::KeyValueWrapper,
+        NodeDistance,
+        NodeId,
+        Peer,
+        PeerDatabase,
+        PeerFeatures,
+        PeerManager,
+        PeerManagerError,
+        PeerQuery,
+        PeerQuerySortBy,
+        ReputationEvent,
+    },
+    types::CommsPublicKey,
+};
+
+const LOG_TARGET: &str = "comms::peer_manager::sqlite";
+const SQLITE_POOL_SIZE: usize = 4;
+/// The number of node id prefix bits that are relaxed on each iteration of a closest peers query
+const CLOSEST_PEERS_PREFIX_STEP: usize = 2;
+/// The number of peers loaded at a time when performing a peer query
+const QUERY_PAGE_SIZE: usize = 100;
+
+type PeerRowSqlType = (BigInt, Binary);
+type PeerRowQuery<'a> = peers::BoxedQuery<'a, Sqlite, PeerRowSqlType>;
+type SqliteConnectionPooled = PooledConnection<ConnectionManager<SqliteConnection>>;
+
+/// A peer database stored in SQLite
+#[derive(Clone)]
+pub struct SqlitePeerDatabase {
+    pool: SqliteConnectionPool,
+}
+
+impl SqlitePeerDatabase {
+    /// Connect to the database at the given path or URL (e.g. `file:peers?mode=memory&cache=shared`) and run any
+    /// pending migrations.
+    pub fn connect_and_migrate<S: Into<String>>(database_url: S) -> Result<Self, PeerManagerError> {
+        let database_url = database_url.into();
+        debug!(target: LOG_TARGET, "Connecting to peer database '{}'", database_url);
+        let mut pool = SqliteConnectionPool::new(database_url, SQLITE_POOL_SIZE, true, true, Duration::from_secs(60));
+        pool.create_pool()
+            .map_err(|err| PeerManagerError::PeerDatabaseError(err.to_string()))?;
+        let db = Self { pool };
+        db.migrate()?;
+        Ok(db)
+    }
+
+    fn migrate(&self) -> Result<(), PeerManagerError> {
+        const MIGRATIONS: EmbeddedMigrations = diesel_migrations::embed_migrations!("./migrations");
+
+        let mut conn = self.connection()?;
+        let versions = conn
+            .run_pending_migrations(MIGRATIONS)
+            .map_err(|err| PeerManagerError::MigrationError(format!("Peer database migration failed: {}", err)))?;
+        for version in versions {
+            debug!(target: LOG_TARGET, "Ran peer database migration {}", version);
+        }
+        Ok(())
+    }
+
+    /// Imports all peers from an LMDB peer database, which is first migrated to the current peer format. Peers that
+    /// already exist are merged. Returns the number of peers that were imported, or an error if any peer could not be
+    /// read so that known peers are never silently dropped.
+    pub fn import_lmdb_peers(&mut self, database: LMDBWrapper<PeerId, Peer>) -> Result<usize, PeerManagerError> {
+        PeerManager::migrate_lmdb(&database.inner())?;
+        let database = KeyValueWrapper::new(database);
+        let num_records = database.size()?;
+        let mut peers = Vec::with_capacity(num_records);
+        database.for_each_ok(|(_, peer)| {
+            peers.push(peer);
+            IterationResult::Continue
+        })?;
+        if peers.len() != num_records {
+            return Err(PeerManagerError::PeerDatabaseError(format!(
+                "Only {} of {} peer(s) in the LMDB peer database could be read",
+                peers.len(),
+                num_records
+            )));
+        }
+
+        let num_peers = peers.len();
+        let mut conn = self.connection()?;
+        conn.transaction::<_, PeerManagerError, _>(|conn| {
+            for peer in peers {
+                upsert_peer(conn, peer)?;
+            }
+            Ok(())
+        })?;
+        info!(target: LOG_TARGET, "Imported {} peer(s) into the peer database", num_peers);
+        Ok(num_peers)
+    }
+
+    fn connection(&self) -> Result<SqliteConnectionPooled, PeerManagerError> {
+        self.pool
+            .get_pooled_connection()
+            .map_err(|err| PeerManagerError::PeerDatabaseError(err.to_string()))
+    }
+
+    fn load_peers(&self, query: PeerRowQuery<'_>) -> Result<Vec<Peer>, PeerManagerError> {
+        let mut conn = self.connection()?;
+        query
+            .load::<PeerRow>(&mut conn)?
+            .into_iter()
+            .map(PeerRow::into_peer)
+            .collect()
+    }
+
+    fn find_peer(&self, query: PeerRowQuery<'_>) -> Result<Option<Peer>, PeerManagerError> {
+        let mut conn = self.connection()?;
+        query
+            .first::<PeerRow>(&mut conn)
+            .optional()?
+            .map(PeerRow::into_peer)
+            .transpose()
+    }
+
+    fn count_where(&self, query: PeerRowQuery<'_>) -> Result<usize, PeerManagerError> {
+        let mut conn = self.connection()?;
+        let count = query.count().get_result::<i64>(&mut conn)?;
+        Ok(usize::try_from(count).unwrap_or(0))
+    }
+
+    /// Loads the peer matching the query, applies `f` and stores the result
+    fn update_peer<F, T>(&self, query: PeerRowQuery<'_>, f: F) -> Result<T, PeerManagerError>
+    where F: FnOnce(&mut Peer) -> T {
+        let mut conn = self.connection()?;
+        conn.transaction::<_, PeerManagerError, _>(|conn| {
+            let row = query
+                .first::<PeerRow>(conn)
+                .optional()?
+                .ok_or(PeerManagerError::PeerNotFoundError)?;
+            let id = row.id;
+            let mut peer = row.into_peer()?;
+            let result = f(&mut peer);
+            diesel::update(peers::table.find(id))
+                .set(PeerSql::try_from(&peer)?)
+                .execute(conn)?;
+            Ok(result)
+        })
+    }
+
+    /// Loads the peers returned by the ordered query a page at a time, returning the peers selected by the peer query
+    /// in that order. Loading stops once the peer query limit is reached.
+    fn select_paged<'a, F>(&self, peer_query: &mut PeerQuery<'_>, query: F) -> Result<Vec<Peer>, PeerManagerError>
+    where F: Fn() -> PeerRowQuery<'a> {
+        let mut selected_peers = Vec::new();
+        let mut offset = 0;
+        loop {
+            if !peer_query.within_limit(selected_peers.len()) {
+                return Ok(selected_peers);
+            }
+            let page = self.load_peers(
+                query()
+                    .limit(to_sql_limit(QUERY_PAGE_SIZE))
+                    .offset(to_sql_limit(offset)),
+            )?;
+            let is_last_page = page.len() < QUERY_PAGE_SIZE;
+            for peer in page {
+                if !peer_query.within_limit(selected_peers.len()) {
+                    return Ok(selected_peers);
+                }
+                if peer_query.is_selected(&peer) {
+                    selected_peers.push(peer);
+                }
+            }
+            if is_last_page {
+                return Ok(selected_peers);
+            }
+            offset += QUERY_PAGE_SIZE;
+        }
+    }
+
+    /// Returns the `n` peers matching the query and the `select` predicate that are closest to `node_id`.
+    ///
+    /// XOR distance cannot be indexed directly, however all peers that share a k-bit prefix with `node_id` are closer
+    /// than any peer that does not. This selects peers in the (indexed) node id range sharing a prefix with `node_id`,
+    /// relaxing the prefix until at least `n` peers are found.
+    fn closest_where<'a, F, P>(
+        &self,
+        node_id: &NodeId,
+        n: usize,
+        query: F,
+        mut select: P,
+    ) -> Result<Vec<Peer>, PeerManagerError>
+    where
+        F: Fn() -> PeerRowQuery<'a>,
+        P: FnMut(&Peer) -> bool,
+    {
+        if n == 0 {
+            return Ok(Vec::new());
+        }
+        // Estimate the prefix length at which we expect to find n peers if node ids are uniformly distributed
+        let num_candidates = self.count_where(query())?;
+        let mut prefix_bits = log2_floor(num_candidates / n).min(NodeId::byte_size() * 8);
+        loop {
+            let (lower, upper) = node_id_prefix_range(node_id.as_bytes(), prefix_bits);
+            let mut peers = self.load_peers(
+                query()
+                    .filter(peers::node_id.ge(lower))
+                    .filter(peers::node_id.le(upper)),
+            )?;
+            peers.retain(|peer| select(peer));
+            if peers.len() >= n || prefix_bits == 0 {
+                peers.sort_by(|a, b| a.node_id.distance(node_id).cmp(&b.node_id.distance(node_id)));
+                peers.truncate(n);
+                return Ok(peers);
+            }
+            prefix_bits = prefix_bits.saturating_sub(CLOSEST_PEERS_PREFIX_STEP);
+        }
+    }
+}
+
+impl PeerDatabase for SqlitePeerDatabase {
+    fn count(&self) -> usize {
+        self.count_where(all_peers()).unwrap_or_else(|err| {
+            warn!(target: LOG_TARGET, "Failed to count peers: {}", err);
+            0
+        })
+    }
+
+    fn add_peer(&mut self, peer: Peer) -> Result<PeerId, PeerManagerError> {
+        let mut conn = self.connection()?;
+        conn.transaction::<_, PeerManagerError, _>(|conn| upsert_peer(conn, peer))
+    }
+
+    fn delete_peer(&mut self, node_id: &NodeId) -> Result<(), PeerManagerError> {
+        self.update_peer(by_node_id(node_id), |peer| {
+            peer.deleted_at = Some(Utc::now().naive_utc());
+        })
+    }
+
+    fn find_by_node_id(&self, node_id: &NodeId) -> Result<Option<Peer>, PeerManagerError> {
+        self.find_peer(by_node_id(node_id))
+    }
+
+    fn find_by_public_key(&self, public_key: &CommsPublicKey) -> Result<Option<Peer>, PeerManagerError> {
+        self.find_peer(by_public_key(public_key))
+    }
+
+    fn find_all_starts_with(&self, partial: &[u8]) -> Result<Vec<Peer>, PeerManagerError> {
+        if partial.is_empty() || partial.len() > NodeId::byte_size() {
+            return Ok(Vec::new());
+        }
+        let mut node_id = partial.to_vec();
+        node_id.resize(NodeId::byte_size(), 0);
+        let (lower, upper) = node_id_prefix_range(&node_id, partial.len() * 8);
+        self.load_peers(
+            all_peers()
+                .filter(peers::node_id.ge(lower))
+                .filter(peers::node_id.le(upper)),
+        )
+    }
+
+    fn exists(&self, public_key: &CommsPublicKey) -> bool {
+        self.count_where(by_public_key(public_key))
+            .map(|count| count > 0)
+            .unwrap_or_else(|err| {
+                warn!(target: LOG_TARGET, "Failed to check if peer exists: {}", err);
+                false
+            })
+    }
+
+    fn exists_node_id(&self, node_id: &NodeId) -> bool {
+        self.count_where(by_node_id(node_id))
+            .map(|count| count > 0)
+            .unwrap_or_else(|err| {
+                warn!(target: LOG_TARGET, "Failed to check if peer exists: {}", err);
+                false
+            })
+    }
+
+    fn perform_query(&self, mut query: PeerQuery<'_>) -> Result<Vec<Peer>, PeerManagerError> {
+        let selected_peers = match query.get_sort_by().clone() {
+            PeerQuerySortBy::None => self.select_paged(&mut query, || all_peers().order_by(peers::id.asc()))?,
+            PeerQuerySortBy::LastConnected => self.select_paged(&mut query, || {
+                // Peers that have never been seen are ordered first, consistent with the other backends
+                all_peers().order_by((peers::last_seen.is_not_null(), peers::last_seen.desc(), peers::id.asc()))
+            })?,
+            PeerQuerySortBy::DistanceFrom(node_id) | PeerQuerySortBy::DistanceFromLastConnected(node_id) => {
+                match query.get_limit() {
+                    Some(n) => self.closest_where(node_id, n, all_peers, |peer| query.is_selected(peer))?,
+                    None => self
+                        .load_peers(all_peers())?
+                        .into_iter()
+                        .filter(|peer| query.is_selected(peer))
+                        .collect(),
+                }
+            },
+        };
+        Ok(query.sort_and_limit(selected_peers))
+    }
+
+    fn all(&self) -> Result<Vec<Peer>, PeerManagerError> {
+        self.load_peers(all_peers().order_by(peers::id.asc()))
+    }
+
+    fn discovery_syncing(
+        &self,
+        mut n: usize,
+        excluded_peers: &[NodeId],
+        features: Option<PeerFeatures>,
+    ) -> Result<Vec<Peer>, PeerManagerError> {
+        if n == 0 {
+            n = PEER_MANAGER_SYNC_PEERS;
+        }
+        self.load_peers(active_peers(features, excluded_peers).limit(to_sql_limit(n)))
+    }
+
+    fn flood_peers(&self) -> Result<Vec<Peer>, PeerManagerError> {
+        self.load_peers(filter_not_banned(all_peers()).limit(to_sql_limit(PEER_MANAGER_SYNC_PEERS)))
+    }
+
+    fn for_each(&self, f: &mut dyn FnMut(Peer) -> IterationResult) -> Result<(), PeerManagerError> {
+        for peer in self.all()? {
+            if let IterationResult::Break = f(peer) {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    fn closest_peers(
+        &self,
+        node_id: &NodeId,
+        n: usize,
+        excluded_peers: &[NodeId],
+        features: Option<PeerFeatures>,
+    ) -> Result<Vec<Peer>, PeerManagerError> {
+        self.closest_where(node_id, n, || active_peers(features, excluded_peers), |_| true)
+    }
+
+    fn random_peers(&self, n: usize, exclude_peers: &[NodeId]) -> Result<Vec<Peer>, PeerManagerError> {
+        if n == 0 {
+            return Ok(Vec::new());
+        }
+        self.load_peers(
+            filter_not_banned(all_peers())
+                .filter(peers::offline_at.is_null())
+                .filter(peers::features.eq(i64::from(PeerFeatures::COMMUNICATION_NODE.bits())))
+                .filter(peers::node_id.ne_all(to_node_id_bytes(exclude_peers)))
+                .order_by(sql::<BigInt>("RANDOM()"))
+                .limit(to_sql_limit(n)),
+        )
+    }
+
+    fn calc_region_threshold(
+        &self,
+        region_node_id: &NodeId,
+        n: usize,
+        features: PeerFeatures,
+    ) -> Result<NodeDistance, PeerManagerError> {
+        if n == 0 {
+            return Ok(NodeDistance::max_distance());
+        }
+
+        let peers = self.closest_where(
+            region_node_id,
+            n,
+            || {
+                filter_not_banned(all_peers())
+                    .filter(peers::features.eq(i64::from(features.bits())))
+                    .filter(peers::offline_at.is_null())
+            },
+            |_| true,
+        )?;
+
+        // If we have less than `n` matching peers in our threshold group, the threshold should be max
+        if peers.len() < n {
+            return Ok(NodeDistance::max_distance());
+        }
+
+        Ok(peers
+            .last()
+            .map(|peer| region_node_id.distance(&peer.node_id))
+            .expect("peers cannot be empty at this point"))
+    }
+
+    fn unban_peer(&mut self, node_id: &NodeId) -> Result<(), PeerManagerError> {
+        self.update_peer(by_node_id(node_id), |peer| peer.unban())
+    }
+
+    fn ban_peer(
+        &mut self,
+        public_key: &CommsPublicKey,
+        duration: Duration,
+        reason: String,
+    ) -> Result<NodeId, PeerManagerError> {
+        self.update_peer(by_public_key(public_key), |peer| {
+            peer.ban_for(duration, reason);
+            peer.node_id.clone()
+        })
+    }
+
+    fn ban_peer_by_node_id(
+        &mut self,
+        node_id: &NodeId,
+        duration: Duration,
+        reason: String,
+    ) -> Result<NodeId, PeerManagerError> {
+        self.update_peer(by_node_id(node_id), |peer| {
+            peer.ban_for(duration, reason);
+            peer.node_id.clone()
+        })
+    }
+
+    fn record_reputation_event(&mut self, node_id: &NodeId, event: ReputationEvent) -> Result<i64, PeerManagerError> {
+        self.update_peer(by_node_id(node_id), |peer| peer.record_reputation_event(event))
+    }
+
+    fn get_reputation_scores(&self, node_ids: &[NodeId]) -> Result<HashMap<NodeId, i64>, PeerManagerError> {
+        let peers = self.load_peers(all_peers().filter(peers::node_id.eq_any(to_node_id_bytes(node_ids))))?;
+        Ok(peers
+            .into_iter()
+            .map(|peer| {
+                let score = peer.reputation_score();
+                (peer.node_id, score)
+            })
+            .collect())
+    }
+
+    fn set_peer_metadata(
+        &mut self,
+        node_id: &NodeId,
+        key: u8,
+        data: Vec<u8>,
+    ) -> Result<Option<Vec<u8>>, PeerManagerError> {
+        self.update_peer(by_node_id(node_id), |peer| peer.set_metadata(key, data))
+    }
+}
+
+/// Inserts the peer or, if a peer with the same public key exists, merges it into the existing peer
+fn upsert_peer(conn: &mut SqliteConnection, mut peer: Peer) -> Result<PeerId, PeerManagerError> {
+    let existing = by_public_key(&peer.public_key).first::<PeerRow>(conn).optional()?;
+    match existing {
+        Some(row) => {
+            trace!(target: LOG_TARGET, "Replacing peer that has NodeId '{}'", peer.node_id);
+            let id = row.id;
+            let mut existing_peer = row.into_peer()?;
+            if let Some(peer_id) = existing_peer.id {
+                peer.set_id(peer_id);
+            }
+            existing_peer.merge(&peer);
+            diesel::update(peers::table.find(id))
+                .set(PeerSql::try_from(&existing_peer)?)
+                .execute(conn)?;
+            to_peer_id(id)
+        },
+        None => {
+            trace!(target: LOG_TARGET, "Adding peer with node id '{}'", peer.node_id);
+            diesel::insert_into(peers::table)
+                .values(PeerSql::try_from(&peer)?)
+                .execute(conn)?;
+            let id = peers::table
+                .select(peers::id)
+                .filter(peers::public_key.eq(peer.public_key.as_bytes()))
+                .first::<i64>(conn)?;
+            to_peer_id(id)
+        },
+    }
+}
+
+fn all_peers<'a>() -> PeerRowQuery<'a> {
+    peers::table.select((peers::id, peers::peer)).into_boxed()
+}
+
+fn by_node_id<'a>(node_id: &NodeId) -> PeerRowQuery<'a> {
+    all_peers().filter(peers::node_id.eq(node_id.as_bytes().to_vec()))
+}
+
+fn by_public_key<'a>(public_key: &CommsPublicKey) -> PeerRowQuery<'a> {
+    all_peers().filter(peers::public_key.eq(public_key.as_bytes().to_vec()))
+}
+
+fn filter_not_banned(query: PeerRowQuery<'_>) -> PeerRowQuery<'_> {
+    query.filter(
+        peers::banned_until
+            .is_null()
+            .or(peers::banned_until.le(Utc::now().naive_utc())),
+    )
+}
+
+/// Peers that are not banned or deleted, have been seen within the last week and optionally have the given features
+fn active_peers<'a>(features: Option<PeerFeatures>, excluded_peers: &[NodeId]) -> PeerRowQuery<'a> {
+    #[allow(clippy::cast_possible_wrap)]
+    let active_since = Utc::now().naive_utc() - ChronoDuration::seconds(PEER_ACTIVE_WITHIN_DURATION as i64);
+    let mut query = filter_not_banned(all_peers())
+        .filter(peers::deleted_at.is_null())
+        .filter(peers::last_seen.ge(active_since));
+    if let Some(features) = features {
+        query = query.filter(peers::features.eq(i64::from(features.bits())));
+    }
+    if !excluded_peers.is_empty() {
+        query = query.filter(peers::node_id.ne_all(to_node_id_bytes(excluded_peers)));
+    }
+    query
+}
+
+fn to_node_id_bytes(node_ids: &[NodeId]) -> Vec<Vec<u8>> {
+    node_ids.iter().map(|node_id| node_id.as_bytes().to_vec()).collect()
+}
+
+fn to_peer_id(id: i64) -> Result<PeerId, PeerManagerError> {
+    u64::try_from(id).map_err(|_| PeerManagerError::DataInconsistency(format!("Invalid peer row id {}", id)))
+}
+
+fn to_sql_limit(n: usize) -> i64 {
+    i64::try_from(n).unwrap_or(i64::MAX)
+}
+
+fn log2_floor(mut n: usize) -> usize {
+    let mut bits = 0;
+    while n > 1 {
+        n >>= 1;
+        bits += 1;
+    }
+    bits
+}
+
+/// Returns the inclusive range of node ids that share the first `prefix_bits` bits with the given node id bytes
+fn node_id_prefix_range(node_id: &[u8], prefix_bits: usize) -> (Vec<u8>, Vec<u8>) {
+    let mut lower = node_id.to_vec();
+    let mut upper = node_id.to_vec();
+    for (i, (l, u)) in lower.iter_mut().zip(upper.iter_mut()).enumerate() {
+        let byte_start = i * 8;
+        if byte_start + 8 <= prefix_bits {
+            continue;
+        }
+        let keep_bits = prefix_bits.saturating_sub(byte_start);
+        let mask = if keep_bits == 0 { 0u8 } else { !(0xffu8 >> keep_bits) };
+        *l &= mask;
+        *u |= !mask;
+    }
+    (lower, upper)
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use rand::rngs::OsRng;
+    use tari_crypto::keys::PublicKey;
+    use tari_storage::lmdb_store::{LMDBBuilder, LMDBConfig, LMDBDatabase};
+    use tari_test_utils::random;
+
+    use super::*;
+    use crate::{
+        net_address::{MultiaddressesWithStats, PeerAddressSource},
+        peer_manager::{
+            migrations::{v8::PeerV7, MIGRATION_VERSION_KEY},
+            PeerFlags,
+        },
+    };
+
+    fn connect_memory() -> SqlitePeerDatabase {
+        SqlitePeerDatabase::connect_and_migrate(format!("file:{}?mode=memory&cache=shared", random::string(8))).unwrap()
+    }
+
+    fn create_test_peer(features: PeerFeatures) -> Peer {
+        let (_sk, pk) = CommsPublicKey::random_keypair(&mut OsRng);
+        let node_id = NodeId::from_key(&pk);
+        let mut addresses = MultiaddressesWithStats::from_addresses_with_source(
+            vec!["/ip4/1.2.3.4/tcp/8000".parse().unwrap()],
+            &PeerAddressSource::Config,
+        );
+        let address = addresses.iter().next().unwrap().address().clone();
+        addresses.mark_last_seen_now(&address);
+        Peer::new(
+            pk,
+            node_id,
+            addresses,
+            PeerFlags::default(),
+            features,
+            Default::default(),
+            Default::default(),
+        )
+    }
+
+    /// Creates an LMDB peer database containing the given peers as they were persisted prior to the v8 migration
+    fn create_pre_v8_lmdb_database(path: &std::path::Path, peers: &[Peer]) -> LMDBDatabase {
+        let datastore = LMDBBuilder::new()
+            .set_path(path)
+            .set_env_config(LMDBConfig::default())
+            .set_max_number_of_databases(1)
+            .add_database("peers", lmdb_zero::db::CREATE)
+            .build()
+            .unwrap();
+        let database = datastore.get_handle("peers").unwrap();
+        for (key, peer) in (0u64..).zip(peers) {
+            database.insert(&key, &PeerV7::from(peer.clone())).unwrap();
+        }
+        database.insert(&MIGRATION_VERSION_KEY, &7u32).unwrap();
+        database
+    }
+
+    #[test]
+    fn import_pre_v8_lmdb_peers() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let peers = (0..10)
+            .map(|_| create_test_peer(PeerFeatures::COMMUNICATION_NODE))
+            .collect::<Vec<_>>();
+        let lmdb = create_pre_v8_lmdb_database(temp_dir.path(), &peers);
+
+        let mut db = connect_memory();
+        let num_imported = db.import_lmdb_peers(LMDBWrapper::new(Arc::new(lmdb))).unwrap();
+        assert_eq!(num_imported, peers.len());
+        assert_eq!(db.count(), peers.len());
+        for peer in &peers {
+            assert!(db.exists_node_id(&peer.node_id));
+        }
+    }
+
+    #[test]
+    fn import_fails_on_unreadable_lmdb_peers() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let peers = vec![create_test_peer(PeerFeatures::COMMUNICATION_NODE)];
+        let lmdb = create_pre_v8_lmdb_database(temp_dir.path(), &peers);
+        lmdb.insert(&100u64, &"not a peer".to_string()).unwrap();
+
+        let mut db = connect_memory();
+        let err = db.import_lmdb_peers(LMDBWrapper::new(Arc::new(lmdb))).unwrap_err();
+        assert!(matches!(err, PeerManagerError::PeerDatabaseError(_)));
+        assert_eq!(db.count(), 0);
+    }
+
+    #[test]
+    fn node_id_prefix_range_bounds() {
+        let node_id = [0b1010_1010u8, 0b1100_1100, 0xff];
+        assert_eq!(node_id_prefix_range(&node_id, 24), (node_id.to_vec(), node_id.to_vec()));
+        assert_eq!(
+            node_id_prefix_range(&node_id, 12),
+            (vec![0b1010_1010, 0b1100_0000, 0x00], vec![
+                0b1010_1010,
+                0b1100_1111,
+                0xff
+            ])
+        );
+        assert_eq!(node_id_prefix_range(&node_id, 0), (vec![0; 3], vec![0xff; 3]));
+    }
+
+    #[test]
+    fn add_find_and_ban_peers() {
+        let mut db = connect_memory();
+        let peers = (0..10)
+            .map(|_| create_test_peer(PeerFeatures::COMMUNICATION_NODE))
+            .collect::<Vec<_>>();
+        for peer in &peers {
+            db.add_peer(peer.clone()).unwrap();
+        }
+        assert_eq!(db.count(), 10);
+        // Adding an existing peer replaces it
+        let id = db.add_peer(peers[0].clone()).unwrap();
+        assert_eq!(db.count(), 10);
+
+        let found = db.find_by_public_key(&peers[0].public_key).unwrap().unwrap();
+        assert_eq!(found.id(), id);
+        assert_eq!(found.node_id, peers[0].node_id);
+        assert!(db.exists_node_id(&peers[1].node_id));
+        let found = db.find_all_starts_with(&peers[2].node_id.as_bytes()[..4]).unwrap();
+        assert!(found.iter().any(|p| p.node_id == peers[2].node_id));
+
+        db.ban_peer_by_node_id(&peers[3].node_id, Duration::from_secs(1000), "test".to_string())
+            .unwrap();
+        assert!(db.is_peer_banned(&peers[3].node_id).unwrap());
+        assert_eq!(db.flood_peers().unwrap().len(), 9);
+        db.unban_peer(&peers[3].node_id).unwrap();
+        assert!(!db.is_peer_banned(&peers[3].node_id).unwrap());
+    }
+
+    #[test]
+    fn closest_peers_matches_full_scan() {
+        let mut db = connect_memory();
+        let peers = (0..50)
+            .map(|_| create_test_peer(PeerFeatures::COMMUNICATION_NODE))
+            .collect::<Vec<_>>();
+        for peer in &peers {
+            db.add_peer(peer.clone()).unwrap();
+        }
+        let target = NodeId::from_key(&CommsPublicKey::random_keypair(&mut OsRng).1);
+
+        let mut expected = peers.iter().map(|p| p.node_id.clone()).collect::<Vec<_>>();
+        expected.sort_by_key(|node_id| node_id.distance(&target));
+        expected.truncate(8);
+
+        let closest = db.closest_peers(&target, 8, &[], None).unwrap();
+        assert_eq!(closest.into_iter().map(|p| p.node_id).collect::<Vec<_>>(), expected);
+
+        let excluded = &expected[..2];
+        let closest = db
+            .closest_peers(&target, 8, excluded, Some(PeerFeatures::COMMUNICATION_NODE))
+            .unwrap();
+        assert!(closest.iter().all(|p| !excluded.contains(&p.node_id)));
+        assert_eq!(closest[0].node_id, expected[2]);
+    }
+
+    #[test]
+    fn perform_query_matches_full_scan() {
+        let mut db = connect_memory();
+        let peers = (0..250)
+            .map(|i| {
+                if i % 2 == 0 {
+                    create_test_peer(PeerFeatures::COMMUNICATION_NODE)
+                } else {
+                    create_test_peer(PeerFeatures::COMMUNICATION_CLIENT)
+                }
+            })
+            .collect::<Vec<_>>();
+        for peer in &peers {
+            db.add_peer(peer.clone()).unwrap();
+        }
+
+        // Unsorted queries span several pages and stop at the limit
+        let query = PeerQuery::new().select_where(|p| p.features == PeerFeatures::COMMUNICATION_CLIENT);
+        assert_eq!(db.perform_query(query).unwrap().len(), 125);
+        let query = PeerQuery::new()
+            .select_where(|p| p.features == PeerFeatures::COMMUNICATION_CLIENT)
+            .limit(110);
+        let found = db.perform_query(query).unwrap();
+        assert_eq!(found.len(), 110);
+        assert!(found.iter().all(|p| p.features == PeerFeatures::COMMUNICATION_CLIENT));
+
+        let target = NodeId::from_key(&CommsPublicKey::random_keypair(&mut OsRng).1);
+        let mut expected = peers
+            .iter()
+            .filter(|p| p.features == PeerFeatures::COMMUNICATION_NODE)
+            .map(|p| p.node_id.clone())
+            .collect::<Vec<_>>();
+        expected.sort_by_key(|node_id| node_id.distance(&target));
+        expected.truncate(5);
+
+        let query = PeerQuery::new()
+            .select_where(|p| p.features == PeerFeatures::COMMUNICATION_NODE)
+            .sort_by(PeerQuerySortBy::DistanceFrom(&target))
+            .limit(5);
+        let closest = db.perform_query(query).unwrap();
+        assert_eq!(closest.into_iter().map(|p| p.node_id).collect::<Vec<_>>(), expected);
+    }
+}
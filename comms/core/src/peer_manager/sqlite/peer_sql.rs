@@ -0,0 +1,81 @@
+//  Copyright 2024, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::convert::TryFrom;
+
+use chrono::NaiveDateTime;
+use diesel::{AsChangeset, Insertable, Queryable};
+use tari_utilities::ByteArray;
+
+use super::schema::peers;
+use crate::peer_manager::{Peer, PeerManagerError};
+
+/// A row from the `peers` table containing the row id and the serialized peer
+#[derive(Debug, Clone, Queryable)]
+pub struct PeerRow {
+    pub id: i64,
+    pub peer: Vec<u8>,
+}
+
+impl PeerRow {
+    /// Deserializes the peer, setting its id to the row id
+    pub fn into_peer(self) -> Result<Peer, PeerManagerError> {
+        let mut peer = bincode::deserialize::<Peer>(&self.peer)
+            .map_err(|err| PeerManagerError::PeerDatabaseError(format!("Failed to deserialize peer: {}", err)))?;
+        let id = u64::try_from(self.id)
+            .map_err(|_| PeerManagerError::DataInconsistency(format!("Invalid peer row id {}", self.id)))?;
+        peer.set_id(id);
+        Ok(peer)
+    }
+}
+
+/// The indexed columns of a peer, along with the serialized peer
+#[derive(Debug, Clone, Insertable, AsChangeset)]
+#[diesel(table_name = peers, treat_none_as_null = true)]
+pub struct PeerSql {
+    pub public_key: Vec<u8>,
+    pub node_id: Vec<u8>,
+    pub features: i64,
+    pub last_seen: Option<NaiveDateTime>,
+    pub offline_at: Option<NaiveDateTime>,
+    pub banned_until: Option<NaiveDateTime>,
+    pub deleted_at: Option<NaiveDateTime>,
+    pub peer: Vec<u8>,
+}
+
+impl TryFrom<&Peer> for PeerSql {
+    type Error = PeerManagerError;
+
+    fn try_from(peer: &Peer) -> Result<Self, Self::Error> {
+        Ok(Self {
+            public_key: peer.public_key.as_bytes().to_vec(),
+            node_id: peer.node_id.as_bytes().to_vec(),
+            features: i64::from(peer.features.bits()),
+            last_seen: peer.last_seen(),
+            offline_at: peer.offline_at(),
+            banned_until: peer.banned_until,
+            deleted_at: peer.deleted_at,
+            peer: bincode::serialize(peer)
+                .map_err(|err| PeerManagerError::PeerDatabaseError(format!("Failed to serialize peer: {}", err)))?,
+        })
+    }
+}
@@ -0,0 +1,16 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+diesel::table! {
+    peers (id) {
+        id -> BigInt,
+        public_key -> Binary,
+        node_id -> Binary,
+        features -> BigInt,
+        last_seen -> Nullable<Timestamp>,
+        offline_at -> Nullable<Timestamp>,
+        banned_until -> Nullable<Timestamp>,
+        deleted_at -> Nullable<Timestamp>,
+        peer -> Binary,
+    }
+}
@@ -84,9 +84,32 @@ use crate::{
 
 const LOG_TARGET: &str = "comms::rpc::client";
 
+/// Receives a copy of every call made by an [RpcClient](self::RpcClient) that was built with
+/// [RpcClientBuilder::with_call_observer](self::RpcClientBuilder::with_call_observer).
+pub trait RpcCallObserver: fmt::Debug + Send + Sync + 'static {
+    /// Called once the call has completed, either because the last response was received, the server returned an
+    /// error status or the caller stopped reading responses. The observer is called before the last response is
+    /// passed on to the caller.
+    fn on_call_completed(&self, call: ObservedRpcCall);
+}
+
+/// A completed RPC call as seen by the client.
+#[derive(Debug, Clone)]
+pub struct ObservedRpcCall {
+    pub protocol_id: ProtocolId,
+    pub method: u32,
+    /// The encoded request message
+    pub request: Bytes,
+    /// The encoded response messages in the order they were received. Unary calls have at most one response.
+    pub responses: Vec<Bytes>,
+    /// The error status returned by the server, if any
+    pub status: Option<RpcStatus>,
+}
+
 #[derive(Clone)]
 pub struct RpcClient {
     connector: ClientConnector,
+    call_observer: Option<(ProtocolId, Arc<dyn RpcCallObserver>)>,
 }
 
 impl RpcClient {
@@ -132,7 +155,10 @@ impl RpcClient {
         ready_rx
             .await
             .expect("ready_rx oneshot is never dropped without a reply")?;
-        Ok(Self { connector })
+        Ok(Self {
+            connector,
+            call_observer: None,
+        })
     }
 
     /// Perform a single request and single response
@@ -145,7 +171,7 @@ impl RpcClient {
         let req_bytes = request.to_encoded_bytes();
         let request = BaseRequest::new(method.into(), req_bytes.into());
 
-        let mut resp = self.call_inner(request).await?;
+        let mut resp = self.call_inner(request, false).await?;
         let resp = resp.recv().await.ok_or(RpcError::ServerClosedRequest)??;
         let resp = R::decode(resp.into_message())?;
 
@@ -162,7 +188,7 @@ impl RpcClient {
         let req_bytes = request.to_encoded_bytes();
         let request = BaseRequest::new(method.into(), req_bytes.into());
 
-        let resp = self.call_inner(request).await?;
+        let resp = self.call_inner(request, true).await?;
 
         Ok(ClientStreaming::new(resp))
     }
@@ -189,13 +215,73 @@ impl RpcClient {
     async fn call_inner(
         &mut self,
         request: BaseRequest<Bytes>,
+        is_streaming: bool,
     ) -> Result<mpsc::Receiver<Result<Response<Bytes>, RpcStatus>>, RpcError> {
+        let observed_request = self
+            .call_observer
+            .as_ref()
+            .map(|_| (request.method().id(), request.message.clone()));
         let svc = self.connector.ready().await?;
         let resp = svc.call(request).await?;
-        Ok(resp)
+        match (self.call_observer.clone(), observed_request) {
+            (Some((protocol_id, observer)), Some((method, request))) => Ok(observe_call(
+                ObservedRpcCall {
+                    protocol_id,
+                    method,
+                    request,
+                    responses: Vec::new(),
+                    status: None,
+                },
+                is_streaming,
+                resp,
+                observer,
+            )),
+            _ => Ok(resp),
+        }
     }
 }
 
+/// Forwards the responses of a call to the caller, keeping a copy of each response payload, and notifies the
+/// observer once the call has completed.
+fn observe_call(
+    mut call: ObservedRpcCall,
+    is_streaming: bool,
+    mut responses: mpsc::Receiver<Result<Response<Bytes>, RpcStatus>>,
+    observer: Arc<dyn RpcCallObserver>,
+) -> mpsc::Receiver<Result<Response<Bytes>, RpcStatus>> {
+    let (tx, rx) = mpsc::channel(1);
+    tokio::spawn(async move {
+        while let Some(resp) = responses.recv().await {
+            let is_last = match &resp {
+                Ok(resp) => {
+                    // A streaming call is terminated by an empty message with the FIN flag set. Unary responses
+                    // carry their payload in the single FIN message.
+                    if !is_streaming || !resp.is_finished() {
+                        call.responses.push(resp.payload.clone());
+                    }
+                    !is_streaming || resp.is_finished()
+                },
+                Err(status) => {
+                    call.status = Some(status.clone());
+                    true
+                },
+            };
+            if is_last {
+                // Notify the observer first so that the call has been observed by the time the caller sees it
+                // complete
+                observer.on_call_completed(call);
+                let _result = tx.send(resp).await;
+                return;
+            }
+            if tx.send(resp).await.is_err() {
+                break;
+            }
+        }
+        observer.on_call_completed(call);
+    });
+    rx
+}
+
 impl fmt::Debug for RpcClient {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "RpcClient {{ inner: ... }}")
@@ -207,6 +293,7 @@ pub struct RpcClientBuilder<TClient> {
     config: RpcClientConfig,
     protocol_id: Option<ProtocolId>,
     node_id: Option<NodeId>,
+    call_observer: Option<Arc<dyn RpcCallObserver>>,
     _client: PhantomData<TClient>,
 }
 
@@ -216,6 +303,7 @@ impl<TClient> Default for RpcClientBuilder<TClient> {
             config: Default::default(),
             protocol_id: None,
             node_id: None,
+            call_observer: None,
             _client: PhantomData,
         }
     }
@@ -266,6 +354,13 @@ impl<TClient> RpcClientBuilder<TClient> {
         self.node_id = Some(node_id);
         self
     }
+
+    /// Pass every request made by the client, and the responses to it, to the given observer.
+    /// Default: None
+    pub fn with_call_observer(mut self, observer: Arc<dyn RpcCallObserver>) -> Self {
+        self.call_observer = Some(observer);
+        self
+    }
 }
 
 impl<TClient> RpcClientBuilder<TClient>
@@ -274,17 +369,20 @@ where TClient: From<RpcClient> + NamedProtocolService
     /// Negotiates and establishes a session to the peer's RPC service
     pub async fn connect<TSubstream>(self, framed: CanonicalFraming<TSubstream>) -> Result<TClient, RpcError>
     where TSubstream: AsyncRead + AsyncWrite + Unpin + Send + StreamId + 'static {
-        RpcClient::connect(
+        let protocol_id = self
+            .protocol_id
+            .as_ref()
+            .cloned()
+            .unwrap_or_else(|| ProtocolId::from_static(TClient::PROTOCOL_NAME));
+        let mut client = RpcClient::connect(
             self.config,
             self.node_id.unwrap_or_default(),
             framed,
-            self.protocol_id
-                .as_ref()
-                .cloned()
-                .unwrap_or_else(|| ProtocolId::from_static(TClient::PROTOCOL_NAME)),
+            protocol_id.clone(),
         )
-        .await
-        .map(Into::into)
+        .await?;
+        client.call_observer = self.call_observer.map(|observer| (protocol_id, observer));
+        Ok(client.into())
     }
 }
 
@@ -124,7 +124,6 @@ impl<T> BaseRequest<T> {
         Self { method, message }
     }
 
-    #[allow(dead_code)]
     pub fn method(&self) -> RpcMethod {
         self.method
     }
@@ -60,6 +60,8 @@ mod client;
 pub use client::{
     pool,
     pool::{RpcClientLease, RpcClientPool, RpcClientPoolError, RpcPoolClient},
+    ObservedRpcCall,
+    RpcCallObserver,
     RpcClient,
     RpcClientBuilder,
     RpcClientConfig,
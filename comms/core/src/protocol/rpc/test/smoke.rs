@@ -23,6 +23,7 @@
 use std::{sync::Arc, time::Duration};
 
 use futures::StreamExt;
+use prost::Message;
 use tari_shutdown::Shutdown;
 use tari_test_utils::unpack_enum;
 use tari_utilities::hex::Hex;
@@ -57,6 +58,8 @@ use crate::{
                 },
                 mock::create_mocked_rpc_context,
             },
+            ObservedRpcCall,
+            RpcCallObserver,
             RpcError,
             RpcServer,
             RpcServerBuilder,
@@ -154,6 +157,68 @@ pub(super) async fn setup<T: GreetingRpc>(
     (inbound_control, outbound, server_hnd, node_identity, shutdown)
 }
 
+#[derive(Debug)]
+struct TestCallObserver(mpsc::UnboundedSender<ObservedRpcCall>);
+
+impl RpcCallObserver for TestCallObserver {
+    fn on_call_completed(&self, call: ObservedRpcCall) {
+        self.0.send(call).unwrap();
+    }
+}
+
+#[tokio::test]
+async fn call_observer_receives_requests_and_responses() {
+    let (_inbound, outbound, server_hnd, _, mut shutdown) = setup(GreetingService::default(), 1).await;
+    let socket = outbound.get_yamux_control().open_stream().await.unwrap();
+
+    let (calls_tx, mut calls_rx) = mpsc::unbounded_channel();
+    let framed = framing::canonical(socket, 1024);
+    let mut client = GreetingClient::builder()
+        .with_call_observer(Arc::new(TestCallObserver(calls_tx)))
+        .connect(framed)
+        .await
+        .unwrap();
+
+    let request = SayHelloRequest {
+        name: "Yathvan".to_string(),
+        language: 1,
+    };
+    let request_bytes = request.encode_to_vec();
+    client.say_hello(request).await.unwrap();
+    let call = calls_rx.try_recv().unwrap();
+    assert_eq!(call.protocol_id, ProtocolId::from_static(GreetingClient::PROTOCOL_NAME));
+    assert_eq!(call.method, 1);
+    assert_eq!(call.request, request_bytes);
+    assert_eq!(call.responses.len(), 1);
+    assert!(call.status.is_none());
+
+    let greetings = client
+        .get_greetings(2)
+        .await
+        .unwrap()
+        .map(|r| r.unwrap())
+        .collect::<Vec<_>>()
+        .await;
+    let call = calls_rx.try_recv().unwrap();
+    assert_eq!(call.method, 3);
+    let responses = call
+        .responses
+        .into_iter()
+        .map(|r| String::decode(r).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(responses, greetings);
+    assert!(call.status.is_none());
+
+    client.return_error().await.unwrap_err();
+    let call = calls_rx.try_recv().unwrap();
+    assert_eq!(call.method, 2);
+    assert!(call.responses.is_empty());
+    assert_eq!(call.status.unwrap().as_status_code(), RpcStatusCode::NotImplemented);
+
+    shutdown.trigger();
+    server_hnd.await.unwrap();
+}
+
 #[tokio::test]
 async fn request_response_errors_and_streaming() {
     let (_inbound, outbound, server_hnd, node_identity, mut shutdown) = setup(GreetingService::default(), 1).await;
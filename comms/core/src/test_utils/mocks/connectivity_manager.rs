@@ -285,6 +285,7 @@ impl ConnectivityManagerMock {
                     })
                     .await
             },
+            RecordReputationEvent(_, _) => {},
             AddPeerToAllowList(_) => {},
             RemovePeerFromAllowList(_) => {},
             GetActiveConnections(reply) => {
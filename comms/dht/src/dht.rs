@@ -40,13 +40,14 @@ use self::outbound::OutboundMessageRequester;
 use crate::{
     actor::{DhtActor, DhtRequest, DhtRequester},
     connectivity::{DhtConnectivity, MetricsCollector, MetricsCollectorHandle},
+    diagnostics::DhtDiagnostics,
     discovery::{DhtDiscoveryRequest, DhtDiscoveryRequester, DhtDiscoveryService},
     event::{DhtEventReceiver, DhtEventSender},
     filter,
     inbound,
     inbound::{DecryptedDhtMessage, DhtInboundMessage, ForwardLayer, MetricsLayer},
     logging_middleware::MessageLoggingLayer,
-    network_discovery::DhtNetworkDiscovery,
+    network_discovery::{DhtNetworkDiscovery, DiscoveryStateHistory},
     outbound,
     outbound::DhtOutboundRequest,
     proto::envelope::DhtMessageType,
@@ -105,6 +106,8 @@ pub struct Dht {
     event_publisher: DhtEventSender,
     /// Used by MetricsLayer to collect metrics and to inform heuristics for peer banning
     metrics_collector: MetricsCollectorHandle,
+    /// Recent network discovery state transitions
+    discovery_state_history: DiscoveryStateHistory,
 }
 
 impl Dht {
@@ -136,6 +139,7 @@ impl Dht {
             connectivity,
             discovery_sender,
             event_publisher,
+            discovery_state_history: DiscoveryStateHistory::new(),
         };
 
         let conn = DbConnection::connect_and_migrate(&dht.config.database_url.clone())
@@ -225,6 +229,7 @@ impl Dht {
             Arc::clone(&self.peer_manager),
             self.connectivity.clone(),
             self.event_publisher.clone(),
+            self.discovery_state_history.clone(),
             shutdown_signal,
         )
     }
@@ -279,6 +284,18 @@ impl Dht {
         self.metrics_collector.clone()
     }
 
+    /// Returns a handle used to generate DHT diagnostics reports
+    pub fn diagnostics(&self) -> DhtDiagnostics {
+        DhtDiagnostics::new(
+            self.config.clone(),
+            Arc::clone(&self.node_identity),
+            Arc::clone(&self.peer_manager),
+            self.connectivity.clone(),
+            self.store_and_forward_requester(),
+            self.discovery_state_history.clone(),
+        )
+    }
+
     /// Returns an the full DHT stack as a `tower::layer::Layer`. This can be composed with
     /// other inbound middleware services which expect an DecryptedDhtMessage
     pub fn inbound_middleware_layer<S>(
@@ -333,6 +350,7 @@ impl Dht {
                 Arc::clone(&self.node_identity),
                 self.outbound_requester(),
                 self.saf_response_signal_sender.clone(),
+                self.event_publisher.clone(),
             ))
             .layer(inbound::DhtHandlerLayer::new(
                 self.config.clone(),
@@ -0,0 +1,136 @@
+//  Copyright 2024, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::fmt::Write;
+
+use serde_json::{json, Value};
+
+use super::{DhtDiagnosticsReport, PeerDiagnostics};
+
+impl DhtDiagnosticsReport {
+    /// Renders the known peers as a graph in the Graphviz DOT format. Each peer is connected to this node by an edge
+    /// labelled with its distance bucket. Neighbours are drawn in bold, connected peers are filled and banned peers
+    /// are drawn in red.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::new();
+        let _ = writeln!(dot, "digraph dht {{");
+        let _ = writeln!(dot, "  rankdir=LR;");
+        let _ = writeln!(dot, "  node [shape=box, fontname=\"monospace\"];");
+        let _ = writeln!(
+            dot,
+            "  \"{}\" [label=\"{}\\n(this node)\", shape=doublecircle];",
+            self.node_id,
+            self.node_id.short_str()
+        );
+        for peer in &self.peers {
+            let _ = writeln!(
+                dot,
+                "  \"{}\" [label=\"{}\\n{}\", {}];",
+                peer.node_id,
+                peer.node_id.short_str(),
+                peer.features.as_role_str(),
+                dot_node_style(peer)
+            );
+            let _ = writeln!(
+                dot,
+                "  \"{}\" -> \"{}\" [label=\"{}\"{}];",
+                self.node_id,
+                peer.node_id,
+                peer.bucket,
+                if peer.is_connected { "" } else { ", style=dashed" }
+            );
+        }
+        let _ = writeln!(dot, "}}");
+        dot
+    }
+
+    /// Returns the report as JSON. Distances are encoded as decimal strings because they exceed the range of a JSON
+    /// number.
+    pub fn to_json(&self) -> Value {
+        json!({
+            "generated_at": self.generated_at.to_rfc3339(),
+            "node_id": self.node_id.to_string(),
+            "public_key": self.public_key.to_string(),
+            "features": self.features.to_string(),
+            "num_neighbouring_nodes": self.num_neighbouring_nodes,
+            "num_connected": self.num_connected(),
+            "peers": self.peers.iter().map(peer_to_json).collect::<Vec<_>>(),
+            "buckets": self.buckets.iter().map(|b| json!({
+                "index": b.index,
+                "num_peers": b.num_peers,
+                "num_connected": b.num_connected,
+                "num_offline": b.num_offline,
+                "num_banned": b.num_banned,
+            })).collect::<Vec<_>>(),
+            "network_discovery": {
+                "current_state": self.network_discovery.current_state,
+                "transitions": self.network_discovery.transitions.iter().map(|t| json!({
+                    "timestamp": t.timestamp.to_rfc3339(),
+                    "from_state": t.from_state,
+                    "event": t.event,
+                    "to_state": t.to_state,
+                })).collect::<Vec<_>>(),
+            },
+            "store_and_forward": {
+                "total": self.store_and_forward.total,
+                "num_high_priority": self.store_and_forward.num_high_priority,
+                "num_low_priority": self.store_and_forward.num_low_priority,
+                "num_encrypted": self.store_and_forward.num_encrypted,
+                "num_anonymous": self.store_and_forward.num_anonymous,
+                "total_body_bytes": self.store_and_forward.total_body_bytes,
+                "oldest": self.store_and_forward.oldest.map(|dt| dt.to_string()),
+                "newest": self.store_and_forward.newest.map(|dt| dt.to_string()),
+            },
+        })
+    }
+}
+
+fn dot_node_style(peer: &PeerDiagnostics) -> String {
+    let mut style = Vec::with_capacity(2);
+    if peer.is_connected {
+        style.push("filled");
+    }
+    if peer.is_neighbour {
+        style.push("bold");
+    }
+    if peer.is_offline {
+        style.push("dotted");
+    }
+    let color = if peer.is_banned { "red" } else { "black" };
+    format!("style=\"{}\", color={}", style.join(","), color)
+}
+
+fn peer_to_json(peer: &PeerDiagnostics) -> Value {
+    json!({
+        "node_id": peer.node_id.to_string(),
+        "public_key": peer.public_key.to_string(),
+        "features": peer.features.to_string(),
+        "distance": peer.distance.to_string(),
+        "bucket": peer.bucket,
+        "is_neighbour": peer.is_neighbour,
+        "is_connected": peer.is_connected,
+        "is_banned": peer.is_banned,
+        "is_offline": peer.is_offline,
+        "last_seen": peer.last_seen.map(|dt| dt.to_string()),
+        "reputation_score": peer.reputation_score,
+    })
+}
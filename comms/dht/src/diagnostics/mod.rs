@@ -0,0 +1,136 @@
+//  Copyright 2024, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! # DHT Diagnostics
+//!
+//! Produces a report of how this node sees the DHT: its closest-peer neighbourhood, distance bucket occupancy,
+//! network discovery state transitions and a summary of the store and forward buffer. The report can be exported
+//! as a DOT or JSON graph of known peers and their distances.
+
+mod error;
+pub use error::DhtDiagnosticsError;
+
+mod export;
+
+mod report;
+use std::{collections::HashSet, sync::Arc};
+
+use chrono::Utc;
+pub use report::{BucketOccupancy, DhtDiagnosticsReport, NetworkDiscoveryDiagnostics, PeerDiagnostics};
+use tari_comms::{
+    connectivity::ConnectivityRequester,
+    peer_manager::{NodeIdentity, PeerFeatures, PeerManager},
+};
+
+use crate::{network_discovery::DiscoveryStateHistory, store_forward::StoreAndForwardRequester, DhtConfig};
+
+/// Handle used to generate [DhtDiagnosticsReport]s. This is obtained from `Dht::diagnostics`.
+#[derive(Clone)]
+pub struct DhtDiagnostics {
+    config: Arc<DhtConfig>,
+    node_identity: Arc<NodeIdentity>,
+    peer_manager: Arc<PeerManager>,
+    connectivity: ConnectivityRequester,
+    saf_requester: StoreAndForwardRequester,
+    discovery_state_history: DiscoveryStateHistory,
+}
+
+impl DhtDiagnostics {
+    pub(crate) fn new(
+        config: Arc<DhtConfig>,
+        node_identity: Arc<NodeIdentity>,
+        peer_manager: Arc<PeerManager>,
+        connectivity: ConnectivityRequester,
+        saf_requester: StoreAndForwardRequester,
+        discovery_state_history: DiscoveryStateHistory,
+    ) -> Self {
+        Self {
+            config,
+            node_identity,
+            peer_manager,
+            connectivity,
+            saf_requester,
+            discovery_state_history,
+        }
+    }
+
+    /// Generates a diagnostics report of the current DHT state
+    pub async fn generate_report(&self) -> Result<DhtDiagnosticsReport, DhtDiagnosticsError> {
+        let node_id = self.node_identity.node_id();
+        let connected = self
+            .connectivity
+            .clone()
+            .get_active_connections()
+            .await?
+            .into_iter()
+            .map(|conn| conn.peer_node_id().clone())
+            .collect::<HashSet<_>>();
+
+        let neighbours = self
+            .peer_manager
+            .closest_peers(
+                node_id,
+                self.config.num_neighbouring_nodes,
+                &[],
+                Some(PeerFeatures::COMMUNICATION_NODE),
+            )
+            .await?
+            .into_iter()
+            .map(|peer| peer.node_id)
+            .collect::<HashSet<_>>();
+
+        let mut peers = self
+            .peer_manager
+            .all()
+            .await?
+            .iter()
+            .filter(|peer| peer.deleted_at.is_none() && peer.node_id != *node_id)
+            .map(|peer| {
+                report::PeerDiagnostics::new(
+                    node_id,
+                    peer,
+                    neighbours.contains(&peer.node_id),
+                    connected.contains(&peer.node_id),
+                )
+            })
+            .collect::<Vec<_>>();
+        peers.sort_by(|a, b| a.distance.cmp(&b.distance));
+        let buckets = BucketOccupancy::from_peers(&peers);
+
+        let store_and_forward = self.saf_requester.clone().get_stored_messages_summary().await?;
+
+        Ok(DhtDiagnosticsReport {
+            generated_at: Utc::now(),
+            node_id: node_id.clone(),
+            public_key: self.node_identity.public_key().clone(),
+            features: self.node_identity.features(),
+            num_neighbouring_nodes: self.config.num_neighbouring_nodes,
+            peers,
+            buckets,
+            network_discovery: NetworkDiscoveryDiagnostics {
+                current_state: self.discovery_state_history.current_state().await,
+                transitions: self.discovery_state_history.transitions().await,
+            },
+            store_and_forward,
+        })
+    }
+}
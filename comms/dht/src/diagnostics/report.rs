@@ -0,0 +1,172 @@
+//  Copyright 2024, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use tari_comms::{
+    peer_manager::{NodeDistance, NodeId, Peer, PeerFeatures},
+    types::CommsPublicKey,
+};
+
+use crate::{network_discovery::DiscoveryStateTransition, store_forward::StoredMessagesSummary};
+
+/// A snapshot of how this node sees the DHT
+#[derive(Debug, Clone)]
+pub struct DhtDiagnosticsReport {
+    pub generated_at: DateTime<Utc>,
+    pub node_id: NodeId,
+    pub public_key: CommsPublicKey,
+    pub features: PeerFeatures,
+    /// The configured number of neighbouring nodes
+    pub num_neighbouring_nodes: usize,
+    /// All known (non-deleted) peers ordered by distance from this node
+    pub peers: Vec<PeerDiagnostics>,
+    /// Peer occupancy of each non-empty distance bucket, ordered by bucket index
+    pub buckets: Vec<BucketOccupancy>,
+    pub network_discovery: NetworkDiscoveryDiagnostics,
+    pub store_and_forward: StoredMessagesSummary,
+}
+
+impl DhtDiagnosticsReport {
+    /// Returns the peers that are within this node's closest-peer neighbourhood
+    pub fn neighbours(&self) -> impl Iterator<Item = &PeerDiagnostics> + '_ {
+        self.peers.iter().filter(|p| p.is_neighbour)
+    }
+
+    /// Returns the number of known peers that are currently connected
+    pub fn num_connected(&self) -> usize {
+        self.peers.iter().filter(|p| p.is_connected).count()
+    }
+}
+
+/// Diagnostic information about a single known peer
+#[derive(Debug, Clone)]
+pub struct PeerDiagnostics {
+    pub node_id: NodeId,
+    pub public_key: CommsPublicKey,
+    pub features: PeerFeatures,
+    /// The XOR distance from this node
+    pub distance: NodeDistance,
+    /// The distance bucket that this peer falls into
+    pub bucket: u8,
+    /// True if the peer is one of this node's closest communication node neighbours
+    pub is_neighbour: bool,
+    pub is_connected: bool,
+    pub is_banned: bool,
+    pub is_offline: bool,
+    pub last_seen: Option<NaiveDateTime>,
+    pub reputation_score: i64,
+}
+
+impl PeerDiagnostics {
+    pub(super) fn new(node_id: &NodeId, peer: &Peer, is_neighbour: bool, is_connected: bool) -> Self {
+        let distance = node_id.distance(&peer.node_id);
+        Self {
+            node_id: peer.node_id.clone(),
+            public_key: peer.public_key.clone(),
+            features: peer.features,
+            bucket: distance.get_bucket_index(),
+            distance,
+            is_neighbour,
+            is_connected,
+            is_banned: peer.is_banned(),
+            is_offline: peer.is_offline(),
+            last_seen: peer.last_seen(),
+            reputation_score: peer.reputation_score(),
+        }
+    }
+}
+
+/// The number of peers in a distance bucket. A peer falls in bucket `i` if 2^i <= distance < 2^(i+1).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BucketOccupancy {
+    pub index: u8,
+    pub num_peers: usize,
+    pub num_connected: usize,
+    pub num_offline: usize,
+    pub num_banned: usize,
+}
+
+impl BucketOccupancy {
+    pub(super) fn from_peers(peers: &[PeerDiagnostics]) -> Vec<Self> {
+        let mut buckets = Vec::<Self>::new();
+        // Peers are ordered by distance and therefore by bucket
+        for peer in peers {
+            if buckets.last().map(|b| b.index != peer.bucket).unwrap_or(true) {
+                buckets.push(Self {
+                    index: peer.bucket,
+                    ..Default::default()
+                });
+            }
+            let bucket = buckets.last_mut().expect("a bucket was pushed if empty");
+            bucket.num_peers += 1;
+            if peer.is_connected {
+                bucket.num_connected += 1;
+            }
+            if peer.is_offline {
+                bucket.num_offline += 1;
+            }
+            if peer.is_banned {
+                bucket.num_banned += 1;
+            }
+        }
+        buckets
+    }
+}
+
+/// The state of the network discovery state machine
+#[derive(Debug, Clone, Default)]
+pub struct NetworkDiscoveryDiagnostics {
+    /// The current state, or None if network discovery has not started or is disabled
+    pub current_state: Option<String>,
+    /// The most recent state transitions, oldest first
+    pub transitions: Vec<DiscoveryStateTransition>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_utils::make_node_identity;
+
+    fn make_peer_diagnostics(node_id: &NodeId, is_connected: bool) -> PeerDiagnostics {
+        let peer = make_node_identity().to_peer();
+        PeerDiagnostics::new(node_id, &peer, false, is_connected)
+    }
+
+    #[test]
+    fn it_calculates_bucket_occupancy() {
+        let node_identity = make_node_identity();
+        let node_id = node_identity.node_id();
+        let mut peers = (0..10)
+            .map(|i| make_peer_diagnostics(node_id, i % 2 == 0))
+            .collect::<Vec<_>>();
+        peers.sort_by(|a, b| a.distance.cmp(&b.distance));
+
+        let buckets = BucketOccupancy::from_peers(&peers);
+        assert_eq!(buckets.iter().map(|b| b.num_peers).sum::<usize>(), 10);
+        assert_eq!(buckets.iter().map(|b| b.num_connected).sum::<usize>(), 5);
+        assert!(buckets.windows(2).all(|w| w[0].index < w[1].index));
+        for bucket in &buckets {
+            let expected = peers.iter().filter(|p| p.bucket == bucket.index).count();
+            assert_eq!(bucket.num_peers, expected);
+        }
+    }
+}
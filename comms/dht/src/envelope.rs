@@ -107,7 +107,7 @@ impl DhtMessageType {
     }
 
     pub fn is_forwardable(self) -> bool {
-        self.is_domain_message() || self.is_dht_discovery() || self.is_dht_join()
+        self.is_domain_message() || self.is_dht_discovery() || self.is_dht_join() || self.is_saf_delivery_receipt()
     }
 
     pub fn is_dht_discovery(self) -> bool {
@@ -122,6 +122,10 @@ impl DhtMessageType {
         matches!(self, DhtMessageType::Join)
     }
 
+    pub fn is_saf_delivery_receipt(self) -> bool {
+        matches!(self, DhtMessageType::SafDeliveryReceipt)
+    }
+
     pub fn is_saf_message(self) -> bool {
         use DhtMessageType::{SafRequestMessages, SafStoredMessages};
         matches!(self, SafRequestMessages | SafStoredMessages)
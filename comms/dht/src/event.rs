@@ -24,7 +24,7 @@ use std::sync::Arc;
 
 use tokio::sync::broadcast;
 
-use crate::network_discovery::DhtNetworkDiscoveryRoundInfo;
+use crate::{network_discovery::DhtNetworkDiscoveryRoundInfo, store_forward::DeliveryReceipt};
 
 pub type DhtEventSender = broadcast::Sender<Arc<DhtEvent>>;
 pub type DhtEventReceiver = broadcast::Receiver<Arc<DhtEvent>>;
@@ -36,6 +36,10 @@ pub enum DhtEvent {
     /// Emitted by the store and forward service upon receipt of a sufficient number of store and forward messages
     StoreAndForwardMessagesReceived,
 
+    /// Emitted by the store and forward message handler when a peer has retrieved one or more of our messages from
+    /// store and forward and sent a delivery receipt.
+    StoreAndForwardMessagesDelivered(DeliveryReceipt),
+
     /// Emitted by the NetworkDiscovery actor once a round of peer syncing has completed.
     NetworkDiscoveryPeersAdded(DhtNetworkDiscoveryRoundInfo),
 }
@@ -85,6 +85,8 @@ pub use config::{DhtConfig, DhtConnectivityConfig};
 
 mod crypt;
 
+pub mod diagnostics;
+
 mod dht;
 pub use dht::{Dht, DhtInitializationError};
 
@@ -95,7 +97,7 @@ mod error;
 pub use error::DhtEncryptError;
 
 mod network_discovery;
-pub use network_discovery::NetworkDiscoveryConfig;
+pub use network_discovery::{DiscoveryStateHistory, DiscoveryStateTransition, NetworkDiscoveryConfig};
 
 mod storage;
 pub use storage::DbConnectionUrl;
@@ -0,0 +1,110 @@
+//  Copyright 2024, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{collections::VecDeque, fmt, sync::Arc};
+
+use chrono::{DateTime, Utc};
+use tokio::sync::RwLock;
+
+/// The maximum number of state transitions that are retained
+const MAX_STATE_HISTORY: usize = 50;
+
+/// A state transition of the network discovery state machine
+#[derive(Debug, Clone)]
+pub struct DiscoveryStateTransition {
+    pub timestamp: DateTime<Utc>,
+    pub from_state: String,
+    pub event: String,
+    pub to_state: String,
+}
+
+impl fmt::Display for DiscoveryStateTransition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} --({})--> {}",
+            self.timestamp.format("%Y-%m-%d %H:%M:%S"),
+            self.from_state,
+            self.event,
+            self.to_state
+        )
+    }
+}
+
+/// Keeps a bounded history of the most recent network discovery state transitions. This is cheap to clone and
+/// shared between the state machine and diagnostics.
+#[derive(Debug, Clone, Default)]
+pub struct DiscoveryStateHistory {
+    transitions: Arc<RwLock<VecDeque<DiscoveryStateTransition>>>,
+}
+
+impl DiscoveryStateHistory {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub(super) async fn record(&self, from_state: String, event: String, to_state: String) {
+        let mut transitions = self.transitions.write().await;
+        if transitions.len() >= MAX_STATE_HISTORY {
+            transitions.pop_front();
+        }
+        transitions.push_back(DiscoveryStateTransition {
+            timestamp: Utc::now(),
+            from_state,
+            event,
+            to_state,
+        });
+    }
+
+    /// Returns the current state of the state machine, or None if the state machine has not transitioned yet
+    pub async fn current_state(&self) -> Option<String> {
+        self.transitions.read().await.back().map(|t| t.to_state.clone())
+    }
+
+    /// Returns the recorded state transitions, oldest first
+    pub async fn transitions(&self) -> Vec<DiscoveryStateTransition> {
+        self.transitions.read().await.iter().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    async fn it_keeps_a_bounded_history() {
+        let history = DiscoveryStateHistory::new();
+        assert!(history.current_state().await.is_none());
+        for i in 0..(MAX_STATE_HISTORY + 5) {
+            history
+                .record(format!("State{}", i), "Event".to_string(), format!("State{}", i + 1))
+                .await;
+        }
+        let transitions = history.transitions().await;
+        assert_eq!(transitions.len(), MAX_STATE_HISTORY);
+        assert_eq!(transitions[0].from_state, "State5");
+        assert_eq!(
+            history.current_state().await.unwrap(),
+            format!("State{}", MAX_STATE_HISTORY + 5)
+        );
+    }
+}
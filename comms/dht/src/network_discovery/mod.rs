@@ -35,6 +35,9 @@ mod discovering;
 mod error;
 pub use error::NetworkDiscoveryError;
 
+mod history;
+pub use history::{DiscoveryStateHistory, DiscoveryStateTransition};
+
 mod initializing;
 mod on_connect;
 mod ready;
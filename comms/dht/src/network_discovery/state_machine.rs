@@ -43,6 +43,7 @@ use crate::{
     event::DhtEvent,
     network_discovery::{
         discovering::Discovering,
+        history::DiscoveryStateHistory,
         initializing::Initializing,
         on_connect::OnConnect,
         ready::DiscoveryReady,
@@ -166,6 +167,7 @@ impl NetworkDiscoveryContext {
 
 pub struct DhtNetworkDiscovery {
     context: NetworkDiscoveryContext,
+    state_history: DiscoveryStateHistory,
     shutdown_signal: ShutdownSignal,
 }
 
@@ -176,6 +178,7 @@ impl DhtNetworkDiscovery {
         peer_manager: Arc<PeerManager>,
         connectivity: ConnectivityRequester,
         event_tx: broadcast::Sender<Arc<DhtEvent>>,
+        state_history: DiscoveryStateHistory,
         shutdown_signal: ShutdownSignal,
     ) -> Self {
         Self {
@@ -189,6 +192,7 @@ impl DhtNetworkDiscovery {
                 last_round: Default::default(),
                 event_tx,
             },
+            state_history,
             shutdown_signal,
         }
     }
@@ -276,7 +280,10 @@ impl DhtNetworkDiscovery {
                 futures::pin_mut!(fut);
                 or_shutdown(shutdown_signal, fut).await
             };
+            let from_state = state.to_string();
+            let event = next_event.to_string();
             state = self.transition(state, next_event).await;
+            self.state_history.record(from_state, event, state.to_string()).await;
             if state.is_shutdown() {
                 break;
             }
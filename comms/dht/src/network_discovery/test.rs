@@ -86,6 +86,7 @@ mod state_machine {
             peer_manager.clone(),
             connectivity,
             event_tx,
+            Default::default(),
             shutdown.to_signal(),
         );
 
@@ -61,6 +61,9 @@ pub struct SafConfig {
     /// The maximum number of peer nodes that a message must be closer than to get stored by SAF
     /// Default: 8
     pub num_neighbouring_nodes: usize,
+    /// When true, a delivery receipt is sent to the origin of encrypted messages retrieved from store and forward
+    /// (Default: true)
+    pub send_delivery_receipts: bool,
 }
 
 impl Default for SafConfig {
@@ -76,6 +79,7 @@ impl Default for SafConfig {
             max_message_size: 512 * 1024,
             max_inflight_request_age: Duration::from_secs(120),
             num_neighbouring_nodes: 8,
+            send_delivery_receipts: true,
         }
     }
 }
@@ -21,9 +21,13 @@
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 mod stored_message;
+mod summary;
+use std::convert::TryFrom;
+
 use chrono::{DateTime, NaiveDateTime, Utc};
 use diesel::{dsl, result::DatabaseErrorKind, BoolExpressionMethods, ExpressionMethods, QueryDsl, RunQueryDsl};
 pub use stored_message::{NewStoredMessage, StoredMessage};
+pub use summary::StoredMessagesSummary;
 use tari_comms::{peer_manager::NodeId, types::CommsPublicKey};
 use tari_utilities::hex::Hex;
 
@@ -84,7 +88,10 @@ impl StoreAndForwardDatabase {
                     .eq(pk_hex)
                     .or(stored_messages::destination_node_id.eq(node_id_hex)),
             )
-            .filter(stored_messages::message_type.eq(DhtMessageType::None as i32))
+            .filter(stored_messages::message_type.eq_any(vec![
+                DhtMessageType::None as i32,
+                DhtMessageType::SafDeliveryReceipt as i32,
+            ]))
             .into_boxed();
 
         if let Some(since) = since {
@@ -171,6 +178,32 @@ impl StoreAndForwardDatabase {
             .map_err(Into::into)
     }
 
+    /// Returns a summary of all stored messages. Message bodies are not loaded.
+    pub fn get_summary(&self) -> Result<StoredMessagesSummary, StorageError> {
+        let mut conn = self.connection.get_pooled_connection()?;
+        let rows = stored_messages::table
+            .select((
+                stored_messages::priority,
+                stored_messages::is_encrypted,
+                stored_messages::destination_pubkey,
+                dsl::sql::<diesel::sql_types::BigInt>("length(body)"),
+                stored_messages::stored_at,
+            ))
+            .load::<(i32, bool, Option<String>, i64, NaiveDateTime)>(&mut conn)?;
+
+        let mut summary = StoredMessagesSummary::default();
+        for (priority, is_encrypted, destination_pubkey, body_len, stored_at) in rows {
+            summary.add(
+                priority,
+                is_encrypted,
+                destination_pubkey.is_some(),
+                usize::try_from(body_len).unwrap_or(0),
+                stored_at,
+            );
+        }
+        Ok(summary)
+    }
+
     #[cfg(test)]
     pub(crate) fn get_all_messages(&self) -> Result<Vec<StoredMessage>, StorageError> {
         let mut conn = self.connection.get_pooled_connection()?;
@@ -302,4 +335,32 @@ mod test {
         assert_eq!(messages[0].body_hash, msg3.body_hash);
         assert_eq!(messages[1].body_hash, msg4.body_hash);
     }
+
+    #[tokio::test]
+    async fn get_summary() {
+        let conn = DbConnection::connect_memory(random::string(8)).unwrap();
+        conn.migrate().unwrap();
+        let db = StoreAndForwardDatabase::new(conn);
+        let mut msg1 = NewStoredMessage::default();
+        msg1.body_hash.push('1');
+        msg1.body = vec![1, 2, 3];
+        msg1.priority = StoredMessagePriority::High as i32;
+        msg1.is_encrypted = true;
+        msg1.destination_pubkey = Some("abcd".to_string());
+        let mut msg2 = NewStoredMessage::default();
+        msg2.body_hash.push('2');
+        msg2.body = vec![1];
+        msg2.priority = StoredMessagePriority::Low as i32;
+        db.insert_message_if_unique(msg1).unwrap();
+        db.insert_message_if_unique(msg2).unwrap();
+
+        let summary = db.get_summary().unwrap();
+        assert_eq!(summary.total, 2);
+        assert_eq!(summary.num_high_priority, 1);
+        assert_eq!(summary.num_low_priority, 1);
+        assert_eq!(summary.num_encrypted, 1);
+        assert_eq!(summary.num_anonymous, 1);
+        assert_eq!(summary.total_body_bytes, 4);
+        assert!(summary.oldest.unwrap() <= summary.newest.unwrap());
+    }
 }
@@ -0,0 +1,98 @@
+//  Copyright 2024, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::fmt;
+
+use chrono::NaiveDateTime;
+
+use crate::store_forward::message::StoredMessagePriority;
+
+/// A summary of the messages held in the store and forward database
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StoredMessagesSummary {
+    /// The total number of stored messages
+    pub total: usize,
+    /// The number of high priority messages
+    pub num_high_priority: usize,
+    /// The number of low priority messages
+    pub num_low_priority: usize,
+    /// The number of messages that are encrypted
+    pub num_encrypted: usize,
+    /// The number of messages without a destination public key
+    pub num_anonymous: usize,
+    /// The total size of all stored message bodies in bytes
+    pub total_body_bytes: usize,
+    /// The stored time of the oldest message
+    pub oldest: Option<NaiveDateTime>,
+    /// The stored time of the newest message
+    pub newest: Option<NaiveDateTime>,
+}
+
+impl StoredMessagesSummary {
+    pub(crate) fn add(
+        &mut self,
+        priority: i32,
+        is_encrypted: bool,
+        has_destination: bool,
+        body_len: usize,
+        stored_at: NaiveDateTime,
+    ) {
+        self.total += 1;
+        if priority == StoredMessagePriority::High as i32 {
+            self.num_high_priority += 1;
+        } else {
+            self.num_low_priority += 1;
+        }
+        if is_encrypted {
+            self.num_encrypted += 1;
+        }
+        if !has_destination {
+            self.num_anonymous += 1;
+        }
+        self.total_body_bytes += body_len;
+        if self.oldest.map(|dt| stored_at < dt).unwrap_or(true) {
+            self.oldest = Some(stored_at);
+        }
+        if self.newest.map(|dt| stored_at > dt).unwrap_or(true) {
+            self.newest = Some(stored_at);
+        }
+    }
+}
+
+impl fmt::Display for StoredMessagesSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} message(s) ({} high priority, {} low priority, {} encrypted, {} anonymous, {} bytes)",
+            self.total,
+            self.num_high_priority,
+            self.num_low_priority,
+            self.num_encrypted,
+            self.num_anonymous,
+            self.total_body_bytes
+        )?;
+        if let (Some(oldest), Some(newest)) = (self.oldest, self.newest) {
+            write!(f, " stored between {} and {}", oldest, newest)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+//  Copyright 2024, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use tari_comms::{message::MessageTag, types::CommsPublicKey};
+use tari_utilities::epoch_time::EpochTime;
+
+use crate::{envelope::epochtime_to_datetime, proto::store_forward::SafDeliveryReceipt};
+
+/// A delivery receipt sent by the recipient of one or more messages once they were retrieved from store and forward.
+/// The recipient is the authenticated origin of the receipt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeliveryReceipt {
+    /// The public key of the peer that retrieved the messages
+    pub recipient: CommsPublicKey,
+    /// The DHT message tags of the messages that were delivered. These are the tags returned when the messages were
+    /// sent.
+    pub message_tags: Vec<MessageTag>,
+    /// The time at which the recipient retrieved the messages
+    pub delivered_at: DateTime<Utc>,
+}
+
+impl DeliveryReceipt {
+    pub(crate) fn from_proto(recipient: CommsPublicKey, receipt: SafDeliveryReceipt) -> Self {
+        Self {
+            recipient,
+            message_tags: receipt.message_tags.into_iter().map(MessageTag::from).collect(),
+            delivered_at: epochtime_to_datetime(EpochTime::from_secs_since_epoch(receipt.delivered_at)),
+        }
+    }
+
+    /// Returns true if the given message tag is included in this receipt
+    pub fn contains(&self, tag: MessageTag) -> bool {
+        self.message_tags.contains(&tag)
+    }
+}
+
+impl fmt::Display for DeliveryReceipt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} message(s) delivered to {} at {}",
+            self.message_tags.len(),
+            self.recipient,
+            self.delivered_at
+        )
+    }
+}
@@ -30,7 +30,7 @@ use crate::{
     envelope::datetime_to_epochtime,
     proto::{
         envelope::DhtHeader,
-        store_forward::{StoredMessage, StoredMessagesRequest, StoredMessagesResponse},
+        store_forward::{SafDeliveryReceipt, StoredMessage, StoredMessagesRequest, StoredMessagesResponse},
     },
     store_forward::{database, StoreAndForwardError},
 };
@@ -53,6 +53,15 @@ impl StoredMessagesRequest {
     }
 }
 
+impl SafDeliveryReceipt {
+    pub fn new(message_tags: Vec<u64>, delivered_at: DateTime<Utc>) -> Self {
+        Self {
+            message_tags,
+            delivered_at: datetime_to_epochtime(delivered_at).as_u64(),
+        }
+    }
+}
+
 #[cfg(test)]
 impl StoredMessage {
     pub fn new(
@@ -28,7 +28,7 @@ mod service;
 pub use service::{StoreAndForwardRequest, StoreAndForwardRequester, StoreAndForwardService};
 
 mod database;
-pub use database::StoredMessage;
+pub use database::{StoredMessage, StoredMessagesSummary};
 
 mod error;
 pub use error::StoreAndForwardError;
@@ -36,6 +36,9 @@ pub use error::StoreAndForwardError;
 mod config;
 pub use config::SafConfig;
 
+mod delivery_receipt;
+pub use delivery_receipt::DeliveryReceipt;
+
 mod message;
 
 mod saf_handler;
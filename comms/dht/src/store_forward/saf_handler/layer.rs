@@ -29,6 +29,7 @@ use tower::layer::Layer;
 use super::middleware::MessageHandlerMiddleware;
 use crate::{
     actor::DhtRequester,
+    event::DhtEventSender,
     outbound::OutboundMessageRequester,
     store_forward::{SafConfig, StoreAndForwardRequester},
 };
@@ -41,6 +42,7 @@ pub struct MessageHandlerLayer {
     node_identity: Arc<NodeIdentity>,
     outbound_service: OutboundMessageRequester,
     saf_response_signal_sender: mpsc::Sender<()>,
+    event_publisher: DhtEventSender,
 }
 
 impl MessageHandlerLayer {
@@ -51,6 +53,7 @@ impl MessageHandlerLayer {
         node_identity: Arc<NodeIdentity>,
         outbound_service: OutboundMessageRequester,
         saf_response_signal_sender: mpsc::Sender<()>,
+        event_publisher: DhtEventSender,
     ) -> Self {
         Self {
             config,
@@ -60,6 +63,7 @@ impl MessageHandlerLayer {
 
             outbound_service,
             saf_response_signal_sender,
+            event_publisher,
         }
     }
 }
@@ -76,6 +80,7 @@ impl<S> Layer<S> for MessageHandlerLayer {
             Arc::clone(&self.node_identity),
             self.outbound_service.clone(),
             self.saf_response_signal_sender.clone(),
+            self.event_publisher.clone(),
         )
     }
 }
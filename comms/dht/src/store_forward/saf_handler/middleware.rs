@@ -30,6 +30,7 @@ use tower::Service;
 use super::task::MessageHandlerTask;
 use crate::{
     actor::DhtRequester,
+    event::DhtEventSender,
     inbound::DecryptedDhtMessage,
     outbound::OutboundMessageRequester,
     store_forward::{SafConfig, StoreAndForwardRequester},
@@ -44,6 +45,7 @@ pub struct MessageHandlerMiddleware<S> {
     node_identity: Arc<NodeIdentity>,
     outbound_service: OutboundMessageRequester,
     saf_response_signal_sender: mpsc::Sender<()>,
+    event_publisher: DhtEventSender,
 }
 
 impl<S> MessageHandlerMiddleware<S> {
@@ -55,6 +57,7 @@ impl<S> MessageHandlerMiddleware<S> {
         node_identity: Arc<NodeIdentity>,
         outbound_service: OutboundMessageRequester,
         saf_response_signal_sender: mpsc::Sender<()>,
+        event_publisher: DhtEventSender,
     ) -> Self {
         Self {
             config,
@@ -65,6 +68,7 @@ impl<S> MessageHandlerMiddleware<S> {
 
             outbound_service,
             saf_response_signal_sender,
+            event_publisher,
         }
     }
 }
@@ -93,6 +97,7 @@ where
                 Arc::clone(&self.node_identity),
                 message,
                 self.saf_response_signal_sender.clone(),
+                self.event_publisher.clone(),
             )
             .run(),
         )
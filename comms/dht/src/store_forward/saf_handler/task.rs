@@ -22,6 +22,7 @@
 
 use std::{
     cmp,
+    collections::HashMap,
     convert::{TryFrom, TryInto},
     sync::Arc,
 };
@@ -46,13 +47,15 @@ use crate::{
     crypt,
     dedup,
     envelope::{epochtime_to_datetime, DhtMessageError, DhtMessageHeader, NodeDestination},
+    event::{DhtEvent, DhtEventSender},
     inbound::{DecryptedDhtMessage, DhtInboundMessage},
     message_signature::{MessageSignature, MessageSignatureError, ProtoMessageSignature},
-    outbound::{OutboundMessageRequester, SendMessageParams},
+    outbound::{OutboundEncryption, OutboundMessageRequester, SendMessageParams},
     proto::{
         envelope::DhtMessageType,
         store_forward::{
             stored_messages_response::SafResponseType,
+            SafDeliveryReceipt,
             StoredMessage as ProtoStoredMessage,
             StoredMessagesRequest,
             StoredMessagesResponse,
@@ -62,6 +65,7 @@ use crate::{
     store_forward::{
         error::StoreAndForwardError,
         service::FetchStoredMessageQuery,
+        DeliveryReceipt,
         SafConfig,
         StoreAndForwardRequester,
     },
@@ -78,6 +82,7 @@ pub struct MessageHandlerTask<S> {
     message: Option<DecryptedDhtMessage>,
     saf_requester: StoreAndForwardRequester,
     saf_response_signal_sender: mpsc::Sender<()>,
+    event_publisher: DhtEventSender,
 }
 
 impl<S> MessageHandlerTask<S>
@@ -92,6 +97,7 @@ where S: Service<DecryptedDhtMessage, Response = (), Error = PipelineError>
         node_identity: Arc<NodeIdentity>,
         message: DecryptedDhtMessage,
         saf_response_signal_sender: mpsc::Sender<()>,
+        event_publisher: DhtEventSender,
     ) -> Self {
         Self {
             config,
@@ -102,6 +108,7 @@ where S: Service<DecryptedDhtMessage, Response = (), Error = PipelineError>
             node_identity,
             message: Some(message),
             saf_response_signal_sender,
+            event_publisher,
         }
     }
 
@@ -154,6 +161,7 @@ where S: Service<DecryptedDhtMessage, Response = (), Error = PipelineError>
             },
 
             DhtMessageType::SafStoredMessages => self.handle_stored_messages(message).await?,
+            DhtMessageType::SafDeliveryReceipt => self.handle_delivery_receipt(message)?,
             // Not a SAF message, call downstream middleware
             _ => {
                 trace!(
@@ -342,6 +350,21 @@ where S: Service<DecryptedDhtMessage, Response = (), Error = PipelineError>
             .process_incoming_stored_messages(source_peer.clone(), response.messages)
             .await?;
 
+        // Delivery receipts for messages we sent while the recipient was offline are handled here and are not
+        // passed on. Receipts are never sent for receipts.
+        let (receipts, successful_messages) = successful_messages
+            .into_iter()
+            .partition::<Vec<_>, _>(|msg| msg.dht_header.message_type.is_saf_delivery_receipt());
+        for receipt in receipts {
+            if let Err(err) = self.handle_delivery_receipt(receipt) {
+                debug!(target: LOG_TARGET, "Discarding stored delivery receipt: {}", err);
+            }
+        }
+
+        if self.config.send_delivery_receipts {
+            self.send_delivery_receipts(&successful_messages).await;
+        }
+
         // Let the SAF Service know we got a SAF response.
         let _ = self
             .saf_response_signal_sender
@@ -363,6 +386,110 @@ where S: Service<DecryptedDhtMessage, Response = (), Error = PipelineError>
         Ok(())
     }
 
+    /// Sends a delivery receipt to the origin of each encrypted domain message that was retrieved from store and
+    /// forward. Messages from the same origin are acknowledged in a single receipt.
+    async fn send_delivery_receipts(&mut self, messages: &[DecryptedDhtMessage]) {
+        let mut tags_by_origin = HashMap::<CommsPublicKey, Vec<u64>>::new();
+        for msg in messages {
+            if !msg.dht_header.message_type.is_domain_message() || !msg.dht_header.flags.is_encrypted() {
+                continue;
+            }
+            let Some(origin) = msg.authenticated_origin.as_ref() else {
+                continue;
+            };
+            if origin == self.node_identity.public_key() {
+                continue;
+            }
+            tags_by_origin
+                .entry(origin.clone())
+                .or_default()
+                .push(msg.dht_header.message_tag.as_value());
+        }
+
+        let delivered_at = Utc::now();
+        for (origin, message_tags) in tags_by_origin {
+            let num_messages = message_tags.len();
+            let result = self
+                .outbound_service
+                .send_message_no_header_no_wait(
+                    SendMessageParams::new()
+                        .closest(NodeId::from_public_key(&origin), vec![])
+                        .with_destination(origin.clone().into())
+                        .with_encryption(OutboundEncryption::encrypt_for(origin.clone()))
+                        .with_dht_message_type(DhtMessageType::SafDeliveryReceipt)
+                        .finish(),
+                    SafDeliveryReceipt::new(message_tags, delivered_at),
+                )
+                .await;
+
+            match result {
+                Ok(_) => {
+                    debug!(
+                        target: LOG_TARGET,
+                        "Sent delivery receipt for {} stored message(s) to '{}'", num_messages, origin
+                    );
+                },
+                Err(err) => {
+                    warn!(
+                        target: LOG_TARGET,
+                        "Failed to send delivery receipt for {} stored message(s) to '{}': {}", num_messages, origin, err
+                    );
+                },
+            }
+        }
+    }
+
+    fn handle_delivery_receipt(&mut self, message: DecryptedDhtMessage) -> Result<(), StoreAndForwardError> {
+        if message.decryption_failed() {
+            trace!(
+                target: LOG_TARGET,
+                "Delivery receipt {} is not for this node. Discarding message. (Trace: {})",
+                message.tag,
+                message.dht_header.message_tag
+            );
+            return Ok(());
+        }
+
+        // Only accept receipts that were encrypted for us and signed by the recipient
+        let recipient = match message.authenticated_origin.as_ref() {
+            Some(pk) if message.dht_header.flags.is_encrypted() => pk.clone(),
+            _ => {
+                debug!(
+                    target: LOG_TARGET,
+                    "Received unauthenticated delivery receipt {} from peer '{}'. Discarding message. (Trace: {})",
+                    message.tag,
+                    message.source_peer.node_id.short_str(),
+                    message.dht_header.message_tag
+                );
+                return Ok(());
+            },
+        };
+
+        let msg = message
+            .success()
+            .expect("already checked that this message decrypted successfully");
+        let receipt = msg
+            .decode_part::<SafDeliveryReceipt>(0)?
+            .ok_or(StoreAndForwardError::EnvelopeBodyMissingMessagePart)?;
+        if receipt.message_tags.is_empty() {
+            return Ok(());
+        }
+
+        let receipt = DeliveryReceipt::from_proto(recipient, receipt);
+        debug!(target: LOG_TARGET, "Received delivery receipt: {}", receipt);
+        let _ = self
+            .event_publisher
+            .send(Arc::new(DhtEvent::StoreAndForwardMessagesDelivered(receipt)))
+            .map_err(|_| {
+                trace!(
+                    target: LOG_TARGET,
+                    "Could not publish DhtEvent as there are no subscribers"
+                )
+            });
+
+        Ok(())
+    }
+
     async fn process_incoming_stored_messages(
         &mut self,
         source_peer: Arc<Peer>,
@@ -755,9 +882,9 @@ mod test {
 
     use chrono::Timelike;
     use tari_comms::{message::MessageExt, wrap_in_envelope_body};
-    use tari_test_utils::collect_recv;
+    use tari_test_utils::{collect_recv, unpack_enum};
     use tari_utilities::{hex, hex::Hex};
-    use tokio::{task, time::sleep};
+    use tokio::{sync::broadcast, task, time::sleep};
 
     use super::*;
     use crate::{
@@ -771,6 +898,7 @@ mod test {
             create_store_and_forward_mock,
             make_dht_header,
             make_dht_inbound_message,
+            make_encrypted_dht_envelope_for,
             make_keypair,
             make_node_identity,
             service_spy,
@@ -863,6 +991,7 @@ mod test {
             node_identity.clone(),
             message.clone(),
             saf_response_signal_sender.clone(),
+            broadcast::channel(1).0,
         );
 
         task::spawn(task.run());
@@ -920,6 +1049,7 @@ mod test {
             node_identity.clone(),
             message,
             saf_response_signal_sender,
+            broadcast::channel(1).0,
         );
 
         task::spawn(task.run());
@@ -1041,6 +1171,7 @@ mod test {
             node_identity,
             message,
             saf_response_signal_sender,
+            broadcast::channel(1).0,
         );
 
         task.run().await.unwrap();
@@ -1150,6 +1281,7 @@ mod test {
             node_identity,
             message,
             saf_response_signal_sender,
+            broadcast::channel(1).0,
         );
 
         let err = task.run().await.unwrap_err();
@@ -1219,6 +1351,7 @@ mod test {
             node_identity,
             message,
             saf_response_signal_sender,
+            broadcast::channel(1).0,
         );
 
         task.run().await.unwrap();
@@ -1292,6 +1425,7 @@ mod test {
             node_identity.clone(),
             message.clone(),
             saf_response_signal_sender.clone(),
+            broadcast::channel(1).0,
         );
 
         task.run().await.unwrap();
@@ -1312,6 +1446,7 @@ mod test {
             node_identity,
             message,
             saf_response_signal_sender,
+            broadcast::channel(1).0,
         );
 
         task.run().await.unwrap();
@@ -1320,4 +1455,220 @@ mod test {
         assert_eq!(spy.call_count(), 1);
         assert_eq!(requests.len(), 1);
     }
+
+    #[tokio::test]
+    async fn delivery_receipt_publishes_event() {
+        let spy = service_spy();
+        let (saf_requester, _) = create_store_and_forward_mock();
+        let (oms_tx, _) = mpsc::channel(1);
+        let (dht_requester, _) = create_dht_actor_mock(1);
+        let (saf_response_signal_sender, _) = mpsc::channel(1);
+        let (event_publisher, mut event_subscriber) = broadcast::channel(1);
+
+        let node_identity = make_node_identity();
+        let recipient = make_node_identity();
+        let message_tags = vec![MessageTag::new(), MessageTag::new()];
+
+        let mut message = DecryptedDhtMessage::succeeded(
+            wrap_in_envelope_body!(SafDeliveryReceipt::new(
+                message_tags.iter().map(|tag| tag.as_value()).collect(),
+                Utc::now()
+            )),
+            Some(recipient.public_key().clone()),
+            make_dht_inbound_message(
+                &recipient,
+                &b"Delivery receipt".to_vec(),
+                DhtMessageFlags::ENCRYPTED,
+                true,
+                false,
+            )
+            .unwrap(),
+        );
+        message.dht_header.message_type = DhtMessageType::SafDeliveryReceipt;
+
+        let task = MessageHandlerTask::new(
+            Default::default(),
+            spy.to_service::<PipelineError>(),
+            saf_requester,
+            dht_requester,
+            OutboundMessageRequester::new(oms_tx),
+            node_identity,
+            message,
+            saf_response_signal_sender,
+            event_publisher,
+        );
+
+        task.run().await.unwrap();
+        // Receipts are not passed on
+        assert!(!spy.is_called());
+
+        let event = event_subscriber.try_recv().unwrap();
+        unpack_enum!(DhtEvent::StoreAndForwardMessagesDelivered(receipt) = &*event);
+        assert_eq!(receipt.recipient, *recipient.public_key());
+        assert_eq!(receipt.message_tags, message_tags);
+        assert!(receipt.contains(message_tags[0]));
+    }
+
+    #[tokio::test]
+    async fn decrypted_stored_messages_are_acknowledged_to_their_origin() {
+        let spy = service_spy();
+        let (saf_requester, saf_mock_state) = create_store_and_forward_mock();
+        let (outbound_requester, outbound_mock) = create_outbound_service_mock(10);
+        let outbound_mock_state = outbound_mock.get_state();
+        task::spawn(outbound_mock.run());
+        let (dht_requester, mock) = create_dht_actor_mock(1);
+        task::spawn(mock.run());
+        let (saf_response_signal_sender, _) = mpsc::channel(1);
+
+        let node_identity = make_node_identity();
+        let origin = make_node_identity();
+        let other_node = make_node_identity();
+        let stored_at = Utc::now() - chrono::Duration::minutes(1);
+
+        // One message for this node and one that was stored for another node, which cannot be decrypted
+        let delivered_tag = MessageTag::new();
+        let delivered = make_encrypted_dht_envelope_for(
+            &origin,
+            &node_identity,
+            &wrap_in_envelope_body!(b"A".to_vec()),
+            delivered_tag,
+        );
+        let not_for_us = make_encrypted_dht_envelope_for(
+            &origin,
+            &other_node,
+            &wrap_in_envelope_body!(b"B".to_vec()),
+            MessageTag::new(),
+        );
+        let messages = [delivered, not_for_us]
+            .into_iter()
+            .map(|envelope| {
+                let header = envelope.header.unwrap().try_into().unwrap();
+                ProtoStoredMessage::new(0, header, envelope.body, stored_at)
+            })
+            .collect();
+
+        let mut message = DecryptedDhtMessage::succeeded(
+            wrap_in_envelope_body!(StoredMessagesResponse {
+                messages,
+                request_id: 123,
+                response_type: 0
+            }),
+            None,
+            make_dht_inbound_message(
+                &node_identity,
+                &b"Stored message".to_vec(),
+                DhtMessageFlags::ENCRYPTED,
+                true,
+                false,
+            )
+            .unwrap(),
+        );
+        message.dht_header.message_type = DhtMessageType::SafStoredMessages;
+        saf_mock_state.set_request_inflight(Some(Duration::from_secs(10))).await;
+
+        let task = MessageHandlerTask::new(
+            SafConfig {
+                send_delivery_receipts: true,
+                ..Default::default()
+            },
+            spy.to_service::<PipelineError>(),
+            saf_requester,
+            dht_requester,
+            outbound_requester,
+            node_identity,
+            message,
+            saf_response_signal_sender,
+            broadcast::channel(1).0,
+        );
+
+        task.run().await.unwrap();
+        assert_eq!(spy.call_count(), 1);
+
+        // A single receipt, for the decrypted message only, is sent to the origin
+        outbound_mock_state
+            .wait_call_count(1, Duration::from_secs(5))
+            .await
+            .unwrap();
+        sleep(Duration::from_millis(100)).await;
+        let mut calls = outbound_mock_state.take_calls().await;
+        assert_eq!(calls.len(), 1);
+        let (params, body) = calls.pop().unwrap();
+        assert_eq!(params.dht_message_type, DhtMessageType::SafDeliveryReceipt);
+        assert_eq!(params.destination, NodeDestination::from(origin.public_key().clone()));
+        unpack_enum!(OutboundEncryption::EncryptFor(recipient) = params.encryption);
+        assert_eq!(*recipient, *origin.public_key());
+        let receipt = EnvelopeBody::decode(body.as_ref())
+            .unwrap()
+            .decode_part::<SafDeliveryReceipt>(0)
+            .unwrap()
+            .unwrap();
+        assert_eq!(receipt.message_tags, vec![delivered_tag.as_value()]);
+    }
+
+    #[tokio::test]
+    async fn undecryptable_stored_messages_are_not_acknowledged() {
+        let spy = service_spy();
+        let (saf_requester, saf_mock_state) = create_store_and_forward_mock();
+        let (outbound_requester, outbound_mock) = create_outbound_service_mock(10);
+        let outbound_mock_state = outbound_mock.get_state();
+        task::spawn(outbound_mock.run());
+        let (dht_requester, mock) = create_dht_actor_mock(1);
+        task::spawn(mock.run());
+        let (saf_response_signal_sender, _) = mpsc::channel(1);
+
+        let node_identity = make_node_identity();
+        let origin = make_node_identity();
+        let other_node = make_node_identity();
+
+        let envelope = make_encrypted_dht_envelope_for(
+            &origin,
+            &other_node,
+            &wrap_in_envelope_body!(b"B".to_vec()),
+            MessageTag::new(),
+        );
+        let stored_message = ProtoStoredMessage::new(
+            0,
+            envelope.header.unwrap().try_into().unwrap(),
+            envelope.body,
+            Utc::now() - chrono::Duration::minutes(1),
+        );
+        let mut message = DecryptedDhtMessage::succeeded(
+            wrap_in_envelope_body!(StoredMessagesResponse {
+                messages: vec![stored_message],
+                request_id: 123,
+                response_type: 0
+            }),
+            None,
+            make_dht_inbound_message(
+                &node_identity,
+                &b"Stored message".to_vec(),
+                DhtMessageFlags::ENCRYPTED,
+                true,
+                false,
+            )
+            .unwrap(),
+        );
+        message.dht_header.message_type = DhtMessageType::SafStoredMessages;
+        saf_mock_state.set_request_inflight(Some(Duration::from_secs(10))).await;
+
+        let task = MessageHandlerTask::new(
+            SafConfig {
+                send_delivery_receipts: true,
+                ..Default::default()
+            },
+            spy.to_service::<PipelineError>(),
+            saf_requester,
+            dht_requester,
+            outbound_requester,
+            node_identity,
+            message,
+            saf_response_signal_sender,
+            broadcast::channel(1).0,
+        );
+
+        task.run().await.unwrap();
+        assert!(!spy.is_called());
+        sleep(Duration::from_millis(100)).await;
+        assert_eq!(outbound_mock_state.call_count().await, 0);
+    }
 }
@@ -39,7 +39,7 @@ use tokio::{
 };
 
 use super::{
-    database::{NewStoredMessage, StoreAndForwardDatabase, StoredMessage},
+    database::{NewStoredMessage, StoreAndForwardDatabase, StoredMessage, StoredMessagesSummary},
     message::StoredMessagePriority,
     SafResult,
     StoreAndForwardError,
@@ -116,6 +116,7 @@ pub enum StoreAndForwardRequest {
     SendStoreForwardRequestToPeer(NodeId),
     SendStoreForwardRequestNeighbours,
     MarkSafResponseReceived(NodeId, oneshot::Sender<Option<Duration>>),
+    GetStoredMessagesSummary(oneshot::Sender<SafResult<StoredMessagesSummary>>),
 }
 
 /// Store and forward actor handle.
@@ -185,6 +186,16 @@ impl StoreAndForwardRequester {
         Ok(())
     }
 
+    /// Returns a summary of the messages held in the local SAF store
+    pub async fn get_stored_messages_summary(&mut self) -> SafResult<StoredMessagesSummary> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.sender
+            .send(StoreAndForwardRequest::GetStoredMessagesSummary(reply_tx))
+            .await
+            .map_err(|_| StoreAndForwardError::RequesterChannelClosed)?;
+        reply_rx.await.map_err(|_| StoreAndForwardError::RequestCancelled)?
+    }
+
     /// Updates internal SAF state that a SAF response has been received, removing it from the pending list.
     pub(crate) async fn mark_saf_response_received(&mut self, peer: NodeId) -> SafResult<Option<Duration>> {
         let (reply_tx, reply_rx) = oneshot::channel();
@@ -374,6 +385,9 @@ impl StoreAndForwardService {
             MarkSafResponseReceived(peer, reply) => {
                 let _ = reply.send(self.local_state.mark_infight_response_received(peer));
             },
+            GetStoredMessagesSummary(reply_tx) => {
+                let _result = reply_tx.send(self.database.get_summary().map_err(Into::into));
+            },
         }
     }
 
@@ -230,6 +230,47 @@ pub fn make_dht_envelope<T: prost::Message>(
     Ok(DhtEnvelope::new(header, message.into()))
 }
 
+/// Makes an encrypted envelope with an undisclosed destination that is signed by `origin` and can only be decrypted by
+/// `recipient`, such as a message that is stored for an offline recipient
+pub fn make_encrypted_dht_envelope_for<T: prost::Message>(
+    origin: &NodeIdentity,
+    recipient: &NodeIdentity,
+    message: &T,
+    trace: MessageTag,
+) -> DhtEnvelope {
+    let (e_secret_key, e_public_key) = make_keypair();
+    let flags = DhtMessageFlags::ENCRYPTED;
+    let shared_secret = CommsDHKE::new(&e_secret_key, recipient.public_key());
+    let key_message = crypt::generate_key_message(&shared_secret);
+    let mask = crypt::generate_key_mask(&shared_secret).unwrap();
+    let masked_origin_public_key = mask.clone() * origin.public_key();
+    let mut body = prepare_message(true, message).unwrap();
+    crypt::encrypt_message(&key_message, &mut body, masked_origin_public_key.as_bytes()).unwrap();
+    let body = body.freeze();
+
+    let destination = NodeDestination::Unknown;
+    let binding_message_representation = crypt::create_message_domain_separated_hash_parts(
+        DhtProtocolVersion::latest(),
+        &destination,
+        DhtMessageType::None,
+        flags,
+        None,
+        Some(&e_public_key),
+        &body,
+    );
+    let header = DhtMessageHeader {
+        version: DhtProtocolVersion::latest(),
+        destination,
+        ephemeral_public_key: Some(e_public_key),
+        message_signature: make_valid_message_signature(&(mask * origin.secret_key()), &binding_message_representation),
+        message_type: DhtMessageType::None,
+        flags,
+        message_tag: trace,
+        expires: None,
+    };
+    DhtEnvelope::new(header.into(), body.into())
+}
+
 pub fn build_peer_manager() -> Arc<PeerManager> {
     let database_name = random::string(8);
     let path = create_temporary_data_path();
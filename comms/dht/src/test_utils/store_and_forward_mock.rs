@@ -36,7 +36,7 @@ use tokio::{
     sync::{mpsc, RwLock},
 };
 
-use crate::store_forward::{StoreAndForwardRequest, StoreAndForwardRequester, StoredMessage};
+use crate::store_forward::{StoreAndForwardRequest, StoreAndForwardRequester, StoredMessage, StoredMessagesSummary};
 
 const LOG_TARGET: &str = "comms::dht::discovery_mock";
 
@@ -171,6 +171,19 @@ impl StoreAndForwardMock {
             MarkSafResponseReceived(_, reply) => {
                 let _ = reply.send(*self.state.inflight_request.read().await);
             },
+            GetStoredMessagesSummary(reply_tx) => {
+                let mut summary = StoredMessagesSummary::default();
+                for msg in self.state.stored_messages.read().await.iter() {
+                    summary.add(
+                        msg.priority,
+                        msg.is_encrypted,
+                        msg.destination_pubkey.is_some(),
+                        msg.body.len(),
+                        msg.stored_at,
+                    );
+                }
+                let _result = reply_tx.send(Ok(summary));
+            },
         }
     }
 }
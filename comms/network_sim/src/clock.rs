@@ -0,0 +1,104 @@
+//  Copyright 2024, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::time::Duration;
+
+use tokio::time::{self, Instant};
+
+/// The simulation clock.
+///
+/// A paused clock is used on a runtime whose time is paused, so time only moves forward when the runtime has no other
+/// work to do (tokio's auto-advance). Timer-driven behaviour such as discovery timeouts, SAF requests and dial
+/// backoffs then runs without real waiting and in a repeatable order. A realtime clock simply measures wall time.
+#[derive(Debug, Clone, Copy)]
+pub struct SimClock {
+    started_at: Instant,
+    is_paused: bool,
+}
+
+impl SimClock {
+    /// Returns a clock that follows real time
+    pub fn realtime() -> Self {
+        Self {
+            started_at: Instant::now(),
+            is_paused: false,
+        }
+    }
+
+    /// Returns a clock for a runtime whose time is paused. Pausing time needs tokio's `test-util` feature, which this
+    /// crate does not enable, so the runtime must be started paused by the caller, e.g. with
+    /// `#[tokio::test(start_paused = true)]`.
+    pub fn paused() -> Self {
+        Self {
+            started_at: Instant::now(),
+            is_paused: true,
+        }
+    }
+
+    /// Returns true if this clock is used on a runtime whose time is paused
+    pub fn is_paused(&self) -> bool {
+        self.is_paused
+    }
+
+    /// Returns the current simulation instant
+    pub fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    /// Returns the amount of simulated time since the clock was started
+    pub fn elapsed(&self) -> Duration {
+        self.started_at.elapsed()
+    }
+
+    /// Moves the clock forward by `duration`. All timers that expire in this period are fired. When time is paused,
+    /// the runtime skips ahead to each timer as soon as it has no other work to do.
+    pub async fn advance(&self, duration: Duration) {
+        time::sleep(duration).await;
+    }
+
+    /// Moves the clock forward until `offset` has elapsed since the clock was started. Does nothing if `offset` has
+    /// already passed.
+    pub async fn advance_to(&self, offset: Duration) {
+        let elapsed = self.elapsed();
+        if offset > elapsed {
+            self.advance(offset - elapsed).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn it_advances_paused_time() {
+        let clock = SimClock::paused();
+        assert!(clock.is_paused());
+        let start = clock.now();
+        clock.advance(Duration::from_secs(60 * 60)).await;
+        assert_eq!(clock.now() - start, Duration::from_secs(60 * 60));
+        clock.advance_to(Duration::from_secs(30)).await;
+        assert_eq!(clock.elapsed(), Duration::from_secs(60 * 60));
+        clock.advance_to(Duration::from_secs(2 * 60 * 60)).await;
+        assert_eq!(clock.elapsed(), Duration::from_secs(2 * 60 * 60));
+    }
+}
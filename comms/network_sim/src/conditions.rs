@@ -0,0 +1,312 @@
+//  Copyright 2024, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, RwLock},
+    time::Duration,
+};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use tari_comms::{multiaddr::Multiaddr, peer_manager::NodeId};
+
+/// The conditions applied to connection attempts and messages between two nodes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LinkConditions {
+    /// The fixed delay applied to every connection attempt and inbound message
+    pub latency: Duration,
+    /// A random delay of up to this amount is added to the latency
+    pub jitter: Duration,
+    /// The probability (0.0 - 1.0) that an inbound message is dropped
+    pub packet_loss: f64,
+}
+
+impl LinkConditions {
+    /// No latency and no packet loss
+    pub const fn perfect() -> Self {
+        Self {
+            latency: Duration::from_secs(0),
+            jitter: Duration::from_secs(0),
+            packet_loss: 0.0,
+        }
+    }
+
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    pub fn with_jitter(mut self, jitter: Duration) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Sets the packet loss probability. The value is clamped to the range 0.0 - 1.0.
+    pub fn with_packet_loss(mut self, packet_loss: f64) -> Self {
+        self.packet_loss = packet_loss.clamp(0.0, 1.0);
+        self
+    }
+}
+
+impl Default for LinkConditions {
+    fn default() -> Self {
+        Self::perfect()
+    }
+}
+
+/// Counters for the decisions made by [NetworkConditions]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ConditionStats {
+    pub num_messages_delivered: u64,
+    pub num_messages_lost: u64,
+    pub num_messages_partitioned: u64,
+    pub num_dials_refused: u64,
+}
+
+/// The result of applying the network conditions to a message or connection attempt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LinkDecision {
+    Deliver(Duration),
+    Drop,
+}
+
+/// Shared, mutable network conditions for a simulated network.
+///
+/// All nodes in a [SimNetwork](crate::SimNetwork) share a handle to the same conditions, so changes (e.g. a partition)
+/// take effect immediately for every node. Random decisions (packet loss and jitter) are taken from an RNG seeded at
+/// construction.
+#[derive(Clone)]
+pub struct NetworkConditions {
+    state: Arc<RwLock<ConditionsState>>,
+    rng: Arc<Mutex<StdRng>>,
+}
+
+#[derive(Default)]
+struct ConditionsState {
+    default_link: LinkConditions,
+    links: HashMap<(NodeId, NodeId), LinkConditions>,
+    partitions: Vec<Vec<NodeId>>,
+    addresses: HashMap<Multiaddr, NodeId>,
+    stats: ConditionStats,
+}
+
+impl ConditionsState {
+    fn link(&self, from: &NodeId, to: &NodeId) -> LinkConditions {
+        self.links
+            .get(&(from.clone(), to.clone()))
+            .copied()
+            .unwrap_or(self.default_link)
+    }
+
+    /// Nodes that are not in any partition group belong to an implicit group of their own
+    fn partition_of(&self, node_id: &NodeId) -> Option<usize> {
+        self.partitions.iter().position(|group| group.contains(node_id))
+    }
+
+    fn can_reach(&self, from: &NodeId, to: &NodeId) -> bool {
+        self.partitions.is_empty() || self.partition_of(from) == self.partition_of(to)
+    }
+}
+
+impl NetworkConditions {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            state: Default::default(),
+            rng: Arc::new(Mutex::new(StdRng::seed_from_u64(seed))),
+        }
+    }
+
+    /// Returns the conditions used for links that have not been explicitly set
+    pub fn default_link(&self) -> LinkConditions {
+        self.state.read().unwrap().default_link
+    }
+
+    /// Sets the conditions used for links that have not been explicitly set
+    pub fn set_default_link(&self, conditions: LinkConditions) {
+        self.state.write().unwrap().default_link = conditions;
+    }
+
+    /// Returns the conditions for messages sent from `from` to `to`
+    pub fn link(&self, from: &NodeId, to: &NodeId) -> LinkConditions {
+        self.state.read().unwrap().link(from, to)
+    }
+
+    /// Sets the conditions for the link between `a` and `b` in both directions
+    pub fn set_link(&self, a: &NodeId, b: &NodeId, conditions: LinkConditions) {
+        let mut state = self.state.write().unwrap();
+        state.links.insert((a.clone(), b.clone()), conditions);
+        state.links.insert((b.clone(), a.clone()), conditions);
+    }
+
+    /// Removes the conditions for the link between `a` and `b` so that the default applies
+    pub fn clear_link(&self, a: &NodeId, b: &NodeId) {
+        let mut state = self.state.write().unwrap();
+        state.links.remove(&(a.clone(), b.clone()));
+        state.links.remove(&(b.clone(), a.clone()));
+    }
+
+    /// Splits the network into the given groups. Nodes can only communicate with nodes in the same group. Nodes that
+    /// are not in any group form one further group. Any previous partition is replaced.
+    pub fn partition<I: IntoIterator<Item = Vec<NodeId>>>(&self, groups: I) {
+        self.state.write().unwrap().partitions = groups.into_iter().filter(|g| !g.is_empty()).collect();
+    }
+
+    /// Removes all partitions
+    pub fn heal(&self) {
+        self.state.write().unwrap().partitions.clear();
+    }
+
+    /// Returns true if the network is currently partitioned
+    pub fn is_partitioned(&self) -> bool {
+        !self.state.read().unwrap().partitions.is_empty()
+    }
+
+    /// Returns true if `from` is able to communicate with `to`
+    pub fn can_reach(&self, from: &NodeId, to: &NodeId) -> bool {
+        self.state.read().unwrap().can_reach(from, to)
+    }
+
+    /// Returns a snapshot of the condition counters
+    pub fn stats(&self) -> ConditionStats {
+        self.state.read().unwrap().stats
+    }
+
+    pub fn reset_stats(&self) {
+        self.state.write().unwrap().stats = ConditionStats::default();
+    }
+
+    pub(crate) fn register_address(&self, address: Multiaddr, node_id: NodeId) {
+        self.state.write().unwrap().addresses.insert(address, node_id);
+    }
+
+    /// Decides if a connection attempt from `from` to `address` should succeed, and how long it should take
+    pub(crate) fn decide_dial(&self, from: &NodeId, address: &Multiaddr) -> LinkDecision {
+        let mut state = self.state.write().unwrap();
+        let to = match state.addresses.get(address) {
+            Some(node_id) => node_id.clone(),
+            // Not a simulated node, let the transport decide
+            None => return LinkDecision::Deliver(Duration::from_secs(0)),
+        };
+        if !state.can_reach(from, &to) {
+            state.stats.num_dials_refused += 1;
+            return LinkDecision::Drop;
+        }
+        let link = state.link(from, &to);
+        LinkDecision::Deliver(self.sample_delay(&link))
+    }
+
+    /// Decides if a message from `from` to `to` should be delivered, and how long it should take
+    pub(crate) fn decide_message(&self, from: &NodeId, to: &NodeId) -> LinkDecision {
+        let mut state = self.state.write().unwrap();
+        if !state.can_reach(from, to) {
+            state.stats.num_messages_partitioned += 1;
+            return LinkDecision::Drop;
+        }
+        let link = state.link(from, to);
+        if link.packet_loss > 0.0 && self.rng.lock().unwrap().gen_bool(link.packet_loss) {
+            state.stats.num_messages_lost += 1;
+            return LinkDecision::Drop;
+        }
+        state.stats.num_messages_delivered += 1;
+        LinkDecision::Deliver(self.sample_delay(&link))
+    }
+
+    fn sample_delay(&self, link: &LinkConditions) -> Duration {
+        if link.jitter.is_zero() {
+            return link.latency;
+        }
+        let factor = self.rng.lock().unwrap().gen::<f64>();
+        link.latency + link.jitter.mul_f64(factor)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::convert::TryFrom;
+
+    use super::*;
+
+    fn node_ids(n: u8) -> Vec<NodeId> {
+        (0..n).map(|i| NodeId::try_from(&[i; 13][..]).unwrap()).collect()
+    }
+
+    #[test]
+    fn it_partitions_the_network() {
+        let conditions = NetworkConditions::new(0);
+        let ids = node_ids(5);
+        assert!(conditions.can_reach(&ids[0], &ids[4]));
+
+        conditions.partition(vec![ids[0..2].to_vec(), ids[2..4].to_vec()]);
+        assert!(conditions.is_partitioned());
+        assert!(conditions.can_reach(&ids[0], &ids[1]));
+        assert!(conditions.can_reach(&ids[2], &ids[3]));
+        assert!(!conditions.can_reach(&ids[1], &ids[2]));
+        // ids[4] is in the implicit "rest" group
+        assert!(!conditions.can_reach(&ids[4], &ids[0]));
+        assert_eq!(conditions.decide_message(&ids[0], &ids[3]), LinkDecision::Drop);
+        assert_eq!(conditions.stats().num_messages_partitioned, 1);
+
+        conditions.heal();
+        assert!(conditions.can_reach(&ids[4], &ids[0]));
+    }
+
+    #[test]
+    fn it_makes_repeatable_loss_decisions() {
+        let ids = node_ids(2);
+        let link = LinkConditions::perfect()
+            .with_latency(Duration::from_millis(100))
+            .with_jitter(Duration::from_millis(50))
+            .with_packet_loss(0.5);
+
+        let run = |seed| {
+            let conditions = NetworkConditions::new(seed);
+            conditions.set_default_link(link);
+            (0..100)
+                .map(|_| conditions.decide_message(&ids[0], &ids[1]))
+                .collect::<Vec<_>>()
+        };
+        let decisions = run(123);
+        assert_eq!(decisions, run(123));
+        let num_lost = decisions.iter().filter(|d| **d == LinkDecision::Drop).count();
+        assert!(num_lost > 20 && num_lost < 80);
+        assert!(decisions.iter().all(|d| match d {
+            LinkDecision::Deliver(delay) => *delay >= link.latency && *delay <= link.latency + link.jitter,
+            LinkDecision::Drop => true,
+        }));
+    }
+
+    #[test]
+    fn it_overrides_individual_links() {
+        let ids = node_ids(3);
+        let conditions = NetworkConditions::new(0);
+        let lossy = LinkConditions::perfect().with_packet_loss(1.0);
+        conditions.set_link(&ids[0], &ids[1], lossy);
+        assert_eq!(conditions.link(&ids[1], &ids[0]), lossy);
+        assert_eq!(conditions.decide_message(&ids[1], &ids[0]), LinkDecision::Drop);
+        assert_eq!(
+            conditions.decide_message(&ids[0], &ids[2]),
+            LinkDecision::Deliver(Duration::from_secs(0))
+        );
+        conditions.clear_link(&ids[0], &ids[1]);
+        assert_eq!(conditions.link(&ids[0], &ids[1]), LinkConditions::perfect());
+    }
+}
@@ -0,0 +1,254 @@
+//  Copyright 2024, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use tari_comms::{peer_manager::NodeId, types::CommsPublicKey};
+
+/// A simulated message received by a node
+#[derive(Debug, Clone)]
+pub struct ReceivedMessage {
+    /// The simulation time at which the message was received
+    pub received_at: Duration,
+    /// The connected peer which forwarded the message to this node
+    pub source_peer: NodeId,
+    /// The authenticated origin of the message, if the message was signed
+    pub authenticated_origin: Option<CommsPublicKey>,
+    pub payload: String,
+    /// True if the message was received from store and forward
+    pub is_saf_message: bool,
+}
+
+#[derive(Debug, Clone)]
+struct SentMessage {
+    sender: NodeId,
+    sent_at: Duration,
+}
+
+#[derive(Default)]
+struct DeliveryLogState {
+    sent: HashMap<String, SentMessage>,
+    received: HashMap<NodeId, Vec<ReceivedMessage>>,
+}
+
+/// Records the simulated messages sent and received by every node in a [SimNetwork](crate::SimNetwork). Payloads are
+/// used as message identifiers, so each payload sent should be unique.
+#[derive(Clone, Default)]
+pub struct DeliveryLog {
+    state: Arc<Mutex<DeliveryLogState>>,
+}
+
+impl DeliveryLog {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub(crate) fn record_sent(&self, sender: NodeId, payload: String, sent_at: Duration) {
+        self.state
+            .lock()
+            .unwrap()
+            .sent
+            .insert(payload, SentMessage { sender, sent_at });
+    }
+
+    /// Records a received message. Returns true if this is the first time that the node has received the payload,
+    /// false if it is a duplicate or the node is the sender of the payload.
+    pub(crate) fn record_received(&self, node_id: &NodeId, message: ReceivedMessage) -> bool {
+        let mut state = self.state.lock().unwrap();
+        if state.sent.get(&message.payload).map(|s| &s.sender) == Some(node_id) {
+            return false;
+        }
+        let received = state.received.entry(node_id.clone()).or_default();
+        let is_first = received.iter().all(|m| m.payload != message.payload);
+        received.push(message);
+        is_first
+    }
+
+    /// Returns all messages, including duplicates, received by the given node
+    pub fn received_by(&self, node_id: &NodeId) -> Vec<ReceivedMessage> {
+        self.state
+            .lock()
+            .unwrap()
+            .received
+            .get(node_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Returns true if the given node has received the payload
+    pub fn has_received(&self, node_id: &NodeId, payload: &str) -> bool {
+        self.state
+            .lock()
+            .unwrap()
+            .received
+            .get(node_id)
+            .map(|msgs| msgs.iter().any(|m| m.payload == payload))
+            .unwrap_or(false)
+    }
+
+    /// Returns the total number of messages, including duplicates, received by all nodes
+    pub fn total_received(&self) -> usize {
+        self.state.lock().unwrap().received.values().map(Vec::len).sum()
+    }
+
+    /// Builds a delivery report for the payload. The sender of the payload is excluded from the expected recipients.
+    pub fn report<'a, I: IntoIterator<Item = &'a NodeId>>(
+        &self,
+        payload: &str,
+        expected_recipients: I,
+    ) -> DeliveryReport {
+        let state = self.state.lock().unwrap();
+        let sent = state.sent.get(payload);
+        let mut report = DeliveryReport {
+            payload: payload.to_string(),
+            num_expected: 0,
+            num_delivered: 0,
+            num_duplicates: 0,
+            undelivered: Vec::new(),
+            max_latency: None,
+        };
+
+        for node_id in expected_recipients {
+            if sent.map(|s| &s.sender) == Some(node_id) {
+                continue;
+            }
+            report.num_expected += 1;
+            let mut received = state
+                .received
+                .get(node_id)
+                .into_iter()
+                .flatten()
+                .filter(|m| m.payload == payload);
+
+            match received.next() {
+                Some(first) => {
+                    report.num_delivered += 1;
+                    report.num_duplicates += received.count();
+                    if let Some(sent) = sent {
+                        let latency = first.received_at.saturating_sub(sent.sent_at);
+                        report.max_latency = Some(report.max_latency.map_or(latency, |l| l.max(latency)));
+                    }
+                },
+                None => report.undelivered.push(node_id.clone()),
+            }
+        }
+
+        report
+    }
+}
+
+/// Delivery statistics for a single payload
+#[derive(Debug, Clone)]
+pub struct DeliveryReport {
+    pub payload: String,
+    /// The number of nodes expected to receive the payload
+    pub num_expected: usize,
+    /// The number of expected nodes that received the payload at least once
+    pub num_delivered: usize,
+    /// The number of additional copies received by the expected nodes
+    pub num_duplicates: usize,
+    /// The expected nodes that have not received the payload
+    pub undelivered: Vec<NodeId>,
+    /// The longest time taken for the payload to reach an expected node
+    pub max_latency: Option<Duration>,
+}
+
+impl DeliveryReport {
+    /// Returns the fraction (0.0 - 1.0) of expected nodes that received the payload
+    #[allow(clippy::cast_precision_loss)]
+    pub fn delivery_rate(&self) -> f64 {
+        if self.num_expected == 0 {
+            return 1.0;
+        }
+        self.num_delivered as f64 / self.num_expected as f64
+    }
+
+    /// Returns true if every expected node received the payload
+    pub fn is_complete(&self) -> bool {
+        self.num_delivered == self.num_expected
+    }
+}
+
+impl fmt::Display for DeliveryReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "'{}' delivered to {}/{} node(s) ({:.1}%), {} duplicate(s)",
+            self.payload,
+            self.num_delivered,
+            self.num_expected,
+            self.delivery_rate() * 100.0,
+            self.num_duplicates
+        )?;
+        if let Some(latency) = self.max_latency {
+            write!(f, ", max latency {:.2?}", latency)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::convert::TryFrom;
+
+    use super::*;
+
+    fn received(source: &NodeId, payload: &str, received_at: Duration) -> ReceivedMessage {
+        ReceivedMessage {
+            received_at,
+            source_peer: source.clone(),
+            authenticated_origin: None,
+            payload: payload.to_string(),
+            is_saf_message: false,
+        }
+    }
+
+    #[test]
+    fn it_builds_a_delivery_report() {
+        let ids = (0u8..4)
+            .map(|i| NodeId::try_from(&[i; 13][..]).unwrap())
+            .collect::<Vec<_>>();
+        let log = DeliveryLog::new();
+        log.record_sent(ids[0].clone(), "hello".to_string(), Duration::from_secs(1));
+
+        assert!(!log.record_received(&ids[0], received(&ids[1], "hello", Duration::from_secs(2))));
+        assert!(log.record_received(&ids[1], received(&ids[0], "hello", Duration::from_secs(2))));
+        assert!(!log.record_received(&ids[1], received(&ids[2], "hello", Duration::from_secs(3))));
+        assert!(log.record_received(&ids[2], received(&ids[1], "hello", Duration::from_secs(4))));
+        assert!(log.has_received(&ids[2], "hello"));
+        assert!(!log.has_received(&ids[3], "hello"));
+
+        let report = log.report("hello", &ids);
+        assert_eq!(report.num_expected, 3);
+        assert_eq!(report.num_delivered, 2);
+        assert_eq!(report.num_duplicates, 1);
+        assert_eq!(report.undelivered, vec![ids[3].clone()]);
+        assert_eq!(report.max_latency, Some(Duration::from_secs(3)));
+        assert!(!report.is_complete());
+        assert!((report.delivery_rate() - 2.0 / 3.0).abs() < f64::EPSILON);
+    }
+}
@@ -0,0 +1,112 @@
+//  Copyright 2024, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::task::Poll;
+
+use futures::{future::BoxFuture, task::Context};
+use log::*;
+use tari_comms::{message::InboundMessage, peer_manager::NodeId, pipeline::PipelineError};
+use tokio::time;
+use tower::{layer::Layer, Service, ServiceExt};
+
+use crate::conditions::{LinkDecision, NetworkConditions};
+
+const LOG_TARGET: &str = "comms::network_sim::middleware";
+
+/// Layer for [SimConditionsMiddleware]. This should be the first layer in a simulated node's inbound pipeline.
+pub struct SimConditionsLayer {
+    node_id: NodeId,
+    conditions: NetworkConditions,
+}
+
+impl SimConditionsLayer {
+    pub fn new(node_id: NodeId, conditions: NetworkConditions) -> Self {
+        Self { node_id, conditions }
+    }
+}
+
+impl<S> Layer<S> for SimConditionsLayer {
+    type Service = SimConditionsMiddleware<S>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        SimConditionsMiddleware::new(service, self.node_id.clone(), self.conditions.clone())
+    }
+}
+
+/// # Sim conditions middleware
+///
+/// Applies the [NetworkConditions] to inbound messages. Messages from partitioned peers or that are "lost" are
+/// dropped, and the remaining messages are delayed by the link latency before being passed on.
+#[derive(Clone)]
+pub struct SimConditionsMiddleware<S> {
+    next_service: S,
+    node_id: NodeId,
+    conditions: NetworkConditions,
+}
+
+impl<S> SimConditionsMiddleware<S> {
+    pub fn new(service: S, node_id: NodeId, conditions: NetworkConditions) -> Self {
+        Self {
+            next_service: service,
+            node_id,
+            conditions,
+        }
+    }
+}
+
+impl<S> Service<InboundMessage> for SimConditionsMiddleware<S>
+where
+    S: Service<InboundMessage, Response = (), Error = PipelineError> + Clone + Send + 'static,
+    S::Future: Send,
+{
+    type Error = PipelineError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+    type Response = ();
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, message: InboundMessage) -> Self::Future {
+        let next_service = self.next_service.clone();
+        let decision = self.conditions.decide_message(&message.source_peer, &self.node_id);
+        Box::pin(async move {
+            match decision {
+                LinkDecision::Deliver(delay) => {
+                    if !delay.is_zero() {
+                        time::sleep(delay).await;
+                    }
+                    next_service.oneshot(message).await
+                },
+                LinkDecision::Drop => {
+                    trace!(
+                        target: LOG_TARGET,
+                        "Dropping message {} from peer '{}'",
+                        message.tag,
+                        message.source_peer.short_str()
+                    );
+                    Ok(())
+                },
+            }
+        })
+    }
+}
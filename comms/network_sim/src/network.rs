@@ -0,0 +1,461 @@
+//  Copyright 2024, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{sync::Arc, time::Duration};
+
+use log::*;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use tari_comms::{
+    peer_manager::{NodeId, NodeIdentity, PeerFeatures},
+    transports::MemoryTransport,
+    Minimized,
+};
+use tari_comms_dht::{
+    domain_message::OutboundDomainMessage,
+    envelope::NodeDestination,
+    outbound::OutboundEncryption,
+    store_forward::SafConfig,
+    DhtConfig,
+};
+use tokio::time;
+
+use crate::{
+    node::{NodeContext, SIM_MESSAGE_TYPE},
+    ChurnEvent,
+    ChurnScript,
+    DeliveryLog,
+    DeliveryReport,
+    LinkConditions,
+    NetworkConditions,
+    SimClock,
+    SimError,
+    SimNode,
+};
+
+const LOG_TARGET: &str = "comms::network_sim";
+
+const DELIVERY_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Builder for a [SimNetwork]
+pub struct SimNetworkBuilder {
+    seed: u64,
+    num_base_nodes: usize,
+    num_clients: usize,
+    num_seed_peers: usize,
+    dht_config: DhtConfig,
+    link_conditions: LinkConditions,
+    pause_clock: bool,
+    auto_propagate: bool,
+}
+
+impl SimNetworkBuilder {
+    fn new() -> Self {
+        Self {
+            seed: 0,
+            num_base_nodes: 0,
+            num_clients: 0,
+            num_seed_peers: 1,
+            dht_config: DhtConfig {
+                saf: SafConfig {
+                    auto_request: true,
+                    ..Default::default()
+                },
+                ..DhtConfig::default_local_test()
+            },
+            link_conditions: LinkConditions::perfect(),
+            pause_clock: false,
+            auto_propagate: true,
+        }
+    }
+
+    /// The seed used for node identities, topology and link decisions
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn with_num_base_nodes(mut self, n: usize) -> Self {
+        self.num_base_nodes = n;
+        self
+    }
+
+    pub fn with_num_clients(mut self, n: usize) -> Self {
+        self.num_clients = n;
+        self
+    }
+
+    /// The number of previously created base nodes that each node knows about when it starts (default: 1)
+    pub fn with_num_seed_peers(mut self, n: usize) -> Self {
+        self.num_seed_peers = n;
+        self
+    }
+
+    /// The DHT config used by every node. The database URL is ignored, each node has its own in-memory database.
+    pub fn with_dht_config(mut self, dht_config: DhtConfig) -> Self {
+        self.dht_config = dht_config;
+        self
+    }
+
+    /// The initial default link conditions
+    pub fn with_link_conditions(mut self, link_conditions: LinkConditions) -> Self {
+        self.link_conditions = link_conditions;
+        self
+    }
+
+    /// Use a [SimClock] for paused tokio time. The runtime must be started paused, see [SimClock::paused].
+    pub fn with_paused_clock(mut self) -> Self {
+        self.pause_clock = true;
+        self
+    }
+
+    /// If true (the default), nodes propagate simulated flood messages that they receive for the first time, as an
+    /// application would.
+    pub fn with_auto_propagation(mut self, auto_propagate: bool) -> Self {
+        self.auto_propagate = auto_propagate;
+        self
+    }
+
+    /// Creates and starts all nodes
+    pub async fn build(self) -> Result<SimNetwork, SimError> {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let conditions = NetworkConditions::new(rng.gen());
+        conditions.set_default_link(self.link_conditions);
+        let clock = if self.pause_clock {
+            SimClock::paused()
+        } else {
+            SimClock::realtime()
+        };
+
+        let num_nodes = self.num_base_nodes + self.num_clients;
+        let mut nodes = Vec::<SimNode>::with_capacity(num_nodes);
+        for index in 0..num_nodes {
+            let (name, features) = if index < self.num_base_nodes {
+                (format!("BaseNode{}", index), PeerFeatures::COMMUNICATION_NODE)
+            } else {
+                (
+                    format!("Client{}", index - self.num_base_nodes),
+                    PeerFeatures::COMMUNICATION_CLIENT,
+                )
+            };
+            let node_identity = make_node_identity(&mut rng, features);
+            conditions.register_address(
+                node_identity
+                    .first_public_address()
+                    .expect("simulated node identities always have an address"),
+                node_identity.node_id().clone(),
+            );
+
+            let num_prev_base_nodes = index.min(self.num_base_nodes);
+            let seed_peers = nodes[..num_prev_base_nodes]
+                .choose_multiple(&mut rng, self.num_seed_peers)
+                .map(|n| n.node_identity().to_peer())
+                .collect();
+            nodes.push(SimNode::new(index, name, node_identity, seed_peers));
+        }
+
+        let context = NodeContext {
+            conditions,
+            delivery_log: DeliveryLog::new(),
+            clock,
+            dht_config: self.dht_config,
+            auto_propagate: self.auto_propagate,
+        };
+        for node in &mut nodes {
+            node.start(&context).await?;
+        }
+        debug!(target: LOG_TARGET, "Started simulated network with {} node(s)", nodes.len());
+
+        Ok(SimNetwork { nodes, context, rng })
+    }
+}
+
+fn make_node_identity(rng: &mut StdRng, features: PeerFeatures) -> Arc<NodeIdentity> {
+    let port = MemoryTransport::acquire_next_memsocket_port();
+    Arc::new(NodeIdentity::random(
+        rng,
+        format!("/memory/{}", port)
+            .parse()
+            .expect("memory address is always a valid multiaddr"),
+        features,
+    ))
+}
+
+/// An in-memory network of base nodes and clients. Base nodes have indexes `0..num_base_nodes` and clients follow.
+pub struct SimNetwork {
+    nodes: Vec<SimNode>,
+    context: NodeContext,
+    rng: StdRng,
+}
+
+impl SimNetwork {
+    pub fn builder() -> SimNetworkBuilder {
+        SimNetworkBuilder::new()
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn nodes(&self) -> &[SimNode] {
+        &self.nodes
+    }
+
+    pub fn online_nodes(&self) -> impl Iterator<Item = &SimNode> {
+        self.nodes.iter().filter(|n| n.is_online())
+    }
+
+    pub fn node(&self, index: usize) -> Result<&SimNode, SimError> {
+        self.nodes.get(index).ok_or(SimError::NodeNotFound(index))
+    }
+
+    /// Returns the index of the node with the given node id
+    pub fn index_of(&self, node_id: &NodeId) -> Option<usize> {
+        self.nodes.iter().position(|n| n.node_id() == node_id)
+    }
+
+    pub fn conditions(&self) -> &NetworkConditions {
+        &self.context.conditions
+    }
+
+    pub fn clock(&self) -> &SimClock {
+        &self.context.clock
+    }
+
+    pub fn delivery_log(&self) -> &DeliveryLog {
+        &self.context.delivery_log
+    }
+
+    /// The seeded RNG of this network, for use in scenarios that need further random choices
+    pub fn rng_mut(&mut self) -> &mut StdRng {
+        &mut self.rng
+    }
+
+    /// Waits until every online node has at least one connection
+    pub async fn wait_for_connectivity(&self, timeout: Duration) -> Result<(), SimError> {
+        for node in self.online_nodes() {
+            node.comms()?.connectivity().wait_for_connectivity(timeout).await?;
+        }
+        Ok(())
+    }
+
+    /// Returns the total number of active connections of all online nodes. Each connection is counted by both peers.
+    pub async fn num_active_connections(&self) -> Result<usize, SimError> {
+        let mut total = 0;
+        for node in self.online_nodes() {
+            total += node.comms()?.connectivity().get_active_connections().await?.len();
+        }
+        Ok(total)
+    }
+
+    /// Shuts the node down. The node keeps its identity and known peers.
+    pub async fn take_offline(&mut self, index: usize) -> Result<(), SimError> {
+        debug!(target: LOG_TARGET, "Taking node {} offline", index);
+        self.nodes
+            .get_mut(index)
+            .ok_or(SimError::NodeNotFound(index))?
+            .stop()
+            .await
+    }
+
+    /// Restarts a node that was taken offline
+    pub async fn bring_online(&mut self, index: usize) -> Result<(), SimError> {
+        debug!(target: LOG_TARGET, "Bringing node {} online", index);
+        let context = self.context.clone();
+        self.nodes
+            .get_mut(index)
+            .ok_or(SimError::NodeNotFound(index))?
+            .start(&context)
+            .await
+    }
+
+    /// Partitions the network into groups of node indexes and disconnects all connections that cross a partition. See
+    /// [NetworkConditions::partition].
+    pub async fn partition(&self, groups: &[Vec<usize>]) -> Result<(), SimError> {
+        let groups = groups
+            .iter()
+            .map(|group| {
+                group
+                    .iter()
+                    .map(|i| self.node(*i).map(|n| n.node_id().clone()))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        debug!(target: LOG_TARGET, "Partitioning network into {} group(s)", groups.len());
+        self.context.conditions.partition(groups);
+
+        for node in self.online_nodes() {
+            let conns = node.comms()?.connectivity().get_active_connections().await?;
+            for mut conn in conns {
+                if self.context.conditions.can_reach(node.node_id(), conn.peer_node_id()) {
+                    continue;
+                }
+                if let Err(err) = conn.disconnect(Minimized::No).await {
+                    debug!(target: LOG_TARGET, "Failed to disconnect partitioned peer: {}", err);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes all partitions. Nodes reconnect as their connectivity managers see fit.
+    pub fn heal(&self) {
+        debug!(target: LOG_TARGET, "Healing network partitions");
+        self.context.conditions.heal();
+    }
+
+    /// Floods a simulated message from the node at `from` to the network. Returns the number of peers that the message
+    /// was initially sent to. Payloads are used to identify messages in the [DeliveryLog] and should be unique.
+    pub async fn broadcast(&self, from: usize, payload: &str) -> Result<usize, SimError> {
+        let node = self.node(from)?;
+        self.context.delivery_log.record_sent(
+            node.node_id().clone(),
+            payload.to_string(),
+            self.context.clock.elapsed(),
+        );
+        let send_states = node
+            .dht()?
+            .outbound_requester()
+            .broadcast(
+                NodeDestination::Unknown,
+                OutboundEncryption::ClearText,
+                vec![],
+                OutboundDomainMessage::new(&SIM_MESSAGE_TYPE, payload.to_string()),
+                "network sim broadcast".to_string(),
+            )
+            .await?;
+        Ok(send_states.len())
+    }
+
+    /// Sends an encrypted simulated message from the node at `from` to the node at `to` using a closest broadcast. If
+    /// `to` is offline, the message can only be delivered using store and forward.
+    pub async fn send_encrypted(&self, from: usize, to: usize, payload: &str) -> Result<usize, SimError> {
+        let node = self.node(from)?;
+        let dest_public_key = self.node(to)?.node_identity().public_key().clone();
+        self.context.delivery_log.record_sent(
+            node.node_id().clone(),
+            payload.to_string(),
+            self.context.clock.elapsed(),
+        );
+        let send_states = node
+            .dht()?
+            .outbound_requester()
+            .closest_broadcast(
+                dest_public_key.clone(),
+                OutboundEncryption::encrypt_for(dest_public_key),
+                vec![],
+                OutboundDomainMessage::new(&SIM_MESSAGE_TYPE, payload.to_string()),
+            )
+            .await?;
+        Ok(send_states.len())
+    }
+
+    /// Returns the delivery report for the payload with every other node as an expected recipient
+    pub fn delivery_report(&self, payload: &str) -> DeliveryReport {
+        self.context
+            .delivery_log
+            .report(payload, self.nodes.iter().map(|n| n.node_id()))
+    }
+
+    /// Returns the delivery report for the payload with the given nodes as the expected recipients
+    pub fn delivery_report_for(&self, payload: &str, indexes: &[usize]) -> Result<DeliveryReport, SimError> {
+        let node_ids = indexes
+            .iter()
+            .map(|i| self.node(*i).map(|n| n.node_id()))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(self.context.delivery_log.report(payload, node_ids))
+    }
+
+    /// Waits until every other node has received the payload or until the timeout expires, and returns the delivery
+    /// report
+    pub async fn wait_for_delivery(&self, payload: &str, timeout: Duration) -> DeliveryReport {
+        let indexes = (0..self.nodes.len()).collect::<Vec<_>>();
+        self.wait_for_delivery_to(payload, &indexes, timeout)
+            .await
+            .expect("all indexes are valid")
+    }
+
+    /// Waits until the given nodes have received the payload or until the timeout expires, and returns the delivery
+    /// report
+    pub async fn wait_for_delivery_to(
+        &self,
+        payload: &str,
+        indexes: &[usize],
+        timeout: Duration,
+    ) -> Result<DeliveryReport, SimError> {
+        let deadline = self.context.clock.now() + timeout;
+        loop {
+            let report = self.delivery_report_for(payload, indexes)?;
+            if report.is_complete() || self.context.clock.now() >= deadline {
+                return Ok(report);
+            }
+            time::sleep(DELIVERY_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Returns a random churn script over the given candidate nodes, using the network's seeded RNG. See
+    /// [ChurnScript::random].
+    pub fn random_churn(&mut self, candidates: &[usize], num_events: usize, duration: Duration) -> ChurnScript {
+        ChurnScript::random(&mut self.rng, candidates, num_events, duration)
+    }
+
+    /// Applies the events of the script at their offsets. Offsets are relative to the time this function is called.
+    pub async fn run_script(&mut self, script: &ChurnScript) -> Result<(), SimError> {
+        let start = self.context.clock.elapsed();
+        for (offset, event) in script.events() {
+            self.context.clock.advance_to(start + offset).await;
+            debug!(target: LOG_TARGET, "[{:.2?}] Applying churn event {:?}", offset, event);
+            self.apply_event(event).await?;
+        }
+        Ok(())
+    }
+
+    pub async fn apply_event(&mut self, event: ChurnEvent) -> Result<(), SimError> {
+        match event {
+            ChurnEvent::Offline(index) => self.take_offline(index).await,
+            ChurnEvent::Online(index) => self.bring_online(index).await,
+            ChurnEvent::Partition(groups) => self.partition(&groups).await,
+            ChurnEvent::Heal => {
+                self.heal();
+                Ok(())
+            },
+            ChurnEvent::SetLinkConditions(conditions) => {
+                self.context.conditions.set_default_link(conditions);
+                Ok(())
+            },
+        }
+    }
+
+    /// Shuts down all online nodes
+    pub async fn shutdown(mut self) {
+        for node in &mut self.nodes {
+            if node.is_online() {
+                if let Err(err) = node.stop().await {
+                    warn!(target: LOG_TARGET, "Failed to shut down node {}: {}", node, err);
+                }
+            }
+        }
+    }
+}
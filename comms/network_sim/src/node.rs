@@ -0,0 +1,314 @@
+//  Copyright 2024, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use log::*;
+use tari_comms::{
+    backoff::ConstantBackoff,
+    peer_manager::{NodeId, NodeIdentity, Peer, PeerStorage},
+    pipeline,
+    pipeline::SinkService,
+    protocol::{messaging::MessagingProtocolExtension, rpc::RpcServer, ProtocolId},
+    CommsBuilder,
+    CommsNode,
+};
+use tari_comms_dht::{
+    domain_message::OutboundDomainMessage,
+    envelope::NodeDestination,
+    inbound::DecryptedDhtMessage,
+    outbound::{OutboundEncryption, OutboundMessageRequester},
+    DbConnectionUrl,
+    Dht,
+    DhtConfig,
+};
+use tari_shutdown::Shutdown;
+use tari_storage::HashmapDatabase;
+use tokio::{
+    sync::{broadcast, mpsc},
+    task,
+};
+use tower::ServiceBuilder;
+
+use crate::{
+    clock::SimClock,
+    conditions::NetworkConditions,
+    delivery::{DeliveryLog, ReceivedMessage},
+    middleware::SimConditionsLayer,
+    transport::SimTransport,
+    SimError,
+};
+
+const LOG_TARGET: &str = "comms::network_sim::node";
+
+static SIM_MSG_PROTOCOL_ID: ProtocolId = ProtocolId::from_static(b"t/msg/1.0");
+/// The domain message type used for all simulated messages
+pub(crate) const SIM_MESSAGE_TYPE: i32 = 0;
+
+const OUTBOUND_CHANNEL_SIZE: usize = 100;
+const INBOUND_CHANNEL_SIZE: usize = 100;
+const MESSAGING_EVENTS_CHANNEL_SIZE: usize = 100;
+const MAX_CONCURRENT_INBOUND_TASKS: usize = 10;
+const DIAL_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Used to give each node instance its own in-memory DHT database
+static DB_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// State shared by all nodes in a simulated network
+#[derive(Clone)]
+pub(crate) struct NodeContext {
+    pub conditions: NetworkConditions,
+    pub delivery_log: DeliveryLog,
+    pub clock: SimClock,
+    pub dht_config: DhtConfig,
+    pub auto_propagate: bool,
+}
+
+struct RunningNode {
+    comms: CommsNode,
+    dht: Dht,
+    shutdown: Shutdown,
+}
+
+/// A node in a simulated network. The node identity and the peers that the node knows about are kept while the node is
+/// offline so that the node can be restarted to simulate churn.
+pub struct SimNode {
+    index: usize,
+    name: String,
+    node_identity: Arc<NodeIdentity>,
+    known_peers: Vec<Peer>,
+    running: Option<RunningNode>,
+}
+
+impl SimNode {
+    pub(crate) fn new(index: usize, name: String, node_identity: Arc<NodeIdentity>, seed_peers: Vec<Peer>) -> Self {
+        Self {
+            index,
+            name,
+            node_identity,
+            known_peers: seed_peers,
+            running: None,
+        }
+    }
+
+    /// The index of this node in the [SimNetwork](crate::SimNetwork)
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn node_identity(&self) -> Arc<NodeIdentity> {
+        self.node_identity.clone()
+    }
+
+    pub fn node_id(&self) -> &NodeId {
+        self.node_identity.node_id()
+    }
+
+    pub fn is_online(&self) -> bool {
+        self.running.is_some()
+    }
+
+    /// The peers that are added to the peer manager when the node is started. While the node is online, this is the
+    /// list of peers that the node was started with.
+    pub fn known_peers(&self) -> &[Peer] {
+        &self.known_peers
+    }
+
+    /// Returns the comms node, or an error if the node is offline
+    pub fn comms(&self) -> Result<&CommsNode, SimError> {
+        self.running
+            .as_ref()
+            .map(|r| &r.comms)
+            .ok_or(SimError::NodeOffline(self.index))
+    }
+
+    /// Returns the DHT, or an error if the node is offline
+    pub fn dht(&self) -> Result<&Dht, SimError> {
+        self.running
+            .as_ref()
+            .map(|r| &r.dht)
+            .ok_or(SimError::NodeOffline(self.index))
+    }
+
+    pub(crate) async fn start(&mut self, context: &NodeContext) -> Result<(), SimError> {
+        if self.is_online() {
+            return Err(SimError::NodeAlreadyOnline(self.index));
+        }
+
+        let node_id = self.node_id().clone();
+        let shutdown = Shutdown::new();
+        let (outbound_tx, outbound_rx) = mpsc::channel(OUTBOUND_CHANNEL_SIZE);
+        let (inbound_tx, inbound_rx) = mpsc::channel(INBOUND_CHANNEL_SIZE);
+
+        let comms = CommsBuilder::new()
+            .allow_test_addresses()
+            // The listener address and the public address are the same (/memory/...)
+            .with_listener_address(
+                self.node_identity
+                    .first_public_address()
+                    .expect("simulated node identities always have an address"),
+            )
+            .with_shutdown_signal(shutdown.to_signal())
+            .with_node_identity(self.node_identity.clone())
+            .with_peer_database(Box::new(PeerStorage::new_indexed(HashmapDatabase::new())?), None)
+            .with_min_connectivity(1)
+            .with_dial_backoff(ConstantBackoff::new(DIAL_BACKOFF))
+            .build()?;
+
+        for peer in self.known_peers.iter().cloned() {
+            comms.peer_manager().add_peer(peer).await?;
+        }
+
+        let db_name = format!("network_sim_{}", DB_COUNTER.fetch_add(1, Ordering::Relaxed));
+        let dht = Dht::builder()
+            .with_config(context.dht_config.clone())
+            .with_database_url(DbConnectionUrl::MemoryShared(db_name))
+            .with_outbound_sender(outbound_tx)
+            .build(
+                comms.node_identity(),
+                comms.peer_manager(),
+                comms.connectivity(),
+                comms.shutdown_signal(),
+            )
+            .await?;
+
+        let dht_outbound_layer = dht.outbound_middleware_layer();
+        let pipeline = pipeline::Builder::new()
+            .with_outbound_pipeline(outbound_rx, |sink| {
+                ServiceBuilder::new().layer(dht_outbound_layer).service(sink)
+            })
+            .max_concurrent_inbound_tasks(MAX_CONCURRENT_INBOUND_TASKS)
+            .with_inbound_pipeline(
+                ServiceBuilder::new()
+                    .layer(SimConditionsLayer::new(node_id.clone(), context.conditions.clone()))
+                    .layer(dht.inbound_middleware_layer())
+                    .service(SinkService::new(inbound_tx)),
+            )
+            .build();
+
+        let (messaging_events_tx, _) = broadcast::channel(MESSAGING_EVENTS_CHANNEL_SIZE);
+        let comms = comms
+            .add_rpc_server(RpcServer::new().add_service(dht.rpc_service()))
+            .add_protocol_extension(MessagingProtocolExtension::new(
+                SIM_MSG_PROTOCOL_ID.clone(),
+                messaging_events_tx,
+                pipeline,
+            ))
+            .spawn_with_transport(SimTransport::new(node_id.clone(), context.conditions.clone()))
+            .await?;
+
+        task::spawn(handle_inbound_messages(
+            node_id,
+            inbound_rx,
+            dht.outbound_requester(),
+            context.clone(),
+        ));
+
+        self.running = Some(RunningNode { comms, dht, shutdown });
+        Ok(())
+    }
+
+    pub(crate) async fn stop(&mut self) -> Result<(), SimError> {
+        let running = self.running.take().ok_or(SimError::NodeOffline(self.index))?;
+        // Remember the peers this node knows about so that it is able to reconnect when it comes back online
+        let peers = running.comms.peer_manager().all().await;
+        let RunningNode {
+            comms, mut shutdown, ..
+        } = running;
+        shutdown.trigger();
+        comms.wait_until_shutdown().await;
+        self.known_peers = peers?;
+        Ok(())
+    }
+}
+
+impl fmt::Display for SimNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.name, self.node_id().short_str())
+    }
+}
+
+/// Records simulated messages received by the node and, if auto-propagation is enabled, propagates flood messages on
+/// to the node's peers.
+async fn handle_inbound_messages(
+    node_id: NodeId,
+    mut inbound_rx: mpsc::Receiver<DecryptedDhtMessage>,
+    mut outbound: OutboundMessageRequester,
+    context: NodeContext,
+) {
+    while let Some(msg) = inbound_rx.recv().await {
+        let payload = match msg.decryption_result.as_ref().map(|body| body.decode_part::<String>(1)) {
+            Ok(Ok(Some(payload))) => payload,
+            _ => {
+                debug!(
+                    target: LOG_TARGET,
+                    "'{}' received a message ({}) that is not a simulated message",
+                    node_id.short_str(),
+                    msg.tag
+                );
+                continue;
+            },
+        };
+
+        let is_first = context.delivery_log.record_received(&node_id, ReceivedMessage {
+            received_at: context.clock.elapsed(),
+            source_peer: msg.source_peer.node_id.clone(),
+            authenticated_origin: msg.authenticated_origin.clone(),
+            payload: payload.clone(),
+            is_saf_message: msg.is_saf_message,
+        });
+
+        let should_propagate = context.auto_propagate &&
+            is_first &&
+            msg.dht_header.destination.is_unknown() &&
+            !msg.dht_header.flags.is_encrypted();
+        if should_propagate {
+            let result = outbound
+                .propagate(
+                    NodeDestination::Unknown,
+                    OutboundEncryption::ClearText,
+                    vec![msg.source_peer.node_id.clone()],
+                    OutboundDomainMessage::new(&SIM_MESSAGE_TYPE, payload),
+                )
+                .await;
+            if let Err(err) = result {
+                warn!(
+                    target: LOG_TARGET,
+                    "'{}' failed to propagate message: {}",
+                    node_id.short_str(),
+                    err
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,151 @@
+//  Copyright 2024, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::{collections::HashSet, time::Duration};
+
+use rand::{seq::SliceRandom, Rng};
+
+use crate::LinkConditions;
+
+/// An event applied to a [SimNetwork](crate::SimNetwork) by a [ChurnScript]
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChurnEvent {
+    /// Take the node at this index offline
+    Offline(usize),
+    /// Bring the node at this index back online
+    Online(usize),
+    /// Partition the network into groups of node indexes
+    Partition(Vec<Vec<usize>>),
+    /// Remove all partitions
+    Heal,
+    /// Change the default link conditions
+    SetLinkConditions(LinkConditions),
+}
+
+/// A timeline of [ChurnEvent]s. Each event has an offset from the start of the script.
+#[derive(Debug, Clone, Default)]
+pub struct ChurnScript {
+    events: Vec<(Duration, ChurnEvent)>,
+}
+
+impl ChurnScript {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Adds an event at the given offset
+    pub fn at(mut self, offset: Duration, event: ChurnEvent) -> Self {
+        self.events.push((offset, event));
+        self
+    }
+
+    /// Generates a script in which `num_events` randomly chosen nodes from `candidates` go offline or come back online
+    /// at random times within `duration`. Nodes that are offline at the end of the script are brought back online at
+    /// `duration`.
+    pub fn random<R: Rng>(rng: &mut R, candidates: &[usize], num_events: usize, duration: Duration) -> Self {
+        let mut offsets = (0..num_events)
+            .map(|_| duration.mul_f64(rng.gen::<f64>()))
+            .collect::<Vec<_>>();
+        offsets.sort();
+
+        let mut offline = HashSet::new();
+        let mut script = Self::new();
+        for offset in offsets {
+            let index = match candidates.choose(rng) {
+                Some(index) => *index,
+                None => break,
+            };
+            let event = if offline.remove(&index) {
+                ChurnEvent::Online(index)
+            } else {
+                offline.insert(index);
+                ChurnEvent::Offline(index)
+            };
+            script = script.at(offset, event);
+        }
+
+        let mut remaining = offline.into_iter().collect::<Vec<_>>();
+        remaining.sort_unstable();
+        for index in remaining {
+            script = script.at(duration, ChurnEvent::Online(index));
+        }
+        script
+    }
+
+    /// Returns the events ordered by offset. Events with the same offset keep the order in which they were added.
+    pub fn events(&self) -> Vec<(Duration, ChurnEvent)> {
+        let mut events = self.events.clone();
+        events.sort_by_key(|(offset, _)| *offset);
+        events
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn it_orders_events() {
+        let script = ChurnScript::new()
+            .at(Duration::from_secs(10), ChurnEvent::Online(1))
+            .at(Duration::from_secs(5), ChurnEvent::Offline(1))
+            .at(Duration::from_secs(10), ChurnEvent::Heal);
+        let events = script.events();
+        assert_eq!(events[0], (Duration::from_secs(5), ChurnEvent::Offline(1)));
+        assert_eq!(events[1], (Duration::from_secs(10), ChurnEvent::Online(1)));
+        assert_eq!(events[2], (Duration::from_secs(10), ChurnEvent::Heal));
+    }
+
+    #[test]
+    fn it_generates_repeatable_random_churn() {
+        let duration = Duration::from_secs(60);
+        let candidates = [2, 3, 4, 5];
+        let script = ChurnScript::random(&mut StdRng::seed_from_u64(1), &candidates, 20, duration);
+        let events = script.events();
+        assert_eq!(
+            events,
+            ChurnScript::random(&mut StdRng::seed_from_u64(1), &candidates, 20, duration).events()
+        );
+
+        // Every node that goes offline comes back online by the end of the script
+        let mut offline = HashSet::new();
+        for (offset, event) in events {
+            assert!(offset <= duration);
+            match event {
+                ChurnEvent::Offline(i) => assert!(candidates.contains(&i) && offline.insert(i)),
+                ChurnEvent::Online(i) => assert!(offline.remove(&i)),
+                _ => panic!("unexpected event"),
+            }
+        }
+        assert!(offline.is_empty());
+    }
+}
@@ -0,0 +1,79 @@
+//  Copyright 2024, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::io;
+
+use async_trait::async_trait;
+use tari_comms::{
+    memsocket::MemorySocket,
+    multiaddr::Multiaddr,
+    peer_manager::NodeId,
+    transports::{MemoryTransport, Transport},
+};
+use tokio::time;
+
+use crate::conditions::{LinkDecision, NetworkConditions};
+
+/// Transport which applies the [NetworkConditions] to outbound connection attempts before delegating to
+/// [MemoryTransport]. Dials across a partition are refused and other dials are delayed by the link latency.
+#[derive(Clone)]
+pub struct SimTransport {
+    node_id: NodeId,
+    conditions: NetworkConditions,
+    inner: MemoryTransport,
+}
+
+impl SimTransport {
+    pub fn new(node_id: NodeId, conditions: NetworkConditions) -> Self {
+        Self {
+            node_id,
+            conditions,
+            inner: MemoryTransport,
+        }
+    }
+}
+
+#[async_trait]
+impl Transport for SimTransport {
+    type Error = io::Error;
+    type Listener = <MemoryTransport as Transport>::Listener;
+    type Output = MemorySocket;
+
+    async fn listen(&self, addr: &Multiaddr) -> Result<(Self::Listener, Multiaddr), Self::Error> {
+        self.inner.listen(addr).await
+    }
+
+    async fn dial(&self, addr: &Multiaddr) -> Result<Self::Output, Self::Error> {
+        match self.conditions.decide_dial(&self.node_id, addr) {
+            LinkDecision::Deliver(delay) => {
+                if !delay.is_zero() {
+                    time::sleep(delay).await;
+                }
+                self.inner.dial(addr).await
+            },
+            LinkDecision::Drop => Err(io::Error::new(
+                io::ErrorKind::ConnectionRefused,
+                format!("'{}' is partitioned from '{}'", self.node_id.short_str(), addr),
+            )),
+        }
+    }
+}
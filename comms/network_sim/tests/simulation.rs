@@ -0,0 +1,145 @@
+//  Copyright 2024, The Tari Project
+//
+//  Redistribution and use in source and binary forms, with or without modification, are permitted provided that the
+//  following conditions are met:
+//
+//  1. Redistributions of source code must retain the above copyright notice, this list of conditions and the following
+//  disclaimer.
+//
+//  2. Redistributions in binary form must reproduce the above copyright notice, this list of conditions and the
+//  following disclaimer in the documentation and/or other materials provided with the distribution.
+//
+//  3. Neither the name of the copyright holder nor the names of its contributors may be used to endorse or promote
+//  products derived from this software without specific prior written permission.
+//
+//  THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS IS" AND ANY EXPRESS OR IMPLIED WARRANTIES,
+//  INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+//  DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL,
+//  SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+//  SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
+//  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+//  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use std::time::Duration;
+
+use tari_comms_network_sim::{ChurnEvent, ChurnScript, LinkConditions, SimNetwork};
+
+#[tokio::test(start_paused = true)]
+async fn it_floods_a_message_to_all_nodes() {
+    let network = SimNetwork::builder()
+        .with_seed(1)
+        .with_paused_clock()
+        .with_num_base_nodes(8)
+        .with_num_seed_peers(2)
+        .with_link_conditions(LinkConditions::perfect().with_latency(Duration::from_millis(10)))
+        .build()
+        .await
+        .unwrap();
+    network.wait_for_connectivity(Duration::from_secs(10)).await.unwrap();
+
+    network.broadcast(0, "hello world").await.unwrap();
+    let report = network.wait_for_delivery("hello world", Duration::from_secs(10)).await;
+    assert!(report.is_complete(), "{}", report);
+    assert!(report.max_latency.unwrap() >= Duration::from_millis(10));
+
+    network.shutdown().await;
+}
+
+#[tokio::test(start_paused = true)]
+async fn it_does_not_deliver_across_a_partition() {
+    let network = SimNetwork::builder()
+        .with_seed(2)
+        .with_paused_clock()
+        .with_num_base_nodes(6)
+        .build()
+        .await
+        .unwrap();
+    network.wait_for_connectivity(Duration::from_secs(10)).await.unwrap();
+
+    network.partition(&[vec![0, 1, 2], vec![3, 4, 5]]).await.unwrap();
+    network.broadcast(0, "only for group one").await.unwrap();
+    let report = network
+        .wait_for_delivery("only for group one", Duration::from_secs(3))
+        .await;
+    assert_eq!(report.num_delivered, 2, "{}", report);
+
+    let report = network.delivery_report_for("only for group one", &[3, 4, 5]).unwrap();
+    assert_eq!(report.num_delivered, 0);
+
+    network.shutdown().await;
+}
+
+#[tokio::test(start_paused = true)]
+async fn it_delivers_to_an_offline_node_with_store_and_forward() {
+    let mut network = SimNetwork::builder()
+        .with_seed(3)
+        .with_paused_clock()
+        .with_num_base_nodes(4)
+        .with_num_clients(1)
+        .with_num_seed_peers(2)
+        .build()
+        .await
+        .unwrap();
+    network.wait_for_connectivity(Duration::from_secs(10)).await.unwrap();
+
+    let client = 4;
+    let script = ChurnScript::new().at(Duration::from_secs(0), ChurnEvent::Offline(client));
+    network.run_script(&script).await.unwrap();
+
+    network.send_encrypted(0, client, "are you there?").await.unwrap();
+    // Give the base nodes time to store the message
+    network.clock().advance(Duration::from_secs(2)).await;
+
+    network.bring_online(client).await.unwrap();
+    network.wait_for_connectivity(Duration::from_secs(10)).await.unwrap();
+    let mut saf_requester = network
+        .node(client)
+        .unwrap()
+        .dht()
+        .unwrap()
+        .store_and_forward_requester();
+    for node in &network.nodes()[..4] {
+        saf_requester
+            .request_saf_messages_from_peer(node.node_id().clone())
+            .await
+            .unwrap();
+    }
+
+    let report = network
+        .wait_for_delivery_to("are you there?", &[client], Duration::from_secs(10))
+        .await
+        .unwrap();
+    assert!(report.is_complete(), "{}", report);
+    let received = network
+        .delivery_log()
+        .received_by(network.node(client).unwrap().node_id());
+    assert!(received
+        .iter()
+        .any(|m| m.payload == "are you there?" && m.is_saf_message));
+
+    network.shutdown().await;
+}
+
+#[tokio::test(start_paused = true)]
+async fn it_reports_lower_delivery_rates_with_packet_loss() {
+    let network = SimNetwork::builder()
+        .with_seed(4)
+        .with_paused_clock()
+        .with_num_base_nodes(6)
+        .with_num_seed_peers(2)
+        .with_auto_propagation(false)
+        .build()
+        .await
+        .unwrap();
+    network.wait_for_connectivity(Duration::from_secs(10)).await.unwrap();
+
+    network
+        .conditions()
+        .set_default_link(LinkConditions::perfect().with_packet_loss(1.0));
+    network.broadcast(0, "lost").await.unwrap();
+    let report = network.wait_for_delivery("lost", Duration::from_secs(2)).await;
+    assert_eq!(report.num_delivered, 0);
+    assert!(network.conditions().stats().num_messages_lost > 0);
+
+    network.shutdown().await;
+}
@@ -222,6 +222,7 @@ pub async fn spawn_base_node_with_config(
             GrpcMethod::GetNetworkStatus,
             GrpcMethod::ListConnectedPeers,
             GrpcMethod::GetMempoolStats,
+            GrpcMethod::GetDhtDiagnostics,
             GrpcMethod::GetActiveValidatorNodes,
             GrpcMethod::GetShardKey,
             GrpcMethod::GetTemplateRegistrations,
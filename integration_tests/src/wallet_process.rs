@@ -221,6 +221,7 @@ pub fn get_default_cli() -> Cli {
         recovery: false,
         seed_words: None,
         seed_words_file_name: None,
+        restore_backup: None,
         non_interactive_mode: true,
         input_file: None,
         command: None,